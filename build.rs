@@ -9,13 +9,11 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use clap::{Command, CommandFactory};
+use clap::Command;
 use clap_complete::{generate_to, shells::Shell, Generator};
 use clap_complete_fig::Fig;
 use clap_complete_nushell::Nushell;
 
-use crate::args::BottomArgs;
-
 fn create_dir(dir: &Path) -> io::Result<()> {
     fs::create_dir_all(dir).inspect_err(|err| {
         eprintln!(
@@ -49,7 +47,7 @@ fn btm_generate() -> io::Result<()> {
             create_dir(&manpage_out_dir)?;
 
             // Generate completions
-            let mut app = BottomArgs::command();
+            let mut app = args::build_cmd();
             generate_completions(Shell::Bash, &mut app, &completion_out_dir)?;
             generate_completions(Shell::Zsh, &mut app, &completion_out_dir)?;
             generate_completions(Shell::Fish, &mut app, &completion_out_dir)?;
@@ -1,33 +1,57 @@
+pub mod ancestry;
 pub mod data_farmer;
+pub mod draw_timings;
 pub mod filter;
 pub mod frozen_state;
+pub mod jump;
+pub mod kill_tracker;
 pub mod layout_manager;
-mod process_killer;
+pub mod list_selection;
+pub mod process_killer;
+pub mod saved_state;
+pub mod settings_overlay;
 pub mod states;
+pub mod time_markers;
+pub mod watchdog;
 
 use std::{
-    cmp::{max, min},
-    time::Instant,
+    net::SocketAddr,
+    time::{Duration, Instant, SystemTime},
 };
 
+use ancestry::AncestryOverlay;
 use anyhow::bail;
 use concat_string::concat_string;
 use data_farmer::*;
+use draw_timings::DrawTimings;
 use filter::*;
 use frozen_state::FrozenState;
 use hashbrown::HashMap;
+use jump::{JumpEntry, JumpPalette, JumpTarget, JUMP_HIGHLIGHT_DURATION};
+use kill_tracker::KillTracker;
 use layout_manager::*;
+use settings_overlay::{LiveSettings, SettingsOverlay};
 pub use states::*;
+use time_markers::{MarkerOverlay, MarkerOverlayMode, TimeMarker};
 use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
+use watchdog::CollectionWatchdog;
 
 use crate::{
-    canvas::components::time_chart::LegendPosition,
-    constants, convert_mem_data_points, convert_swap_data_points,
-    data_collection::{processes::Pid, temperature},
+    canvas::components::{data_table::ColumnSeparator, time_chart::LegendPosition},
+    constants, convert_gaps, convert_mem_data_points, convert_network_interface_points,
+    convert_swap_data_points, convert_time_markers,
+    data_collection::{
+        cpu::CpuDataType,
+        processes::{Pid, HAS_LAST_CPU},
+        temperature,
+    },
     data_conversion::ConvertedData,
     get_network_points,
-    utils::data_units::DataUnit,
-    widgets::{ProcWidgetColumn, ProcWidgetMode},
+    utils::{data_units::DataUnit, lock_file::InstanceConflict},
+    widgets::{
+        CpuWidgetTableData, DiskColumn, ProcWidgetColumn, ProcWidgetMode, TempWidgetColumn,
+        TreeStyle,
+    },
 };
 
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
@@ -39,15 +63,28 @@ pub enum AxisScaling {
 
 /// AppConfigFields is meant to cover basic fields that would normally be set
 /// by config files or launch options.
-#[derive(Debug, Default, Eq, PartialEq)]
+#[derive(Debug, Default, PartialEq)]
 pub struct AppConfigFields {
     pub update_rate: u64,
+    pub temperature_update_rate: u64,
     pub temperature_type: temperature::TemperatureType,
     pub use_dot: bool,
+    pub use_thick_lines: bool,
+    pub newest_on_left: bool,
     pub cpu_left_legend: bool,
     pub show_average_cpu: bool, // TODO: Unify this in CPU options
     pub use_current_cpu_total: bool,
     pub unnormalized_cpu: bool,
+    /// Whether to colour the CPU legend's usages by how high they are.
+    pub cpu_legend_heatmap: bool,
+    /// Whether to show the load averages in the CPU widget's title.
+    pub cpu_show_load_avg: bool,
+    /// Whether to show one entry per socket instead of per core, on machines
+    /// with more than one socket.
+    pub cpu_per_socket: bool,
+    /// Whether the CPU widget starts by showing how CPU time is split between
+    /// user, system, iowait, and steal time, rather than per-core usage.
+    pub cpu_breakdown: bool,
     pub use_basic_mode: bool,
     pub default_time_value: u64,
     pub time_interval: u64,
@@ -60,21 +97,68 @@ pub struct AppConfigFields {
     pub enable_cache_memory: bool,
     pub show_table_scroll_position: bool,
     pub is_advanced_kill: bool,
+    pub default_kill_signal: usize,
+    /// The signals to list in the advanced kill dialog.
+    pub kill_signals: Vec<usize>,
     pub memory_legend_position: Option<LegendPosition>,
+    pub gpu_legend_position: Option<LegendPosition>,
     // TODO: Remove these, move network details state-side.
     pub network_unit_type: DataUnit,
     pub network_legend_position: Option<LegendPosition>,
     pub network_scale_type: AxisScaling,
     pub network_use_binary_prefix: bool,
+    pub network_show_interfaces: bool,
+    /// Whether the network graph's y-axis never shrinks within a session.
+    pub network_lock_to_session_max: bool,
+    /// Whether to draw the highest rates seen this session on the network graph.
+    pub network_show_session_max: bool,
+    /// Whether to show the peak rates and totals this session in the network legend.
+    pub network_show_session_totals: bool,
+    /// The network graph's y-axis max in bits per second, if it is pinned.
+    pub network_y_max: Option<u64>,
+    /// Whether to show uids instead of usernames for processes, or [`None`] to
+    /// decide based on whether our user database is the system's.
+    pub uid_numeric: Option<bool>,
+    /// What to draw between the process table's columns.
+    pub process_column_separator: ColumnSeparator,
+    /// How the process tree is drawn.
+    pub process_tree_style: TreeStyle,
+    /// Whether to show how many processes are in each group next to its name.
+    pub process_show_group_count: bool,
+    /// Only group processes if there are more than this many with the same name.
+    pub process_group_threshold: usize,
+    /// The CPU usage a process must go over to count as a spike.
+    pub process_spike_threshold: f32,
+    /// Whether the process table's text columns are sorted by case.
+    pub process_case_sensitive_sort: bool,
+    /// Whether the disk table's text columns are sorted by case.
+    pub disk_case_sensitive_sort: bool,
+    /// What to show instead of certain mount points and disk names.
+    pub disk_aliases: HashMap<String, String>,
+    /// Whether the temperature table's sensor column is sorted by case.
+    pub temp_case_sensitive_sort: bool,
     pub retention_ms: u64,
     pub dedicated_average_row: bool,
+    pub compact_bytes: bool,
+    pub process_command_tooltip: bool,
+    pub save_state: bool,
+    /// What to do if another instance is already writing the state file.
+    pub instance_conflict: InstanceConflict,
+    /// Where to serve metrics, if at all.
+    pub metrics_listen: Option<SocketAddr>,
+    /// How close consecutive points must be to be merged, as a percentage, if
+    /// at all.
+    pub merge_tolerance: Option<f64>,
+    /// Whether to show the slowest widget's draw time over it.
+    pub debug_ui: bool,
 }
 
 /// For filtering out information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct DataFilters {
     pub disk_filter: Option<Filter>,
     pub mount_filter: Option<Filter>,
+    pub fs_filter: Option<Filter>,
     pub temp_filter: Option<Filter>,
     pub net_filter: Option<Filter>,
 }
@@ -105,12 +189,29 @@ pub struct App {
     second_char: Option<char>,
     pub dd_err: Option<String>, // FIXME: The way we do deletes is really gross.
     to_delete_process_list: Option<(String, Vec<Pid>)>,
+    pub kill_tracker: KillTracker,
+    pub collection_watchdog: CollectionWatchdog,
+    pub mouse_position: Option<(u16, u16)>,
+    /// The table column border being dragged, if any.
+    pub column_drag: Option<ColumnDrag>,
     pub frozen_state: FrozenState,
     last_key_press: Instant,
     pub converted_data: ConvertedData,
     pub data_collection: DataCollection,
     pub delete_dialog_state: AppDeleteDialogState,
     pub help_dialog_state: AppHelpDialogState,
+    pub jump_palette: JumpPalette,
+    pub ancestry_overlay: AncestryOverlay,
+    pub settings_overlay: SettingsOverlay,
+    pub marker_overlay: MarkerOverlay,
+    pub draw_timings: DrawTimings,
+    /// Whether the UI state won't be saved, as another instance holds the lock on it.
+    pub is_state_unsaved: bool,
+    /// Whether to save a snapshot of the selected graph once the current event
+    /// has been handled.
+    pub is_graph_snapshot_requested: bool,
+    /// A message shown at the bottom of the screen until the next key press.
+    pub status_message: Option<String>,
     pub is_expanded: bool,
     pub is_force_redraw: bool,
     pub is_determining_widget_boundary: bool,
@@ -119,7 +220,18 @@ pub struct App {
     pub app_config_fields: AppConfigFields,
     pub widget_map: HashMap<u64, BottomWidget>,
     pub current_widget: BottomWidget,
+    /// The layouts to switch between as the terminal is resized, if any.
+    pub layout_profiles: Option<LayoutProfiles>,
+    /// The layouts of whichever of basic mode and the normal layout isn't in
+    /// use, to switch to while running.
+    pub alternate_layout_profiles: Option<LayoutProfiles>,
+    /// A layout switched to outside of drawing, which still needs to be drawn.
+    switched_layout: Option<BottomLayout>,
     pub used_widgets: UsedWidgets,
+    /// The data needed by the layouts in
+    /// [`alternate_layout_profiles`](Self::alternate_layout_profiles), which
+    /// is only collected once switched to.
+    pub alternate_used_widgets: UsedWidgets,
     pub filters: DataFilters,
 }
 
@@ -135,12 +247,38 @@ impl App {
             second_char: None,
             dd_err: None,
             to_delete_process_list: None,
+            kill_tracker: KillTracker::default(),
+            collection_watchdog: CollectionWatchdog::new(
+                app_config_fields.update_rate,
+                Instant::now(),
+            ),
+            mouse_position: None,
+            column_drag: None,
             frozen_state: FrozenState::default(),
             last_key_press: Instant::now(),
             converted_data: ConvertedData::default(),
-            data_collection: DataCollection::default(),
+            data_collection: DataCollection {
+                process_data: ProcessData {
+                    unnormalized_cpu: app_config_fields.unnormalized_cpu,
+                    spike_threshold: app_config_fields.process_spike_threshold,
+                    ..Default::default()
+                },
+                merge_tolerance: app_config_fields.merge_tolerance,
+                gap_threshold: Some(Duration::from_millis(
+                    app_config_fields.update_rate * GAP_RATE_MULTIPLIER,
+                )),
+                ..Default::default()
+            },
             delete_dialog_state: AppDeleteDialogState::default(),
             help_dialog_state: AppHelpDialogState::default(),
+            jump_palette: JumpPalette::default(),
+            ancestry_overlay: AncestryOverlay::default(),
+            settings_overlay: SettingsOverlay::default(),
+            marker_overlay: MarkerOverlay::default(),
+            draw_timings: DrawTimings::new(app_config_fields.debug_ui),
+            is_state_unsaved: false,
+            is_graph_snapshot_requested: false,
+            status_message: None,
             is_expanded,
             is_force_redraw: false,
             is_determining_widget_boundary: false,
@@ -149,21 +287,83 @@ impl App {
             app_config_fields,
             widget_map,
             current_widget,
+            layout_profiles: None,
+            alternate_layout_profiles: None,
+            switched_layout: None,
             used_widgets,
+            alternate_used_widgets: UsedWidgets::default(),
             filters,
         }
     }
 
+    /// Filters the process widgets to the core selected in the currently
+    /// focused CPU legend. Selecting "All" or the average clears the filter.
+    ///
+    /// This does nothing on platforms that don't report the core a process
+    /// last ran on.
+    fn update_process_core_filter(&mut self) {
+        if !HAS_LAST_CPU || self.current_widget.widget_type != BottomWidgetType::CpuLegend {
+            return;
+        }
+
+        let Some(cpu_widget_state) = self
+            .states
+            .cpu_state
+            .widget_states
+            .get(&(self.current_widget.widget_id - 1))
+        else {
+            return;
+        };
+
+        let core = match cpu_widget_state.table.current_item() {
+            Some(CpuWidgetTableData::Entry {
+                data_type: CpuDataType::Cpu(core),
+                ..
+            }) => Some(*core),
+            _ => None,
+        };
+
+        for proc in self.states.proc_state.widget_states.values_mut() {
+            proc.set_core_filter(core);
+        }
+    }
+
+    /// Whether any network widget needs per-interface data, either to list
+    /// every interface or to graph a single one.
+    pub fn uses_network_interfaces(&self) -> bool {
+        self.app_config_fields.network_show_interfaces
+            || self
+                .states
+                .net_state
+                .widget_states
+                .values()
+                .any(|state| state.interface.is_some())
+    }
+
+    /// Whether to collect per-interface network data, either for the network
+    /// widgets or to serve as metrics.
+    pub fn collects_network_interfaces(&self) -> bool {
+        self.uses_network_interfaces() || self.app_config_fields.metrics_listen.is_some()
+    }
+
     /// Update the data in the [`App`].
     pub fn update_data(&mut self) {
-        let data_source = match &self.frozen_state {
-            FrozenState::NotFrozen => &self.data_collection,
-            FrozenState::Frozen(data) => data,
-        };
+        self.update_process_core_filter();
+
+        let data_source = self.frozen_state.data_source(&self.data_collection);
+        let diff_baseline = self.frozen_state.diff_baseline();
+
+        self.converted_data.gaps = convert_gaps(data_source);
+        // Markers always come from the live data, so ones dropped while frozen
+        // are drawn too.
+        self.converted_data.time_markers = convert_time_markers(
+            &self.data_collection.time_markers,
+            data_source.current_instant,
+        );
 
         for proc in self.states.proc_state.widget_states.values_mut() {
             if proc.force_update_data {
-                proc.set_table_data(data_source);
+                proc.set_table_data(data_source, diff_baseline);
                 proc.force_update_data = false;
             }
         }
@@ -222,6 +422,22 @@ impl App {
             self.states.mem_state.force_update = None;
         }
 
+        if self.states.battery_state.force_update.is_some() {
+            #[cfg(feature = "battery")]
+            {
+                self.converted_data.battery_data = crate::convert_battery_data_points(data_source);
+            }
+            self.states.battery_state.force_update = None;
+        }
+
+        if self.states.gpu_state.force_update.is_some() {
+            #[cfg(feature = "gpu")]
+            {
+                self.converted_data.gpu_util_data = crate::convert_gpu_util_data(data_source);
+            }
+            self.states.gpu_state.force_update = None;
+        }
+
         if self.states.net_state.force_update.is_some() {
             let (rx, tx) = get_network_points(
                 data_source,
@@ -231,6 +447,16 @@ impl App {
             );
             self.converted_data.network_data_rx = rx;
             self.converted_data.network_data_tx = tx;
+
+            if self.uses_network_interfaces() {
+                self.converted_data.network_interface_data = convert_network_interface_points(
+                    data_source,
+                    &self.app_config_fields.network_scale_type,
+                    &self.app_config_fields.network_unit_type,
+                    self.app_config_fields.network_use_binary_prefix,
+                );
+            }
+
             self.states.net_state.force_update = None;
         }
     }
@@ -242,6 +468,10 @@ impl App {
         // Reset dialog state
         self.help_dialog_state.is_showing_help = false;
         self.delete_dialog_state.is_showing_dd = false;
+        self.jump_palette.close();
+        self.ancestry_overlay.close();
+        self.settings_overlay.close();
+        self.marker_overlay = MarkerOverlay::default();
 
         // Close all searches and reset it
         self.states
@@ -268,18 +498,149 @@ impl App {
         self.data_collection.reset();
     }
 
+    /// Switches to the best layout for a terminal of the given size, and
+    /// returns the new layout if it changed. Widget states are kept, as is the
+    /// selected widget if the new layout has it.
+    pub fn fit_layout(&mut self, width: u16, height: u16) -> Option<BottomLayout> {
+        let Some(profile) = self
+            .layout_profiles
+            .as_mut()
+            .and_then(|profiles| profiles.fit(width, height))
+            .cloned()
+        else {
+            return self.switched_layout.take();
+        };
+
+        self.switched_layout = None;
+        self.use_layout_profile(&profile, None);
+
+        Some(profile.layout)
+    }
+
+    /// Switches the widgets to those of a layout. The selected widget becomes
+    /// `selected` if given, or stays the same if the layout has it, and is
+    /// otherwise the layout's default widget.
+    fn use_layout_profile(&mut self, profile: &LayoutProfile, selected: Option<u64>) {
+        self.widget_map = profile
+            .layout
+            .widgets()
+            .map(|widget| (widget.widget_id, widget.clone()))
+            .collect();
+
+        if let Some(widget) = selected
+            .and_then(|id| self.widget_map.get(&id))
+            .or_else(|| {
+                self.widget_map
+                    .get(&self.current_widget.widget_id)
+                    .filter(|widget| widget.widget_type == self.current_widget.widget_type)
+            })
+            .or_else(|| self.widget_map.get(&profile.default_widget_id))
+        {
+            self.current_widget = widget.clone();
+        }
+        self.draw_timings.clear();
+        self.is_force_redraw = true;
+    }
+
+    /// Switches between basic mode and the normal layout. The collected data is
+    /// kept, as are the states of widgets that both have (e.g. the sort and
+    /// search of a process widget) and the selected widget where possible.
+    pub fn toggle_basic_mode(&mut self) {
+        let (Some(old_profiles), Some(_)) =
+            (&self.layout_profiles, &self.alternate_layout_profiles)
+        else {
+            return;
+        };
+        let old_layout = old_profiles.current().layout.clone();
+
+        std::mem::swap(
+            &mut self.layout_profiles,
+            &mut self.alternate_layout_profiles,
+        );
+        std::mem::swap(&mut self.used_widgets, &mut self.alternate_used_widgets);
+        let Some(profile) = self
+            .layout_profiles
+            .as_ref()
+            .map(|profiles| profiles.current().clone())
+        else {
+            return;
+        };
+
+        let pairs = equivalent_widgets(&old_layout, &profile.layout);
+        self.states.swap_widget_states(&pairs);
+
+        let is_basic = !self.app_config_fields.use_basic_mode;
+        self.app_config_fields.use_basic_mode = is_basic;
+        for proc in self.states.proc_state.widget_states.values_mut() {
+            proc.table.props.is_basic = is_basic;
+            proc.force_data_update();
+        }
+        for disk in self.states.disk_state.widget_states.values_mut() {
+            disk.table.props.is_basic = is_basic;
+            disk.force_data_update();
+        }
+        for temp in self.states.temp_state.widget_states.values_mut() {
+            temp.table.props.is_basic = is_basic;
+            temp.force_data_update();
+        }
+
+        let selected = pairs
+            .iter()
+            .find(|(from, _)| *from == self.current_widget.widget_id)
+            .map(|(_, to)| *to);
+        self.use_layout_profile(&profile, selected);
+
+        // Basic mode has no expanded widgets, and shows one table at a time.
+        self.states.basic_table_widget_state = is_basic.then(|| {
+            let (widget_type, widget_id) = match self.current_widget.widget_type {
+                BottomWidgetType::Proc | BottomWidgetType::Disk | BottomWidgetType::Temp => (
+                    self.current_widget.widget_type.clone(),
+                    self.current_widget.widget_id,
+                ),
+                _ => (BottomWidgetType::Proc, constants::DEFAULT_WIDGET_ID),
+            };
+
+            BasicTableWidgetState {
+                currently_displayed_widget_type: widget_type,
+                currently_displayed_widget_id: widget_id,
+                left_tlc: None,
+                left_brc: None,
+                right_tlc: None,
+                right_brc: None,
+            }
+        });
+        if is_basic {
+            self.is_expanded = false;
+        }
+
+        self.switched_layout = Some(profile.layout);
+    }
+
     pub fn should_get_widget_bounds(&self) -> bool {
         self.is_force_redraw || self.is_determining_widget_boundary
     }
 
     fn close_dd(&mut self) {
         self.delete_dialog_state.is_showing_dd = false;
-        self.delete_dialog_state.selected_signal = KillSignal::default();
+        self.delete_dialog_state.selected_signal = self.default_kill_signal();
         self.delete_dialog_state.scroll_pos = 0;
         self.to_delete_process_list = None;
         self.dd_err = None;
     }
 
+    /// The signal selected by default when opening the kill dialog.
+    fn default_kill_signal(&self) -> KillSignal {
+        KillSignal::Kill(self.app_config_fields.default_kill_signal)
+    }
+
+    /// Moves the selection in the advanced kill dialog by an offset.
+    fn change_dd_signal_position(&mut self, change: i64) {
+        let signals = &self.app_config_fields.kill_signals;
+        let position = self.delete_dialog_state.selected_position(signals) as i64;
+        self.delete_dialog_state
+            .select_position(signals, (position + change).max(0) as usize);
+    }
+
     pub fn on_esc(&mut self) {
         self.reset_multi_tap_keys();
         if self.is_in_dialog() {
@@ -358,7 +719,12 @@ impl App {
     }
 
     fn is_in_dialog(&self) -> bool {
-        self.help_dialog_state.is_showing_help || self.delete_dialog_state.is_showing_dd
+        self.help_dialog_state.is_showing_help
+            || self.delete_dialog_state.is_showing_dd
+            || self.jump_palette.is_open()
+            || self.ancestry_overlay.is_open()
+            || self.settings_overlay.is_open()
+            || self.marker_overlay.is_open()
     }
 
     fn ignore_normal_keybinds(&self) -> bool {
@@ -378,6 +744,372 @@ impl App {
                     proc_widget_state.toggle_tab();
                 }
             }
+        } else if self.help_dialog_state.is_showing_help {
+            self.help_dialog_state.toggle_expanded();
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Opens the jump palette, indexing what the table widgets currently show.
+    pub fn open_jump_palette(&mut self) {
+        if !self.ignore_normal_keybinds() {
+            let entries = self.jump_entries();
+            self.jump_palette.open(entries);
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Closes the jump palette without changing the selected widget.
+    pub fn close_jump_palette(&mut self) {
+        self.jump_palette.close();
+        self.is_force_redraw = true;
+    }
+
+    /// Returns the ID of the widget of a type that the jump palette should
+    /// search in, preferring the current widget if it is of that type.
+    fn jump_widget_id(&self, widget_type: &BottomWidgetType) -> Option<u64> {
+        let current_id = match (&self.current_widget.widget_type, widget_type) {
+            (BottomWidgetType::ProcSearch, BottomWidgetType::Proc) => {
+                Some(self.current_widget.widget_id - 1)
+            }
+            (BottomWidgetType::ProcSort, BottomWidgetType::Proc) => {
+                Some(self.current_widget.widget_id - 2)
+            }
+            (current, widget_type) if current == widget_type => Some(self.current_widget.widget_id),
+            _ => None,
+        };
+
+        current_id.or_else(|| {
+            self.widget_map
+                .values()
+                .filter(|widget| widget.widget_type == *widget_type)
+                .map(|widget| widget.widget_id)
+                .min()
+        })
+    }
+
+    /// Builds the entries for the jump palette, grouped by widget.
+    fn jump_entries(&self) -> Vec<JumpEntry> {
+        let mut entries = vec![];
+
+        if let Some(widget_id) = self.jump_widget_id(&BottomWidgetType::Proc) {
+            if let Some(proc_widget_state) = self.states.proc_state.widget_states.get(&widget_id) {
+                entries.extend(
+                    proc_widget_state
+                        .table
+                        .data()
+                        .iter()
+                        .map(|process| JumpEntry {
+                            widget_id,
+                            widget_type: BottomWidgetType::Proc,
+                            label: format!("{} ({})", process.id.as_str(), process.pid),
+                            target: JumpTarget::Process(process.pid),
+                        }),
+                );
+            }
+        }
+
+        if let Some(widget_id) = self.jump_widget_id(&BottomWidgetType::Disk) {
+            if let Some(disk_widget_state) = self.states.disk_state.widget_states.get(&widget_id) {
+                entries.extend(disk_widget_state.table.data().iter().map(|disk| JumpEntry {
+                    widget_id,
+                    widget_type: BottomWidgetType::Disk,
+                    label: format!("{} ({})", disk.display_name(), disk.display_mount()),
+                    target: JumpTarget::Disk(disk.name.to_string(), disk.mount_point.to_string()),
+                }));
+            }
+        }
+
+        if let Some(widget_id) = self.jump_widget_id(&BottomWidgetType::Temp) {
+            if let Some(temp_widget_state) = self.states.temp_state.widget_states.get(&widget_id) {
+                entries.extend(
+                    temp_widget_state
+                        .table
+                        .data()
+                        .iter()
+                        .map(|sensor| JumpEntry {
+                            widget_id,
+                            widget_type: BottomWidgetType::Temp,
+                            label: sensor.sensor.to_string(),
+                            target: JumpTarget::Temp(sensor.sensor.to_string()),
+                        }),
+                );
+            }
+        }
+
+        #[cfg(feature = "battery")]
+        if let Some(widget_id) = self.jump_widget_id(&BottomWidgetType::Battery) {
+            entries.extend(
+                self.data_collection
+                    .battery_harvest
+                    .iter()
+                    .map(|battery| JumpEntry {
+                        widget_id,
+                        widget_type: BottomWidgetType::Battery,
+                        label: format!("Battery {}", battery.id),
+                        target: JumpTarget::Battery(battery.id),
+                    }),
+            );
+        }
+
+        entries
+    }
+
+    /// Jumps to the entry selected in the jump palette, focusing its widget and
+    /// selecting and briefly highlighting the entry.
+    pub fn jump_to_selected(&mut self) {
+        let Some(entry) = self.jump_palette.selected_entry().cloned() else {
+            return;
+        };
+        self.close_jump_palette();
+
+        let Some(widget) = self.widget_map.get(&entry.widget_id) else {
+            return;
+        };
+        self.current_widget = widget.clone();
+        if let Some(basic_table_widget_state) = &mut self.states.basic_table_widget_state {
+            basic_table_widget_state.currently_displayed_widget_id = entry.widget_id;
+            basic_table_widget_state.currently_displayed_widget_type = entry.widget_type.clone();
+        }
+
+        let highlight_until = Some(Instant::now() + JUMP_HIGHLIGHT_DURATION);
+        match &entry.target {
+            JumpTarget::Process(pid) => {
+                if let Some(pws) = self.states.proc_state.get_mut_widget_state(entry.widget_id) {
+                    if pws
+                        .table
+                        .select_where(|process| process.pid == *pid)
+                        .is_some()
+                    {
+                        pws.table.state.highlight_until = highlight_until;
+                    }
+                }
+            }
+            JumpTarget::Disk(name, mount_point) => {
+                if let Some(disk) = self.states.disk_state.get_mut_widget_state(entry.widget_id) {
+                    if disk
+                        .table
+                        .select_where(|disk| disk.name == *name && disk.mount_point == *mount_point)
+                        .is_some()
+                    {
+                        disk.table.state.highlight_until = highlight_until;
+                    }
+                }
+            }
+            JumpTarget::Temp(sensor) => {
+                if let Some(temp) = self.states.temp_state.get_mut_widget_state(entry.widget_id) {
+                    if temp
+                        .table
+                        .select_where(|data| data.sensor == *sensor)
+                        .is_some()
+                    {
+                        temp.table.state.highlight_until = highlight_until;
+                    }
+                }
+            }
+            JumpTarget::Battery(_id) => {
+                #[cfg(feature = "battery")]
+                if let (Some(battery), Some(index)) = (
+                    self.states
+                        .battery_state
+                        .get_mut_widget_state(entry.widget_id),
+                    self.data_collection
+                        .battery_harvest
+                        .iter()
+                        .position(|battery| battery.id == *_id),
+                ) {
+                    battery.currently_selected_battery_index = index;
+                }
+            }
+        }
+
+        self.reset_multi_tap_keys();
+    }
+
+    /// Opens the settings overlay, filled in with the current settings.
+    pub fn open_settings_overlay(&mut self) {
+        if !self.ignore_normal_keybinds() {
+            self.settings_overlay.open(
+                self.app_config_fields.update_rate,
+                self.app_config_fields.retention_ms,
+            );
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Marks the current point in time on the graphs, and starts entering a
+    /// label for it.
+    pub fn drop_time_marker(&mut self) {
+        if !self.ignore_normal_keybinds() {
+            let marker = TimeMarker::new(Instant::now(), SystemTime::now());
+            self.marker_overlay.start_labelling(&marker);
+            self.data_collection.add_time_marker(marker);
+            self.update_time_markers();
+        }
+    }
+
+    /// Opens the list of markers.
+    pub fn open_marker_list(&mut self) {
+        if !self.ignore_normal_keybinds() {
+            self.marker_overlay
+                .open_list(self.data_collection.time_markers.len());
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Starts entering a label for the marker selected in the list.
+    pub fn start_labelling_selected_marker(&mut self) {
+        if let Some(marker) = self
+            .data_collection
+            .time_markers
+            .get(self.marker_overlay.selected())
+        {
+            self.marker_overlay.start_labelling(marker);
+        }
+    }
+
+    /// Sets the entered label on the marker being labelled.
+    pub fn apply_marker_label(&mut self) {
+        if let MarkerOverlayMode::Labelling(time) = self.marker_overlay.mode() {
+            let label = self.marker_overlay.label().map(str::to_string);
+            self.data_collection.set_time_marker_label(time, label);
+            self.update_time_markers();
+        }
+        self.close_marker_overlay();
+    }
+
+    /// Removes the marker selected in the list.
+    pub fn remove_selected_marker(&mut self) {
+        self.data_collection
+            .remove_time_marker(self.marker_overlay.selected());
+        self.marker_overlay
+            .clamp_selection(self.data_collection.time_markers.len());
+        self.update_time_markers();
+    }
+
+    /// Closes the marker list or label entry, going back to the list if a
+    /// label was being entered from it.
+    pub fn close_marker_overlay(&mut self) {
+        self.marker_overlay
+            .close(self.data_collection.time_markers.len());
+        self.is_force_redraw = true;
+    }
+
+    /// Updates where the markers are drawn, so that changes to them show up
+    /// before the next data comes in.
+    fn update_time_markers(&mut self) {
+        let current_instant = self
+            .frozen_state
+            .data_source(&self.data_collection)
+            .current_instant;
+        self.converted_data.time_markers =
+            convert_time_markers(&self.data_collection.time_markers, current_instant);
+        self.is_force_redraw = true;
+    }
+
+    /// Shows or hides how long each widget takes to draw.
+    pub fn toggle_draw_timings(&mut self) {
+        self.draw_timings.toggle_overlay();
+        self.is_force_redraw = true;
+    }
+
+    pub fn close_settings_overlay(&mut self) {
+        self.settings_overlay.close();
+        self.is_force_redraw = true;
+    }
+
+    /// Applies the settings entered in the settings overlay until bottom exits,
+    /// returning the new update rate if it changed so the collection thread
+    /// can be told. Invalid settings leave the overlay open, showing why.
+    pub fn apply_settings(&mut self) -> Option<u64> {
+        let LiveSettings {
+            update_rate_ms,
+            retention_ms,
+        } = match self.settings_overlay.settings() {
+            Ok(settings) => settings,
+            Err(err) => {
+                self.settings_overlay.set_error(err);
+                return None;
+            }
+        };
+        self.close_settings_overlay();
+
+        // Shrinking the retention drops the older data now, while growing it just
+        // lets more data build up.
+        if retention_ms < self.app_config_fields.retention_ms {
+            self.data_collection.clean_data(retention_ms);
+        }
+        self.app_config_fields.retention_ms = retention_ms;
+        self.app_config_fields.default_time_value =
+            self.app_config_fields.default_time_value.min(retention_ms);
+        self.app_config_fields.time_interval =
+            self.app_config_fields.time_interval.min(retention_ms);
+        self.states.limit_display_times(retention_ms);
+
+        let is_rate_changed = update_rate_ms != self.app_config_fields.update_rate;
+        self.app_config_fields.update_rate = update_rate_ms;
+        self.collection_watchdog.set_update_rate(update_rate_ms);
+        self.data_collection.gap_threshold =
+            Some(Duration::from_millis(update_rate_ms * GAP_RATE_MULTIPLIER));
+
+        self.status_message = Some("Settings applied until bottom exits".to_string());
+
+        is_rate_changed.then_some(update_rate_ms)
+    }
+
+    /// Opens the ancestry overlay for the selected process.
+    pub fn open_ancestry_overlay(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        let widget_id = self.current_widget.widget_id;
+        let Some(pws) = self.states.proc_state.widget_states.get(&widget_id) else {
+            return;
+        };
+
+        // A grouped entry doesn't have a single parent chain.
+        if matches!(pws.mode, ProcWidgetMode::Grouped | ProcWidgetMode::Users) {
+            return;
+        }
+
+        if let Some(current) = pws.table.current_item() {
+            let data_source = self.frozen_state.data_source(&self.data_collection);
+            let chain =
+                ancestry::parent_chain(&data_source.process_data.process_harvest, current.pid);
+
+            self.ancestry_overlay.open(widget_id, chain);
+            self.is_force_redraw = true;
+        }
+    }
+
+    pub fn close_ancestry_overlay(&mut self) {
+        self.ancestry_overlay.close();
+        self.is_force_redraw = true;
+    }
+
+    /// Selects the ancestor selected in the ancestry overlay in its process
+    /// widget, if it is still running and shown.
+    pub fn jump_to_selected_ancestor(&mut self) {
+        let Some(ancestor) = self.ancestry_overlay.selected_ancestor() else {
+            return;
+        };
+        if ancestor.process.is_none() {
+            return;
+        }
+
+        let pid = ancestor.pid;
+        let widget_id = self.ancestry_overlay.widget_id();
+        self.close_ancestry_overlay();
+
+        if let Some(pws) = self.states.proc_state.get_mut_widget_state(widget_id) {
+            if pws
+                .table
+                .select_where(|process| process.pid == pid)
+                .is_some()
+            {
+                pws.table.state.highlight_until = Some(Instant::now() + JUMP_HIGHLIGHT_DURATION);
+            }
         }
     }
 
@@ -442,6 +1174,16 @@ impl App {
                     pws.force_data_update();
                 }
             }
+            BottomWidgetType::Disk => {
+                if let Some(disk) = self
+                    .states
+                    .disk_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    disk.table.toggle_order();
+                    disk.force_data_update();
+                }
+            }
             _ => {}
         }
     }
@@ -529,11 +1271,59 @@ impl App {
                     };
                     proc_widget_state.force_rerender_and_update();
                 }
-                ProcWidgetMode::Grouped => {}
+                ProcWidgetMode::Grouped | ProcWidgetMode::Users => {}
             }
         }
     }
 
+    pub fn toggle_users_mode(&mut self) {
+        if let Some(proc_widget_state) = self
+            .states
+            .proc_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            proc_widget_state.toggle_users();
+        }
+    }
+
+    pub fn toggle_cpu_normalization(&mut self) {
+        if let Some(proc_widget_state) = self
+            .states
+            .proc_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            proc_widget_state.toggle_cpu_normalization();
+        }
+    }
+
+    /// Switches the selected CPU widget between showing the entries in its
+    /// legend and showing how CPU time is split between user, system, iowait,
+    /// and steal time.
+    pub fn toggle_cpu_breakdown(&mut self) {
+        let widget_id = match self.current_widget.widget_type {
+            BottomWidgetType::Cpu => self.current_widget.widget_id,
+            BottomWidgetType::CpuLegend => self.current_widget.widget_id - 1,
+            _ => return,
+        };
+
+        if let Some(cpu_widget_state) = self.states.cpu_state.widget_states.get_mut(&widget_id) {
+            cpu_widget_state.show_breakdown = !cpu_widget_state.show_breakdown;
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Asks for a text snapshot of the selected graph to be saved.
+    pub fn request_graph_snapshot(&mut self) {
+        if let BottomWidgetType::Cpu
+        | BottomWidgetType::CpuLegend
+        | BottomWidgetType::Mem
+        | BottomWidgetType::Net
+        | BottomWidgetType::Gpu = self.current_widget.widget_type
+        {
+            self.is_graph_snapshot_requested = true;
+        }
+    }
+
     /// One of two functions allowed to run while in a dialog...
     pub fn on_enter(&mut self) {
         if self.delete_dialog_state.is_showing_dd {
@@ -545,7 +1335,7 @@ impl App {
                     // Also ensure that we didn't just fail a dd...
                     let dd_result = self.kill_highlighted_process();
                     self.delete_dialog_state.scroll_pos = 0;
-                    self.delete_dialog_state.selected_signal = KillSignal::default();
+                    self.delete_dialog_state.selected_signal = self.default_kill_signal();
 
                     // Check if there was an issue... if so, inform the user.
                     if let Err(dd_err) = dd_result {
@@ -556,7 +1346,7 @@ impl App {
                 }
             } else {
                 self.delete_dialog_state.scroll_pos = 0;
-                self.delete_dialog_state.selected_signal = KillSignal::default();
+                self.delete_dialog_state.selected_signal = self.default_kill_signal();
                 self.delete_dialog_state.is_showing_dd = false;
             }
             self.is_force_redraw = true;
@@ -572,6 +1362,17 @@ impl App {
                     self.move_widget_selection(&WidgetDirection::Right);
                     self.is_force_redraw = true;
                 }
+            } else if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                if let Some(proc_widget_state) = self
+                    .states
+                    .proc_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    if let Some(user) = proc_widget_state.selected_user().map(str::to_string) {
+                        proc_widget_state.search_user(&user);
+                        self.is_force_redraw = true;
+                    }
+                }
             }
         }
     }
@@ -677,20 +1478,21 @@ impl App {
             {
                 self.delete_dialog_state.keyboard_signal_select = 0;
             }
-            let mut kbd_signal = self.delete_dialog_state.keyboard_signal_select * 10;
-            kbd_signal += number_char.to_digit(10).unwrap() as usize;
-            if kbd_signal > 64 {
-                kbd_signal %= 100;
-            }
-            #[cfg(target_os = "linux")]
-            if kbd_signal > 64 || kbd_signal == 32 || kbd_signal == 33 {
-                kbd_signal %= 10;
+            let digit = number_char.to_digit(10).unwrap() as usize;
+            let signals = &self.app_config_fields.kill_signals;
+
+            // Try to combine this with the previous digit, falling back to just this
+            // one if that isn't a listed signal.
+            let mut kbd_signal = self.delete_dialog_state.keyboard_signal_select * 10 + digit;
+            if !signals.contains(&kbd_signal) {
+                kbd_signal = digit;
             }
-            #[cfg(target_os = "macos")]
-            if kbd_signal > 31 {
-                kbd_signal %= 10;
+
+            if kbd_signal == 0 {
+                self.delete_dialog_state.selected_signal = KillSignal::Cancel;
+            } else if signals.contains(&kbd_signal) {
+                self.delete_dialog_state.selected_signal = KillSignal::Kill(kbd_signal);
             }
-            self.delete_dialog_state.selected_signal = KillSignal::Kill(kbd_signal);
             if kbd_signal < 10 {
                 self.delete_dialog_state.keyboard_signal_select = kbd_signal;
             } else {
@@ -783,19 +1585,9 @@ impl App {
             #[cfg(target_family = "unix")]
             {
                 if self.app_config_fields.is_advanced_kill {
-                    match self.delete_dialog_state.selected_signal {
-                        KillSignal::Kill(prev_signal) => {
-                            self.delete_dialog_state.selected_signal = match prev_signal - 1 {
-                                0 => KillSignal::Cancel,
-                                // 32 + 33 are skipped
-                                33 => KillSignal::Kill(31),
-                                signal => KillSignal::Kill(signal),
-                            };
-                        }
-                        KillSignal::Cancel => {}
-                    };
+                    self.change_dd_signal_position(-1);
                 } else {
-                    self.delete_dialog_state.selected_signal = KillSignal::default();
+                    self.delete_dialog_state.selected_signal = self.default_kill_signal();
                 }
             }
             #[cfg(target_os = "windows")]
@@ -849,17 +1641,7 @@ impl App {
             #[cfg(target_family = "unix")]
             {
                 if self.app_config_fields.is_advanced_kill {
-                    let new_signal = match self.delete_dialog_state.selected_signal {
-                        KillSignal::Cancel => 1,
-                        // 32+33 are skipped
-                        #[cfg(target_os = "linux")]
-                        KillSignal::Kill(31) => 34,
-                        #[cfg(target_os = "macos")]
-                        KillSignal::Kill(31) => 31,
-                        KillSignal::Kill(64) => 64,
-                        KillSignal::Kill(signal) => signal + 1,
-                    };
-                    self.delete_dialog_state.selected_signal = KillSignal::Kill(new_signal);
+                    self.change_dd_signal_position(1);
                 } else {
                     self.delete_dialog_state.selected_signal = KillSignal::Cancel;
                 }
@@ -873,17 +1655,7 @@ impl App {
 
     pub fn on_page_up(&mut self) {
         if self.delete_dialog_state.is_showing_dd {
-            let mut new_signal = match self.delete_dialog_state.selected_signal {
-                KillSignal::Cancel => 0,
-                KillSignal::Kill(signal) => max(signal, 8) - 8,
-            };
-            if new_signal > 23 && new_signal < 33 {
-                new_signal -= 2;
-            }
-            self.delete_dialog_state.selected_signal = match new_signal {
-                0 => KillSignal::Cancel,
-                sig => KillSignal::Kill(sig),
-            };
+            self.change_dd_signal_position(-8);
         } else if self.help_dialog_state.is_showing_help {
             let current = &mut self.help_dialog_state.scroll_state.current_scroll_index;
             let amount = self.help_dialog_state.height;
@@ -903,14 +1675,7 @@ impl App {
 
     pub fn on_page_down(&mut self) {
         if self.delete_dialog_state.is_showing_dd {
-            let mut new_signal = match self.delete_dialog_state.selected_signal {
-                KillSignal::Cancel => 8,
-                KillSignal::Kill(signal) => min(signal + 8, MAX_PROCESS_SIGNAL),
-            };
-            if new_signal > 31 && new_signal < 42 {
-                new_signal += 2;
-            }
-            self.delete_dialog_state.selected_signal = KillSignal::Kill(new_signal);
+            self.change_dd_signal_position(8);
         } else if self.help_dialog_state.is_showing_help {
             let current = self.help_dialog_state.scroll_state.current_scroll_index;
             let amount = self.help_dialog_state.height;
@@ -1106,7 +1871,18 @@ impl App {
             .widget_states
             .get(&self.current_widget.widget_id)
         {
+            // Don't kill every process of a user from a single keypress.
+            if let ProcWidgetMode::Users = pws.mode {
+                return;
+            }
+
             if let Some(current) = pws.table.current_item() {
+                // An exited process can't be killed, and its PID may now belong to
+                // something else.
+                if current.is_exited() {
+                    return;
+                }
+
                 let id = current.id.to_string();
                 if let Some(pids) = pws
                     .id_pid_map
@@ -1117,6 +1893,7 @@ impl App {
                     let current_process = (id, pids);
 
                     self.to_delete_process_list = Some(current_process);
+                    self.delete_dialog_state.selected_signal = self.default_kill_signal();
                     self.delete_dialog_state.is_showing_dd = true;
                     self.is_determining_widget_boundary = true;
                 }
@@ -1182,14 +1959,9 @@ impl App {
         } else if self.help_dialog_state.is_showing_help {
             match caught_char {
                 '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
-                    let potential_index = caught_char.to_digit(10);
-                    if let Some(potential_index) = potential_index {
-                        let potential_index = potential_index as usize;
-                        if (potential_index) < self.help_dialog_state.index_shortcuts.len() {
-                            self.help_scroll_to_or_max(
-                                self.help_dialog_state.index_shortcuts[potential_index],
-                            );
-                        }
+                    if let Some(section) = caught_char.to_digit(10) {
+                        self.help_dialog_state.jump_to_section(section as usize);
+                        self.is_force_redraw = true;
                     }
                 }
                 'j' | 'k' | 'g' | 'G' => self.handle_char(caught_char),
@@ -1255,7 +2027,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(0);
+                    disk.select_column(DiskColumn::Disk);
                 }
             }
             'g' => {
@@ -1280,6 +2052,12 @@ impl App {
             'f' => {
                 self.frozen_state.toggle(&self.data_collection); // TODO: Thawing should force a full data refresh and redraw immediately.
             }
+            'F' => {
+                self.frozen_state.toggle_diff(&self.data_collection);
+                for proc in self.states.proc_state.widget_states.values_mut() {
+                    proc.force_data_update();
+                }
+            }
             'c' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     if let Some(proc_widget_state) = self
@@ -1289,6 +2067,8 @@ impl App {
                     {
                         proc_widget_state.select_column(ProcWidgetColumn::Cpu);
                     }
+                } else {
+                    self.toggle_cpu_breakdown();
                 }
             }
             'm' => {
@@ -1305,7 +2085,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(1);
+                    disk.select_column(DiskColumn::Mount);
                 }
             }
             'p' => {
@@ -1322,7 +2102,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(5);
+                    disk.select_column(DiskColumn::UsedPercent);
                 }
             }
             'P' => {
@@ -1350,7 +2130,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(3);
+                    disk.select_column(DiskColumn::Free);
                 }
             }
             #[cfg(feature = "gpu")]
@@ -1378,9 +2158,15 @@ impl App {
                 }
             }
             '?' => {
-                self.help_dialog_state.is_showing_help = true;
+                self.help_dialog_state
+                    .open(&self.current_widget.widget_type);
                 self.is_force_redraw = true;
             }
+            'a' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.open_ancestry_overlay();
+                }
+            }
             'H' | 'A' => self.move_widget_selection(&WidgetDirection::Left),
             'L' | 'D' => self.move_widget_selection(&WidgetDirection::Right),
             'K' | 'W' => self.move_widget_selection(&WidgetDirection::Up),
@@ -1393,14 +2179,13 @@ impl App {
                     .temp_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    temp.table.set_sort_index(1);
-                    temp.force_data_update();
+                    temp.select_column(TempWidgetColumn::Temp);
                 } else if let Some(disk) = self
                     .states
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(4);
+                    disk.select_column(DiskColumn::Total);
                 }
             }
             '+' => self.on_plus(),
@@ -1415,8 +2200,7 @@ impl App {
                     .temp_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    temp.table.set_sort_index(0);
-                    temp.force_data_update();
+                    temp.select_column(TempWidgetColumn::Sensor);
                     self.is_force_redraw = true;
                 }
             }
@@ -1426,7 +2210,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(2);
+                    disk.select_column(DiskColumn::Used);
                 }
             }
             'r' => {
@@ -1435,7 +2219,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(6);
+                    disk.select_column(DiskColumn::IoRead);
                 }
             }
             'w' => {
@@ -1444,11 +2228,39 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(7);
+                    disk.select_column(DiskColumn::IoWrite);
+                }
+            }
+            'U' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.toggle_users_mode();
+                }
+            }
+            'N' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.toggle_cpu_normalization();
                 }
             }
             'I' => self.invert_sort(),
+            'O' => self.open_settings_overlay(),
+            'b' => self.drop_time_marker(),
+            'v' => self.open_marker_list(),
+            'T' => self.toggle_draw_timings(),
+            'B' => self.toggle_basic_mode(),
+            'E' => self.request_graph_snapshot(),
             '%' => self.toggle_percentages(),
+            #[cfg(target_family = "unix")]
+            'X' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    if self.is_confirming_sigkill() {
+                        self.reset_multi_tap_keys();
+                        self.escalate_stuck_kills();
+                    } else if self.kill_tracker.stuck().next().is_some() {
+                        self.awaiting_second_char = true;
+                        self.second_char = Some('X');
+                    }
+                }
+            }
             _ => {}
         }
 
@@ -1465,7 +2277,8 @@ impl App {
                 #[cfg(target_family = "unix")]
                 let signal = match self.delete_dialog_state.selected_signal {
                     KillSignal::Kill(sig) => sig,
-                    KillSignal::Cancel => 15, // should never happen, so just TERM
+                    // Should never happen, so just use the default.
+                    KillSignal::Cancel => self.app_config_fields.default_kill_signal,
                 };
                 for pid in pids {
                     #[cfg(target_family = "unix")]
@@ -1476,6 +2289,17 @@ impl App {
                     {
                         process_killer::kill_process_given_pid(*pid)?;
                     }
+
+                    if let Some(process) =
+                        self.data_collection.process_data.process_harvest.get(pid)
+                    {
+                        #[cfg(target_family = "unix")]
+                        let signal = Some(signal);
+                        #[cfg(target_os = "windows")]
+                        let signal = None;
+
+                        self.kill_tracker.track(process, signal, Instant::now());
+                    }
                 }
             }
             self.to_delete_process_list = None;
@@ -1485,6 +2309,31 @@ impl App {
         }
     }
 
+    /// Whether `X` was just pressed, and pressing it again sends `SIGKILL` to
+    /// the killed processes that still haven't exited.
+    #[cfg(target_family = "unix")]
+    pub fn is_confirming_sigkill(&self) -> bool {
+        self.awaiting_second_char
+            && self.second_char == Some('X')
+            && self.last_key_press.elapsed().as_millis()
+                <= constants::MAX_KEY_TIMEOUT_IN_MILLISECONDS.into()
+    }
+
+    /// Sends `SIGKILL` to any killed processes that still haven't exited.
+    #[cfg(target_family = "unix")]
+    fn escalate_stuck_kills(&mut self) {
+        const SIGKILL: usize = 9;
+
+        for pid in self.kill_tracker.take_stuck() {
+            if process_killer::kill_process_given_pid(pid, SIGKILL).is_ok() {
+                if let Some(process) = self.data_collection.process_data.process_harvest.get(&pid) {
+                    self.kill_tracker
+                        .track(process, Some(SIGKILL), Instant::now());
+                }
+            }
+        }
+    }
+
     pub fn get_to_delete_processes(&self) -> Option<(String, Vec<Pid>)> {
         self.to_delete_process_list.clone()
     }
@@ -2089,7 +2938,9 @@ impl App {
             self.help_dialog_state.scroll_state.current_scroll_index =
                 self.help_dialog_state.scroll_state.max_scroll_index;
         } else if self.delete_dialog_state.is_showing_dd {
-            self.delete_dialog_state.selected_signal = KillSignal::Kill(MAX_PROCESS_SIGNAL);
+            let signals = &self.app_config_fields.kill_signals;
+            self.delete_dialog_state
+                .select_position(signals, signals.len());
         }
     }
 
@@ -2346,6 +3197,65 @@ impl App {
                     }
                 }
             }
+            BottomWidgetType::Battery => {
+                if let Some(battery_widget_state) = self
+                    .states
+                    .battery_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = battery_widget_state
+                        .current_display_time
+                        .saturating_add(self.app_config_fields.time_interval);
+
+                    if new_time <= self.app_config_fields.retention_ms {
+                        battery_widget_state.current_display_time = new_time;
+                        self.states.battery_state.force_update =
+                            Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            battery_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if battery_widget_state.current_display_time
+                        != self.app_config_fields.retention_ms
+                    {
+                        battery_widget_state.current_display_time =
+                            self.app_config_fields.retention_ms;
+                        self.states.battery_state.force_update =
+                            Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            battery_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Gpu => {
+                if let Some(gpu_widget_state) = self
+                    .states
+                    .gpu_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = gpu_widget_state
+                        .current_display_time
+                        .saturating_add(self.app_config_fields.time_interval);
+
+                    if new_time <= self.app_config_fields.retention_ms {
+                        gpu_widget_state.current_display_time = new_time;
+                        self.states.gpu_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            gpu_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if gpu_widget_state.current_display_time
+                        != self.app_config_fields.retention_ms
+                    {
+                        gpu_widget_state.current_display_time = self.app_config_fields.retention_ms;
+                        self.states.gpu_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            gpu_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -2436,6 +3346,65 @@ impl App {
                     }
                 }
             }
+            BottomWidgetType::Battery => {
+                if let Some(battery_widget_state) = self
+                    .states
+                    .battery_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = battery_widget_state
+                        .current_display_time
+                        .saturating_sub(self.app_config_fields.time_interval);
+
+                    if new_time >= constants::STALE_MIN_MILLISECONDS {
+                        battery_widget_state.current_display_time = new_time;
+                        self.states.battery_state.force_update =
+                            Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            battery_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if battery_widget_state.current_display_time
+                        != constants::STALE_MIN_MILLISECONDS
+                    {
+                        battery_widget_state.current_display_time =
+                            constants::STALE_MIN_MILLISECONDS;
+                        self.states.battery_state.force_update =
+                            Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            battery_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Gpu => {
+                if let Some(gpu_widget_state) = self
+                    .states
+                    .gpu_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = gpu_widget_state
+                        .current_display_time
+                        .saturating_sub(self.app_config_fields.time_interval);
+
+                    if new_time >= constants::STALE_MIN_MILLISECONDS {
+                        gpu_widget_state.current_display_time = new_time;
+                        self.states.gpu_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            gpu_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if gpu_widget_state.current_display_time
+                        != constants::STALE_MIN_MILLISECONDS
+                    {
+                        gpu_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
+                        self.states.gpu_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            gpu_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -2485,15 +3454,83 @@ impl App {
         }
     }
 
+    fn reset_battery_zoom(&mut self) {
+        if let Some(battery_widget_state) = self
+            .states
+            .battery_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            battery_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            self.states.battery_state.force_update = Some(self.current_widget.widget_id);
+            if self.app_config_fields.autohide_time {
+                battery_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
+    fn reset_gpu_zoom(&mut self) {
+        if let Some(gpu_widget_state) = self
+            .states
+            .gpu_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            gpu_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            self.states.gpu_state.force_update = Some(self.current_widget.widget_id);
+            if self.app_config_fields.autohide_time {
+                gpu_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
     fn reset_zoom(&mut self) {
         match self.current_widget.widget_type {
             BottomWidgetType::Cpu => self.reset_cpu_zoom(),
             BottomWidgetType::Mem => self.reset_mem_zoom(),
             BottomWidgetType::Net => self.reset_net_zoom(),
+            BottomWidgetType::Battery => self.reset_battery_zoom(),
+            BottomWidgetType::Gpu => self.reset_gpu_zoom(),
+            _ => {}
+        }
+    }
+
+    /// Tracks where the mouse cursor is, for things like tooltips.
+    pub fn on_mouse_move(&mut self, x: u16, y: u16) {
+        self.mouse_position = Some((x, y));
+    }
+
+    /// Resizes the columns of a table if one of their borders is being dragged.
+    pub fn on_left_mouse_drag(&mut self, x: u16) {
+        let Some(drag) = &self.column_drag else {
+            return;
+        };
+
+        match drag.widget_type {
+            BottomWidgetType::Proc => {
+                if let Some(state) = self.states.proc_state.get_mut_widget_state(drag.widget_id) {
+                    state.table.resize_column(drag.column, x);
+                }
+            }
+            BottomWidgetType::Temp => {
+                if let Some(temp) = self.states.temp_state.get_mut_widget_state(drag.widget_id) {
+                    temp.table.resize_column(drag.column, x);
+                }
+            }
+            BottomWidgetType::Disk => {
+                if let Some(disk) = self.states.disk_state.get_mut_widget_state(drag.widget_id) {
+                    disk.table.resize_column(drag.column, x);
+                }
+            }
             _ => {}
         }
     }
 
+    /// Stops dragging a column border, if one was being dragged.
+    pub fn on_left_mouse_release(&mut self) {
+        self.column_drag = None;
+    }
+
     /// Moves the mouse to the widget that was clicked on, then propagates the
     /// click down to be handled by the widget specifically.
     pub fn on_left_mouse_up(&mut self, x: u16, y: u16) {
@@ -2584,12 +3621,8 @@ impl App {
                 Some((_, _, _, _, 0)) => {
                     self.delete_dialog_state.selected_signal = KillSignal::Cancel
                 }
-                Some((_, _, _, _, idx)) => {
-                    if *idx > 31 {
-                        self.delete_dialog_state.selected_signal = KillSignal::Kill(*idx + 2)
-                    } else {
-                        self.delete_dialog_state.selected_signal = KillSignal::Kill(*idx)
-                    }
+                Some((_, _, _, _, signal)) => {
+                    self.delete_dialog_state.selected_signal = KillSignal::Kill(*signal)
                 }
                 _ => {}
             }
@@ -2760,7 +3793,18 @@ impl App {
                                             .proc_state
                                             .get_mut_widget_state(self.current_widget.widget_id)
                                         {
-                                            if state.table.try_select_location(x, y).is_some() {
+                                            if let Some(column) = state.table.column_border_at(x, y)
+                                            {
+                                                self.column_drag = Some(ColumnDrag {
+                                                    widget_id: self.current_widget.widget_id,
+                                                    widget_type: BottomWidgetType::Proc,
+                                                    column,
+                                                });
+                                            } else if state
+                                                .table
+                                                .try_select_location(x, y)
+                                                .is_some()
+                                            {
                                                 state.force_data_update();
                                             }
                                         }
@@ -2771,7 +3815,15 @@ impl App {
                                             .temp_state
                                             .get_mut_widget_state(self.current_widget.widget_id)
                                         {
-                                            if temp.table.try_select_location(x, y).is_some() {
+                                            if let Some(column) = temp.table.column_border_at(x, y)
+                                            {
+                                                self.column_drag = Some(ColumnDrag {
+                                                    widget_id: self.current_widget.widget_id,
+                                                    widget_type: BottomWidgetType::Temp,
+                                                    column,
+                                                });
+                                            } else if temp.table.try_select_location(x, y).is_some()
+                                            {
                                                 temp.force_data_update();
                                             }
                                         }
@@ -2782,7 +3834,15 @@ impl App {
                                             .disk_state
                                             .get_mut_widget_state(self.current_widget.widget_id)
                                         {
-                                            if disk.table.try_select_location(x, y).is_some() {
+                                            if let Some(column) = disk.table.column_border_at(x, y)
+                                            {
+                                                self.column_drag = Some(ColumnDrag {
+                                                    widget_id: self.current_widget.widget_id,
+                                                    widget_type: BottomWidgetType::Disk,
+                                                    column,
+                                                });
+                                            } else if disk.table.try_select_location(x, y).is_some()
+                                            {
                                                 disk.force_data_update();
                                             }
                                         }
@@ -2850,6 +3910,14 @@ impl App {
         // Partially copy-pasted from the single-char variant; should probably clean up
         // this process in the future. In particular, encapsulate this entire
         // logic and add some tests to make it less potentially error-prone.
+        if let MarkerOverlayMode::Labelling(_) = self.marker_overlay.mode() {
+            // Labels are a single line.
+            for c in paste.lines().next().unwrap_or_default().chars() {
+                self.marker_overlay.push_char(c);
+            }
+            return;
+        }
+
         let is_in_search_widget = self.is_in_search_widget();
         if let Some(proc_widget_state) = self
             .states
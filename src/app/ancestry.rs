@@ -0,0 +1,233 @@
+//! An overlay showing the parent chain of a process as a breadcrumb, so that
+//! it can be seen without switching to tree mode.
+
+use std::collections::{BTreeMap, HashSet};
+
+use super::list_selection::ListSelection;
+use crate::data_collection::processes::{Pid, ProcessHarvest};
+
+/// The most ancestors to follow. This is just a backstop, as loops are already
+/// detected.
+const MAX_DEPTH: usize = 256;
+
+/// A process in a parent chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ancestor {
+    pub pid: Pid,
+
+    /// The name and CPU usage of the process, or [`None`] if it has exited.
+    pub process: Option<(String, f32)>,
+}
+
+/// Returns the parent chain of `pid` from its oldest known ancestor down to
+/// the process itself, following the parent PIDs in `processes`. The chain
+/// stops at the first ancestor that has exited.
+pub fn parent_chain(processes: &BTreeMap<Pid, ProcessHarvest>, pid: Pid) -> Vec<Ancestor> {
+    let mut chain = vec![];
+    let mut seen = HashSet::new();
+    let mut current = Some(pid);
+
+    while let Some(pid) = current {
+        // Bad data could have processes be their own ancestors, so stop if we've
+        // been here before.
+        if !seen.insert(pid) || chain.len() >= MAX_DEPTH {
+            break;
+        }
+
+        match processes.get(&pid) {
+            Some(process) => {
+                chain.push(Ancestor {
+                    pid,
+                    process: Some((process.name.clone(), process.cpu_usage_percent)),
+                });
+
+                // A parent PID of 0 means there is no parent.
+                current = process.parent_pid.filter(|&parent_pid| parent_pid != 0);
+            }
+            None => {
+                chain.push(Ancestor { pid, process: None });
+                break;
+            }
+        }
+    }
+
+    chain.reverse();
+    chain
+}
+
+/// The state of the ancestry overlay.
+#[derive(Debug, Default)]
+pub struct AncestryOverlay {
+    /// The process widget the overlay was opened from.
+    widget_id: u64,
+    chain: Vec<Ancestor>,
+
+    /// The selected ancestor.
+    selected: ListSelection,
+}
+
+impl AncestryOverlay {
+    /// Opens the overlay with the given parent chain, selecting the process
+    /// itself.
+    pub fn open(&mut self, widget_id: u64, chain: Vec<Ancestor>) {
+        self.widget_id = widget_id;
+        self.selected = ListSelection::last(chain.len());
+        self.chain = chain;
+    }
+
+    pub fn close(&mut self) {
+        self.chain.clear();
+        self.selected = ListSelection::default();
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.chain.is_empty()
+    }
+
+    pub fn widget_id(&self) -> u64 {
+        self.widget_id
+    }
+
+    pub fn chain(&self) -> &[Ancestor] {
+        &self.chain
+    }
+
+    pub fn move_selection(&mut self, change: i64) {
+        self.selected.move_by(change, self.chain.len());
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected.index()
+    }
+
+    pub fn selected_ancestor(&self) -> Option<&Ancestor> {
+        self.chain.get(self.selected.index())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn processes(processes: &[(Pid, Option<Pid>, &str)]) -> BTreeMap<Pid, ProcessHarvest> {
+        processes
+            .iter()
+            .map(|&(pid, parent_pid, name)| {
+                (
+                    pid,
+                    ProcessHarvest {
+                        pid,
+                        parent_pid,
+                        name: name.to_string(),
+                        cpu_usage_percent: 1.5,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn names(chain: &[Ancestor]) -> Vec<Option<&str>> {
+        chain
+            .iter()
+            .map(|ancestor| ancestor.process.as_ref().map(|(name, _)| name.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn full_chain() {
+        let processes = processes(&[
+            (1, Some(0), "systemd"),
+            (10, Some(1), "sshd"),
+            (20, Some(10), "bash"),
+            (30, Some(20), "cargo"),
+            (40, Some(30), "rustc"),
+            (50, Some(20), "vim"),
+        ]);
+
+        let chain = parent_chain(&processes, 40);
+        assert_eq!(
+            names(&chain),
+            vec![
+                Some("systemd"),
+                Some("sshd"),
+                Some("bash"),
+                Some("cargo"),
+                Some("rustc")
+            ]
+        );
+        assert_eq!(
+            chain
+                .iter()
+                .map(|ancestor| ancestor.pid)
+                .collect::<Vec<_>>(),
+            vec![1, 10, 20, 30, 40]
+        );
+        assert_eq!(chain[0].process, Some(("systemd".to_string(), 1.5)));
+
+        // A process without a parent.
+        assert_eq!(names(&parent_chain(&processes, 1)), vec![Some("systemd")]);
+    }
+
+    #[test]
+    fn exited_ancestors() {
+        let processes = processes(&[(20, Some(10), "bash"), (30, Some(20), "cargo")]);
+
+        let chain = parent_chain(&processes, 30);
+        assert_eq!(names(&chain), vec![None, Some("bash"), Some("cargo")]);
+        assert_eq!(chain[0].pid, 10);
+
+        // The process itself may have exited as well.
+        assert_eq!(
+            parent_chain(&processes, 99),
+            vec![Ancestor {
+                pid: 99,
+                process: None
+            }]
+        );
+    }
+
+    #[test]
+    fn parent_loops() {
+        let processes = processes(&[
+            (1, Some(3), "a"),
+            (2, Some(1), "b"),
+            (3, Some(2), "c"),
+            (4, Some(4), "d"),
+        ]);
+
+        assert_eq!(
+            names(&parent_chain(&processes, 3)),
+            vec![Some("a"), Some("b"), Some("c")]
+        );
+        assert_eq!(names(&parent_chain(&processes, 4)), vec![Some("d")]);
+    }
+
+    #[test]
+    fn overlay_selection() {
+        let processes = processes(&[(1, None, "a"), (2, Some(1), "b"), (3, Some(2), "c")]);
+
+        let mut overlay = AncestryOverlay::default();
+        assert!(!overlay.is_open());
+
+        overlay.open(5, parent_chain(&processes, 3));
+        assert!(overlay.is_open());
+        assert_eq!(overlay.widget_id(), 5);
+        assert_eq!(
+            overlay.selected_ancestor().map(|ancestor| ancestor.pid),
+            Some(3)
+        );
+
+        overlay.move_selection(1);
+        assert_eq!(overlay.selected_index(), 2);
+        overlay.move_selection(-5);
+        assert_eq!(
+            overlay.selected_ancestor().map(|ancestor| ancestor.pid),
+            Some(1)
+        );
+
+        overlay.close();
+        assert!(!overlay.is_open());
+        assert!(overlay.selected_ancestor().is_none());
+    }
+}
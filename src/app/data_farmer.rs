@@ -13,10 +13,16 @@
 //! memory usage and higher CPU usage - you will be trying to process more and
 //! more points as this is used!
 
-use std::{collections::BTreeMap, time::Instant, vec::Vec};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, VecDeque},
+    time::{Duration, Instant},
+    vec::Vec,
+};
 
 use hashbrown::HashMap;
 
+use super::time_markers::TimeMarker;
 #[cfg(feature = "battery")]
 use crate::data_collection::batteries;
 use crate::{
@@ -25,14 +31,17 @@ use crate::{
         processes::{Pid, ProcessHarvest},
         temperature, Data,
     },
-    dec_bytes_per_second_string,
+    utils::general::partial_ordering,
 };
 
 #[derive(Debug, Default, Clone)]
 pub struct TimedData {
-    pub rx_data: f64,
-    pub tx_data: f64,
-    pub cpu_data: Vec<f64>,
+    pub rx_data: Option<f64>,
+    pub tx_data: Option<f64>,
+    pub interface_data: Vec<Option<(f64, f64)>>,
+    pub cpu_data: Vec<Option<f64>>,
+    /// How CPU time was split between user, system, iowait, and steal time.
+    pub cpu_breakdown: Option<cpu::CpuBreakdown>,
     pub mem_data: Option<f64>,
     #[cfg(not(target_os = "windows"))]
     pub cache_data: Option<f64>,
@@ -41,9 +50,141 @@ pub struct TimedData {
     pub arc_data: Option<f64>,
     #[cfg(feature = "gpu")]
     pub gpu_data: Vec<Option<f64>>,
+    /// How busy each GPU was, in the order of
+    /// [`DataCollection::gpu_util_harvest`].
+    #[cfg(feature = "gpu")]
+    pub gpu_util_data: Vec<Option<f64>>,
+    /// The charge of each battery, indexed by [`batteries::BatteryData::id`].
+    /// Batteries missing from a harvest (e.g. unplugged ones) are `None`.
+    #[cfg(feature = "battery")]
+    pub battery_data: Vec<Option<f64>>,
+    /// Whether this point ends a run of points that were merged into it, in
+    /// which case the values between the previous point and this one were
+    /// the same as the previous point's (within the merge tolerance).
+    pub merged: bool,
+    /// Whether this point came after one or more missed harvests (e.g. the
+    /// system was suspended), in which case it shouldn't be connected to the
+    /// point before it.
+    pub after_gap: bool,
+}
+
+impl TimedData {
+    /// Roughly how many bytes this point takes up when stored, including what
+    /// its vectors hold.
+    fn footprint(&self) -> usize {
+        #[cfg_attr(not(any(feature = "gpu", feature = "battery")), expect(unused_mut))]
+        let mut bytes = std::mem::size_of::<(Instant, TimedData)>()
+            + self.interface_data.len() * std::mem::size_of::<Option<(f64, f64)>>()
+            + self.cpu_data.len() * std::mem::size_of::<Option<f64>>();
+        #[cfg(feature = "gpu")]
+        {
+            bytes += (self.gpu_data.len() + self.gpu_util_data.len())
+                * std::mem::size_of::<Option<f64>>();
+        }
+        #[cfg(feature = "battery")]
+        {
+            bytes += self.battery_data.len() * std::mem::size_of::<Option<f64>>();
+        }
+
+        bytes
+    }
+
+    /// Whether every value of `self` is within `tolerance` percent of the
+    /// matching value in `other`, relative to the larger of the two. Values
+    /// that are missing from one but not the other are never within tolerance.
+    fn is_within(&self, other: &TimedData, tolerance: f64) -> bool {
+        // Being relative, the tolerance works the same for every series,
+        // whether it is a percentage or a rate in bytes.
+        let close = |a: f64, b: f64| (a - b).abs() <= a.abs().max(b.abs()) * tolerance / 100.0;
+        let close_opt = |a: &Option<f64>, b: &Option<f64>| match (a, b) {
+            (Some(a), Some(b)) => close(*a, *b),
+            (None, None) => true,
+            _ => false,
+        };
+        let close_vec = |a: &[Option<f64>], b: &[Option<f64>]| {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| close_opt(a, b))
+        };
+
+        let within = close_opt(&self.rx_data, &other.rx_data)
+            && close_opt(&self.tx_data, &other.tx_data)
+            && self.interface_data.len() == other.interface_data.len()
+            && self
+                .interface_data
+                .iter()
+                .zip(&other.interface_data)
+                .all(|(a, b)| match (a, b) {
+                    (Some((a_rx, a_tx)), Some((b_rx, b_tx))) => {
+                        close(*a_rx, *b_rx) && close(*a_tx, *b_tx)
+                    }
+                    (None, None) => true,
+                    _ => false,
+                })
+            && close_vec(&self.cpu_data, &other.cpu_data)
+            && match (&self.cpu_breakdown, &other.cpu_breakdown) {
+                (Some(a), Some(b)) => cpu::CpuTimeKind::ALL
+                    .iter()
+                    .all(|kind| close_opt(&a.get(*kind), &b.get(*kind))),
+                (None, None) => true,
+                _ => false,
+            }
+            && close_opt(&self.mem_data, &other.mem_data)
+            && close_opt(&self.swap_data, &other.swap_data);
+
+        #[cfg(not(target_os = "windows"))]
+        let within = within && close_opt(&self.cache_data, &other.cache_data);
+
+        #[cfg(feature = "zfs")]
+        let within = within && close_opt(&self.arc_data, &other.arc_data);
+
+        #[cfg(feature = "gpu")]
+        let within = within
+            && close_vec(&self.gpu_data, &other.gpu_data)
+            && close_vec(&self.gpu_util_data, &other.gpu_util_data);
+
+        #[cfg(feature = "battery")]
+        let within = within && close_vec(&self.battery_data, &other.battery_data);
+
+        within
+    }
 }
 
-#[derive(Clone, Debug, Default)]
+/// The readings of a temperature sensor this session.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TempStats {
+    pub min: f32,
+    pub max: f32,
+    pub last: f32,
+    /// The reading before the last one, if there has been one.
+    pub previous: Option<f32>,
+}
+
+impl TempStats {
+    fn new(temperature: f32) -> Self {
+        Self {
+            min: temperature,
+            max: temperature,
+            last: temperature,
+            previous: None,
+        }
+    }
+
+    fn add(&mut self, temperature: f32) {
+        self.min = self.min.min(temperature);
+        self.max = self.max.max(temperature);
+        self.previous = Some(self.last);
+        self.last = temperature;
+    }
+
+    /// Whether the last reading went up, down, or stayed the same compared to
+    /// the one before, as shown (i.e. rounded up to a whole degree).
+    pub fn trend(&self) -> Option<Ordering> {
+        let previous = self.previous?;
+
+        Some(partial_ordering(self.last.ceil(), previous.ceil()))
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct ProcessData {
     /// A PID to process data map.
     pub process_harvest: BTreeMap<Pid, ProcessHarvest>,
@@ -53,10 +194,103 @@ pub struct ProcessData {
 
     /// PIDs corresponding to processes that have no parents.
     pub orphan_pids: Vec<Pid>,
+
+    /// A mapping between a process PID and its most recent CPU usage values,
+    /// oldest first. Bounded to [`PROCESS_CPU_HISTORY_LEN`] entries.
+    pub cpu_history: HashMap<Pid, VecDeque<f32>>,
+
+    /// When each process's CPU usage last went over [`Self::spike_threshold`].
+    /// Processes that have exited are dropped.
+    pub last_spike: HashMap<Pid, LastSpike>,
+
+    /// The CPU usage a process must go over to count as a spike.
+    pub spike_threshold: f32,
+
+    /// When the processes were last harvested.
+    pub harvested_at: Option<Instant>,
+
+    /// Whether the stored CPU usage is out of 100% per core, rather than out
+    /// of 100% across all cores.
+    pub unnormalized_cpu: bool,
+
+    /// The number of CPU cores the CPU usage was measured across, or 0 if it
+    /// isn't known.
+    pub num_cpus: usize,
+}
+
+/// When a process's CPU usage last went over the spike threshold, for each way
+/// it can be shown, as the threshold is compared with the usage as shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LastSpike {
+    /// The process's start time, to tell apart reused PIDs.
+    start_time: u64,
+    /// When the usage out of 100% across all cores went over the threshold.
+    normalized: Option<Instant>,
+    /// When the usage out of 100% per core went over the threshold.
+    unnormalized: Option<Instant>,
+}
+
+/// How many times larger the capacity of [`DataCollection::timed_data_vec`] can
+/// be compared to its length before it is shrunk.
+const TIMED_DATA_SHRINK_RATIO: usize = 4;
+
+/// How many update intervals can pass between harvests before it is treated as
+/// a gap in the data.
+pub const GAP_RATE_MULTIPLIER: u64 = 3;
+
+/// How many CPU usage values to keep per process.
+pub const PROCESS_CPU_HISTORY_LEN: usize = 8;
+
+/// The default CPU usage a process must go over to count as a spike.
+pub const DEFAULT_SPIKE_THRESHOLD: f32 = 50.0;
+
+impl Default for ProcessData {
+    fn default() -> Self {
+        Self {
+            process_harvest: BTreeMap::default(),
+            process_parent_mapping: HashMap::default(),
+            orphan_pids: Vec::default(),
+            cpu_history: HashMap::default(),
+            last_spike: HashMap::default(),
+            spike_threshold: DEFAULT_SPIKE_THRESHOLD,
+            harvested_at: None,
+            unnormalized_cpu: false,
+            num_cpus: 0,
+        }
+    }
 }
 
 impl ProcessData {
-    fn ingest(&mut self, list_of_processes: Vec<ProcessHarvest>) {
+    /// Returns what to multiply the stored CPU usage by to show it either out
+    /// of 100% per core if `unnormalized` is set, or out of 100% across all
+    /// cores otherwise. If the number of cores isn't known, it is shown as is.
+    pub fn cpu_usage_scale(&self, unnormalized: bool) -> f32 {
+        if self.num_cpus == 0 || self.unnormalized_cpu == unnormalized {
+            1.0
+        } else if unnormalized {
+            self.num_cpus as f32
+        } else {
+            1.0 / self.num_cpus as f32
+        }
+    }
+
+    /// How long ago a process's CPU usage last went over the spike threshold,
+    /// as of the latest harvest, or [`None`] if it never has. The usage is
+    /// compared as it is shown, either out of 100% per core if `unnormalized`
+    /// is set, or out of 100% across all cores otherwise.
+    pub fn spiked_ago(&self, pid: Pid, unnormalized: bool) -> Option<Duration> {
+        let last_spike = self.last_spike.get(&pid)?;
+        let spiked_at = if unnormalized {
+            last_spike.unnormalized
+        } else {
+            last_spike.normalized
+        }?;
+        let harvested_at = self.harvested_at?;
+
+        Some(harvested_at.saturating_duration_since(spiked_at))
+    }
+
+    fn ingest(&mut self, list_of_processes: Vec<ProcessHarvest>, harvested_at: Instant) {
         self.process_parent_mapping.clear();
 
         // Reverse as otherwise the pid mappings are in the wrong order.
@@ -79,6 +313,44 @@ impl ProcessData {
             .collect();
         self.process_harvest = process_pid_map;
 
+        // Drop the history of any processes that have exited.
+        self.cpu_history
+            .retain(|pid, _| self.process_harvest.contains_key(pid));
+
+        // A reused PID is a different process, so its spikes are dropped too.
+        self.last_spike.retain(|pid, last_spike| {
+            self.process_harvest
+                .get(pid)
+                .is_some_and(|process| process.start_time == last_spike.start_time)
+        });
+
+        let normalized_scale = self.cpu_usage_scale(false);
+        let unnormalized_scale = self.cpu_usage_scale(true);
+
+        for process in self.process_harvest.values() {
+            let history = self.cpu_history.entry(process.pid).or_default();
+            if history.len() >= PROCESS_CPU_HISTORY_LEN {
+                history.pop_front();
+            }
+            history.push_back(process.cpu_usage_percent);
+
+            let is_spike = |scale: f32| process.cpu_usage_percent * scale > self.spike_threshold;
+            if is_spike(normalized_scale) || is_spike(unnormalized_scale) {
+                let last_spike = self.last_spike.entry(process.pid).or_insert(LastSpike {
+                    start_time: process.start_time,
+                    normalized: None,
+                    unnormalized: None,
+                });
+                if is_spike(normalized_scale) {
+                    last_spike.normalized = Some(harvested_at);
+                }
+                if is_spike(unnormalized_scale) {
+                    last_spike.unnormalized = Some(harvested_at);
+                }
+            }
+        }
+        self.harvested_at = Some(harvested_at);
+
         // We collect all processes that either:
         // - Do not have a parent PID (that is, they are orphan processes)
         // - Have a parent PID but we don't have the parent (we promote them as orphans)
@@ -108,24 +380,58 @@ pub struct DataCollection {
     pub current_instant: Instant,
     pub timed_data_vec: Vec<(Instant, TimedData)>,
     pub network_harvest: network::NetworkHarvest,
+    pub network_interface_harvest: Vec<(String, network::NetworkHarvest)>,
+    /// Every network interface seen so far, in the order their values are stored
+    /// in [`TimedData::interface_data`].
+    pub network_interface_names: Vec<String>,
+    /// The highest RX and TX rates seen this session, in bits per second.
+    pub network_session_max: (u64, u64),
+    /// The highest RX and TX rates seen this session for each interface, in
+    /// the order of [`Self::network_interface_names`].
+    pub network_interface_session_max: Vec<(u64, u64)>,
+    /// How much has been received and transmitted this session, in bits.
+    pub network_session_total: (u64, u64),
     pub memory_harvest: memory::MemHarvest,
     #[cfg(not(target_os = "windows"))]
     pub cache_harvest: memory::MemHarvest,
     pub swap_harvest: memory::MemHarvest,
     pub cpu_harvest: cpu::CpuHarvest,
+    /// Every CPU entry seen so far, in the order their values are stored in
+    /// [`TimedData::cpu_data`].
+    pub cpu_ids: Vec<cpu::CpuDataType>,
     pub load_avg_harvest: cpu::LoadAvgHarvest,
     pub process_data: ProcessData,
     pub disk_harvest: Vec<disks::DiskHarvest>,
     pub io_harvest: disks::IoHarvest,
     pub io_labels_and_prev: Vec<((u64, u64), (u64, u64))>,
-    pub io_labels: Vec<(String, String)>,
+    pub io_rates: Vec<Option<(u64, u64)>>,
     pub temp_harvest: Vec<temperature::TempHarvest>,
+    /// The readings of each temperature sensor this session, keyed by name.
+    pub temp_stats: HashMap<String, TempStats>,
     #[cfg(feature = "battery")]
     pub battery_harvest: Vec<batteries::BatteryData>,
     #[cfg(feature = "zfs")]
     pub arc_harvest: memory::MemHarvest,
+    /// The ARC's hit ratio as a percentage, if ARC stats are available.
+    #[cfg(feature = "zfs")]
+    pub arc_hit_ratio: Option<f64>,
+    /// The ARC's data/metadata split and the L2ARC's size.
+    #[cfg(feature = "zfs")]
+    pub arc_breakdown: memory::arc::ArcBreakdown,
     #[cfg(feature = "gpu")]
     pub gpu_harvest: Vec<(String, memory::MemHarvest)>,
+    /// How busy each GPU is, as a percentage, by name.
+    #[cfg(feature = "gpu")]
+    pub gpu_util_harvest: Vec<(String, f64)>,
+    /// If set, a new point within this percentage of the run of points before
+    /// it replaces the end of that run instead of being added, to save memory
+    /// when values hold steady. See [`TimedData::merged`].
+    pub merge_tolerance: Option<f64>,
+    /// If set, a harvest that arrives more than this long after the previous
+    /// one is marked as coming after a gap. See [`TimedData::after_gap`].
+    pub gap_threshold: Option<Duration>,
+    /// Points in time marked on the graphs, oldest first.
+    pub time_markers: Vec<TimeMarker>,
 }
 
 impl Default for DataCollection {
@@ -134,24 +440,40 @@ impl Default for DataCollection {
             current_instant: Instant::now(),
             timed_data_vec: Vec::default(),
             network_harvest: network::NetworkHarvest::default(),
+            network_interface_harvest: Vec::default(),
+            network_interface_names: Vec::default(),
+            network_session_max: (0, 0),
+            network_interface_session_max: Vec::default(),
+            network_session_total: (0, 0),
             memory_harvest: memory::MemHarvest::default(),
             #[cfg(not(target_os = "windows"))]
             cache_harvest: memory::MemHarvest::default(),
             swap_harvest: memory::MemHarvest::default(),
             cpu_harvest: cpu::CpuHarvest::default(),
+            cpu_ids: Vec::default(),
             load_avg_harvest: cpu::LoadAvgHarvest::default(),
             process_data: Default::default(),
             disk_harvest: Vec::default(),
             io_harvest: disks::IoHarvest::default(),
             io_labels_and_prev: Vec::default(),
-            io_labels: Vec::default(),
+            io_rates: Vec::default(),
             temp_harvest: Vec::default(),
+            temp_stats: HashMap::default(),
             #[cfg(feature = "battery")]
             battery_harvest: Vec::default(),
             #[cfg(feature = "zfs")]
             arc_harvest: memory::MemHarvest::default(),
+            #[cfg(feature = "zfs")]
+            arc_hit_ratio: None,
+            #[cfg(feature = "zfs")]
+            arc_breakdown: memory::arc::ArcBreakdown::default(),
             #[cfg(feature = "gpu")]
             gpu_harvest: Vec::default(),
+            #[cfg(feature = "gpu")]
+            gpu_util_harvest: Vec::default(),
+            merge_tolerance: None,
+            gap_threshold: None,
+            time_markers: Vec::default(),
         }
     }
 }
@@ -160,14 +482,25 @@ impl DataCollection {
     pub fn reset(&mut self) {
         self.timed_data_vec = Vec::default();
         self.network_harvest = network::NetworkHarvest::default();
+        self.network_interface_harvest = Vec::default();
+        self.network_interface_names = Vec::default();
+        self.network_session_max = (0, 0);
+        self.network_interface_session_max = Vec::default();
+        self.network_session_total = (0, 0);
         self.memory_harvest = memory::MemHarvest::default();
         self.swap_harvest = memory::MemHarvest::default();
         self.cpu_harvest = cpu::CpuHarvest::default();
-        self.process_data = Default::default();
+        self.cpu_ids = Vec::default();
+        self.process_data = ProcessData {
+            unnormalized_cpu: self.process_data.unnormalized_cpu,
+            spike_threshold: self.process_data.spike_threshold,
+            ..Default::default()
+        };
         self.disk_harvest = Vec::default();
         self.io_harvest = disks::IoHarvest::default();
         self.io_labels_and_prev = Vec::default();
         self.temp_harvest = Vec::default();
+        self.temp_stats = HashMap::default();
         #[cfg(feature = "battery")]
         {
             self.battery_harvest = Vec::default();
@@ -175,31 +508,192 @@ impl DataCollection {
         #[cfg(feature = "zfs")]
         {
             self.arc_harvest = memory::MemHarvest::default();
+            self.arc_hit_ratio = None;
+            self.arc_breakdown = memory::arc::ArcBreakdown::default();
         }
         #[cfg(feature = "gpu")]
         {
             self.gpu_harvest = Vec::default();
+            self.gpu_util_harvest = Vec::default();
         }
+        self.time_markers = Vec::default();
+    }
+
+    /// Marks a point in time on the graphs.
+    pub fn add_time_marker(&mut self, marker: TimeMarker) {
+        let index = self
+            .time_markers
+            .partition_point(|existing| existing.time <= marker.time);
+        self.time_markers.insert(index, marker);
+    }
+
+    /// Sets the label of the marker at `time`, if it still exists.
+    pub fn set_time_marker_label(&mut self, time: Instant, label: Option<String>) {
+        if let Some(marker) = self.time_markers.iter_mut().find(|m| m.time == time) {
+            marker.label = label;
+        }
+    }
+
+    pub fn remove_time_marker(&mut self, index: usize) {
+        if index < self.time_markers.len() {
+            self.time_markers.remove(index);
+        }
+    }
+
+    /// Estimates how many bytes of graph data are kept with the given retention
+    /// and update rate, based on the size of the latest point. Merged points
+    /// aren't taken into account, so this is an upper bound.
+    pub fn estimated_footprint(&self, retention_ms: u64, update_rate_ms: u64) -> u64 {
+        let point_bytes = match self.timed_data_vec.last() {
+            Some((_, data)) => data.footprint(),
+            None => TimedData::default().footprint(),
+        };
+        let points = retention_ms / update_rate_ms.max(1) + 1;
+
+        points.saturating_mul(point_bytes as u64)
     }
 
     pub fn clean_data(&mut self, max_time_millis: u64) {
         let current_time = Instant::now();
 
-        let remove_index = match self
-            .timed_data_vec
-            .binary_search_by(|(instant, _timed_data)| {
-                current_time
-                    .duration_since(*instant)
-                    .as_millis()
-                    .cmp(&(max_time_millis.into()))
-                    .reverse()
-            }) {
-            Ok(index) => index,
-            Err(index) => index,
-        };
+        let mut remove_index =
+            match self
+                .timed_data_vec
+                .binary_search_by(|(instant, _timed_data)| {
+                    current_time
+                        .duration_since(*instant)
+                        .as_millis()
+                        .cmp(&(max_time_millis.into()))
+                        .reverse()
+                }) {
+                Ok(index) => index,
+                Err(index) => index,
+            };
+
+        // Keep the start of a merged run that is still partly within the retention
+        // period, as otherwise the run would be lost.
+        if remove_index > 0
+            && self
+                .timed_data_vec
+                .get(remove_index)
+                .is_some_and(|(_, data)| data.merged)
+        {
+            remove_index -= 1;
+        }
 
         self.timed_data_vec.drain(0..remove_index);
-        self.timed_data_vec.shrink_to_fit();
+        self.prune_network_interfaces();
+        self.time_markers.retain(|marker| {
+            current_time
+                .saturating_duration_since(marker.time)
+                .as_millis()
+                <= max_time_millis.into()
+        });
+
+        // Only shrink once the capacity is well past what is actually used, so we
+        // don't reallocate on every clean but still reclaim memory over long
+        // sessions.
+        if self.timed_data_vec.capacity() > TIMED_DATA_SHRINK_RATIO * self.timed_data_vec.len() {
+            self.timed_data_vec.shrink_to_fit();
+        }
+    }
+
+    /// Forgets network interfaces that no stored data point has values for,
+    /// such as those of containers that have since stopped, so that the list of
+    /// interfaces doesn't keep growing.
+    fn prune_network_interfaces(&mut self) {
+        let mut is_used = vec![false; self.network_interface_names.len()];
+        for (_, data) in &self.timed_data_vec {
+            for (used, value) in is_used.iter_mut().zip(&data.interface_data) {
+                *used |= value.is_some();
+            }
+        }
+
+        if is_used.iter().all(|used| *used) {
+            return;
+        }
+
+        let mut used = is_used.iter();
+        self.network_interface_names
+            .retain(|_| used.next().copied().unwrap_or(true));
+        let mut used = is_used.iter();
+        self.network_interface_session_max
+            .retain(|_| used.next().copied().unwrap_or(true));
+        for (_, data) in &mut self.timed_data_vec {
+            let mut used = is_used.iter();
+            data.interface_data
+                .retain(|_| used.next().copied().unwrap_or(true));
+        }
+    }
+
+    /// Downsamples a series from [`TimedData`] into at most `buckets` values,
+    /// bucketed by time from the oldest stored point to the most recent one.
+    ///
+    /// Each bucket keeps its minimum or maximum, whichever is further from the
+    /// previous bucket's value, so spikes survive downsampling. Buckets without
+    /// any points (e.g. a gap in collection) are returned as `None`, unless
+    /// they are covered by a merged run, which is filled back in.
+    pub fn downsample(
+        &self, field: impl Fn(&TimedData) -> Option<f64>, buckets: usize,
+    ) -> Vec<Option<f64>> {
+        let (Some((start, _)), Some((end, _))) =
+            (self.timed_data_vec.first(), self.timed_data_vec.last())
+        else {
+            return vec![];
+        };
+
+        if buckets == 0 {
+            return vec![];
+        }
+
+        let span = end.duration_since(*start).as_secs_f64();
+        let mut extremes: Vec<Option<(f64, f64)>> = vec![None; buckets];
+        let mut add = |index: usize, value: f64| {
+            extremes[index] = match extremes[index] {
+                Some((min, max)) => Some((min.min(value), max.max(value))),
+                None => Some((value, value)),
+            };
+        };
+
+        let mut prev_point: Option<(usize, f64)> = None;
+        for (time, data) in &self.timed_data_vec {
+            let Some(value) = field(data) else {
+                prev_point = None;
+                continue;
+            };
+
+            let index = if span > 0.0 {
+                let offset = time.duration_since(*start).as_secs_f64() / span;
+                ((offset * buckets as f64) as usize).min(buckets - 1)
+            } else {
+                0
+            };
+
+            // The points merged away held the previous point's value.
+            if let Some((prev_index, prev_value)) = prev_point.filter(|_| data.merged) {
+                for index in (prev_index + 1)..index {
+                    add(index, prev_value);
+                }
+            }
+
+            add(index, value);
+            prev_point = Some((index, value));
+        }
+
+        let mut prev: Option<f64> = None;
+        extremes
+            .into_iter()
+            .map(|bucket| {
+                let (min, max) = bucket?;
+                let value = match prev {
+                    Some(prev) if (prev - min).abs() > (max - prev).abs() => min,
+                    _ => max,
+                };
+                prev = Some(value);
+
+                Some(value)
+            })
+            .collect()
     }
 
     #[allow(
@@ -210,11 +704,21 @@ impl DataCollection {
         let harvested_time = harvested_data.collection_time;
         let mut new_entry = TimedData::default();
 
+        // Disk counters kept going while the system was suspended, so start their rates
+        // over instead of counting all of that against the last interval.
+        if harvested_data.resumed {
+            self.io_labels_and_prev.clear();
+        }
+
         // Network
         if let Some(network) = harvested_data.network {
             self.eat_network(network, &mut new_entry);
         }
 
+        if let Some(interfaces) = harvested_data.network_interfaces {
+            self.eat_network_interfaces(interfaces, &mut new_entry);
+        }
+
         // Memory, Swap
         if let (Some(memory), Some(swap)) = (harvested_data.memory, harvested_data.swap) {
             self.eat_memory_and_swap(memory, swap, &mut new_entry);
@@ -229,6 +733,8 @@ impl DataCollection {
         #[cfg(feature = "zfs")]
         if let Some(arc) = harvested_data.arc {
             self.eat_arc(arc, &mut new_entry);
+            self.arc_hit_ratio = harvested_data.arc_hit_ratio;
+            self.arc_breakdown = harvested_data.arc_breakdown.unwrap_or_default();
         }
 
         #[cfg(feature = "gpu")]
@@ -236,10 +742,16 @@ impl DataCollection {
             self.eat_gpu(gpu, &mut new_entry);
         }
 
+        #[cfg(feature = "gpu")]
+        if let Some(gpu_util) = harvested_data.gpu_util {
+            self.eat_gpu_util(gpu_util, &mut new_entry);
+        }
+
         // CPU
         if let Some(cpu) = harvested_data.cpu {
             self.eat_cpu(cpu, &mut new_entry);
         }
+        new_entry.cpu_breakdown = harvested_data.cpu_breakdown;
 
         // Load average
         if let Some(load_avg) = harvested_data.load_avg {
@@ -260,20 +772,58 @@ impl DataCollection {
 
         // Processes
         if let Some(list_of_processes) = harvested_data.list_of_processes {
-            self.eat_proc(list_of_processes);
+            self.eat_proc(list_of_processes, harvested_time);
+        }
+        if let Some(num_cpus) = harvested_data.num_cpus {
+            self.process_data.num_cpus = num_cpus;
         }
 
         #[cfg(feature = "battery")]
         {
             // Battery
             if let Some(list_of_batteries) = harvested_data.list_of_batteries {
-                self.eat_battery(list_of_batteries);
+                self.eat_battery(list_of_batteries, &mut new_entry);
             }
         }
 
         // And we're done eating.  Update time and push the new entry!
+        // As time while suspended doesn't count towards instants, the harvest after a
+        // suspend can look like it came right after the one before it.
+        new_entry.after_gap = match (self.gap_threshold, self.timed_data_vec.last()) {
+            (_, Some(_)) if harvested_data.resumed => true,
+            (Some(threshold), Some((last_time, _))) => {
+                harvested_time.saturating_duration_since(*last_time) > threshold
+            }
+            _ => false,
+        };
         self.current_instant = harvested_time;
-        self.timed_data_vec.push((harvested_time, new_entry));
+        if self.extends_run(&new_entry) {
+            new_entry.merged = true;
+            if let Some(last) = self.timed_data_vec.last_mut() {
+                *last = (harvested_time, new_entry);
+            }
+        } else {
+            self.timed_data_vec.push((harvested_time, new_entry));
+        }
+    }
+
+    /// Whether `entry` continues the run of points that the last two stored
+    /// points form, given the merge tolerance. Runs are compared against their
+    /// first point so that slow drifts still end them.
+    fn extends_run(&self, entry: &TimedData) -> bool {
+        let Some(tolerance) = self.merge_tolerance.filter(|_| !entry.after_gap) else {
+            return false;
+        };
+
+        match self.timed_data_vec.as_slice() {
+            [.., (_, start), (_, end)] => {
+                // A run can't span a gap.
+                !end.after_gap
+                    && start.is_within(end, tolerance)
+                    && start.is_within(entry, tolerance)
+            }
+            _ => false,
+        }
     }
 
     fn eat_memory_and_swap(
@@ -294,26 +844,75 @@ impl DataCollection {
     }
 
     fn eat_network(&mut self, network: network::NetworkHarvest, new_entry: &mut TimedData) {
-        // RX
-        if network.rx > 0 {
-            new_entry.rx_data = network.rx as f64;
-        }
+        new_entry.rx_data = Some(network.rx as f64);
+        new_entry.tx_data = Some(network.tx as f64);
 
-        // TX
-        if network.tx > 0 {
-            new_entry.tx_data = network.tx as f64;
-        }
+        self.network_session_max = (
+            self.network_session_max.0.max(network.rx),
+            self.network_session_max.1.max(network.tx),
+        );
+        self.network_session_total = (
+            self.network_session_total
+                .0
+                .saturating_add(network.rx_delta),
+            self.network_session_total
+                .1
+                .saturating_add(network.tx_delta),
+        );
 
         // In addition copy over latest data for easy reference
         self.network_harvest = network;
     }
 
+    fn eat_network_interfaces(
+        &mut self, interfaces: Vec<(String, network::NetworkHarvest)>, new_entry: &mut TimedData,
+    ) {
+        // Like with CPUs, new interfaces are only ever appended so older data points
+        // stay aligned, and interfaces missing from this harvest are left as gaps.
+        let indices = interfaces
+            .iter()
+            .map(
+                |(name, _)| match self.network_interface_names.iter().position(|n| n == name) {
+                    Some(index) => index,
+                    None => {
+                        self.network_interface_names.push(name.clone());
+                        self.network_interface_names.len() - 1
+                    }
+                },
+            )
+            .collect::<Vec<_>>();
+
+        new_entry.interface_data = vec![None; self.network_interface_names.len()];
+        self.network_interface_session_max
+            .resize(self.network_interface_names.len(), (0, 0));
+        for (index, (_, network)) in indices.into_iter().zip(&interfaces) {
+            new_entry.interface_data[index] = Some((network.rx as f64, network.tx as f64));
+
+            let (rx_max, tx_max) = &mut self.network_interface_session_max[index];
+            *rx_max = (*rx_max).max(network.rx);
+            *tx_max = (*tx_max).max(network.tx);
+        }
+
+        self.network_interface_harvest = interfaces;
+    }
+
     fn eat_cpu(&mut self, cpu: Vec<cpu::CpuData>, new_entry: &mut TimedData) {
-        // Note this only pre-calculates the data points - the names will be
-        // within the local copy of cpu_harvest.  Since it's all sequential
-        // it probably doesn't matter anyways.
-        cpu.iter()
-            .for_each(|cpu| new_entry.cpu_data.push(cpu.cpu_usage));
+        // Note this only pre-calculates the data points, aligned by the order in
+        // `cpu_ids`. New entries are only ever appended so older data points stay
+        // aligned, and entries missing from this harvest (e.g. an offline core) are
+        // left as gaps.
+        for cpu in &cpu {
+            if !self.cpu_ids.contains(&cpu.data_type) {
+                self.cpu_ids.push(cpu.data_type);
+            }
+        }
+
+        new_entry.cpu_data = vec![None; self.cpu_ids.len()];
+        for cpu in &cpu {
+            if let Some(index) = self.cpu_ids.iter().position(|id| *id == cpu.data_type) {
+                new_entry.cpu_data[index] = Some(cpu.cpu_usage);
+            }
+        }
 
         self.cpu_harvest = cpu;
     }
@@ -323,6 +922,19 @@ impl DataCollection {
     }
 
     fn eat_temp(&mut self, temperature_sensors: Vec<temperature::TempHarvest>) {
+        // Sensors without a reading (e.g. powered down GPUs) keep their stats.
+        for sensor in &temperature_sensors {
+            if let Some(temperature) = sensor.temperature {
+                match self.temp_stats.get_mut(&sensor.name) {
+                    Some(stats) => stats.add(temperature),
+                    None => {
+                        self.temp_stats
+                            .insert(sensor.name.clone(), TempStats::new(temperature));
+                    }
+                }
+            }
+        }
+
         self.temp_harvest = temperature_sensors;
     }
 
@@ -361,51 +973,28 @@ impl DataCollection {
             };
 
             if let Some(checked_name) = checked_name {
-                let io_device = {
-                    #[cfg(target_os = "macos")]
-                    {
-                        use std::sync::OnceLock;
-
-                        use regex::Regex;
-
-                        // Must trim one level further for macOS!
-                        static DISK_REGEX: OnceLock<Regex> = OnceLock::new();
-
-                        #[expect(
-                            clippy::regex_creation_in_loops,
-                            reason = "this is fine since it's done via a static OnceLock. In the future though, separate it out."
-                        )]
-                        if let Some(new_name) = DISK_REGEX
-                            .get_or_init(|| Regex::new(r"disk\d+").unwrap())
-                            .find(checked_name)
-                        {
-                            io.get(new_name.as_str())
-                        } else {
-                            None
-                        }
-                    }
-                    #[cfg(not(target_os = "macos"))]
-                    {
-                        io.get(checked_name)
-                    }
-                };
-
-                if let Some(io_device) = io_device {
+                // On macOS, disks were already paired with the I/O of the physical disks
+                // they are on when harvested.
+                if let Some(io_device) = io.get(checked_name) {
                     let (io_r_pt, io_w_pt) = if let Some(io) = io_device {
                         (io.read_bytes, io.write_bytes)
                     } else {
                         (0, 0)
                     };
 
-                    if self.io_labels.len() <= itx {
-                        self.io_labels.push((String::default(), String::default()));
+                    if self.io_rates.len() <= itx {
+                        self.io_rates.push(None);
                     }
 
                     if self.io_labels_and_prev.len() <= itx {
+                        // There is nothing to work out a rate from yet, so this is only a
+                        // baseline.
                         self.io_labels_and_prev.push(((0, 0), (io_r_pt, io_w_pt)));
-                    }
 
-                    if let Some((io_curr, io_prev)) = self.io_labels_and_prev.get_mut(itx) {
+                        if let Some(io_rate) = self.io_rates.get_mut(itx) {
+                            *io_rate = None;
+                        }
+                    } else if let Some((io_curr, io_prev)) = self.io_labels_and_prev.get_mut(itx) {
                         let r_rate = ((io_r_pt.saturating_sub(io_prev.0)) as f64
                             / time_since_last_harvest)
                             .round() as u64;
@@ -416,21 +1005,17 @@ impl DataCollection {
                         *io_curr = (r_rate, w_rate);
                         *io_prev = (io_r_pt, io_w_pt);
 
-                        // TODO: idk why I'm generating this here tbh
-                        if let Some(io_labels) = self.io_labels.get_mut(itx) {
-                            *io_labels = (
-                                dec_bytes_per_second_string(r_rate),
-                                dec_bytes_per_second_string(w_rate),
-                            );
+                        if let Some(io_rate) = self.io_rates.get_mut(itx) {
+                            *io_rate = Some((r_rate, w_rate));
                         }
                     }
                 } else {
-                    if self.io_labels.len() <= itx {
-                        self.io_labels.push((String::default(), String::default()));
+                    if self.io_rates.len() <= itx {
+                        self.io_rates.push(None);
                     }
 
-                    if let Some(io_labels) = self.io_labels.get_mut(itx) {
-                        *io_labels = ("N/A".to_string(), "N/A".to_string());
+                    if let Some(io_rate) = self.io_rates.get_mut(itx) {
+                        *io_rate = None;
                     }
                 }
             }
@@ -440,12 +1025,25 @@ impl DataCollection {
         self.io_harvest = io;
     }
 
-    fn eat_proc(&mut self, list_of_processes: Vec<ProcessHarvest>) {
-        self.process_data.ingest(list_of_processes);
+    fn eat_proc(&mut self, list_of_processes: Vec<ProcessHarvest>, harvested_time: Instant) {
+        self.process_data.ingest(list_of_processes, harvested_time);
     }
 
     #[cfg(feature = "battery")]
-    fn eat_battery(&mut self, list_of_batteries: Vec<batteries::BatteryData>) {
+    fn eat_battery(
+        &mut self, list_of_batteries: Vec<batteries::BatteryData>, new_entry: &mut TimedData,
+    ) {
+        let len = list_of_batteries
+            .iter()
+            .map(|battery| battery.id + 1)
+            .max()
+            .unwrap_or(0);
+
+        new_entry.battery_data = vec![None; len];
+        for battery in &list_of_batteries {
+            new_entry.battery_data[battery.id] = Some(battery.charge_percent);
+        }
+
         self.battery_harvest = list_of_batteries;
     }
 
@@ -465,4 +1063,647 @@ impl DataCollection {
         });
         self.gpu_harvest = gpu;
     }
+
+    #[cfg(feature = "gpu")]
+    fn eat_gpu_util(&mut self, gpu_util: Vec<(String, f64)>, new_entry: &mut TimedData) {
+        new_entry.gpu_util_data = gpu_util.iter().map(|(_, util)| Some(*util)).collect();
+        self.gpu_util_harvest = gpu_util;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn collection_from(points: &[(u64, Option<f64>)]) -> DataCollection {
+        let start = Instant::now();
+
+        DataCollection {
+            timed_data_vec: points
+                .iter()
+                .map(|(secs, value)| {
+                    let data = TimedData {
+                        mem_data: *value,
+                        ..Default::default()
+                    };
+
+                    (start + Duration::from_secs(*secs), data)
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn merge_consecutive_points() {
+        use crate::data_collection::cpu::{CpuData, CpuDataType};
+
+        let values = [10.0, 10.0, 10.2, 10.0, 30.0, 30.0, 30.0, 12.0];
+        let eat_all = |data: &mut DataCollection, start: Instant| {
+            for (secs, value) in values.iter().enumerate() {
+                data.eat_data(Box::new(Data {
+                    collection_time: start + Duration::from_secs(secs as u64),
+                    cpu: Some(vec![CpuData {
+                        data_type: CpuDataType::Cpu(0),
+                        cpu_usage: *value,
+                    }]),
+                    ..Default::default()
+                }));
+            }
+        };
+
+        let start = Instant::now();
+        let mut data = DataCollection {
+            merge_tolerance: Some(5.0),
+            ..Default::default()
+        };
+        eat_all(&mut data, start);
+
+        // Runs collapse to their first and last points, which keep their times.
+        let stored: Vec<(u64, Option<f64>, bool)> = data
+            .timed_data_vec
+            .iter()
+            .map(|(time, timed_data)| {
+                (
+                    time.duration_since(start).as_secs(),
+                    timed_data.cpu_data[0],
+                    timed_data.merged,
+                )
+            })
+            .collect();
+        assert_eq!(
+            stored,
+            vec![
+                (0, Some(10.0), false),
+                (3, Some(10.0), true),
+                (4, Some(30.0), false),
+                (6, Some(30.0), true),
+                (7, Some(12.0), false),
+            ]
+        );
+        assert_eq!(data.current_instant, start + Duration::from_secs(7));
+
+        // The merged points are filled back in when read.
+        assert_eq!(
+            data.downsample(|d| d.cpu_data[0], 8),
+            vec![
+                Some(10.0),
+                Some(10.0),
+                Some(10.0),
+                Some(10.0),
+                Some(30.0),
+                Some(30.0),
+                Some(30.0),
+                Some(12.0)
+            ]
+        );
+
+        // Nothing is merged without a tolerance.
+        let mut data = DataCollection::default();
+        eat_all(&mut data, start);
+        assert_eq!(data.timed_data_vec.len(), values.len());
+        assert!(data.timed_data_vec.iter().all(|(_, d)| !d.merged));
+    }
+
+    #[test]
+    fn merge_tolerance_is_relative() {
+        let entry = |rx: f64, mem: f64| TimedData {
+            rx_data: Some(rx),
+            mem_data: Some(mem),
+            ..Default::default()
+        };
+
+        // Rates in bytes are compared the same way as percentages.
+        assert!(entry(1_000_000.0, 50.0).is_within(&entry(1_040_000.0, 51.0), 5.0));
+        assert!(!entry(1_000_000.0, 50.0).is_within(&entry(1_100_000.0, 50.0), 5.0));
+        assert!(!entry(0.0, 1.0).is_within(&entry(0.0, 1.1), 5.0));
+
+        // A tolerance of 0 only merges equal values.
+        assert!(entry(0.0, 1.0).is_within(&entry(0.0, 1.0), 0.0));
+        assert!(!entry(0.0, 1.0).is_within(&entry(0.0, 1.01), 0.0));
+    }
+
+    #[test]
+    fn missed_harvests_leave_gaps() {
+        let start = Instant::now();
+        let mut data = DataCollection {
+            gap_threshold: Some(Duration::from_secs(3)),
+            merge_tolerance: Some(0.0),
+            ..Default::default()
+        };
+
+        // A stall between 2s and 10s, e.g. from the system being suspended.
+        for secs in [0, 1, 2, 10, 11, 12] {
+            data.eat_data(Box::new(Data {
+                collection_time: start + Duration::from_secs(secs),
+                ..Default::default()
+            }));
+        }
+
+        // The harvest after the stall isn't merged into the run before it either.
+        let stored: Vec<(u64, bool)> = data
+            .timed_data_vec
+            .iter()
+            .map(|(time, timed_data)| (time.duration_since(start).as_secs(), timed_data.after_gap))
+            .collect();
+        assert_eq!(
+            stored,
+            vec![(0, false), (2, false), (10, true), (12, false)]
+        );
+        assert_eq!(crate::data_conversion::convert_gaps(&data), vec![-2000.0]);
+
+        // Nothing is a gap without a threshold.
+        let mut data = DataCollection::default();
+        for secs in [0, 100] {
+            data.eat_data(Box::new(Data {
+                collection_time: start + Duration::from_secs(secs),
+                ..Default::default()
+            }));
+        }
+        assert!(data.timed_data_vec.iter().all(|(_, d)| !d.after_gap));
+    }
+
+    #[test]
+    fn resuming_rebaselines_rates() {
+        let start = Instant::now();
+        let mut data = DataCollection::default();
+        let harvest = |secs: u64, written: u64, resumed: bool| {
+            Box::new(Data {
+                collection_time: start + Duration::from_secs(secs),
+                resumed,
+                disks: Some(vec![disks::DiskHarvest {
+                    name: "/dev/disk0".into(),
+                    ..Default::default()
+                }]),
+                io: Some(
+                    [(
+                        "disk0".to_string(),
+                        Some(disks::IoData {
+                            read_bytes: 0,
+                            write_bytes: written,
+                        }),
+                    )]
+                    .into_iter()
+                    .collect(),
+                ),
+                ..Default::default()
+            })
+        };
+
+        data.eat_data(harvest(1, 1000, false));
+        data.eat_data(harvest(2, 2000, false));
+        assert_eq!(data.io_rates, vec![Some((0, 1000))]);
+
+        // Lots was written while suspended, but instants only advanced by a second.
+        // The harvest is only a new baseline.
+        data.eat_data(harvest(3, 1_000_000_000, true));
+        assert_eq!(data.io_rates, vec![None]);
+
+        data.eat_data(harvest(4, 1_000_000_500, false));
+        assert_eq!(data.io_rates, vec![Some((0, 500))]);
+
+        // The harvest after the suspend is disconnected from the one before it, even
+        // though it came within the gap threshold.
+        let gaps: Vec<bool> = data
+            .timed_data_vec
+            .iter()
+            .map(|(_, timed_data)| timed_data.after_gap)
+            .collect();
+        assert_eq!(gaps, vec![false, false, true, false]);
+    }
+
+    #[test]
+    fn network_session_totals() {
+        let mut data = DataCollection::default();
+        let harvest = |rx: u64, rx_delta: u64| {
+            Box::new(Data {
+                network: Some(network::NetworkHarvest {
+                    rx,
+                    rx_delta,
+                    tx_delta: 8,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+        };
+
+        data.eat_data(harvest(100, 100));
+        data.eat_data(harvest(300, 300));
+        data.eat_data(harvest(50, 50));
+        assert_eq!(data.network_session_max, (300, 0));
+        assert_eq!(data.network_session_total, (450, 24));
+
+        data.reset();
+        assert_eq!(data.network_session_total, (0, 0));
+    }
+
+    #[test]
+    fn footprint_estimate() {
+        let mut data = DataCollection::default();
+        let empty = data.estimated_footprint(10_000, 1000);
+        assert_eq!(empty, 11 * TimedData::default().footprint() as u64);
+
+        // More retention or a faster rate means more points.
+        assert_eq!(data.estimated_footprint(20_000, 1000), 21 * empty / 11);
+        assert_eq!(data.estimated_footprint(10_000, 500), 21 * empty / 11);
+
+        // Points with more CPUs take up more space.
+        data.timed_data_vec.push((
+            Instant::now(),
+            TimedData {
+                cpu_data: vec![Some(1.0); 8],
+                ..Default::default()
+            },
+        ));
+        assert_eq!(
+            data.estimated_footprint(10_000, 1000),
+            empty + 11 * 8 * std::mem::size_of::<Option<f64>>() as u64
+        );
+    }
+
+    #[test]
+    fn clean_data_bounds_capacity() {
+        let mut data = DataCollection::default();
+        let now = Instant::now();
+        let old = now - Duration::from_secs(10);
+        let fill = |data: &mut DataCollection, old_points, new_points| {
+            data.timed_data_vec.clear();
+            data.timed_data_vec
+                .extend(std::iter::repeat((old, TimedData::default())).take(old_points));
+            data.timed_data_vec
+                .extend(std::iter::repeat((now, TimedData::default())).take(new_points));
+        };
+
+        // Dropping only a little keeps the allocation around for new data.
+        fill(&mut data, 100, 900);
+        let capacity = data.timed_data_vec.capacity();
+        data.clean_data(5000);
+        assert_eq!(data.timed_data_vec.len(), 900);
+        assert_eq!(data.timed_data_vec.capacity(), capacity);
+
+        // Dropping most of it gives the memory back.
+        for _ in 0..50 {
+            fill(&mut data, 1000, 10);
+            data.clean_data(5000);
+
+            assert_eq!(data.timed_data_vec.len(), 10);
+            assert!(data.timed_data_vec.capacity() <= TIMED_DATA_SHRINK_RATIO * 10);
+        }
+    }
+
+    #[test]
+    fn unused_network_interfaces_are_pruned() {
+        let now = Instant::now();
+        let interfaces = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| {
+                    let rx = name.len() as u64;
+                    (
+                        name.to_string(),
+                        network::NetworkHarvest {
+                            rx,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut data = DataCollection::default();
+
+        let mut old = TimedData::default();
+        data.eat_network_interfaces(interfaces(&["eth0", "veth1"]), &mut old);
+        let mut new = TimedData::default();
+        data.eat_network_interfaces(interfaces(&["eth0", "veth2"]), &mut new);
+        data.timed_data_vec = vec![(now - Duration::from_secs(10), old), (now, new)];
+        assert_eq!(data.network_interface_names, ["eth0", "veth1", "veth2"]);
+        assert_eq!(data.network_interface_session_max, [(4, 0), (5, 0), (5, 0)]);
+
+        // Only the old point has values for veth1.
+        data.clean_data(5000);
+        assert_eq!(data.network_interface_names, ["eth0", "veth2"]);
+        assert_eq!(data.network_interface_session_max, [(4, 0), (5, 0)]);
+        assert_eq!(
+            data.timed_data_vec[0].1.interface_data,
+            [Some((4.0, 0.0)), Some((5.0, 0.0))]
+        );
+    }
+
+    #[test]
+    fn time_markers_stay_sorted_and_age_out() {
+        let now = Instant::now();
+        let marker = |secs_ago| {
+            TimeMarker::new(
+                now - Duration::from_secs(secs_ago),
+                std::time::SystemTime::now(),
+            )
+        };
+        let mut data = DataCollection::default();
+
+        data.add_time_marker(marker(5));
+        data.add_time_marker(marker(20));
+        data.add_time_marker(marker(1));
+        let times = |data: &DataCollection| {
+            data.time_markers
+                .iter()
+                .map(|marker| now.duration_since(marker.time).as_secs())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(times(&data), [20, 5, 1]);
+
+        data.set_time_marker_label(now - Duration::from_secs(5), Some("deploy".into()));
+        assert_eq!(data.time_markers[1].label.as_deref(), Some("deploy"));
+
+        data.clean_data(10_000);
+        assert_eq!(times(&data), [5, 1]);
+
+        data.remove_time_marker(0);
+        data.remove_time_marker(5);
+        assert_eq!(times(&data), [1]);
+    }
+
+    #[test]
+    fn first_samples_are_only_baselines() {
+        use crate::{
+            app::AxisScaling,
+            data_collection::{
+                cpu::{CpuData, CpuDataType},
+                disks::{DiskHarvest, IoData},
+            },
+            data_conversion::get_network_points,
+            utils::data_units::DataUnit,
+        };
+
+        let start = Instant::now();
+        let disk = |read_bytes, write_bytes| {
+            (
+                Some(vec![DiskHarvest {
+                    name: "/dev/sda".to_string(),
+                    mount_point: "/".to_string(),
+                    ..Default::default()
+                }]),
+                Some(
+                    [(
+                        "sda".to_string(),
+                        Some(IoData {
+                            read_bytes,
+                            write_bytes,
+                        }),
+                    )]
+                    .into_iter()
+                    .collect(),
+                ),
+            )
+        };
+
+        // The collector leaves out network and CPU usage the first time, as there is
+        // nothing to work them out from.
+        let mut data = DataCollection::default();
+        let (disks, io) = disk(1000, 500);
+        data.eat_data(Box::new(Data {
+            collection_time: start,
+            disks,
+            io,
+            ..Default::default()
+        }));
+        assert_eq!(data.io_rates, [None]);
+
+        let (disks, io) = disk(5000, 2500);
+        data.eat_data(Box::new(Data {
+            collection_time: start + Duration::from_secs(2),
+            network: Some(network::NetworkHarvest {
+                rx: 800,
+                tx: 400,
+                ..Default::default()
+            }),
+            cpu: Some(vec![CpuData {
+                data_type: CpuDataType::Cpu(0),
+                cpu_usage: 25.0,
+            }]),
+            disks,
+            io,
+            ..Default::default()
+        }));
+
+        let (rx, tx) = get_network_points(&data, &AxisScaling::Linear, &DataUnit::Bit, false);
+        assert_eq!(rx, [(0.0, 800.0)]);
+        assert_eq!(tx, [(0.0, 400.0)]);
+
+        let cpu = data
+            .timed_data_vec
+            .iter()
+            .map(|(_, timed_data)| timed_data.cpu_data.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(cpu, [vec![], vec![Some(25.0)]]);
+
+        assert_eq!(data.io_rates, [Some((2000, 1000))]);
+    }
+
+    #[cfg(feature = "gpu")]
+    #[test]
+    fn gpu_util_series() {
+        let now = Instant::now();
+        let mut data = DataCollection::default();
+
+        for (secs_ago, util) in [(20, 10.0), (10, 50.0), (0, 90.0)] {
+            data.eat_data(Box::new(Data {
+                collection_time: now - Duration::from_secs(secs_ago),
+                gpu_util: Some(vec![
+                    ("GPU 0".to_string(), util),
+                    ("GPU 1".to_string(), 100.0 - util),
+                ]),
+                ..Default::default()
+            }));
+        }
+
+        let series = |data: &DataCollection| {
+            data.timed_data_vec
+                .iter()
+                .map(|(_, timed_data)| timed_data.gpu_util_data.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            series(&data),
+            [
+                [Some(10.0), Some(90.0)],
+                [Some(50.0), Some(50.0)],
+                [Some(90.0), Some(10.0)]
+            ]
+        );
+        assert_eq!(
+            data.gpu_util_harvest,
+            [("GPU 0".to_string(), 90.0), ("GPU 1".to_string(), 10.0)]
+        );
+
+        data.clean_data(15_000);
+        assert_eq!(
+            series(&data),
+            [[Some(50.0), Some(50.0)], [Some(90.0), Some(10.0)]]
+        );
+    }
+
+    #[test]
+    fn process_cpu_history_is_bounded() {
+        let process = |pid, cpu_usage_percent| ProcessHarvest {
+            pid,
+            cpu_usage_percent,
+            ..Default::default()
+        };
+
+        let mut data = ProcessData::default();
+        for i in 0..(PROCESS_CPU_HISTORY_LEN + 2) {
+            data.ingest(vec![process(1, i as f32), process(2, 50.0)], Instant::now());
+        }
+
+        let history = data.cpu_history.get(&1).unwrap();
+        assert_eq!(history.len(), PROCESS_CPU_HISTORY_LEN);
+        assert_eq!(history.front(), Some(&2.0));
+        assert_eq!(
+            history.back(),
+            Some(&(PROCESS_CPU_HISTORY_LEN as f32 + 1.0))
+        );
+
+        data.ingest(vec![process(1, 0.0)], Instant::now());
+        assert!(!data.cpu_history.contains_key(&2));
+    }
+
+    #[test]
+    fn last_cpu_spike() {
+        let process = |pid, start_time, cpu_usage_percent| ProcessHarvest {
+            pid,
+            start_time,
+            cpu_usage_percent,
+            ..Default::default()
+        };
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        let mut data = ProcessData::default();
+        data.ingest(vec![process(1, 0, 10.0), process(2, 0, 80.0)], at(0));
+        assert_eq!(data.spiked_ago(1, false), None);
+        assert_eq!(data.spiked_ago(2, false), Some(Duration::ZERO));
+
+        // Going idle keeps the last spike, and only a usage over the threshold
+        // counts.
+        data.ingest(vec![process(1, 0, 50.0), process(2, 0, 0.0)], at(30));
+        assert_eq!(data.spiked_ago(1, false), None);
+        assert_eq!(data.spiked_ago(2, false), Some(Duration::from_secs(30)));
+
+        data.ingest(vec![process(1, 0, 51.0), process(2, 0, 0.0)], at(90));
+        assert_eq!(data.spiked_ago(1, false), Some(Duration::ZERO));
+        assert_eq!(data.spiked_ago(2, false), Some(Duration::from_secs(90)));
+
+        // Spikes are dropped once a process exits or its PID is reused.
+        data.ingest(vec![process(2, 1, 0.0)], at(100));
+        assert!(data.last_spike.is_empty());
+        assert_eq!(data.spiked_ago(2, false), None);
+    }
+
+    #[test]
+    fn cpu_spikes_are_compared_as_shown() {
+        let process = |pid, cpu_usage_percent| ProcessHarvest {
+            pid,
+            cpu_usage_percent,
+            ..Default::default()
+        };
+        let now = Instant::now();
+
+        // Usage is stored out of 100% across all 4 cores.
+        let mut data = ProcessData {
+            num_cpus: 4,
+            ..Default::default()
+        };
+        data.ingest(vec![process(1, 20.0), process(2, 60.0)], now);
+
+        // 20% across all cores is 80% of one core.
+        assert_eq!(data.spiked_ago(1, false), None);
+        assert_eq!(data.spiked_ago(1, true), Some(Duration::ZERO));
+        assert_eq!(data.spiked_ago(2, false), Some(Duration::ZERO));
+        assert_eq!(data.spiked_ago(2, true), Some(Duration::ZERO));
+
+        // Usage is stored out of 100% per core.
+        let mut data = ProcessData {
+            num_cpus: 4,
+            unnormalized_cpu: true,
+            ..Default::default()
+        };
+        data.ingest(vec![process(1, 80.0), process(2, 240.0)], now);
+
+        assert_eq!(data.spiked_ago(1, false), None);
+        assert_eq!(data.spiked_ago(1, true), Some(Duration::ZERO));
+        assert_eq!(data.spiked_ago(2, false), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn downsample_keeps_spikes() {
+        let mut points: Vec<(u64, Option<f64>)> = (0..100).map(|t| (t, Some(10.0))).collect();
+        points[57].1 = Some(95.0);
+        points[23].1 = Some(1.0);
+        let data = collection_from(&points);
+
+        let downsampled = data.downsample(|d| d.mem_data, 10);
+
+        assert_eq!(downsampled.len(), 10);
+        assert!(downsampled.contains(&Some(95.0)));
+        assert!(downsampled.contains(&Some(1.0)));
+        assert!(downsampled.iter().all(Option::is_some));
+    }
+
+    #[test]
+    fn downsample_gaps_are_none() {
+        let mut points: Vec<(u64, Option<f64>)> = (0..40).map(|t| (t, Some(50.0))).collect();
+        points.extend((80..100).map(|t| (t, Some(20.0))));
+        points[10].1 = None;
+        let data = collection_from(&points);
+
+        let downsampled = data.downsample(|d| d.mem_data, 10);
+
+        assert_eq!(downsampled.len(), 10);
+        assert!(downsampled[..4].iter().all(|v| *v == Some(50.0)));
+        assert!(downsampled[4..8].iter().all(Option::is_none));
+        assert!(downsampled[8..].iter().all(|v| *v == Some(20.0)));
+    }
+
+    #[test]
+    fn downsample_empty() {
+        let data = DataCollection::default();
+        assert!(data.downsample(|d| d.mem_data, 10).is_empty());
+
+        let data = collection_from(&[(0, Some(1.0))]);
+        assert!(data.downsample(|d| d.mem_data, 0).is_empty());
+        assert_eq!(
+            data.downsample(|d| d.mem_data, 3),
+            vec![Some(1.0), None, None]
+        );
+    }
+
+    #[test]
+    fn temp_stats_follow_sensor_names() {
+        let mut data = DataCollection::default();
+        let sensor = |name: &str, temperature: Option<f32>| temperature::TempHarvest {
+            name: name.to_string(),
+            temperature,
+        };
+
+        data.eat_temp(vec![sensor("cpu", Some(50.0)), sensor("gpu", Some(40.0))]);
+        assert_eq!(data.temp_stats["cpu"].trend(), None);
+
+        // The order of the sensors can change between harvests.
+        data.eat_temp(vec![sensor("gpu", Some(35.5)), sensor("cpu", Some(61.0))]);
+        data.eat_temp(vec![sensor("gpu", None), sensor("cpu", Some(60.5))]);
+
+        let cpu = data.temp_stats["cpu"];
+        assert_eq!((cpu.min, cpu.max, cpu.last), (50.0, 61.0, 60.5));
+        assert_eq!(cpu.trend(), Some(Ordering::Equal));
+
+        // Sensors without a reading keep their last stats.
+        let gpu = data.temp_stats["gpu"];
+        assert_eq!((gpu.min, gpu.max, gpu.last), (35.5, 40.0, 35.5));
+        assert_eq!(gpu.trend(), Some(Ordering::Less));
+
+        data.reset();
+        assert!(data.temp_stats.is_empty());
+    }
 }
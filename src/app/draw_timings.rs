@@ -0,0 +1,166 @@
+//! How long each widget takes to draw, to help find which widget is making
+//! the UI slow.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use hashbrown::HashMap;
+
+use super::layout_manager::BottomWidgetType;
+
+/// How many of a widget's latest draws its average is taken over.
+const DRAW_SAMPLES: usize = 16;
+
+/// The latest draw times of a single widget.
+#[derive(Debug)]
+pub struct WidgetDrawTime {
+    pub widget_type: BottomWidgetType,
+    samples: VecDeque<Duration>,
+}
+
+impl WidgetDrawTime {
+    /// The average of the latest draw times.
+    pub fn average(&self) -> Duration {
+        let total: Duration = self.samples.iter().sum();
+        total / self.samples.len().max(1) as u32
+    }
+}
+
+/// Draw times for each widget. These are only kept while they can be seen,
+/// so drawing stays as cheap as possible otherwise.
+#[derive(Debug, Default)]
+pub struct DrawTimings {
+    /// Whether to show the slowest widget's draw time over that widget.
+    show_slowest: bool,
+    is_overlay_open: bool,
+    widgets: HashMap<u64, WidgetDrawTime>,
+}
+
+impl DrawTimings {
+    pub fn new(show_slowest: bool) -> Self {
+        Self {
+            show_slowest,
+            ..Default::default()
+        }
+    }
+
+    /// Whether draw times are being kept.
+    pub fn is_enabled(&self) -> bool {
+        self.show_slowest || self.is_overlay_open
+    }
+
+    /// Whether the slowest widget's draw time is shown over that widget.
+    pub fn show_slowest(&self) -> bool {
+        self.show_slowest
+    }
+
+    pub fn is_overlay_open(&self) -> bool {
+        self.is_overlay_open
+    }
+
+    pub fn toggle_overlay(&mut self) {
+        self.is_overlay_open = !self.is_overlay_open;
+    }
+
+    /// Returns when a draw started, if draw times are being kept.
+    pub fn start(&self) -> Option<Instant> {
+        self.is_enabled().then(Instant::now)
+    }
+
+    /// Records how long a widget took to draw.
+    pub fn record(&mut self, widget_id: u64, widget_type: &BottomWidgetType, elapsed: Duration) {
+        let widget = self
+            .widgets
+            .entry(widget_id)
+            .or_insert_with(|| WidgetDrawTime {
+                widget_type: widget_type.clone(),
+                samples: VecDeque::with_capacity(DRAW_SAMPLES),
+            });
+
+        if widget.samples.len() == DRAW_SAMPLES {
+            widget.samples.pop_front();
+        }
+        widget.samples.push_back(elapsed);
+    }
+
+    /// Forgets all draw times, such as when the widgets being drawn change.
+    pub fn clear(&mut self) {
+        self.widgets.clear();
+    }
+
+    /// Returns each widget's ID and draw times, slowest first.
+    pub fn slowest_first(&self) -> Vec<(u64, &WidgetDrawTime)> {
+        let mut widgets = self
+            .widgets
+            .iter()
+            .map(|(id, widget)| (*id, widget))
+            .collect::<Vec<_>>();
+        widgets.sort_by(|(a_id, a), (b_id, b)| b.average().cmp(&a.average()).then(a_id.cmp(b_id)));
+
+        widgets
+    }
+
+    /// Returns the ID and average draw time of the slowest widget.
+    pub fn slowest(&self) -> Option<(u64, Duration)> {
+        self.slowest_first()
+            .first()
+            .map(|(id, widget)| (*id, widget.average()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn only_kept_when_shown() {
+        let mut timings = DrawTimings::default();
+        assert!(timings.start().is_none());
+
+        timings.toggle_overlay();
+        assert!(timings.start().is_some());
+        timings.toggle_overlay();
+        assert!(timings.start().is_none());
+
+        assert!(DrawTimings::new(true).start().is_some());
+    }
+
+    #[test]
+    fn rolling_average() {
+        let mut timings = DrawTimings::default();
+        timings.record(1, &BottomWidgetType::Cpu, ms(10));
+        timings.record(1, &BottomWidgetType::Cpu, ms(20));
+        assert_eq!(timings.slowest(), Some((1, ms(15))));
+
+        // Older draws stop counting.
+        for _ in 0..DRAW_SAMPLES {
+            timings.record(1, &BottomWidgetType::Cpu, ms(2));
+        }
+        assert_eq!(timings.slowest(), Some((1, ms(2))));
+    }
+
+    #[test]
+    fn sorted_slowest_first() {
+        let mut timings = DrawTimings::default();
+        timings.record(1, &BottomWidgetType::Cpu, ms(1));
+        timings.record(2, &BottomWidgetType::Proc, ms(5));
+        timings.record(3, &BottomWidgetType::Disk, ms(3));
+
+        let ids = timings
+            .slowest_first()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [2, 3, 1]);
+        assert_eq!(timings.slowest(), Some((2, ms(5))));
+
+        timings.clear();
+        assert_eq!(timings.slowest(), None);
+    }
+}
@@ -42,6 +42,12 @@ impl Filter {
         self.list.iter().any(|regex| regex.is_match(value))
     }
 
+    /// Whether the filter has nothing to match against.
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
     /// Whether entries matching the list should be ignored or kept.
     #[inline]
     pub(crate) fn ignore_matches(&self) -> bool {
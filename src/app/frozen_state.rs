@@ -1,11 +1,19 @@
+use hashbrown::HashMap;
+
 use super::DataCollection;
+use crate::data_collection::processes::{Pid, ProcessHarvest};
+
+/// How many updates a process from the snapshot is still shown for after it
+/// has exited, when showing changes since a freeze.
+pub const EXITED_PROCESS_UPDATES: usize = 3;
 
 /// The [`FrozenState`] indicates whether the application state should be
-/// frozen. It is either not frozen or frozen and containing a copy of the state
-/// at the time.
+/// frozen. It is either not frozen, frozen and containing a copy of the state
+/// at the time, or updating while comparing processes against such a copy.
 pub enum FrozenState {
     NotFrozen,
     Frozen(Box<DataCollection>),
+    Diff(Box<DiffBaseline>),
 }
 
 impl Default for FrozenState {
@@ -17,7 +25,8 @@ impl Default for FrozenState {
 pub type IsFrozen = bool;
 
 impl FrozenState {
-    /// Checks whether the [`FrozenState`] is currently frozen.
+    /// Checks whether the [`FrozenState`] is currently frozen. Showing changes
+    /// since a freeze doesn't count, as the data still updates.
     pub fn is_frozen(&self) -> IsFrozen {
         matches!(self, FrozenState::Frozen(_))
     }
@@ -32,15 +41,229 @@ impl FrozenState {
         *self = FrozenState::NotFrozen;
     }
 
-    /// Toggles the [`FrozenState`] and returns whether it is now frozen.
+    /// Toggles the [`FrozenState`] and returns whether it is now frozen. This
+    /// also stops showing changes since a freeze.
     pub fn toggle(&mut self, data: &DataCollection) -> IsFrozen {
-        if self.is_frozen() {
-            self.thaw();
-            false
-        } else {
+        if let FrozenState::NotFrozen = self {
             // Could we use an Arc instead? Is it worth it?
             self.freeze(Box::new(data.clone()));
             true
+        } else {
+            self.thaw();
+            false
+        }
+    }
+
+    /// Toggles showing changes since a freeze, and returns whether they are now
+    /// shown. If already frozen, changes are shown against that copy of the
+    /// state, and otherwise against a copy of the current state.
+    pub fn toggle_diff(&mut self, data: &DataCollection) -> bool {
+        match std::mem::take(self) {
+            FrozenState::NotFrozen => {
+                *self = FrozenState::Diff(Box::new(DiffBaseline::new(data.clone())));
+                true
+            }
+            FrozenState::Frozen(snapshot) => {
+                *self = FrozenState::Diff(Box::new(DiffBaseline::new(*snapshot)));
+                true
+            }
+            FrozenState::Diff(_) => false,
+        }
+    }
+
+    /// Returns the text shown at the bottom of the screen to say that the data
+    /// is frozen, if it is.
+    pub fn status_text(&self) -> Option<&'static str> {
+        match self {
+            FrozenState::NotFrozen => None,
+            FrozenState::Frozen(_) => Some("Frozen, press 'f' to unfreeze"),
+            FrozenState::Diff(_) => Some("Showing changes since freezing, press 'F' to stop"),
+        }
+    }
+
+    /// Returns the data to display, which is the frozen copy if frozen, and
+    /// `live` otherwise.
+    pub fn data_source<'a>(&'a self, live: &'a DataCollection) -> &'a DataCollection {
+        match self {
+            FrozenState::Frozen(data) => data,
+            FrozenState::NotFrozen | FrozenState::Diff(_) => live,
+        }
+    }
+
+    /// Returns what processes are compared against, if changes since a freeze
+    /// are being shown.
+    pub fn diff_baseline(&self) -> Option<&DiffBaseline> {
+        match self {
+            FrozenState::Diff(baseline) => Some(baseline),
+            FrozenState::NotFrozen | FrozenState::Frozen(_) => None,
+        }
+    }
+
+    /// Updates how long processes from the snapshot have been gone for. This
+    /// should be called each time new data comes in.
+    pub fn on_update(&mut self, live: &DataCollection) {
+        if let FrozenState::Diff(baseline) = self {
+            baseline.on_update(live);
+        }
+    }
+}
+
+/// A copy of the state to compare processes against, along with how long
+/// each process in it has been gone for.
+pub struct DiffBaseline {
+    snapshot: DataCollection,
+
+    /// How many updates each process from the snapshot has been gone for,
+    /// by PID. Processes that are still running aren't listed.
+    exited_updates: HashMap<Pid, usize>,
+}
+
+impl DiffBaseline {
+    fn new(snapshot: DataCollection) -> Self {
+        Self {
+            snapshot,
+            exited_updates: HashMap::default(),
+        }
+    }
+
+    /// Returns the process in the snapshot that is the same as `process`. A
+    /// process with the same PID but a different start time is a different
+    /// process that reused the PID.
+    pub fn matching(&self, process: &ProcessHarvest) -> Option<&ProcessHarvest> {
+        self.snapshot
+            .process_data
+            .process_harvest
+            .get(&process.pid)
+            .filter(|old| old.start_time == process.start_time)
+    }
+
+    /// Returns the processes in the snapshot that have exited, but only
+    /// recently enough that they should still be shown.
+    pub fn recently_exited(&self) -> impl Iterator<Item = &ProcessHarvest> {
+        self.snapshot
+            .process_data
+            .process_harvest
+            .values()
+            .filter(|process| {
+                self.exited_updates
+                    .get(&process.pid)
+                    .is_some_and(|updates| *updates <= EXITED_PROCESS_UPDATES)
+            })
+    }
+
+    /// The copy of the state that processes are compared against.
+    pub fn data(&self) -> &DataCollection {
+        &self.snapshot
+    }
+
+    fn on_update(&mut self, live: &DataCollection) {
+        let live = &live.process_data.process_harvest;
+
+        for process in self.snapshot.process_data.process_harvest.values() {
+            let is_running = live
+                .get(&process.pid)
+                .is_some_and(|current| current.start_time == process.start_time);
+
+            if is_running {
+                self.exited_updates.remove(&process.pid);
+            } else {
+                *self.exited_updates.entry(process.pid).or_default() += 1;
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn data(processes: &[(Pid, u64)]) -> DataCollection {
+        let mut data = DataCollection::default();
+        data.process_data.process_harvest = processes
+            .iter()
+            .map(|&(pid, start_time)| {
+                (
+                    pid,
+                    ProcessHarvest {
+                        pid,
+                        start_time,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+
+        data
+    }
+
+    fn exited(baseline: &DiffBaseline) -> Vec<Pid> {
+        let mut pids = baseline
+            .recently_exited()
+            .map(|process| process.pid)
+            .collect::<Vec<_>>();
+        pids.sort_unstable();
+        pids
+    }
+
+    #[test]
+    fn cycling_modes() {
+        let live = data(&[(1, 0)]);
+        let mut state = FrozenState::default();
+
+        assert!(state.toggle_diff(&live));
+        assert!(!state.is_frozen());
+        assert!(state.diff_baseline().is_some());
+        assert!(!state.toggle_diff(&live));
+        assert!(state.diff_baseline().is_none());
+
+        // Showing changes from a freeze keeps what was frozen.
+        assert!(state.toggle(&live));
+        assert!(state.toggle_diff(&data(&[(2, 0)])));
+        let baseline = state.diff_baseline().unwrap();
+        assert!(baseline
+            .data()
+            .process_data
+            .process_harvest
+            .contains_key(&1));
+
+        // Unfreezing also stops showing changes.
+        assert!(!state.toggle(&live));
+        assert!(state.diff_baseline().is_none());
+    }
+
+    #[test]
+    fn matching_processes() {
+        let baseline = DiffBaseline::new(data(&[(1, 100), (2, 200)]));
+
+        let same = ProcessHarvest {
+            pid: 1,
+            start_time: 100,
+            ..Default::default()
+        };
+        assert_eq!(baseline.matching(&same).map(|p| p.pid), Some(1));
+
+        // A reused PID is a different process.
+        let reused = ProcessHarvest {
+            pid: 2,
+            start_time: 300,
+            ..Default::default()
+        };
+        assert!(baseline.matching(&reused).is_none());
+    }
+
+    #[test]
+    fn exited_processes_are_shown_for_a_while() {
+        let mut baseline = DiffBaseline::new(data(&[(1, 100), (2, 200), (3, 300)]));
+        assert!(exited(&baseline).is_empty());
+
+        // 2 exits, and 3's PID is reused.
+        let live = data(&[(1, 100), (3, 301)]);
+        for _ in 0..EXITED_PROCESS_UPDATES {
+            baseline.on_update(&live);
+            assert_eq!(exited(&baseline), [2, 3]);
+        }
+
+        baseline.on_update(&live);
+        assert!(exited(&baseline).is_empty());
+    }
+}
@@ -0,0 +1,324 @@
+//! A palette to search for an entry across all table widgets, and to jump to
+//! the widget showing it.
+
+use std::time::Duration;
+
+use super::{layout_manager::BottomWidgetType, list_selection::ListSelection};
+use crate::data_collection::processes::Pid;
+
+/// How long a jumped-to entry stays highlighted for.
+pub const JUMP_HIGHLIGHT_DURATION: Duration = Duration::from_millis(1500);
+
+/// Identifies an entry within its widget, independent of where the entry
+/// currently is in the widget's (possibly sorted) table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JumpTarget {
+    Process(Pid),
+    /// A disk, by its name and mount point. Both are needed as a disk can be
+    /// mounted in more than one place, and ZFS pools aren't mounted at all.
+    Disk(String, String),
+    /// A temperature sensor, by its name.
+    Temp(String),
+    /// A battery, by its ID.
+    Battery(usize),
+}
+
+/// An entry that can be jumped to.
+#[derive(Debug, Clone)]
+pub struct JumpEntry {
+    pub widget_id: u64,
+    pub widget_type: BottomWidgetType,
+    pub label: String,
+    pub target: JumpTarget,
+}
+
+/// The state of the jump palette.
+#[derive(Debug, Default)]
+pub struct JumpPalette {
+    is_open: bool,
+    query: String,
+    entries: Vec<JumpEntry>,
+
+    /// Indices into `entries` that match the query, grouped by widget.
+    results: Vec<usize>,
+
+    /// The selected result.
+    selected: ListSelection,
+}
+
+impl JumpPalette {
+    /// Opens the palette with a fresh set of entries, in the order their
+    /// widgets should be listed in.
+    pub fn open(&mut self, entries: Vec<JumpEntry>) {
+        self.is_open = true;
+        self.query.clear();
+        self.entries = entries;
+        self.update_results();
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.query.clear();
+        self.entries.clear();
+        self.results.clear();
+        self.selected = ListSelection::default();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+        self.update_results();
+    }
+
+    pub fn pop_char(&mut self) {
+        self.query.pop();
+        self.update_results();
+    }
+
+    pub fn move_selection(&mut self, change: i64) {
+        self.selected.move_by(change, self.results.len());
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected.index()
+    }
+
+    pub fn selected_entry(&self) -> Option<&JumpEntry> {
+        self.results
+            .get(self.selected.index())
+            .and_then(|&index| self.entries.get(index))
+    }
+
+    /// The entries matching the query, grouped by widget.
+    pub fn results(&self) -> impl Iterator<Item = &JumpEntry> {
+        self.results.iter().map(|&index| &self.entries[index])
+    }
+
+    fn update_results(&mut self) {
+        let mut scored = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                fuzzy_score(&self.query, &entry.label).map(|score| (index, score))
+            })
+            .collect::<Vec<_>>();
+
+        // Entries are already grouped by widget, so sort by the position of each
+        // group first to keep them together.
+        let group_start = |index: usize| {
+            let widget_id = self.entries[index].widget_id;
+            self.entries
+                .iter()
+                .position(|entry| entry.widget_id == widget_id)
+                .unwrap_or(index)
+        };
+        scored.sort_by_key(|&(index, score)| (group_start(index), std::cmp::Reverse(score)));
+
+        self.results = scored.into_iter().map(|(index, _)| index).collect();
+        self.selected = ListSelection::default();
+    }
+}
+
+/// Scores how well a query fuzzily matches some text, where the query's
+/// characters must all appear in order. Higher is better; [`None`] means no
+/// match. Matching is case-insensitive.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let mut score = 0;
+    let mut text_chars = text.chars().flat_map(char::to_lowercase).enumerate();
+    let mut last_match: Option<usize> = None;
+
+    for query_char in query.chars().flat_map(char::to_lowercase) {
+        let (index, _) = text_chars.find(|(_, c)| *c == query_char)?;
+
+        score += 1;
+        if index == 0 {
+            score += 3;
+        }
+        if last_match.is_some_and(|last| last + 1 == index) {
+            score += 2;
+        }
+        last_match = Some(index);
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::*;
+    use crate::{
+        app::App,
+        args::BottomArgs,
+        canvas::components::data_table::SortOrder,
+        options::{config::Config, init_app},
+        widgets::DiskWidgetData,
+    };
+
+    fn entry(widget_id: u64, label: &str) -> JumpEntry {
+        JumpEntry {
+            widget_id,
+            widget_type: BottomWidgetType::Temp,
+            label: label.to_string(),
+            target: JumpTarget::Temp(label.to_string()),
+        }
+    }
+
+    #[test]
+    fn fuzzy_matching() {
+        assert!(fuzzy_score("nvme", "nvme Composite").is_some());
+        assert!(fuzzy_score("NVC", "nvme Composite").is_some());
+        assert!(fuzzy_score("nvx", "nvme Composite").is_none());
+        assert!(fuzzy_score("mn", "nvme").is_none());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+
+        // Contiguous and prefix matches score higher.
+        assert!(fuzzy_score("cpu", "cpu0").unwrap() > fuzzy_score("cpu", "c_p_u").unwrap());
+        assert!(fuzzy_score("cp", "cpu").unwrap() > fuzzy_score("cp", "acpi").unwrap());
+    }
+
+    #[test]
+    fn results_stay_grouped() {
+        let mut palette = JumpPalette::default();
+        palette.open(vec![
+            entry(1, "acpitz"),
+            entry(1, "nvme Composite"),
+            entry(2, "nvme0n1"),
+            entry(2, "sda"),
+            entry(3, "nvme"),
+        ]);
+        assert_eq!(palette.results().count(), 5);
+
+        for c in "nvme".chars() {
+            palette.push_char(c);
+        }
+        let labels = palette
+            .results()
+            .map(|entry| entry.label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(labels, vec!["nvme Composite", "nvme0n1", "nvme"]);
+
+        palette.move_selection(5);
+        assert_eq!(palette.selected_entry().unwrap().label, "nvme");
+        palette.move_selection(-1);
+        assert_eq!(palette.selected_entry().unwrap().widget_id, 2);
+
+        // Changing the query resets the selection.
+        palette.pop_char();
+        assert_eq!(palette.selected_index(), 0);
+
+        palette.close();
+        assert!(!palette.is_open());
+        assert!(palette.selected_entry().is_none());
+    }
+
+    fn disk(name: String, mount_point: String) -> DiskWidgetData {
+        DiskWidgetData {
+            name: name.into(),
+            mount_point: mount_point.into(),
+            name_alias: None,
+            mount_alias: None,
+            file_system: "ext4".into(),
+            free_bytes: None,
+            used_bytes: None,
+            total_bytes: None,
+            summed_total_bytes: None,
+            io_read_rate: None,
+            io_write_rate: None,
+            pool: None,
+            compact_bytes: false,
+        }
+    }
+
+    fn disk_widget_id(app: &App) -> u64 {
+        app.widget_map
+            .values()
+            .find(|widget| widget.widget_type == BottomWidgetType::Disk)
+            .map(|widget| widget.widget_id)
+            .unwrap()
+    }
+
+    #[test]
+    fn jump_lands_on_sorted_and_scrolled_row() {
+        let (mut app, _, _) = init_app(BottomArgs::parse_from(["btm"]), Config::default()).unwrap();
+        let disk_id = disk_widget_id(&app);
+        assert_ne!(app.current_widget.widget_id, disk_id);
+
+        let data = (0..30)
+            .map(|i| disk(format!("sda{i:02}"), format!("/mnt/disk{i:02}")))
+            .collect::<Vec<_>>();
+
+        // Sort by mount point in reverse, and scroll away from the entry.
+        let disk = app.states.disk_state.get_mut_widget_state(disk_id).unwrap();
+        disk.table.set_sort_index(1);
+        disk.table.set_order(SortOrder::Descending);
+        disk.set_table_data(&data);
+        disk.table.set_position(5);
+        disk.table.state.display_start_index = 2;
+
+        app.open_jump_palette();
+        for c in "disk03".chars() {
+            app.jump_palette.push_char(c);
+        }
+        assert_eq!(
+            app.jump_palette.selected_entry().unwrap().target,
+            JumpTarget::Disk("sda03".to_string(), "/mnt/disk03".to_string())
+        );
+
+        app.jump_to_selected();
+        assert!(!app.jump_palette.is_open());
+        assert_eq!(app.current_widget.widget_id, disk_id);
+
+        let disk = app.states.disk_state.get_mut_widget_state(disk_id).unwrap();
+        assert_eq!(disk.table.current_index(), 26);
+        assert_eq!(
+            disk.table.current_item().unwrap().mount_point,
+            "/mnt/disk03"
+        );
+        assert!(disk.table.state.highlight_until.is_some());
+    }
+
+    #[test]
+    fn jump_to_unmounted_pool() {
+        let (mut app, _, _) = init_app(BottomArgs::parse_from(["btm"]), Config::default()).unwrap();
+        let disk_id = disk_widget_id(&app);
+
+        // ZFS pools all have an empty mount point.
+        let data = ["boot", "tank"]
+            .map(|name| disk(name.to_string(), String::new()))
+            .to_vec();
+        let disk = app.states.disk_state.get_mut_widget_state(disk_id).unwrap();
+        disk.set_table_data(&data);
+
+        app.open_jump_palette();
+        for c in "tank".chars() {
+            app.jump_palette.push_char(c);
+        }
+        app.jump_to_selected();
+
+        let disk = app.states.disk_state.get_mut_widget_state(disk_id).unwrap();
+        assert_eq!(disk.table.current_item().unwrap().name, "tank");
+    }
+
+    #[test]
+    fn esc_keeps_focus() {
+        let (mut app, _, _) = init_app(BottomArgs::parse_from(["btm"]), Config::default()).unwrap();
+        let widget_id = app.current_widget.widget_id;
+
+        app.open_jump_palette();
+        app.jump_palette.push_char('a');
+        app.close_jump_palette();
+
+        assert!(!app.jump_palette.is_open());
+        assert_eq!(app.current_widget.widget_id, widget_id);
+    }
+}
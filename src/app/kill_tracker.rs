@@ -0,0 +1,209 @@
+//! Tracks processes that were sent a kill signal, so we can tell the user if
+//! they don't actually go away (e.g. a process stuck in uninterruptible sleep).
+
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use crate::data_collection::processes::{Pid, ProcessHarvest};
+
+/// How many harvests a killed process can stick around for before we notify
+/// the user about it.
+pub const KILL_TIMEOUT_TICKS: u32 = 3;
+
+/// A process that was sent a signal, and which we are waiting on to exit.
+#[derive(Debug, Clone)]
+pub struct PendingKill {
+    pub pid: Pid,
+
+    /// The signal that was sent, if any. This is only set on Unix-like systems.
+    pub signal: Option<usize>,
+
+    /// The process' uptime when the signal was sent. If the PID later shows up
+    /// with a lower uptime, it was reused by a different process.
+    time: Duration,
+
+    /// When the signal was sent.
+    sent_at: Instant,
+
+    /// How many harvests the process has been seen in since the signal was sent.
+    ticks: u32,
+
+    /// The last known process state character, e.g. 'D'.
+    state: char,
+}
+
+impl PendingKill {
+    /// Whether this process has outlived [`KILL_TIMEOUT_TICKS`].
+    pub fn is_stuck(&self) -> bool {
+        self.ticks >= KILL_TIMEOUT_TICKS
+    }
+
+    /// Returns a notice describing this process, given the current time.
+    pub fn notice(&self, now: Instant) -> String {
+        let elapsed = now.saturating_duration_since(self.sent_at).as_secs();
+        let sent = match self.signal {
+            Some(signal) => signal_name(signal),
+            None => "termination".to_string(),
+        };
+
+        format!(
+            "PID {} still running {elapsed}s after {sent} (state {})",
+            self.pid, self.state
+        )
+    }
+}
+
+/// Returns a name for a signal, e.g. "SIGTERM".
+fn signal_name(signal: usize) -> String {
+    match signal {
+        1 => "SIGHUP".to_string(),
+        2 => "SIGINT".to_string(),
+        3 => "SIGQUIT".to_string(),
+        9 => "SIGKILL".to_string(),
+        15 => "SIGTERM".to_string(),
+        _ => format!("signal {signal}"),
+    }
+}
+
+/// Keeps track of in-flight kills.
+#[derive(Debug, Default)]
+pub struct KillTracker {
+    pending: Vec<PendingKill>,
+}
+
+impl KillTracker {
+    /// Starts tracking a process that was just sent a signal. If the process is
+    /// already being tracked, it is reset.
+    pub fn track(&mut self, process: &ProcessHarvest, signal: Option<usize>, now: Instant) {
+        self.pending.retain(|pending| pending.pid != process.pid);
+        self.pending.push(PendingKill {
+            pid: process.pid,
+            signal,
+            time: process.time,
+            sent_at: now,
+            ticks: 0,
+            state: process.process_state.1,
+        });
+    }
+
+    /// Updates the tracked processes with a new harvest, removing any that have
+    /// exited.
+    pub fn update(&mut self, processes: &BTreeMap<Pid, ProcessHarvest>) {
+        self.pending
+            .retain_mut(|pending| match processes.get(&pending.pid) {
+                Some(process) if process.time >= pending.time => {
+                    pending.ticks += 1;
+                    pending.state = process.process_state.1;
+                    true
+                }
+                _ => false,
+            });
+    }
+
+    /// Returns the tracked processes that haven't exited in time.
+    pub fn stuck(&self) -> impl Iterator<Item = &PendingKill> {
+        self.pending.iter().filter(|pending| pending.is_stuck())
+    }
+
+    /// Stops tracking all stuck processes, returning their PIDs.
+    pub fn take_stuck(&mut self) -> Vec<Pid> {
+        let stuck = self.stuck().map(|pending| pending.pid).collect();
+        self.pending.retain(|pending| !pending.is_stuck());
+
+        stuck
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn process(pid: Pid, secs: u64, state: char) -> ProcessHarvest {
+        ProcessHarvest {
+            pid,
+            time: Duration::from_secs(secs),
+            process_state: ("".to_string(), state),
+            ..Default::default()
+        }
+    }
+
+    fn harvest(processes: &[ProcessHarvest]) -> BTreeMap<Pid, ProcessHarvest> {
+        processes.iter().map(|p| (p.pid, p.clone())).collect()
+    }
+
+    #[test]
+    fn exited_processes_are_removed() {
+        let mut tracker = KillTracker::default();
+        tracker.track(&process(1, 10, 'S'), Some(15), Instant::now());
+        tracker.track(&process(2, 10, 'S'), Some(15), Instant::now());
+
+        tracker.update(&harvest(&[process(2, 11, 'S')]));
+        assert_eq!(tracker.pending.len(), 1);
+        assert_eq!(tracker.pending[0].pid, 2);
+
+        tracker.update(&harvest(&[]));
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn stuck_after_timeout() {
+        let start = Instant::now();
+        let mut tracker = KillTracker::default();
+        tracker.track(&process(1234, 10, 'S'), Some(15), start);
+        tracker.track(&process(5, 10, 'S'), Some(15), start);
+
+        for tick in 1..=KILL_TIMEOUT_TICKS {
+            assert_eq!(tracker.stuck().count(), 0);
+            tracker.update(&harvest(&[
+                process(1234, 10 + tick as u64, 'D'),
+                process(5, 10 + tick as u64, 'S'),
+            ]));
+        }
+
+        let stuck = tracker.stuck().map(|p| p.pid).collect::<Vec<_>>();
+        assert_eq!(stuck, vec![1234, 5]);
+
+        let notice = tracker
+            .stuck()
+            .next()
+            .unwrap()
+            .notice(start + Duration::from_secs(3));
+        assert_eq!(notice, "PID 1234 still running 3s after SIGTERM (state D)");
+
+        // The second one exits, so only the first should still be stuck.
+        tracker.update(&harvest(&[process(1234, 14, 'D')]));
+        assert_eq!(tracker.take_stuck(), vec![1234]);
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn reused_pid_is_not_the_same_process() {
+        let mut tracker = KillTracker::default();
+        tracker.track(&process(1, 100, 'S'), Some(15), Instant::now());
+
+        // Same PID, but it has only been running for a second, so it's a
+        // different process.
+        tracker.update(&harvest(&[process(1, 1, 'R')]));
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn retracking_resets() {
+        let mut tracker = KillTracker::default();
+        tracker.track(&process(1, 10, 'D'), Some(15), Instant::now());
+        for tick in 1..=KILL_TIMEOUT_TICKS {
+            tracker.update(&harvest(&[process(1, 10 + tick as u64, 'D')]));
+        }
+        assert_eq!(tracker.stuck().count(), 1);
+
+        tracker.track(&process(1, 13, 'D'), Some(9), Instant::now());
+        assert_eq!(tracker.pending.len(), 1);
+        assert_eq!(tracker.stuck().count(), 0);
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::{cmp::Reverse, collections::BTreeMap};
 
 use crate::{constants::DEFAULT_WIDGET_ID, options::OptionError};
 
@@ -531,6 +531,15 @@ impl BottomLayout {
         }
     }
 
+    /// Returns all widgets in the layout.
+    pub fn widgets(&self) -> impl Iterator<Item = &BottomWidget> {
+        self.rows
+            .iter()
+            .flat_map(|row| &row.children)
+            .flat_map(|col| &col.children)
+            .flat_map(|col_row| &col_row.children)
+    }
+
     pub fn init_basic_default(use_battery: bool) -> Self {
         let table_widgets = if use_battery {
             let disk_widget = BottomWidget::new(BottomWidgetType::Disk, 4)
@@ -685,6 +694,122 @@ impl BottomLayout {
     }
 }
 
+/// Pairs up the widgets of two layouts that show the same thing, in the order
+/// they are laid out, such as the first process widget of each or a CPU widget
+/// and a basic CPU widget. Returns the IDs of each pair, from `from` to `to`.
+pub fn equivalent_widgets(from: &BottomLayout, to: &BottomLayout) -> Vec<(u64, u64)> {
+    fn kind(widget_type: &BottomWidgetType) -> BottomWidgetType {
+        match widget_type {
+            BottomWidgetType::BasicCpu => BottomWidgetType::Cpu,
+            BottomWidgetType::BasicMem => BottomWidgetType::Mem,
+            BottomWidgetType::BasicNet => BottomWidgetType::Net,
+            other => other.clone(),
+        }
+    }
+
+    let mut unpaired = to.widgets().collect::<Vec<_>>();
+    from.widgets()
+        .filter_map(|widget| {
+            let position = unpaired
+                .iter()
+                .position(|other| kind(&other.widget_type) == kind(&widget.widget_type))?;
+
+            Some((widget.widget_id, unpaired.remove(position).widget_id))
+        })
+        .collect()
+}
+
+/// A layout, along with the smallest terminal size it is used for.
+#[derive(Clone, Debug)]
+pub struct LayoutProfile {
+    pub layout: BottomLayout,
+    pub default_widget_id: u64,
+    pub min_width: Option<u16>,
+    pub min_height: Option<u16>,
+}
+
+impl LayoutProfile {
+    /// Creates a [`LayoutProfile`] that is used for any terminal size.
+    pub fn new(layout: BottomLayout, default_widget_id: u64) -> Self {
+        Self {
+            layout,
+            default_widget_id,
+            min_width: None,
+            min_height: None,
+        }
+    }
+
+    /// Returns whether this is used for any terminal size.
+    pub fn is_unconditional(&self) -> bool {
+        self.min_width.is_none() && self.min_height.is_none()
+    }
+
+    /// Returns whether this can be used for a terminal of the given size.
+    pub fn fits(&self, width: u16, height: u16) -> bool {
+        self.min_width.map_or(true, |min_width| width >= min_width)
+            && self
+                .min_height
+                .map_or(true, |min_height| height >= min_height)
+    }
+}
+
+/// The layouts to pick from based on the terminal size.
+#[derive(Clone, Debug)]
+pub struct LayoutProfiles {
+    profiles: Vec<LayoutProfile>,
+    current: usize,
+}
+
+impl LayoutProfiles {
+    /// Creates a [`LayoutProfiles`], starting with the first layout that is
+    /// used for any terminal size. Returns [`None`] if there is no such layout.
+    pub fn new(profiles: Vec<LayoutProfile>) -> Option<Self> {
+        let current = profiles.iter().position(LayoutProfile::is_unconditional)?;
+
+        Some(Self { profiles, current })
+    }
+
+    /// The layout currently in use.
+    pub fn current(&self) -> &LayoutProfile {
+        &self.profiles[self.current]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LayoutProfile> {
+        self.profiles.iter()
+    }
+
+    /// Returns the index of the best layout for a terminal of the given size.
+    /// This is the one with the largest minimum width that fits, then the
+    /// largest minimum height, and then the one listed first.
+    fn best_match(&self, width: u16, height: u16) -> usize {
+        self.profiles
+            .iter()
+            .enumerate()
+            .filter(|(_, profile)| profile.fits(width, height))
+            .min_by_key(|(index, profile)| {
+                (
+                    Reverse(profile.min_width.unwrap_or(0)),
+                    Reverse(profile.min_height.unwrap_or(0)),
+                    *index,
+                )
+            })
+            .map(|(index, _)| index)
+            .unwrap_or(self.current)
+    }
+
+    /// Switches to the best layout for a terminal of the given size, and
+    /// returns it if it changed.
+    pub fn fit(&mut self, width: u16, height: u16) -> Option<&LayoutProfile> {
+        let best = self.best_match(width, height);
+        if best == self.current {
+            None
+        } else {
+            self.current = best;
+            Some(&self.profiles[best])
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum IntermediaryConstraint {
     PartialRatio(u32),
@@ -859,6 +984,9 @@ pub struct BottomWidget {
 
     /// Bottom right corner when drawn, for mouse click detection. (x, y)
     pub bottom_right_corner: Option<(u16, u16)>,
+
+    /// The only network interface to graph, if this is a network widget.
+    pub interface: Option<String>,
 }
 
 impl BottomWidget {
@@ -874,9 +1002,15 @@ impl BottomWidget {
             parent_reflector: None,
             top_left_corner: None,
             bottom_right_corner: None,
+            interface: None,
         }
     }
 
+    pub(crate) fn interface(mut self, interface: Option<String>) -> Self {
+        self.interface = interface;
+        self
+    }
+
     pub(crate) fn left_neighbour(mut self, left_neighbour: Option<u64>) -> Self {
         self.left_neighbour = left_neighbour;
         self
@@ -943,6 +1077,8 @@ pub enum BottomWidgetType {
     BasicNet,
     BasicTables,
     Battery,
+    LoadAvg,
+    Gpu,
 }
 
 impl BottomWidgetType {
@@ -953,7 +1089,7 @@ impl BottomWidgetType {
 
     pub fn is_widget_graph(&self) -> bool {
         use BottomWidgetType::*;
-        matches!(self, Cpu | Net | Mem)
+        matches!(self, Cpu | Net | Mem | Battery | Gpu)
     }
 
     pub fn get_pretty_name(&self) -> &str {
@@ -966,6 +1102,8 @@ impl BottomWidgetType {
             Temp => "Temperature",
             Disk => "Disks",
             Battery => "Battery",
+            LoadAvg => "Load Average",
+            Gpu => "GPU",
             _ => "",
         }
     }
@@ -986,71 +1124,81 @@ impl std::str::FromStr for BottomWidgetType {
             "empty" => Ok(BottomWidgetType::Empty),
             #[cfg(feature = "battery")]
             "battery" | "batt" => Ok(BottomWidgetType::Battery),
+            #[cfg(target_family = "unix")]
+            "load" | "loadavg" => Ok(BottomWidgetType::LoadAvg),
+            #[cfg(not(target_family = "unix"))]
+            "load" | "loadavg" => Err(OptionError::config(format!(
+                "'{s}' isn't supported on this platform, as it has no load average."
+            ))),
+            #[cfg(feature = "gpu")]
+            "gpu" => Ok(BottomWidgetType::Gpu),
+            #[cfg(not(feature = "gpu"))]
+            "gpu" => Err(OptionError::config(format!(
+                "'{s}' isn't supported, as bottom was built without GPU support."
+            ))),
             _ => {
-                #[cfg(feature = "battery")]
-                {
-                    Err(OptionError::config(format!(
-                        "'{s}' is an invalid widget name.
-        
-Supported widget names:
-+--------------------------+
-|            cpu           |
-+--------------------------+
-|        mem, memory       |
-+--------------------------+
-|       net, network       |
-+--------------------------+
-| proc, process, processes |
-+--------------------------+
-|     temp, temperature    |
-+--------------------------+
-|           disk           |
-+--------------------------+
-|       batt, battery      |
-+--------------------------+
-|           empty          |
-+--------------------------+
-                ",
-                    )))
-                }
-                #[cfg(not(feature = "battery"))]
-                {
-                    Err(OptionError::config(format!(
-                        "'{s}' is an invalid widget name.
-
-Supported widget names:
-+--------------------------+
-|            cpu           |
-+--------------------------+
-|        mem, memory       |
-+--------------------------+
-|       net, network       |
-+--------------------------+
-| proc, process, processes |
-+--------------------------+
-|     temp, temperature    |
-+--------------------------+
-|           disk           |
-+--------------------------+
-|           empty          |
-+--------------------------+
-                ",
-                    )))
+                const WIDGET_NAMES: &[&str] = &[
+                    "cpu",
+                    "mem, memory",
+                    "net, network",
+                    "proc, process, processes",
+                    "temp, temperature",
+                    "disk",
+                    #[cfg(feature = "battery")]
+                    "batt, battery",
+                    #[cfg(target_family = "unix")]
+                    "load, loadavg",
+                    #[cfg(feature = "gpu")]
+                    "gpu",
+                    "empty",
+                ];
+                const BORDER: &str = "+--------------------------+";
+
+                let mut table = String::from(BORDER);
+                for name in WIDGET_NAMES {
+                    table.push_str(&format!("\n|{name:^26}|\n{BORDER}"));
                 }
+
+                Err(OptionError::config(format!(
+                    "'{s}' is an invalid widget name.\n\nSupported widget names:\n{table}\n"
+                )))
             }
         }
     }
 }
 
-#[derive(Clone, Default, Debug, Copy)]
+#[derive(Clone, Default, Debug, Copy, PartialEq, Eq)]
 pub struct UsedWidgets {
     pub use_cpu: bool,
     pub use_mem: bool,
     pub use_cache: bool,
     pub use_gpu: bool,
+    /// Whether to collect how busy each GPU is, for the GPU widget.
+    pub use_gpu_util: bool,
     pub use_net: bool,
     pub use_proc: bool,
+    /// Whether to read where each process's executable is, for the process
+    /// widget's location column.
+    pub use_proc_exe: bool,
     pub use_disk: bool,
     pub use_temp: bool,
     pub use_battery: bool,
+    pub use_load_avg: bool,
+}
+
+impl UsedWidgets {
+    /// Also collects everything that is exported, e.g. as metrics or as a JSON
+    /// log, rather than just what the layout shows.
+    pub fn with_exported(self) -> Self {
+        Self {
+            use_cpu: true,
+            use_mem: true,
+            use_net: true,
+            use_proc: true,
+            use_disk: true,
+            use_temp: true,
+            use_load_avg: true,
+            ..self
+        }
+    }
 }
@@ -0,0 +1,57 @@
+//! The selected entry of a list, such as in an overlay's list of results.
+
+/// The index of the selected entry of a list, which moves without going past
+/// either end of the list.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ListSelection {
+    index: usize,
+}
+
+impl ListSelection {
+    /// Selects the last entry of a list `len` entries long.
+    pub fn last(len: usize) -> Self {
+        Self {
+            index: len.saturating_sub(1),
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Moves the selection by an offset, clamping at either end of a list
+    /// `len` entries long.
+    pub fn move_by(&mut self, change: i64, len: usize) {
+        let max_index = len.saturating_sub(1) as i64;
+        self.index = (self.index as i64 + change).clamp(0, max_index) as usize;
+    }
+
+    /// Keeps the selection within a list `len` entries long, such as after an
+    /// entry is removed.
+    pub fn clamp(&mut self, len: usize) {
+        self.index = self.index.min(len.saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn selection_stays_in_the_list() {
+        let mut selection = ListSelection::default();
+        selection.move_by(-1, 3);
+        assert_eq!(selection.index(), 0);
+
+        selection.move_by(10, 3);
+        assert_eq!(selection.index(), 2);
+        selection.move_by(-1, 3);
+        assert_eq!(selection.index(), 1);
+
+        selection.clamp(1);
+        assert_eq!(selection.index(), 0);
+
+        assert_eq!(ListSelection::last(4).index(), 3);
+        assert_eq!(ListSelection::last(0).index(), 0);
+    }
+}
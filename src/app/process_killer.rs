@@ -12,6 +12,179 @@ use windows::Win32::{
 
 use crate::data_collection::processes::Pid;
 
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        /// The signals that can be sent to a process, and their names.
+        pub const SIGNALS: &[(usize, &str)] = &[
+            (1, "HUP"),
+            (2, "INT"),
+            (3, "QUIT"),
+            (4, "ILL"),
+            (5, "TRAP"),
+            (6, "ABRT"),
+            (7, "BUS"),
+            (8, "FPE"),
+            (9, "KILL"),
+            (10, "USR1"),
+            (11, "SEGV"),
+            (12, "USR2"),
+            (13, "PIPE"),
+            (14, "ALRM"),
+            (15, "TERM"),
+            (16, "STKFLT"),
+            (17, "CHLD"),
+            (18, "CONT"),
+            (19, "STOP"),
+            (20, "TSTP"),
+            (21, "TTIN"),
+            (22, "TTOU"),
+            (23, "URG"),
+            (24, "XCPU"),
+            (25, "XFSZ"),
+            (26, "VTALRM"),
+            (27, "PROF"),
+            (28, "WINCH"),
+            (29, "IO"),
+            (30, "PWR"),
+            (31, "SYS"),
+            (34, "RTMIN"),
+            (35, "RTMIN+1"),
+            (36, "RTMIN+2"),
+            (37, "RTMIN+3"),
+            (38, "RTMIN+4"),
+            (39, "RTMIN+5"),
+            (40, "RTMIN+6"),
+            (41, "RTMIN+7"),
+            (42, "RTMIN+8"),
+            (43, "RTMIN+9"),
+            (44, "RTMIN+10"),
+            (45, "RTMIN+11"),
+            (46, "RTMIN+12"),
+            (47, "RTMIN+13"),
+            (48, "RTMIN+14"),
+            (49, "RTMIN+15"),
+            (50, "RTMAX-14"),
+            (51, "RTMAX-13"),
+            (52, "RTMAX-12"),
+            (53, "RTMAX-11"),
+            (54, "RTMAX-10"),
+            (55, "RTMAX-9"),
+            (56, "RTMAX-8"),
+            (57, "RTMAX-7"),
+            (58, "RTMAX-6"),
+            (59, "RTMAX-5"),
+            (60, "RTMAX-4"),
+            (61, "RTMAX-3"),
+            (62, "RTMAX-2"),
+            (63, "RTMAX-1"),
+            (64, "RTMAX"),
+        ];
+    } else if #[cfg(target_os = "macos")] {
+        /// The signals that can be sent to a process, and their names.
+        pub const SIGNALS: &[(usize, &str)] = &[
+            (1, "HUP"),
+            (2, "INT"),
+            (3, "QUIT"),
+            (4, "ILL"),
+            (5, "TRAP"),
+            (6, "ABRT"),
+            (7, "EMT"),
+            (8, "FPE"),
+            (9, "KILL"),
+            (10, "BUS"),
+            (11, "SEGV"),
+            (12, "SYS"),
+            (13, "PIPE"),
+            (14, "ALRM"),
+            (15, "TERM"),
+            (16, "URG"),
+            (17, "STOP"),
+            (18, "TSTP"),
+            (19, "CONT"),
+            (20, "CHLD"),
+            (21, "TTIN"),
+            (22, "TTOU"),
+            (23, "IO"),
+            (24, "XCPU"),
+            (25, "XFSZ"),
+            (26, "VTALRM"),
+            (27, "PROF"),
+            (28, "WINCH"),
+            (29, "INFO"),
+            (30, "USR1"),
+            (31, "USR2"),
+        ];
+    } else if #[cfg(target_os = "freebsd")] {
+        /// The signals that can be sent to a process, and their names.
+        pub const SIGNALS: &[(usize, &str)] = &[
+            (1, "HUP"),
+            (2, "INT"),
+            (3, "QUIT"),
+            (4, "ILL"),
+            (5, "TRAP"),
+            (6, "ABRT"),
+            (7, "EMT"),
+            (8, "FPE"),
+            (9, "KILL"),
+            (10, "BUS"),
+            (11, "SEGV"),
+            (12, "SYS"),
+            (13, "PIPE"),
+            (14, "ALRM"),
+            (15, "TERM"),
+            (16, "URG"),
+            (17, "STOP"),
+            (18, "TSTP"),
+            (19, "CONT"),
+            (20, "CHLD"),
+            (21, "TTIN"),
+            (22, "TTOU"),
+            (23, "IO"),
+            (24, "XCPU"),
+            (25, "XFSZ"),
+            (26, "VTALRM"),
+            (27, "PROF"),
+            (28, "WINCH"),
+            (29, "INFO"),
+            (30, "USR1"),
+            (31, "USR2"),
+            (32, "THR"),
+            (33, "LIBRT"),
+        ];
+    } else {
+        /// The signals that can be sent to a process, and their names. Other
+        /// platforms only support killing a process outright.
+        pub const SIGNALS: &[(usize, &str)] = &[];
+    }
+}
+
+/// Returns the name of a signal, e.g. "TERM".
+pub fn signal_name(signal: usize) -> Option<&'static str> {
+    SIGNALS
+        .iter()
+        .find(|(number, _)| *number == signal)
+        .map(|(_, name)| *name)
+}
+
+/// Parses a signal from either its number or its name, e.g. "9", "KILL", or
+/// "SIGKILL". Names are case-insensitive. Returns [`None`] if the signal can't
+/// be sent on this platform.
+pub fn parse_signal(signal: &str) -> Option<usize> {
+    let signal = signal.trim();
+
+    if let Ok(number) = signal.parse::<usize>() {
+        return signal_name(number).map(|_| number);
+    }
+
+    let upper = signal.to_ascii_uppercase();
+    let name = upper.strip_prefix("SIG").unwrap_or(&upper);
+
+    SIGNALS
+        .iter()
+        .find(|(_, signal_name)| *signal_name == name)
+        .map(|(number, _)| *number)
+}
+
 /// Based from [this SO answer](https://stackoverflow.com/a/55231715).
 #[cfg(target_os = "windows")]
 struct Process(HANDLE);
@@ -82,3 +255,30 @@ pub fn kill_process_given_pid(pid: Pid, signal: usize) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn parses_signals() {
+        assert_eq!(parse_signal("9"), Some(9));
+        assert_eq!(parse_signal("KILL"), Some(9));
+        assert_eq!(parse_signal("sigterm"), Some(15));
+        assert_eq!(parse_signal(" Hup "), Some(1));
+        assert_eq!(parse_signal("0"), None);
+        assert_eq!(parse_signal("NOTASIGNAL"), None);
+        assert_eq!(parse_signal("SIG"), None);
+        assert_eq!(signal_name(15), Some("TERM"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_skips_reserved_signals() {
+        assert_eq!(SIGNALS.len(), 62);
+        assert_eq!(parse_signal("32"), None);
+        assert_eq!(parse_signal("RTMIN+1"), Some(35));
+        assert_eq!(parse_signal("64"), Some(64));
+    }
+}
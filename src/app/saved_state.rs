@@ -0,0 +1,472 @@
+//! Saving and restoring some UI state between runs, such as how process
+//! widgets are sorted and how far graphs are zoomed out.
+//!
+//! Restoring is best-effort; anything in the state file that doesn't apply to
+//! the current layout is ignored. The state file is versioned (see
+//! [`persist`]), and one from a newer version of bottom is neither restored nor
+//! overwritten.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::App;
+use crate::{
+    args::BottomArgs,
+    canvas::components::data_table::SortOrder,
+    constants::STALE_MIN_MILLISECONDS,
+    utils::persist::{self, FormatVersion, Migration, Persisted},
+    widgets::ProcWidgetMode,
+};
+
+/// The default state file sub-path.
+const DEFAULT_STATE_FILE_LOCATION: &str = "bottom/state.toml";
+
+/// Returns the path of the state file, which is `<SYSTEM_STATE_FOLDER>/bottom/state.toml`.
+/// If the system has no state folder (e.g. on macOS or Windows), then the local
+/// data folder is used instead.
+pub fn state_path() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .map(|path| path.join(DEFAULT_STATE_FILE_LOCATION))
+}
+
+/// The mode of a process widget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SavedProcMode {
+    Normal,
+    Grouped,
+    Tree,
+}
+
+/// The state of a process widget.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedProcState {
+    pub widget_id: u64,
+    pub mode: SavedProcMode,
+    pub sort_index: usize,
+    pub sort_descending: bool,
+}
+
+/// The state of a graph widget.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedGraphState {
+    pub widget_id: u64,
+    pub display_time: u64,
+}
+
+/// The selected widget.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedSelection {
+    pub widget_id: u64,
+
+    /// The widget's type, to check that the layout hasn't changed since.
+    pub widget_type: String,
+}
+
+/// Which parts of the saved state were also set through command-line
+/// arguments, and so should not be restored.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StateOverrides {
+    pub process_mode: bool,
+    pub display_time: bool,
+    pub selection: bool,
+}
+
+impl StateOverrides {
+    pub fn from_args(args: &BottomArgs) -> Self {
+        Self {
+            process_mode: args.process.group_processes || args.process.tree,
+            display_time: args.general.default_time_value.is_some(),
+            selection: args.general.default_widget_type.is_some(),
+        }
+    }
+}
+
+/// UI state that is saved on exit and restored on startup.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedState {
+    pub selected: Option<SavedSelection>,
+
+    #[serde(default)]
+    pub processes: Vec<SavedProcState>,
+
+    #[serde(default)]
+    pub graphs: Vec<SavedGraphState>,
+}
+
+impl Persisted for SavedState {
+    const KIND: &'static str = "state file";
+    const VERSION: FormatVersion = FormatVersion::new(1, 0);
+    const MIGRATIONS: &'static [Migration] = &[];
+}
+
+impl SavedState {
+    /// Reads the state file at `path`. A missing, invalid, or too new state
+    /// file is ignored.
+    pub fn load(path: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(path).ok()?;
+        persist::from_toml(&contents).ok()
+    }
+
+    /// Writes the state file to `path`, unless it would replace one from a
+    /// newer version of bottom.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Ok(existing) = fs::read_to_string(path) {
+            if persist::is_too_new::<Self>(&existing) {
+                anyhow::bail!("the existing state file is from a newer version of bottom");
+            }
+        }
+
+        if let Some(parent_path) = path.parent() {
+            fs::create_dir_all(parent_path)?;
+        }
+
+        fs::write(path, persist::to_toml(self)?)?;
+
+        Ok(())
+    }
+
+    /// Captures the current state of the app.
+    pub fn capture(app: &App) -> Self {
+        let selected = (!app.app_config_fields.use_basic_mode).then(|| SavedSelection {
+            widget_id: app.current_widget.widget_id,
+            widget_type: app.current_widget.widget_type.get_pretty_name().to_string(),
+        });
+
+        let mut processes = app
+            .states
+            .proc_state
+            .widget_states
+            .iter()
+            .map(|(&widget_id, state)| SavedProcState {
+                widget_id,
+                mode: match state.mode {
+                    // The users view is for drilling into, so it isn't restored.
+                    ProcWidgetMode::Normal | ProcWidgetMode::Users => SavedProcMode::Normal,
+                    ProcWidgetMode::Grouped => SavedProcMode::Grouped,
+                    ProcWidgetMode::Tree { .. } => SavedProcMode::Tree,
+                },
+                sort_index: state.table.sort_index(),
+                sort_descending: state.table.order() == SortOrder::Descending,
+            })
+            .collect::<Vec<_>>();
+        processes.sort_by_key(|state| state.widget_id);
+
+        let states = &app.states;
+        let mut graphs = states
+            .cpu_state
+            .widget_states
+            .iter()
+            .map(|(&id, state)| (id, state.current_display_time))
+            .chain(
+                states
+                    .mem_state
+                    .widget_states
+                    .iter()
+                    .map(|(&id, state)| (id, state.current_display_time)),
+            )
+            .chain(
+                states
+                    .net_state
+                    .widget_states
+                    .iter()
+                    .map(|(&id, state)| (id, state.current_display_time)),
+            )
+            .chain(
+                states
+                    .battery_state
+                    .widget_states
+                    .iter()
+                    .map(|(&id, state)| (id, state.current_display_time)),
+            )
+            .chain(
+                states
+                    .gpu_state
+                    .widget_states
+                    .iter()
+                    .map(|(&id, state)| (id, state.current_display_time)),
+            )
+            .map(|(widget_id, display_time)| SavedGraphState {
+                widget_id,
+                display_time,
+            })
+            .collect::<Vec<_>>();
+        graphs.sort_by_key(|state| state.widget_id);
+
+        Self {
+            selected,
+            processes,
+            graphs,
+        }
+    }
+
+    /// Restores the state onto the app, skipping anything set through
+    /// `overrides` or that no longer matches the layout.
+    pub fn restore(&self, app: &mut App, overrides: StateOverrides) {
+        for saved in &self.processes {
+            let Some(state) = app
+                .states
+                .proc_state
+                .widget_states
+                .get_mut(&saved.widget_id)
+            else {
+                continue;
+            };
+
+            if !overrides.process_mode {
+                // Go through normal mode so grouping also updates the columns.
+                if matches!(state.mode, ProcWidgetMode::Grouped | ProcWidgetMode::Users) {
+                    state.toggle_tab();
+                }
+                state.mode = ProcWidgetMode::Normal;
+
+                match saved.mode {
+                    SavedProcMode::Normal => {}
+                    SavedProcMode::Grouped => state.toggle_tab(),
+                    SavedProcMode::Tree => {
+                        state.mode = ProcWidgetMode::Tree {
+                            collapsed_pids: Default::default(),
+                        };
+                    }
+                }
+            }
+
+            if saved.sort_index < state.table.columns.len() {
+                state.table.set_sort_index(saved.sort_index);
+                state.table.set_order(if saved.sort_descending {
+                    SortOrder::Descending
+                } else {
+                    SortOrder::Ascending
+                });
+            }
+
+            state.force_rerender_and_update();
+        }
+
+        if !overrides.display_time {
+            let retention_ms = app.app_config_fields.retention_ms;
+            let states = &mut app.states;
+
+            for saved in &self.graphs {
+                let display_time = saved.display_time.clamp(
+                    STALE_MIN_MILLISECONDS,
+                    retention_ms.max(STALE_MIN_MILLISECONDS),
+                );
+                let id = &saved.widget_id;
+
+                if let Some(state) = states.cpu_state.widget_states.get_mut(id) {
+                    state.current_display_time = display_time;
+                } else if let Some(state) = states.mem_state.widget_states.get_mut(id) {
+                    state.current_display_time = display_time;
+                } else if let Some(state) = states.net_state.widget_states.get_mut(id) {
+                    state.current_display_time = display_time;
+                } else if let Some(state) = states.battery_state.widget_states.get_mut(id) {
+                    state.current_display_time = display_time;
+                } else if let Some(state) = states.gpu_state.widget_states.get_mut(id) {
+                    state.current_display_time = display_time;
+                }
+            }
+        }
+
+        if let Some(selected) = &self.selected {
+            if overrides.selection || app.app_config_fields.use_basic_mode {
+                return;
+            }
+
+            if let Some(widget) = app.widget_map.get(&selected.widget_id) {
+                if widget.widget_type.get_pretty_name() == selected.widget_type {
+                    app.current_widget = widget.clone();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::*;
+    use crate::{app::layout_manager::BottomWidgetType, options::config::Config};
+
+    fn init_app(args: &[&str]) -> App {
+        crate::options::init_app(BottomArgs::parse_from(args), Config::default())
+            .unwrap()
+            .0
+    }
+
+    fn widget_id(app: &App, widget_type: BottomWidgetType) -> u64 {
+        app.widget_map
+            .values()
+            .find(|widget| widget.widget_type == widget_type)
+            .map(|widget| widget.widget_id)
+            .unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut app = init_app(&["btm"]);
+        let proc_id = widget_id(&app, BottomWidgetType::Proc);
+        let cpu_id = widget_id(&app, BottomWidgetType::Cpu);
+
+        let proc = app
+            .states
+            .proc_state
+            .widget_states
+            .get_mut(&proc_id)
+            .unwrap();
+        proc.toggle_tab();
+        proc.table.set_sort_index(3);
+        proc.table.set_order(SortOrder::Ascending);
+        app.states
+            .cpu_state
+            .widget_states
+            .get_mut(&cpu_id)
+            .unwrap()
+            .current_display_time = 120_000;
+        app.current_widget = app.widget_map[&cpu_id].clone();
+
+        let state = SavedState::capture(&app);
+        let path = std::env::temp_dir().join(format!("btm_state_{}.toml", std::process::id()));
+        state.save(&path).unwrap();
+        let loaded = SavedState::load(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(loaded, state);
+
+        let mut restored = init_app(&["btm"]);
+        loaded.restore(&mut restored, StateOverrides::default());
+
+        let proc = &restored.states.proc_state.widget_states[&proc_id];
+        assert_eq!(proc.mode, ProcWidgetMode::Grouped);
+        assert_eq!(proc.table.sort_index(), 3);
+        assert_eq!(proc.table.order(), SortOrder::Ascending);
+        assert_eq!(
+            restored.states.cpu_state.widget_states[&cpu_id].current_display_time,
+            120_000
+        );
+        assert_eq!(restored.current_widget.widget_id, cpu_id);
+    }
+
+    #[test]
+    fn invalid_state_is_ignored() {
+        let path = std::env::temp_dir().join(format!("btm_bad_state_{}.toml", std::process::id()));
+        fs::write(&path, "processes = \"oops\"\n[[[").unwrap();
+        assert_eq!(SavedState::load(&path), None);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(SavedState::load(Path::new("/nonexistent/state.toml")), None);
+    }
+
+    #[test]
+    fn newer_state_is_ignored() {
+        let path =
+            std::env::temp_dir().join(format!("btm_newer_state_{}.toml", std::process::id()));
+        let newer = "version = \"2.0\"\n[[panels]]\nwidget_id = 1\n";
+        fs::write(&path, newer).unwrap();
+
+        // The app carries on with its defaults...
+        assert_eq!(SavedState::load(&path), None);
+
+        // ...and doesn't clobber the newer file when it exits.
+        let app = init_app(&["btm"]);
+        assert!(SavedState::capture(&app).save(&path).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), newer);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn unversioned_state_is_loaded() {
+        // State files from before versioning.
+        let path =
+            std::env::temp_dir().join(format!("btm_unversioned_state_{}.toml", std::process::id()));
+        fs::write(&path, "[[graphs]]\nwidget_id = 3\ndisplay_time = 60000\n").unwrap();
+        let loaded = SavedState::load(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(
+            loaded,
+            Some(SavedState {
+                graphs: vec![SavedGraphState {
+                    widget_id: 3,
+                    display_time: 60000,
+                }],
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn stale_state_is_ignored() {
+        let mut app = init_app(&["btm"]);
+        let current_id = app.current_widget.widget_id;
+
+        let state = SavedState {
+            selected: Some(SavedSelection {
+                widget_id: current_id,
+                widget_type: "Not a widget".to_string(),
+            }),
+            processes: vec![SavedProcState {
+                widget_id: 12345,
+                mode: SavedProcMode::Tree,
+                sort_index: 100,
+                sort_descending: true,
+            }],
+            graphs: vec![SavedGraphState {
+                widget_id: 12345,
+                display_time: 1,
+            }],
+        };
+        state.restore(&mut app, StateOverrides::default());
+
+        assert_eq!(app.current_widget.widget_id, current_id);
+        assert!(app
+            .states
+            .proc_state
+            .widget_states
+            .values()
+            .all(|state| state.mode == ProcWidgetMode::Normal));
+    }
+
+    #[test]
+    fn args_override_state() {
+        let mut app = init_app(&["btm"]);
+        let proc_id = widget_id(&app, BottomWidgetType::Proc);
+        let cpu_id = widget_id(&app, BottomWidgetType::Cpu);
+        app.states
+            .proc_state
+            .widget_states
+            .get_mut(&proc_id)
+            .unwrap()
+            .mode = ProcWidgetMode::Tree {
+            collapsed_pids: Default::default(),
+        };
+        app.states
+            .cpu_state
+            .widget_states
+            .get_mut(&cpu_id)
+            .unwrap()
+            .current_display_time = 120_000;
+        let state = SavedState::capture(&app);
+
+        let args = ["btm", "--group_processes", "--default_time_value", "30s"];
+        let mut app = init_app(&args);
+        state.restore(
+            &mut app,
+            StateOverrides::from_args(&BottomArgs::parse_from(args)),
+        );
+
+        assert_eq!(
+            app.states.proc_state.widget_states[&proc_id].mode,
+            ProcWidgetMode::Grouped
+        );
+        assert_eq!(
+            app.states.cpu_state.widget_states[&cpu_id].current_display_time,
+            30_000
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! An overlay to change how often data is collected and how long it is kept
+//! for while bottom is running.
+
+use std::time::Duration;
+
+use humantime::format_duration;
+
+use crate::{
+    constants::{MIN_REFRESH_RATE_IN_MILLISECONDS, STALE_MIN_MILLISECONDS},
+    options::try_parse_ms,
+};
+
+/// A field in the settings overlay.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsField {
+    #[default]
+    Rate,
+    Retention,
+}
+
+/// Settings that have been checked and can be applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiveSettings {
+    pub update_rate_ms: u64,
+    pub retention_ms: u64,
+}
+
+/// The state of the settings overlay.
+#[derive(Debug, Default)]
+pub struct SettingsOverlay {
+    is_open: bool,
+    selected: SettingsField,
+    rate: String,
+    retention: String,
+
+    /// Why the last attempt to apply the settings failed.
+    error: Option<String>,
+}
+
+impl SettingsOverlay {
+    /// Opens the overlay, filled in with the current settings.
+    pub fn open(&mut self, update_rate_ms: u64, retention_ms: u64) {
+        self.is_open = true;
+        self.selected = SettingsField::Rate;
+        self.rate = format_duration(Duration::from_millis(update_rate_ms)).to_string();
+        self.retention = format_duration(Duration::from_millis(retention_ms)).to_string();
+        self.error = None;
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.error = None;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn selected(&self) -> SettingsField {
+        self.selected
+    }
+
+    /// Switches between the fields.
+    pub fn toggle_field(&mut self) {
+        self.selected = match self.selected {
+            SettingsField::Rate => SettingsField::Retention,
+            SettingsField::Retention => SettingsField::Rate,
+        };
+    }
+
+    /// The text entered for a field.
+    pub fn value(&self, field: SettingsField) -> &str {
+        match field {
+            SettingsField::Rate => &self.rate,
+            SettingsField::Retention => &self.retention,
+        }
+    }
+
+    fn selected_value_mut(&mut self) -> &mut String {
+        match self.selected {
+            SettingsField::Rate => &mut self.rate,
+            SettingsField::Retention => &mut self.retention,
+        }
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.selected_value_mut().push(c);
+        self.error = None;
+    }
+
+    pub fn pop_char(&mut self) {
+        self.selected_value_mut().pop();
+        self.error = None;
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Checks the entered settings, with the same limits as the command-line
+    /// options.
+    pub fn settings(&self) -> Result<LiveSettings, String> {
+        let update_rate_ms = try_parse_ms(self.rate.trim())
+            .map_err(|_| format!("'{}' isn't a valid rate", self.rate.trim()))?;
+        if update_rate_ms < MIN_REFRESH_RATE_IN_MILLISECONDS {
+            return Err(format!(
+                "The rate must be at least {}",
+                format_duration(Duration::from_millis(MIN_REFRESH_RATE_IN_MILLISECONDS))
+            ));
+        }
+
+        // Graphs can't show less than this, and never show more than is kept.
+        let retention_ms = try_parse_ms(self.retention.trim())
+            .map_err(|_| format!("'{}' isn't a valid retention", self.retention.trim()))?;
+        if retention_ms < STALE_MIN_MILLISECONDS {
+            return Err(format!(
+                "The retention must be at least {}",
+                format_duration(Duration::from_millis(STALE_MIN_MILLISECONDS))
+            ));
+        }
+
+        Ok(LiveSettings {
+            update_rate_ms,
+            retention_ms,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn overlay(rate: &str, retention: &str) -> SettingsOverlay {
+        SettingsOverlay {
+            is_open: true,
+            rate: rate.to_string(),
+            retention: retention.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn opens_with_current_settings() {
+        let mut overlay = SettingsOverlay::default();
+        overlay.open(1000, 600_000);
+
+        assert_eq!(overlay.value(SettingsField::Rate), "1s");
+        assert_eq!(overlay.value(SettingsField::Retention), "10m");
+        assert_eq!(
+            overlay.settings(),
+            Ok(LiveSettings {
+                update_rate_ms: 1000,
+                retention_ms: 600_000,
+            })
+        );
+    }
+
+    #[test]
+    fn settings_are_validated() {
+        assert_eq!(
+            overlay("500", "1h").settings(),
+            Ok(LiveSettings {
+                update_rate_ms: 500,
+                retention_ms: 3_600_000,
+            })
+        );
+        assert_eq!(
+            overlay("250ms", " 30s ").settings(),
+            Ok(LiveSettings {
+                update_rate_ms: 250,
+                retention_ms: 30_000,
+            })
+        );
+
+        assert_eq!(
+            overlay("100ms", "1h").settings(),
+            Err("The rate must be at least 250ms".to_string())
+        );
+        assert_eq!(
+            overlay("1s", "10s").settings(),
+            Err("The retention must be at least 30s".to_string())
+        );
+        assert!(overlay("soon", "1h").settings().is_err());
+        assert!(overlay("1s", "forever").settings().is_err());
+        assert!(overlay("", "1h").settings().is_err());
+    }
+
+    #[test]
+    fn editing_the_selected_field() {
+        let mut overlay = overlay("1s", "10m");
+        overlay.set_error("bad".into());
+
+        overlay.pop_char();
+        overlay.push_char('0');
+        overlay.push_char('s');
+        assert_eq!(overlay.value(SettingsField::Rate), "10s");
+        assert_eq!(overlay.error(), None);
+
+        overlay.toggle_field();
+        overlay.pop_char();
+        overlay.push_char('h');
+        assert_eq!(overlay.value(SettingsField::Retention), "10h");
+    }
+}
@@ -4,13 +4,14 @@ use hashbrown::HashMap;
 use indexmap::IndexMap;
 use unicode_ellipsis::grapheme_width;
 use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete, UnicodeSegmentation};
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
     app::layout_manager::BottomWidgetType,
     constants,
     widgets::{
-        query::ProcessQuery, BatteryWidgetState, CpuWidgetState, DiskTableWidget, MemWidgetState,
-        NetWidgetState, ProcWidgetState, TempWidgetState,
+        query::ProcessQuery, BatteryWidgetState, CpuWidgetState, DiskTableWidget, GpuWidgetState,
+        MemWidgetState, NetWidgetState, ProcWidgetState, TempWidgetState,
     },
 };
 
@@ -22,9 +23,102 @@ pub struct AppWidgetStates {
     pub temp_state: TempState,
     pub disk_state: DiskState,
     pub battery_state: AppBatteryState,
+    pub gpu_state: GpuState,
     pub basic_table_widget_state: Option<BasicTableWidgetState>,
 }
 
+impl AppWidgetStates {
+    /// Moves widget states over when swapping layouts, given pairs of the ID
+    /// of a widget in the old layout and the ID of its equivalent in the new
+    /// one. Any states already kept for the new widgets go to the old ones in
+    /// turn, so nothing is dropped or added, and swapping back undoes this.
+    pub fn swap_widget_states(&mut self, pairs: &[(u64, u64)]) {
+        swap_states(&mut self.cpu_state.widget_states, pairs);
+        swap_states(&mut self.mem_state.widget_states, pairs);
+        swap_states(&mut self.net_state.widget_states, pairs);
+        swap_states(&mut self.proc_state.widget_states, pairs);
+        swap_states(&mut self.temp_state.widget_states, pairs);
+        swap_states(&mut self.disk_state.widget_states, pairs);
+        swap_states(&mut self.battery_state.widget_states, pairs);
+        swap_states(&mut self.gpu_state.widget_states, pairs);
+    }
+
+    /// Limits how much time each graph shows to at most `max_ms`, e.g. once
+    /// less data is kept.
+    pub fn limit_display_times(&mut self, max_ms: u64) {
+        let limit = |display_time: &mut u64| *display_time = (*display_time).min(max_ms);
+
+        self.cpu_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| limit(&mut state.current_display_time));
+        self.mem_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| limit(&mut state.current_display_time));
+        self.net_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| limit(&mut state.current_display_time));
+        self.battery_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| limit(&mut state.current_display_time));
+        self.gpu_state
+            .widget_states
+            .values_mut()
+            .for_each(|state| limit(&mut state.current_display_time));
+    }
+
+    /// The number of widget states kept, across every type of widget.
+    #[cfg(test)]
+    pub fn widget_state_count(&self) -> usize {
+        self.cpu_state.widget_states.len()
+            + self.mem_state.widget_states.len()
+            + self.net_state.widget_states.len()
+            + self.proc_state.widget_states.len()
+            + self.temp_state.widget_states.len()
+            + self.disk_state.widget_states.len()
+            + self.battery_state.widget_states.len()
+            + self.gpu_state.widget_states.len()
+    }
+}
+
+/// See [`AppWidgetStates::swap_widget_states`]. Only pairs whose old widget
+/// has a state here are of this type of widget.
+fn swap_states<S>(states: &mut HashMap<u64, S>, pairs: &[(u64, u64)]) {
+    let pairs = pairs
+        .iter()
+        .filter(|(from, to)| from != to && states.contains_key(from))
+        .collect::<Vec<_>>();
+
+    let moved = pairs
+        .iter()
+        .filter_map(|&&(from, to)| Some((to, states.remove(&from)?)))
+        .collect::<Vec<_>>();
+    let displaced = pairs
+        .iter()
+        .filter_map(|(_, to)| states.remove(to))
+        .collect::<Vec<_>>();
+    let vacated = pairs
+        .iter()
+        .map(|(from, _)| *from)
+        .filter(|from| !pairs.iter().any(|(_, to)| to == from))
+        .collect::<Vec<_>>();
+
+    states.extend(moved);
+    states.extend(vacated.into_iter().zip(displaced));
+}
+
+/// A table column's border that is being dragged to resize the column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnDrag {
+    pub widget_id: u64,
+    pub widget_type: BottomWidgetType,
+    /// The index of the shown column the border is after.
+    pub column: usize,
+}
+
 #[derive(Debug)]
 pub enum CursorDirection {
     Left,
@@ -52,18 +146,50 @@ impl Default for KillSignal {
 pub struct AppDeleteDialogState {
     pub is_showing_dd: bool,
     pub selected_signal: KillSignal,
-    /// tl x, tl y, br x, br y, index/signal
+    /// tl x, tl y, br x, br y, signal (0 to cancel)
     pub button_positions: Vec<(u16, u16, u16, u16, usize)>,
     pub keyboard_signal_select: usize,
     pub last_number_press: Option<Instant>,
     pub scroll_pos: usize,
 }
 
+impl AppDeleteDialogState {
+    /// Returns the position of the selected signal in a list of signals, where
+    /// 0 is the option to cancel.
+    pub fn selected_position(&self, signals: &[usize]) -> usize {
+        match self.selected_signal {
+            KillSignal::Cancel => 0,
+            KillSignal::Kill(signal) => signals
+                .iter()
+                .position(|&s| s == signal)
+                .map_or(0, |position| position + 1),
+        }
+    }
+
+    /// Selects the signal at a position in a list of signals, where 0 is the
+    /// option to cancel. Positions past the end select the last signal.
+    pub fn select_position(&mut self, signals: &[usize], position: usize) {
+        self.selected_signal = match position.min(signals.len()) {
+            0 => KillSignal::Cancel,
+            position => KillSignal::Kill(signals[position - 1]),
+        };
+    }
+}
+
 pub struct AppHelpDialogState {
     pub is_showing_help: bool,
     pub height: u16,
     pub scroll_state: ParagraphScrollState,
+    /// Where each section of [`constants::HELP_TEXT`] starts, indexed by its
+    /// number. Sections that aren't shown are left as is.
     pub index_shortcuts: Vec<u16>,
+    /// The sections for the widget that was selected when help was opened.
+    relevant_sections: &'static [usize],
+    /// Whether every section is shown, rather than just the relevant ones
+    /// and the general one.
+    pub is_expanded: bool,
+    /// A section to jump to once it has been drawn and its position is known.
+    pub pending_jump: Option<usize>,
 }
 
 impl Default for AppHelpDialogState {
@@ -73,7 +199,132 @@ impl Default for AppHelpDialogState {
             height: 0,
             scroll_state: ParagraphScrollState::default(),
             index_shortcuts: vec![0; constants::HELP_TEXT.len()],
+            relevant_sections: &[],
+            is_expanded: false,
+            pending_jump: None,
+        }
+    }
+}
+
+/// The index of the general section of [`constants::HELP_TEXT`], which
+/// applies to every widget.
+const GENERAL_HELP_SECTION: usize = 1;
+
+/// Returns the sections of [`constants::HELP_TEXT`] that apply to a widget
+/// type, most relevant first, not including the general section.
+fn help_sections(widget_type: &BottomWidgetType) -> &'static [usize] {
+    use BottomWidgetType::*;
+
+    match widget_type {
+        Cpu | CpuLegend | BasicCpu => &[2],
+        Proc => &[3, 4, 5],
+        ProcSearch => &[4, 3],
+        ProcSort => &[5, 3],
+        Temp => &[6],
+        Disk => &[7],
+        Battery => &[8],
+        BasicMem => &[9],
+        Empty | Mem | Net | BasicNet | BasicTables | LoadAvg | Gpu => &[],
+    }
+}
+
+impl AppHelpDialogState {
+    /// Opens the help dialog with the sections that apply to `widget_type`
+    /// first, followed by the general section.
+    pub fn open(&mut self, widget_type: &BottomWidgetType) {
+        self.is_showing_help = true;
+        self.relevant_sections = help_sections(widget_type);
+        self.is_expanded = false;
+        self.pending_jump = None;
+        self.scroll_state.current_scroll_index = 0;
+    }
+
+    /// Toggles between showing every section and just the relevant ones.
+    pub fn toggle_expanded(&mut self) {
+        self.is_expanded = !self.is_expanded;
+        self.scroll_state.current_scroll_index = 0;
+    }
+
+    /// Returns the numbers of the sections that are shown, in order.
+    pub fn sections(&self) -> Vec<usize> {
+        let mut sections = self.relevant_sections.to_vec();
+        sections.push(GENERAL_HELP_SECTION);
+
+        if self.is_expanded {
+            sections.extend(
+                (GENERAL_HELP_SECTION + 1..constants::HELP_TEXT.len())
+                    .filter(|section| !self.relevant_sections.contains(section)),
+            );
+        }
+
+        sections
+    }
+
+    /// Returns the lines of the table of contents, followed by those of each
+    /// section that is shown.
+    pub fn text(&self) -> Vec<Vec<&'static str>> {
+        let sections = self.sections();
+        let contents = constants::HELP_TEXT[0];
+
+        let mut text = Vec::with_capacity(sections.len() + 1);
+        text.push(
+            std::iter::once(contents[0])
+                .chain(sections.iter().map(|&section| contents[section]))
+                .chain(std::iter::once(if self.is_expanded {
+                    constants::HELP_COLLAPSE_TEXT
+                } else {
+                    constants::HELP_EXPAND_TEXT
+                }))
+                .collect(),
+        );
+        text.extend(
+            sections
+                .iter()
+                .map(|&section| constants::HELP_TEXT[section].to_vec()),
+        );
+
+        text
+    }
+
+    /// Jumps to a section by its number, showing every section first if it
+    /// isn't shown.
+    pub fn jump_to_section(&mut self, section: usize) {
+        if section == 0 || section >= constants::HELP_TEXT.len() {
+            return;
+        }
+
+        if !self.sections().contains(&section) {
+            self.is_expanded = true;
+        }
+        self.pending_jump = Some(section);
+    }
+
+    /// Updates where each shown section starts in a paragraph of the given
+    /// width, returning how many extra lines wrapping adds.
+    pub fn update_index_shortcuts(&mut self, paragraph_width: u16) -> u16 {
+        let paragraph_width = paragraph_width.max(1);
+        let mut overflow_buffer = 0;
+        let mut position = 0;
+
+        for (section, lines) in std::iter::once(0).chain(self.sections()).zip(self.text()) {
+            let buffer: u16 = lines
+                .iter()
+                .map(|line| {
+                    UnicodeWidthStr::width(*line).saturating_sub(1) as u16 / paragraph_width
+                })
+                .sum();
+
+            // Each section after the contents is preceded by a blank line.
+            if section > 0 {
+                position += 1;
+            }
+            self.index_shortcuts[section] = position;
+
+            position += lines.len() as u16 + buffer;
+            overflow_buffer += buffer;
         }
+
+        overflow_buffer
     }
 }
 
@@ -382,12 +633,16 @@ pub struct BasicTableWidgetState {
 }
 
 pub struct AppBatteryState {
+    pub force_update: Option<u64>,
     pub widget_states: HashMap<u64, BatteryWidgetState>,
 }
 
 impl AppBatteryState {
     pub fn init(widget_states: HashMap<u64, BatteryWidgetState>) -> Self {
-        AppBatteryState { widget_states }
+        AppBatteryState {
+            force_update: None,
+            widget_states,
+        }
     }
 
     pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut BatteryWidgetState> {
@@ -395,6 +650,20 @@ impl AppBatteryState {
     }
 }
 
+pub struct GpuState {
+    pub force_update: Option<u64>,
+    pub widget_states: HashMap<u64, GpuWidgetState>,
+}
+
+impl GpuState {
+    pub fn init(widget_states: HashMap<u64, GpuWidgetState>) -> Self {
+        GpuState {
+            force_update: None,
+            widget_states,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct ParagraphScrollState {
     pub current_scroll_index: u16,
@@ -405,6 +674,100 @@ pub struct ParagraphScrollState {
 mod test {
     use super::*;
 
+    #[test]
+    fn swapping_states() {
+        let mut states = HashMap::from([(1, "a"), (2, "b"), (10, "x"), (20, "y"), (5, "other")]);
+        let pairs = [(1, 10), (2, 20), (3, 30)];
+
+        swap_states(&mut states, &pairs);
+        assert_eq!(states[&10], "a");
+        assert_eq!(states[&20], "b");
+        assert_eq!(states[&1], "x");
+        assert_eq!(states[&2], "y");
+        assert_eq!(states[&5], "other");
+        assert_eq!(states.len(), 5);
+
+        let reversed = pairs.map(|(from, to)| (to, from));
+        swap_states(&mut states, &reversed);
+        assert_eq!(
+            states,
+            HashMap::from([(1, "a"), (2, "b"), (10, "x"), (20, "y"), (5, "other")])
+        );
+
+        // IDs used by both layouts.
+        let mut states = HashMap::from([(1, "a"), (2, "b")]);
+        swap_states(&mut states, &[(1, 2), (2, 3)]);
+        assert_eq!(states, HashMap::from([(2, "a"), (3, "b")]));
+        swap_states(&mut states, &[(2, 1), (3, 2)]);
+        assert_eq!(states, HashMap::from([(1, "a"), (2, "b")]));
+    }
+
+    #[test]
+    fn help_for_process_widget() {
+        let mut state = AppHelpDialogState::default();
+        state.open(&BottomWidgetType::Proc);
+
+        assert_eq!(state.sections(), vec![3, 4, 5, 1]);
+
+        let text = state.text();
+        assert_eq!(
+            text[0][1..],
+            [
+                "3 - Process widget",
+                "4 - Process search widget",
+                "5 - Process sort widget",
+                "1 - General",
+                constants::HELP_EXPAND_TEXT,
+            ]
+        );
+        assert_eq!(text[1][0], "3 - Process widget");
+        assert_eq!(text[4][0], "1 - General");
+
+        // Jumps follow the new order, where each section after the contents is
+        // preceded by a blank line.
+        state.update_index_shortcuts(200);
+        let contents_len = text[0].len() as u16;
+        assert_eq!(state.index_shortcuts[3], contents_len + 1);
+        assert_eq!(
+            state.index_shortcuts[4],
+            state.index_shortcuts[3] + 1 + constants::HELP_TEXT[3].len() as u16
+        );
+
+        // Everything else follows once expanded.
+        state.toggle_expanded();
+        assert_eq!(state.sections(), vec![3, 4, 5, 1, 2, 6, 7, 8, 9]);
+        assert_eq!(
+            state.text()[0].last().copied(),
+            Some(constants::HELP_COLLAPSE_TEXT)
+        );
+    }
+
+    #[test]
+    fn help_for_temperature_widget() {
+        let mut state = AppHelpDialogState::default();
+        state.open(&BottomWidgetType::Temp);
+
+        assert_eq!(state.sections(), vec![6, 1]);
+        state.update_index_shortcuts(200);
+        assert_eq!(state.index_shortcuts[6], state.text()[0].len() as u16 + 1);
+
+        // Jumping to a section that isn't shown shows every section.
+        state.jump_to_section(7);
+        assert!(state.is_expanded);
+        assert_eq!(state.pending_jump, Some(7));
+        assert_eq!(state.sections(), vec![6, 1, 2, 3, 4, 5, 7, 8, 9]);
+
+        state.update_index_shortcuts(200);
+        let text = state.text();
+        let before: u16 = text[..7].iter().map(|lines| lines.len() as u16 + 1).sum();
+        assert_eq!(state.index_shortcuts[7], before);
+
+        // Widgets without their own section just show the general one.
+        state.open(&BottomWidgetType::Net);
+        assert!(!state.is_expanded);
+        assert_eq!(state.sections(), vec![1]);
+    }
+
     fn move_right(state: &mut AppSearchState) {
         state.walk_forward();
         state.cursor_direction = CursorDirection::Right;
@@ -0,0 +1,176 @@
+//! Markers for points in time on the graphs, such as when a service was
+//! restarted, and the overlay to label and remove them.
+
+use std::time::{Instant, SystemTime};
+
+use super::list_selection::ListSelection;
+
+/// A marked point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeMarker {
+    pub time: Instant,
+    /// The same point in time, for exports.
+    pub system_time: SystemTime,
+    pub label: Option<String>,
+}
+
+impl TimeMarker {
+    pub fn new(time: Instant, system_time: SystemTime) -> Self {
+        Self {
+            time,
+            system_time,
+            label: None,
+        }
+    }
+}
+
+/// What the marker overlay is showing.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MarkerOverlayMode {
+    #[default]
+    Closed,
+    /// Entering a label for the marker at the given time.
+    Labelling(Instant),
+    /// The list of markers.
+    Listing,
+}
+
+/// The state of the marker overlay.
+#[derive(Debug, Default)]
+pub struct MarkerOverlay {
+    mode: MarkerOverlayMode,
+    label: String,
+
+    /// Whether to go back to the list once done labelling.
+    labelling_from_list: bool,
+
+    /// The selected marker in the list.
+    selected: ListSelection,
+}
+
+impl MarkerOverlay {
+    pub fn is_open(&self) -> bool {
+        self.mode != MarkerOverlayMode::Closed
+    }
+
+    pub fn mode(&self) -> MarkerOverlayMode {
+        self.mode
+    }
+
+    /// Starts entering a label for `marker`, filled in with its current label.
+    pub fn start_labelling(&mut self, marker: &TimeMarker) {
+        self.labelling_from_list = self.mode == MarkerOverlayMode::Listing;
+        self.mode = MarkerOverlayMode::Labelling(marker.time);
+        self.label = marker.label.clone().unwrap_or_default();
+    }
+
+    /// Opens the list of `num_markers` markers.
+    pub fn open_list(&mut self, num_markers: usize) {
+        self.mode = MarkerOverlayMode::Listing;
+        self.labelling_from_list = false;
+        self.clamp_selection(num_markers);
+    }
+
+    /// Stops labelling, going back to the list if that is where labelling
+    /// started, or closes the overlay.
+    pub fn close(&mut self, num_markers: usize) {
+        match self.mode {
+            MarkerOverlayMode::Labelling(_) if self.labelling_from_list => {
+                self.open_list(num_markers)
+            }
+            _ => self.mode = MarkerOverlayMode::Closed,
+        }
+    }
+
+    /// The label entered, or [`None`] if it is blank.
+    pub fn label(&self) -> Option<&str> {
+        let label = self.label.trim();
+        (!label.is_empty()).then_some(label)
+    }
+
+    /// The label entered so far, as typed.
+    pub fn label_text(&self) -> &str {
+        &self.label
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.label.push(c);
+    }
+
+    pub fn pop_char(&mut self) {
+        self.label.pop();
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected.index()
+    }
+
+    pub fn move_selection(&mut self, change: i64, num_markers: usize) {
+        self.selected.move_by(change, num_markers);
+    }
+
+    pub fn clamp_selection(&mut self, num_markers: usize) {
+        self.selected.clamp(num_markers);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn labelling_a_new_marker() {
+        let marker = TimeMarker::new(Instant::now(), SystemTime::now());
+        let mut overlay = MarkerOverlay::default();
+
+        overlay.start_labelling(&marker);
+        assert_eq!(overlay.mode(), MarkerOverlayMode::Labelling(marker.time));
+        assert_eq!(overlay.label(), None);
+
+        for c in " restarted ".chars() {
+            overlay.push_char(c);
+        }
+        overlay.pop_char();
+        assert_eq!(overlay.label(), Some("restarted"));
+
+        overlay.close(1);
+        assert!(!overlay.is_open());
+    }
+
+    #[test]
+    fn labelling_from_the_list_goes_back_to_it() {
+        let mut marker = TimeMarker::new(Instant::now(), SystemTime::now());
+        marker.label = Some("deploy".to_string());
+        let mut overlay = MarkerOverlay::default();
+
+        overlay.open_list(1);
+        overlay.start_labelling(&marker);
+        assert_eq!(overlay.label_text(), "deploy");
+
+        overlay.close(1);
+        assert_eq!(overlay.mode(), MarkerOverlayMode::Listing);
+        overlay.close(1);
+        assert!(!overlay.is_open());
+    }
+
+    #[test]
+    fn selection_stays_in_the_list() {
+        let mut overlay = MarkerOverlay::default();
+        overlay.open_list(3);
+
+        overlay.move_selection(-1, 3);
+        assert_eq!(overlay.selected(), 0);
+
+        for _ in 0..5 {
+            overlay.move_selection(1, 3);
+        }
+        assert_eq!(overlay.selected(), 2);
+
+        // The last marker was removed.
+        overlay.clamp_selection(2);
+        assert_eq!(overlay.selected(), 1);
+
+        overlay.clamp_selection(0);
+        assert_eq!(overlay.selected(), 0);
+    }
+}
@@ -0,0 +1,267 @@
+//! Detects when the collection thread stops sending data (e.g. due to a disk
+//! usage call stuck on a hung network mount), so that the UI can indicate the
+//! shown data is stale.
+
+use std::time::{Duration, Instant};
+
+use super::layout_manager::BottomWidgetType;
+use crate::data_collection::{CollectionProgress, CollectionStage};
+
+/// How many update intervals can pass without a harvest before the data is
+/// considered stale.
+const STALE_RATE_MULTIPLIER: u32 = 5;
+
+/// The minimum time without a harvest before the data is considered stale.
+const MIN_STALE_DURATION: Duration = Duration::from_secs(5);
+
+/// The minimum update rate for which a countdown to the next harvest is shown;
+/// anything faster would just flicker.
+const MIN_COUNTDOWN_RATE: Duration = Duration::from_secs(5);
+
+/// Information about stale data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Staleness {
+    /// How long it has been since the last harvest.
+    pub elapsed: Duration,
+
+    /// The stage the collection thread is presumed to be stuck on.
+    pub stuck: CollectionStage,
+
+    /// The stage the collection thread last completed.
+    pub last_completed: CollectionStage,
+}
+
+impl Staleness {
+    /// Whether a widget shows data from the stuck stage. If we can't tell
+    /// which stage is stuck, all widgets are affected.
+    pub fn affects(&self, widget_type: &BottomWidgetType) -> bool {
+        use BottomWidgetType::*;
+
+        match self.stuck {
+            CollectionStage::Idle | CollectionStage::Refresh => true,
+            CollectionStage::Cpu => matches!(widget_type, Cpu | CpuLegend | BasicCpu),
+            CollectionStage::Memory => matches!(widget_type, Mem | BasicMem),
+            CollectionStage::Temperature => matches!(widget_type, Temp),
+            CollectionStage::Battery => matches!(widget_type, Battery),
+            CollectionStage::Gpu => matches!(widget_type, Mem | BasicMem | Temp),
+            CollectionStage::Processes => matches!(widget_type, Proc | ProcSearch | ProcSort),
+            CollectionStage::Network => matches!(widget_type, Net | BasicNet),
+            CollectionStage::Disks => matches!(widget_type, Disk),
+        }
+    }
+
+    /// A short indicator to show on affected widgets, e.g. " (stale 42s) ".
+    pub fn indicator(&self) -> String {
+        format!(" (stale {}s) ", self.elapsed.as_secs())
+    }
+}
+
+/// A short indicator of how long until the next harvest, e.g. " next 12s ".
+pub fn countdown_indicator(countdown: Duration) -> String {
+    // Round up, so that the countdown only reaches zero once it is due.
+    let secs = countdown.as_secs() + u64::from(countdown.subsec_nanos() > 0);
+
+    if secs == 0 {
+        " updating ".to_string()
+    } else {
+        format!(" next {secs}s ")
+    }
+}
+
+/// Keeps track of when the last harvest arrived.
+#[derive(Debug)]
+pub struct CollectionWatchdog {
+    progress: CollectionProgress,
+    last_update: Instant,
+    update_rate: Duration,
+    stale_after: Duration,
+    is_stale: bool,
+}
+
+impl CollectionWatchdog {
+    pub fn new(update_rate_ms: u64, now: Instant) -> Self {
+        let mut watchdog = Self {
+            progress: CollectionProgress::default(),
+            last_update: now,
+            update_rate: Duration::ZERO,
+            stale_after: Duration::ZERO,
+            is_stale: false,
+        };
+        watchdog.set_update_rate(update_rate_ms);
+
+        watchdog
+    }
+
+    /// Changes how often harvests are expected.
+    pub fn set_update_rate(&mut self, update_rate_ms: u64) {
+        self.update_rate = Duration::from_millis(update_rate_ms);
+        self.stale_after = (self.update_rate * STALE_RATE_MULTIPLIER).max(MIN_STALE_DURATION);
+    }
+
+    /// The progress tracker that should be handed to the collection thread.
+    pub fn progress(&self) -> CollectionProgress {
+        self.progress.clone()
+    }
+
+    /// Marks that a harvest has arrived.
+    pub fn on_update(&mut self, now: Instant) {
+        self.last_update = now;
+
+        if self.is_stale {
+            self.is_stale = false;
+            crate::info!("Collection thread resumed.");
+        }
+    }
+
+    /// Returns information about the data's staleness if no harvest has arrived
+    /// in a while.
+    pub fn staleness(&self, now: Instant) -> Option<Staleness> {
+        let elapsed = now.saturating_duration_since(self.last_update);
+
+        (elapsed >= self.stale_after).then(|| Staleness {
+            elapsed,
+            stuck: self.progress.current(),
+            last_completed: self.progress.last_completed(),
+        })
+    }
+
+    /// Returns how long until the next harvest is expected, if the update rate
+    /// is slow enough for a countdown to be worth showing.
+    pub fn countdown(&self, now: Instant) -> Option<Duration> {
+        (self.update_rate >= MIN_COUNTDOWN_RATE).then(|| {
+            self.update_rate
+                .saturating_sub(now.saturating_duration_since(self.last_update))
+        })
+    }
+
+    /// Checks whether the data is stale, logging when the data first becomes
+    /// stale.
+    pub fn check(&mut self, now: Instant) -> Option<Staleness> {
+        let staleness = self.staleness(now);
+
+        match staleness {
+            Some(_staleness) if !self.is_stale => {
+                self.is_stale = true;
+                crate::warn!(
+                    "Collection thread stalled for {}s; last completed '{}', presumed stuck on '{}'.",
+                    _staleness.elapsed.as_secs(),
+                    _staleness.last_completed.name(),
+                    _staleness.stuck.name()
+                );
+            }
+            _ => {}
+        }
+
+        staleness
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A stand-in for the collection thread, which goes through the stages of a
+    /// harvest and sends the result to the watchdog.
+    struct MockCollector {
+        progress: CollectionProgress,
+    }
+
+    impl MockCollector {
+        const STAGES: [CollectionStage; 4] = [
+            CollectionStage::Refresh,
+            CollectionStage::Cpu,
+            CollectionStage::Network,
+            CollectionStage::Disks,
+        ];
+
+        fn harvest(&self, watchdog: &mut CollectionWatchdog, now: Instant) {
+            for stage in Self::STAGES {
+                self.progress.enter(stage);
+            }
+            self.progress.finish();
+            watchdog.on_update(now);
+        }
+
+        /// Starts a harvest, but gets stuck on `stuck`.
+        fn harvest_until(&self, stuck: CollectionStage) {
+            for stage in Self::STAGES {
+                self.progress.enter(stage);
+                if stage == stuck {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn stale_when_harvests_stop() {
+        let start = Instant::now();
+        let mut watchdog = CollectionWatchdog::new(1000, start);
+        let collector = MockCollector {
+            progress: watchdog.progress(),
+        };
+
+        collector.harvest(&mut watchdog, start);
+        collector.harvest(&mut watchdog, start + Duration::from_secs(1));
+        assert_eq!(watchdog.check(start + Duration::from_secs(5)), None);
+
+        // The collector stops sending while stuck on disks.
+        collector.harvest_until(CollectionStage::Disks);
+        let staleness = watchdog
+            .check(start + Duration::from_secs(43))
+            .expect("should be stale");
+        assert!(watchdog.is_stale);
+        assert_eq!(staleness.elapsed, Duration::from_secs(42));
+        assert_eq!(staleness.stuck, CollectionStage::Disks);
+        assert_eq!(staleness.last_completed, CollectionStage::Network);
+        assert_eq!(staleness.indicator(), " (stale 42s) ");
+        assert!(staleness.affects(&BottomWidgetType::Disk));
+        assert!(!staleness.affects(&BottomWidgetType::Cpu));
+
+        // The collector recovers.
+        collector.progress.finish();
+        watchdog.on_update(start + Duration::from_secs(44));
+        assert!(!watchdog.is_stale);
+        assert_eq!(watchdog.check(start + Duration::from_secs(45)), None);
+    }
+
+    #[test]
+    fn unknown_stage_affects_everything() {
+        let start = Instant::now();
+        let mut watchdog = CollectionWatchdog::new(250, start);
+
+        // Nothing has been harvested, and it isn't clear what is stuck.
+        assert_eq!(watchdog.check(start + Duration::from_secs(4)), None);
+        let staleness = watchdog
+            .check(start + MIN_STALE_DURATION)
+            .expect("should be stale");
+        assert_eq!(staleness.stuck, CollectionStage::Idle);
+        assert!(staleness.affects(&BottomWidgetType::Cpu));
+        assert!(staleness.affects(&BottomWidgetType::Disk));
+    }
+
+    #[test]
+    fn countdown() {
+        let start = Instant::now();
+        let mut watchdog = CollectionWatchdog::new(30_000, start);
+
+        let countdown = watchdog.countdown(start + Duration::from_millis(12_500));
+        assert_eq!(countdown, Some(Duration::from_millis(17_500)));
+        assert_eq!(countdown_indicator(countdown.unwrap()), " next 18s ");
+
+        // A harvest, e.g. from a manual refresh, restarts the countdown.
+        watchdog.on_update(start + Duration::from_secs(20));
+        assert_eq!(
+            watchdog.countdown(start + Duration::from_secs(20)),
+            Some(Duration::from_secs(30))
+        );
+
+        // A late harvest doesn't count below zero.
+        let countdown = watchdog.countdown(start + Duration::from_secs(60));
+        assert_eq!(countdown, Some(Duration::ZERO));
+        assert_eq!(countdown_indicator(Duration::ZERO), " updating ");
+
+        // Fast update rates don't get a countdown.
+        assert_eq!(CollectionWatchdog::new(1000, start).countdown(start), None);
+    }
+}
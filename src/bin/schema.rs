@@ -45,6 +45,21 @@ fn generate_schema(schema_options: SchemaOptions) -> anyhow::Result<()> {
             }
             _ => anyhow::bail!("missing disk columns definition"),
         }
+
+        let temp_columns = schema.definitions.get_mut("TempWidgetColumn").unwrap();
+        match temp_columns {
+            schemars::schema::Schema::Object(temp_columns) => {
+                let enums = temp_columns.enum_values.as_mut().unwrap();
+                *enums = widgets::TempWidgetColumn::VARIANTS
+                    .iter()
+                    .flat_map(|var| var.get_schema_names())
+                    .sorted()
+                    .map(|v| serde_json::Value::String(v.to_string()))
+                    .dedup()
+                    .collect();
+            }
+            _ => anyhow::bail!("missing temperature columns definition"),
+        }
     }
 
     let metadata = schema.schema.metadata.as_mut().unwrap();
@@ -1,8 +1,12 @@
 pub mod components;
 mod dialogs;
-mod drawing_utils;
+pub mod drawing_utils;
+mod snapshot;
+pub mod theme_preview;
 mod widgets;
 
+use std::time::Instant;
+
 use itertools::izip;
 use tui::{
     backend::Backend,
@@ -12,9 +16,11 @@ use tui::{
     Frame, Terminal,
 };
 
+use self::drawing_utils::ColourDepth;
 use crate::{
     app::{
         layout_manager::{BottomColRow, BottomLayout, BottomWidgetType, IntermediaryConstraint},
+        watchdog::countdown_indicator,
         App,
     },
     constants::*,
@@ -24,6 +30,7 @@ use crate::{
 /// Handles the canvas' state.
 pub struct Painter {
     pub styles: Styles,
+    pub colour_depth: ColourDepth,
     previous_height: u16,
     previous_width: u16,
 
@@ -46,8 +53,42 @@ pub enum LayoutConstraint {
     Ratio(u32, u32),
 }
 
+/// Draws a widget with `draw`, recording how long it took if draw times are
+/// being kept.
+fn timed_draw(
+    app_state: &mut App, widget_id: u64, widget_type: &BottomWidgetType,
+    draw: impl FnOnce(&mut App),
+) {
+    let start = app_state.draw_timings.start();
+    draw(app_state);
+    if let Some(start) = start {
+        app_state
+            .draw_timings
+            .record(widget_id, widget_type, start.elapsed());
+    }
+}
+
 impl Painter {
     pub fn init(layout: BottomLayout, styling: Styles) -> anyhow::Result<Self> {
+        let mut painter = Painter {
+            styles: styling,
+            colour_depth: ColourDepth::detect(),
+            previous_height: 0,
+            previous_width: 0,
+            row_constraints: Vec::default(),
+            col_constraints: Vec::default(),
+            col_row_constraints: Vec::default(),
+            layout_constraints: Vec::default(),
+            widget_layout: layout.clone(),
+            derived_widget_draw_locs: Vec::default(),
+        };
+        painter.set_layout(layout);
+
+        Ok(painter)
+    }
+
+    /// Sets the layout to draw, e.g. after switching layouts on a resize.
+    pub fn set_layout(&mut self, layout: BottomLayout) {
         // Now for modularity; we have to also initialize the base layouts!
         // We want to do this ONCE and reuse; after this we can just construct
         // based on the console size.
@@ -130,19 +171,12 @@ impl Painter {
             col_constraints.push(new_col_constraints);
         });
 
-        let painter = Painter {
-            styles: styling,
-            previous_height: 0,
-            previous_width: 0,
-            row_constraints,
-            col_constraints,
-            col_row_constraints,
-            layout_constraints,
-            widget_layout: layout,
-            derived_widget_draw_locs: Vec::default(),
-        };
-
-        Ok(painter)
+        self.row_constraints = row_constraints;
+        self.col_constraints = col_constraints;
+        self.col_row_constraints = col_row_constraints;
+        self.layout_constraints = layout_constraints;
+        self.widget_layout = layout;
+        self.derived_widget_draw_locs = Vec::default();
     }
 
     /// Determines the border style.
@@ -155,12 +189,17 @@ impl Painter {
         }
     }
 
-    fn draw_frozen_indicator(&self, f: &mut Frame<'_>, draw_loc: Rect) {
+    /// Draws the line at the bottom of the screen, which shows either the last
+    /// status message or that the data is frozen.
+    fn draw_bottom_line(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let text = app_state
+            .status_message
+            .as_deref()
+            .or(app_state.frozen_state.status_text())
+            .unwrap_or_default();
+
         f.render_widget(
-            Paragraph::new(Span::styled(
-                "Frozen, press 'f' to unfreeze",
-                self.styles.selected_text_style,
-            )),
+            Paragraph::new(Span::styled(text, self.styles.selected_text_style)),
             Layout::default()
                 .horizontal_margin(1)
                 .constraints([Constraint::Length(1)])
@@ -168,13 +207,108 @@ impl Painter {
         )
     }
 
+    /// Draws an indicator over the top border of a widget if the data it shows
+    /// has gone stale. Otherwise, the selected widget shows a countdown to the
+    /// next update.
+    ///
+    /// Returns how much of the right side of the top border is taken up.
+    fn draw_collection_indicator(
+        &self, f: &mut Frame<'_>, app_state: &App, widget_type: &BottomWidgetType, widget_id: u64,
+        draw_loc: Rect, is_expanded: bool,
+    ) -> u16 {
+        const EXPANDED_HINT: &str = " Esc to go back ";
+
+        // Skip the corner, as well as the hint shown on expanded widgets.
+        let right_offset = if is_expanded {
+            EXPANDED_HINT.len() as u16 + 1
+        } else {
+            1
+        };
+
+        let now = Instant::now();
+        let watchdog = &app_state.collection_watchdog;
+        let (indicator, style) = match watchdog.staleness(now) {
+            Some(staleness) if staleness.affects(widget_type) => {
+                (staleness.indicator(), self.styles.invalid_query_style)
+            }
+            _ if widget_id == app_state.current_widget.widget_id => match watchdog.countdown(now) {
+                Some(countdown) => (
+                    countdown_indicator(countdown),
+                    self.styles.widget_title_style,
+                ),
+                None => return right_offset,
+            },
+            _ => return right_offset,
+        };
+
+        let width = indicator.len() as u16;
+
+        if draw_loc.height == 0 || draw_loc.width < width + right_offset + 1 {
+            return right_offset;
+        }
+
+        f.render_widget(
+            Paragraph::new(Span::styled(indicator, style)),
+            Rect::new(
+                draw_loc.x + draw_loc.width - right_offset - width,
+                draw_loc.y,
+                width,
+                1,
+            ),
+        );
+
+        right_offset + width
+    }
+
+    /// Draws the average draw time of the slowest widget in its title row, to
+    /// the left of the last `right_offset` columns, if that is this widget and
+    /// `--debug_ui` is set.
+    fn draw_slowest_indicator(
+        &self, f: &mut Frame<'_>, app_state: &App, widget_id: u64, draw_loc: Rect,
+        right_offset: u16,
+    ) {
+        if !app_state.draw_timings.show_slowest() {
+            return;
+        }
+
+        let Some((slowest_id, average)) = app_state.draw_timings.slowest() else {
+            return;
+        };
+        if slowest_id != widget_id {
+            return;
+        }
+
+        let indicator = format!(" Draw {:.1}ms ", average.as_secs_f64() * 1000.0);
+        let width = indicator.len() as u16;
+        if draw_loc.height == 0 || draw_loc.width < width + right_offset + 1 {
+            return;
+        }
+
+        f.render_widget(
+            Paragraph::new(Span::styled(indicator, self.styles.widget_title_style)),
+            Rect::new(
+                draw_loc.x + draw_loc.width - right_offset - width,
+                draw_loc.y,
+                width,
+                1,
+            ),
+        );
+    }
+
     pub fn draw_data<B: Backend>(
         &mut self, terminal: &mut Terminal<B>, app_state: &mut App,
     ) -> Result<(), std::io::Error> {
         use BottomWidgetType::*;
 
         terminal.draw(|f| {
-            let (terminal_size, frozen_draw_loc) = if app_state.frozen_state.is_frozen() {
+            let full_area = f.area();
+            if let Some(layout) = app_state.fit_layout(full_area.width, full_area.height) {
+                self.set_layout(layout);
+            }
+
+            let (terminal_size, status_draw_loc) = if app_state.frozen_state.status_text().is_some()
+                || app_state.status_message.is_some()
+            {
                 // TODO: Remove built-in cache?
                 let split_loc = Layout::default()
                     .constraints([Constraint::Min(0), Constraint::Length(1)])
@@ -257,7 +391,7 @@ impl Painter {
                 {
                     7
                 } else {
-                    22
+                    (app_state.app_config_fields.kill_signals.len() as u16 + 5).min(22)
                 };
 
                 let vertical_bordering = terminal_height.saturating_sub(text_height) / 2;
@@ -283,70 +417,115 @@ impl Painter {
                 // This is a bit nasty, but it works well... I guess.
                 app_state.delete_dialog_state.is_showing_dd =
                     self.draw_dd_dialog(f, dd_text, app_state, middle_dialog_chunk[1]);
+            } else if app_state.jump_palette.is_open() {
+                let dialog_width = if terminal_width < 100 {
+                    terminal_width * 90 / 100
+                } else {
+                    terminal_width * 50 / 100
+                };
+                let dialog_height = terminal_height * 70 / 100;
+
+                let vertical_bordering = terminal_height.saturating_sub(dialog_height) / 2;
+                let horizontal_bordering = terminal_width.saturating_sub(dialog_width) / 2;
+
+                self.draw_jump_dialog(
+                    f,
+                    app_state,
+                    Rect::new(
+                        terminal_size.x + horizontal_bordering,
+                        terminal_size.y + vertical_bordering,
+                        dialog_width,
+                        dialog_height,
+                    ),
+                );
             } else if app_state.is_expanded {
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_bottom_line(f, app_state, status_draw_loc);
                 }
 
                 let rect = Layout::default()
                     .margin(0)
                     .constraints([Constraint::Percentage(100)])
                     .split(terminal_size);
-                match &app_state.current_widget.widget_type {
-                    Cpu => self.draw_cpu(f, app_state, rect[0], app_state.current_widget.widget_id),
-                    CpuLegend => self.draw_cpu(
-                        f,
-                        app_state,
-                        rect[0],
-                        app_state.current_widget.widget_id - 1,
-                    ),
-                    Mem | BasicMem => self.draw_memory_graph(
-                        f,
-                        app_state,
-                        rect[0],
-                        app_state.current_widget.widget_id,
-                    ),
-                    Disk => self.draw_disk_table(
-                        f,
-                        app_state,
-                        rect[0],
-                        app_state.current_widget.widget_id,
-                    ),
-                    Temp => self.draw_temp_table(
-                        f,
-                        app_state,
-                        rect[0],
-                        app_state.current_widget.widget_id,
-                    ),
-                    Net => self.draw_network_graph(
-                        f,
-                        app_state,
-                        rect[0],
-                        app_state.current_widget.widget_id,
-                        false,
-                    ),
-                    Proc | ProcSearch | ProcSort => {
-                        let widget_id = app_state.current_widget.widget_id
-                            - match &app_state.current_widget.widget_type {
-                                ProcSearch => 1,
-                                ProcSort => 2,
-                                _ => 0,
-                            };
-
-                        self.draw_process(f, app_state, rect[0], widget_id);
-                    }
-                    Battery =>
-                    {
-                        #[cfg(feature = "battery")]
-                        self.draw_battery(f, app_state, rect[0], app_state.current_widget.widget_id)
-                    }
-                    _ => {}
-                }
+                let widget_id = app_state.current_widget.widget_id;
+                let widget_type = app_state.current_widget.widget_type.clone();
+                timed_draw(
+                    app_state,
+                    widget_id,
+                    &widget_type,
+                    |app_state| match &app_state.current_widget.widget_type {
+                        Cpu => {
+                            self.draw_cpu(f, app_state, rect[0], app_state.current_widget.widget_id)
+                        }
+                        CpuLegend => self.draw_cpu(
+                            f,
+                            app_state,
+                            rect[0],
+                            app_state.current_widget.widget_id - 1,
+                        ),
+                        Mem | BasicMem => self.draw_memory_graph(
+                            f,
+                            app_state,
+                            rect[0],
+                            app_state.current_widget.widget_id,
+                        ),
+                        Disk => self.draw_disk_table(
+                            f,
+                            app_state,
+                            rect[0],
+                            app_state.current_widget.widget_id,
+                        ),
+                        Temp => self.draw_temp_table(
+                            f,
+                            app_state,
+                            rect[0],
+                            app_state.current_widget.widget_id,
+                        ),
+                        Net => self.draw_network_graph(
+                            f,
+                            app_state,
+                            rect[0],
+                            app_state.current_widget.widget_id,
+                            false,
+                        ),
+                        Proc | ProcSearch | ProcSort => {
+                            let widget_id = app_state.current_widget.widget_id
+                                - match &app_state.current_widget.widget_type {
+                                    ProcSearch => 1,
+                                    ProcSort => 2,
+                                    _ => 0,
+                                };
+
+                            self.draw_process(f, app_state, rect[0], widget_id);
+                        }
+                        Battery =>
+                        {
+                            #[cfg(feature = "battery")]
+                            self.draw_battery(
+                                f,
+                                app_state,
+                                rect[0],
+                                app_state.current_widget.widget_id,
+                            )
+                        }
+                        _ => {}
+                    },
+                );
+
+                let right_offset = self.draw_collection_indicator(
+                    f,
+                    app_state,
+                    &app_state.current_widget.widget_type,
+                    app_state.current_widget.widget_id,
+                    rect[0],
+                    true,
+                );
+                self.draw_slowest_indicator(f, app_state, widget_id, rect[0], right_offset);
             } else if app_state.app_config_fields.use_basic_mode {
                 // Basic mode.  This basically removes all graphs but otherwise
                 // the same info.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_bottom_line(f, app_state, status_draw_loc);
                 }
 
                 let actual_cpu_data_len = app_state.converted_data.cpu_data.len().saturating_sub(1);
@@ -416,13 +595,22 @@ impl Painter {
                     .split(vertical_chunks[1]);
 
                 if vertical_chunks[0].width >= 2 {
-                    self.draw_basic_cpu(f, app_state, vertical_chunks[0], 1);
+                    timed_draw(app_state, 1, &BasicCpu, |app_state| {
+                        self.draw_basic_cpu(f, app_state, vertical_chunks[0], 1)
+                    });
+                    self.draw_slowest_indicator(f, app_state, 1, vertical_chunks[0], 0);
                 }
                 if middle_chunks[0].width >= 2 {
-                    self.draw_basic_memory(f, app_state, middle_chunks[0], 2);
+                    timed_draw(app_state, 2, &BasicMem, |app_state| {
+                        self.draw_basic_memory(f, app_state, middle_chunks[0], 2)
+                    });
+                    self.draw_slowest_indicator(f, app_state, 2, middle_chunks[0], 0);
                 }
                 if middle_chunks[1].width >= 2 {
-                    self.draw_basic_network(f, app_state, middle_chunks[1], 3);
+                    timed_draw(app_state, 3, &BasicNet, |app_state| {
+                        self.draw_basic_network(f, app_state, middle_chunks[1], 3)
+                    });
+                    self.draw_slowest_indicator(f, app_state, 3, middle_chunks[1], 0);
                 }
 
                 let mut later_widget_id: Option<u64> = None;
@@ -430,40 +618,56 @@ impl Painter {
                     let widget_id = basic_table_widget_state.currently_displayed_widget_id;
                     later_widget_id = Some(widget_id);
                     if vertical_chunks[3].width >= 2 {
-                        match basic_table_widget_state.currently_displayed_widget_type {
-                            Disk => {
-                                self.draw_disk_table(f, app_state, vertical_chunks[3], widget_id)
-                            }
-                            Proc | ProcSort => {
-                                let wid = widget_id
-                                    - match basic_table_widget_state.currently_displayed_widget_type
-                                    {
-                                        ProcSearch => 1,
-                                        ProcSort => 2,
-                                        _ => 0,
-                                    };
-                                self.draw_process(f, app_state, vertical_chunks[3], wid);
-                            }
-                            Temp => {
-                                self.draw_temp_table(f, app_state, vertical_chunks[3], widget_id)
-                            }
-                            Battery =>
-                            {
-                                #[cfg(feature = "battery")]
-                                self.draw_battery(f, app_state, vertical_chunks[3], widget_id)
-                            }
-                            _ => {}
-                        }
+                        let widget_type = basic_table_widget_state
+                            .currently_displayed_widget_type
+                            .clone();
+                        timed_draw(
+                            app_state,
+                            widget_id,
+                            &widget_type,
+                            |app_state| match widget_type {
+                                Disk => self.draw_disk_table(
+                                    f,
+                                    app_state,
+                                    vertical_chunks[3],
+                                    widget_id,
+                                ),
+                                Proc | ProcSort => {
+                                    let wid = widget_id
+                                        - match widget_type {
+                                            ProcSearch => 1,
+                                            ProcSort => 2,
+                                            _ => 0,
+                                        };
+                                    self.draw_process(f, app_state, vertical_chunks[3], wid);
+                                }
+                                Temp => self.draw_temp_table(
+                                    f,
+                                    app_state,
+                                    vertical_chunks[3],
+                                    widget_id,
+                                ),
+                                Battery =>
+                                {
+                                    #[cfg(feature = "battery")]
+                                    self.draw_battery(f, app_state, vertical_chunks[3], widget_id)
+                                }
+                                _ => {}
+                            },
+                        );
                     }
                 }
 
                 if let Some(widget_id) = later_widget_id {
                     self.draw_basic_table_arrows(f, app_state, vertical_chunks[2], widget_id);
+
+                    // The tables have no title, so use the empty row above the arrows.
+                    self.draw_slowest_indicator(f, app_state, widget_id, vertical_chunks[2], 1);
                 }
             } else {
                 // Draws using the passed in (or default) layout.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_bottom_line(f, app_state, status_draw_loc);
                 }
 
                 if self.derived_widget_draw_locs.is_empty() || app_state.is_force_redraw {
@@ -697,6 +901,19 @@ impl Painter {
                         });
                 }
             }
+
+            if app_state.draw_timings.is_overlay_open() {
+                self.draw_timings_dialog(f, app_state, terminal_size);
+            }
+
+            // Overlays are drawn over the widgets, so what they are about stays visible.
+            if app_state.ancestry_overlay.is_open() {
+                self.draw_ancestry_dialog(f, app_state, terminal_size);
+            } else if app_state.settings_overlay.is_open() {
+                self.draw_settings_dialog(f, app_state, terminal_size);
+            } else if app_state.marker_overlay.is_open() {
+                self.draw_marker_dialog(f, app_state, terminal_size);
+            }
         })?;
 
         if let Some(updated_current_widget) = app_state
@@ -719,20 +936,51 @@ impl Painter {
         use BottomWidgetType::*;
         for (widget, draw_loc) in widgets.children.iter().zip(widget_draw_locs) {
             if draw_loc.width >= 2 && draw_loc.height >= 2 {
-                match &widget.widget_type {
-                    Cpu => self.draw_cpu(f, app_state, *draw_loc, widget.widget_id),
-                    Mem => self.draw_memory_graph(f, app_state, *draw_loc, widget.widget_id),
-                    Net => self.draw_network(f, app_state, *draw_loc, widget.widget_id),
-                    Temp => self.draw_temp_table(f, app_state, *draw_loc, widget.widget_id),
-                    Disk => self.draw_disk_table(f, app_state, *draw_loc, widget.widget_id),
-                    Proc => self.draw_process(f, app_state, *draw_loc, widget.widget_id),
-                    Battery =>
-                    {
-                        #[cfg(feature = "battery")]
-                        self.draw_battery(f, app_state, *draw_loc, widget.widget_id)
-                    }
-                    _ => {}
-                }
+                timed_draw(
+                    app_state,
+                    widget.widget_id,
+                    &widget.widget_type,
+                    |app_state| match &widget.widget_type {
+                        Cpu => self.draw_cpu(f, app_state, *draw_loc, widget.widget_id),
+                        Mem => self.draw_memory_graph(f, app_state, *draw_loc, widget.widget_id),
+                        Net => self.draw_network(f, app_state, *draw_loc, widget.widget_id),
+                        Temp => self.draw_temp_table(f, app_state, *draw_loc, widget.widget_id),
+                        Disk => self.draw_disk_table(f, app_state, *draw_loc, widget.widget_id),
+                        Proc => self.draw_process(f, app_state, *draw_loc, widget.widget_id),
+                        LoadAvg =>
+                        {
+                            #[cfg(target_family = "unix")]
+                            self.draw_load_avg(f, app_state, *draw_loc, widget.widget_id)
+                        }
+                        Battery =>
+                        {
+                            #[cfg(feature = "battery")]
+                            self.draw_battery(f, app_state, *draw_loc, widget.widget_id)
+                        }
+                        Gpu =>
+                        {
+                            #[cfg(feature = "gpu")]
+                            self.draw_gpu_graph(f, app_state, *draw_loc, widget.widget_id)
+                        }
+                        _ => {}
+                    },
+                );
+
+                let right_offset = self.draw_collection_indicator(
+                    f,
+                    app_state,
+                    &widget.widget_type,
+                    widget.widget_id,
+                    *draw_loc,
+                    false,
+                );
+                self.draw_slowest_indicator(
+                    f,
+                    app_state,
+                    widget.widget_id,
+                    *draw_loc,
+                    right_offset,
+                );
             }
         }
     }
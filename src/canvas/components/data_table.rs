@@ -11,9 +11,9 @@ use std::{convert::TryInto, marker::PhantomData};
 pub use column::*;
 pub use data_type::*;
 pub use draw::*;
-pub use props::DataTableProps;
+pub use props::{ColumnSeparator, DataTableProps};
 pub use sortable::*;
-pub use state::{DataTableState, ScrollDirection};
+pub use state::DataTableState;
 pub use styling::*;
 
 use crate::utils::general::ClampExt;
@@ -71,23 +71,42 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
     /// Sets the scroll position to the first value.
     pub fn scroll_to_first(&mut self) {
         self.state.current_index = 0;
-        self.state.scroll_direction = ScrollDirection::Up;
     }
 
     /// Sets the scroll position to the last value.
     pub fn scroll_to_last(&mut self) {
         self.state.current_index = self.data.len().saturating_sub(1);
-        self.state.scroll_direction = ScrollDirection::Down;
     }
 
     /// Updates the scroll position to be valid for the number of entries.
+    ///
+    /// If the selected entry has a [key](DataToCell::row_key) and is still
+    /// there, it stays selected and in the same place on screen, even if it
+    /// moved, e.g. from re-sorting. Otherwise, the position is kept as close as
+    /// possible.
     pub fn set_data(&mut self, data: Vec<DataType>) {
+        let selected_key = self
+            .current_item()
+            .and_then(DataToCell::row_key)
+            .map(RowKey::into_owned);
         self.data = data;
+
+        if let Some(new_index) = selected_key.and_then(|key| {
+            self.data
+                .iter()
+                .position(|item| item.row_key().as_ref() == Some(&key))
+        }) {
+            let old_index = self.state.current_index;
+            self.state.display_start_index =
+                (self.state.display_start_index + new_index).saturating_sub(old_index);
+            self.state.current_index = new_index;
+            return;
+        }
+
         let max_pos = self.data.len().saturating_sub(1);
         if self.state.current_index > max_pos {
             self.state.current_index = max_pos;
             self.state.display_start_index = 0;
-            self.state.scroll_direction = ScrollDirection::Down;
         }
     }
 
@@ -111,11 +130,6 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
             if let Ok(proposed) = proposed {
                 if proposed < self.data.len() {
                     self.state.current_index = proposed;
-                    self.state.scroll_direction = if change < 0 {
-                        ScrollDirection::Up
-                    } else {
-                        ScrollDirection::Down
-                    };
 
                     return Some(self.state.current_index);
                 }
@@ -126,14 +140,8 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
     }
 
     /// Updates the scroll position to a selected index.
-    #[expect(clippy::comparison_chain)]
     pub fn set_position(&mut self, new_index: usize) {
         let new_index = new_index.clamp_upper(self.data.len().saturating_sub(1));
-        if self.state.current_index < new_index {
-            self.state.scroll_direction = ScrollDirection::Down;
-        } else if self.state.current_index > new_index {
-            self.state.scroll_direction = ScrollDirection::Up;
-        }
         self.state.current_index = new_index;
     }
 
@@ -147,10 +155,48 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
         self.data.get(self.state.current_index)
     }
 
+    /// Returns the entries in the order they are displayed.
+    pub fn data(&self) -> &[DataType] {
+        &self.data
+    }
+
+    /// Selects the first entry matching a predicate, returning its index if
+    /// there is one.
+    pub fn select_where(&mut self, predicate: impl Fn(&DataType) -> bool) -> Option<usize> {
+        let index = self.data.iter().position(predicate)?;
+        self.set_position(index);
+
+        Some(index)
+    }
+
     /// Returns ratatui's internal selection.
     pub fn ratatui_selected(&self) -> Option<usize> {
         self.state.table_state.selected()
     }
+
+    /// Returns the index of the entry drawn at the given terminal position, if
+    /// there is one, based on the last draw.
+    pub fn index_at(&self, x: u16, y: u16) -> Option<usize> {
+        let inner_rect = self.state.inner_rect;
+        let rows_top = inner_rect.y + self.state.header_height;
+
+        if x < inner_rect.left()
+            || x >= inner_rect.right()
+            || y < rows_top
+            || y >= inner_rect.bottom()
+        {
+            return None;
+        }
+
+        let index = self.state.display_start_index + usize::from(y - rows_top);
+        (index < self.data.len()).then_some(index)
+    }
+
+    /// Returns the entry drawn at the given terminal position, if there is one,
+    /// based on the last draw.
+    pub fn item_at(&self, x: u16, y: u16) -> Option<&DataType> {
+        self.index_at(x, y).and_then(|index| self.data.get(index))
+    }
 }
 
 #[cfg(test)]
@@ -181,6 +227,53 @@ mod test {
         }
     }
 
+    /// An entry that is kept selected by its key.
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    struct KeyedType(&'static str);
+
+    impl DataToCell<&'static str> for KeyedType {
+        fn to_cell(
+            &self, _column: &&'static str, _calculated_width: NonZeroU16,
+        ) -> Option<Cow<'static, str>> {
+            None
+        }
+
+        fn row_key(&self) -> Option<RowKey<'_>> {
+            Some(RowKey::Group(Cow::Borrowed(self.0)))
+        }
+
+        fn column_widths<C: DataTableColumn<&'static str>>(
+            _data: &[Self], _columns: &[C],
+        ) -> Vec<u16>
+        where
+            Self: Sized,
+        {
+            vec![]
+        }
+    }
+
+    fn keyed_table(keys: &[&'static str]) -> DataTable<KeyedType, &'static str> {
+        let props = DataTableProps {
+            title: None,
+            table_gap: 1,
+            left_to_right: false,
+            is_basic: false,
+            show_table_scroll_position: false,
+            show_current_entry_when_unfocused: false,
+            column_separator: ColumnSeparator::default(),
+            show_scrollbar: false,
+        };
+
+        let mut table = DataTable::new([Column::hard("a", 10)], props, Default::default());
+        set_keys(&mut table, keys);
+
+        table
+    }
+
+    fn set_keys(table: &mut DataTable<KeyedType, &'static str>, keys: &[&'static str]) {
+        table.set_data(keys.iter().copied().map(KeyedType).collect());
+    }
+
     #[test]
     fn test_data_table_operations() {
         let columns = [Column::hard("a", 10), Column::hard("b", 10)];
@@ -191,6 +284,8 @@ mod test {
             is_basic: false,
             show_table_scroll_position: true,
             show_current_entry_when_unfocused: false,
+            column_separator: ColumnSeparator::default(),
+            show_scrollbar: false,
         };
         let styling = DataTableStyling::default();
 
@@ -199,54 +294,139 @@ mod test {
 
         table.scroll_to_last();
         assert_eq!(table.current_index(), 4);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
 
         table.scroll_to_first();
         assert_eq!(table.current_index(), 0);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Up);
 
         table.set_position(4);
         assert_eq!(table.current_index(), 4);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
 
         table.set_position(100);
         assert_eq!(table.current_index(), 4);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 4 }));
 
         table.increment_position(-1);
         assert_eq!(table.current_index(), 3);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Up);
         assert_eq!(table.current_item(), Some(&TestType { index: 3 }));
 
         table.increment_position(-3);
         assert_eq!(table.current_index(), 0);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Up);
         assert_eq!(table.current_item(), Some(&TestType { index: 0 }));
 
         table.increment_position(-3);
         assert_eq!(table.current_index(), 0);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Up);
         assert_eq!(table.current_item(), Some(&TestType { index: 0 }));
 
         table.increment_position(1);
         assert_eq!(table.current_index(), 1);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 1 }));
 
         table.increment_position(3);
         assert_eq!(table.current_index(), 4);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 4 }));
 
         table.increment_position(10);
         assert_eq!(table.current_index(), 4);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 4 }));
 
         table.set_data((0..=2).map(|index| TestType { index }).collect::<Vec<_>>());
         assert_eq!(table.current_index(), 2);
-        assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 2 }));
     }
+
+    #[test]
+    fn test_index_at() {
+        let columns = [Column::hard("a", 10)];
+        let props = DataTableProps {
+            title: None,
+            table_gap: 1,
+            left_to_right: false,
+            is_basic: false,
+            show_table_scroll_position: false,
+            show_current_entry_when_unfocused: false,
+            column_separator: ColumnSeparator::default(),
+            show_scrollbar: false,
+        };
+        let styling = DataTableStyling::default();
+
+        let mut table = DataTable::new(columns, props, styling);
+        table.set_data((0..10).map(|index| TestType { index }).collect::<Vec<_>>());
+
+        // A table at (5, 5) with a border, a header, and a table gap, scrolled
+        // down by 3 entries.
+        table.state.inner_rect = tui::layout::Rect::new(6, 6, 20, 6);
+        table.state.header_height = 2;
+        table.state.display_start_index = 3;
+
+        // The header and the gap.
+        assert_eq!(table.index_at(10, 6), None);
+        assert_eq!(table.index_at(10, 7), None);
+
+        assert_eq!(table.item_at(10, 8), Some(&TestType { index: 3 }));
+        assert_eq!(table.item_at(6, 11), Some(&TestType { index: 6 }));
+        assert_eq!(table.item_at(25, 9), Some(&TestType { index: 4 }));
+
+        // Outside the rows, such as the border.
+        assert_eq!(table.index_at(5, 8), None);
+        assert_eq!(table.index_at(26, 8), None);
+        assert_eq!(table.index_at(10, 12), None);
+
+        // Past the end of the data.
+        table.state.display_start_index = 8;
+        assert_eq!(table.index_at(10, 9), Some(9));
+        assert_eq!(table.index_at(10, 10), None);
+    }
+
+    #[test]
+    fn selection_follows_sorting() {
+        let mut table = keyed_table(&["a", "b", "c", "d", "e"]);
+        table.set_position(3);
+        table.state.display_start_index = 2;
+
+        // It stays in the same place on screen.
+        set_keys(&mut table, &["a", "b", "c", "e", "d"]);
+        assert_eq!(table.current_item(), Some(&KeyedType("d")));
+        assert_eq!(table.current_index(), 4);
+        assert_eq!(table.state.display_start_index, 3);
+
+        // Unless it moves too far up for that.
+        set_keys(&mut table, &["d", "e", "c", "b", "a"]);
+        assert_eq!(table.current_item(), Some(&KeyedType("d")));
+        assert_eq!(table.current_index(), 0);
+        assert_eq!(table.state.display_start_index, 0);
+    }
+
+    #[test]
+    fn selection_falls_back_to_the_nearest_entry() {
+        let mut table = keyed_table(&["a", "b", "c", "d", "e"]);
+        table.set_position(2);
+
+        set_keys(&mut table, &["a", "b", "d", "e"]);
+        assert_eq!(table.current_index(), 2);
+        assert_eq!(table.current_item(), Some(&KeyedType("d")));
+
+        table.set_position(3);
+        set_keys(&mut table, &["a", "b"]);
+        assert_eq!(table.current_index(), 1);
+        assert_eq!(table.current_item(), Some(&KeyedType("b")));
+
+        set_keys(&mut table, &[]);
+        assert_eq!(table.current_item(), None);
+        set_keys(&mut table, &["a", "b"]);
+        assert_eq!(table.current_index(), 0);
+    }
+
+    #[test]
+    fn selection_survives_filtering() {
+        let mut table = keyed_table(&["a", "b", "c", "d", "e"]);
+        table.set_position(3);
+
+        set_keys(&mut table, &["b", "d"]);
+        assert_eq!(table.current_index(), 1);
+        assert_eq!(table.current_item(), Some(&KeyedType("d")));
+
+        set_keys(&mut table, &["a", "b", "c", "d", "e"]);
+        assert_eq!(table.current_index(), 3);
+        assert_eq!(table.current_item(), Some(&KeyedType("d")));
+    }
 }
@@ -146,7 +146,9 @@ pub trait CalculateColumnWidths<H> {
     ///   try and work with.
     /// * `left_to_right` is whether to size from left-to-right (`true`) or
     ///   right-to-left (`false`).
-    fn calculate_column_widths(&self, total_width: u16, left_to_right: bool) -> Vec<NonZeroU16>;
+    fn calculate_column_widths(
+        &self, total_width: u16, left_to_right: bool, column_spacing: u16,
+    ) -> Vec<NonZeroU16>;
 }
 
 impl<H, C> CalculateColumnWidths<H> for [C]
@@ -154,11 +156,11 @@ where
     H: ColumnHeader,
     C: DataTableColumn<H>,
 {
-    fn calculate_column_widths(&self, total_width: u16, left_to_right: bool) -> Vec<NonZeroU16> {
+    fn calculate_column_widths(
+        &self, total_width: u16, left_to_right: bool, column_spacing: u16,
+    ) -> Vec<NonZeroU16> {
         use itertools::Either;
 
-        const COLUMN_SPACING: u16 = 1;
-
         #[inline]
         fn stop_allocating_space(desired: u16, available: u16) -> bool {
             desired > available || desired == 0
@@ -201,7 +203,7 @@ where
                         break;
                     } else {
                         total_width_left =
-                            total_width_left.saturating_sub(space_taken + COLUMN_SPACING);
+                            total_width_left.saturating_sub(space_taken + column_spacing);
 
                         // SAFETY: This is safe as we call `stop_allocating_space` which checks that
                         // the value pushed is greater than zero.
@@ -216,7 +218,7 @@ where
                         break;
                     } else {
                         total_width_left =
-                            total_width_left.saturating_sub(min_width + COLUMN_SPACING);
+                            total_width_left.saturating_sub(min_width + column_spacing);
 
                         // SAFETY: This is safe as we call `stop_allocating_space` which checks that
                         // the value pushed is greater than zero.
@@ -231,7 +233,7 @@ where
                         break;
                     } else {
                         total_width_left =
-                            total_width_left.saturating_sub(min_width + COLUMN_SPACING);
+                            total_width_left.saturating_sub(min_width + column_spacing);
 
                         // SAFETY: This is safe as we call `stop_allocating_space` which checks that
                         // the value pushed is greater than zero.
@@ -1,9 +1,43 @@
 use std::{borrow::Cow, num::NonZeroU16};
 
-use tui::widgets::Row;
+use tui::{style::Style, widgets::Row};
 
 use super::{ColumnHeader, DataTableColumn};
-use crate::canvas::Painter;
+use crate::{canvas::Painter, data_collection::processes::Pid};
+
+/// What identifies a table entry between data updates. See
+/// [`DataToCell::row_key`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RowKey<'a> {
+    /// A process, by its PID.
+    Pid(Pid),
+
+    /// A group of processes, by what they're grouped by.
+    Group(Cow<'a, str>),
+
+    /// An entry by its name, and by how many entries before it have the same
+    /// name, as names aren't always unique.
+    Name(Cow<'a, str>, usize),
+
+    /// A disk, by its name and where it's mounted, as a disk can be mounted in
+    /// more than one place.
+    Mount(Cow<'a, str>, Cow<'a, str>),
+}
+
+impl RowKey<'_> {
+    /// Returns a copy of the key that doesn't borrow from its entry.
+    pub fn into_owned(self) -> RowKey<'static> {
+        match self {
+            RowKey::Pid(pid) => RowKey::Pid(pid),
+            RowKey::Group(id) => RowKey::Group(Cow::Owned(id.into_owned())),
+            RowKey::Name(name, index) => RowKey::Name(Cow::Owned(name.into_owned()), index),
+            RowKey::Mount(name, mount_point) => RowKey::Mount(
+                Cow::Owned(name.into_owned()),
+                Cow::Owned(mount_point.into_owned()),
+            ),
+        }
+    }
+}
 
 pub trait DataToCell<H>
 where
@@ -22,6 +56,24 @@ where
         row
     }
 
+    /// Returns a style for a single cell, applied on top of the row's style.
+    ///
+    /// The default implementation doesn't style any cells.
+    #[inline(always)]
+    fn style_cell(&self, _column: &H, _painter: &Painter) -> Option<Style> {
+        None
+    }
+
+    /// Returns what identifies this entry between data updates, so that it
+    /// stays selected even if it moves. Entries without one are selected by
+    /// position.
+    ///
+    /// The default implementation returns [`None`].
+    #[inline(always)]
+    fn row_key(&self) -> Option<RowKey<'_>> {
+        None
+    }
+
     /// Returns the desired column widths in light of having seen data.
     fn column_widths<C: DataTableColumn<H>>(data: &[Self], columns: &[C]) -> Vec<u16>
     where
@@ -1,25 +1,29 @@
 use std::{
     cmp::{max, min},
     iter::once,
+    num::NonZeroU16,
+    time::Instant,
 };
 
 use concat_string::concat_string;
 use tui::{
     layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    symbols,
     text::{Line, Span, Text},
-    widgets::{Block, Row, Table},
+    widgets::{Block, Cell, Row, Table},
     Frame,
 };
 
 use super::{
-    CalculateColumnWidths, ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataToCell,
-    SortType,
+    CalculateColumnWidths, ColumnHeader, ColumnSeparator, ColumnWidthBounds, DataTable,
+    DataTableColumn, DataToCell, SortType,
 };
 use crate::{
     app::layout_manager::BottomWidget,
-    canvas::{drawing_utils::widget_block, Painter},
+    canvas::{components::scrollbar::Scrollbar, drawing_utils::widget_block, Painter},
     constants::TABLE_GAP_HEIGHT_LIMIT,
-    utils::strings::truncate_to_text,
+    utils::strings::truncate_cell,
 };
 
 pub enum SelectionState {
@@ -59,6 +63,65 @@ impl DrawInfo {
     }
 }
 
+/// Returns the x positions of the lines between columns of the given widths,
+/// where the first column starts at `x`. Each line is in the middle of the gap
+/// after a column, and there is no line after the last column.
+fn separator_positions(x: u16, widths: &[NonZeroU16], spacing: u16) -> Vec<u16> {
+    let mut positions = Vec::with_capacity(widths.len().saturating_sub(1));
+    let mut column_end = x;
+
+    for width in widths.iter().take(widths.len().saturating_sub(1)) {
+        column_end = column_end.saturating_add(width.get());
+        positions.push(column_end.saturating_add(spacing / 2));
+        column_end = column_end.saturating_add(spacing);
+    }
+
+    positions
+}
+
+/// Splits the width taken by the `calculated` widths between the same number
+/// of columns by their shares of it. Columns without a share keep their
+/// calculated width, and the rest split what is left by their shares. Returns
+/// [`None`] if the number of columns differs, such as when some don't fit, or
+/// a column would be empty.
+fn scale_resized_widths(
+    shares: &[Option<f32>], calculated: &[NonZeroU16],
+) -> Option<Vec<NonZeroU16>> {
+    if shares.len() != calculated.len() {
+        return None;
+    }
+
+    let kept = shares
+        .iter()
+        .zip(calculated)
+        .filter(|(share, _)| share.is_none())
+        .map(|(_, width)| width.get())
+        .sum::<u16>();
+    let total = f32::from(calculated.iter().map(|width| width.get()).sum::<u16>() - kept);
+    let total_share = shares.iter().flatten().sum::<f32>();
+    let mut column_end = 0.0;
+    let mut previous_end = 0;
+
+    // Round where each column ends rather than each width, so the widths still
+    // add up to the total.
+    shares
+        .iter()
+        .zip(calculated)
+        .map(|(share, width)| {
+            let Some(share) = share else {
+                return Some(*width);
+            };
+
+            column_end += share / total_share * total;
+            let end = (column_end.round() as u16).min(total as u16);
+            let width = NonZeroU16::new(end - previous_end.min(end));
+            previous_end = end;
+
+            width
+        })
+        .collect()
+}
+
 impl<DataType, H, S, C> DataTable<DataType, H, S, C>
 where
     DataType: DataToCell<H>,
@@ -169,9 +232,26 @@ where
                         }
                     });
 
-                self.state.calculated_widths = self
-                    .columns
-                    .calculate_column_widths(inner_width, self.props.left_to_right);
+                self.state.calculated_widths = self.columns.calculate_column_widths(
+                    inner_width,
+                    self.props.left_to_right,
+                    self.props.column_separator.width(),
+                );
+                if !self.state.resized_widths.is_empty() {
+                    let shares = self
+                        .columns
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, column)| !column.is_hidden())
+                        .map(|(index, _)| self.state.resized_widths.get(&index).copied())
+                        .collect::<Vec<_>>();
+
+                    if let Some(widths) =
+                        scale_resized_widths(&shares, &self.state.calculated_widths)
+                    {
+                        self.state.calculated_widths = widths;
+                    }
+                }
 
                 // Update draw loc in widget map
                 if let Some(widget) = widget {
@@ -188,6 +268,7 @@ where
             } else {
                 self.props.table_gap
             };
+            self.state.header_height = header_height + table_gap;
 
             if !self.data.is_empty() || !self.first_draw {
                 if self.first_draw {
@@ -200,9 +281,8 @@ where
                 }
 
                 let columns = &self.columns;
+                let num_rows = usize::from(inner_height.saturating_sub(table_gap + header_height));
                 let rows = {
-                    let num_rows =
-                        usize::from(inner_height.saturating_sub(table_gap + header_height));
                     self.state
                         .get_start_position(num_rows, draw_info.force_redraw);
                     let start = self.state.display_start_index;
@@ -215,11 +295,20 @@ where
                         let row = Row::new(
                             columns
                                 .iter()
+                                .filter(|column| !column.is_hidden())
                                 .zip(&self.state.calculated_widths)
                                 .filter_map(|(column, &width)| {
-                                    data_row
-                                        .to_cell(column.inner(), width)
-                                        .map(|content| truncate_to_text(&content, width.get()))
+                                    data_row.to_cell(column.inner(), width).map(|content| {
+                                        let cell = Cell::from(truncate_cell(
+                                            content,
+                                            usize::from(width.get()),
+                                        ));
+
+                                        match data_row.style_cell(column.inner(), painter) {
+                                            Some(style) => cell.style(style),
+                                            None => cell,
+                                        }
+                                    })
                                 }),
                         );
 
@@ -234,7 +323,15 @@ where
                     .bottom_margin(table_gap);
 
                 let widget = {
-                    let highlight_style = if draw_info.is_on_widget()
+                    let is_highlighted = self
+                        .state
+                        .highlight_until
+                        .is_some_and(|until| Instant::now() < until);
+                    let highlight_style = if is_highlighted {
+                        self.styling
+                            .highlighted_text_style
+                            .add_modifier(Modifier::REVERSED | Modifier::BOLD)
+                    } else if draw_info.is_on_widget()
                         || self.props.show_current_entry_when_unfocused
                     {
                         self.styling.highlighted_text_style
@@ -246,6 +343,7 @@ where
                         self.state.calculated_widths.iter().map(|nzu| nzu.get()),
                     )
                     .block(block)
+                    .column_spacing(self.props.column_separator.width())
                     .row_highlight_style(highlight_style)
                     .style(self.styling.text_style);
 
@@ -258,6 +356,40 @@ where
 
                 let table_state = &mut self.state.table_state;
                 f.render_stateful_widget(widget, margined_draw_loc, table_state);
+
+                // Basic tables only have a right border when selected.
+                if self.props.show_scrollbar && (!self.props.is_basic || draw_info.is_on_widget()) {
+                    let border_style = if draw_info.is_on_widget() {
+                        self.styling.highlighted_border_style
+                    } else {
+                        self.styling.border_style
+                    };
+
+                    Scrollbar::new(
+                        self.state.display_start_index,
+                        self.data.len().saturating_sub(num_rows),
+                        num_rows,
+                    )
+                    .style(border_style)
+                    .draw(f.buffer_mut(), margined_draw_loc);
+                }
+
+                if let ColumnSeparator::Line = self.props.column_separator {
+                    let inner_rect = self.state.inner_rect;
+                    let buffer = f.buffer_mut();
+
+                    for x in separator_positions(
+                        inner_rect.x,
+                        &self.state.calculated_widths,
+                        self.props.column_separator.width(),
+                    ) {
+                        for y in inner_rect.top()..inner_rect.bottom() {
+                            if let Some(cell) = buffer.cell_mut((x, y)) {
+                                cell.set_symbol(symbols::line::VERTICAL);
+                            }
+                        }
+                    }
+                }
             } else {
                 let table = Table::new(
                     once(Row::new(Text::raw("No data"))),
@@ -270,3 +402,59 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn widths(widths: &[u16]) -> Vec<NonZeroU16> {
+        widths
+            .iter()
+            .map(|&width| NonZeroU16::new(width).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn separators_are_between_columns() {
+        // "PID" at 2..7, "Name" at 10..18, and "CPU%" at 21..26, with lines in
+        // the gaps at 8 and 19.
+        assert_eq!(
+            separator_positions(2, &widths(&[5, 8, 5]), ColumnSeparator::Line.width()),
+            vec![8, 19]
+        );
+
+        assert!(separator_positions(2, &widths(&[5]), 3).is_empty());
+        assert!(separator_positions(2, &[], 3).is_empty());
+    }
+
+    #[test]
+    fn resized_widths_are_scaled() {
+        assert_eq!(
+            scale_resized_widths(&[Some(0.25), Some(0.5), Some(0.25)], &widths(&[10, 10, 20])),
+            Some(widths(&[10, 20, 10]))
+        );
+
+        // Rounding doesn't change the total.
+        let scaled = scale_resized_widths(&[Some(1.0 / 3.0); 3], &widths(&[30, 1, 1])).unwrap();
+        assert_eq!(scaled.iter().map(|width| width.get()).sum::<u16>(), 32);
+
+        // Newly shown columns keep their width, and the rest split what's left,
+        // even if a resized column was hidden since.
+        assert_eq!(
+            scale_resized_widths(&[Some(0.25), None, Some(0.25)], &widths(&[10, 8, 12])),
+            Some(widths(&[11, 8, 11]))
+        );
+
+        // Not every column fits.
+        assert_eq!(
+            scale_resized_widths(&[Some(0.5), Some(0.5)], &widths(&[10])),
+            None
+        );
+
+        // A column would be too narrow to show.
+        assert_eq!(
+            scale_resized_widths(&[Some(0.01), Some(0.99)], &widths(&[5, 5])),
+            None
+        );
+    }
+}
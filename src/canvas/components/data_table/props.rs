@@ -1,5 +1,31 @@
 use std::borrow::Cow;
 
+use serde::Deserialize;
+
+/// What is drawn between the columns of a table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnSeparator {
+    /// A single space.
+    #[default]
+    Space,
+    /// Three spaces.
+    Padding,
+    /// A vertical line, with a space on either side.
+    Line,
+}
+
+impl ColumnSeparator {
+    /// The width of the gap between two columns.
+    pub fn width(self) -> u16 {
+        match self {
+            ColumnSeparator::Space => 1,
+            ColumnSeparator::Padding | ColumnSeparator::Line => 3,
+        }
+    }
+}
+
 pub struct DataTableProps {
     /// An optional title for the table.
     pub title: Option<Cow<'static, str>>,
@@ -18,4 +44,11 @@ pub struct DataTableProps {
 
     /// Whether to show the current entry as highlighted when not focused.
     pub show_current_entry_when_unfocused: bool,
+
+    /// What to draw between columns.
+    pub column_separator: ColumnSeparator,
+
+    /// Whether to show a scrollbar on the right border when there are more
+    /// rows than fit.
+    pub show_scrollbar: bool,
 }
@@ -48,6 +48,9 @@ pub struct Sortable {
 
     /// The current sorting order.
     pub order: SortOrder,
+
+    /// Whether text is sorted by case.
+    pub case_sensitive: bool,
 }
 
 /// The [`SortType`] trait is meant to be used in the typing of a [`DataTable`]
@@ -66,6 +69,7 @@ pub trait SortType: private::Sealed {
         Row::new(
             columns
                 .iter()
+                .filter(|c| !c.is_hidden())
                 .zip(widths)
                 .map(|(c, &width)| truncate_to_text(&c.header(), width.get())),
         )
@@ -95,9 +99,10 @@ impl SortType for Sortable {
         Row::new(
             columns
                 .iter()
-                .zip(widths)
                 .enumerate()
-                .map(|(index, (c, &width))| {
+                .filter(|(_, c)| !c.is_hidden())
+                .zip(widths)
+                .map(|((index, c), &width)| {
                     if index == self.sort_index {
                         let arrow = match self.order {
                             SortOrder::Ascending => UP_ARROW,
@@ -119,8 +124,9 @@ impl SortType for Sortable {
 pub trait SortsRow {
     type DataType;
 
-    /// Sorts data.
-    fn sort_data(&self, data: &mut [Self::DataType], descending: bool);
+    /// Sorts data. Text is sorted ignoring case unless `case_sensitive` is
+    /// set.
+    fn sort_data(&self, data: &mut [Self::DataType], descending: bool, case_sensitive: bool);
 }
 
 #[derive(Debug, Clone)]
@@ -226,9 +232,9 @@ where
 
     /// Given a [`SortColumn`] and the sort order, sort a mutable slice of
     /// associated data.
-    pub fn sort_by(&self, data: &mut [D], order: SortOrder) {
+    pub fn sort_by(&self, data: &mut [D], order: SortOrder, case_sensitive: bool) {
         let descending = matches!(order, SortOrder::Descending);
-        self.inner.sort_data(data, descending);
+        self.inner.sort_data(data, descending, case_sensitive);
     }
 }
 
@@ -236,6 +242,7 @@ pub struct SortDataTableProps {
     pub inner: DataTableProps,
     pub sort_index: usize,
     pub order: SortOrder,
+    pub case_sensitive: bool,
 }
 
 /// A type alias for a sortable [`DataTable`].
@@ -257,6 +264,7 @@ where
             sort_type: Sortable {
                 sort_index: props.sort_index,
                 order: props.order,
+                case_sensitive: props.case_sensitive,
             },
             first_draw: true,
             first_index: None,
@@ -275,6 +283,11 @@ where
         self.sort_type.order
     }
 
+    /// Whether text is sorted by case.
+    pub fn is_case_sensitive(&self) -> bool {
+        self.sort_type.case_sensitive
+    }
+
     /// Toggles the current sort order.
     pub fn toggle_order(&mut self) {
         self.sort_type.order = match self.sort_type.order {
@@ -301,6 +314,84 @@ where
         }
     }
 
+    /// Returns the index of the shown column whose right border is at `x` and
+    /// `y`, which is in the gap between it and the next column in the header.
+    pub fn column_border_at(&self, x: u16, y: u16) -> Option<usize> {
+        if self.state.inner_rect.height <= 1 || self.state.inner_rect.y != y {
+            return None;
+        }
+
+        let spacing = self.props.column_separator.width();
+        let widths = &self.state.calculated_widths;
+        let mut column_start = self.state.inner_rect.x;
+
+        widths
+            .iter()
+            .take(widths.len().saturating_sub(1))
+            .enumerate()
+            .find_map(|(index, width)| {
+                let gap_start = column_start + width.get();
+                column_start = gap_start + spacing;
+
+                (gap_start..column_start).contains(&x).then_some(index)
+            })
+    }
+
+    /// Moves the right border of the shown column at `index` to `x`, which
+    /// resizes it and the column after it. The new widths are kept when the
+    /// table is resized. Returns whether the widths changed.
+    pub fn resize_column(&mut self, index: usize, x: u16) -> bool {
+        let spacing = self.props.column_separator.width();
+        let widths = &mut self.state.calculated_widths;
+        if index + 1 >= widths.len() {
+            return false;
+        }
+
+        let column_start = self.state.inner_rect.x
+            + widths[..index]
+                .iter()
+                .map(|width| width.get() + spacing)
+                .sum::<u16>();
+        let both = widths[index].get() + widths[index + 1].get();
+        let width = x.saturating_sub(column_start).clamp(1, both - 1);
+
+        match (NonZeroU16::new(width), NonZeroU16::new(both - width)) {
+            (Some(left), Some(right)) if left != widths[index] => {
+                widths[index] = left;
+                widths[index + 1] = right;
+            }
+            _ => return false,
+        }
+
+        let total = f32::from(widths.iter().map(|width| width.get()).sum::<u16>());
+        self.state.resized_widths = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| !column.is_hidden())
+            .zip(widths.iter())
+            .map(|((column, _), width)| (column, f32::from(width.get()) / total))
+            .collect();
+
+        true
+    }
+
+    /// Returns how wide the column at `index` was last drawn, or [`None`] if it
+    /// is hidden or there wasn't room for it. Hidden columns have no width, so
+    /// only the shown columns before it are counted.
+    pub fn column_width(&self, index: usize) -> Option<NonZeroU16> {
+        if self.columns.get(index)?.is_hidden() {
+            return None;
+        }
+
+        let shown_index = self.columns[..index]
+            .iter()
+            .filter(|column| !column.is_hidden())
+            .count();
+
+        self.state.calculated_widths.get(shown_index).copied()
+    }
+
     /// Updates the sort index, and sets the sort order as appropriate.
     ///
     /// If the index is different from the previous one, it will move to the new
@@ -322,31 +413,43 @@ where
         self.sort_type.sort_index
     }
 
-    /// Given a `needle` coordinate, select the corresponding index and value.
+    /// Given a `needle` coordinate, select the corresponding column index.
     fn get_range(&self, needle: u16) -> Option<usize> {
         let mut start = self.state.inner_rect.x;
+        let spacing = self.props.column_separator.width();
         let range = self
             .state
             .calculated_widths
             .iter()
             .map(|width| {
                 let entry_start = start;
-                start += width.get() + 1; // +1 for the gap b/w cols.
+                start += width.get() + spacing;
 
                 entry_start
             })
             .collect_vec();
 
-        match range.binary_search(&needle) {
+        let shown_index = match range.binary_search(&needle) {
             Ok(index) => Some(index),
             Err(index) => index.checked_sub(1),
-        }
+        }?;
+
+        // Hidden columns have no width, so skip over them.
+        self.columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| !column.is_hidden())
+            .nth(shown_index)
+            .map(|(index, _)| index)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use tui::layout::Rect;
+
     use super::*;
+    use crate::canvas::components::data_table::ColumnSeparator;
 
     #[derive(Clone, PartialEq, Eq, Debug)]
     struct TestType {
@@ -386,7 +489,7 @@ mod test {
     impl SortsRow for ColumnType {
         type DataType = TestType;
 
-        fn sort_data(&self, data: &mut [TestType], descending: bool) {
+        fn sort_data(&self, data: &mut [TestType], descending: bool, _case_sensitive: bool) {
             match self {
                 ColumnType::Index => data.sort_by_key(|t| t.index),
                 ColumnType::Data => data.sort_by_key(|t| t.data),
@@ -398,6 +501,116 @@ mod test {
         }
     }
 
+    fn table() -> SortDataTable<TestType, ColumnType> {
+        let columns = [
+            SortColumn::new(ColumnType::Index),
+            SortColumn::new(ColumnType::Data),
+            SortColumn::new(ColumnType::Data),
+        ];
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                title: None,
+                table_gap: 0,
+                left_to_right: true,
+                is_basic: false,
+                show_table_scroll_position: false,
+                show_current_entry_when_unfocused: false,
+                column_separator: ColumnSeparator::Line,
+                show_scrollbar: false,
+            },
+            sort_index: 0,
+            order: SortOrder::Ascending,
+            case_sensitive: false,
+        };
+
+        let mut table = DataTable::new_sortable(columns, props, DataTableStyling::default());
+        // Columns at 1..11, 14..24, and 27..37.
+        table.state.inner_rect = Rect::new(1, 1, 36, 10);
+        table.state.calculated_widths = [10, 10, 10]
+            .map(|width| NonZeroU16::new(width).unwrap())
+            .to_vec();
+
+        table
+    }
+
+    fn widths(table: &SortDataTable<TestType, ColumnType>) -> Vec<u16> {
+        table
+            .state
+            .calculated_widths
+            .iter()
+            .map(|width| width.get())
+            .collect()
+    }
+
+    #[test]
+    fn column_widths_skip_hidden_columns() {
+        let mut table = table();
+        table.columns[0].is_hidden = true;
+        table.state.calculated_widths =
+            [4, 7].map(|width| NonZeroU16::new(width).unwrap()).to_vec();
+
+        let width = |table: &SortDataTable<TestType, ColumnType>, index| {
+            table.column_width(index).map(NonZeroU16::get)
+        };
+        assert_eq!(width(&table, 0), None);
+        assert_eq!(width(&table, 1), Some(4));
+        assert_eq!(width(&table, 2), Some(7));
+        assert_eq!(width(&table, 3), None);
+    }
+
+    #[test]
+    fn column_borders() {
+        let table = table();
+
+        assert_eq!(table.column_border_at(11, 1), Some(0));
+        assert_eq!(table.column_border_at(13, 1), Some(0));
+        assert_eq!(table.column_border_at(24, 1), Some(1));
+
+        // Inside a column, after the last one, or not in the header.
+        assert_eq!(table.column_border_at(10, 1), None);
+        assert_eq!(table.column_border_at(14, 1), None);
+        assert_eq!(table.column_border_at(37, 1), None);
+        assert_eq!(table.column_border_at(11, 2), None);
+    }
+
+    #[test]
+    fn resizing_columns() {
+        let mut table = table();
+
+        assert!(table.resize_column(0, 15));
+        assert_eq!(widths(&table), [14, 6, 10]);
+        let shares = &table.state.resized_widths;
+        assert_eq!(shares.len(), 3);
+        assert!((shares[&0] - 14.0 / 30.0).abs() < f32::EPSILON);
+        assert!(!table.resize_column(0, 15));
+
+        // The column after can't be shrunk away.
+        assert!(table.resize_column(0, 100));
+        assert_eq!(widths(&table), [19, 1, 10]);
+        assert!(!table.resize_column(1, 18));
+        assert!(table.resize_column(1, 26));
+        assert_eq!(widths(&table), [19, 3, 8]);
+
+        // There is no border after the last column.
+        assert!(!table.resize_column(2, 20));
+    }
+
+    #[test]
+    fn resized_widths_are_kept_by_column() {
+        let mut table = table();
+        table.columns[0].is_hidden = true;
+        table.state.calculated_widths = [10, 10]
+            .map(|width| NonZeroU16::new(width).unwrap())
+            .to_vec();
+
+        // The first shown column is the second column.
+        assert!(table.resize_column(0, 6));
+        assert_eq!(widths(&table), [5, 15]);
+        let mut shares = table.state.resized_widths.iter().collect::<Vec<_>>();
+        shares.sort_by_key(|(column, _)| **column);
+        assert_eq!(shares, [(&1, &0.25), (&2, &0.75)]);
+    }
+
     #[test]
     fn test_sorting() {
         let columns = [
@@ -412,12 +625,15 @@ mod test {
                 is_basic: false,
                 show_table_scroll_position: true,
                 show_current_entry_when_unfocused: false,
+                column_separator: ColumnSeparator::default(),
+                show_scrollbar: false,
             };
 
             SortDataTableProps {
                 inner,
                 sort_index: 0,
                 order: SortOrder::Descending,
+                case_sensitive: false,
             }
         };
 
@@ -451,7 +667,7 @@ mod test {
             .columns
             .get(table.sort_type.sort_index)
             .unwrap()
-            .sort_by(&mut data, SortOrder::Ascending);
+            .sort_by(&mut data, SortOrder::Ascending, false);
         assert_eq!(
             data,
             vec![
@@ -482,7 +698,7 @@ mod test {
             .columns
             .get(table.sort_type.sort_index)
             .unwrap()
-            .sort_by(&mut data, SortOrder::Descending);
+            .sort_by(&mut data, SortOrder::Descending, false);
         assert_eq!(
             data,
             vec![
@@ -514,7 +730,7 @@ mod test {
             .columns
             .get(table.sort_type.sort_index)
             .unwrap()
-            .sort_by(&mut data, SortOrder::Ascending);
+            .sort_by(&mut data, SortOrder::Ascending, false);
         assert_eq!(
             data,
             vec![
@@ -1,17 +1,8 @@
-use std::num::NonZeroU16;
+use std::{num::NonZeroU16, time::Instant};
 
+use hashbrown::HashMap;
 use tui::{layout::Rect, widgets::TableState};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
-pub enum ScrollDirection {
-    // UP means scrolling up --- this usually DECREMENTS
-    Up,
-
-    // DOWN means scrolling down --- this usually INCREMENTS
-    #[default]
-    Down,
-}
-
 /// Internal state representation of a [`DataTable`](super::DataTable).
 pub struct DataTableState {
     /// The index from where to start displaying the rows.
@@ -20,17 +11,27 @@ pub struct DataTableState {
     /// The current scroll position.
     pub current_index: usize,
 
-    /// The direction of the last attempted scroll.
-    pub scroll_direction: ScrollDirection,
-
     /// ratatui's internal table state.
     pub table_state: TableState,
 
     /// The calculated widths.
     pub calculated_widths: Vec<NonZeroU16>,
 
+    /// The share of the width each column took when the columns were last
+    /// resized by dragging their borders, keyed by the column's index. This is
+    /// kept when the table is resized, and columns shown since then keep their
+    /// calculated widths.
+    pub resized_widths: HashMap<usize, f32>,
+
     /// The current inner [`Rect`].
     pub inner_rect: Rect,
+
+    /// The height of the header, including the table gap, as of the last draw.
+    pub header_height: u16,
+
+    /// If set, the current entry is emphasized until this time, e.g. after
+    /// jumping to it.
+    pub highlight_until: Option<Instant>,
 }
 
 impl Default for DataTableState {
@@ -38,16 +39,19 @@ impl Default for DataTableState {
         Self {
             display_start_index: 0,
             current_index: 0,
-            scroll_direction: ScrollDirection::Down,
             calculated_widths: vec![],
+            resized_widths: HashMap::new(),
             table_state: TableState::default(),
             inner_rect: Rect::default(),
+            header_height: 0,
+            highlight_until: None,
         }
     }
 }
 
 impl DataTableState {
-    /// Gets the starting position of a table.
+    /// Gets the starting position of a table, such that the current entry is
+    /// always in view, no matter how far or in which direction it moved.
     pub fn get_start_position(&mut self, num_rows: usize, is_force_redraw: bool) {
         let start_index = if is_force_redraw {
             0
@@ -55,34 +59,89 @@ impl DataTableState {
             self.display_start_index
         };
         let current_scroll_position = self.current_index;
-        let scroll_direction = self.scroll_direction;
-
-        self.display_start_index = match scroll_direction {
-            ScrollDirection::Down => {
-                if current_scroll_position < start_index + num_rows {
-                    // If, using the current scroll position, we can see the element
-                    // (so within that and + num_rows) just reuse the current previously
-                    // scrolled position.
-                    start_index
-                } else if current_scroll_position >= num_rows {
-                    // If the current position past the last element visible in the list,
-                    // then skip until we can see that element.
-                    current_scroll_position - num_rows + 1
-                } else {
-                    // Else, if it is not past the last element visible, do not omit anything.
-                    0
-                }
-            }
-            ScrollDirection::Up => {
-                if current_scroll_position <= start_index {
-                    // If it's past the first element, then show from that element downwards
-                    current_scroll_position
-                } else if current_scroll_position >= start_index + num_rows {
-                    current_scroll_position - num_rows + 1
-                } else {
-                    start_index
-                }
-            }
+
+        self.display_start_index = if current_scroll_position < start_index {
+            // If the current position is above the first visible element, then
+            // show from that element downwards.
+            current_scroll_position
+        } else if current_scroll_position >= start_index + num_rows {
+            // If the current position is past the last visible element, then skip
+            // until we can see that element.
+            (current_scroll_position + 1).saturating_sub(num_rows.max(1))
+        } else {
+            // Otherwise, we can already see the element, so just reuse the
+            // previously scrolled position.
+            start_index
         };
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn scroll(state: &mut DataTableState, index: usize, num_rows: usize) -> usize {
+        state.current_index = index;
+        state.get_start_position(num_rows, false);
+
+        state.display_start_index
+    }
+
+    #[test]
+    fn scrolling_down() {
+        let mut state = DataTableState::default();
+
+        assert_eq!(scroll(&mut state, 1, 5), 0);
+        assert_eq!(scroll(&mut state, 4, 5), 0);
+        assert_eq!(scroll(&mut state, 5, 5), 1);
+        assert_eq!(scroll(&mut state, 20, 5), 16);
+    }
+
+    #[test]
+    fn scrolling_up_by_one() {
+        let mut state = DataTableState::default();
+        scroll(&mut state, 20, 5);
+
+        // Moving within the view doesn't scroll.
+        assert_eq!(scroll(&mut state, 19, 5), 16);
+        assert_eq!(scroll(&mut state, 16, 5), 16);
+
+        // Moving past the top scrolls by one.
+        assert_eq!(scroll(&mut state, 15, 5), 15);
+        assert_eq!(scroll(&mut state, 14, 5), 14);
+    }
+
+    #[test]
+    fn jumping_up() {
+        let mut state = DataTableState::default();
+        scroll(&mut state, 20, 5);
+
+        assert_eq!(scroll(&mut state, 10, 5), 10);
+        assert_eq!(scroll(&mut state, 8, 5), 8);
+
+        // Back to the top.
+        scroll(&mut state, 20, 5);
+        assert_eq!(scroll(&mut state, 0, 5), 0);
+    }
+
+    #[test]
+    fn current_entry_is_always_shown() {
+        let mut state = DataTableState::default();
+        scroll(&mut state, 20, 5);
+
+        // Even if the last scroll was downwards, e.g. after the data shrank.
+        state.current_index = 2;
+        state.get_start_position(5, false);
+        assert_eq!(state.display_start_index, 2);
+
+        // Forcing a redraw starts from the top again.
+        state.current_index = 3;
+        state.get_start_position(5, true);
+        assert_eq!(state.display_start_index, 0);
+
+        // Even with no space.
+        state.current_index = 7;
+        state.get_start_position(0, false);
+        assert_eq!(state.display_start_index, 7);
+    }
+}
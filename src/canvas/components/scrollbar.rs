@@ -0,0 +1,131 @@
+//! A scrollbar drawn over the right border of a widget.
+
+use tui::{buffer::Buffer, layout::Rect, style::Style, symbols};
+
+/// The part of a scrollbar's track showing what is in view, in cells from
+/// the top of the track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Thumb {
+    pub start: u16,
+    pub len: u16,
+}
+
+/// A scrollbar for something scrolled `current` lines down, out of at most
+/// `max`, showing `viewport_height` lines at a time.
+pub struct Scrollbar {
+    current: usize,
+    max: usize,
+    viewport_height: usize,
+    style: Style,
+}
+
+impl Scrollbar {
+    pub fn new(current: usize, max: usize, viewport_height: usize) -> Self {
+        Self {
+            current,
+            max,
+            viewport_height,
+            style: Style::default(),
+        }
+    }
+
+    /// Sets the style of the thumb.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Returns where the thumb goes in a track of `track_len` cells, or
+    /// [`None`] if everything fits and there is nothing to scroll.
+    pub fn thumb(&self, track_len: u16) -> Option<Thumb> {
+        if self.max == 0 || track_len == 0 {
+            return None;
+        }
+
+        let track = usize::from(track_len);
+        let total = self.max + self.viewport_height;
+
+        // The thumb is as much of the track as the viewport is of everything,
+        // but always at least one cell so it can be seen.
+        let len = (track * self.viewport_height)
+            .div_ceil(total)
+            .clamp(1, track);
+        let current = self.current.min(self.max);
+        let start = ((track - len) * current + self.max / 2) / self.max;
+
+        Some(Thumb {
+            start: start as u16,
+            len: len as u16,
+        })
+    }
+
+    /// Draws the thumb over the right border of `area`, between its corners.
+    pub fn draw(&self, buffer: &mut Buffer, area: Rect) {
+        if area.width == 0 || area.height < 3 {
+            return;
+        }
+
+        let x = area.right() - 1;
+        let track_top = area.top() + 1;
+
+        if let Some(thumb) = self.thumb(area.height - 2) {
+            for y in track_top + thumb.start..track_top + thumb.start + thumb.len {
+                if let Some(cell) = buffer.cell_mut((x, y)) {
+                    cell.set_symbol(symbols::line::THICK_VERTICAL)
+                        .set_style(self.style);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn thumb(current: usize, max: usize, height: usize, track_len: u16) -> Option<Thumb> {
+        Scrollbar::new(current, max, height).thumb(track_len)
+    }
+
+    #[test]
+    fn thumb_positions() {
+        // Half of everything is in view.
+        assert_eq!(thumb(0, 10, 10, 10), Some(Thumb { start: 0, len: 5 }));
+        assert_eq!(thumb(5, 10, 10, 10), Some(Thumb { start: 3, len: 5 }));
+        assert_eq!(thumb(10, 10, 10, 10), Some(Thumb { start: 5, len: 5 }));
+
+        // Over-scrolling stays at the bottom.
+        assert_eq!(thumb(20, 10, 10, 10), Some(Thumb { start: 5, len: 5 }));
+
+        // Long lists still get a visible thumb.
+        assert_eq!(thumb(0, 1000, 10, 10), Some(Thumb { start: 0, len: 1 }));
+        assert_eq!(thumb(500, 1000, 10, 10), Some(Thumb { start: 5, len: 1 }));
+        assert_eq!(thumb(1000, 1000, 10, 10), Some(Thumb { start: 9, len: 1 }));
+    }
+
+    #[test]
+    fn nothing_to_scroll() {
+        assert_eq!(thumb(0, 0, 10, 10), None);
+        assert_eq!(thumb(0, 10, 10, 0), None);
+    }
+
+    #[test]
+    fn single_line() {
+        assert_eq!(thumb(0, 5, 1, 1), Some(Thumb { start: 0, len: 1 }));
+        assert_eq!(thumb(5, 5, 1, 1), Some(Thumb { start: 0, len: 1 }));
+        assert_eq!(thumb(3, 5, 1, 6), Some(Thumb { start: 3, len: 1 }));
+    }
+
+    #[test]
+    fn draws_on_the_right_border() {
+        let area = Rect::new(0, 0, 4, 6);
+        let mut buffer = Buffer::empty(area);
+        Scrollbar::new(4, 4, 4).draw(&mut buffer, area);
+
+        let column = (0..6)
+            .map(|y| buffer[(3, y)].symbol().to_string())
+            .collect::<String>();
+        assert_eq!(column, "   ┃┃ ");
+        assert_eq!(buffer[(2, 3)].symbol(), " ");
+    }
+}
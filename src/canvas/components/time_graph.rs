@@ -2,20 +2,24 @@ use std::borrow::Cow;
 
 use concat_string::concat_string;
 use tui::{
-    layout::{Constraint, Rect},
-    style::Style,
+    layout::{Alignment, Constraint, Rect},
+    style::{Modifier, Style},
     symbols::Marker,
     text::{Line, Span},
-    widgets::{BorderType, GraphType},
+    widgets::{BorderType, GraphType, Paragraph},
     Frame,
 };
 
 use crate::canvas::drawing_utils::widget_block;
 
 use super::time_chart::{
-    Axis, Dataset, LegendPosition, Point, TimeChart, DEFAULT_LEGEND_CONSTRAINTS,
+    Axis, Dataset, HorizontalMarker, LegendPosition, Point, TimeChart, VerticalMarker,
+    DEFAULT_LEGEND_CONSTRAINTS,
 };
 
+/// What is shown in a graph until its first data comes in.
+pub const COLLECTING_TEXT: &str = "Collecting data...";
+
 /// Represents the data required by the [`TimeGraph`].
 pub struct GraphData<'a> {
     pub points: &'a [Point],
@@ -67,6 +71,33 @@ pub struct TimeGraph<'a> {
     /// The marker type. Unlike ratatui's native charts, we assume
     /// only a single type of marker.
     pub marker: Marker,
+
+    /// Where gaps in the data end, as x-positions.
+    pub gaps: &'a [f64],
+
+    /// Any horizontal lines to draw behind the data.
+    pub markers: &'a [HorizontalMarker<'a>],
+
+    /// Any vertical lines to draw behind the data, such as marked points in
+    /// time.
+    pub vertical_markers: &'a [VerticalMarker<'a>],
+
+    /// The style of the vertical lines.
+    pub vertical_marker_style: Style,
+
+    /// Whether to draw the data with thicker lines.
+    pub thick_lines: bool,
+
+    /// Whether to draw the newest data on the left rather than the right.
+    pub newest_on_left: bool,
+
+    /// Where to draw faint horizontal lines behind the data, in the same scale
+    /// as the y-axis bounds.
+    pub gridlines: &'a [f64],
+
+    /// Text to draw instead of the graph, such as [`COLLECTING_TEXT`] while
+    /// nothing has been collected yet.
+    pub placeholder: Option<&'a str>,
 }
 
 impl TimeGraph<'_> {
@@ -82,10 +113,13 @@ impl TimeGraph<'_> {
             let xb_one = (self.x_bounds[1] / 1000).to_string();
             let xb_zero = (self.x_bounds[0] / 1000).to_string();
 
-            let x_labels = vec![
+            let mut x_labels = vec![
                 Span::styled(concat_string!(xb_one, "s"), self.graph_style),
                 Span::styled(concat_string!(xb_zero, "s"), self.graph_style),
             ];
+            if self.newest_on_left {
+                x_labels.reverse();
+            }
 
             Axis::default()
                 .bounds(adjusted_x_bounds)
@@ -117,13 +151,6 @@ impl TimeGraph<'_> {
     /// - Expects `graph_data`, which represents *what* data to draw, and
     ///   various details like style and optional legends.
     pub fn draw_time_graph(&self, f: &mut Frame<'_>, draw_loc: Rect, graph_data: &[GraphData<'_>]) {
-        let x_axis = self.generate_x_axis();
-        let y_axis = self.generate_y_axis();
-
-        // This is some ugly manual loop unswitching. Maybe unnecessary.
-        // TODO: Optimize this step. Cut out unneeded points.
-        let data = graph_data.iter().map(create_dataset).collect();
-
         let block = {
             let mut b = widget_block(false, self.is_selected, self.border_type)
                 .border_style(self.border_style)
@@ -136,12 +163,42 @@ impl TimeGraph<'_> {
             b
         };
 
+        if let Some(placeholder) = self.placeholder {
+            let inner = block.inner(draw_loc);
+            let text_loc = Rect {
+                y: inner.y + inner.height.saturating_sub(1) / 2,
+                height: inner.height.min(1),
+                ..inner
+            };
+
+            f.render_widget(block, draw_loc);
+            f.render_widget(
+                Paragraph::new(Line::styled(placeholder, self.graph_style))
+                    .alignment(Alignment::Center),
+                text_loc,
+            );
+            return;
+        }
+
+        let x_axis = self.generate_x_axis();
+        let y_axis = self.generate_y_axis();
+
+        // This is some ugly manual loop unswitching. Maybe unnecessary.
+        // TODO: Optimize this step. Cut out unneeded points.
+        let data = graph_data.iter().map(create_dataset).collect();
+
         f.render_widget(
             TimeChart::new(data)
                 .block(block)
                 .x_axis(x_axis)
                 .y_axis(y_axis)
                 .marker(self.marker)
+                .gaps(self.gaps)
+                .markers(self.markers)
+                .vertical_markers(self.vertical_markers, self.vertical_marker_style)
+                .thick_lines(self.thick_lines)
+                .reverse_x(self.newest_on_left)
+                .gridlines(self.gridlines, self.graph_style.add_modifier(Modifier::DIM))
                 .legend_style(self.graph_style)
                 .legend_position(self.legend_position)
                 .hidden_legend_constraints(
@@ -184,8 +241,8 @@ mod test {
         widgets::BorderType,
     };
 
-    use super::TimeGraph;
-    use crate::canvas::components::time_chart::Axis;
+    use super::{GraphData, TimeGraph, COLLECTING_TEXT};
+    use crate::canvas::{components::time_chart::Axis, snapshot::render_to_text};
 
     const Y_LABELS: [Cow<'static, str>; 3] = [
         Cow::Borrowed("0%"),
@@ -209,6 +266,14 @@ mod test {
             legend_position: None,
             legend_constraints: None,
             marker: Marker::Braille,
+            gaps: &[],
+            markers: &[],
+            vertical_markers: &[],
+            vertical_marker_style: Style::default(),
+            thick_lines: false,
+            newest_on_left: false,
+            gridlines: &[],
+            placeholder: None,
         }
     }
 
@@ -246,4 +311,28 @@ mod test {
         assert_eq!(y_axis.labels, actual.labels);
         assert_eq!(y_axis.style, actual.style);
     }
+
+    #[test]
+    fn placeholder_until_data_comes_in() {
+        let points = [(-5000.0, 50.0), (0.0, 50.0)];
+        let data = [GraphData {
+            points: &points,
+            style: Style::default(),
+            name: None,
+        }];
+        let draw = |tg: &TimeGraph<'_>| {
+            render_to_text(30, 7, |f| tg.draw_time_graph(f, f.area(), &data)).unwrap()
+        };
+
+        let mut tg = create_time_graph();
+        tg.placeholder = Some(COLLECTING_TEXT);
+        let text = draw(&tg);
+        assert!(text.lines().nth(3).unwrap().contains(COLLECTING_TEXT));
+        assert!(!text.contains("15s"));
+
+        tg.placeholder = None;
+        let text = draw(&tg);
+        assert!(!text.contains(COLLECTING_TEXT));
+        assert!(text.contains("15s"));
+    }
 }
@@ -7,7 +7,7 @@
 mod canvas;
 mod points;
 
-use std::{cmp::max, str::FromStr};
+use std::{borrow::Cow, cmp::max, str::FromStr};
 
 use canvas::*;
 use tui::{
@@ -16,7 +16,7 @@ use tui::{
     style::{Color, Style, Styled},
     symbols::{self, Marker},
     text::{Line, Span},
-    widgets::{block::BlockExt, Block, Borders, GraphType, Widget},
+    widgets::{block::BlockExt, canvas::Line as CanvasLine, Block, Borders, GraphType, Widget},
 };
 use unicode_width::UnicodeWidthStr;
 
@@ -26,6 +26,37 @@ pub const DEFAULT_LEGEND_CONSTRAINTS: (Constraint, Constraint) =
 /// A single graph point.
 pub type Point = (f64, f64);
 
+/// What gridlines are drawn with.
+const GRIDLINE_SYMBOL: &str = "┈";
+
+/// What vertical markers are drawn with.
+const VERTICAL_MARKER_SYMBOL: &str = "┊";
+
+/// A horizontal line across the whole graph at some y-value, such as a
+/// reference or threshold line, optionally labelled at the right edge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HorizontalMarker<'a> {
+    /// Where to draw the line, in the same scale as the y-axis bounds.
+    pub value: f64,
+    /// The style of the line and its label.
+    pub style: Style,
+    /// The label, drawn on top of the line.
+    pub label: Option<Cow<'a, str>>,
+}
+
+/// A vertical line across the whole graph at some x-value, such as a point in
+/// time that was marked. The line is tagged at the top, and its label, if any,
+/// is listed in the legend after the datasets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerticalMarker<'a> {
+    /// Where to draw the line, in the same scale as the x-axis bounds.
+    pub value: f64,
+    /// A short name for the line, such as a number.
+    pub tag: Cow<'a, str>,
+    /// The label, listed in the legend.
+    pub label: Option<Cow<'a, str>>,
+}
+
 /// An X or Y axis for the [`TimeChart`] widget
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Axis<'a> {
@@ -388,6 +419,23 @@ pub struct TimeChart<'a> {
     legend_position: Option<LegendPosition>,
     /// The marker type.
     marker: Marker,
+    /// Where gaps in the data end, as x-positions. Points on either side of a
+    /// gap aren't connected.
+    gaps: &'a [f64],
+    /// Horizontal lines drawn behind the datasets.
+    markers: &'a [HorizontalMarker<'a>],
+    /// Vertical lines drawn in the cells that the datasets leave empty.
+    vertical_markers: &'a [VerticalMarker<'a>],
+    /// The style of the vertical lines, their tags, and their labels.
+    vertical_marker_style: Style,
+    /// Whether to draw the datasets with thicker lines.
+    thick_lines: bool,
+    /// Whether the x-axis runs from right to left.
+    reverse_x: bool,
+    /// Where to draw gridlines, in the same scale as the y-axis bounds.
+    gridlines: &'a [f64],
+    /// The style of the gridlines.
+    gridline_style: Style,
 }
 
 impl<'a> TimeChart<'a> {
@@ -405,6 +453,14 @@ impl<'a> TimeChart<'a> {
             hidden_legend_constraints: (Constraint::Ratio(1, 4), Constraint::Ratio(1, 4)),
             legend_position: Some(LegendPosition::default()),
             marker: Marker::Braille,
+            gaps: &[],
+            markers: &[],
+            vertical_markers: &[],
+            vertical_marker_style: Style::default(),
+            thick_lines: false,
+            reverse_x: false,
+            gridlines: &[],
+            gridline_style: Style::default(),
         }
     }
 
@@ -460,6 +516,57 @@ impl<'a> TimeChart<'a> {
         self
     }
 
+    /// Sets where gaps in the data end, as x-positions.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn gaps(mut self, gaps: &'a [f64]) -> TimeChart<'a> {
+        self.gaps = gaps;
+        self
+    }
+
+    /// Sets the horizontal lines to draw behind the datasets.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn markers(mut self, markers: &'a [HorizontalMarker<'a>]) -> TimeChart<'a> {
+        self.markers = markers;
+        self
+    }
+
+    /// Sets the vertical lines to draw and their style. Like gridlines, they
+    /// are only drawn in otherwise empty cells.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn vertical_markers(
+        mut self, markers: &'a [VerticalMarker<'a>], style: Style,
+    ) -> TimeChart<'a> {
+        self.vertical_markers = markers;
+        self.vertical_marker_style = style;
+        self
+    }
+
+    /// Sets where to draw gridlines and their style. Gridlines are only drawn
+    /// in otherwise empty cells, so they never cover the data.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn gridlines(mut self, gridlines: &'a [f64], style: Style) -> TimeChart<'a> {
+        self.gridlines = gridlines;
+        self.gridline_style = style;
+        self
+    }
+
+    /// Sets whether to draw the datasets with thicker lines, by drawing an
+    /// extra point next to each point.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn thick_lines(mut self, thick_lines: bool) -> TimeChart<'a> {
+        self.thick_lines = thick_lines;
+        self
+    }
+
+    /// Sets whether the x-axis runs from right to left, so the largest
+    /// x-values are drawn on the left. The x-axis labels are still drawn in
+    /// the order given.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn reverse_x(mut self, reverse_x: bool) -> TimeChart<'a> {
+        self.reverse_x = reverse_x;
+        self
+    }
+
     /// Sets the constraints used to determine whether the legend should be
     /// shown or not.
     ///
@@ -546,7 +653,11 @@ impl<'a> TimeChart<'a> {
             let legends = self
                 .datasets
                 .iter()
-                .filter_map(|d| Some(d.name.as_ref()?.width() as u16));
+                .filter_map(|d| Some(d.name.as_ref()?.width() as u16))
+                .chain(
+                    self.legend_markers()
+                        .map(|(tag, label, _)| (tag.width() + 1 + label.width()) as u16),
+                );
 
             if let Some(inner_width) = legends.clone().max() {
                 let legend_width = inner_width + 2;
@@ -711,6 +822,122 @@ impl<'a> TimeChart<'a> {
             }
         }
     }
+
+    fn draw_markers(&self, ctx: &mut Context<'_>) {
+        let [left, right] = self.x_axis.bounds;
+        for marker in self.markers {
+            ctx.draw(&CanvasLine {
+                x1: left,
+                y1: marker.value,
+                x2: right,
+                y2: marker.value,
+                color: marker.style.fg.unwrap_or(Color::Reset),
+            });
+        }
+    }
+
+    /// Returns a blank [`Context`] the size of `graph_area`, to find where
+    /// points are drawn in it.
+    fn context(&self, graph_area: Rect) -> Context<'_> {
+        let mut ctx = Context::new(
+            graph_area.width,
+            graph_area.height,
+            self.x_axis.bounds,
+            self.y_axis.bounds,
+            self.marker,
+        );
+        ctx.reverse_x(self.reverse_x);
+
+        ctx
+    }
+
+    /// Returns the rows, counting from the top of `graph_area`, that the
+    /// gridlines are in. Gridlines outside of the y-axis bounds are skipped.
+    fn gridline_rows(&self, graph_area: Rect) -> Vec<u16> {
+        let mut ctx = self.context(graph_area);
+
+        self.gridlines
+            .iter()
+            .filter_map(|value| ctx.cell_row(*value, graph_area.height))
+            .collect()
+    }
+
+    fn render_gridlines(&self, buf: &mut Buffer, graph_area: Rect) {
+        for row in self.gridline_rows(graph_area) {
+            let y = graph_area.top() + row;
+            for x in graph_area.left()..graph_area.right() {
+                if let Some(cell) = buf.cell_mut((x, y)) {
+                    if cell.symbol() == " " {
+                        cell.set_symbol(GRIDLINE_SYMBOL)
+                            .set_style(self.gridline_style);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the tag, label, and style of each vertical marker with a label.
+    fn legend_markers(&self) -> impl Iterator<Item = (&str, &str, Style)> + Clone {
+        self.vertical_markers.iter().filter_map(|marker| {
+            Some((
+                marker.tag.as_ref(),
+                marker.label.as_deref()?,
+                self.vertical_marker_style,
+            ))
+        })
+    }
+
+    /// Draws each vertical marker as a line down the empty cells of the column
+    /// it is in, with its tag at the top.
+    fn render_vertical_markers(&self, buf: &mut Buffer, graph_area: Rect) {
+        for marker in self.vertical_markers {
+            let mut ctx = self.context(graph_area);
+            let Some(column) = ctx.cell_column(marker.value, graph_area.width) else {
+                continue;
+            };
+
+            let x = graph_area.left() + column;
+            for y in graph_area.top()..graph_area.bottom() {
+                if let Some(cell) = buf.cell_mut((x, y)) {
+                    if cell.symbol() == " " {
+                        cell.set_symbol(VERTICAL_MARKER_SYMBOL)
+                            .set_style(self.vertical_marker_style);
+                    }
+                }
+            }
+
+            buf.set_stringn(
+                x,
+                graph_area.top(),
+                &marker.tag,
+                (graph_area.right() - x).into(),
+                self.vertical_marker_style,
+            );
+        }
+    }
+
+    /// Draws each marker's label right-aligned on the row its line is in.
+    fn render_marker_labels(&self, buf: &mut Buffer, graph_area: Rect) {
+        for marker in self.markers {
+            let Some(label) = &marker.label else {
+                continue;
+            };
+
+            let mut ctx = self.context(graph_area);
+            let Some(row) = ctx.cell_row(marker.value, graph_area.height) else {
+                continue;
+            };
+
+            let width = (label.width() as u16).min(graph_area.width);
+            buf.set_stringn(
+                graph_area.right() - width,
+                graph_area.top() + row,
+                label,
+                width.into(),
+                marker.style,
+            );
+        }
+    }
 }
 
 impl Widget for TimeChart<'_> {
@@ -772,11 +999,17 @@ impl Widget for TimeChart<'_> {
             .x_bounds(self.x_axis.bounds)
             .y_bounds(self.y_axis.bounds)
             .marker(self.marker)
+            .reverse_x(self.reverse_x)
             .paint(|ctx| {
+                self.draw_markers(ctx);
                 self.draw_points(ctx);
             })
             .render(graph_area, buf);
 
+        self.render_vertical_markers(buf, graph_area);
+        self.render_gridlines(buf, graph_area);
+        self.render_marker_labels(buf, graph_area);
+
         if let Some((x, y)) = layout.title_x {
             let title = self.x_axis.title.as_ref().unwrap();
             let width = graph_area
@@ -820,13 +1053,15 @@ impl Widget for TimeChart<'_> {
                 .border_style(self.legend_style)
                 .render(legend_area, buf);
 
-            for (i, (dataset_name, dataset_style)) in self
+            let dataset_names = self
                 .datasets
                 .iter()
-                .filter_map(|ds| Some((ds.name.as_ref()?, ds.style())))
-                .enumerate()
-            {
-                let name = dataset_name.clone().patch_style(dataset_style);
+                .filter_map(|ds| Some(ds.name.as_ref()?.clone().patch_style(ds.style())));
+            let marker_labels = self.legend_markers().map(|(tag, label, style)| {
+                Line::from(vec![Span::raw(tag), Span::raw(" "), Span::raw(label)]).style(style)
+            });
+
+            for (i, name) in dataset_names.chain(marker_labels).enumerate() {
                 name.render(
                     Rect {
                         x: legend_area.x + 1,
@@ -1117,6 +1352,45 @@ mod tests {
         assert_eq!(buffer, expected);
     }
 
+    #[test]
+    fn vertical_markers_are_tagged_and_labelled() {
+        let markers = [
+            VerticalMarker {
+                value: -5.0,
+                tag: "1".into(),
+                label: Some("boot".into()),
+            },
+            // Out of bounds, so not drawn.
+            VerticalMarker {
+                value: -20.0,
+                tag: "2".into(),
+                label: None,
+            },
+        ];
+        let chart = TimeChart::new(vec![Dataset::default().name("Ds1")])
+            .x_axis(Axis::default().bounds([-10.0, 0.0]))
+            .y_axis(Axis::default().bounds([0.0, 1.0]))
+            .legend_position(Some(LegendPosition::TopLeft))
+            .hidden_legend_constraints((Constraint::Percentage(100), Constraint::Percentage(100)))
+            .vertical_markers(&markers, Style::default());
+
+        let area = Rect::new(0, 0, 20, 6);
+        let mut buffer = Buffer::empty(area);
+
+        chart.render(buffer.area, &mut buffer);
+
+        let expected = Buffer::with_lines(vec![
+            "┌──────┐ 1          ",
+            "│Ds1   │ ┊          ",
+            "│1 boot│ ┊          ",
+            "└──────┘ ┊          ",
+            "         ┊          ",
+            "         ┊          ",
+        ]);
+
+        assert_eq!(buffer, expected);
+    }
+
     #[test]
     fn test_chart_have_a_long_y_axis_title_overlapping_legend() {
         let chart = TimeChart::new(vec![Dataset::default().name("Ds1")])
@@ -1371,4 +1645,136 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn markers_are_drawn_with_labels() {
+        let markers = [
+            HorizontalMarker {
+                value: 5.0,
+                style: Style::default(),
+                label: Some("max".into()),
+            },
+            HorizontalMarker {
+                value: 20.0,
+                style: Style::default(),
+                label: Some("hidden".into()),
+            },
+        ];
+        let chart = TimeChart::new(vec![])
+            .x_axis(Axis::default().bounds([-10.0, 0.0]))
+            .y_axis(Axis::default().bounds([0.0, 10.0]))
+            .marker(Marker::Dot)
+            .markers(&markers);
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 10, 5));
+        chart.render(buffer.area, &mut buffer);
+
+        // Markers outside of the bounds aren't drawn at all.
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "          ",
+                "          ",
+                "•••••••max",
+                "          ",
+                "          ",
+            ])
+        );
+    }
+
+    #[test]
+    fn gridline_positions() {
+        let chart = TimeChart::new(vec![])
+            .x_axis(Axis::default().bounds([-10.0, 0.0]))
+            .y_axis(Axis::default().bounds([0.0, 100.0]))
+            .gridlines(&[25.0, 50.0, 75.0, 150.0], Style::default());
+
+        assert_eq!(chart.gridline_rows(Rect::new(0, 0, 10, 9)), [6, 4, 2]);
+        assert_eq!(chart.gridline_rows(Rect::new(3, 5, 10, 5)), [3, 2, 1]);
+    }
+
+    #[test]
+    fn gridlines_stay_behind_the_data() {
+        let data = [(-10.0, 5.0), (0.0, 5.0)];
+        let chart = TimeChart::new(vec![Dataset::default()
+            .data(&data)
+            .graph_type(GraphType::Line)])
+        .x_axis(Axis::default().bounds([-10.0, 0.0]))
+        .y_axis(Axis::default().bounds([0.0, 10.0]))
+        .marker(Marker::Dot)
+        .gridlines(&[5.0, 7.5], Style::default());
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 10, 5));
+        chart.render(buffer.area, &mut buffer);
+
+        assert_eq!(
+            buffer,
+            Buffer::with_lines(vec![
+                "          ",
+                "┈┈┈┈┈┈┈┈┈┈",
+                "••••••••••",
+                "          ",
+                "          ",
+            ])
+        );
+    }
+
+    #[test]
+    fn thick_lines_double_the_points() {
+        /// Counts the braille dots drawn.
+        fn dots(thick_lines: bool) -> u32 {
+            let data = [(-10.0, 5.0), (0.0, 5.0)];
+            let chart = TimeChart::new(vec![Dataset::default()
+                .data(&data)
+                .graph_type(GraphType::Line)])
+            .x_axis(Axis::default().bounds([-10.0, 0.0]))
+            .y_axis(Axis::default().bounds([0.0, 10.0]))
+            .thick_lines(thick_lines);
+
+            let mut buffer = Buffer::empty(Rect::new(0, 0, 10, 5));
+            chart.render(buffer.area, &mut buffer);
+
+            buffer
+                .content()
+                .iter()
+                .filter_map(|cell| cell.symbol().chars().next())
+                .map(|c| (c as u32).checked_sub(0x2800).map_or(0, u32::count_ones))
+                .sum()
+        }
+
+        assert_eq!(dots(false), 20);
+        assert_eq!(dots(true), 40);
+    }
+
+    #[test]
+    fn reversed_x_mirrors_the_points() {
+        /// Returns each row of the chart, left to right.
+        fn rows(reverse_x: bool) -> Vec<String> {
+            let data = [(-9.0, 0.0), (-6.0, 3.0), (-2.0, 5.0), (0.0, 9.0)];
+            let chart = TimeChart::new(vec![Dataset::default()
+                .data(&data)
+                .graph_type(GraphType::Scatter)])
+            .x_axis(Axis::default().bounds([-9.0, 0.0]))
+            .y_axis(Axis::default().bounds([0.0, 9.0]))
+            .marker(Marker::Dot)
+            .reverse_x(reverse_x);
+
+            let mut buffer = Buffer::empty(Rect::new(0, 0, 10, 10));
+            chart.render(buffer.area, &mut buffer);
+
+            buffer
+                .content()
+                .chunks(10)
+                .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+                .collect()
+        }
+
+        let mirrored = rows(false)
+            .iter()
+            .map(|row| row.chars().rev().collect::<String>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(rows(false)[0], "         •");
+        assert_eq!(rows(true), mirrored);
+    }
 }
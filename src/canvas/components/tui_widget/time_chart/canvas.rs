@@ -436,7 +436,12 @@ impl Painter<'_, '_> {
         if width == 0.0 || height == 0.0 {
             return None;
         }
-        let x = ((x - left) * self.resolution.0 / width) as usize;
+        let x_offset = if self.context.reverse_x {
+            right - x
+        } else {
+            x - left
+        };
+        let x = (x_offset * self.resolution.0 / width) as usize;
         let y = ((top - y) * self.resolution.1 / height) as usize;
         Some((x, y))
     }
@@ -457,6 +462,14 @@ impl Painter<'_, '_> {
     /// ```
     pub fn paint(&mut self, x: usize, y: usize, color: Color) {
         self.context.grid.paint(x, y, color);
+
+        // Thick lines also get the point above, or below if on the top row.
+        if self.context.thick {
+            let y = if y > 0 { y - 1 } else { y + 1 };
+            if y <= self.resolution.1 as usize {
+                self.context.grid.paint(x, y, color);
+            }
+        }
     }
 }
 
@@ -478,6 +491,8 @@ pub struct Context<'a> {
     grid: Box<dyn Grid>,
     dirty: bool,
     labels: Vec<Label<'a>>,
+    thick: bool,
+    reverse_x: bool,
 }
 
 impl<'a> Context<'a> {
@@ -497,9 +512,23 @@ impl<'a> Context<'a> {
             grid,
             dirty: false,
             labels: Vec::new(),
+            thick: false,
+            reverse_x: false,
         }
     }
 
+    /// Sets whether every point drawn from now on is doubled up with the
+    /// point next to it, for thicker lines.
+    pub fn thick(&mut self, thick: bool) {
+        self.thick = thick;
+    }
+
+    /// Sets whether the x-axis runs from right to left, so the smallest `x`
+    /// is drawn on the right.
+    pub fn reverse_x(&mut self, reverse_x: bool) {
+        self.reverse_x = reverse_x;
+    }
+
     /// Draw any object that may implement the Shape trait
     pub fn draw<S>(&mut self, shape: &S)
     where
@@ -509,6 +538,30 @@ impl<'a> Context<'a> {
         let mut painter = Painter::from(self);
         shape.draw(&mut painter);
     }
+
+    /// Returns the row of cells, counting from the top of a canvas `height`
+    /// cells tall, that a point at `y` would be drawn in, or [`None`] if `y`
+    /// is out of bounds.
+    pub fn cell_row(&mut self, y: f64, height: u16) -> Option<u16> {
+        let x = self.x_bounds[0];
+        let painter = Painter::from(self);
+        let (_, grid_y) = painter.get_point(x, y)?;
+        let points_per_row = (painter.resolution.1 + 1.0) / f64::from(height);
+
+        Some(((grid_y as f64 / points_per_row) as u16).min(height.saturating_sub(1)))
+    }
+
+    /// Returns the column of cells, counting from the left of a canvas `width`
+    /// cells wide, that a point at `x` would be drawn in, or [`None`] if `x` is
+    /// out of bounds.
+    pub fn cell_column(&mut self, x: f64, width: u16) -> Option<u16> {
+        let y = self.y_bounds[0];
+        let painter = Painter::from(self);
+        let (grid_x, _) = painter.get_point(x, y)?;
+        let points_per_column = (painter.resolution.0 + 1.0) / f64::from(width);
+
+        Some(((grid_x as f64 / points_per_column) as u16).min(width.saturating_sub(1)))
+    }
 }
 
 /// The Canvas widget may be used to draw more detailed figures using braille
@@ -523,6 +576,7 @@ where
     painter: Option<F>,
     background_color: Color,
     marker: symbols::Marker,
+    reverse_x: bool,
 }
 
 impl<'a, F> Default for Canvas<'a, F>
@@ -537,6 +591,7 @@ where
             painter: None,
             background_color: Color::Reset,
             marker: symbols::Marker::Braille,
+            reverse_x: false,
         }
     }
 }
@@ -555,6 +610,12 @@ where
         self
     }
 
+    /// Sets whether the x-axis runs from right to left.
+    pub fn reverse_x(mut self, reverse_x: bool) -> Canvas<'a, F> {
+        self.reverse_x = reverse_x;
+        self
+    }
+
     /// Store the closure that will be used to draw to the Canvas
     pub fn paint(mut self, f: F) -> Canvas<'a, F> {
         self.painter = Some(f);
@@ -625,6 +686,7 @@ where
             self.y_bounds,
             self.marker,
         );
+        ctx.reverse_x(self.reverse_x);
         // Paint to this context
         painter(&mut ctx);
 
@@ -668,7 +730,12 @@ where
             .iter()
             .filter(|l| l.x >= left && l.x <= right && l.y <= top && l.y >= bottom)
         {
-            let x = ((label.x - left) * resolution.0 / width) as u16 + canvas_area.left();
+            let x_offset = if self.reverse_x {
+                right - label.x
+            } else {
+                label.x - left
+            };
+            let x = (x_offset * resolution.0 / width) as u16 + canvas_area.left();
             let y = ((top - label.y) * resolution.1 / height) as u16 + canvas_area.top();
             buf.set_line(x, y, &label.spans, canvas_area.right() - x);
         }
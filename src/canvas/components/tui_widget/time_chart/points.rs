@@ -27,6 +27,11 @@ impl TimeChart<'_> {
         // jarring gaps between the edges when there's a point that is off
         // screen and so a line isn't drawn (right edge generally won't have this issue
         // issue but it can happen in some cases).
+        //
+        // Points on either side of a gap in the data are never connected, so
+        // that a missed harvest doesn't look like a straight line.
+
+        ctx.thick(self.thick_lines);
 
         for dataset in &self.datasets {
             let color = dataset.style.fg.unwrap_or(Color::Reset);
@@ -40,10 +45,12 @@ impl TimeChart<'_> {
             let data_slice = &dataset.data[start_index..end_index];
 
             if let Some(interpolate_start) = interpolate_start {
-                if let (Some(older_point), Some(newer_point)) = (
-                    dataset.data.get(interpolate_start),
-                    dataset.data.get(interpolate_start + 1),
-                ) {
+                if let Some((older_point, newer_point)) = dataset
+                    .data
+                    .get(interpolate_start)
+                    .zip(dataset.data.get(interpolate_start + 1))
+                    .filter(|(older, newer)| !self.crosses_gap(older, newer))
+                {
                     let interpolated_point = (
                         self.x_axis.bounds[0],
                         interpolate_point(older_point, newer_point, self.x_axis.bounds[0]),
@@ -68,6 +75,10 @@ impl TimeChart<'_> {
 
             if let GraphType::Line = dataset.graph_type {
                 for data in data_slice.windows(2) {
+                    if self.crosses_gap(&data[0], &data[1]) {
+                        continue;
+                    }
+
                     ctx.draw(&CanvasLine {
                         x1: data[0].0,
                         y1: data[0].1,
@@ -84,10 +95,12 @@ impl TimeChart<'_> {
             }
 
             if let Some(interpolate_end) = interpolate_end {
-                if let (Some(older_point), Some(newer_point)) = (
-                    dataset.data.get(interpolate_end - 1),
-                    dataset.data.get(interpolate_end),
-                ) {
+                if let Some((older_point, newer_point)) = dataset
+                    .data
+                    .get(interpolate_end - 1)
+                    .zip(dataset.data.get(interpolate_end))
+                    .filter(|(older, newer)| !self.crosses_gap(older, newer))
+                {
                     let interpolated_point = (
                         self.x_axis.bounds[1],
                         interpolate_point(older_point, newer_point, self.x_axis.bounds[1]),
@@ -110,6 +123,15 @@ impl TimeChart<'_> {
                 }
             }
         }
+
+        ctx.thick(false);
+    }
+
+    /// Whether there is a gap in the data between two consecutive points.
+    fn crosses_gap(&self, older_point: &Point, newer_point: &Point) -> bool {
+        self.gaps
+            .iter()
+            .any(|gap| older_point.0 < *gap && *gap <= newer_point.0)
     }
 }
 
@@ -177,6 +199,17 @@ mod test {
         assert_eq!(interpolate_point(&data[0], &data[1], -3.0), 8.0);
     }
 
+    #[test]
+    fn time_chart_gaps() {
+        let gaps = [-2.0];
+        let chart = TimeChart::new(vec![]).gaps(&gaps);
+
+        assert!(chart.crosses_gap(&(-3.0, 1.0), &(-2.0, 1.0)));
+        assert!(chart.crosses_gap(&(-2.5, 1.0), &(-1.0, 1.0)));
+        assert!(!chart.crosses_gap(&(-4.0, 1.0), &(-3.0, 1.0)));
+        assert!(!chart.crosses_gap(&(-2.0, 1.0), &(-1.0, 1.0)));
+    }
+
     #[test]
     fn time_chart_empty_dataset() {
         let data = [];
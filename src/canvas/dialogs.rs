@@ -1,2 +1,7 @@
+pub mod ancestry_dialog;
 pub mod dd_dialog;
 pub mod help_dialog;
+pub mod jump_dialog;
+pub mod marker_dialog;
+pub mod settings_dialog;
+pub mod timings_dialog;
@@ -0,0 +1,90 @@
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    app::{ancestry::Ancestor, App},
+    canvas::{drawing_utils::dialog_block, Painter},
+};
+
+const SEPARATOR: &str = " → ";
+
+fn crumb_text(ancestor: &Ancestor) -> String {
+    match &ancestor.process {
+        Some((name, cpu_usage_percent)) => {
+            format!("{name} ({}, {cpu_usage_percent:.1}%)", ancestor.pid)
+        }
+        None => format!("(exited) ({})", ancestor.pid),
+    }
+}
+
+impl Painter {
+    /// Draws the ancestry overlay centered in `draw_loc`, as a breadcrumb that
+    /// wraps between ancestors.
+    pub fn draw_ancestry_dialog(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let overlay = &app_state.ancestry_overlay;
+
+        let dialog_width = if draw_loc.width < 100 {
+            draw_loc.width * 90 / 100
+        } else {
+            draw_loc.width * 60 / 100
+        };
+        let text_width = usize::from(dialog_width.saturating_sub(2)).max(1);
+
+        // Lay out the crumbs ourselves so that they aren't split across lines.
+        let mut lines: Vec<Line<'_>> = vec![Line::default()];
+        let mut line_width = 0;
+        for (index, ancestor) in overlay.chain().iter().enumerate() {
+            let text = crumb_text(ancestor);
+            let width = text.width();
+
+            if line_width > 0 && line_width + width > text_width {
+                lines.push(Line::default());
+                line_width = 0;
+            }
+
+            let style = if index == overlay.selected_index() {
+                self.styles.selected_text_style
+            } else if ancestor.process.is_none() {
+                self.styles.disabled_text_style
+            } else {
+                self.styles.text_style
+            };
+
+            let line = lines.last_mut().expect("there is always a line");
+            line.push_span(Span::styled(text, style));
+            line_width += width;
+
+            if index + 1 < overlay.chain().len() {
+                line.push_span(Span::styled(SEPARATOR, self.styles.table_header_style));
+                line_width += SEPARATOR.width();
+            }
+        }
+
+        let dialog_height = (lines.len() as u16 + 2).min(draw_loc.height);
+        let dialog_loc = Rect::new(
+            draw_loc.x + draw_loc.width.saturating_sub(dialog_width) / 2,
+            draw_loc.y + draw_loc.height.saturating_sub(dialog_height) / 2,
+            dialog_width,
+            dialog_height,
+        );
+
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.border_style)
+            .title_top(Line::styled(" Ancestry ", self.styles.widget_title_style))
+            .title_top(
+                Line::styled(" Esc to close ", self.styles.widget_title_style).right_aligned(),
+            )
+            .title_bottom(
+                Line::styled(" Enter to go to process ", self.styles.widget_title_style)
+                    .right_aligned(),
+            );
+
+        f.render_widget(Clear, dialog_loc);
+        f.render_widget(Paragraph::new(lines).block(block), dialog_loc);
+    }
+}
@@ -9,153 +9,11 @@ use tui::{
 };
 
 use crate::{
-    app::{App, KillSignal, MAX_PROCESS_SIGNAL},
+    app::{process_killer, App, KillSignal, MAX_PROCESS_SIGNAL},
     canvas::{drawing_utils::dialog_block, Painter},
     widgets::ProcWidgetMode,
 };
 
-cfg_if::cfg_if! {
-    if #[cfg(target_os = "linux")] {
-        const SIGNAL_TEXT: [&str; 63] = [
-            "0: Cancel",
-            "1: HUP",
-            "2: INT",
-            "3: QUIT",
-            "4: ILL",
-            "5: TRAP",
-            "6: ABRT",
-            "7: BUS",
-            "8: FPE",
-            "9: KILL",
-            "10: USR1",
-            "11: SEGV",
-            "12: USR2",
-            "13: PIPE",
-            "14: ALRM",
-            "15: TERM",
-            "16: STKFLT",
-            "17: CHLD",
-            "18: CONT",
-            "19: STOP",
-            "20: TSTP",
-            "21: TTIN",
-            "22: TTOU",
-            "23: URG",
-            "24: XCPU",
-            "25: XFSZ",
-            "26: VTALRM",
-            "27: PROF",
-            "28: WINCH",
-            "29: IO",
-            "30: PWR",
-            "31: SYS",
-            "34: RTMIN",
-            "35: RTMIN+1",
-            "36: RTMIN+2",
-            "37: RTMIN+3",
-            "38: RTMIN+4",
-            "39: RTMIN+5",
-            "40: RTMIN+6",
-            "41: RTMIN+7",
-            "42: RTMIN+8",
-            "43: RTMIN+9",
-            "44: RTMIN+10",
-            "45: RTMIN+11",
-            "46: RTMIN+12",
-            "47: RTMIN+13",
-            "48: RTMIN+14",
-            "49: RTMIN+15",
-            "50: RTMAX-14",
-            "51: RTMAX-13",
-            "52: RTMAX-12",
-            "53: RTMAX-11",
-            "54: RTMAX-10",
-            "55: RTMAX-9",
-            "56: RTMAX-8",
-            "57: RTMAX-7",
-            "58: RTMAX-6",
-            "59: RTMAX-5",
-            "60: RTMAX-4",
-            "61: RTMAX-3",
-            "62: RTMAX-2",
-            "63: RTMAX-1",
-            "64: RTMAX",
-        ];
-    } else if #[cfg(target_os = "macos")] {
-        const SIGNAL_TEXT: [&str; 32] = [
-            "0: Cancel",
-            "1: HUP",
-            "2: INT",
-            "3: QUIT",
-            "4: ILL",
-            "5: TRAP",
-            "6: ABRT",
-            "7: EMT",
-            "8: FPE",
-            "9: KILL",
-            "10: BUS",
-            "11: SEGV",
-            "12: SYS",
-            "13: PIPE",
-            "14: ALRM",
-            "15: TERM",
-            "16: URG",
-            "17: STOP",
-            "18: TSTP",
-            "19: CONT",
-            "20: CHLD",
-            "21: TTIN",
-            "22: TTOU",
-            "23: IO",
-            "24: XCPU",
-            "25: XFSZ",
-            "26: VTALRM",
-            "27: PROF",
-            "28: WINCH",
-            "29: INFO",
-            "30: USR1",
-            "31: USR2",
-        ];
-    } else if #[cfg(target_os = "freebsd")] {
-        const SIGNAL_TEXT: [&str; 34] = [
-            "0: Cancel",
-            "1: HUP",
-            "2: INT",
-            "3: QUIT",
-            "4: ILL",
-            "5: TRAP",
-            "6: ABRT",
-            "7: EMT",
-            "8: FPE",
-            "9: KILL",
-            "10: BUS",
-            "11: SEGV",
-            "12: SYS",
-            "13: PIPE",
-            "14: ALRM",
-            "15: TERM",
-            "16: URG",
-            "17: STOP",
-            "18: TSTP",
-            "19: CONT",
-            "20: CHLD",
-            "21: TTIN",
-            "22: TTOU",
-            "23: IO",
-            "24: XCPU",
-            "25: XFSZ",
-            "26: VTALRM",
-            "27: PROF",
-            "28: WINCH",
-            "29: INFO",
-            "30: USR1",
-            "31: USR2",
-            "32: THR",
-            "33: LIBRT",
-        ];
-    }
-}
-
 impl Painter {
     pub fn get_dd_spans(&self, app_state: &App) -> Option<Text<'_>> {
         if let Some(dd_err) = &app_state.dd_err {
@@ -243,7 +101,7 @@ impl Painter {
             );
 
             if app_state.should_get_widget_bounds() {
-                const SIGNAL: usize = if cfg!(target_os = "windows") { 1 } else { 15 };
+                let signal = app_state.app_config_fields.default_kill_signal;
 
                 // This is kinda weird, but the gist is:
                 // - We have three sections; we put our mouse bounding box for the "yes" button
@@ -262,7 +120,7 @@ impl Painter {
                         button_layout[0].y,
                         button_layout[0].x + button_layout[0].width,
                         button_layout[0].y,
-                        SIGNAL,
+                        signal,
                     ),
                     // No
                     (
@@ -290,14 +148,16 @@ impl Painter {
                     )
                     .split(*button_draw_loc)[1];
 
-                let mut selected = match app_state.delete_dialog_state.selected_signal {
-                    KillSignal::Cancel => 0,
-                    KillSignal::Kill(signal) => signal,
-                };
-                // 32+33 are skipped
-                if selected > 31 {
-                    selected -= 2;
-                }
+                let signals = &app_state.app_config_fields.kill_signals;
+                let signal_text = std::iter::once("0: Cancel".to_string())
+                    .chain(signals.iter().map(|&signal| {
+                        format!(
+                            "{signal}: {}",
+                            process_killer::signal_name(signal).unwrap_or("?")
+                        )
+                    }))
+                    .collect::<Vec<_>>();
+                let selected = app_state.delete_dialog_state.selected_position(signals);
 
                 let layout = Layout::default()
                     .direction(Direction::Vertical)
@@ -316,26 +176,32 @@ impl Painter {
                 };
                 let scroll_offset: usize = app_state.delete_dialog_state.scroll_pos;
 
-                let mut buttons = SIGNAL_TEXT
-                    [scroll_offset + 1..min((layout.len()) + scroll_offset, SIGNAL_TEXT.len())]
+                let mut buttons = signal_text
+                    [scroll_offset + 1..min((layout.len()) + scroll_offset, signal_text.len())]
                     .iter()
-                    .map(|text| Span::styled(*text, self.styles.text_style))
+                    .map(|text| Span::styled(text.as_str(), self.styles.text_style))
                     .collect::<Vec<Span<'_>>>();
-                buttons.insert(0, Span::styled(SIGNAL_TEXT[0], self.styles.text_style));
+                buttons.insert(0, Span::styled(&signal_text[0], self.styles.text_style));
                 buttons[selected - scroll_offset] =
-                    Span::styled(SIGNAL_TEXT[selected], self.styles.selected_text_style);
+                    Span::styled(&signal_text[selected], self.styles.selected_text_style);
 
                 app_state.delete_dialog_state.button_positions = layout
                     .iter()
                     .enumerate()
-                    .map(|(i, pos)| {
-                        (
+                    .filter_map(|(i, pos)| {
+                        let position = if i == 0 { 0 } else { scroll_offset + i };
+                        let signal = match position {
+                            0 => 0,
+                            position => *signals.get(position - 1)?,
+                        };
+
+                        Some((
                             pos.x,
                             pos.y,
                             pos.x + pos.width - 1,
                             pos.y + pos.height - 1,
-                            if i == 0 { 0 } else { scroll_offset } + i,
-                        )
+                            signal,
+                        ))
                     })
                     .collect::<Vec<(u16, u16, u16, u16, usize)>>();
 
@@ -382,7 +248,8 @@ impl Painter {
                         if !app_state.app_config_fields.is_advanced_kill {
                             3
                         } else {
-                            20
+                            // Two rows of margin, plus a row for cancelling.
+                            (app_state.app_config_fields.kill_signals.len() as u16 + 3).min(20)
                         }
                     }
                 }
@@ -1,4 +1,4 @@
-use std::cmp::{max, min};
+use std::cmp::min;
 
 use tui::{
     layout::{Alignment, Rect},
@@ -6,21 +6,19 @@ use tui::{
     widgets::{Paragraph, Wrap},
     Frame,
 };
-use unicode_width::UnicodeWidthStr;
 
 use crate::{
     app::App,
-    canvas::{drawing_utils::dialog_block, Painter},
-    constants::{self, HELP_TEXT},
+    canvas::{components::scrollbar::Scrollbar, drawing_utils::dialog_block, Painter},
 };
 
 // TODO: [REFACTOR] Make generic dialog boxes to build off of instead?
 impl Painter {
-    fn help_text_lines(&self) -> Vec<Line<'_>> {
+    fn help_text_lines(&self, help_text: &[Vec<&'static str>]) -> Vec<Line<'_>> {
         let mut styled_help_spans = Vec::new();
 
         // Init help text:
-        HELP_TEXT.iter().enumerate().for_each(|(itx, section)| {
+        help_text.iter().enumerate().for_each(|(itx, section)| {
             let mut section = section.iter();
 
             if itx > 0 {
@@ -39,52 +37,35 @@ impl Painter {
     }
 
     pub fn draw_help_dialog(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect) {
-        let styled_help_text = self.help_text_lines();
+        let styled_help_text = self.help_text_lines(&app_state.help_dialog_state.text());
 
-        let block = dialog_block(self.styles.border_type)
+        let mut block = dialog_block(self.styles.border_type)
             .border_style(self.styles.border_style)
             .title_top(Line::styled(" Help ", self.styles.widget_title_style))
             .title_top(
                 Line::styled(" Esc to close ", self.styles.widget_title_style).right_aligned(),
             );
 
+        if app_state.is_state_unsaved {
+            block = block.title_bottom(
+                Line::styled(
+                    " State won't be saved, another instance is saving it ",
+                    self.styles.disabled_text_style,
+                )
+                .right_aligned(),
+            );
+        }
+
         if app_state.should_get_widget_bounds() {
             // We must also recalculate how many lines are wrapping to properly get
             // scrolling to work on small terminal sizes... oh joy.
 
             app_state.help_dialog_state.height = block.inner(draw_loc).height;
 
-            let mut overflow_buffer = 0;
-            let paragraph_width = max(draw_loc.width.saturating_sub(2), 1);
-            let mut prev_section_len = 0;
-
-            constants::HELP_TEXT
-                .iter()
-                .enumerate()
-                .for_each(|(itx, section)| {
-                    let mut buffer = 0;
-
-                    if itx == 0 {
-                        section.iter().for_each(|text_line| {
-                            buffer += UnicodeWidthStr::width(*text_line).saturating_sub(1) as u16
-                                / paragraph_width;
-                        });
-
-                        app_state.help_dialog_state.index_shortcuts[itx] = 0;
-                    } else {
-                        section.iter().for_each(|text_line| {
-                            buffer += UnicodeWidthStr::width(*text_line).saturating_sub(1) as u16
-                                / paragraph_width;
-                        });
-
-                        app_state.help_dialog_state.index_shortcuts[itx] =
-                            app_state.help_dialog_state.index_shortcuts[itx - 1]
-                                + 1
-                                + prev_section_len;
-                    }
-                    prev_section_len = section.len() as u16 + buffer;
-                    overflow_buffer += buffer;
-                });
+            let paragraph_width = draw_loc.width.saturating_sub(2);
+            let overflow_buffer = app_state
+                .help_dialog_state
+                .update_index_shortcuts(paragraph_width);
 
             let max_scroll_index = &mut app_state.help_dialog_state.scroll_state.max_scroll_index;
             *max_scroll_index = (styled_help_text.len() as u16 + 3 + overflow_buffer)
@@ -99,6 +80,22 @@ impl Painter {
             *index = min(*index, *max_scroll_index);
         }
 
+        if let Some(section) = app_state.help_dialog_state.pending_jump.take() {
+            let state = &mut app_state.help_dialog_state;
+            state.scroll_state.current_scroll_index = min(
+                state.index_shortcuts[section],
+                state.scroll_state.max_scroll_index,
+            );
+        }
+
+        let scroll_state = &app_state.help_dialog_state.scroll_state;
+        let scrollbar = Scrollbar::new(
+            scroll_state.current_scroll_index.into(),
+            scroll_state.max_scroll_index.into(),
+            app_state.help_dialog_state.height.into(),
+        )
+        .style(self.styles.border_style);
+
         f.render_widget(
             Paragraph::new(styled_help_text.clone())
                 .block(block)
@@ -114,5 +111,6 @@ impl Painter {
                 )),
             draw_loc,
         );
+        scrollbar.draw(f.buffer_mut(), draw_loc);
     }
 }
@@ -0,0 +1,74 @@
+use tui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{
+    app::App,
+    canvas::{drawing_utils::dialog_block, Painter},
+};
+
+impl Painter {
+    pub fn draw_jump_dialog(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let palette = &app_state.jump_palette;
+
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.border_style)
+            .title_top(Line::styled(" Jump to ", self.styles.widget_title_style))
+            .title_top(
+                Line::styled(" Esc to close ", self.styles.widget_title_style).right_aligned(),
+            );
+        let inner = block.inner(draw_loc);
+        f.render_widget(block, draw_loc);
+
+        let [query_loc, results_loc] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .areas(inner);
+
+        f.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled("> ", self.styles.table_header_style),
+                Span::styled(palette.query(), self.styles.text_style),
+            ])),
+            query_loc,
+        );
+
+        // Build the result lines, with a header whenever the widget changes.
+        let mut lines = vec![];
+        let mut selected_line = 0;
+        let mut last_widget_id = None;
+        for (index, entry) in palette.results().enumerate() {
+            if last_widget_id != Some(entry.widget_id) {
+                last_widget_id = Some(entry.widget_id);
+                lines.push(Line::styled(
+                    entry.widget_type.get_pretty_name(),
+                    self.styles.table_header_style,
+                ));
+            }
+
+            let style = if index == palette.selected_index() {
+                selected_line = lines.len();
+                self.styles.selected_text_style
+            } else {
+                self.styles.text_style
+            };
+            lines.push(Line::styled(format!("  {}", entry.label), style));
+        }
+
+        if lines.is_empty() {
+            lines.push(Line::styled("No matches", self.styles.disabled_text_style));
+        }
+
+        // Scroll so the selected line is visible.
+        let height = usize::from(results_loc.height);
+        let offset = (selected_line + 1).saturating_sub(height);
+
+        f.render_widget(
+            Paragraph::new(lines.into_iter().skip(offset).collect::<Vec<_>>()),
+            results_loc,
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use std::time::{Duration, Instant};
+
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    app::{time_markers::MarkerOverlayMode, App},
+    canvas::{drawing_utils::dialog_block, Painter},
+};
+
+/// The width of the marker overlay, including its borders.
+const DIALOG_WIDTH: u16 = 50;
+
+/// Formats how long ago a marker was added in its largest unit, like `2m ago`.
+fn format_ago(ago: Duration) -> String {
+    let secs = ago.as_secs();
+
+    match secs {
+        86400.. => format!("{}d ago", secs / 86400),
+        3600.. => format!("{}h ago", secs / 3600),
+        60.. => format!("{}m ago", secs / 60),
+        _ => format!("{secs}s ago"),
+    }
+}
+
+impl Painter {
+    /// Draws the marker overlay centered in `draw_loc`.
+    pub fn draw_marker_dialog(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let overlay = &app_state.marker_overlay;
+        let markers = &app_state.data_collection.time_markers;
+
+        let (title, lines, hint) = match overlay.mode() {
+            MarkerOverlayMode::Labelling(time) => {
+                let number = markers
+                    .iter()
+                    .position(|marker| marker.time == time)
+                    .map(|index| index + 1)
+                    .unwrap_or_default();
+
+                let line = Line::from(vec![
+                    Span::styled("Label: ", self.styles.table_header_style),
+                    Span::styled(overlay.label_text(), self.styles.text_style),
+                ]);
+
+                (format!(" Marker {number} "), vec![line], " Enter to save ")
+            }
+            MarkerOverlayMode::Listing | MarkerOverlayMode::Closed => {
+                let now = Instant::now();
+                let mut lines = markers
+                    .iter()
+                    .enumerate()
+                    .map(|(index, marker)| {
+                        let (selector, style) = if index == overlay.selected() {
+                            ("> ", self.styles.selected_text_style)
+                        } else {
+                            ("  ", self.styles.text_style)
+                        };
+                        let text = format!(
+                            "{:<3}{:<9}{}",
+                            index + 1,
+                            format_ago(now.saturating_duration_since(marker.time)),
+                            marker.label.as_deref().unwrap_or_default()
+                        );
+
+                        Line::from(vec![
+                            Span::styled(selector, self.styles.table_header_style),
+                            Span::styled(text, style),
+                        ])
+                    })
+                    .collect::<Vec<_>>();
+
+                if lines.is_empty() {
+                    lines.push(Line::styled(
+                        "No markers, press 'b' to add one",
+                        self.styles.disabled_text_style,
+                    ));
+                }
+
+                (
+                    " Markers ".to_string(),
+                    lines,
+                    " Enter to label, d to remove ",
+                )
+            }
+        };
+
+        let dialog_width = DIALOG_WIDTH.min(draw_loc.width);
+        let dialog_height = (lines.len() as u16 + 2).min(draw_loc.height);
+        let dialog_loc = Rect::new(
+            draw_loc.x + draw_loc.width.saturating_sub(dialog_width) / 2,
+            draw_loc.y + draw_loc.height.saturating_sub(dialog_height) / 2,
+            dialog_width,
+            dialog_height,
+        );
+
+        // Keep the selected marker in view.
+        let visible = usize::from(dialog_height.saturating_sub(2)).max(1);
+        let scroll = match overlay.mode() {
+            MarkerOverlayMode::Listing => overlay.selected().saturating_sub(visible - 1),
+            _ => 0,
+        };
+
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.border_style)
+            .title_top(Line::styled(title, self.styles.widget_title_style))
+            .title_top(
+                Line::styled(" Esc to close ", self.styles.widget_title_style).right_aligned(),
+            )
+            .title_bottom(Line::styled(hint, self.styles.widget_title_style).right_aligned());
+
+        f.render_widget(Clear, dialog_loc);
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .scroll((scroll as u16, 0)),
+            dialog_loc,
+        );
+    }
+}
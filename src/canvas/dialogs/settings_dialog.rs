@@ -0,0 +1,89 @@
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    app::{settings_overlay::SettingsField, App},
+    canvas::{drawing_utils::dialog_block, Painter},
+    utils::data_prefixes::get_binary_bytes,
+};
+
+/// The width of the settings overlay, including its borders.
+const DIALOG_WIDTH: u16 = 50;
+
+impl Painter {
+    /// Draws the settings overlay centered in `draw_loc`.
+    pub fn draw_settings_dialog(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let overlay = &app_state.settings_overlay;
+
+        let field_line = |name: &'static str, field: SettingsField| {
+            let (marker, style) = if overlay.selected() == field {
+                ("> ", self.styles.selected_text_style)
+            } else {
+                ("  ", self.styles.text_style)
+            };
+
+            Line::from(vec![
+                Span::styled(marker, self.styles.table_header_style),
+                Span::styled(format!("{name:<11}"), self.styles.table_header_style),
+                Span::styled(overlay.value(field).to_string(), style),
+            ])
+        };
+
+        let mut lines = vec![
+            field_line("Rate", SettingsField::Rate),
+            field_line("Retention", SettingsField::Retention),
+            Line::default(),
+        ];
+
+        match overlay.settings() {
+            Ok(settings) => {
+                let bytes = app_state
+                    .data_collection
+                    .estimated_footprint(settings.retention_ms, settings.update_rate_ms);
+                let (value, unit) = get_binary_bytes(bytes);
+
+                lines.push(Line::styled(
+                    format!("Graph data kept: up to {value:.1}{unit}"),
+                    self.styles.text_style,
+                ));
+            }
+            Err(_) => lines.push(Line::styled(
+                "Graph data kept: unknown",
+                self.styles.disabled_text_style,
+            )),
+        }
+
+        if let Some(error) = overlay.error() {
+            lines.push(Line::styled(
+                error.to_string(),
+                self.styles.invalid_query_style,
+            ));
+        }
+
+        let dialog_width = DIALOG_WIDTH.min(draw_loc.width);
+        let dialog_height = (lines.len() as u16 + 2).min(draw_loc.height);
+        let dialog_loc = Rect::new(
+            draw_loc.x + draw_loc.width.saturating_sub(dialog_width) / 2,
+            draw_loc.y + draw_loc.height.saturating_sub(dialog_height) / 2,
+            dialog_width,
+            dialog_height,
+        );
+
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.border_style)
+            .title_top(Line::styled(" Settings ", self.styles.widget_title_style))
+            .title_top(
+                Line::styled(" Esc to close ", self.styles.widget_title_style).right_aligned(),
+            )
+            .title_bottom(
+                Line::styled(" Enter to apply ", self.styles.widget_title_style).right_aligned(),
+            );
+
+        f.render_widget(Clear, dialog_loc);
+        f.render_widget(Paragraph::new(lines).block(block), dialog_loc);
+    }
+}
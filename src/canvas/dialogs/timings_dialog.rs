@@ -0,0 +1,78 @@
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    app::{layout_manager::BottomWidgetType, App},
+    canvas::{drawing_utils::dialog_block, Painter},
+};
+
+/// The width of the draw times overlay, including its borders.
+const DIALOG_WIDTH: u16 = 30;
+
+/// The name of a widget in the draw times overlay. Parts of widgets and the
+/// basic mode widgets are named after the widget they belong to.
+fn widget_name(widget_type: &BottomWidgetType) -> &str {
+    use BottomWidgetType::*;
+
+    match widget_type {
+        CpuLegend | BasicCpu => "CPU",
+        BasicMem => "Memory",
+        BasicNet => "Network",
+        ProcSearch | ProcSort => "Processes",
+        _ => widget_type.get_pretty_name(),
+    }
+}
+
+impl Painter {
+    /// Draws how long each widget takes to draw, slowest first, in the top
+    /// right corner of `draw_loc`. Unlike the other overlays, this can stay open
+    /// while using bottom.
+    pub fn draw_timings_dialog(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let text_width = usize::from(DIALOG_WIDTH - 2);
+
+        let mut lines = app_state
+            .draw_timings
+            .slowest_first()
+            .into_iter()
+            .map(|(_, widget)| {
+                let time = format!("{:.2}ms", widget.average().as_secs_f64() * 1000.0);
+                let name = widget_name(&widget.widget_type);
+                let padding = text_width.saturating_sub(name.len() + time.len() + 2);
+
+                Line::from(vec![
+                    Span::styled(format!(" {name}{:padding$}", ""), self.styles.text_style),
+                    Span::styled(format!("{time} "), self.styles.table_header_style),
+                ])
+            })
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            lines.push(Line::styled(
+                " Nothing drawn yet",
+                self.styles.disabled_text_style,
+            ));
+        }
+
+        let dialog_width = DIALOG_WIDTH.min(draw_loc.width);
+        let dialog_height = (lines.len() as u16 + 2).min(draw_loc.height);
+        let dialog_loc = Rect::new(
+            draw_loc.x + draw_loc.width.saturating_sub(dialog_width),
+            draw_loc.y,
+            dialog_width,
+            dialog_height,
+        );
+
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.border_style)
+            .title_top(Line::styled(" Draw times ", self.styles.widget_title_style))
+            .title_top(
+                Line::styled(" T to close ", self.styles.widget_title_style).right_aligned(),
+            );
+
+        f.render_widget(Clear, dialog_loc);
+        f.render_widget(Paragraph::new(lines).block(block), dialog_loc);
+    }
+}
@@ -2,6 +2,7 @@ use std::time::Instant;
 
 use tui::{
     layout::Rect,
+    style::Color,
     widgets::{Block, BorderType, Borders},
 };
 
@@ -51,6 +52,69 @@ pub fn dialog_block(border_type: BorderType) -> Block<'static> {
         .borders(Borders::all())
 }
 
+/// How many colours the terminal can show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColourDepth {
+    TrueColour,
+    Ansi256,
+    Ansi16,
+}
+
+impl ColourDepth {
+    /// Guesses the terminal's colour depth from the `COLORTERM` and `TERM`
+    /// environment variables.
+    pub fn detect() -> Self {
+        Self::from_env(
+            std::env::var("COLORTERM").ok().as_deref(),
+            std::env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            ColourDepth::TrueColour
+        } else if term.is_some_and(|term| term.contains("256color")) {
+            ColourDepth::Ansi256
+        } else {
+            ColourDepth::Ansi16
+        }
+    }
+}
+
+/// Returns a colour from green through yellow to red for a load between 0 and
+/// 100 percent, using only colours that `depth` can show.
+pub fn load_colour(percent: f64, depth: ColourDepth) -> Color {
+    let load = (percent / 100.0).clamp(0.0, 1.0);
+
+    match depth {
+        ColourDepth::Ansi16 => {
+            if load < 1.0 / 3.0 {
+                Color::Green
+            } else if load < 2.0 / 3.0 {
+                Color::Yellow
+            } else {
+                Color::Red
+            }
+        }
+        ColourDepth::TrueColour | ColourDepth::Ansi256 => {
+            let red = (load * 2.0).min(1.0);
+            let green = ((1.0 - load) * 2.0).min(1.0);
+
+            if depth == ColourDepth::TrueColour {
+                Color::Rgb(
+                    (red * 255.0).round() as u8,
+                    (green * 255.0).round() as u8,
+                    0,
+                )
+            } else {
+                // The 6x6x6 colour cube of the 256-colour palette.
+                let level = |value: f64| (value * 5.0).round() as u8;
+                Color::Indexed(16 + 36 * level(red) + 6 * level(green))
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -91,4 +155,50 @@ mod test {
         ));
         assert!(over_timer.is_none());
     }
+
+    #[test]
+    fn colour_depth_detection() {
+        assert_eq!(
+            ColourDepth::from_env(Some("truecolor"), Some("xterm")),
+            ColourDepth::TrueColour
+        );
+        assert_eq!(
+            ColourDepth::from_env(None, Some("xterm-256color")),
+            ColourDepth::Ansi256
+        );
+        assert_eq!(
+            ColourDepth::from_env(None, Some("linux")),
+            ColourDepth::Ansi16
+        );
+        assert_eq!(ColourDepth::from_env(None, None), ColourDepth::Ansi16);
+    }
+
+    #[test]
+    fn load_colours() {
+        use tui::style::Color;
+
+        assert_eq!(
+            load_colour(0.0, ColourDepth::TrueColour),
+            Color::Rgb(0, 255, 0)
+        );
+        assert_eq!(
+            load_colour(50.0, ColourDepth::TrueColour),
+            Color::Rgb(255, 255, 0)
+        );
+        assert_eq!(
+            load_colour(150.0, ColourDepth::TrueColour),
+            Color::Rgb(255, 0, 0)
+        );
+
+        assert_eq!(load_colour(0.0, ColourDepth::Ansi256), Color::Indexed(46));
+        assert_eq!(load_colour(50.0, ColourDepth::Ansi256), Color::Indexed(226));
+        assert_eq!(
+            load_colour(100.0, ColourDepth::Ansi256),
+            Color::Indexed(196)
+        );
+
+        assert_eq!(load_colour(10.0, ColourDepth::Ansi16), Color::Green);
+        assert_eq!(load_colour(50.0, ColourDepth::Ansi16), Color::Yellow);
+        assert_eq!(load_colour(90.0, ColourDepth::Ansi16), Color::Red);
+    }
 }
@@ -0,0 +1,197 @@
+//! Rendering graphs as text, using the same drawing code as on screen but
+//! into an off-screen buffer.
+
+use std::io;
+
+use tui::{backend::TestBackend, buffer::Buffer, Frame, Terminal};
+use unicode_width::UnicodeWidthStr;
+
+use super::Painter;
+use crate::app::{layout_manager::BottomWidgetType, App};
+
+/// The width and height of graph snapshots, in cells.
+const SNAPSHOT_SIZE: (u16, u16) = (100, 25);
+
+/// Returns the contents of `buffer` as lines of text, without any styling or
+/// trailing whitespace.
+fn buffer_to_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut lines = Vec::with_capacity(area.height.into());
+
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        let mut skip = 0;
+
+        for x in area.left()..area.right() {
+            // Wide characters take up the cells after them too.
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+
+            let symbol = buffer[(x, y)].symbol();
+            skip = symbol.width().saturating_sub(1);
+            line.push_str(symbol);
+        }
+
+        lines.push(line.trim_end().to_string());
+    }
+
+    lines.join("\n")
+}
+
+/// Draws with `draw` onto an off-screen buffer of the given size.
+pub fn render_to_buffer(
+    width: u16, height: u16, draw: impl FnOnce(&mut Frame<'_>),
+) -> io::Result<Buffer> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    terminal.draw(draw)?;
+
+    Ok(terminal.backend().buffer().clone())
+}
+
+/// Draws with `draw` onto an off-screen buffer of the given size, and returns
+/// the result as text.
+pub fn render_to_text(
+    width: u16, height: u16, draw: impl FnOnce(&mut Frame<'_>),
+) -> io::Result<String> {
+    render_to_buffer(width, height, draw).map(|buffer| buffer_to_text(&buffer))
+}
+
+impl Painter {
+    /// Renders the selected graph as text, returning its name and the
+    /// rendered graph. Returns [`None`] if the selected widget isn't a graph.
+    pub fn graph_snapshot(&self, app_state: &mut App) -> Option<(&'static str, String)> {
+        let widget_id = app_state.current_widget.widget_id;
+        let (name, widget_type, widget_id) = match app_state.current_widget.widget_type {
+            BottomWidgetType::Cpu => ("CPU", BottomWidgetType::Cpu, widget_id),
+            BottomWidgetType::CpuLegend => ("CPU", BottomWidgetType::Cpu, widget_id - 1),
+            BottomWidgetType::Mem => ("Memory", BottomWidgetType::Mem, widget_id),
+            BottomWidgetType::Net => ("Network", BottomWidgetType::Net, widget_id),
+            #[cfg(feature = "gpu")]
+            BottomWidgetType::Gpu => ("GPU", BottomWidgetType::Gpu, widget_id),
+            _ => return None,
+        };
+
+        // Don't let the snapshot's size replace the widget's on-screen bounds.
+        let bounds_flags = (
+            app_state.is_force_redraw,
+            app_state.is_determining_widget_boundary,
+        );
+        app_state.is_force_redraw = false;
+        app_state.is_determining_widget_boundary = false;
+
+        let (width, height) = SNAPSHOT_SIZE;
+        let snapshot = render_to_text(width, height, |f| {
+            let area = f.area();
+            match widget_type {
+                BottomWidgetType::Cpu => self.draw_cpu(f, app_state, area, widget_id),
+                BottomWidgetType::Mem => self.draw_memory_graph(f, app_state, area, widget_id),
+                #[cfg(feature = "gpu")]
+                BottomWidgetType::Gpu => self.draw_gpu_graph(f, app_state, area, widget_id),
+                _ => self.draw_network(f, app_state, area, widget_id),
+            }
+        });
+
+        (
+            app_state.is_force_redraw,
+            app_state.is_determining_widget_boundary,
+        ) = bounds_flags;
+
+        snapshot.ok().map(|mut snapshot| {
+            snapshot.push_str(&marker_list(app_state));
+            (name, snapshot)
+        })
+    }
+}
+
+/// Lists the points in time marked on the graphs, to go after a snapshot, as
+/// their tags alone don't say when they were.
+fn marker_list(app_state: &App) -> String {
+    app_state
+        .data_collection
+        .time_markers
+        .iter()
+        .enumerate()
+        .map(|(index, marker)| {
+            let time = humantime::format_rfc3339_seconds(marker.system_time);
+            match &marker.label {
+                Some(label) => format!("\nMarker {}: {time} {label}", index + 1),
+                None => format!("\nMarker {}: {time}", index + 1),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use tui::{style::Style, symbols::Marker, widgets::BorderType};
+
+    use super::*;
+    use crate::canvas::components::time_graph::{GraphData, TimeGraph};
+
+    #[test]
+    fn renders_a_graph_to_text() {
+        const Y_LABELS: [Cow<'static, str>; 2] = [Cow::Borrowed("0%"), Cow::Borrowed("100%")];
+
+        let graph = TimeGraph {
+            title: " CPU ".into(),
+            x_bounds: [0, 10_000],
+            hide_x_labels: false,
+            y_bounds: [0.0, 100.0],
+            y_labels: &Y_LABELS,
+            graph_style: Style::default(),
+            border_style: Style::default(),
+            border_type: BorderType::Plain,
+            is_selected: false,
+            is_expanded: false,
+            title_style: Style::default(),
+            legend_position: None,
+            legend_constraints: None,
+            marker: Marker::Dot,
+            gaps: &[],
+            markers: &[],
+            vertical_markers: &[],
+            vertical_marker_style: Style::default(),
+            thick_lines: false,
+            newest_on_left: false,
+            gridlines: &[],
+            placeholder: None,
+        };
+
+        // A line going from the bottom left to the top right.
+        let points = [(-10_000.0, 0.0), (0.0, 100.0)];
+        let data = [GraphData {
+            points: &points,
+            style: Style::default(),
+            name: None,
+        }];
+
+        let text = render_to_text(20, 7, |f| graph.draw_time_graph(f, f.area(), &data)).unwrap();
+        assert_eq!(
+            text,
+            [
+                "┌ CPU ─────────────┐",
+                "│100%│          •••│",
+                "│    │    ••••••   │",
+                "│0%  │••••         │",
+                "│    └─────────────│",
+                "│  10s           0s│",
+                "└──────────────────┘",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn wide_characters_take_one_symbol() {
+        let text = render_to_text(6, 1, |f| {
+            f.render_widget(tui::widgets::Paragraph::new("日本a"), f.area())
+        })
+        .unwrap();
+
+        assert_eq!(text, "日本a");
+    }
+}
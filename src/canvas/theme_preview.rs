@@ -0,0 +1,426 @@
+//! A small static preview of a theme's colors, for comparing themes without
+//! starting bottom.
+
+use std::io::{self, Write};
+
+use crossterm::{
+    queue,
+    style::{Attribute, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
+};
+use tui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use unicode_width::UnicodeWidthStr;
+
+use super::{drawing_utils::widget_block, snapshot::render_to_buffer};
+use crate::{data_collection::cpu::CpuTimeKind, options::config::style::Styles};
+
+/// The width of the preview, in cells.
+const PREVIEW_WIDTH: u16 = 80;
+
+/// A bit of a graph line, to show off a graph colour.
+const GRAPH_LINE: &str = "⡠⠊";
+
+/// Returns a graph line and name for each style, separated by spaces and
+/// wrapped to fit in `width` cells.
+fn legend<'a>(entries: impl IntoIterator<Item = (String, Style)>, width: usize) -> Vec<Line<'a>> {
+    let mut lines = vec![];
+    let mut spans = vec![];
+    let mut line_width = 0;
+
+    for (name, style) in entries {
+        let entry_width = GRAPH_LINE.width() + name.width();
+        if !spans.is_empty() {
+            if line_width + 1 + entry_width > width {
+                lines.push(Line::from(std::mem::take(&mut spans)));
+                line_width = 0;
+            } else {
+                spans.push(Span::raw(" "));
+                line_width += 1;
+            }
+        }
+
+        spans.push(Span::styled(GRAPH_LINE, style));
+        spans.push(Span::styled(name, style));
+        line_width += entry_width;
+    }
+
+    if !spans.is_empty() {
+        lines.push(Line::from(spans));
+    }
+
+    lines
+}
+
+/// Numbers each style, e.g. "CPU0", "CPU1", and so on.
+fn numbered(prefix: &str, styles: &[Style]) -> Vec<(String, Style)> {
+    styles
+        .iter()
+        .enumerate()
+        .map(|(itx, style)| (format!("{prefix}{itx}"), *style))
+        .collect()
+}
+
+/// A widget in the preview.
+struct Section {
+    title: &'static str,
+    is_selected: bool,
+    lines: Vec<Line<'static>>,
+}
+
+/// Returns the widgets to preview each of the theme's styles with.
+fn preview_sections(styles: &Styles) -> Vec<Section> {
+    // Leave room for the borders.
+    let width = usize::from(PREVIEW_WIDTH - 2);
+
+    let mut cpu = vec![
+        ("AVG".to_string(), styles.avg_cpu_colour),
+        ("ALL".to_string(), styles.all_cpu_colour),
+    ];
+    cpu.extend(numbered("CPU", &styles.cpu_colour_styles));
+    let mut cpu = legend(cpu, width);
+    cpu.extend(legend(
+        [
+            (CpuTimeKind::User, styles.cpu_user_style),
+            (CpuTimeKind::System, styles.cpu_system_style),
+            (CpuTimeKind::IoWait, styles.cpu_iowait_style),
+            (CpuTimeKind::Steal, styles.cpu_steal_style),
+        ]
+        .map(|(kind, style)| (kind.name().to_string(), style)),
+        width,
+    ));
+    cpu.push(Line::from(vec![
+        Span::styled("0%│──────────│100%", styles.graph_style),
+        Span::raw(" "),
+        Span::styled("Legend", styles.graph_legend_style),
+    ]));
+
+    let mut memory = vec![("RAM".to_string(), styles.ram_style)];
+    #[cfg(not(target_os = "windows"))]
+    memory.push(("Cache".to_string(), styles.cache_style));
+    memory.push(("Swap".to_string(), styles.swap_style));
+    #[cfg(feature = "zfs")]
+    memory.push(("ARC".to_string(), styles.arc_style));
+    #[cfg(feature = "gpu")]
+    memory.extend(numbered("GPU", &styles.gpu_colours));
+    let mut data = legend(memory, width);
+    data.extend(legend(
+        [
+            ("RX".to_string(), styles.rx_style),
+            ("TX".to_string(), styles.tx_style),
+            ("Total RX".to_string(), styles.total_rx_style),
+            ("Total TX".to_string(), styles.total_tx_style),
+        ],
+        width,
+    ));
+    data.extend(legend(
+        numbered("eth", &styles.network_interface_colours),
+        width,
+    ));
+
+    vec![
+        Section {
+            title: " CPU ",
+            is_selected: true,
+            lines: cpu,
+        },
+        Section {
+            title: " Memory and Network ",
+            is_selected: false,
+            lines: data,
+        },
+        Section {
+            title: " Processes ",
+            is_selected: false,
+            lines: vec![
+                Line::styled("PID    Name        CPU%", styles.table_header_style),
+                Line::styled("1      init        0.1%", styles.selected_text_style),
+                Line::styled("42     bash        2.5%", styles.text_style),
+                Line::styled("77     zombie      0.0%", styles.disabled_text_style),
+                Line::styled("99     cargo       9.9%", styles.added_text_style),
+                Line::styled("Search: name = (", styles.invalid_query_style),
+            ],
+        },
+        Section {
+            title: " Battery ",
+            is_selected: false,
+            lines: vec![Line::from(vec![
+                Span::styled("High ██████████", styles.high_battery),
+                Span::raw("  "),
+                Span::styled("Medium ██████", styles.medium_battery),
+                Span::raw("  "),
+                Span::styled("Low ██", styles.low_battery),
+            ])],
+        },
+    ]
+}
+
+/// Draws each section, one under the other.
+fn draw_sections(f: &mut Frame<'_>, styles: &Styles, sections: Vec<Section>) {
+    let areas = Layout::vertical(
+        sections
+            .iter()
+            .map(|section| Constraint::Length(section.lines.len() as u16 + 2)),
+    )
+    .split(f.area());
+
+    for (section, area) in sections.into_iter().zip(areas.iter()) {
+        let border_style = if section.is_selected {
+            styles.highlighted_border_style
+        } else {
+            styles.border_style
+        };
+        let block = widget_block(false, section.is_selected, styles.border_type)
+            .border_style(border_style)
+            .title_top(Line::styled(section.title, styles.widget_title_style));
+
+        f.render_widget(Paragraph::new(section.lines).block(block), *area);
+    }
+}
+
+/// Renders the preview of `styles` to a buffer.
+fn render_theme_preview(styles: &Styles) -> io::Result<Buffer> {
+    let sections = preview_sections(styles);
+    let height = sections
+        .iter()
+        .map(|section| section.lines.len() as u16 + 2)
+        .sum();
+
+    render_to_buffer(PREVIEW_WIDTH, height, |f| {
+        draw_sections(f, styles, sections)
+    })
+}
+
+/// Writes a buffer to `out` as lines of text, using escape codes for colours.
+fn write_buffer(out: &mut impl Write, buffer: &Buffer) -> io::Result<()> {
+    const ATTRIBUTES: [(Modifier, Attribute); 5] = [
+        (Modifier::BOLD, Attribute::Bold),
+        (Modifier::DIM, Attribute::Dim),
+        (Modifier::ITALIC, Attribute::Italic),
+        (Modifier::UNDERLINED, Attribute::Underlined),
+        (Modifier::REVERSED, Attribute::Reverse),
+    ];
+
+    let area = buffer.area;
+    for y in area.top()..area.bottom() {
+        let mut current = None;
+
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            let style = (cell.fg, cell.bg, cell.modifier);
+
+            if current != Some(style) {
+                queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+                queue!(
+                    out,
+                    SetForegroundColor(cell.fg.into()),
+                    SetBackgroundColor(cell.bg.into())
+                )?;
+                for (modifier, attribute) in ATTRIBUTES {
+                    if cell.modifier.contains(modifier) {
+                        queue!(out, SetAttribute(attribute))?;
+                    }
+                }
+
+                current = Some(style);
+            }
+
+            queue!(out, Print(cell.symbol()))?;
+        }
+
+        queue!(out, SetAttribute(Attribute::Reset), ResetColor, Print("\n"))?;
+    }
+
+    out.flush()
+}
+
+/// Prints a preview of a built-in theme's colours to stdout.
+pub fn print_theme_preview(theme: &str) -> anyhow::Result<()> {
+    let styles = Styles::from_theme(theme)?;
+    let buffer = render_theme_preview(&styles)?;
+
+    println!("Theme: {theme}");
+    write_buffer(&mut io::stdout().lock(), &buffer)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use tui::style::Color;
+
+    use super::*;
+
+    /// Returns styles where every style has its own colour.
+    fn unique_styles() -> Styles {
+        let mut next = 0;
+        let mut unique = || {
+            next += 1;
+            Style::default().fg(Color::Indexed(next))
+        };
+
+        Styles {
+            ram_style: unique(),
+            #[cfg(not(target_os = "windows"))]
+            cache_style: unique(),
+            swap_style: unique(),
+            #[cfg(feature = "zfs")]
+            arc_style: unique(),
+            #[cfg(feature = "gpu")]
+            gpu_colours: vec![unique(), unique()],
+            rx_style: unique(),
+            tx_style: unique(),
+            total_rx_style: unique(),
+            total_tx_style: unique(),
+            network_interface_colours: vec![unique(), unique()],
+            all_cpu_colour: unique(),
+            avg_cpu_colour: unique(),
+            cpu_colour_styles: vec![unique(), unique(), unique()],
+            cpu_user_style: unique(),
+            cpu_system_style: unique(),
+            cpu_iowait_style: unique(),
+            cpu_steal_style: unique(),
+            border_style: unique(),
+            highlighted_border_style: unique(),
+            text_style: unique(),
+            selected_text_style: unique(),
+            table_header_style: unique(),
+            widget_title_style: unique(),
+            graph_style: unique(),
+            graph_legend_style: unique(),
+            high_battery: unique(),
+            medium_battery: unique(),
+            low_battery: unique(),
+            invalid_query_style: unique(),
+            disabled_text_style: unique(),
+            added_text_style: unique(),
+            ..Styles::default_style()
+        }
+    }
+
+    #[test]
+    fn preview_uses_every_style() {
+        let styles = unique_styles();
+        let buffer = render_theme_preview(&styles).unwrap();
+        let used = buffer
+            .content()
+            .iter()
+            .map(|cell| cell.fg)
+            .collect::<HashSet<_>>();
+
+        // Every style is listed, so that new ones have to be previewed too.
+        let Styles {
+            ram_style,
+            #[cfg(not(target_os = "windows"))]
+            cache_style,
+            swap_style,
+            #[cfg(feature = "zfs")]
+            arc_style,
+            #[cfg(feature = "gpu")]
+            gpu_colours,
+            rx_style,
+            tx_style,
+            total_rx_style,
+            total_tx_style,
+            network_interface_colours,
+            all_cpu_colour,
+            avg_cpu_colour,
+            cpu_colour_styles,
+            cpu_user_style,
+            cpu_system_style,
+            cpu_iowait_style,
+            cpu_steal_style,
+            border_style,
+            highlighted_border_style,
+            text_style,
+            selected_text_style,
+            table_header_style,
+            widget_title_style,
+            graph_style,
+            graph_legend_style,
+            high_battery,
+            medium_battery,
+            low_battery,
+            invalid_query_style,
+            disabled_text_style,
+            added_text_style,
+            border_type: _,
+            gridlines: _,
+        } = &styles;
+
+        let mut expected = vec![
+            ram_style,
+            #[cfg(not(target_os = "windows"))]
+            cache_style,
+            swap_style,
+            #[cfg(feature = "zfs")]
+            arc_style,
+            rx_style,
+            tx_style,
+            total_rx_style,
+            total_tx_style,
+            all_cpu_colour,
+            avg_cpu_colour,
+            cpu_user_style,
+            cpu_system_style,
+            cpu_iowait_style,
+            cpu_steal_style,
+            border_style,
+            highlighted_border_style,
+            text_style,
+            selected_text_style,
+            table_header_style,
+            widget_title_style,
+            graph_style,
+            graph_legend_style,
+            high_battery,
+            medium_battery,
+            low_battery,
+            invalid_query_style,
+            disabled_text_style,
+            added_text_style,
+        ];
+        #[cfg(feature = "gpu")]
+        expected.extend(gpu_colours);
+        expected.extend(network_interface_colours);
+        expected.extend(cpu_colour_styles);
+
+        for style in expected {
+            let colour = style.fg.unwrap();
+            assert!(used.contains(&colour), "{colour:?} isn't in the preview");
+        }
+    }
+
+    #[test]
+    fn preview_is_written_with_colours() {
+        let buffer = render_theme_preview(&Styles::default()).unwrap();
+        let mut out = vec![];
+        write_buffer(&mut out, &buffer).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.lines().count(), usize::from(buffer.area.height));
+        assert!(out.contains("\x1b["));
+        assert!(out.contains("Battery"));
+    }
+
+    #[test]
+    fn legends_wrap() {
+        let style = Style::default();
+        let entries = ["a", "bb", "c"].map(|name| (name.to_string(), style));
+
+        let lines = legend(entries.clone(), 10);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].to_string(), "⡠⠊a ⡠⠊bb");
+        assert_eq!(lines[1].to_string(), "⡠⠊c");
+
+        assert_eq!(legend(entries, 80).len(), 1);
+    }
+}
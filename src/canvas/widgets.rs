@@ -1,6 +1,10 @@
 pub mod cpu_basic;
 pub mod cpu_graph;
 pub mod disk_table;
+#[cfg(feature = "gpu")]
+pub mod gpu_graph;
+#[cfg(target_family = "unix")]
+pub mod load_avg;
 pub mod mem_basic;
 pub mod mem_graph;
 pub mod network_basic;
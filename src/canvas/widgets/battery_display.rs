@@ -1,7 +1,8 @@
-use std::cmp::min;
+use std::{borrow::Cow, cmp::min};
 
 use tui::{
     layout::{Constraint, Direction, Layout, Rect},
+    symbols::Marker,
     text::{Line, Span},
     widgets::{Cell, Paragraph, Row, Table, Tabs},
     Frame,
@@ -10,11 +11,22 @@ use unicode_width::UnicodeWidthStr;
 
 use crate::{
     app::App,
-    canvas::{drawing_utils::widget_block, Painter},
+    canvas::{
+        components::{
+            time_chart::LegendPosition,
+            time_graph::{GraphData, TimeGraph},
+        },
+        drawing_utils::{should_hide_x_label, widget_block},
+        Painter,
+    },
     constants::*,
     data_collection::batteries::BatteryState,
 };
 
+/// The minimum height needed to draw the charge graph below the battery
+/// details.
+const MIN_GRAPH_HEIGHT: u16 = 7;
+
 /// Calculate how many bars are to be drawn within basic mode's components.
 fn calculate_basic_use_bars(use_percentage: f64, num_bars_available: usize) -> usize {
     min(
@@ -65,13 +77,14 @@ impl Painter {
                 block
             };
 
-            if app_state.data_collection.battery_harvest.len() > 1 {
+            let has_multiple_batteries = app_state.data_collection.battery_harvest.len() > 1;
+
+            if has_multiple_batteries {
                 let battery_names = app_state
                     .data_collection
                     .battery_harvest
                     .iter()
-                    .enumerate()
-                    .map(|(itx, _)| format!("Battery {itx}"))
+                    .map(|battery| format!("Battery {}", battery.id))
                     .collect::<Vec<_>>();
 
                 let tab_draw_loc = Layout::default()
@@ -124,6 +137,28 @@ impl Painter {
                 .direction(Direction::Horizontal)
                 .split(draw_loc)[0];
 
+            // The details take up the borders, the charge bar, the gap below it, and four
+            // rows of info. If there's enough space left (and we're not in basic mode), draw
+            // the charge graph below.
+            let details_height = 8
+                + table_gap
+                + if has_multiple_batteries {
+                    1 + table_gap
+                } else {
+                    0
+                };
+            let (details_draw_loc, graph_draw_loc) =
+                if !is_basic && margined_draw_loc.height >= details_height + MIN_GRAPH_HEIGHT {
+                    let split = Layout::default()
+                        .constraints([Constraint::Length(details_height), Constraint::Min(0)])
+                        .direction(Direction::Vertical)
+                        .split(margined_draw_loc);
+
+                    (split[0], Some(split[1]))
+                } else {
+                    (margined_draw_loc, None)
+                };
+
             if let Some(battery_details) = app_state
                 .data_collection
                 .battery_harvest
@@ -202,7 +237,7 @@ impl Painter {
 
                 battery_rows.push(Row::new(["Health", &health]).style(self.styles.text_style));
 
-                let header = if app_state.data_collection.battery_harvest.len() > 1 {
+                let header = if has_multiple_batteries {
                     Row::new([""]).bottom_margin(table_gap)
                 } else {
                     Row::default()
@@ -213,7 +248,7 @@ impl Painter {
                     Table::new(battery_charge_rows, [Constraint::Percentage(100)])
                         .block(block.clone())
                         .header(header.clone()),
-                    margined_draw_loc,
+                    details_draw_loc,
                 );
 
                 // Draw info
@@ -224,7 +259,7 @@ impl Painter {
                     )
                     .block(block)
                     .header(header),
-                    margined_draw_loc,
+                    details_draw_loc,
                 );
             } else {
                 let mut contents = vec![Line::default(); table_gap.into()];
@@ -234,7 +269,78 @@ impl Painter {
                     self.styles.text_style,
                 )));
 
-                f.render_widget(Paragraph::new(contents).block(block), margined_draw_loc);
+                f.render_widget(Paragraph::new(contents).block(block), details_draw_loc);
+            }
+
+            if let Some(graph_draw_loc) = graph_draw_loc {
+                const Y_BOUNDS: [f64; 2] = [0.0, 100.5];
+                const Y_LABELS: [Cow<'static, str>; 2] =
+                    [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
+
+                let hide_x_labels = should_hide_x_label(
+                    app_state.app_config_fields.hide_time,
+                    app_state.app_config_fields.autohide_time,
+                    &mut battery_widget_state.autohide_timer,
+                    graph_draw_loc,
+                );
+
+                // Each chunk is drawn as its own series so that no line is drawn across
+                // the time a battery was missing; only the most recent one is named.
+                let colours = &self.styles.cpu_colour_styles;
+                let points = app_state
+                    .converted_data
+                    .battery_data
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(id, chunks)| {
+                        let style = colours[id % colours.len()];
+                        let last_chunk = chunks.len().saturating_sub(1);
+
+                        chunks
+                            .iter()
+                            .enumerate()
+                            .map(move |(itx, chunk)| GraphData {
+                                points: chunk,
+                                style,
+                                name: (itx == last_chunk).then(|| {
+                                    let charge =
+                                        chunk.last().map(|(_, charge)| *charge).unwrap_or(0.0);
+                                    format!("Battery {id}:{charge:3.0}%").into()
+                                }),
+                            })
+                    })
+                    .collect::<Vec<_>>();
+
+                let marker = if app_state.app_config_fields.use_dot {
+                    Marker::Dot
+                } else {
+                    Marker::Braille
+                };
+                TimeGraph {
+                    x_bounds: [0, battery_widget_state.current_display_time],
+                    hide_x_labels,
+                    y_bounds: Y_BOUNDS,
+                    y_labels: &Y_LABELS,
+                    graph_style: self.styles.graph_style,
+                    border_style,
+                    border_type: self.styles.border_type,
+                    title: " Charge ".into(),
+                    is_selected,
+                    is_expanded: false,
+                    title_style: self.styles.widget_title_style,
+                    legend_position: Some(LegendPosition::default()),
+                    legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                    marker,
+                    gaps: &app_state.converted_data.gaps,
+                    markers: &[],
+                    vertical_markers: &app_state.converted_data.time_markers,
+                    vertical_marker_style: self.styles.highlighted_border_style,
+                    thick_lines: app_state.app_config_fields.use_thick_lines,
+                    newest_on_left: app_state.app_config_fields.newest_on_left,
+                    gridlines: &[],
+                    placeholder: None,
+                }
+                .draw_time_graph(f, graph_draw_loc, &points);
             }
 
             if should_get_widget_bounds {
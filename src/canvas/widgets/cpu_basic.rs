@@ -159,6 +159,10 @@ impl Painter {
                 format!("{index:<3}",),
                 self.styles.cpu_colour_styles[index % self.styles.cpu_colour_styles.len()],
             ),
+            CpuDataType::Socket(index) => (
+                format!("S{index:<2}"),
+                self.styles.cpu_colour_styles[index % self.styles.cpu_colour_styles.len()],
+            ),
         };
         let inner = format!("{:>3.0}%", last_entry.round());
         let ratio = last_entry / 100.0;
@@ -11,24 +11,35 @@ use crate::{
     canvas::{
         components::{
             data_table::{DrawInfo, SelectionState},
-            time_graph::{GraphData, TimeGraph},
+            time_chart::LegendPosition,
+            time_graph::{GraphData, TimeGraph, COLLECTING_TEXT},
         },
         drawing_utils::should_hide_x_label,
         Painter,
     },
-    data_conversion::CpuWidgetData,
+    data_collection::cpu::CpuTimeKind,
+    data_conversion::{CpuBreakdownData, CpuWidgetData},
     widgets::CpuWidgetState,
 };
 
 const AVG_POSITION: usize = 1;
 const ALL_POSITION: usize = 0;
 
+/// What is shown instead of the CPU time breakdown where it isn't collected.
+const UNSUPPORTED_BREAKDOWN_TEXT: &str = "CPU time breakdown is unsupported";
+
 impl Painter {
     pub fn draw_cpu(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64) {
         let legend_width = (draw_loc.width as f64 * 0.15) as u16;
+        let show_breakdown = app_state
+            .states
+            .cpu_state
+            .widget_states
+            .get(&widget_id)
+            .is_some_and(|state| state.show_breakdown);
 
-        if legend_width < 6 {
-            // Skip drawing legend
+        if legend_width < 6 || show_breakdown {
+            // Skip drawing the legend, which is also unused while showing the breakdown.
             if app_state.current_widget.widget_id == (widget_id + 1) {
                 if app_state.app_config_fields.cpu_left_legend {
                     app_state.move_widget_selection(&WidgetDirection::Right);
@@ -50,6 +61,12 @@ impl Painter {
                     bottom_widget.bottom_right_corner =
                         Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
                 }
+
+                // The legend isn't drawn, so it shouldn't be clickable.
+                if let Some(legend_widget) = app_state.widget_map.get_mut(&(widget_id + 1)) {
+                    legend_widget.top_left_corner = None;
+                    legend_widget.bottom_right_corner = None;
+                }
             }
         } else {
             let graph_width = draw_loc.width - legend_width;
@@ -175,6 +192,29 @@ impl Painter {
         }
     }
 
+    /// Creates a line for each kind of CPU time, named with its latest share.
+    fn generate_breakdown_points<'a>(
+        &self, cpu_breakdown_data: &'a [CpuBreakdownData],
+    ) -> Vec<GraphData<'a>> {
+        cpu_breakdown_data
+            .iter()
+            .map(|kind| {
+                let style = match kind.kind {
+                    CpuTimeKind::User => self.styles.cpu_user_style,
+                    CpuTimeKind::System => self.styles.cpu_system_style,
+                    CpuTimeKind::IoWait => self.styles.cpu_iowait_style,
+                    CpuTimeKind::Steal => self.styles.cpu_steal_style,
+                };
+
+                GraphData {
+                    points: &kind.data,
+                    style,
+                    name: Some(format!("{:<6} {:3.0}%", kind.kind.name(), kind.last_entry).into()),
+                }
+            })
+            .collect()
+    }
+
     fn draw_cpu_graph(
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
@@ -193,23 +233,29 @@ impl Painter {
                 draw_loc,
             );
 
-            let points = self.generate_points(
-                cpu_widget_state,
-                cpu_data,
-                app_state.app_config_fields.show_average_cpu,
-            );
+            let points = if cpu_widget_state.show_breakdown {
+                self.generate_breakdown_points(&app_state.converted_data.cpu_breakdown_data)
+            } else {
+                self.generate_points(
+                    cpu_widget_state,
+                    cpu_data,
+                    app_state.app_config_fields.show_average_cpu,
+                )
+            };
 
             // TODO: Maybe hide load avg if too long? Or maybe the CPU part.
             let title = {
                 #[cfg(target_family = "unix")]
                 {
-                    let load_avg = app_state.converted_data.load_avg_data;
-                    let load_avg_str = format!(
-                        "─ {:.2} {:.2} {:.2} ",
-                        load_avg[0], load_avg[1], load_avg[2]
-                    );
+                    if app_state.app_config_fields.cpu_show_load_avg {
+                        let load_avg_str = super::load_avg::format_load_avg(
+                            &app_state.converted_data.load_avg_data,
+                        );
 
-                    concat_string::concat_string!(" CPU ", load_avg_str).into()
+                        concat_string::concat_string!(" CPU ─ load ", load_avg_str, " ").into()
+                    } else {
+                        " CPU ".into()
+                    }
                 }
                 #[cfg(not(target_family = "unix"))]
                 {
@@ -222,7 +268,6 @@ impl Painter {
             } else {
                 Marker::Braille
             };
-
             TimeGraph {
                 x_bounds,
                 hide_x_labels,
@@ -235,9 +280,33 @@ impl Painter {
                 is_selected: app_state.current_widget.widget_id == widget_id,
                 is_expanded: app_state.is_expanded,
                 title_style: self.styles.widget_title_style,
-                legend_position: None,
+                legend_position: cpu_widget_state
+                    .show_breakdown
+                    .then(LegendPosition::default),
                 legend_constraints: None,
                 marker,
+                gaps: &app_state.converted_data.gaps,
+                markers: &[],
+                vertical_markers: &app_state.converted_data.time_markers,
+                vertical_marker_style: self.styles.highlighted_border_style,
+                thick_lines: app_state.app_config_fields.use_thick_lines,
+                newest_on_left: app_state.app_config_fields.newest_on_left,
+                gridlines: &self.styles.gridlines,
+                placeholder: if cpu_widget_state.show_breakdown {
+                    let converted = &app_state.converted_data;
+                    if converted.is_cpu_breakdown_unsupported() {
+                        Some(UNSUPPORTED_BREAKDOWN_TEXT)
+                    } else {
+                        converted
+                            .is_collecting_cpu_breakdown()
+                            .then_some(COLLECTING_TEXT)
+                    }
+                } else {
+                    app_state
+                        .converted_data
+                        .is_collecting_cpu()
+                        .then_some(COLLECTING_TEXT)
+                },
             }
             .draw_time_graph(f, draw_loc, &points);
         }
@@ -1,4 +1,4 @@
-use tui::{layout::Rect, Frame};
+use tui::{layout::Rect, text::Line, widgets::Paragraph, Frame};
 
 use crate::{
     app,
@@ -28,12 +28,55 @@ impl Painter {
                 selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
             };
 
+            // Leave out the borders.
+            if recalculate_column_widths {
+                disk_widget_state.fit_columns(draw_loc.width.saturating_sub(2));
+            }
+
             disk_widget_state.table.draw(
                 f,
                 &draw_info,
                 app_state.widget_map.get_mut(&widget_id),
                 self,
             );
+
+            if app_state.is_expanded {
+                self.draw_alias_status_line(f, app_state, draw_loc, widget_id);
+            }
+        }
+    }
+
+    /// Draws the real name and mount point of the selected disk over the
+    /// bottom border, if either is shown as an alias.
+    fn draw_alias_status_line(
+        &self, f: &mut Frame<'_>, app_state: &app::App, draw_loc: Rect, widget_id: u64,
+    ) {
+        if draw_loc.height < 3 || draw_loc.width < 3 {
+            return;
         }
+
+        let Some(disk) = app_state
+            .states
+            .disk_state
+            .widget_states
+            .get(&widget_id)
+            .and_then(|state| state.table.current_item())
+            .filter(|disk| disk.has_alias())
+        else {
+            return;
+        };
+
+        let status_loc = Rect::new(
+            draw_loc.x + 1,
+            draw_loc.y + draw_loc.height - 1,
+            draw_loc.width - 2,
+            1,
+        );
+        let status = format!(" {} on {} ", disk.name, disk.mount_point);
+
+        f.render_widget(
+            Paragraph::new(Line::styled(status, self.styles.text_style)),
+            status_loc,
+        );
     }
 }
@@ -0,0 +1,150 @@
+use std::borrow::Cow;
+
+use tui::{
+    layout::{Constraint, Rect},
+    style::Style,
+    symbols::Marker,
+    Frame,
+};
+
+use crate::{
+    app::App,
+    canvas::{
+        components::time_graph::{GraphData, TimeGraph, COLLECTING_TEXT},
+        drawing_utils::should_hide_x_label,
+        Painter,
+    },
+    data_conversion::ConvertedData,
+    options::config::style::Styles,
+};
+
+/// Shown instead of the graph once data has come in without any GPUs.
+const NO_GPUS_TEXT: &str = "No GPUs found";
+
+/// Shown instead of the graph if GPUs aren't collected.
+const GPUS_DISABLED_TEXT: &str = "GPU collection is disabled";
+
+/// Returns what to show instead of the graph, if anything.
+fn placeholder(app_state: &App) -> Option<&'static str> {
+    if !app_state.used_widgets.use_gpu {
+        Some(GPUS_DISABLED_TEXT)
+    } else if app_state.converted_data.gpu_util_data.is_empty()
+        && !app_state.data_collection.timed_data_vec.is_empty()
+    {
+        Some(NO_GPUS_TEXT)
+    } else if app_state.converted_data.is_collecting_gpu_util() {
+        Some(COLLECTING_TEXT)
+    } else {
+        None
+    }
+}
+
+/// Returns a line for each GPU, coloured with the GPU colours in turn.
+fn gpu_graph_data<'a>(styles: &Styles, data: &'a ConvertedData) -> Vec<GraphData<'a>> {
+    data.gpu_util_data
+        .iter()
+        .enumerate()
+        .map(|(index, gpu)| GraphData {
+            points: &gpu.points,
+            style: if styles.gpu_colours.is_empty() {
+                Style::default()
+            } else {
+                styles.gpu_colours[index % styles.gpu_colours.len()]
+            },
+            name: Some(format!("{}:{}", gpu.name, gpu.util_label).into()),
+        })
+        .collect()
+}
+
+impl Painter {
+    pub fn draw_gpu_graph(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        const Y_BOUNDS: [f64; 2] = [0.0, 100.5];
+        const Y_LABELS: [Cow<'static, str>; 2] = [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
+
+        if let Some(gpu_widget_state) = app_state.states.gpu_state.widget_states.get_mut(&widget_id)
+        {
+            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let x_bounds = [0, gpu_widget_state.current_display_time];
+            let hide_x_labels = should_hide_x_label(
+                app_state.app_config_fields.hide_time,
+                app_state.app_config_fields.autohide_time,
+                &mut gpu_widget_state.autohide_timer,
+                draw_loc,
+            );
+            let points = gpu_graph_data(&self.styles, &app_state.converted_data);
+
+            let marker = if app_state.app_config_fields.use_dot {
+                Marker::Dot
+            } else {
+                Marker::Braille
+            };
+            TimeGraph {
+                x_bounds,
+                hide_x_labels,
+                y_bounds: Y_BOUNDS,
+                y_labels: &Y_LABELS,
+                graph_style: self.styles.graph_style,
+                border_style,
+                border_type: self.styles.border_type,
+                title: " GPU ".into(),
+                is_selected: app_state.current_widget.widget_id == widget_id,
+                is_expanded: app_state.is_expanded,
+                title_style: self.styles.widget_title_style,
+                legend_position: app_state.app_config_fields.gpu_legend_position,
+                legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                marker,
+                gaps: &app_state.converted_data.gaps,
+                markers: &[],
+                vertical_markers: &app_state.converted_data.time_markers,
+                vertical_marker_style: self.styles.highlighted_border_style,
+                thick_lines: app_state.app_config_fields.use_thick_lines,
+                newest_on_left: app_state.app_config_fields.newest_on_left,
+                gridlines: &self.styles.gridlines,
+                placeholder: placeholder(app_state),
+            }
+            .draw_time_graph(f, draw_loc, &points);
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use clap::Parser;
+
+    use super::*;
+    use crate::options::{args::BottomArgs, config::Config};
+
+    fn init_app(args: &[&str]) -> App {
+        crate::options::init_app(BottomArgs::parse_from(args), Config::default())
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn placeholders() {
+        let mut app = init_app(&["btm"]);
+        assert_eq!(placeholder(&app), Some(COLLECTING_TEXT));
+
+        // Data came in without any GPUs.
+        app.data_collection
+            .timed_data_vec
+            .push((Instant::now(), Default::default()));
+        assert_eq!(placeholder(&app), Some(NO_GPUS_TEXT));
+
+        let app = init_app(&["btm", "--disable_gpu"]);
+        assert_eq!(placeholder(&app), Some(GPUS_DISABLED_TEXT));
+    }
+}
@@ -0,0 +1,62 @@
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{
+    app::App,
+    canvas::{drawing_utils::widget_block, Painter},
+    data_collection::cpu::LoadAvgHarvest,
+};
+
+/// The windows of each load average, in minutes.
+const LOAD_AVG_WINDOWS: [&str; 3] = ["1", "5", "15"];
+
+/// Formats the 1, 5, and 15 minute load averages, in that order.
+pub fn format_load_avg(load_avg: &LoadAvgHarvest) -> String {
+    format!("{:.2} {:.2} {:.2}", load_avg[0], load_avg[1], load_avg[2])
+}
+
+impl Painter {
+    /// Draws the load averages, one per line if there is room, or all on one
+    /// line otherwise.
+    pub fn draw_load_avg(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let is_selected = widget_id == app_state.current_widget.widget_id;
+        let block = widget_block(false, is_selected, self.styles.border_type)
+            .border_style(self.get_border_style(widget_id, app_state.current_widget.widget_id))
+            .title_top(Line::styled(" Load ", self.styles.widget_title_style));
+
+        let load_avg = &app_state.converted_data.load_avg_data;
+        let text = if draw_loc.height >= LOAD_AVG_WINDOWS.len() as u16 + 2 {
+            LOAD_AVG_WINDOWS
+                .iter()
+                .zip(load_avg)
+                .map(|(window, load)| {
+                    Line::from(vec![
+                        Span::styled(format!("{window:>2}m "), self.styles.widget_title_style),
+                        Span::styled(format!("{load:.2}"), self.styles.text_style),
+                    ])
+                })
+                .collect()
+        } else {
+            vec![Line::styled(
+                format_load_avg(load_avg),
+                self.styles.text_style,
+            )]
+        };
+
+        f.render_widget(Paragraph::new(text).block(block), draw_loc);
+
+        if app_state.should_get_widget_bounds() {
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
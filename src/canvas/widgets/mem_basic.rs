@@ -110,11 +110,11 @@ impl Painter {
             } else {
                 0.0
             };
-            if let Some((_, label_frac)) = &app_state.converted_data.arc_labels {
+            if let Some(arc_labels) = &app_state.converted_data.arc_labels {
                 let arc_fraction_label = if app_state.basic_mode_use_percent {
                     format!("{:3.0}%", arc_percentage.round())
                 } else {
-                    label_frac.trim().to_string()
+                    arc_labels.frac.trim().to_string()
                 };
                 draw_widgets.push(
                     PipeGauge::default()
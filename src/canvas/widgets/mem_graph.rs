@@ -9,12 +9,117 @@ use tui::{
 use crate::{
     app::App,
     canvas::{
-        components::time_graph::{GraphData, TimeGraph},
+        components::time_graph::{GraphData, TimeGraph, COLLECTING_TEXT},
         drawing_utils::should_hide_x_label,
         Painter,
     },
+    data_conversion::ConvertedData,
+    options::config::style::Styles,
+    widgets::MemorySeries,
 };
 
+/// Returns the lines to draw for each of `series` that has data, in order.
+fn memory_graph_data<'a>(
+    styles: &Styles, data: &'a ConvertedData, series: &[MemorySeries],
+) -> Vec<GraphData<'a>> {
+    let mut points = Vec::with_capacity(series.len());
+
+    let labelled = |prefix: &str, labels: &Option<(String, String)>, data: &'a [_], style| {
+        labels
+            .as_ref()
+            .map(|(label_percent, label_frac)| GraphData {
+                points: data,
+                style,
+                name: Some(format!("{prefix}:{label_percent}{label_frac}").into()),
+            })
+    };
+
+    for series in series {
+        match series {
+            MemorySeries::Ram => points.extend(labelled(
+                "RAM",
+                &data.mem_labels,
+                &data.mem_data,
+                styles.ram_style,
+            )),
+            MemorySeries::Cache =>
+            {
+                #[cfg(not(target_os = "windows"))]
+                points.extend(labelled(
+                    "CHE",
+                    &data.cache_labels,
+                    &data.cache_data,
+                    styles.cache_style,
+                ))
+            }
+            MemorySeries::Swap => points.extend(labelled(
+                "SWP",
+                &data.swap_labels,
+                &data.swap_data,
+                styles.swap_style,
+            )),
+            MemorySeries::Arc =>
+            {
+                #[cfg(feature = "zfs")]
+                points.extend(data.arc_labels.as_ref().map(|labels| {
+                    GraphData {
+                        points: &data.arc_data,
+                        style: styles.arc_style,
+                        name: Some(
+                            format!(
+                                "ARC:{}{}{}",
+                                labels.percent,
+                                labels.frac,
+                                labels.hit_ratio.as_deref().unwrap_or_default()
+                            )
+                            .into(),
+                        ),
+                    }
+                }))
+            }
+            MemorySeries::ArcDetails =>
+            {
+                #[cfg(feature = "zfs")]
+                points.extend(data.arc_detail_labels.iter().map(|label| GraphData {
+                    points: &[],
+                    style: styles.arc_style,
+                    name: Some(label.as_str().into()),
+                }))
+            }
+            MemorySeries::Gpu => {
+                #[cfg(feature = "gpu")]
+                if let Some(gpu_data) = &data.gpu_data {
+                    let mut color_index = 0;
+                    let gpu_styles = &styles.gpu_colours;
+                    gpu_data.iter().for_each(|gpu| {
+                        let gpu_label =
+                            format!("{}:{}{}", gpu.name, gpu.mem_percent, gpu.mem_total);
+                        let style = {
+                            if gpu_styles.is_empty() {
+                                tui::style::Style::default()
+                            } else if color_index >= gpu_styles.len() {
+                                // cycle styles
+                                color_index = 1;
+                                gpu_styles[color_index - 1]
+                            } else {
+                                color_index += 1;
+                                gpu_styles[color_index - 1]
+                            }
+                        };
+                        points.push(GraphData {
+                            points: gpu.points.as_slice(),
+                            style,
+                            name: Some(gpu_label.into()),
+                        });
+                    });
+                }
+            }
+        }
+    }
+
+    points
+}
+
 impl Painter {
     pub fn draw_memory_graph(
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
@@ -32,97 +137,17 @@ impl Painter {
                 &mut mem_widget_state.autohide_timer,
                 draw_loc,
             );
-            let points = {
-                let mut size = 1;
-                if app_state.converted_data.swap_labels.is_some() {
-                    size += 1; // add capacity for SWAP
-                }
-                #[cfg(feature = "zfs")]
-                {
-                    if app_state.converted_data.arc_labels.is_some() {
-                        size += 1; // add capacity for ARC
-                    }
-                }
-                #[cfg(feature = "gpu")]
-                {
-                    if let Some(gpu_data) = &app_state.converted_data.gpu_data {
-                        size += gpu_data.len(); // add row(s) for gpu
-                    }
-                }
-
-                let mut points = Vec::with_capacity(size);
-                if let Some((label_percent, label_frac)) = &app_state.converted_data.mem_labels {
-                    let mem_label = format!("RAM:{label_percent}{label_frac}");
-                    points.push(GraphData {
-                        points: &app_state.converted_data.mem_data,
-                        style: self.styles.ram_style,
-                        name: Some(mem_label.into()),
-                    });
-                }
-                #[cfg(not(target_os = "windows"))]
-                if let Some((label_percent, label_frac)) = &app_state.converted_data.cache_labels {
-                    let cache_label = format!("CHE:{label_percent}{label_frac}");
-                    points.push(GraphData {
-                        points: &app_state.converted_data.cache_data,
-                        style: self.styles.cache_style,
-                        name: Some(cache_label.into()),
-                    });
-                }
-                if let Some((label_percent, label_frac)) = &app_state.converted_data.swap_labels {
-                    let swap_label = format!("SWP:{label_percent}{label_frac}");
-                    points.push(GraphData {
-                        points: &app_state.converted_data.swap_data,
-                        style: self.styles.swap_style,
-                        name: Some(swap_label.into()),
-                    });
-                }
-                #[cfg(feature = "zfs")]
-                if let Some((label_percent, label_frac)) = &app_state.converted_data.arc_labels {
-                    let arc_label = format!("ARC:{label_percent}{label_frac}");
-                    points.push(GraphData {
-                        points: &app_state.converted_data.arc_data,
-                        style: self.styles.arc_style,
-                        name: Some(arc_label.into()),
-                    });
-                }
-                #[cfg(feature = "gpu")]
-                {
-                    if let Some(gpu_data) = &app_state.converted_data.gpu_data {
-                        let mut color_index = 0;
-                        let gpu_styles = &self.styles.gpu_colours;
-                        gpu_data.iter().for_each(|gpu| {
-                            let gpu_label =
-                                format!("{}:{}{}", gpu.name, gpu.mem_percent, gpu.mem_total);
-                            let style = {
-                                if gpu_styles.is_empty() {
-                                    tui::style::Style::default()
-                                } else if color_index >= gpu_styles.len() {
-                                    // cycle styles
-                                    color_index = 1;
-                                    gpu_styles[color_index - 1]
-                                } else {
-                                    color_index += 1;
-                                    gpu_styles[color_index - 1]
-                                }
-                            };
-                            points.push(GraphData {
-                                points: gpu.points.as_slice(),
-                                style,
-                                name: Some(gpu_label.into()),
-                            });
-                        });
-                    }
-                }
-
-                points
-            };
+            let points = memory_graph_data(
+                &self.styles,
+                &app_state.converted_data,
+                &mem_widget_state.series,
+            );
 
             let marker = if app_state.app_config_fields.use_dot {
                 Marker::Dot
             } else {
                 Marker::Braille
             };
-
             TimeGraph {
                 x_bounds,
                 hide_x_labels,
@@ -138,6 +163,17 @@ impl Painter {
                 legend_position: app_state.app_config_fields.memory_legend_position,
                 legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
                 marker,
+                gaps: &app_state.converted_data.gaps,
+                markers: &[],
+                vertical_markers: &app_state.converted_data.time_markers,
+                vertical_marker_style: self.styles.highlighted_border_style,
+                thick_lines: app_state.app_config_fields.use_thick_lines,
+                newest_on_left: app_state.app_config_fields.newest_on_left,
+                gridlines: &self.styles.gridlines,
+                placeholder: app_state
+                    .converted_data
+                    .is_collecting_mem()
+                    .then_some(COLLECTING_TEXT),
             }
             .draw_time_graph(f, draw_loc, &points);
         }
@@ -152,3 +188,96 @@ impl Painter {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn names(points: &[GraphData<'_>]) -> Vec<String> {
+        points
+            .iter()
+            .filter_map(|p| p.name.as_ref().map(|n| n.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn configured_series_order() {
+        let data = ConvertedData {
+            mem_labels: Some(("50%".into(), "".into())),
+            swap_labels: Some(("10%".into(), "".into())),
+            ..Default::default()
+        };
+        let styles = Styles::default();
+
+        assert_eq!(
+            names(&memory_graph_data(
+                &styles,
+                &data,
+                &crate::widgets::DEFAULT_MEMORY_SERIES
+            )),
+            ["RAM:50%", "SWP:10%"]
+        );
+        assert_eq!(
+            names(&memory_graph_data(
+                &styles,
+                &data,
+                &[MemorySeries::Arc, MemorySeries::Swap, MemorySeries::Ram]
+            )),
+            ["SWP:10%", "RAM:50%"]
+        );
+
+        // Series that aren't listed are hidden.
+        assert_eq!(
+            names(&memory_graph_data(&styles, &data, &[MemorySeries::Swap])),
+            ["SWP:10%"]
+        );
+    }
+
+    #[cfg(feature = "zfs")]
+    #[test]
+    fn arc_details_follow_their_position() {
+        use crate::data_conversion::ArcLabels;
+
+        let data = ConvertedData {
+            mem_labels: Some(("50%".into(), "".into())),
+            arc_labels: Some(ArcLabels {
+                percent: "25%".into(),
+                hit_ratio: Some(" 97.0% hit".into()),
+                ..Default::default()
+            }),
+            arc_detail_labels: vec!["ARC data/meta:   1G/2G".into(), "L2ARC:   3G".into()],
+            ..Default::default()
+        };
+        let styles = Styles::default();
+
+        // Only shown when listed.
+        assert_eq!(
+            names(&memory_graph_data(
+                &styles,
+                &data,
+                &crate::widgets::DEFAULT_MEMORY_SERIES
+            )),
+            ["RAM:50%", "ARC:25% 97.0% hit"]
+        );
+
+        let points = memory_graph_data(
+            &styles,
+            &data,
+            &[
+                MemorySeries::Arc,
+                MemorySeries::ArcDetails,
+                MemorySeries::Ram,
+            ],
+        );
+        assert_eq!(
+            names(&points),
+            [
+                "ARC:25% 97.0% hit",
+                "ARC data/meta:   1G/2G",
+                "L2ARC:   3G",
+                "RAM:50%"
+            ]
+        );
+        assert!(points[1].points.is_empty());
+    }
+}
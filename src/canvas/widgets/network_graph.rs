@@ -1,5 +1,6 @@
 use tui::{
     layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
     symbols::Marker,
     text::Text,
     widgets::{Block, Borders, Row, Table},
@@ -10,12 +11,13 @@ use crate::{
     app::{App, AxisScaling},
     canvas::{
         components::{
-            time_chart::Point,
-            time_graph::{GraphData, TimeGraph},
+            time_chart::{HorizontalMarker, Point},
+            time_graph::{GraphData, TimeGraph, COLLECTING_TEXT},
         },
         drawing_utils::should_hide_x_label,
         Painter,
     },
+    data_conversion::{scale_network_point, ConvertedData},
     utils::{data_prefixes::*, data_units::DataUnit, general::partial_ordering},
 };
 
@@ -59,8 +61,14 @@ impl Painter {
         if let Some(network_widget_state) =
             app_state.states.net_state.widget_states.get_mut(&widget_id)
         {
-            let network_data_rx = &app_state.converted_data.network_data_rx;
-            let network_data_tx = &app_state.converted_data.network_data_tx;
+            let picked_interface = network_widget_state.interface.clone();
+            let [(network_data_rx, rx_display), (network_data_tx, tx_display)] =
+                graphed_series(&app_state.converted_data, picked_interface.as_deref());
+            let interface_data = &app_state.converted_data.network_interface_data;
+            let show_interfaces = app_state.app_config_fields.network_show_interfaces
+                && !app_state.app_config_fields.use_old_network_legend
+                && picked_interface.is_none()
+                && !interface_data.is_empty();
             let time_start = -(network_widget_state.current_display_time as f64);
             let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
             let x_bounds = [0, network_widget_state.current_display_time];
@@ -78,20 +86,59 @@ impl Painter {
             //   last checked; we only want to update if it is TOO big!)
 
             // Find the maximal rx/tx so we know how to scale, and return it.
-            let (_best_time, max_entry) = get_max_entry(
-                network_data_rx,
-                network_data_tx,
-                time_start,
-                &app_state.app_config_fields.network_scale_type,
-                app_state.app_config_fields.network_use_binary_prefix,
-            );
+            let max_entry = if app_state.app_config_fields.network_y_max.is_some() {
+                // Not needed, as the axis is pinned.
+                0.0
+            } else {
+                let window_max = if show_interfaces {
+                    interface_data
+                        .iter()
+                        .map(|interface| {
+                            get_max_entry(
+                                &interface.rx,
+                                &interface.tx,
+                                time_start,
+                                &app_state.app_config_fields.network_scale_type,
+                                app_state.app_config_fields.network_use_binary_prefix,
+                            )
+                        })
+                        .max_by(|(_, a), (_, b)| partial_ordering(a, b))
+                        .unwrap_or_default()
+                        .1
+                } else {
+                    get_max_entry(
+                        network_data_rx,
+                        network_data_tx,
+                        time_start,
+                        &app_state.app_config_fields.network_scale_type,
+                        app_state.app_config_fields.network_use_binary_prefix,
+                    )
+                    .1
+                };
 
-            let (max_range, labels) = adjust_network_data_point(
-                max_entry,
-                &app_state.app_config_fields.network_scale_type,
-                &app_state.app_config_fields.network_unit_type,
-                app_state.app_config_fields.network_use_binary_prefix,
-            );
+                if app_state.app_config_fields.network_lock_to_session_max {
+                    // Follow the largest of whichever series are shown.
+                    session_max_point(app_state, show_interfaces, picked_interface.as_deref())
+                        .map_or(window_max, |max| max.max(window_max))
+                } else {
+                    window_max
+                }
+            };
+
+            let (max_range, labels) = match app_state.app_config_fields.network_y_max {
+                Some(y_max) => pinned_network_axis(
+                    y_max,
+                    &app_state.app_config_fields.network_scale_type,
+                    &app_state.app_config_fields.network_unit_type,
+                    app_state.app_config_fields.network_use_binary_prefix,
+                ),
+                None => adjust_network_data_point(
+                    max_entry,
+                    &app_state.app_config_fields.network_scale_type,
+                    &app_state.app_config_fields.network_unit_type,
+                    app_state.app_config_fields.network_use_binary_prefix,
+                ),
+            };
 
             let y_labels = labels.iter().map(|label| label.into()).collect::<Vec<_>>();
             let y_bounds = [0.0, max_range];
@@ -103,7 +150,46 @@ impl Painter {
             };
 
             // TODO: Add support for clicking on legend to only show that value on chart.
-            let points = if app_state.app_config_fields.use_old_network_legend && !hide_legend {
+            let points = if show_interfaces {
+                let colours = &self.styles.network_interface_colours;
+                let mut points = interface_data
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(itx, interface)| {
+                        [
+                            GraphData {
+                                points: &interface.rx,
+                                style: colours[(itx * 2) % colours.len()],
+                                name: Some(
+                                    format!("{} RX: {}", interface.name, interface.rx_display)
+                                        .into(),
+                                ),
+                            },
+                            GraphData {
+                                points: &interface.tx,
+                                style: colours[(itx * 2 + 1) % colours.len()],
+                                name: Some(
+                                    format!("{} TX: {}", interface.name, interface.tx_display)
+                                        .into(),
+                                ),
+                            },
+                        ]
+                    })
+                    .collect::<Vec<_>>();
+
+                // The legend is only shown if it fits in 3/4 of the graph's height (see
+                // the legend constraints above), so cut it down if there are too many
+                // interfaces rather than hiding it entirely. We subtract the borders and
+                // x-axis from the available height, then the legend's own borders.
+                let max_legend_entries =
+                    (usize::from(draw_loc.height.saturating_sub(4)) * 3 / 4).saturating_sub(2);
+                truncate_legend(&mut points, max_legend_entries, self.styles.text_style);
+
+                points
+            } else if app_state.app_config_fields.use_old_network_legend
+                && picked_interface.is_none()
+                && !hide_legend
+            {
                 vec![
                     GraphData {
                         points: network_data_rx,
@@ -137,22 +223,41 @@ impl Painter {
                     GraphData {
                         points: network_data_rx,
                         style: self.styles.rx_style,
-                        name: Some((&app_state.converted_data.rx_display).into()),
+                        name: Some(rx_display.into()),
                     },
                     GraphData {
                         points: network_data_tx,
                         style: self.styles.tx_style,
-                        name: Some((&app_state.converted_data.tx_display).into()),
+                        name: Some(tx_display.into()),
                     },
                 ]
             };
 
+            let session_max_markers = if app_state.app_config_fields.network_show_session_max {
+                let [rx_max, tx_max] = &app_state.converted_data.network_session_max;
+                [
+                    (rx_max, self.styles.rx_style),
+                    (tx_max, self.styles.tx_style),
+                ]
+                .into_iter()
+                .filter_map(|(max, style)| {
+                    let (value, label) = max.as_ref()?;
+                    Some(HorizontalMarker {
+                        value: *value,
+                        style,
+                        label: Some(label.into()),
+                    })
+                })
+                .collect()
+            } else {
+                vec![]
+            };
+
             let marker = if app_state.app_config_fields.use_dot {
                 Marker::Dot
             } else {
                 Marker::Braille
             };
-
             TimeGraph {
                 x_bounds,
                 hide_x_labels,
@@ -161,13 +266,27 @@ impl Painter {
                 graph_style: self.styles.graph_style,
                 border_style,
                 border_type: self.styles.border_type,
-                title: " Network ".into(),
+                title: match &picked_interface {
+                    Some(interface) => format!(" Network ─ {interface} ").into(),
+                    None => " Network ".into(),
+                },
                 is_selected: app_state.current_widget.widget_id == widget_id,
                 is_expanded: app_state.is_expanded,
                 title_style: self.styles.widget_title_style,
                 legend_position: app_state.app_config_fields.network_legend_position,
                 legend_constraints: Some(legend_constraints),
                 marker,
+                gaps: &app_state.converted_data.gaps,
+                markers: &session_max_markers,
+                vertical_markers: &app_state.converted_data.time_markers,
+                vertical_marker_style: self.styles.highlighted_border_style,
+                thick_lines: app_state.app_config_fields.use_thick_lines,
+                newest_on_left: app_state.app_config_fields.newest_on_left,
+                gridlines: &[],
+                placeholder: app_state
+                    .converted_data
+                    .is_collecting_network()
+                    .then_some(COLLECTING_TEXT),
             }
             .draw_time_graph(f, draw_loc, &points);
         }
@@ -214,7 +333,67 @@ impl Painter {
     }
 }
 
+/// Hides the names of any graph entries past what fits in a legend with
+/// `max_entries` rows, replacing them with a single entry that notes how many
+/// were hidden. The hidden entries are still drawn on the graph.
+fn truncate_legend(points: &mut Vec<GraphData<'_>>, max_entries: usize, style: Style) {
+    if points.len() <= max_entries {
+        return;
+    }
+
+    let shown = max_entries.saturating_sub(1);
+    let hidden = points.len() - shown;
+
+    for point in &mut points[shown..] {
+        point.name = None;
+    }
+
+    if max_entries > 0 {
+        points.push(GraphData {
+            points: &[],
+            style,
+            name: Some(format!("+{hidden} more").into()),
+        });
+    }
+}
+
 /// Returns the max data point and time given a time.
+/// Returns where the highest rate seen this session in the graphed series is on
+/// the graph, or [`None`] if there hasn't been any traffic yet. The series are
+/// either every interface, the picked interface, or the total.
+fn session_max_point(
+    app_state: &App, show_interfaces: bool, picked_interface: Option<&str>,
+) -> Option<f64> {
+    let data = &app_state.data_collection;
+    let maxes = if show_interfaces {
+        data.network_interface_session_max.clone()
+    } else if let Some(interface) = picked_interface {
+        data.network_interface_names
+            .iter()
+            .position(|name| name == interface)
+            .and_then(|index| data.network_interface_session_max.get(index))
+            .into_iter()
+            .copied()
+            .collect()
+    } else {
+        vec![data.network_session_max]
+    };
+
+    maxes
+        .into_iter()
+        .flat_map(|(rx, tx)| [rx, tx])
+        .filter(|max| *max > 0)
+        .map(|max| {
+            scale_network_point(
+                max as f64,
+                &app_state.app_config_fields.network_scale_type,
+                &app_state.app_config_fields.network_unit_type,
+                app_state.app_config_fields.network_use_binary_prefix,
+            )
+        })
+        .max_by(|a, b| partial_ordering(a, b))
+}
+
 fn get_max_entry(
     rx: &[Point], tx: &[Point], time_start: f64, network_scale_type: &AxisScaling,
     network_use_binary_prefix: bool,
@@ -336,6 +515,38 @@ fn get_max_entry(
     }
 }
 
+/// Returns the max data point and labels for a y-axis pinned to `y_max`, in bits
+/// per second. On a log scale, the axis goes up to the first unit at or above
+/// `y_max` instead.
+fn pinned_network_axis(
+    y_max: u64, network_scale_type: &AxisScaling, network_unit_type: &DataUnit,
+    network_use_binary_prefix: bool,
+) -> (f64, Vec<String>) {
+    let max_entry = scale_network_point(
+        y_max as f64,
+        network_scale_type,
+        network_unit_type,
+        network_use_binary_prefix,
+    );
+
+    match network_scale_type {
+        // A linear axis normally leaves room above the largest value, so take that
+        // back off for the axis to end at the max.
+        AxisScaling::Linear => adjust_network_data_point(
+            max_entry / 1.5,
+            network_scale_type,
+            network_unit_type,
+            network_use_binary_prefix,
+        ),
+        AxisScaling::Log => adjust_network_data_point(
+            max_entry,
+            network_scale_type,
+            network_unit_type,
+            network_use_binary_prefix,
+        ),
+    }
+}
+
 /// Returns the required max data point and labels.
 fn adjust_network_data_point(
     max_entry: f64, network_scale_type: &AxisScaling, network_unit_type: &DataUnit,
@@ -545,3 +756,130 @@ fn adjust_network_data_point(
         }
     }
 }
+
+/// Returns the RX and TX points and current rates to graph. These are for the
+/// picked interface if there is one, and across all interfaces otherwise.
+fn graphed_series<'a>(
+    data: &'a ConvertedData, interface: Option<&str>,
+) -> [(&'a [Point], &'a str); 2] {
+    match interface {
+        Some(name) => match data
+            .network_interface_data
+            .iter()
+            .find(|interface| interface.name == name)
+        {
+            Some(interface) => [
+                (&interface.rx, &interface.rx_display),
+                (&interface.tx, &interface.tx_display),
+            ],
+            // The interface might not be up yet.
+            None => [(&[], "N/A"), (&[], "N/A")],
+        },
+        None => [
+            (&data.network_data_rx, &data.rx_display),
+            (&data.network_data_tx, &data.tx_display),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::data_conversion::ConvertedInterfaceData;
+
+    fn graph_data(name: &str) -> GraphData<'_> {
+        GraphData {
+            points: &[],
+            style: Style::default(),
+            name: Some(name.into()),
+        }
+    }
+
+    fn legend_names(points: &[GraphData<'_>]) -> Vec<String> {
+        points
+            .iter()
+            .filter_map(|p| p.name.as_ref().map(|n| n.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn picked_interface_is_graphed() {
+        let interface = |name: &str, rate: f64| ConvertedInterfaceData {
+            name: name.to_string(),
+            rx: vec![(0.0, rate)],
+            tx: vec![(0.0, rate * 2.0)],
+            rx_display: format!("{rate}b/s"),
+            tx_display: format!("{}b/s", rate * 2.0),
+        };
+        let data = ConvertedData {
+            network_data_rx: vec![(0.0, 30.0)],
+            network_data_tx: vec![(0.0, 60.0)],
+            rx_display: "30b/s".to_string(),
+            tx_display: "60b/s".to_string(),
+            network_interface_data: vec![interface("eth0", 10.0), interface("wlan0", 20.0)],
+            ..Default::default()
+        };
+
+        let [(rx, rx_display), (tx, tx_display)] = graphed_series(&data, Some("wlan0"));
+        assert_eq!((rx, rx_display), (&[(0.0, 20.0)][..], "20b/s"));
+        assert_eq!((tx, tx_display), (&[(0.0, 40.0)][..], "40b/s"));
+
+        // Without an interface, the totals are graphed.
+        let [(rx, _), (tx, _)] = graphed_series(&data, None);
+        assert_eq!((rx, tx), (&[(0.0, 30.0)][..], &[(0.0, 60.0)][..]));
+
+        let [(rx, rx_display), _] = graphed_series(&data, Some("tun0"));
+        assert!(rx.is_empty());
+        assert_eq!(rx_display, "N/A");
+    }
+
+    #[test]
+    fn axis_fits_peak() {
+        // A 300 Kb/s peak gets room above it, with labels in the same unit.
+        let (max, labels) =
+            adjust_network_data_point(300_000.0, &AxisScaling::Linear, &DataUnit::Bit, false);
+        assert_eq!(max, 450_000.0);
+        assert_eq!(labels, ["  0Kb", "150.0", "300.0", "450.0"]);
+
+        let (max, labels) = adjust_network_data_point(
+            300_000.0_f64.log10(),
+            &AxisScaling::Log,
+            &DataUnit::Bit,
+            false,
+        );
+        assert_eq!(max, LOG_MEGA_LIMIT);
+        assert_eq!(labels, [" 0b", "1Kb", "1Mb"]);
+    }
+
+    #[test]
+    fn pinned_axis() {
+        // 100 MB/s, shown in bytes.
+        let (max, labels) =
+            pinned_network_axis(800_000_000, &AxisScaling::Linear, &DataUnit::Byte, false);
+        assert_eq!(max, 100_000_000.0);
+        assert_eq!(labels, ["  0MB", " 33.3", " 66.7", "100.0"]);
+
+        let (max, labels) =
+            pinned_network_axis(800_000_000, &AxisScaling::Log, &DataUnit::Byte, false);
+        assert_eq!(max, LOG_GIGA_LIMIT);
+        assert_eq!(labels, [" 0B", "1KB", "1MB", "1GB"]);
+    }
+
+    #[test]
+    fn test_truncate_legend() {
+        let names = ["a", "b", "c", "d", "e"];
+
+        let mut points = names.iter().map(|n| graph_data(n)).collect::<Vec<_>>();
+        truncate_legend(&mut points, 5, Style::default());
+        assert_eq!(legend_names(&points), names);
+
+        let mut points = names.iter().map(|n| graph_data(n)).collect::<Vec<_>>();
+        truncate_legend(&mut points, 3, Style::default());
+        assert_eq!(legend_names(&points), ["a", "b", "+3 more"]);
+        assert_eq!(points.len(), 6);
+
+        let mut points = names.iter().map(|n| graph_data(n)).collect::<Vec<_>>();
+        truncate_legend(&mut points, 0, Style::default());
+        assert!(legend_names(&points).is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use tui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::Style,
@@ -6,6 +8,7 @@ use tui::{
     Frame,
 };
 use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
     app::{App, AppSearchState},
@@ -14,6 +17,7 @@ use crate::{
         drawing_utils::widget_block,
         Painter,
     },
+    widgets::ProcWidgetColumn,
 };
 
 const SORT_MENU_WIDTH: u16 = 7;
@@ -52,6 +56,7 @@ impl Painter {
             }
 
             self.draw_processes_table(f, app_state, proc_draw_loc, widget_id);
+            self.draw_status_line(f, app_state, proc_draw_loc, widget_id);
         }
 
         if let Some(proc_widget_state) = app_state
@@ -100,6 +105,34 @@ impl Painter {
         }
     }
 
+    /// Draws a status line over the bottom of the process table, either
+    /// warning about killed processes that haven't exited in time, or showing
+    /// the full command of the process under the mouse cursor.
+    fn draw_status_line(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect, widget_id: u64) {
+        if draw_loc.height < 3 || draw_loc.width < 3 {
+            return;
+        }
+
+        let Some((status, style)) = kill_notice(app_state)
+            .map(|notice| (notice, self.styles.invalid_query_style))
+            .or_else(|| {
+                command_tooltip(app_state, widget_id)
+                    .map(|tooltip| (tooltip, self.styles.text_style))
+            })
+        else {
+            return;
+        };
+
+        let status_loc = Rect::new(
+            draw_loc.x + 1,
+            draw_loc.y + draw_loc.height - 1,
+            draw_loc.width - 2,
+            1,
+        );
+
+        f.render_widget(Paragraph::new(Line::styled(status, style)), status_loc);
+    }
+
     /// Draws the process search field.
     /// - `widget_id` represents the widget ID of the search box itself --- NOT
     ///   the process widget state that is stored.
@@ -326,3 +359,58 @@ impl Painter {
         }
     }
 }
+
+/// Returns a notice if any killed processes haven't exited in time.
+fn kill_notice(app_state: &App) -> Option<String> {
+    let mut stuck = app_state.kill_tracker.stuck();
+    let first = stuck.next()?;
+
+    let mut notice = format!(" {}", first.notice(Instant::now()));
+    let remaining = stuck.count();
+    if remaining > 0 {
+        notice.push_str(&format!(" (+{remaining} more)"));
+    }
+    #[cfg(target_family = "unix")]
+    if app_state.is_confirming_sigkill() {
+        notice.push_str(", X again to confirm SIGKILL");
+    } else {
+        notice.push_str(", XX to SIGKILL");
+    }
+    notice.push(' ');
+
+    Some(notice)
+}
+
+/// Returns the full command of the process under the mouse cursor, if its
+/// name/command is truncated in the table.
+fn command_tooltip(app_state: &App, widget_id: u64) -> Option<String> {
+    if !app_state.app_config_fields.process_command_tooltip {
+        return None;
+    }
+
+    let (x, y) = app_state.mouse_position?;
+    let proc_widget_state = app_state.states.proc_state.widget_states.get(&widget_id)?;
+    let process = proc_widget_state.table.item_at(x, y)?;
+
+    let is_truncated = proc_widget_state
+        .column_mapping
+        .get_index_of(&ProcWidgetColumn::ProcNameOrCommand)
+        .and_then(|index| proc_widget_state.table.column_width(index))
+        .map_or(true, |width| {
+            UnicodeWidthStr::width(process.id.to_prefixed_string().as_str())
+                > usize::from(width.get())
+        });
+
+    if is_truncated {
+        let command = app_state
+            .data_collection
+            .process_data
+            .process_harvest
+            .get(&process.pid)
+            .map(|process| process.command.as_str())?;
+
+        Some(format!(" {command} "))
+    } else {
+        None
+    }
+}
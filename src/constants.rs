@@ -11,6 +11,11 @@ pub const AUTOHIDE_TIMEOUT_MILLISECONDS: u64 = 5000; // 5 seconds to autohide
 
 // How fast the screen refreshes
 pub const DEFAULT_REFRESH_RATE_IN_MILLISECONDS: u64 = 1000;
+pub const MIN_REFRESH_RATE_IN_MILLISECONDS: u64 = 250;
+
+// How often temperatures are refreshed by default. This is slower than the
+// normal refresh rate as reading some sensors can wake the device they're on.
+pub const DEFAULT_TEMPERATURE_REFRESH_RATE_IN_MILLISECONDS: u64 = 5000;
 pub const MAX_KEY_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
 
 // Limits for when we should stop showing table gaps/labels (anything less means
@@ -35,14 +40,23 @@ const HELP_CONTENTS_TEXT: [&str; 10] = [
     "9 - Basic memory widget",
 ];
 
+/// The last line of the help menu's contents when only the sections for the
+/// selected widget are shown.
+pub(crate) const HELP_EXPAND_TEXT: &str = "Tab - Show the sections for all widgets";
+
+/// The last line of the help menu's contents when every section is shown.
+pub(crate) const HELP_COLLAPSE_TEXT: &str = "Tab - Only show the sections for the selected widget";
+
 // TODO [Help]: Search in help?
 // TODO [Help]: Move to using tables for easier formatting?
-pub(crate) const GENERAL_HELP_TEXT: [&str; 32] = [
+pub(crate) const GENERAL_HELP_TEXT: [&str; 41] = [
     "1 - General",
     "q, Ctrl-c        Quit",
     "Esc              Close dialog windows, search, widgets, or exit expanded mode",
     "Ctrl-r           Reset display and any collected data",
     "f                Freeze/unfreeze updating with new data",
+    "F                Show/hide how processes changed since freezing",
+    "R                Refresh data now, rather than waiting for the next update",
     "Ctrl-Left,       ",
     "Shift-Left,      Move widget selection left",
     "H, A             ",
@@ -59,45 +73,58 @@ pub(crate) const GENERAL_HELP_TEXT: [&str; 32] = [
     "Down, j          Move down within widget",
     "Up, k            Move up within widget",
     "Right, l         Move right within widget",
-    "?                Open help menu",
+    "?                Open help menu for the selected widget",
     "gg               Jump to the first entry",
     "G                Jump to the last entry",
     "e                Toggle expanding the currently selected widget",
     "+                Zoom in on chart (decrease time range)",
     "-                Zoom out on chart (increase time range)",
     "=                Reset zoom",
+    "E                Save a text snapshot of the selected graph",
+    "b                Mark this point in time on the graphs",
+    "v                List the marked points in time, to label or remove them",
     "PgUp, PgDown     Scroll up/down a table by a page",
     "Ctrl-u, Ctrl-d   Scroll up/down a table by half a page",
+    "Ctrl-p           Search the table widgets and jump to an entry",
+    "O                Change the update rate and retention until bottom exits",
+    "T                Show/hide how long each widget takes to draw",
+    "B                Switch between basic mode and the normal layout",
     "Mouse scroll     Scroll through the tables or zoom in/out of charts by scrolling up/down",
     "Mouse click      Selects the clicked widget, table entry, dialog option, or tab",
 ];
 
-const CPU_HELP_TEXT: [&str; 2] = [
+const CPU_HELP_TEXT: [&str; 3] = [
     "2 - CPU widget",
+    "c                Toggle showing user, system, iowait, and steal time",
     "Mouse scroll     Scrolling over an CPU core/average shows only that entry on the chart",
 ];
 
-const PROCESS_HELP_TEXT: [&str; 17] = [
+const PROCESS_HELP_TEXT: [&str; 20 + cfg!(target_family = "unix") as usize] = [
     "3 - Process widget",
     "dd, F9           Kill the selected process",
+    #[cfg(target_family = "unix")]
+    "XX               Send SIGKILL to killed processes that are still running",
     "c                Sort by CPU usage, press again to reverse",
     "m                Sort by memory usage, press again to reverse",
     "p                Sort by PID name, press again to reverse",
     "n                Sort by process name, press again to reverse",
     "Tab              Group/un-group processes with the same name",
+    "U                Show one row per user, Enter on one searches for its processes",
     "Ctrl-f, /        Open process search widget",
     "P                Toggle between showing the full command or just the process name",
     "s, F6            Open process sort widget",
     "I                Invert current sort",
     "%                Toggle between values and percentages for memory usage",
+    "N                Toggle between CPU usage per core and across all cores",
     "t, F5            Toggle tree mode",
+    "a                Show the selected process's parent chain, Enter on one selects it",
     "+, -, click      Collapse/expand a branch while in tree mode",
     "click on header  Sorts the entries by that column, click again to invert the sort",
     "C                Sort by GPU usage, press again to reverse",
     "M                Sort by GPU memory usage, press again to reverse",
 ];
 
-const SEARCH_HELP_TEXT: [&str; 51] = [
+const SEARCH_HELP_TEXT: [&str; 52] = [
     "4 - Process search widget",
     "Esc              Close the search widget (retains the filter)",
     "Ctrl-a           Skip to the start of the search query",
@@ -115,7 +142,7 @@ const SEARCH_HELP_TEXT: [&str; 51] = [
     "",
     "Supported search types:",
     "<by name/cmd>    ex: btm",
-    "pid              ex: pid 825",
+    "pid              ex: pid 825, pid:>1000",
     "cpu, cpu%        ex: cpu > 4.2",
     "mem, mem%        ex: mem < 4.2",
     "memb             ex: memb < 100 kb",
@@ -124,7 +151,8 @@ const SEARCH_HELP_TEXT: [&str; 51] = [
     "tread, t.read    ex: tread = 1",
     "twrite, t.write  ex: twrite = 1",
     "user             ex: user = root",
-    "state            ex: state = running",
+    "state            ex: state:zombie",
+    "spiked_within    ex: spiked_within:5m",
     "gpu%             ex: gpu% < 4.2",
     "gmem             ex: gmem < 100 kb",
     "gmem%            ex: gmem% < 4.2",
@@ -166,7 +194,7 @@ const TEMP_HELP_WIDGET: [&str; 3] = [
     "'t'              Sort by temperature, press again to reverse",
 ];
 
-const DISK_HELP_WIDGET: [&str; 9] = [
+const DISK_HELP_WIDGET: [&str; 10] = [
     "7 - Disk widget",
     "'d'              Sort by disk name, press again to reverse",
     "'m'              Sort by disk mount, press again to reverse",
@@ -176,6 +204,7 @@ const DISK_HELP_WIDGET: [&str; 9] = [
     "'p'              Sort by disk usage percentage, press again to reverse",
     "'r'              Sort by disk read activity, press again to reverse",
     "'w'              Sort by disk write activity, press again to reverse",
+    "I                Invert current sort",
 ];
 
 const BATTERY_HELP_TEXT: [&str; 3] = [
@@ -271,9 +300,18 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Whether to use dot markers rather than braille.
 #dot_marker = false
 
+# Whether to draw graph lines thicker.
+#thick_lines = false
+
+# Whether to draw the newest data on the left of graphs rather than the right.
+#newest_on_left = false
+
 # The update rate of the application.
 #rate = "1s"
 
+# The update rate of temperature sensors. Reading some sensors (e.g. a discrete GPU's) can wake them up.
+#temperature_rate = "5s"
+
 # Whether to put the CPU legend to the left.
 #cpu_left_legend = false
 
@@ -346,6 +384,9 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Show processes as their commands by default in the process widget.
 #process_command = false
 
+# Shows the full command of the process under the mouse cursor if it is truncated.
+#process_command_tooltip = false
+
 # Displays the network widget with binary prefixes.
 #network_use_binary_prefix = false
 
@@ -367,9 +408,27 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # How much data is stored at once in terms of time.
 #retention = "10m"
 
+# Merges consecutive data points within this percentage of each other to save memory. Merged runs are drawn as flat lines.
+#merge_tolerance = "1"
+
+# Saves some UI state (e.g. process sorting and graph zoom) on exit, and restores it on the next start.
+#save_state = false
+
+# What to do if another running instance is already writing to the same state, JSON log or graph snapshot file. One of "skip" (don't write) or "suffix" (write to an instance-numbered file instead, e.g. "state.2.toml").
+#instance_conflict = "skip"
+
+# Serves metrics in the Prometheus format at "/metrics" on the given address.
+#metrics_listen = "127.0.0.1:9185"
+
+# Shows byte values in the disk, memory, and process widgets in a compact format (e.g. "1.2G").
+#compact_bytes = false
+
 # Where to place the legend for the memory widget. One of "none", "top-left", "top", "top-right", "left", "right", "bottom-left", "bottom", "bottom-right".
 #memory_legend = "top-right"
 
+# Where to place the legend for the GPU widget. One of "none", "top-left", "top", "top-right", "left", "right", "bottom-left", "bottom", "bottom-right".
+#gpu_legend = "top-right"
+
 # Where to place the legend for the network widget. One of "none", "top-left", "top", "top-right", "left", "right", "bottom-left", "bottom", "bottom-right".
 #network_legend = "top-right"
 
@@ -377,22 +436,91 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Processes widget configuration
 #[processes]
 # The columns shown by the process widget. The following columns are supported (the GPU columns are only available if the GPU feature is enabled when built):
-# PID, Name, CPU%, Mem%, R/s, W/s, T.Read, T.Write, User, State, Time, GMem%, GPU%
+# PID, Name, CPU%, CPU History, Mem%, R/s, W/s, T.Read, T.Write, User, Group, State, Time, Location, GMem%, GPU%
 #columns = ["PID", "Name", "CPU%", "Mem%", "R/s", "W/s", "T.Read", "T.Write", "User", "State", "GMem%", "GPU%"]
 
+# The signal sent when killing a process, by name or number. Defaults to "TERM". Unix-like systems only.
+#kill_signal_default = "KILL"
+
+# The signals listed in the advanced kill dialog, by name or number. Defaults to all signals. Unix-like systems only.
+#kill_signals = ["TERM", "KILL", "HUP"]
+
+# Whether to show numeric UIDs and GIDs instead of user and group names. Unix-like systems only. If unset on Linux, this is done
+# automatically if bottom is in a container that can see the host's processes.
+#uid_numeric = false
+
+# What is drawn between columns: "space" (default), "padding" for extra space, or "line" for a vertical line.
+#column_separator = "line"
+
+# How the process tree is drawn: "lines" (default) to join processes to their parents with lines, or "plain" to only
+# indent them, for fonts that draw box characters poorly.
+#tree_style = "plain"
+
+# Whether to show how many processes are in each group next to its name when grouping processes, e.g. "chrome (12)".
+# Defaults to false.
+#show_group_count = true
+
+# When grouping processes, only group those with more than this many processes of the same name, and show the rest
+# on their own. Defaults to 0, which groups everything.
+#group_threshold = 2
+
+# Whether to sort text columns like the name by case, so "Xorg" comes before "bash". Defaults to false.
+#case_sensitive_sort = false
+
+# The CPU usage a process must go over for the "last spike" column and "spiked_within" searches to count it. Defaults
+# to 50.
+#spike_threshold = 80
+
+# Custom columns, computed from other process values. These are shown after all other columns.
+# The unit is one of "none" (default), "bytes", "bytes/s", or "percent".
+#[[processes.custom_columns]]
+#name = "Mem/CPU"
+#expr = "mem_bytes / cpu_percent"
+#unit = "bytes"
+
 
 # CPU widget configuration
 #[cpu]
 # One of "all" (default), "average"/"avg"
 #default = "average"
 
+# Whether to colour each usage in the CPU legend from green to red by how high it is. Defaults to false.
+#legend_heatmap = false
+
+# Whether to show the 1, 5, and 15 minute load averages in the CPU widget's title. Defaults to true.
+#show_load_avg = true
+
+# Whether to show one entry per socket instead of one per core, on Linux machines with more than one socket. Defaults to false.
+#per_socket = false
+
+# Whether to start by showing how CPU time is split between user, system, iowait, and steal time. Defaults to false.
+#breakdown = false
+
+
+# Memory widget configuration
+#[memory]
+# The series shown by the memory graph, in order. Series that aren't listed are hidden. Supported series are:
+# "ram" (or "memory"), "cache", "swap", "arc", "arc_details", "gpu"
+#series = ["ram", "cache", "swap", "arc", "gpu"]
+
 
 # Disk widget configuration
 #[disk]
-# The columns shown by the process widget. The following columns are supported:
-# Disk, Mount, Used, Free, Total, Used%, Free%, R/s, W/s
+# The columns shown by the disk widget, in order. The following columns are supported (ignoring case):
+# Disk, Mount, FS, Used, Free, Total, Used%, Free%, R/s, W/s, Usage, Health, Frag%
 #columns = ["Disk", "Mount", "Used", "Free", "Total", "Used%", "R/s", "W/s"]
 
+# Whether to add a column with a bar showing how full each disk is. Defaults to false.
+#show_usage_bar = false
+
+# Whether to sort text columns like the mount by case. Defaults to false.
+#case_sensitive_sort = false
+
+# Names to show instead of mount points or disk names. Filters still match the real values.
+#[disk.aliases]
+#"/srv/storage/media/library" = "media"
+#"/dev/nvme0n1p2" = "system"
+
 # By default, there are no disk name filters enabled. These can be turned on to filter out specific data entries if you
 # don't want to see them. An example use case is provided below.
 #[disk.name_filter]
@@ -428,9 +556,33 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Whether to be require matching the whole word. Defaults to false.
 #whole_word = false
 
+# By default, there are no filesystem type filters enabled. An example use case is provided below.
+#[disk.fs_filter]
+# Whether to ignore any matches. Defaults to true.
+#is_list_ignored = true
+
+# A list of filters to try and match.
+#list = ["vfat", "fuseblk"]
+
+# Whether to use regex. Defaults to false.
+#regex = false
+
+# Whether to be case-sensitive. Defaults to false.
+#case_sensitive = false
+
+# Whether to be require matching the whole word. Defaults to false.
+#whole_word = true
+
 
 # Temperature widget configuration
 #[temperature]
+# The columns shown by the temperature widget, in order. The following columns are supported (ignoring case):
+# Sensor, Temp, Max, Min, Trend
+#columns = ["Sensor", "Temp"]
+
+# Whether to sort the sensor column by case. Defaults to false.
+#case_sensitive_sort = false
+
 # By default, there are no temperature sensor filters enabled. An example use case is provided below.
 #[temperature.sensor_filter]
 # Whether to ignore any matches. Defaults to true.
@@ -451,6 +603,17 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 
 # Network widget configuration
 #[network]
+# Whether to show each network interface's usage separately in the network graph.
+#show_interfaces = false
+# Whether the network graph's y-axis should only grow to the largest value seen this session, rather than fit what is shown.
+#lock_to_session_max = false
+# Whether to draw a line at the highest RX and TX rates seen this session in the network graph.
+#show_session_max = false
+# Whether to show the highest rates and the totals seen this session in the network graph's legend.
+#show_session_totals = false
+# Pins the network graph's y-axis max to a rate per second, e.g. "100Mb" or "1GiB", rather than fitting what is shown.
+#y_max = "100Mb"
+
 # By default, there are no network interface filters enabled. An example use case is provided below.
 #[network.interface_filter]
 # Whether to ignore any matches. Defaults to true.
@@ -488,6 +651,10 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #all_entry_color = "green"
 #avg_entry_color = "red"
 #cpu_core_colors = ["light magenta", "light yellow", "light cyan", "light green", "light blue", "cyan", "green", "blue"]
+#user_color = "light green"
+#system_color = "light red"
+#iowait_color = "light blue"
+#steal_color = "light yellow"
 
 #[styles.memory]
 #ram_color = "light magenta"
@@ -501,6 +668,7 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #tx_color = "light yellow"
 #rx_total_color = "light cyan"
 #tx_total_color = "light green"
+#interface_colors = ["light magenta", "light yellow", "light cyan", "light green", "light blue", "cyan", "green", "blue"]
 
 #[styles.battery]
 #high_battery_color = "green"
@@ -513,6 +681,8 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #[styles.graphs]
 #graph_color = "gray"
 #legend_text = {color = "gray"}
+#show_gridlines = false
+#gridlines = [25, 50, 75]
 
 #[styles.widgets]
 #border_color = "gray"
@@ -521,6 +691,7 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #text = {color = "gray"}
 #selected_text = {color = "black", bg_color = "light blue"}
 #disabled_text = {color = "dark gray"}
+#added_text = {color = "green"}
 
 # Layout - layouts follow a pattern like this:
 # [[row]] represents a row in the application.
@@ -552,6 +723,14 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #  [[row.child]]
 #    type="proc"
 #    default=true
+#
+# Layouts that are only used if the terminal is at least a certain size can be
+# added with [[layouts]], each having a min_width and/or min_height:
+#[[layouts]]
+#  min_width=120
+#  [[layouts.row]]
+#    [[layouts.row.child]]
+#    type="proc"
 "#;
 
 #[cfg(test)]
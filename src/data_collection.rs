@@ -6,6 +6,12 @@ pub mod nvidia;
 #[cfg(all(target_os = "linux", feature = "gpu"))]
 pub mod amd;
 
+#[cfg(all(target_os = "linux", feature = "gpu"))]
+pub mod intel;
+
+#[cfg(all(target_os = "linux", feature = "gpu"))]
+mod drm;
+
 #[cfg(feature = "battery")]
 pub mod batteries;
 pub mod cpu;
@@ -16,9 +22,14 @@ pub mod network;
 pub mod processes;
 pub mod temperature;
 
-use std::time::{Duration, Instant};
+use std::{
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant, SystemTime},
+};
 
-#[cfg(any(target_os = "linux", feature = "gpu"))]
 use hashbrown::HashMap;
 #[cfg(not(target_os = "windows"))]
 use processes::Pid;
@@ -32,7 +43,13 @@ use crate::app::layout_manager::UsedWidgets;
 #[derive(Clone, Debug)]
 pub struct Data {
     pub collection_time: Instant,
+    /// Whether the system was suspended since the last harvest, in which case
+    /// rates were measured from a new baseline.
+    pub resumed: bool,
     pub cpu: Option<cpu::CpuHarvest>,
+    /// How CPU time was split between user, system, iowait, and steal time
+    /// since the last harvest, across all cores.
+    pub cpu_breakdown: Option<cpu::CpuBreakdown>,
     pub load_avg: Option<cpu::LoadAvgHarvest>,
     pub memory: Option<memory::MemHarvest>,
     #[cfg(not(target_os = "windows"))]
@@ -40,22 +57,34 @@ pub struct Data {
     pub swap: Option<memory::MemHarvest>,
     pub temperature_sensors: Option<Vec<temperature::TempHarvest>>,
     pub network: Option<network::NetworkHarvest>,
+    pub network_interfaces: Option<Vec<(String, network::NetworkHarvest)>>,
     pub list_of_processes: Option<Vec<processes::ProcessHarvest>>,
+    /// The number of CPU cores that process CPU usage was measured across.
+    pub num_cpus: Option<usize>,
     pub disks: Option<Vec<disks::DiskHarvest>>,
     pub io: Option<disks::IoHarvest>,
     #[cfg(feature = "battery")]
     pub list_of_batteries: Option<Vec<batteries::BatteryData>>,
     #[cfg(feature = "zfs")]
     pub arc: Option<memory::MemHarvest>,
+    #[cfg(feature = "zfs")]
+    pub arc_hit_ratio: Option<f64>,
+    #[cfg(feature = "zfs")]
+    pub arc_breakdown: Option<memory::arc::ArcBreakdown>,
     #[cfg(feature = "gpu")]
     pub gpu: Option<Vec<(String, memory::MemHarvest)>>,
+    /// How busy each GPU's cores are, as a percentage.
+    #[cfg(feature = "gpu")]
+    pub gpu_util: Option<Vec<(String, f64)>>,
 }
 
 impl Default for Data {
     fn default() -> Self {
         Data {
             collection_time: Instant::now(),
+            resumed: false,
             cpu: None,
+            cpu_breakdown: None,
             load_avg: None,
             memory: None,
             #[cfg(not(target_os = "windows"))]
@@ -63,15 +92,23 @@ impl Default for Data {
             swap: None,
             temperature_sensors: None,
             list_of_processes: None,
+            num_cpus: None,
             disks: None,
             io: None,
             network: None,
+            network_interfaces: None,
             #[cfg(feature = "battery")]
             list_of_batteries: None,
             #[cfg(feature = "zfs")]
             arc: None,
+            #[cfg(feature = "zfs")]
+            arc_hit_ratio: None,
+            #[cfg(feature = "zfs")]
+            arc_breakdown: None,
             #[cfg(feature = "gpu")]
             gpu: None,
+            #[cfg(feature = "gpu")]
+            gpu_util: None,
         }
     }
 }
@@ -85,22 +122,122 @@ impl Data {
         self.memory = None;
         self.swap = None;
         self.cpu = None;
+        self.cpu_breakdown = None;
         self.load_avg = None;
 
         if let Some(network) = &mut self.network {
             network.first_run_cleanup();
         }
+        if let Some(interfaces) = &mut self.network_interfaces {
+            interfaces
+                .iter_mut()
+                .for_each(|(_, network)| network.first_run_cleanup());
+        }
         #[cfg(feature = "zfs")]
         {
             self.arc = None;
+            self.arc_hit_ratio = None;
+            self.arc_breakdown = None;
         }
         #[cfg(feature = "gpu")]
         {
             self.gpu = None;
+            self.gpu_util = None;
         }
     }
 }
 
+/// A part of a harvest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CollectionStage {
+    /// Not currently harvesting.
+    Idle = 0,
+    /// Refreshing data shared by multiple widgets, like sysinfo's.
+    Refresh,
+    Cpu,
+    Memory,
+    Temperature,
+    Battery,
+    Gpu,
+    Processes,
+    Network,
+    Disks,
+}
+
+impl CollectionStage {
+    const ALL: [CollectionStage; 10] = [
+        CollectionStage::Idle,
+        CollectionStage::Refresh,
+        CollectionStage::Cpu,
+        CollectionStage::Memory,
+        CollectionStage::Temperature,
+        CollectionStage::Battery,
+        CollectionStage::Gpu,
+        CollectionStage::Processes,
+        CollectionStage::Network,
+        CollectionStage::Disks,
+    ];
+
+    #[cfg_attr(not(feature = "logging"), allow(dead_code))]
+    pub fn name(&self) -> &'static str {
+        match self {
+            CollectionStage::Idle => "idle",
+            CollectionStage::Refresh => "refresh",
+            CollectionStage::Cpu => "cpu",
+            CollectionStage::Memory => "memory",
+            CollectionStage::Temperature => "temperature",
+            CollectionStage::Battery => "battery",
+            CollectionStage::Gpu => "gpu",
+            CollectionStage::Processes => "processes",
+            CollectionStage::Network => "network",
+            CollectionStage::Disks => "disks",
+        }
+    }
+}
+
+/// Tracks which part of a harvest the collection thread is working on. This is
+/// shared with the app thread, so it can tell which part is stuck if harvests
+/// stop arriving.
+#[derive(Debug, Default, Clone)]
+pub struct CollectionProgress {
+    current: Arc<AtomicU8>,
+    last_completed: Arc<AtomicU8>,
+}
+
+impl CollectionProgress {
+    fn load(stage: &AtomicU8) -> CollectionStage {
+        CollectionStage::ALL
+            .get(stage.load(Ordering::Relaxed) as usize)
+            .copied()
+            .unwrap_or(CollectionStage::Idle)
+    }
+
+    /// Marks that the collection thread has started on `stage`, completing the
+    /// current stage if there is one.
+    pub fn enter(&self, stage: CollectionStage) {
+        let current = self.current.swap(stage as u8, Ordering::Relaxed);
+        if current != CollectionStage::Idle as u8 {
+            self.last_completed.store(current, Ordering::Relaxed);
+        }
+    }
+
+    /// Marks that the current harvest is finished.
+    pub fn finish(&self) {
+        self.enter(CollectionStage::Idle);
+    }
+
+    /// The stage that the collection thread is currently working on.
+    pub fn current(&self) -> CollectionStage {
+        Self::load(&self.current)
+    }
+
+    /// The stage that the collection thread most recently completed.
+    pub fn last_completed(&self) -> CollectionStage {
+        Self::load(&self.last_completed)
+    }
+}
+
 /// A wrapper around the sysinfo data source. We use sysinfo for the following
 /// data:
 /// - CPU usage
@@ -147,11 +284,28 @@ pub struct DataCollector {
     use_current_cpu_total: bool,
     unnormalized_cpu: bool,
     last_collection_time: Instant,
-    total_rx: u64,
-    total_tx: u64,
+    /// The system time of the last collection, used to tell whether the system
+    /// was suspended in between.
+    last_collection_system_time: SystemTime,
+    temperature_update_rate: Duration,
+    last_temperature_time: Option<Instant>,
+    prev_network_totals: HashMap<String, (u64, u64)>,
+    /// Whether network totals have been read since starting or resuming. Rates
+    /// can't be worked out from the first read, so it is only kept as a baseline.
+    has_network_baseline: bool,
+    /// Whether CPU usage has been read before. Like network rates, CPU usage is
+    /// worked out from what changed since the last read.
+    has_cpu_baseline: bool,
+    /// The CPU times from the last read, to work out how the time since then
+    /// was spent.
+    prev_cpu_times: Option<cpu::CpuTimes>,
     show_average_cpu: bool,
+    /// Which socket each core is on, if cores are shown per socket.
+    cpu_packages: Option<cpu::CpuPackages>,
+    show_network_interfaces: bool,
     widgets_to_harvest: UsedWidgets,
     filters: DataFilters,
+    progress: CollectionProgress,
 
     #[cfg(target_os = "linux")]
     pid_mapping: HashMap<Pid, processes::PrevProcDetails>,
@@ -167,6 +321,11 @@ pub struct DataCollector {
 
     #[cfg(target_family = "unix")]
     user_table: processes::UserTable,
+    #[cfg(target_family = "unix")]
+    uid_numeric: Option<bool>,
+
+    #[cfg(target_os = "macos")]
+    io_pairing: disks::IoPairing,
 
     #[cfg(feature = "gpu")]
     gpu_pids: Option<Vec<HashMap<u32, (u64, u32)>>>,
@@ -179,6 +338,10 @@ impl DataCollector {
         // Initialize it to the past to force it to load on initialization.
         let now = Instant::now();
         let last_collection_time = now.checked_sub(Duration::from_secs(600)).unwrap_or(now);
+        let system_now = SystemTime::now();
+        let last_collection_system_time = system_now
+            .checked_sub(Duration::from_secs(600))
+            .unwrap_or(system_now);
 
         DataCollector {
             data: Data::default(),
@@ -193,17 +356,29 @@ impl DataCollector {
             use_current_cpu_total: false,
             unnormalized_cpu: false,
             last_collection_time,
-            total_rx: 0,
-            total_tx: 0,
+            last_collection_system_time,
+            temperature_update_rate: Duration::ZERO,
+            last_temperature_time: None,
+            prev_network_totals: HashMap::default(),
+            has_network_baseline: false,
+            has_cpu_baseline: false,
+            prev_cpu_times: None,
             show_average_cpu: false,
+            cpu_packages: None,
+            show_network_interfaces: false,
             widgets_to_harvest: UsedWidgets::default(),
             #[cfg(feature = "battery")]
             battery_manager: None,
             #[cfg(feature = "battery")]
             battery_list: None,
             filters,
+            progress: CollectionProgress::default(),
             #[cfg(target_family = "unix")]
             user_table: Default::default(),
+            #[cfg(target_family = "unix")]
+            uid_numeric: None,
+            #[cfg(target_os = "macos")]
+            io_pairing: disks::IoPairing::default(),
             #[cfg(feature = "gpu")]
             gpu_pids: None,
             #[cfg(feature = "gpu")]
@@ -212,6 +387,20 @@ impl DataCollector {
     }
 
     pub fn init(&mut self) {
+        #[cfg(target_family = "unix")]
+        {
+            // If we can see processes from outside our container, our passwd would give
+            // them the wrong usernames.
+            #[cfg(target_os = "linux")]
+            let uid_numeric = self
+                .uid_numeric
+                .unwrap_or_else(|| processes::has_foreign_passwd(std::path::Path::new("/proc")));
+            #[cfg(not(target_os = "linux"))]
+            let uid_numeric = self.uid_numeric.unwrap_or(false);
+
+            self.user_table.set_numeric(uid_numeric);
+        }
+
         #[cfg(feature = "battery")]
         {
             if self.widgets_to_harvest.use_battery {
@@ -236,6 +425,19 @@ impl DataCollector {
         self.data.cleanup();
     }
 
+    /// Resets harvested data, as well as anything cached across harvests.
+    pub fn reset(&mut self) {
+        self.data.cleanup();
+
+        #[cfg(target_family = "unix")]
+        self.user_table.clear();
+    }
+
+    #[cfg(target_family = "unix")]
+    pub fn set_uid_numeric(&mut self, uid_numeric: Option<bool>) {
+        self.uid_numeric = uid_numeric;
+    }
+
     pub fn set_data_collection(&mut self, used_widgets: UsedWidgets) {
         self.widgets_to_harvest = used_widgets;
     }
@@ -244,6 +446,14 @@ impl DataCollector {
         self.temperature_type = temperature_type;
     }
 
+    pub fn set_temperature_update_rate(&mut self, temperature_update_rate: Duration) {
+        self.temperature_update_rate = temperature_update_rate;
+    }
+
+    pub fn set_progress(&mut self, progress: CollectionProgress) {
+        self.progress = progress;
+    }
+
     pub fn set_use_current_cpu_total(&mut self, use_current_cpu_total: bool) {
         self.use_current_cpu_total = use_current_cpu_total;
     }
@@ -256,6 +466,40 @@ impl DataCollector {
         self.show_average_cpu = show_average_cpu;
     }
 
+    /// Sets whether cores are shown as one entry per socket. Which socket each
+    /// core is on is only read once, here.
+    pub fn set_cpu_per_socket(&mut self, cpu_per_socket: bool) {
+        self.cpu_packages = cpu_per_socket.then(cpu::get_cpu_packages);
+    }
+
+    pub fn set_show_network_interfaces(&mut self, show_network_interfaces: bool) {
+        self.show_network_interfaces = show_network_interfaces;
+    }
+
+    /// Harvests the disks once and returns them for listing. Only the
+    /// collector's filters apply, so this lists every disk with the default
+    /// ones, which helps with writing filters.
+    pub fn list_disks(&mut self) -> Vec<String> {
+        self.widgets_to_harvest.use_disk = true;
+        self.refresh_sysinfo_data(false);
+        self.update_disks();
+
+        disks::disk_listing(self.data.disks.as_deref().unwrap_or_default())
+    }
+
+    /// Harvests the network interfaces once and returns their names for
+    /// listing. Like [`Self::list_disks`], only the collector's filters apply.
+    pub fn list_network_interfaces(&mut self) -> Vec<String> {
+        self.widgets_to_harvest.use_net = true;
+        self.show_network_interfaces = true;
+        // Only the names are needed, so there's no need for a baseline reading first.
+        self.has_network_baseline = true;
+        self.refresh_sysinfo_data(false);
+        self.update_network_usage();
+
+        network::interface_listing(self.data.network_interfaces.as_deref().unwrap_or_default())
+    }
+
     /// Refresh sysinfo data. We use sysinfo for the following data:
     /// - CPU usage
     /// - Memory usage
@@ -263,7 +507,8 @@ impl DataCollector {
     /// - Processes (non-Linux)
     /// - Disk (Windows)
     /// - Temperatures (non-Linux)
-    fn refresh_sysinfo_data(&mut self) {
+    #[cfg_attr(target_os = "linux", allow(unused_variables))]
+    fn refresh_sysinfo_data(&mut self, harvest_temps: bool) {
         // Refresh the list of objects once every minute. If it's too frequent it can
         // cause segfaults.
         const LIST_REFRESH_TIME: Duration = Duration::from_secs(60);
@@ -278,7 +523,11 @@ impl DataCollector {
         }
 
         if self.widgets_to_harvest.use_net {
-            if refresh_start.duration_since(self.last_collection_time) > LIST_REFRESH_TIME {
+            // If we're filtering interfaces, refresh the list every time so that interfaces
+            // that come and go (e.g. VPN tunnels) are picked up on the next harvest.
+            if self.filters.net_filter.is_some()
+                || refresh_start.duration_since(self.last_collection_time) > LIST_REFRESH_TIME
+            {
                 self.sys.network.refresh_list();
             }
             self.sys.network.refresh();
@@ -305,7 +554,7 @@ impl DataCollector {
                 }
             }
 
-            if self.widgets_to_harvest.use_temp {
+            if harvest_temps {
                 if refresh_start.duration_since(self.last_collection_time) > LIST_REFRESH_TIME {
                     self.sys.temps.refresh_list();
                 }
@@ -322,34 +571,86 @@ impl DataCollector {
         }
     }
 
+    /// Whether temperatures should be harvested at `now`. Temperatures are
+    /// refreshed at their own, slower rate, as reading some sensors can wake
+    /// up the device they belong to.
+    fn should_harvest_temps(&self, now: Instant) -> bool {
+        self.widgets_to_harvest.use_temp
+            && self.last_temperature_time.map_or(true, |last| {
+                now.saturating_duration_since(last) >= self.temperature_update_rate
+            })
+    }
+
     pub fn update_data(&mut self) {
-        self.refresh_sysinfo_data();
+        let harvest_temps = self.should_harvest_temps(Instant::now());
+
+        self.progress.enter(CollectionStage::Refresh);
+        self.refresh_sysinfo_data(harvest_temps);
 
         self.data.collection_time = Instant::now();
+        let collection_system_time = SystemTime::now();
+        self.data.resumed = was_suspended(
+            self.data
+                .collection_time
+                .saturating_duration_since(self.last_collection_time),
+            collection_system_time,
+            self.last_collection_system_time,
+        );
+        if self.data.resumed {
+            // Anything sent or received while suspended would otherwise be counted as if it
+            // happened since the last harvest, so start the network rates over.
+            self.prev_network_totals.clear();
+            self.has_network_baseline = false;
+        }
 
+        self.progress.enter(CollectionStage::Cpu);
         self.update_cpu_usage();
+        self.progress.enter(CollectionStage::Memory);
         self.update_memory_usage();
-        self.update_temps();
+        if harvest_temps {
+            self.progress.enter(CollectionStage::Temperature);
+            self.update_temps();
+            self.last_temperature_time = Some(self.data.collection_time);
+        }
 
         #[cfg(feature = "battery")]
-        self.update_batteries();
+        {
+            self.progress.enter(CollectionStage::Battery);
+            self.update_batteries();
+        }
 
         #[cfg(feature = "gpu")]
-        self.update_gpus(); // update_gpus before procs for gpu_pids but after temps for appending
+        {
+            self.progress.enter(CollectionStage::Gpu);
+            self.update_gpus(harvest_temps); // update_gpus before procs for gpu_pids but after temps for appending
+        }
 
+        self.progress.enter(CollectionStage::Processes);
         self.update_processes();
+        self.progress.enter(CollectionStage::Network);
         self.update_network_usage();
+        self.progress.enter(CollectionStage::Disks);
         self.update_disks();
+        self.progress.finish();
 
         // Update times for future reference.
         self.last_collection_time = self.data.collection_time;
+        self.last_collection_system_time = collection_system_time;
     }
 
     #[cfg(feature = "gpu")]
     #[inline]
-    fn update_gpus(&mut self) {
+    fn update_gpus(&mut self, harvest_temps: bool) {
         if self.widgets_to_harvest.use_gpu {
+            // GPU temperatures follow the temperature refresh rate, same as all other
+            // temperature sensors.
+            let used_widgets = UsedWidgets {
+                use_temp: harvest_temps,
+                ..self.widgets_to_harvest
+            };
+
             let mut local_gpu: Vec<(String, memory::MemHarvest)> = Vec::new();
+            let mut local_gpu_util: Vec<(String, f64)> = Vec::new();
             let mut local_gpu_pids: Vec<HashMap<u32, (u64, u32)>> = Vec::new();
             let mut local_gpu_total_mem: u64 = 0;
 
@@ -357,7 +658,7 @@ impl DataCollector {
             if let Some(data) = nvidia::get_nvidia_vecs(
                 &self.temperature_type,
                 &self.filters.temp_filter,
-                &self.widgets_to_harvest,
+                &used_widgets,
             ) {
                 if let Some(mut temp) = data.temperature {
                     if let Some(sensors) = &mut self.data.temperature_sensors {
@@ -369,17 +670,17 @@ impl DataCollector {
                 if let Some(mut mem) = data.memory {
                     local_gpu.append(&mut mem);
                 }
-                if let Some(mut proc) = data.procs {
-                    local_gpu_pids.append(&mut proc.1);
-                    local_gpu_total_mem += proc.0;
+                if let Some(mut utilization) = data.utilization {
+                    local_gpu_util.append(&mut utilization);
                 }
+                append_gpu_procs(&mut local_gpu_pids, &mut local_gpu_total_mem, data.procs);
             }
 
             #[cfg(target_os = "linux")]
             if let Some(data) = amd::get_amd_vecs(
                 &self.temperature_type,
                 &self.filters.temp_filter,
-                &self.widgets_to_harvest,
+                &used_widgets,
                 self.last_collection_time,
             ) {
                 if let Some(mut temp) = data.temperature {
@@ -392,13 +693,25 @@ impl DataCollector {
                 if let Some(mut mem) = data.memory {
                     local_gpu.append(&mut mem);
                 }
-                if let Some(mut proc) = data.procs {
-                    local_gpu_pids.append(&mut proc.1);
-                    local_gpu_total_mem += proc.0;
+                if let Some(mut utilization) = data.utilization {
+                    local_gpu_util.append(&mut utilization);
+                }
+                append_gpu_procs(&mut local_gpu_pids, &mut local_gpu_total_mem, data.procs);
+            }
+
+            #[cfg(target_os = "linux")]
+            if let Some(data) = intel::get_intel_vecs(&used_widgets, self.last_collection_time) {
+                if let Some(mut mem) = data.memory {
+                    local_gpu.append(&mut mem);
+                }
+                if let Some(mut utilization) = data.utilization {
+                    local_gpu_util.append(&mut utilization);
                 }
+                append_gpu_procs(&mut local_gpu_pids, &mut local_gpu_total_mem, data.procs);
             }
 
             self.data.gpu = (!local_gpu.is_empty()).then_some(local_gpu);
+            self.data.gpu_util = (!local_gpu_util.is_empty()).then_some(local_gpu_util);
             self.gpu_pids = (!local_gpu_pids.is_empty()).then_some(local_gpu_pids);
             self.gpus_total_mem = (local_gpu_total_mem > 0).then_some(local_gpu_total_mem);
         }
@@ -407,12 +720,30 @@ impl DataCollector {
     #[inline]
     fn update_cpu_usage(&mut self) {
         if self.widgets_to_harvest.use_cpu {
-            self.data.cpu = cpu::get_cpu_data_list(&self.sys.system, self.show_average_cpu).ok();
+            // The first read has nothing to compare against, so it would show as
+            // either 0% or 100%.
+            if self.has_cpu_baseline {
+                self.data.cpu = cpu::get_cpu_data_list(&self.sys.system, self.show_average_cpu)
+                    .ok()
+                    .map(|harvest| match &self.cpu_packages {
+                        Some(packages) => cpu::group_by_socket(harvest, packages),
+                        None => harvest,
+                    });
+            }
+            self.has_cpu_baseline = true;
 
-            #[cfg(target_family = "unix")]
-            {
-                self.data.load_avg = Some(cpu::get_load_avg());
+            // This is always read, so that switching the CPU widget to show the
+            // breakdown has history to show right away.
+            let cpu_times = cpu::get_cpu_times();
+            if let (Some(now), Some(prev)) = (&cpu_times, &self.prev_cpu_times) {
+                self.data.cpu_breakdown = now.breakdown_since(prev);
             }
+            self.prev_cpu_times = cpu_times;
+        }
+
+        #[cfg(target_family = "unix")]
+        if self.widgets_to_harvest.use_cpu || self.widgets_to_harvest.use_load_avg {
+            self.data.load_avg = Some(cpu::get_load_avg());
         }
     }
 
@@ -425,6 +756,7 @@ impl DataCollector {
                 // if we're sorting by PID, since we already did it here!
                 process_list.sort_unstable_by_key(|p| p.pid);
                 self.data.list_of_processes = Some(process_list);
+                self.data.num_cpus = Some(self.sys.system.cpus().len());
             }
         }
     }
@@ -464,7 +796,10 @@ impl DataCollector {
 
             #[cfg(feature = "zfs")]
             {
-                self.data.arc = memory::arc::get_arc_usage();
+                let arc_stats = memory::arc::get_arc_stats();
+                self.data.arc = Some(arc_stats.usage());
+                self.data.arc_hit_ratio = arc_stats.hit_ratio();
+                self.data.arc_breakdown = Some(arc_stats.breakdown);
             }
         }
     }
@@ -474,18 +809,22 @@ impl DataCollector {
         let current_instant = self.data.collection_time;
 
         if self.widgets_to_harvest.use_net {
-            let net_data = network::get_network_data(
+            let (net_data, interface_data) = network::get_network_data(
                 &self.sys.network,
                 self.last_collection_time,
-                &mut self.total_rx,
-                &mut self.total_tx,
+                &mut self.prev_network_totals,
                 current_instant,
                 &self.filters.net_filter,
             );
 
-            self.total_rx = net_data.total_rx;
-            self.total_tx = net_data.total_tx;
-            self.data.network = Some(net_data);
+            if self.has_network_baseline {
+                self.data.network = Some(net_data);
+
+                if self.show_network_interfaces {
+                    self.data.network_interfaces = Some(interface_data);
+                }
+            }
+            self.has_network_baseline = true;
         }
     }
 
@@ -505,6 +844,14 @@ impl DataCollector {
         if self.widgets_to_harvest.use_disk {
             self.data.disks = disks::get_disk_usage(self).ok();
             self.data.io = disks::get_io_usage().ok();
+
+            // I/O is only counted for physical disks on macOS, so pair each disk with
+            // the ones it is on.
+            #[cfg(target_os = "macos")]
+            if let (Some(disks), Some(io)) = (&self.data.disks, &mut self.data.io) {
+                self.io_pairing
+                    .pair(disks, io, Instant::now(), disks::apfs_list);
+            }
         }
     }
 
@@ -519,6 +866,23 @@ impl DataCollector {
     }
 }
 
+/// How much more the system time has to advance than [`Instant`] between two
+/// harvests before we assume that the system was suspended in between.
+const SUSPEND_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Whether the system was likely suspended between two harvests, given the
+/// time [`Instant`] measured between them and the system time of each.
+///
+/// On most platforms, [`Instant`] doesn't advance while the system is
+/// suspended but the system time does. Where [`Instant`] does advance, the
+/// measured time is already correct and this doesn't matter anyway.
+fn was_suspended(elapsed: Duration, system_time: SystemTime, prev_system_time: SystemTime) -> bool {
+    // If the system time went backwards, it was changed; that isn't a suspend.
+    system_time
+        .duration_since(prev_system_time)
+        .is_ok_and(|system_elapsed| system_elapsed.saturating_sub(elapsed) > SUSPEND_THRESHOLD)
+}
+
 /// We set a sleep duration between 10ms and 250ms, ideally sysinfo's
 /// [`sysinfo::MINIMUM_CPU_UPDATE_INTERVAL`] + 1.
 ///
@@ -557,3 +921,119 @@ where
         .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "key not found"))
         .and_then(|val| serde_json::from_value(val).map_err(|err| err.into()))
 }
+
+/// Adds the processes on one kind of GPU to those already found, along with
+/// the total memory of those GPUs.
+#[cfg(feature = "gpu")]
+fn append_gpu_procs(
+    gpu_pids: &mut Vec<HashMap<u32, (u64, u32)>>, total_mem: &mut u64,
+    procs: Option<(u64, Vec<HashMap<u32, (u64, u32)>>)>,
+) {
+    if let Some((mem, mut pids)) = procs {
+        gpu_pids.append(&mut pids);
+        *total_mem += mem;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn temperature_collector() -> DataCollector {
+        let mut collector = DataCollector::new(DataFilters {
+            disk_filter: None,
+            mount_filter: None,
+            fs_filter: None,
+            temp_filter: None,
+            net_filter: None,
+        });
+        collector.set_data_collection(UsedWidgets {
+            use_temp: true,
+            ..Default::default()
+        });
+        collector.set_temperature_update_rate(Duration::from_secs(5));
+
+        collector
+    }
+
+    #[test]
+    fn temperatures_use_their_own_rate() {
+        let mut collector = temperature_collector();
+        let start = Instant::now();
+
+        assert!(collector.should_harvest_temps(start));
+
+        collector.last_temperature_time = Some(start);
+        assert!(!collector.should_harvest_temps(start + Duration::from_secs(1)));
+        assert!(!collector.should_harvest_temps(start + Duration::from_millis(4999)));
+        assert!(collector.should_harvest_temps(start + Duration::from_secs(5)));
+
+        collector.widgets_to_harvest.use_temp = false;
+        assert!(!collector.should_harvest_temps(start + Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn temperature_harvest_skipped_within_interval() {
+        let mut collector = temperature_collector();
+
+        collector.update_data();
+        let harvested_at = collector.last_temperature_time;
+        assert!(harvested_at.is_some());
+
+        collector.data.cleanup();
+        collector.update_data();
+        assert_eq!(collector.last_temperature_time, harvested_at);
+        assert!(collector.data.temperature_sensors.is_none());
+    }
+
+    #[test]
+    fn first_reads_are_only_baselines() {
+        let mut collector = temperature_collector();
+        collector.set_data_collection(UsedWidgets {
+            use_cpu: true,
+            use_net: true,
+            ..Default::default()
+        });
+
+        collector.update_data();
+        assert!(collector.data.cpu.is_none());
+        assert!(collector.data.cpu_breakdown.is_none());
+        assert!(collector.data.network.is_none());
+
+        collector.data.cleanup();
+        collector.update_data();
+        assert!(collector.data.cpu.is_some());
+        assert!(collector.data.network.is_some());
+    }
+
+    #[test]
+    fn suspends_are_detected() {
+        let before = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+        // Normal harvests, with a bit of clock drift.
+        assert!(!was_suspended(
+            Duration::from_secs(1),
+            before + Duration::from_millis(1010),
+            before
+        ));
+        assert!(!was_suspended(
+            Duration::from_secs(60),
+            before + Duration::from_secs(60),
+            before
+        ));
+
+        // An hour passed, but only a second of it while the system was awake.
+        assert!(was_suspended(
+            Duration::from_secs(1),
+            before + Duration::from_secs(3600),
+            before
+        ));
+
+        // The system time was set back.
+        assert!(!was_suspended(
+            Duration::from_secs(1),
+            before - Duration::from_secs(3600),
+            before
+        ));
+    }
+}
@@ -3,6 +3,7 @@ mod amdgpu_marketing;
 use crate::{
     app::{filter::Filter, layout_manager::UsedWidgets},
     data_collection::{
+        drm::{find_vendor_devs, DRM_CLASS_PATH},
         memory::MemHarvest,
         temperature::{TempHarvest, TemperatureType},
     },
@@ -18,6 +19,8 @@ use std::{
 
 pub struct AMDGPUData {
     pub memory: Option<Vec<(String, MemHarvest)>>,
+    /// How busy each GPU is, as a percentage.
+    pub utilization: Option<Vec<(String, f64)>>,
     pub temperature: Option<Vec<TempHarvest>>,
     pub procs: Option<(u64, Vec<HashMap<u32, (u64, u32)>>)>,
 }
@@ -49,24 +52,11 @@ pub struct AMDGPUProc {
 static PROC_DATA: LazyLock<Mutex<HashMap<PathBuf, HashMap<u32, AMDGPUProc>>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
 
-pub fn get_amd_devs() -> Option<Vec<PathBuf>> {
-    let mut devices = Vec::new();
-
-    // read all PCI devices controlled by the AMDGPU module
-    let Ok(paths) = fs::read_dir("/sys/module/amdgpu/drivers/pci:amdgpu") else {
-        return None;
-    };
-
-    for path in paths {
-        let Ok(path) = path else { continue };
+/// The PCI vendor ID of AMD (and ATI) devices.
+const AMD_VENDOR_ID: &str = "0x1002";
 
-        // test if it has a valid vendor path
-        let device_path = path.path();
-        let test_path = device_path.join("vendor");
-        if test_path.as_path().exists() {
-            devices.push(device_path);
-        }
-    }
+pub fn get_amd_devs() -> Option<Vec<PathBuf>> {
+    let devices = find_vendor_devs(Path::new(DRM_CLASS_PATH), AMD_VENDOR_ID);
 
     if devices.is_empty() {
         None
@@ -113,6 +103,16 @@ pub fn get_amd_name(device_path: &Path) -> Option<String> {
         .map(|tuple| tuple.2.to_string())
 }
 
+/// Returns how busy the GPU is as a percentage, which the driver works out
+/// itself.
+pub fn get_amd_busy_percent(device_path: &Path) -> Option<f64> {
+    read_to_string(device_path.join("gpu_busy_percent"))
+        .ok()?
+        .trim_end()
+        .parse::<f64>()
+        .ok()
+}
+
 pub fn get_amd_vram(device_path: &Path) -> Option<AMDGPUMemory> {
     // get vram memory info from sysfs
     let vram_total_path = device_path.join("mem_info_vram_total");
@@ -180,17 +180,17 @@ pub fn get_amd_temp(device_path: &Path) -> Option<Vec<AMDGPUTemperature>> {
             }
 
             // construct label path
-            let hwmon_sensor_label_name = hwmon_sensor_name.replace("_input", "_label");
-            let hwmon_sensor_label_path = hwmon_path.join(hwmon_sensor_label_name);
+            let hwmon_sensor_prefix = hwmon_sensor_name.trim_end_matches("_input");
+            let hwmon_sensor_label_path = hwmon_path.join(format!("{hwmon_sensor_prefix}_label"));
 
             // read and remove newlines
             let Ok(mut hwmon_sensor_data) = read_to_string(hwmon_sensor_path) else {
                 continue;
             };
 
-            let Ok(mut hwmon_sensor_label) = read_to_string(hwmon_sensor_label_path) else {
-                continue;
-            };
+            // older kernels may not label sensors, so fall back to e.g. "temp1"
+            let mut hwmon_sensor_label = read_to_string(hwmon_sensor_label_path)
+                .unwrap_or_else(|_| hwmon_sensor_prefix.to_string());
 
             hwmon_sensor_data = hwmon_sensor_data.trim_end().to_string();
             hwmon_sensor_label = hwmon_sensor_label.trim_end().to_string();
@@ -200,7 +200,7 @@ pub fn get_amd_temp(device_path: &Path) -> Option<Vec<AMDGPUTemperature>> {
             };
 
             // uppercase first character
-            if hwmon_sensor_label.is_ascii() {
+            if hwmon_sensor_label.is_ascii() && !hwmon_sensor_label.is_empty() {
                 let (hwmon_sensor_label_head, hwmon_sensor_label_tail) =
                     hwmon_sensor_label.split_at(1);
 
@@ -411,6 +411,7 @@ pub fn get_amd_vecs(
     let num_gpu = device_path_list.len();
     let mut temp_vec = Vec::with_capacity(num_gpu);
     let mut mem_vec = Vec::with_capacity(num_gpu);
+    let mut util_vec = Vec::with_capacity(num_gpu);
     let mut proc_vec = Vec::with_capacity(num_gpu);
     let mut total_mem = 0;
 
@@ -432,6 +433,12 @@ pub fn get_amd_vecs(
             total_mem += mem.total
         }
 
+        if widgets_to_harvest.use_gpu_util {
+            if let Some(busy_percent) = get_amd_busy_percent(&device_path) {
+                util_vec.push((device_name.clone(), busy_percent));
+            }
+        }
+
         // TODO: Not sure if this overlaps with the existing generic temperature code.
         if widgets_to_harvest.use_temp && Filter::optional_should_keep(filter, &device_name) {
             if let Some(temperatures) = get_amd_temp(&device_path) {
@@ -501,7 +508,77 @@ pub fn get_amd_vecs(
 
     Some(AMDGPUData {
         memory: (!mem_vec.is_empty()).then_some(mem_vec),
+        utilization: (!util_vec.is_empty()).then_some(util_vec),
         temperature: (!temp_vec.is_empty()).then_some(temp_vec),
         procs: (!proc_vec.is_empty()).then_some((total_mem, proc_vec)),
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn missing_files_omit_metrics() {
+        let dir = tempfile::tempdir().unwrap();
+        let device = dir.path();
+
+        // No VRAM info, and sensors with and without labels.
+        write(&device.join("mem_info_vram_total"), "8589934592\n");
+        write(&device.join("hwmon/hwmon3/temp1_input"), "45000\n");
+        write(&device.join("hwmon/hwmon3/temp1_label"), "edge\n");
+        write(&device.join("hwmon/hwmon3/temp2_input"), "51500\n");
+        write(&device.join("hwmon/hwmon3/temp3_label"), "mem\n");
+
+        assert!(get_amd_vram(device).is_none());
+
+        let mut temperatures = get_amd_temp(device)
+            .unwrap()
+            .into_iter()
+            .map(|temp| (temp.name, temp.temperature))
+            .collect::<Vec<_>>();
+        temperatures.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            temperatures,
+            vec![("Edge".to_string(), 45.0), ("Temp2".to_string(), 51.5)]
+        );
+
+        write(&device.join("mem_info_vram_used"), "1073741824\n");
+        let vram = get_amd_vram(device).unwrap();
+        assert_eq!((vram.used, vram.total), (1073741824, 8589934592));
+
+        assert!(get_amd_busy_percent(device).is_none());
+    }
+
+    #[test]
+    fn sample_card() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let device = root.join("card0/device");
+
+        write(&device.join("vendor"), "0x1002\n");
+        write(&device.join("mem_info_vram_total"), "17163091968\n");
+        write(&device.join("mem_info_vram_used"), "2147483648\n");
+        write(&device.join("gpu_busy_percent"), "37\n");
+        write(&device.join("hwmon/hwmon2/temp1_input"), "52000\n");
+        write(&device.join("hwmon/hwmon2/temp1_label"), "junction\n");
+
+        assert_eq!(find_vendor_devs(root, AMD_VENDOR_ID), vec![device.clone()]);
+
+        let vram = get_amd_vram(&device).unwrap();
+        assert_eq!((vram.used, vram.total), (2147483648, 17163091968));
+        assert_eq!(get_amd_busy_percent(&device), Some(37.0));
+
+        let temperatures = get_amd_temp(&device)
+            .unwrap()
+            .into_iter()
+            .map(|temp| (temp.name, temp.temperature))
+            .collect::<Vec<_>>();
+        assert_eq!(temperatures, vec![("Junction".to_string(), 52.0)]);
+    }
+}
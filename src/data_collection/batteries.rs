@@ -46,6 +46,10 @@ impl BatteryState {
 
 #[derive(Debug, Clone)]
 pub struct BatteryData {
+    /// The battery's position in the list of batteries found on startup. Unlike
+    /// its position in a harvest, this stays the same if another battery is
+    /// removed.
+    pub id: usize,
     /// Current charge percent.
     pub charge_percent: f64,
     /// Power consumption, in watts.
@@ -69,9 +73,11 @@ impl BatteryData {
 pub fn refresh_batteries(manager: &Manager, batteries: &mut [Battery]) -> Vec<BatteryData> {
     batteries
         .iter_mut()
-        .filter_map(|battery| {
+        .enumerate()
+        .filter_map(|(id, battery)| {
             if manager.refresh(battery).is_ok() {
                 Some(BatteryData {
+                    id,
                     charge_percent: f64::from(battery.state_of_charge().get::<percent>()),
                     power_consumption: f64::from(battery.energy_rate().get::<watt>()),
                     health_percent: f64::from(battery.state_of_health().get::<percent>()),
@@ -1,14 +1,23 @@
 //! Data collection for CPU usage and load average.
 
 pub mod sysinfo;
-pub use self::sysinfo::*;
+pub mod times;
+pub use self::{sysinfo::*, times::*};
+
+use std::collections::BTreeMap;
+
+use hashbrown::{HashMap, HashSet};
 
 pub type LoadAvgHarvest = [f32; 3];
 
-#[derive(Debug, Clone, Copy)]
+/// The type of a CPU entry. Per-core entries are identified by their core ID,
+/// which is stable even if other cores go offline, and per-socket entries by
+/// their physical package ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum CpuDataType {
     Avg,
     Cpu(usize),
+    Socket(usize),
 }
 
 #[derive(Debug, Clone)]
@@ -18,3 +27,133 @@ pub struct CpuData {
 }
 
 pub type CpuHarvest = Vec<CpuData>;
+
+/// The physical package (socket) of each core, by core ID.
+pub type CpuPackages = HashMap<usize, usize>;
+
+/// Reads which physical package each core is on. Cores whose package can't
+/// be read are left out.
+#[cfg(target_os = "linux")]
+pub fn get_cpu_packages() -> CpuPackages {
+    let Ok(entries) = std::fs::read_dir("/sys/devices/system/cpu") else {
+        return CpuPackages::default();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let core = name.to_str()?.strip_prefix("cpu")?.parse().ok()?;
+            let package =
+                std::fs::read_to_string(entry.path().join("topology/physical_package_id")).ok()?;
+
+            Some((core, package.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// Reads which physical package each core is on. This is only supported on
+/// Linux.
+#[cfg(not(target_os = "linux"))]
+pub fn get_cpu_packages() -> CpuPackages {
+    CpuPackages::default()
+}
+
+/// Replaces the per-core entries with one entry per socket, each the average
+/// usage of its cores. This is only done if the cores are on more than one
+/// socket, and cores with no known socket are kept as they are.
+pub fn group_by_socket(harvest: CpuHarvest, packages: &CpuPackages) -> CpuHarvest {
+    if packages.values().collect::<HashSet<_>>().len() < 2 {
+        return harvest;
+    }
+
+    let mut grouped = Vec::with_capacity(harvest.len());
+    let mut socket_usage: BTreeMap<usize, (f64, usize)> = BTreeMap::new();
+
+    for cpu in harvest {
+        match cpu.data_type {
+            CpuDataType::Cpu(core) if packages.contains_key(&core) => {
+                let (total, count) = socket_usage.entry(packages[&core]).or_default();
+                *total += cpu.cpu_usage;
+                *count += 1;
+            }
+            _ => grouped.push(cpu),
+        }
+    }
+
+    // Sockets go after the average, and before any cores without one.
+    let position = grouped
+        .iter()
+        .take_while(|cpu| cpu.data_type == CpuDataType::Avg)
+        .count();
+    grouped.splice(
+        position..position,
+        socket_usage
+            .into_iter()
+            .map(|(socket, (total, count))| CpuData {
+                data_type: CpuDataType::Socket(socket),
+                cpu_usage: total / count as f64,
+            }),
+    );
+
+    grouped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cpu(data_type: CpuDataType, cpu_usage: f64) -> CpuData {
+        CpuData {
+            data_type,
+            cpu_usage,
+        }
+    }
+
+    fn entries(harvest: &CpuHarvest) -> Vec<(CpuDataType, f64)> {
+        harvest
+            .iter()
+            .map(|cpu| (cpu.data_type, cpu.cpu_usage))
+            .collect()
+    }
+
+    #[test]
+    fn cores_grouped_by_package() {
+        let harvest = vec![
+            cpu(CpuDataType::Avg, 40.0),
+            cpu(CpuDataType::Cpu(0), 10.0),
+            cpu(CpuDataType::Cpu(1), 70.0),
+            cpu(CpuDataType::Cpu(2), 30.0),
+            cpu(CpuDataType::Cpu(3), 50.0),
+            cpu(CpuDataType::Cpu(4), 5.0),
+        ];
+        // Cores are interleaved between the sockets, and core 4's socket is
+        // unknown.
+        let packages = CpuPackages::from([(0, 0), (1, 1), (2, 0), (3, 1)]);
+
+        assert_eq!(
+            entries(&group_by_socket(harvest, &packages)),
+            [
+                (CpuDataType::Avg, 40.0),
+                (CpuDataType::Socket(0), 20.0),
+                (CpuDataType::Socket(1), 60.0),
+                (CpuDataType::Cpu(4), 5.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn single_socket_is_left_alone() {
+        let harvest = vec![
+            cpu(CpuDataType::Cpu(0), 10.0),
+            cpu(CpuDataType::Cpu(1), 30.0),
+        ];
+        let packages = CpuPackages::from([(0, 0), (1, 0)]);
+
+        assert_eq!(
+            entries(&group_by_socket(harvest, &packages)),
+            [(CpuDataType::Cpu(0), 10.0), (CpuDataType::Cpu(1), 30.0)]
+        );
+        assert!(entries(&group_by_socket(vec![], &CpuPackages::default())).is_empty());
+    }
+}
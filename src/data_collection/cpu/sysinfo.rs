@@ -14,7 +14,7 @@ pub fn get_cpu_data_list(sys: &System, show_average_cpu: bool) -> CollectionResu
         .iter()
         .enumerate()
         .map(|(i, cpu)| CpuData {
-            data_type: CpuDataType::Cpu(i),
+            data_type: CpuDataType::Cpu(core_id(cpu.name(), i)),
             cpu_usage: cpu.cpu_usage() as f64,
         })
         .collect();
@@ -31,6 +31,22 @@ pub fn get_cpu_data_list(sys: &System, show_average_cpu: bool) -> CollectionResu
     Ok(Vec::from(cpu_deque))
 }
 
+/// Returns the core ID of a CPU. On Linux, this is taken from its name (e.g.
+/// `cpu2`), as offline cores are skipped entirely and would otherwise shift the
+/// position of every core after it. Otherwise, this is just its position.
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+fn core_id(name: &str, index: usize) -> usize {
+    #[cfg(target_os = "linux")]
+    {
+        let id_start = name.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        if let Ok(id) = name[id_start..].parse() {
+            return id;
+        }
+    }
+
+    index
+}
+
 #[cfg(target_family = "unix")]
 pub(crate) fn get_load_avg() -> crate::data_collection::cpu::LoadAvgHarvest {
     // The API for sysinfo apparently wants you to call it like this, rather than
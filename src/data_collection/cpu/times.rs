@@ -0,0 +1,295 @@
+//! How CPU time is split between user, system, iowait, and steal time, across
+//! all cores.
+//!
+//! Only Linux tracks iowait and steal time, so elsewhere just user and system
+//! time are read.
+
+/// How long the CPUs have spent in each state so far, across all cores, in
+/// whatever unit the OS counts in. States that the OS doesn't track are
+/// [`None`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub system: u64,
+    pub iowait: Option<u64>,
+    pub steal: Option<u64>,
+    /// All time, including idle time.
+    pub total: u64,
+}
+
+impl CpuTimes {
+    /// Returns how the time since `prev` was spent, or [`None`] if no time
+    /// was counted in between.
+    pub fn breakdown_since(&self, prev: &CpuTimes) -> Option<CpuBreakdown> {
+        let total = self
+            .total
+            .checked_sub(prev.total)
+            .filter(|total| *total > 0)? as f64;
+        let percent = |now: u64, prev: u64| now.saturating_sub(prev) as f64 / total * 100.0;
+
+        Some(CpuBreakdown {
+            user: percent(self.user, prev.user),
+            system: percent(self.system, prev.system),
+            iowait: self
+                .iowait
+                .zip(prev.iowait)
+                .map(|(now, prev)| percent(now, prev)),
+            steal: self
+                .steal
+                .zip(prev.steal)
+                .map(|(now, prev)| percent(now, prev)),
+        })
+    }
+}
+
+/// A kind of CPU time in a [`CpuBreakdown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuTimeKind {
+    User,
+    System,
+    IoWait,
+    Steal,
+}
+
+impl CpuTimeKind {
+    pub const ALL: [CpuTimeKind; 4] = [
+        CpuTimeKind::User,
+        CpuTimeKind::System,
+        CpuTimeKind::IoWait,
+        CpuTimeKind::Steal,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CpuTimeKind::User => "User",
+            CpuTimeKind::System => "System",
+            CpuTimeKind::IoWait => "IOWait",
+            CpuTimeKind::Steal => "Steal",
+        }
+    }
+}
+
+/// How much of the CPUs' time went to each state between two reads, as
+/// percentages.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CpuBreakdown {
+    pub user: f64,
+    pub system: f64,
+    pub iowait: Option<f64>,
+    pub steal: Option<f64>,
+}
+
+impl CpuBreakdown {
+    /// The percentage of time of the given kind, if it is tracked.
+    pub fn get(&self, kind: CpuTimeKind) -> Option<f64> {
+        match kind {
+            CpuTimeKind::User => Some(self.user),
+            CpuTimeKind::System => Some(self.system),
+            CpuTimeKind::IoWait => self.iowait,
+            CpuTimeKind::Steal => self.steal,
+        }
+    }
+}
+
+/// Reads the CPU times from `/proc/stat`.
+#[cfg(target_os = "linux")]
+pub fn get_cpu_times() -> Option<CpuTimes> {
+    parse_proc_stat(&std::fs::read_to_string("/proc/stat").ok()?)
+}
+
+/// Parses the `cpu` line of `/proc/stat`, which holds the user, nice, system,
+/// idle, iowait, irq, softirq, and steal times of all cores. These are followed
+/// by guest times, which are already counted as user time. Older kernels leave
+/// out some of the later fields.
+#[cfg(target_os = "linux")]
+fn parse_proc_stat(stat: &str) -> Option<CpuTimes> {
+    let line = stat.lines().find(|line| line.starts_with("cpu "))?;
+    let fields = line
+        .split_whitespace()
+        .skip(1)
+        .take(8)
+        .map(|field| field.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    if fields.len() < 4 {
+        return None;
+    }
+
+    let field = |index: usize| fields.get(index).copied();
+
+    Some(CpuTimes {
+        user: fields[0] + fields[1],
+        system: fields[2] + field(5).unwrap_or(0) + field(6).unwrap_or(0),
+        iowait: field(4),
+        steal: field(7),
+        total: fields.iter().sum(),
+    })
+}
+
+/// Reads the CPU times from the host's load info.
+#[cfg(target_os = "macos")]
+pub fn get_cpu_times() -> Option<CpuTimes> {
+    // SAFETY: A zeroed `host_cpu_load_info` is valid, as it only holds integers.
+    let mut info: libc::host_cpu_load_info = unsafe { std::mem::zeroed() };
+    let mut count = libc::HOST_CPU_LOAD_INFO_COUNT;
+
+    // SAFETY: `info` and `count` outlive the call, and `count` is the size of
+    // `info` in integers, as expected for `HOST_CPU_LOAD_INFO`.
+    let result = unsafe {
+        #[allow(deprecated)]
+        let host = libc::mach_host_self();
+        libc::host_statistics(
+            host,
+            libc::HOST_CPU_LOAD_INFO,
+            (&mut info as *mut libc::host_cpu_load_info).cast(),
+            &mut count,
+        )
+    };
+    if result != libc::KERN_SUCCESS {
+        return None;
+    }
+
+    let ticks = info.cpu_ticks.map(u64::from);
+    let tick = |state: libc::c_int| ticks[state as usize];
+
+    Some(CpuTimes {
+        user: tick(libc::CPU_STATE_USER) + tick(libc::CPU_STATE_NICE),
+        system: tick(libc::CPU_STATE_SYSTEM),
+        iowait: None,
+        steal: None,
+        total: ticks.iter().sum(),
+    })
+}
+
+/// Reads the CPU times from the `kern.cp_time` sysctl.
+#[cfg(target_os = "freebsd")]
+pub fn get_cpu_times() -> Option<CpuTimes> {
+    let mut ticks = [0 as libc::c_long; libc::CPUSTATES as usize];
+    let mut size = std::mem::size_of_val(&ticks);
+
+    // SAFETY: `ticks` and `size` outlive the call, and `size` is the size of
+    // `ticks` in bytes.
+    let result = unsafe {
+        libc::sysctlbyname(
+            c"kern.cp_time".as_ptr(),
+            ticks.as_mut_ptr().cast(),
+            &mut size,
+            std::ptr::null(),
+            0,
+        )
+    };
+    if result != 0 {
+        return None;
+    }
+
+    let ticks = ticks.map(|tick| tick.max(0) as u64);
+    let tick = |state: libc::c_int| ticks[state as usize];
+
+    Some(CpuTimes {
+        user: tick(libc::CP_USER) + tick(libc::CP_NICE),
+        system: tick(libc::CP_SYS) + tick(libc::CP_INTR),
+        iowait: None,
+        steal: None,
+        total: ticks.iter().sum(),
+    })
+}
+
+/// Reads the CPU times from `GetSystemTimes`.
+#[cfg(target_os = "windows")]
+pub fn get_cpu_times() -> Option<CpuTimes> {
+    use windows::Win32::{Foundation::FILETIME, System::Threading::GetSystemTimes};
+
+    let mut idle = FILETIME::default();
+    let mut kernel = FILETIME::default();
+    let mut user = FILETIME::default();
+
+    // SAFETY: Each pointer is to a `FILETIME` that outlives the call.
+    unsafe {
+        GetSystemTimes(
+            Some(&mut idle as *mut FILETIME),
+            Some(&mut kernel as *mut FILETIME),
+            Some(&mut user as *mut FILETIME),
+        )
+    }
+    .ok()?;
+
+    let ticks =
+        |time: FILETIME| (u64::from(time.dwHighDateTime) << 32) | u64::from(time.dwLowDateTime);
+    let (idle, kernel, user) = (ticks(idle), ticks(kernel), ticks(user));
+
+    // Kernel time includes idle time.
+    Some(CpuTimes {
+        user,
+        system: kernel.saturating_sub(idle),
+        iowait: None,
+        steal: None,
+        total: kernel + user,
+    })
+}
+
+/// Reading CPU times isn't supported on this platform.
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "windows"
+)))]
+pub fn get_cpu_times() -> Option<CpuTimes> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn proc_stat_fields() {
+        let stat = "cpu  100 20 50 800 10 3 2 15 7 0\ncpu0 50 10 25 400 5 1 1 7 3 0\nintr 1 2 3\n";
+        assert_eq!(
+            parse_proc_stat(stat),
+            Some(CpuTimes {
+                user: 120,
+                system: 55,
+                iowait: Some(10),
+                steal: Some(15),
+                total: 1000,
+            })
+        );
+
+        // Older kernels without steal time.
+        assert_eq!(
+            parse_proc_stat("cpu  100 20 50 800 10 3 2\n").map(|times| times.steal),
+            Some(None)
+        );
+        assert_eq!(parse_proc_stat("cpu  100 20 50\n"), None);
+        assert_eq!(parse_proc_stat("cpu0 100 20 50 800\n"), None);
+    }
+
+    #[test]
+    fn breakdown_between_reads() {
+        let prev = CpuTimes {
+            user: 100,
+            system: 50,
+            iowait: Some(10),
+            steal: None,
+            total: 1000,
+        };
+        let now = CpuTimes {
+            user: 150,
+            system: 60,
+            iowait: Some(30),
+            steal: None,
+            total: 1200,
+        };
+
+        let breakdown = now.breakdown_since(&prev).unwrap();
+        assert_eq!(breakdown.user, 25.0);
+        assert_eq!(breakdown.system, 5.0);
+        assert_eq!(breakdown.get(CpuTimeKind::IoWait), Some(10.0));
+        assert_eq!(breakdown.get(CpuTimeKind::Steal), None);
+
+        // No time passed.
+        assert_eq!(prev.breakdown_since(&prev), None);
+    }
+}
@@ -8,6 +8,8 @@ cfg_if! {
         #[cfg(feature = "zfs")]
         mod zfs_io_counters;
         #[cfg(feature = "zfs")]
+        mod zpool;
+        #[cfg(feature = "zfs")]
         pub use io_counters::IoCounters;
         pub(crate) use self::freebsd::*;
     } else if #[cfg(target_os = "windows")] {
@@ -16,7 +18,11 @@ cfg_if! {
     } else if #[cfg(target_os = "linux")] {
         mod unix;
         #[cfg(feature = "zfs")]
+        mod zfs;
+        #[cfg(feature = "zfs")]
         mod zfs_io_counters;
+        #[cfg(feature = "zfs")]
+        mod zpool;
         pub(crate) use self::unix::*;
     } else if #[cfg(target_os = "macos")] {
         mod unix;
@@ -27,6 +33,11 @@ cfg_if! {
     }
 }
 
+#[cfg(any(target_os = "macos", test))]
+mod io_pairing;
+#[cfg(target_os = "macos")]
+pub(crate) use io_pairing::{apfs_list, IoPairing};
+
 use cfg_if::cfg_if;
 use hashbrown::HashMap;
 
@@ -37,6 +48,9 @@ pub struct DiskHarvest {
     pub name: String,
     pub mount_point: String,
 
+    /// The filesystem type (e.g. `ext4` or `NTFS`), if known.
+    pub file_system: Option<String>,
+
     /// Windows also contains an additional volume name field.
     #[cfg(target_os = "windows")]
     pub volume_name: Option<String>,
@@ -45,6 +59,19 @@ pub struct DiskHarvest {
     pub free_space: Option<u64>,
     pub used_space: Option<u64>,
     pub total_space: Option<u64>,
+
+    /// Details only ZFS pools have, if this is a pool.
+    pub pool: Option<PoolDetails>,
+}
+
+/// Details about a ZFS pool.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PoolDetails {
+    /// The pool's health, e.g. `ONLINE` or `DEGRADED`.
+    pub health: Option<String>,
+
+    /// How fragmented the pool's free space is, as a percentage.
+    pub fragmentation: Option<u8>,
 }
 
 #[derive(Clone, Debug)]
@@ -99,9 +126,14 @@ cfg_if! {
 ///    in a filter where `is_list_ignored` is `true`? If so, we always deny this
 ///    entry.
 /// 3. Anything else is allowed.
+///
+/// Filters with an empty list are treated as if they were not set.
 pub fn keep_disk_entry(
     disk_name: &str, mount_point: &str, disk_filter: &Option<Filter>, mount_filter: &Option<Filter>,
 ) -> bool {
+    let disk_filter = disk_filter.as_ref().filter(|f| !f.is_empty());
+    let mount_filter = mount_filter.as_ref().filter(|f| !f.is_empty());
+
     match (disk_filter, mount_filter) {
         (Some(d), Some(m)) => match (d.ignore_matches(), m.ignore_matches()) {
             (true, true) => !(d.has_match(disk_name) || m.has_match(mount_point)),
@@ -127,11 +159,42 @@ pub fn keep_disk_entry(
     }
 }
 
+/// Whether to keep the current disk entry given the filesystem filter and the
+/// disk's filesystem type. This is checked in addition to [`keep_disk_entry`],
+/// so a disk must pass both to be kept.
+///
+/// Disks with an unknown filesystem type are only kept if the filter ignores
+/// matches, since they can't match an allow list.
+pub fn keep_file_system(file_system: Option<&str>, fs_filter: &Option<Filter>) -> bool {
+    match fs_filter.as_ref().filter(|f| !f.is_empty()) {
+        Some(filter) => match file_system {
+            Some(file_system) => filter.should_keep(file_system),
+            None => filter.ignore_matches(),
+        },
+        None => true,
+    }
+}
+
+/// Formats disks for listing, with each line containing the disk's name and
+/// mount point.
+pub fn disk_listing(disks: &[DiskHarvest]) -> Vec<String> {
+    let name_width = disks
+        .iter()
+        .map(|disk| disk.name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    disks
+        .iter()
+        .map(|disk| format!("{:name_width$}  {}", disk.name, disk.mount_point))
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use regex::Regex;
 
-    use super::keep_disk_entry;
+    use super::{disk_listing, keep_disk_entry, keep_file_system, DiskHarvest};
     use crate::app::filter::Filter;
 
     fn run_filter(disk_filter: &Option<Filter>, mount_filter: &Option<Filter>) -> Vec<usize> {
@@ -177,4 +240,70 @@ mod test {
         assert_eq!(run_filter(&disk_ignore, &mount_keep), vec![0, 3, 4]);
         assert_eq!(run_filter(&disk_keep, &mount_keep), vec![0, 1, 2, 4]);
     }
+
+    #[test]
+    fn test_empty_filters_keep_everything() {
+        let empty_ignore = Some(Filter::new(true, vec![]));
+        let empty_keep = Some(Filter::new(false, vec![]));
+        let mount_ignore = Some(Filter::new(true, vec![Regex::new("boot").unwrap()]));
+        let mount_keep = Some(Filter::new(false, vec![Regex::new("boot").unwrap()]));
+
+        assert_eq!(run_filter(&empty_ignore, &None), vec![0, 1, 2, 3, 4]);
+        assert_eq!(run_filter(&empty_keep, &None), vec![0, 1, 2, 3, 4]);
+        assert_eq!(run_filter(&None, &empty_keep), vec![0, 1, 2, 3, 4]);
+        assert_eq!(run_filter(&empty_keep, &empty_keep), vec![0, 1, 2, 3, 4]);
+
+        // An empty filter shouldn't change what the other filter does.
+        assert_eq!(run_filter(&empty_keep, &mount_ignore), vec![1, 2, 3]);
+        assert_eq!(run_filter(&empty_ignore, &mount_keep), vec![0, 4]);
+    }
+
+    #[test]
+    fn test_file_system_filter() {
+        let targets = [Some("ext4"), Some("tmpfs"), Some("overlay"), None];
+        let run_filter = |fs_filter: &Option<Filter>| -> Vec<usize> {
+            targets
+                .into_iter()
+                .enumerate()
+                .filter_map(|(itx, file_system)| {
+                    keep_file_system(file_system, fs_filter).then_some(itx)
+                })
+                .collect()
+        };
+
+        let fs_ignore = Some(Filter::new(
+            true,
+            vec![
+                Regex::new("^tmpfs$").unwrap(),
+                Regex::new("^overlay$").unwrap(),
+            ],
+        ));
+        let fs_keep = Some(Filter::new(false, vec![Regex::new("^ext4$").unwrap()]));
+
+        assert_eq!(run_filter(&None), vec![0, 1, 2, 3]);
+        assert_eq!(
+            run_filter(&Some(Filter::new(true, vec![]))),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(run_filter(&fs_ignore), vec![0, 3]);
+        assert_eq!(run_filter(&fs_keep), vec![0]);
+    }
+
+    #[test]
+    fn test_disk_listing() {
+        let harvest = [("/dev/nvme0n1p2", "/"), ("/dev/sda1", "/mnt/test")]
+            .into_iter()
+            .map(|(name, mount_point)| DiskHarvest {
+                name: name.to_string(),
+                mount_point: mount_point.to_string(),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            disk_listing(&harvest),
+            vec!["/dev/nvme0n1p2  /", "/dev/sda1       /mnt/test"]
+        );
+        assert!(disk_listing(&[]).is_empty());
+    }
 }
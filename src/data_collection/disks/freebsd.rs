@@ -5,7 +5,7 @@ use std::io;
 use hashbrown::HashMap;
 use serde::Deserialize;
 
-use super::{keep_disk_entry, DiskHarvest, IoHarvest};
+use super::{keep_disk_entry, keep_file_system, DiskHarvest, IoHarvest};
 use crate::data_collection::{
     deserialize_xo, disks::IoData, error::CollectionResult, DataCollector,
 };
@@ -24,6 +24,9 @@ struct FileSystem {
     used_blocks: u64,
     available_blocks: u64,
     mounted_on: String,
+    /// The file system type, which `df` only gives with `-T`.
+    #[serde(rename = "type", default)]
+    file_system: Option<String>,
 }
 
 pub fn get_io_usage() -> CollectionResult<IoHarvest> {
@@ -60,18 +63,23 @@ pub fn get_io_usage() -> CollectionResult<IoHarvest> {
 pub fn get_disk_usage(collector: &DataCollector) -> CollectionResult<Vec<DiskHarvest>> {
     let disk_filter = &collector.filters.disk_filter;
     let mount_filter = &collector.filters.mount_filter;
-    let vec_disks: Vec<DiskHarvest> = get_disk_info().map(|storage_system_information| {
+    let fs_filter = &collector.filters.fs_filter;
+    let mut vec_disks: Vec<DiskHarvest> = get_disk_info().map(|storage_system_information| {
         storage_system_information
             .filesystem
             .into_iter()
             .filter_map(|disk| {
-                if keep_disk_entry(&disk.name, &disk.mounted_on, disk_filter, mount_filter) {
+                if keep_disk_entry(&disk.name, &disk.mounted_on, disk_filter, mount_filter)
+                    && keep_file_system(disk.file_system.as_deref(), fs_filter)
+                {
                     Some(DiskHarvest {
                         free_space: Some(disk.available_blocks * 1024),
                         used_space: Some(disk.used_blocks * 1024),
                         total_space: Some(disk.total_blocks * 1024),
                         mount_point: disk.mounted_on,
+                        file_system: disk.file_system,
                         name: disk.name,
+                        pool: None,
                     })
                 } else {
                     None
@@ -80,13 +88,20 @@ pub fn get_disk_usage(collector: &DataCollector) -> CollectionResult<Vec<DiskHar
             .collect()
     })?;
 
+    #[cfg(feature = "zfs")]
+    vec_disks.extend(super::zpool::get_zpool_usage(
+        disk_filter,
+        mount_filter,
+        fs_filter,
+    ));
+
     Ok(vec_disks)
 }
 
 fn get_disk_info() -> io::Result<StorageSystemInformation> {
     // TODO: Ideally we don't have to shell out to a new program.
     let output = std::process::Command::new("df")
-        .args(["--libxo", "json", "-k", "-t", "ufs,msdosfs,zfs"])
+        .args(["--libxo", "json", "-k", "-T", "-t", "ufs,msdosfs,zfs"])
         .output()?;
     deserialize_xo("storage-system-information", &output.stdout)
 }
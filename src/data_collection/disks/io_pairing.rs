@@ -0,0 +1,401 @@
+//! Pairing macOS disks with the I/O of the physical disks they are on.
+//!
+//! macOS only counts I/O for whole physical disks (e.g. `disk0`), but mounts
+//! are of slices of them (`disk0s1`), or of APFS volumes (`disk3s1s1`) whose
+//! container (`disk3`) is synthesized from slices of one or more physical
+//! disks (`disk0s2`). Containers are mapped to their physical disks through
+//! `diskutil apfs list`, which is only re-run when a disk appears that can't
+//! otherwise be paired, and at most once every [`RELIST_INTERVAL`].
+
+use std::time::{Duration, Instant};
+
+use hashbrown::{HashMap, HashSet};
+
+use super::{DiskHarvest, IoData, IoHarvest};
+
+/// The least time between runs of `diskutil`, as it blocks the collection
+/// thread.
+const RELIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Returns the whole disk that a BSD device (e.g. `/dev/disk3s1s1`) is part
+/// of, or [`None`] if it isn't a disk device, like a network share.
+fn whole_disk(device: &str) -> Option<&str> {
+    let name = device.strip_prefix("/dev/").unwrap_or(device);
+    let number = name.strip_prefix("disk")?;
+    let digits = number
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(number.len());
+
+    if digits == 0 || !(number[digits..].is_empty() || number[digits..].starts_with('s')) {
+        return None;
+    }
+
+    Some(&name[.."disk".len() + digits])
+}
+
+/// Parses the output of `diskutil apfs list`, returning the whole disks each
+/// APFS container is made from, keyed by the container's disk.
+fn parse_apfs_list(output: &str) -> HashMap<String, Vec<String>> {
+    const CONTAINER: &str = "APFS Container Reference:";
+    const PHYSICAL_STORE: &str = "APFS Physical Store Disk:";
+
+    let mut containers: HashMap<String, Vec<String>> = HashMap::new();
+    let mut container = None;
+
+    for line in output.lines() {
+        let line = line.trim_start_matches(['|', ' ']);
+
+        if let Some(reference) = line.strip_prefix(CONTAINER) {
+            container = Some(reference.trim().to_string());
+        } else if let Some(store) = line.strip_prefix(PHYSICAL_STORE) {
+            let (Some(container), Some(store)) = (&container, whole_disk(store.trim())) else {
+                continue;
+            };
+
+            let stores = containers.entry(container.clone()).or_default();
+            if !stores.iter().any(|s| s == store) {
+                stores.push(store.to_string());
+            }
+        }
+    }
+
+    containers
+}
+
+/// Pairs disks with the physical disks that their I/O is counted for.
+#[derive(Debug, Default)]
+pub(crate) struct IoPairing {
+    /// The whole disks each APFS container is made from.
+    containers: HashMap<String, Vec<String>>,
+
+    /// Whole disks without any I/O of their own that the containers have been
+    /// checked for, so `diskutil` isn't re-run for them.
+    checked: HashSet<String>,
+
+    /// When `diskutil` was last run.
+    last_listed: Option<Instant>,
+}
+
+impl IoPairing {
+    /// Returns the whole disks with I/O counts that `device` is on. This is
+    /// empty if none are known.
+    fn io_disks<'a>(&'a self, device: &'a str, io: &IoHarvest) -> Vec<&'a str> {
+        let Some(disk) = whole_disk(device) else {
+            return vec![];
+        };
+
+        if io.contains_key(disk) {
+            return vec![disk];
+        }
+
+        self.containers
+            .get(disk)
+            .map(|stores| {
+                stores
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|store| io.contains_key(*store))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Adds an entry to `io` for each disk, named like the disk's device, with
+    /// the I/O of the physical disks it is on. Disks that can't be paired are
+    /// left out, so they are shown without any I/O.
+    ///
+    /// `apfs_list` returns the output of `diskutil apfs list`, and is only
+    /// called if there is a disk that hasn't been checked against it yet, and
+    /// it hasn't been called in the last [`RELIST_INTERVAL`] before `now`.
+    pub(crate) fn pair(
+        &mut self, disks: &[DiskHarvest], io: &mut IoHarvest, now: Instant,
+        apfs_list: impl FnOnce() -> Option<String>,
+    ) {
+        let unchecked = disks
+            .iter()
+            .filter_map(|disk| whole_disk(&disk.name))
+            .any(|disk| {
+                !io.contains_key(disk)
+                    && !self.containers.contains_key(disk)
+                    && !self.checked.contains(disk)
+            });
+        let can_relist = self
+            .last_listed
+            .map_or(true, |last| now.duration_since(last) >= RELIST_INTERVAL);
+
+        if unchecked && can_relist {
+            self.last_listed = Some(now);
+            if let Some(output) = apfs_list() {
+                self.containers = parse_apfs_list(&output);
+            }
+
+            self.checked.extend(
+                disks
+                    .iter()
+                    .filter_map(|disk| whole_disk(&disk.name))
+                    .filter(|disk| !io.contains_key(*disk))
+                    .map(str::to_string),
+            );
+        }
+
+        let mut paired = vec![];
+        for disk in disks {
+            let Some(name) = disk.name.rsplit('/').next() else {
+                continue;
+            };
+            if io.contains_key(name) {
+                continue;
+            }
+
+            let totals = self
+                .io_disks(&disk.name, io)
+                .into_iter()
+                .filter_map(|store| io.get(store)?.as_ref())
+                .fold(None, |total: Option<IoData>, data| {
+                    let total = total.unwrap_or(IoData {
+                        read_bytes: 0,
+                        write_bytes: 0,
+                    });
+
+                    Some(IoData {
+                        read_bytes: total.read_bytes + data.read_bytes,
+                        write_bytes: total.write_bytes + data.write_bytes,
+                    })
+                });
+
+            if let Some(totals) = totals {
+                paired.push((name.to_string(), totals));
+            }
+        }
+
+        for (name, totals) in paired {
+            io.insert(name, Some(totals));
+        }
+    }
+}
+
+/// Runs `diskutil apfs list`, returning its output if it succeeded.
+#[cfg(target_os = "macos")]
+pub(crate) fn apfs_list() -> Option<String> {
+    let output = std::process::Command::new("diskutil")
+        .args(["apfs", "list"])
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::Cell, time::Instant};
+
+    use super::*;
+
+    /// An internal disk with the usual system and data volumes, an external
+    /// APFS drive, and a Fusion drive made from two physical disks.
+    const APFS_LIST: &str = "\
+APFS Containers (3 found)
+|
++-- Container disk3 2D6B7E4A-9B1C-4E5F-8A2D-0F1E2D3C4B5A
+|   ====================================================
+|   APFS Container Reference:     disk3
+|   Size (Capacity Ceiling):      494384795648 B (494.4 GB)
+|   Capacity In Use By Volumes:   217543467008 B (217.5 GB) (44.0% used)
+|   |
+|   +-< Physical Store disk0s2 6E3F1A2B-3C4D-5E6F-7A8B-9C0D1E2F3A4B
+|   |   -----------------------------------------------------------
+|   |   APFS Physical Store Disk:   disk0s2
+|   |   Size:                       494384795648 B (494.4 GB)
+|   |
+|   +-> Volume disk3s1 1A2B3C4D-5E6F-7A8B-9C0D-1E2F3A4B5C6D
+|   |   ---------------------------------------------------
+|   |   APFS Volume Disk (Role):   disk3s1 (System)
+|   |   Name:                      Macintosh HD (Case-insensitive)
+|   |   Mount Point:               /
+|   |
+|   +-> Volume disk3s5 2B3C4D5E-6F7A-8B9C-0D1E-2F3A4B5C6D7E
+|       ---------------------------------------------------
+|       APFS Volume Disk (Role):   disk3s5 (Data)
+|       Name:                      Macintosh HD - Data (Case-insensitive)
+|       Mount Point:               /System/Volumes/Data
+|
++-- Container disk5 3C4D5E6F-7A8B-9C0D-1E2F-3A4B5C6D7E8F
+|   ====================================================
+|   APFS Container Reference:     disk5
+|   |
+|   +-< Physical Store disk4s2 4D5E6F7A-8B9C-0D1E-2F3A-4B5C6D7E8F9A
+|   |   -----------------------------------------------------------
+|   |   APFS Physical Store Disk:   disk4s2
+|   |
+|   +-> Volume disk5s1 5E6F7A8B-9C0D-1E2F-3A4B-5C6D7E8F9A0B
+|       ---------------------------------------------------
+|       APFS Volume Disk (Role):   disk5s1 (No specific role)
+|       Name:                      Backup (Case-insensitive)
+|       Mount Point:               /Volumes/Backup
+|
++-- Container disk7 6F7A8B9C-0D1E-2F3A-4B5C-6D7E8F9A0B1C
+    ====================================================
+    APFS Container Reference:     disk7
+    |
+    +-< Physical Store disk1s2 7A8B9C0D-1E2F-3A4B-5C6D-7E8F9A0B1C2D
+    |   -----------------------------------------------------------
+    |   APFS Physical Store Disk:   disk1s2
+    |
+    +-< Physical Store disk2s2 8B9C0D1E-2F3A-4B5C-6D7E-8F9A0B1C2D3E
+    |   -----------------------------------------------------------
+    |   APFS Physical Store Disk:   disk2s2
+    |
+    +-> Volume disk7s1 9C0D1E2F-3A4B-5C6D-7E8F-9A0B1C2D3E4F
+        ---------------------------------------------------
+        APFS Volume Disk (Role):   disk7s1 (No specific role)
+        Name:                      Fusion (Case-insensitive)
+        Mount Point:               /Volumes/Fusion
+";
+
+    fn disk(name: &str, mount_point: &str) -> DiskHarvest {
+        DiskHarvest {
+            name: name.to_string(),
+            mount_point: mount_point.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn io(disks: &[(&str, u64)]) -> IoHarvest {
+        disks
+            .iter()
+            .map(|(name, bytes)| {
+                (
+                    name.to_string(),
+                    Some(IoData {
+                        read_bytes: *bytes,
+                        write_bytes: *bytes * 2,
+                    }),
+                )
+            })
+            .collect()
+    }
+
+    fn read_bytes(io: &IoHarvest, name: &str) -> Option<u64> {
+        Some(io.get(name)?.as_ref()?.read_bytes)
+    }
+
+    #[test]
+    fn whole_disks() {
+        assert_eq!(whole_disk("/dev/disk0"), Some("disk0"));
+        assert_eq!(whole_disk("/dev/disk3s1s1"), Some("disk3"));
+        assert_eq!(whole_disk("disk12s2"), Some("disk12"));
+
+        assert_eq!(whole_disk("//user@nas._smb._tcp.local/share"), None);
+        assert_eq!(whole_disk("map auto_home"), None);
+        assert_eq!(whole_disk("devfs"), None);
+        assert_eq!(whole_disk("/dev/disk"), None);
+        assert_eq!(whole_disk("/dev/diskimage"), None);
+    }
+
+    #[test]
+    fn apfs_containers() {
+        let containers = parse_apfs_list(APFS_LIST);
+
+        assert_eq!(containers.len(), 3);
+        assert_eq!(containers["disk3"], ["disk0"]);
+        assert_eq!(containers["disk5"], ["disk4"]);
+        assert_eq!(containers["disk7"], ["disk1", "disk2"]);
+
+        assert!(parse_apfs_list("No APFS Containers found").is_empty());
+    }
+
+    #[test]
+    fn pairing() {
+        let disks = [
+            // The sealed system snapshot and the data volume, on the internal disk.
+            disk("/dev/disk3s1s1", "/"),
+            disk("/dev/disk3s5", "/System/Volumes/Data"),
+            // An external USB drive formatted as exFAT, which has its own I/O.
+            disk("/dev/disk6s1", "/Volumes/USB"),
+            // An external APFS drive.
+            disk("/dev/disk5s1", "/Volumes/Backup"),
+            // A Fusion drive.
+            disk("/dev/disk7s1", "/Volumes/Fusion"),
+            // A disk image, which doesn't have any I/O counted.
+            disk("/dev/disk8s1", "/Volumes/Installer"),
+            // Network shares and automounts.
+            disk("//user@nas._smb._tcp.local/share", "/Volumes/share"),
+            disk("map auto_home", "/System/Volumes/Data/home"),
+        ];
+        let mut io = io(&[
+            ("disk0", 10),
+            ("disk1", 1),
+            ("disk2", 2),
+            ("disk4", 40),
+            ("disk6", 60),
+        ]);
+
+        let mut pairing = IoPairing::default();
+        pairing.pair(&disks, &mut io, Instant::now(), || {
+            Some(APFS_LIST.to_string())
+        });
+
+        assert_eq!(read_bytes(&io, "disk3s1s1"), Some(10));
+        assert_eq!(read_bytes(&io, "disk3s5"), Some(10));
+        assert_eq!(read_bytes(&io, "disk6s1"), Some(60));
+        assert_eq!(read_bytes(&io, "disk5s1"), Some(40));
+        assert_eq!(read_bytes(&io, "disk7s1"), Some(3));
+        assert_eq!(
+            io.get("disk7s1")
+                .cloned()
+                .flatten()
+                .map(|io| io.write_bytes),
+            Some(6)
+        );
+
+        // These are shown without any I/O.
+        assert!(!io.contains_key("disk8s1"));
+        assert!(!io.contains_key("share"));
+        assert!(!io.contains_key("map auto_home"));
+    }
+
+    #[test]
+    fn diskutil_is_only_rerun_for_new_disks_once_in_a_while() {
+        let runs = Cell::new(0);
+        let apfs_list = || {
+            runs.set(runs.get() + 1);
+            Some(APFS_LIST.to_string())
+        };
+        let io_harvest = || io(&[("disk0", 10), ("disk6", 60)]);
+        let start = Instant::now();
+
+        let mut pairing = IoPairing::default();
+        let disks = [
+            disk("/dev/disk3s1s1", "/"),
+            disk("/dev/disk6s1", "/Volumes/USB"),
+        ];
+        pairing.pair(&disks, &mut io_harvest(), start, apfs_list);
+        assert_eq!(runs.get(), 1);
+
+        // Known containers and disks with their own I/O don't need another run...
+        pairing.pair(&disks, &mut io_harvest(), start, apfs_list);
+        assert_eq!(runs.get(), 1);
+
+        // ...and new disks wait until enough time has passed since the last run...
+        let disks = [disk("/dev/disk8s1", "/Volumes/Installer")];
+        pairing.pair(&disks, &mut io_harvest(), start, apfs_list);
+        assert_eq!(runs.get(), 1);
+        let later = start + RELIST_INTERVAL;
+        pairing.pair(&disks, &mut io_harvest(), later, apfs_list);
+        assert_eq!(runs.get(), 2);
+
+        // ...after which disks that couldn't be paired aren't checked again.
+        let much_later = later + RELIST_INTERVAL;
+        pairing.pair(&disks, &mut io_harvest(), much_later, apfs_list);
+        assert_eq!(runs.get(), 2);
+
+        // If diskutil fails, disks are still shown, just without I/O.
+        let mut pairing = IoPairing::default();
+        let mut io = io_harvest();
+        pairing.pair(&[disk("/dev/disk3s5", "/")], &mut io, start, || None);
+        assert!(!io.contains_key("disk3s5"));
+    }
+}
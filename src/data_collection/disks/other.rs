@@ -1,12 +1,13 @@
 //! Fallback disk info using sysinfo.
 
-use super::{keep_disk_entry, DiskHarvest};
+use super::{keep_disk_entry, keep_file_system, DiskHarvest};
 use crate::data_collection::DataCollector;
 
 pub(crate) fn get_disk_usage(collector: &DataCollector) -> anyhow::Result<Vec<DiskHarvest>> {
     let disks = &collector.sys.disks;
     let disk_filter = &collector.filters.disk_filter;
     let mount_filter = &collector.filters.mount_filter;
+    let fs_filter = &collector.filters.fs_filter;
 
     Ok(disks
         .iter()
@@ -30,7 +31,15 @@ pub(crate) fn get_disk_usage(collector: &DataCollector) -> anyhow::Result<Vec<Di
                 .into_string()
                 .unwrap_or_else(|_| "Mount Unavailable".to_string());
 
-            if keep_disk_entry(&name, &mount_point, disk_filter, mount_filter) {
+            let file_system = disk
+                .file_system()
+                .to_str()
+                .filter(|file_system| !file_system.is_empty())
+                .map(str::to_string);
+
+            if keep_disk_entry(&name, &mount_point, disk_filter, mount_filter)
+                && keep_file_system(file_system.as_deref(), fs_filter)
+            {
                 let free_space = disk.available_space();
                 let total_space = disk.total_space();
                 let used_space = total_space - free_space;
@@ -38,9 +47,11 @@ pub(crate) fn get_disk_usage(collector: &DataCollector) -> anyhow::Result<Vec<Di
                 Some(DiskHarvest {
                     name,
                     mount_point,
+                    file_system,
                     free_space: Some(free_space),
                     used_space: Some(used_space),
                     total_space: Some(total_space),
+                    pool: None,
                 })
             } else {
                 None
@@ -24,18 +24,20 @@ cfg_if::cfg_if! {
 use file_systems::*;
 use usage::*;
 
-use super::{keep_disk_entry, DiskHarvest};
+use super::{keep_disk_entry, keep_file_system, DiskHarvest};
 use crate::data_collection::DataCollector;
 
 /// Returns the disk usage of the mounted (and for now, physical) disks.
 pub fn get_disk_usage(collector: &DataCollector) -> anyhow::Result<Vec<DiskHarvest>> {
     let disk_filter = &collector.filters.disk_filter;
     let mount_filter = &collector.filters.mount_filter;
+    let fs_filter = &collector.filters.fs_filter;
     let mut vec_disks: Vec<DiskHarvest> = Vec::new();
 
     for partition in physical_partitions()? {
         let name = partition.get_device_name();
         let mount_point = partition.mount_point().to_string_lossy().to_string();
+        let file_system = partition.fs_type().as_str().to_string();
 
         // Precedence ordering in the case where name and mount filters disagree,
         // "allow" takes precedence over "deny".
@@ -49,7 +51,9 @@ pub fn get_disk_usage(collector: &DataCollector) -> anyhow::Result<Vec<DiskHarve
         //    entry.
         // 3. Anything else is allowed.
 
-        if keep_disk_entry(&name, &mount_point, disk_filter, mount_filter) {
+        if keep_disk_entry(&name, &mount_point, disk_filter, mount_filter)
+            && keep_file_system(Some(&file_system), fs_filter)
+        {
             // The usage line can fail in some cases (for example, if you use Void Linux +
             // LUKS, see https://github.com/ClementTsang/bottom/issues/419 for details).
             if let Ok(usage) = partition.usage() {
@@ -60,7 +64,9 @@ pub fn get_disk_usage(collector: &DataCollector) -> anyhow::Result<Vec<DiskHarve
                     used_space: Some(total - usage.available()),
                     total_space: Some(total),
                     mount_point,
+                    file_system: Some(file_system),
                     name,
+                    pool: None,
                 });
             } else {
                 vec_disks.push(DiskHarvest {
@@ -68,11 +74,20 @@ pub fn get_disk_usage(collector: &DataCollector) -> anyhow::Result<Vec<DiskHarve
                     used_space: None,
                     total_space: None,
                     mount_point,
+                    file_system: Some(file_system),
                     name,
+                    pool: None,
                 });
             }
         }
     }
 
+    #[cfg(all(target_os = "linux", feature = "zfs"))]
+    vec_disks.extend(super::zpool::get_zpool_usage(
+        disk_filter,
+        mount_filter,
+        fs_filter,
+    ));
+
     Ok(vec_disks)
 }
@@ -64,12 +64,10 @@ impl FromStr for IoCounters {
     }
 }
 
-/// Returns an iterator of disk I/O stats. Pulls data from `/proc/diskstats`.
-pub fn io_stats() -> anyhow::Result<Vec<IoCounters>> {
-    const PROC_DISKSTATS: &str = "/proc/diskstats";
-
+/// Parses disk I/O stats in the format of `/proc/diskstats`, skipping any
+/// lines that can't be parsed.
+fn read_io_stats(mut reader: impl BufRead) -> Vec<IoCounters> {
     let mut results = vec![];
-    let mut reader = BufReader::new(File::open(PROC_DISKSTATS)?);
     let mut line = String::new();
 
     // This saves us from doing a string allocation on each iteration compared to
@@ -85,6 +83,16 @@ pub fn io_stats() -> anyhow::Result<Vec<IoCounters>> {
         }
     }
 
+    results
+}
+
+/// Returns an iterator of disk I/O stats. Pulls data from `/proc/diskstats`.
+pub fn io_stats() -> anyhow::Result<Vec<IoCounters>> {
+    const PROC_DISKSTATS: &str = "/proc/diskstats";
+
+    #[cfg_attr(not(feature = "zfs"), expect(unused_mut))]
+    let mut results = read_io_stats(BufReader::new(File::open(PROC_DISKSTATS)?));
+
     #[cfg(feature = "zfs")]
     {
         use crate::data_collection::disks::zfs_io_counters;
@@ -95,3 +103,42 @@ pub fn io_stats() -> anyhow::Result<Vec<IoCounters>> {
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_diskstats() {
+        // A whole disk with discard and flush stats (5.5+), a partition, a disk with
+        // only the original 11 stats, and some malformed lines.
+        let diskstats = "\
+   8       0 sda 1000 10 2048 300 500 20 4096 400 0 700 700 0 0 0 0 0 0
+   8       1 sda1 900 5 1024 200 400 10 2048 300 0 500 500 0 0 0 0
+ 259       0 nvme0n1 147 0 6650 13 12 0 80 0 0 36 13
+   7       0 loop0 0 0
+this is not a valid line
+";
+
+        let stats = read_io_stats(diskstats.as_bytes());
+        let stats = stats
+            .iter()
+            .map(|io| {
+                (
+                    io.device_name().to_str().unwrap(),
+                    io.read_bytes(),
+                    io.write_bytes(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            stats,
+            vec![
+                ("sda", 2048 * DISK_SECTOR_SIZE, 4096 * DISK_SECTOR_SIZE),
+                ("sda1", 1024 * DISK_SECTOR_SIZE, 2048 * DISK_SECTOR_SIZE),
+                ("nvme0n1", 6650 * DISK_SECTOR_SIZE, 80 * DISK_SECTOR_SIZE),
+            ]
+        );
+    }
+}
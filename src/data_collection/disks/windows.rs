@@ -5,7 +5,7 @@ mod bindings;
 use bindings::*;
 use itertools::Itertools;
 
-use super::{keep_disk_entry, DiskHarvest};
+use super::{keep_disk_entry, keep_file_system, DiskHarvest};
 use crate::data_collection::{disks::IoCounters, DataCollector};
 
 /// Returns I/O stats.
@@ -29,6 +29,7 @@ pub(crate) fn get_disk_usage(collector: &DataCollector) -> anyhow::Result<Vec<Di
     let disks = &collector.sys.disks;
     let disk_filter = &collector.filters.disk_filter;
     let mount_filter = &collector.filters.mount_filter;
+    let fs_filter = &collector.filters.fs_filter;
 
     Ok(disks
         .iter()
@@ -52,9 +53,17 @@ pub(crate) fn get_disk_usage(collector: &DataCollector) -> anyhow::Result<Vec<Di
                 .into_string()
                 .unwrap_or_else(|_| "Mount Unavailable".to_string());
 
+            let file_system = disk
+                .file_system()
+                .to_str()
+                .filter(|file_system| !file_system.is_empty())
+                .map(str::to_string);
+
             let volume_name = volume_name_from_mount(&mount_point).ok();
 
-            if keep_disk_entry(&name, &mount_point, disk_filter, mount_filter) {
+            if keep_disk_entry(&name, &mount_point, disk_filter, mount_filter)
+                && keep_file_system(file_system.as_deref(), fs_filter)
+            {
                 let free_space = disk.available_space();
                 let total_space = disk.total_space();
                 let used_space = total_space - free_space;
@@ -62,10 +71,12 @@ pub(crate) fn get_disk_usage(collector: &DataCollector) -> anyhow::Result<Vec<Di
                 Some(DiskHarvest {
                     name,
                     mount_point,
+                    file_system,
                     volume_name,
                     free_space: Some(free_space),
                     used_space: Some(used_space),
                     total_space: Some(total_space),
+                    pool: None,
                 })
             } else {
                 None
@@ -0,0 +1,94 @@
+//! ZFS pool statistics from the kernel's kstat files, which are exposed under
+//! `/proc/spl/kstat/zfs` on Linux.
+//!
+//! Parsing is kept separate from reading the files, so that other platforms'
+//! kstats can reuse it.
+
+use std::{fs, path::Path};
+
+/// Where the ZFS kstats live on Linux.
+const KSTAT_ZFS_PATH: &str = "/proc/spl/kstat/zfs";
+
+/// Parses a pool's `state` kstat, which is just its health (e.g. `ONLINE`).
+/// Returns [`None`] if the contents don't look like a health.
+fn parse_pool_state(contents: &str) -> Option<String> {
+    let state = contents.trim();
+
+    if !state.is_empty() && state.chars().all(|c| c.is_ascii_uppercase()) {
+        Some(state.to_string())
+    } else {
+        None
+    }
+}
+
+/// Returns the name and health of each pool with kstats under `root`. Each
+/// pool has its own directory, next to files for global stats such as
+/// `arcstats`.
+///
+/// Missing or unreadable kstats (e.g. if the ZFS module isn't loaded) just
+/// mean there are no pools to report.
+fn read_pool_states(root: &Path) -> Vec<(String, String)> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return vec![];
+    };
+
+    let mut pools: Vec<(String, String)> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let state = fs::read_to_string(entry.path().join("state")).ok()?;
+
+            Some((name, parse_pool_state(&state)?))
+        })
+        .collect();
+
+    pools.sort_unstable();
+    pools
+}
+
+/// Returns the name and health of each imported pool.
+pub(super) fn pool_states() -> Vec<(String, String)> {
+    read_pool_states(Path::new(KSTAT_ZFS_PATH))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_pool_state() {
+        assert_eq!(parse_pool_state("ONLINE\n"), Some("ONLINE".to_string()));
+        assert_eq!(parse_pool_state("DEGRADED"), Some("DEGRADED".to_string()));
+        assert_eq!(parse_pool_state(""), None);
+        assert_eq!(parse_pool_state("12 1 0x01 7 2160 0\n"), None);
+    }
+
+    #[test]
+    fn test_read_pool_states() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+
+        for (pool, state) in [("tank", "ONLINE\n"), ("backup", "DEGRADED\n")] {
+            fs::create_dir(root.join(pool)).unwrap();
+            fs::write(root.join(pool).join("state"), state).unwrap();
+        }
+
+        // Global stats, a pool from an older module without a state kstat, and one
+        // with a state we don't understand.
+        fs::write(root.join("arcstats"), "13 1 0x01 147 39984 0 0\n").unwrap();
+        fs::create_dir(root.join("old")).unwrap();
+        fs::create_dir(root.join("odd")).unwrap();
+        fs::write(root.join("odd").join("state"), "name type data\n").unwrap();
+
+        assert_eq!(
+            read_pool_states(root),
+            vec![
+                ("backup".to_string(), "DEGRADED".to_string()),
+                ("tank".to_string(), "ONLINE".to_string())
+            ]
+        );
+
+        assert!(read_pool_states(&root.join("missing")).is_empty());
+    }
+}
@@ -0,0 +1,319 @@
+//! Capacity and health of ZFS pools, gathered through `zpool list` and, where
+//! available, the pools' kstats.
+
+use std::{
+    process::{Command, Stdio},
+    sync::{LazyLock, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::{keep_disk_entry, keep_file_system, DiskHarvest, PoolDetails};
+use crate::app::filter::Filter;
+
+/// The file system type given to pool entries, so that they can be told apart
+/// from (and filtered separately from) their datasets.
+const ZPOOL_FILE_SYSTEM: &str = "zpool";
+
+/// How long the output of `zpool list` is reused for. Pool capacity changes
+/// slowly, and running `zpool` on every harvest is wasteful.
+const ZPOOL_LIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long `zpool list` may run before it is killed, as it can hang on a
+/// suspended or stalled pool.
+const ZPOOL_LIST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The pools from the last run of `zpool list`.
+#[derive(Debug, Default)]
+struct ZpoolListCache {
+    pools: Vec<DiskHarvest>,
+    last_run: Option<Instant>,
+    is_running: bool,
+}
+
+impl ZpoolListCache {
+    /// Whether `zpool list` should be run again.
+    fn needs_refresh(&self, now: Instant) -> bool {
+        !self.is_running
+            && self.last_run.map_or(true, |last_run| {
+                now.duration_since(last_run) >= ZPOOL_LIST_INTERVAL
+            })
+    }
+}
+
+static ZPOOL_LIST: LazyLock<Mutex<ZpoolListCache>> = LazyLock::new(Default::default);
+
+/// Returns a [`DiskHarvest`] per ZFS pool. Pools are never mounted, so they
+/// have an empty mount point.
+///
+/// `zpool list` is run in the background at most every
+/// [`ZPOOL_LIST_INTERVAL`], so this returns the pools from its last run. Failing
+/// to run `zpool` (e.g. if ZFS isn't installed) just means there are no pools
+/// to report, other than any that have kstats.
+pub(crate) fn get_zpool_usage(
+    disk_filter: &Option<Filter>, mount_filter: &Option<Filter>, fs_filter: &Option<Filter>,
+) -> Vec<DiskHarvest> {
+    let pools = cached_zpool_list();
+
+    #[cfg(target_os = "linux")]
+    let pools = merge_pool_states(pools, super::zfs::pool_states());
+
+    pools
+        .into_iter()
+        .filter(|pool| {
+            keep_disk_entry(&pool.name, &pool.mount_point, disk_filter, mount_filter)
+                && keep_file_system(pool.file_system.as_deref(), fs_filter)
+        })
+        .collect()
+}
+
+/// Returns the pools from the last run of `zpool list`, starting another run in
+/// the background if the last one is too old.
+fn cached_zpool_list() -> Vec<DiskHarvest> {
+    let Ok(mut cache) = ZPOOL_LIST.lock() else {
+        return vec![];
+    };
+
+    let now = Instant::now();
+    if cache.needs_refresh(now) {
+        cache.is_running = true;
+        cache.last_run = Some(now);
+
+        thread::spawn(|| {
+            let pools = run_zpool_list();
+
+            if let Ok(mut cache) = ZPOOL_LIST.lock() {
+                // If `zpool` timed out, keep the last pools rather than losing
+                // them while a pool is stalled.
+                if let Some(pools) = pools {
+                    cache.pools = pools;
+                }
+                cache.is_running = false;
+            }
+        });
+    }
+
+    cache.pools.clone()
+}
+
+/// Runs `zpool list`, killing it if it takes longer than
+/// [`ZPOOL_LIST_TIMEOUT`]. Returns [`None`] if it timed out, and no pools if
+/// it couldn't be run.
+fn run_zpool_list() -> Option<Vec<DiskHarvest>> {
+    let Ok(mut child) = Command::new("zpool")
+        .args(["list", "-Hp", "-o", "name,size,alloc,free,frag,health"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return Some(vec![]);
+    };
+
+    let deadline = Instant::now() + ZPOOL_LIST_TIMEOUT;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(50)),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            Err(_) => return Some(vec![]),
+        }
+    }
+
+    match child.wait_with_output() {
+        Ok(output) if output.status.success() => {
+            Some(parse_zpool_list(&String::from_utf8_lossy(&output.stdout)))
+        }
+        _ => Some(vec![]),
+    }
+}
+
+/// Returns a pool entry without any details other than its name.
+fn pool_entry(name: &str) -> DiskHarvest {
+    DiskHarvest {
+        name: name.to_string(),
+        mount_point: String::new(),
+        file_system: Some(ZPOOL_FILE_SYSTEM.to_string()),
+        pool: Some(PoolDetails::default()),
+        ..Default::default()
+    }
+}
+
+/// Fills in the health of `pools` from their kstats, which are more current
+/// than `zpool list`. Pools that only have kstats (e.g. if `zpool` couldn't be
+/// run) are added without their capacity.
+#[cfg(target_os = "linux")]
+fn merge_pool_states(
+    mut pools: Vec<DiskHarvest>, states: Vec<(String, String)>,
+) -> Vec<DiskHarvest> {
+    for (name, health) in states {
+        let index = match pools.iter().position(|pool| pool.name == name) {
+            Some(index) => index,
+            None => {
+                pools.push(pool_entry(&name));
+                pools.len() - 1
+            }
+        };
+
+        pools[index]
+            .pool
+            .get_or_insert_with(Default::default)
+            .health = Some(health);
+    }
+
+    pools
+}
+
+/// Parses the output of `zpool list -Hp -o name,size,alloc,free,frag,health`,
+/// where each line has the pool's name, its size, allocated space, and free
+/// space in bytes, its fragmentation as a percentage, and its health, separated
+/// by tabs. The fragmentation and health are optional, and lines that can't
+/// otherwise be parsed are skipped.
+fn parse_zpool_list(output: &str) -> Vec<DiskHarvest> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+
+            let name = fields.next().filter(|name| !name.is_empty())?;
+            let total = fields.next()?.trim().parse::<u64>().ok()?;
+            let used = fields.next()?.trim().parse::<u64>().ok()?;
+            let free = fields.next()?.trim().parse::<u64>().ok()?;
+
+            // Pools without fragmentation info show "-".
+            let fragmentation = fields
+                .next()
+                .and_then(|frag| frag.trim().trim_end_matches('%').parse::<u8>().ok());
+            let health = fields
+                .next()
+                .map(str::trim)
+                .filter(|health| !health.is_empty() && *health != "-")
+                .map(str::to_string);
+
+            Some(DiskHarvest {
+                free_space: Some(free),
+                used_space: Some(used),
+                total_space: Some(total),
+                pool: Some(PoolDetails {
+                    health,
+                    fragmentation,
+                }),
+                ..pool_entry(name)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_zpool_list() {
+        // A pool that is missing its sizes, one with a mangled size in between, and
+        // one from an older `zpool` without the last columns.
+        let output = "\
+boot\t1061158912\t270471168\t790687744\t2\tONLINE
+tank\t3985729650688\t1516246618112\t2469483032576\t11\tDEGRADED
+faulted\t-\t-\t-\t-\tFAULTED
+broken\t12G\t1000\t2000\t0\tONLINE
+
+backup\t1000\t250\t750
+";
+
+        let pools = parse_zpool_list(output);
+        assert_eq!(
+            pools
+                .iter()
+                .map(|pool| (
+                    pool.name.as_str(),
+                    pool.total_space,
+                    pool.used_space,
+                    pool.free_space
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("boot", Some(1061158912), Some(270471168), Some(790687744)),
+                (
+                    "tank",
+                    Some(3985729650688),
+                    Some(1516246618112),
+                    Some(2469483032576)
+                ),
+                ("backup", Some(1000), Some(250), Some(750)),
+            ]
+        );
+        assert!(pools.iter().all(
+            |pool| pool.mount_point.is_empty() && pool.file_system.as_deref() == Some("zpool")
+        ));
+        assert_eq!(
+            pools
+                .iter()
+                .map(|pool| pool.pool.clone().unwrap())
+                .collect::<Vec<_>>(),
+            vec![
+                PoolDetails {
+                    health: Some("ONLINE".to_string()),
+                    fragmentation: Some(2)
+                },
+                PoolDetails {
+                    health: Some("DEGRADED".to_string()),
+                    fragmentation: Some(11)
+                },
+                PoolDetails::default(),
+            ]
+        );
+    }
+
+    #[test]
+    fn zpool_list_is_rerun_on_an_interval() {
+        let now = Instant::now();
+        let mut cache = ZpoolListCache::default();
+        assert!(cache.needs_refresh(now));
+
+        cache.last_run = Some(now);
+        assert!(!cache.needs_refresh(now + Duration::from_secs(1)));
+        assert!(cache.needs_refresh(now + ZPOOL_LIST_INTERVAL));
+
+        // Never run twice at once, such as while `zpool` is hanging.
+        cache.is_running = true;
+        assert!(!cache.needs_refresh(now + ZPOOL_LIST_INTERVAL * 2));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_merge_pool_states() {
+        let pools = parse_zpool_list("tank\t1000\t250\t750\t5\tONLINE\n");
+        let pools = merge_pool_states(
+            pools,
+            vec![
+                ("scratch".to_string(), "SUSPENDED".to_string()),
+                ("tank".to_string(), "DEGRADED".to_string()),
+            ],
+        );
+
+        assert_eq!(pools.len(), 2);
+        assert_eq!(pools[0].name, "tank");
+        assert_eq!(pools[0].total_space, Some(1000));
+        assert_eq!(
+            pools[0].pool,
+            Some(PoolDetails {
+                health: Some("DEGRADED".to_string()),
+                fragmentation: Some(5)
+            })
+        );
+
+        // Only known through its kstats.
+        assert_eq!(pools[1].name, "scratch");
+        assert_eq!(pools[1].total_space, None);
+        assert_eq!(pools[1].file_system.as_deref(), Some("zpool"));
+        assert_eq!(
+            pools[1].pool.as_ref().unwrap().health.as_deref(),
+            Some("SUSPENDED")
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//! Finding GPUs through the DRM subsystem in sysfs, for the vendors whose
+//! drivers expose their data there.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Where DRM devices are listed in sysfs.
+pub(super) const DRM_CLASS_PATH: &str = "/sys/class/drm";
+
+/// Returns the device directory of each card under `drm_root` with the given
+/// PCI vendor ID, so that cards from other vendors (e.g. NVIDIA cards, which
+/// are reported through NVML) aren't reported twice.
+pub(super) fn find_vendor_devs(drm_root: &Path, vendor_id: &str) -> Vec<PathBuf> {
+    let Ok(paths) = fs::read_dir(drm_root) else {
+        return Vec::new();
+    };
+
+    let mut devices: Vec<PathBuf> = paths
+        .flatten()
+        .filter(|entry| {
+            // Skip connectors like "card0-DP-1" and render nodes, which share their
+            // card's device.
+            entry.file_name().to_str().is_some_and(|name| {
+                name.strip_prefix("card")
+                    .is_some_and(|index| index.parse::<u32>().is_ok())
+            })
+        })
+        .map(|entry| entry.path().join("device"))
+        .filter(|device_path| {
+            fs::read_to_string(device_path.join("vendor"))
+                .is_ok_and(|vendor| vendor.trim().eq_ignore_ascii_case(vendor_id))
+        })
+        .collect();
+
+    devices.sort();
+    devices
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_find_vendor_devs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+
+        write(&root.join("card1/device/vendor"), "0x1002\n");
+        write(&root.join("card0/device/vendor"), "0x10de\n");
+        write(&root.join("card2/device/vendor"), "0x8086\n");
+        write(&root.join("card1-DP-1/device/vendor"), "0x1002\n");
+        write(&root.join("renderD128/device/vendor"), "0x1002\n");
+        fs::create_dir_all(root.join("card3")).unwrap();
+
+        assert_eq!(
+            find_vendor_devs(root, "0x1002"),
+            vec![root.join("card1/device")]
+        );
+        assert_eq!(
+            find_vendor_devs(root, "0x8086"),
+            vec![root.join("card2/device")]
+        );
+        assert!(find_vendor_devs(&root.join("missing"), "0x1002").is_empty());
+    }
+}
@@ -0,0 +1,644 @@
+//! Best-effort data collection for Intel GPUs using the i915 and xe drivers.
+//!
+//! Neither driver reports a card's memory usage in sysfs, so it is read from
+//! debugfs, which usually needs elevated permissions and is skipped if it can't
+//! be read. Per-process usage comes from each DRM client's fdinfo, and a card's
+//! utilization is the sum of its clients' usage.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{
+    app::layout_manager::UsedWidgets,
+    data_collection::{
+        drm::{find_vendor_devs, DRM_CLASS_PATH},
+        memory::MemHarvest,
+    },
+};
+
+/// The PCI vendor ID of Intel devices.
+const INTEL_VENDOR_ID: &str = "0x8086";
+
+/// The name given to Intel cards, as the drivers don't expose a marketing name.
+const INTEL_DEFAULT_NAME: &str = "Intel Graphics";
+
+/// Where each DRM minor's debugfs directory is.
+const DEBUGFS_DRI_PATH: &str = "/sys/kernel/debug/dri";
+
+pub struct IntelGpuData {
+    pub memory: Option<Vec<(String, MemHarvest)>>,
+    /// How busy each GPU is, as a percentage.
+    pub utilization: Option<Vec<(String, f64)>>,
+    pub procs: Option<(u64, Vec<HashMap<u32, (u64, u32)>>)>,
+}
+
+/// How busy an engine has been. For i915, this is the busy time in
+/// nanoseconds. For xe, this is the busy GPU cycles, along with the total
+/// cycles elapsed so that the two can be compared.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct EngineTime {
+    busy: u64,
+    total: Option<u64>,
+}
+
+/// A DRM client's usage of a card, from its fdinfo.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct ClientUsage {
+    /// The PCI address of the card, e.g. `0000:00:02.0`.
+    pdev: String,
+    client_id: u64,
+    engines: HashMap<String, EngineTime>,
+    /// Resident device-local memory, in bytes.
+    memory: u64,
+}
+
+// Engine times are cumulative, so the previous ones are needed to calculate
+// utilization. They are keyed by card and pid.
+static PREV_ENGINES: LazyLock<Mutex<HashMap<(String, u32), HashMap<String, EngineTime>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Parses a size such as `1024 KiB` from fdinfo.
+fn parse_size(value: &str) -> Option<u64> {
+    let mut parts = value.split_whitespace();
+    let amount = parts.next()?.parse::<u64>().ok()?;
+
+    let shift = match parts.next() {
+        None => 0,
+        Some("KiB") => 10,
+        Some("MiB") => 20,
+        Some("GiB") => 30,
+        Some(_) => return None,
+    };
+
+    Some(amount << shift)
+}
+
+/// Parses the fdinfo of a DRM file descriptor, returning [`None`] if it doesn't
+/// belong to an i915 or xe client.
+fn parse_fdinfo(contents: &str) -> Option<ClientUsage> {
+    let mut usage = ClientUsage::default();
+    let mut is_intel = false;
+    let mut has_client_id = false;
+
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        if key == "drm-driver" {
+            is_intel = matches!(value, "i915" | "xe");
+        } else if key == "drm-pdev" {
+            usage.pdev = value.to_string();
+        } else if key == "drm-client-id" {
+            usage.client_id = value.parse().ok()?;
+            has_client_id = true;
+        } else if let Some(class) = key.strip_prefix("drm-total-cycles-") {
+            // xe
+            if let Ok(total) = value.parse() {
+                usage.engines.entry(class.to_string()).or_default().total = Some(total);
+            }
+        } else if let Some(class) = key.strip_prefix("drm-cycles-") {
+            // xe
+            if let Ok(busy) = value.parse() {
+                usage.engines.entry(class.to_string()).or_default().busy = busy;
+            }
+        } else if let Some(engine) = key.strip_prefix("drm-engine-") {
+            // i915, where the capacity is how many engines of a class there are.
+            if engine.starts_with("capacity-") {
+                continue;
+            }
+
+            if let Some(busy) = value
+                .strip_suffix("ns")
+                .and_then(|busy| busy.trim().parse().ok())
+            {
+                usage.engines.entry(engine.to_string()).or_default().busy = busy;
+            }
+        } else if let Some(region) = key.strip_prefix("drm-resident-") {
+            // Only count device-local memory, as integrated GPUs use system memory.
+            if region.starts_with("local") || region.starts_with("vram") {
+                usage.memory += parse_size(value).unwrap_or(0);
+            }
+        }
+    }
+
+    (is_intel && has_client_id).then_some(usage)
+}
+
+/// Returns the usage of each i915 and xe DRM client opened by a process under
+/// `proc_root`, along with the process' pid. Clients shared by several file
+/// descriptors in the same process are only counted once.
+fn read_clients(proc_root: &Path) -> Vec<(u32, ClientUsage)> {
+    let Ok(entries) = fs::read_dir(proc_root) else {
+        return vec![];
+    };
+
+    let mut clients = vec![];
+
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|pid| pid.parse::<u32>().ok())
+        else {
+            continue;
+        };
+
+        // Other users' processes can't be read without elevated permissions.
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+
+        let mut observed_ids = HashSet::new();
+
+        for fd in fds.flatten() {
+            let is_drm = fs::read_link(fd.path()).is_ok_and(|link| link.starts_with("/dev/dri"));
+            if !is_drm {
+                continue;
+            }
+
+            let Some(client) = fs::read_to_string(entry.path().join("fdinfo").join(fd.file_name()))
+                .ok()
+                .and_then(|fdinfo| parse_fdinfo(&fdinfo))
+            else {
+                continue;
+            };
+
+            if observed_ids.insert((client.pdev.clone(), client.client_id)) {
+                clients.push((pid, client));
+            }
+        }
+    }
+
+    clients
+}
+
+/// Parses i915's `i915_gem_objects`, which lists each memory region, e.g.
+/// `local0: total:0x00000003fa000000, available:0x000000038b3fc000 bytes`.
+/// Only device-local regions are counted, so integrated GPUs have none.
+fn parse_i915_gem_objects(contents: &str) -> Option<MemHarvest> {
+    fn parse_number(value: &str) -> Option<u64> {
+        let value = value.trim().trim_end_matches("bytes").trim();
+
+        match value.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => value.parse().ok(),
+        }
+    }
+
+    let mut memory: Option<MemHarvest> = None;
+
+    for line in contents.lines() {
+        let Some((region, sizes)) = line.trim().split_once(':') else {
+            continue;
+        };
+        if !region.starts_with("local") {
+            continue;
+        }
+
+        let mut total = None;
+        let mut available = None;
+        for size in sizes.split(',') {
+            if let Some((key, value)) = size.split_once(':') {
+                match key.trim() {
+                    "total" => total = parse_number(value),
+                    "available" => available = parse_number(value),
+                    _ => {}
+                }
+            }
+        }
+
+        if let (Some(total), Some(available)) = (total, available) {
+            let memory = memory.get_or_insert(MemHarvest {
+                total_bytes: 0,
+                used_bytes: 0,
+            });
+            memory.total_bytes += total;
+            memory.used_bytes += total.saturating_sub(available);
+        }
+    }
+
+    memory
+}
+
+/// Parses one of xe's `vram<N>_mm` files, whose `size` and `usage` lines are
+/// the region's total and used memory in bytes.
+fn parse_xe_vram_mm(contents: &str) -> Option<MemHarvest> {
+    let mut total = None;
+    let mut used = None;
+
+    for line in contents.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            match key.trim() {
+                "size" => total = value.trim().parse().ok(),
+                "usage" => used = value.trim().parse().ok(),
+                _ => {}
+            }
+        }
+    }
+
+    Some(MemHarvest {
+        total_bytes: total?,
+        used_bytes: used?,
+    })
+}
+
+/// Returns a card's device-local memory from its debugfs directory, or
+/// [`None`] if it has none or it can't be read.
+fn read_debugfs_memory(debugfs_path: &Path) -> Option<MemHarvest> {
+    if let Ok(contents) = fs::read_to_string(debugfs_path.join("i915_gem_objects")) {
+        return parse_i915_gem_objects(&contents);
+    }
+
+    // xe has a file per VRAM region (one per tile).
+    (0..)
+        .map_while(|index| fs::read_to_string(debugfs_path.join(format!("vram{index}_mm"))).ok())
+        .filter_map(|contents| parse_xe_vram_mm(&contents))
+        .reduce(|a, b| MemHarvest {
+            total_bytes: a.total_bytes + b.total_bytes,
+            used_bytes: a.used_bytes + b.used_bytes,
+        })
+}
+
+/// Returns a card's debugfs directory, which is named after its DRM minor
+/// (e.g. `0` for `card0`).
+fn debugfs_path(device_path: &Path) -> Option<PathBuf> {
+    let card = device_path.parent()?.file_name()?.to_str()?;
+    let minor = card.strip_prefix("card")?;
+
+    Some(Path::new(DEBUGFS_DRI_PATH).join(minor))
+}
+
+/// Returns how much busier each engine has been since `prev`, along with the
+/// cycles elapsed, if known. Engines that weren't in `prev` are skipped.
+fn engine_deltas<'a>(
+    prev: &'a HashMap<String, EngineTime>, current: &'a HashMap<String, EngineTime>,
+) -> impl Iterator<Item = (&'a str, EngineTime)> {
+    current.iter().filter_map(|(engine, current)| {
+        let prev = prev.get(engine)?;
+
+        Some((
+            engine.as_str(),
+            EngineTime {
+                busy: current.busy.checked_sub(prev.busy)?,
+                total: prev
+                    .total
+                    .zip(current.total)
+                    .and_then(|(prev_total, total)| total.checked_sub(prev_total)),
+            },
+        ))
+    })
+}
+
+/// Returns how busy the busiest engine was, as a percentage, given how much
+/// busier each one has been over `interval`. Only the busiest counts, as the
+/// engines run in parallel.
+fn busiest_engine<'a>(
+    deltas: impl Iterator<Item = (&'a str, EngineTime)>, interval: Duration,
+) -> u32 {
+    deltas
+        .filter_map(|(_, delta)| {
+            let percent = match delta.total {
+                Some(total) => delta.busy.saturating_mul(100).checked_div(total)?,
+                None => (u128::from(delta.busy) * 100)
+                    .checked_div(interval.as_nanos())
+                    .map(|percent| percent as u64)?,
+            };
+
+            Some(percent.min(100) as u32)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns how busy a process was on a card since it was last seen, as a
+/// percentage.
+fn utilization(
+    prev: &HashMap<String, EngineTime>, current: &HashMap<String, EngineTime>, interval: Duration,
+) -> u32 {
+    busiest_engine(engine_deltas(prev, current), interval)
+}
+
+/// Returns how busy a card was, as a percentage, from how much busier each of
+/// its processes' engines have been. Each engine's usage is summed over the
+/// processes, so processes that have exited or just started don't count.
+fn card_utilization<'a>(
+    processes: impl Iterator<
+        Item = (
+            &'a HashMap<String, EngineTime>,
+            &'a HashMap<String, EngineTime>,
+        ),
+    >,
+    interval: Duration,
+) -> u32 {
+    let mut engines: HashMap<&str, EngineTime> = HashMap::new();
+
+    for (prev, current) in processes {
+        for (engine, delta) in engine_deltas(prev, current) {
+            let sum = engines.entry(engine).or_default();
+            sum.busy += delta.busy;
+            // The cycles elapsed are the same for each process, so they aren't summed.
+            sum.total = sum.total.max(delta.total);
+        }
+    }
+
+    busiest_engine(engines.into_iter(), interval)
+}
+
+pub fn get_intel_vecs(
+    widgets_to_harvest: &UsedWidgets, prev_time: Instant,
+) -> Option<IntelGpuData> {
+    let device_path_list = find_vendor_devs(Path::new(DRM_CLASS_PATH), INTEL_VENDOR_ID);
+    if device_path_list.is_empty() {
+        return None;
+    }
+
+    let interval = Instant::now().duration_since(prev_time);
+    let mut mem_vec = Vec::with_capacity(device_path_list.len());
+    let mut util_vec = Vec::with_capacity(device_path_list.len());
+    let mut proc_vec = Vec::with_capacity(device_path_list.len());
+    let mut total_mem = 0;
+
+    let clients = if widgets_to_harvest.use_proc || widgets_to_harvest.use_gpu_util {
+        read_clients(Path::new("/proc"))
+    } else {
+        vec![]
+    };
+    let mut prev_engines = PREV_ENGINES.lock().unwrap();
+    let mut current_engines = HashMap::new();
+
+    for device_path in device_path_list {
+        if let Some(mem) = debugfs_path(&device_path).and_then(|path| read_debugfs_memory(&path)) {
+            total_mem += mem.total_bytes;
+
+            if widgets_to_harvest.use_mem {
+                mem_vec.push((INTEL_DEFAULT_NAME.to_string(), mem));
+            }
+        }
+
+        // fdinfo refers to cards by their PCI address, which sysfs links the device to.
+        let Some(pdev) = fs::canonicalize(&device_path)
+            .ok()
+            .and_then(|path| Some(path.file_name()?.to_str()?.to_string()))
+        else {
+            continue;
+        };
+
+        let mut procs_map: HashMap<u32, (u64, u32)> = HashMap::new();
+        let mut engines_by_pid: HashMap<u32, HashMap<String, EngineTime>> = HashMap::new();
+
+        for (pid, client) in clients.iter().filter(|(_, client)| client.pdev == pdev) {
+            procs_map.entry(*pid).or_default().0 += client.memory;
+
+            let engines = engines_by_pid.entry(*pid).or_default();
+            for (engine, time) in &client.engines {
+                let total = engines.entry(engine.clone()).or_default();
+                total.busy += time.busy;
+                total.total = time.total.or(total.total);
+            }
+        }
+
+        // A card without any clients that can be read is skipped, rather than
+        // shown as idle, as its clients may just belong to other users.
+        if widgets_to_harvest.use_gpu_util && !engines_by_pid.is_empty() {
+            let processes = engines_by_pid.iter().filter_map(|(pid, engines)| {
+                Some((prev_engines.get(&(pdev.clone(), *pid))?, engines))
+            });
+            util_vec.push((
+                INTEL_DEFAULT_NAME.to_string(),
+                card_utilization(processes, interval).into(),
+            ));
+        }
+
+        for (pid, engines) in engines_by_pid {
+            let key = (pdev.clone(), pid);
+
+            if let Some(prev) = prev_engines.get(&key) {
+                if let Some(usage) = procs_map.get_mut(&pid) {
+                    usage.1 = utilization(prev, &engines, interval);
+                }
+            }
+
+            current_engines.insert(key, engines);
+        }
+
+        procs_map.retain(|_, (mem, util)| *mem > 0 || *util > 0);
+        if widgets_to_harvest.use_proc && !procs_map.is_empty() {
+            proc_vec.push(procs_map);
+        }
+    }
+
+    // Only keep the processes that still have a client, so exited ones don't pile up.
+    *prev_engines = current_engines;
+
+    Some(IntelGpuData {
+        memory: (!mem_vec.is_empty()).then_some(mem_vec),
+        utilization: (!util_vec.is_empty()).then_some(util_vec),
+        procs: (!proc_vec.is_empty()).then_some((total_mem, proc_vec)),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_i915_fdinfo() {
+        let fdinfo = "\
+pos:\t0
+flags:\t02100002
+drm-driver:\ti915
+drm-client-id:\t7
+drm-pdev:\t0000:03:00.0
+drm-total-local0:\t4096 KiB
+drm-resident-local0:\t2048 KiB
+drm-resident-system0:\t1024 KiB
+drm-engine-render:\t25662044495 ns
+drm-engine-copy:\t0 ns
+drm-engine-capacity-video:\t2
+";
+
+        let usage = parse_fdinfo(fdinfo).unwrap();
+        assert_eq!(usage.pdev, "0000:03:00.0");
+        assert_eq!(usage.client_id, 7);
+        assert_eq!(usage.memory, 2048 << 10);
+        assert_eq!(
+            usage.engines.get("render"),
+            Some(&EngineTime {
+                busy: 25662044495,
+                total: None
+            })
+        );
+        assert_eq!(usage.engines.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_xe_fdinfo() {
+        let fdinfo = "\
+drm-driver:\txe
+drm-client-id:\t12
+drm-pdev:\t0000:00:02.0
+drm-resident-vram0:\t512 MiB
+drm-cycles-rcs:\t28257900
+drm-total-cycles-rcs:\t7655183225
+";
+
+        let usage = parse_fdinfo(fdinfo).unwrap();
+        assert_eq!(usage.memory, 512 << 20);
+        assert_eq!(
+            usage.engines.get("rcs"),
+            Some(&EngineTime {
+                busy: 28257900,
+                total: Some(7655183225)
+            })
+        );
+
+        // Other drivers, and clients without an ID.
+        assert!(parse_fdinfo("drm-driver:\tamdgpu\ndrm-client-id:\t1\n").is_none());
+        assert!(parse_fdinfo("drm-driver:\ti915\n").is_none());
+    }
+
+    #[test]
+    fn test_utilization() {
+        let engines = |busy: u64, total: Option<u64>| {
+            HashMap::from_iter([
+                ("render".to_string(), EngineTime { busy, total }),
+                ("copy".to_string(), EngineTime { busy: 0, total }),
+            ])
+        };
+        let interval = Duration::from_secs(1);
+
+        // Busy for a quarter of a second.
+        assert_eq!(
+            utilization(
+                &engines(1_000_000_000, None),
+                &engines(1_250_000_000, None),
+                interval
+            ),
+            25
+        );
+
+        // Busy for half of the elapsed cycles.
+        assert_eq!(
+            utilization(
+                &engines(100, Some(1000)),
+                &engines(600, Some(2000)),
+                interval
+            ),
+            50
+        );
+
+        // Nothing to compare with yet.
+        assert_eq!(
+            utilization(&HashMap::new(), &engines(100, None), interval),
+            0
+        );
+    }
+
+    #[test]
+    fn test_card_utilization() {
+        let engines = |render: u64, copy: u64| {
+            HashMap::from_iter([
+                (
+                    "render".to_string(),
+                    EngineTime {
+                        busy: render,
+                        total: None,
+                    },
+                ),
+                (
+                    "copy".to_string(),
+                    EngineTime {
+                        busy: copy,
+                        total: None,
+                    },
+                ),
+            ])
+        };
+        let interval = Duration::from_secs(1);
+
+        // Each engine is summed over the processes, and the busiest one counts.
+        let first = (engines(0, 0), engines(300_000_000, 100_000_000));
+        let second = (engines(0, 0), engines(200_000_000, 0));
+        let processes = [(&first.0, &first.1), (&second.0, &second.1)];
+        assert_eq!(card_utilization(processes.into_iter(), interval), 50);
+
+        // Busy cycles are summed, but the elapsed cycles aren't.
+        let cycles = |busy: u64, total: u64| {
+            HashMap::from_iter([(
+                "rcs".to_string(),
+                EngineTime {
+                    busy,
+                    total: Some(total),
+                },
+            )])
+        };
+        let first = (cycles(0, 1000), cycles(300, 2000));
+        let second = (cycles(0, 1000), cycles(400, 2000));
+        let processes = [(&first.0, &first.1), (&second.0, &second.1)];
+        assert_eq!(card_utilization(processes.into_iter(), interval), 70);
+
+        assert_eq!(card_utilization(std::iter::empty(), interval), 0);
+    }
+
+    #[test]
+    fn test_parse_memory() {
+        let gem_objects = "\
+system: total:0x0000000fe4a36000, available:0x0000000fe4a36000 bytes
+stolen-system: total:0x0000000004000000, available:0x0000000003000000 bytes
+local0: total:0x0000000400000000, available:0x0000000300000000 bytes
+";
+        let memory = parse_i915_gem_objects(gem_objects).unwrap();
+        assert_eq!(
+            (memory.total_bytes, memory.used_bytes),
+            (0x400000000, 0x100000000)
+        );
+
+        // Integrated GPUs only have system memory.
+        assert!(parse_i915_gem_objects(gem_objects.lines().next().unwrap()).is_none());
+
+        let vram_mm = "  use_type: 1\n  use_tt: 0\n  size: 17163091968\n  usage: 1073741824\n";
+        let memory = parse_xe_vram_mm(vram_mm).unwrap();
+        assert_eq!(
+            (memory.total_bytes, memory.used_bytes),
+            (17163091968, 1073741824)
+        );
+    }
+
+    #[test]
+    fn test_read_clients() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let fdinfo = "drm-driver:\ti915\ndrm-client-id:\t3\ndrm-pdev:\t0000:00:02.0\n";
+
+        // Two file descriptors sharing a client, and an unrelated file.
+        let process = root.join("42");
+        fs::create_dir_all(process.join("fd")).unwrap();
+        fs::create_dir_all(process.join("fdinfo")).unwrap();
+        for (fd, target) in [
+            ("3", "/dev/dri/card0"),
+            ("4", "/dev/dri/renderD128"),
+            ("5", "/tmp"),
+        ] {
+            std::os::unix::fs::symlink(target, process.join("fd").join(fd)).unwrap();
+            fs::write(process.join("fdinfo").join(fd), fdinfo).unwrap();
+        }
+
+        // Not a process.
+        fs::create_dir_all(root.join("self_test")).unwrap();
+
+        let clients = read_clients(root);
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].0, 42);
+        assert_eq!(clients[0].1.client_id, 3);
+
+        assert!(read_clients(&root.join("missing")).is_empty());
+    }
+}
@@ -1,70 +1,190 @@
 use super::MemHarvest;
 
-/// Return ARC usage.
+/// The ARC stats that bottom uses, in bytes for sizes.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ArcStats {
+    pub size: u64,
+    pub c_max: u64,
+    pub hits: Option<u64>,
+    pub misses: Option<u64>,
+    pub breakdown: ArcBreakdown,
+}
+
+/// How much of the ARC holds data and metadata, and the size of the L2ARC, in
+/// bytes. Each is [`None`] if it isn't known.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArcBreakdown {
+    pub data: Option<u64>,
+    pub metadata: Option<u64>,
+    pub l2_size: Option<u64>,
+}
+
+impl ArcStats {
+    /// Return ARC usage.
+    pub fn usage(&self) -> MemHarvest {
+        MemHarvest {
+            total_bytes: self.c_max,
+            used_bytes: self.size,
+        }
+    }
+
+    /// The percentage of ARC lookups that were hits since the ARC was created
+    /// (usually at boot). This is [`None`] if the hits or misses are unknown,
+    /// or if there haven't been any lookups yet.
+    pub fn hit_ratio(&self) -> Option<f64> {
+        let hits = self.hits?;
+        let lookups = hits.checked_add(self.misses?)?;
+
+        (lookups > 0).then(|| hits as f64 / lookups as f64 * 100.0)
+    }
+}
+
+/// Parses the contents of `/proc/spl/kstat/zfs/arcstats`. After a header,
+/// each line has a stat's name, type, and value, separated by whitespace.
+/// Stats that are missing or can't be parsed are left at their defaults.
+#[cfg(any(target_os = "linux", test))]
+fn parse_arcstats(arc_stats: &str) -> ArcStats {
+    let mut stats = ArcStats::default();
+
+    for line in arc_stats.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(label), Some(_type), Some(value)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+
+        // Remember that the sizes are in bytes!
+        let Ok(value) = value.parse::<u64>() else {
+            continue;
+        };
+
+        match label {
+            "size" => stats.size = value,
+            "c_max" => stats.c_max = value,
+            "hits" => stats.hits = Some(value),
+            "misses" => stats.misses = Some(value),
+            "data_size" => stats.breakdown.data = Some(value),
+            "metadata_size" => stats.breakdown.metadata = Some(value),
+            // Older modules only have the total metadata usage.
+            "arc_meta_used" => {
+                stats.breakdown.metadata.get_or_insert(value);
+            }
+            "l2_size" => stats.breakdown.l2_size = Some(value),
+            _ => {}
+        }
+    }
+
+    stats
+}
+
+/// Return the ARC's stats. If they can't be read, everything is zero or
+/// unknown.
 #[cfg(feature = "zfs")]
-pub(crate) fn get_arc_usage() -> Option<MemHarvest> {
-    let (mem_total, mem_used) = {
-        cfg_if::cfg_if! {
-            if #[cfg(target_os = "linux")] {
-                // TODO: [OPT] is this efficient?
-                use std::fs::read_to_string;
-                if let Ok(arc_stats) = read_to_string("/proc/spl/kstat/zfs/arcstats") {
-                    let mut mem_arc = 0;
-                    let mut mem_total = 0;
-                    let mut zfs_keys_read: u8 = 0;
-                    const ZFS_KEYS_NEEDED: u8 = 2;
-
-                    for line in arc_stats.lines() {
-                        if let Some((label, value)) = line.split_once(' ') {
-                            let to_write = match label {
-                                "size" => &mut mem_arc,
-                                "c_max" => &mut mem_total,
-                                _ => {
-                                    continue;
-                                }
-                            };
-
-                            if let Some((_type, number)) = value.trim_start().rsplit_once(' ') {
-                                // Parse the value, remember it's in bytes!
-                                if let Ok(number) = number.parse::<u64>() {
-                                    *to_write = number;
-                                    // We only need a few keys, so we can bail early.
-                                    zfs_keys_read += 1;
-                                    if zfs_keys_read == ZFS_KEYS_NEEDED {
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    (mem_total, mem_arc)
-                } else {
-                    (0, 0)
-                }
-            } else if #[cfg(target_os = "freebsd")] {
-                use sysctl::Sysctl;
-                if let (Ok(mem_arc_value), Ok(mem_sys_value)) = (
-                    sysctl::Ctl::new("kstat.zfs.misc.arcstats.size"),
-                    sysctl::Ctl::new("kstat.zfs.misc.arcstats.c_max"),
-                ) {
-                    if let (Ok(sysctl::CtlValue::U64(arc)), Ok(sysctl::CtlValue::Ulong(mem))) =
-                        (mem_arc_value.value(), mem_sys_value.value())
-                    {
-                        (mem, arc)
-                    } else {
-                        (0, 0)
-                    }
-                } else {
-                    (0, 0)
+pub(crate) fn get_arc_stats() -> ArcStats {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            match std::fs::read_to_string("/proc/spl/kstat/zfs/arcstats") {
+                Ok(arc_stats) => parse_arcstats(&arc_stats),
+                Err(_) => ArcStats::default(),
+            }
+        } else if #[cfg(target_os = "freebsd")] {
+            use sysctl::Sysctl;
+
+            fn read_u64(name: &str) -> Option<u64> {
+                match sysctl::Ctl::new(name).ok()?.value().ok()? {
+                    sysctl::CtlValue::U64(value) => Some(value),
+                    sysctl::CtlValue::Ulong(value) => Some(value as u64),
+                    _ => None,
                 }
-            } else {
-                (0, 0)
             }
+
+            let (size, c_max) = match (
+                read_u64("kstat.zfs.misc.arcstats.size"),
+                read_u64("kstat.zfs.misc.arcstats.c_max"),
+            ) {
+                (Some(size), Some(c_max)) => (size, c_max),
+                _ => (0, 0),
+            };
+
+            ArcStats {
+                size,
+                c_max,
+                hits: read_u64("kstat.zfs.misc.arcstats.hits"),
+                misses: read_u64("kstat.zfs.misc.arcstats.misses"),
+                breakdown: ArcBreakdown {
+                    data: read_u64("kstat.zfs.misc.arcstats.data_size"),
+                    metadata: read_u64("kstat.zfs.misc.arcstats.metadata_size")
+                        .or_else(|| read_u64("kstat.zfs.misc.arcstats.arc_meta_used")),
+                    l2_size: read_u64("kstat.zfs.misc.arcstats.l2_size"),
+                },
+            }
+        } else {
+            ArcStats::default()
         }
-    };
+    }
+}
 
-    Some(MemHarvest {
-        total_bytes: mem_total,
-        used_bytes: mem_used,
-    })
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_arcstats() {
+        let arc_stats = "\
+13 1 0x01 147 39984 5296148437 2147254530291
+name                            type data
+hits                            4    970000
+misses                          4    30000
+demand_data_hits                4    123
+size                            4    1073741824
+c_max                           4    4294967296
+data_size                       4    805306368
+metadata_size                   4    201326592
+arc_meta_used                   4    268435456
+l2_size                         4    17179869184
+";
+
+        let stats = parse_arcstats(arc_stats);
+        assert_eq!(
+            stats,
+            ArcStats {
+                size: 1073741824,
+                c_max: 4294967296,
+                hits: Some(970000),
+                misses: Some(30000),
+                breakdown: ArcBreakdown {
+                    data: Some(805306368),
+                    metadata: Some(201326592),
+                    l2_size: Some(17179869184),
+                },
+            }
+        );
+        assert_eq!(stats.hit_ratio(), Some(97.0));
+        assert_eq!(stats.usage().checked_percent(), Some(25.0));
+    }
+
+    #[test]
+    fn test_hit_ratio_without_lookups() {
+        // Nothing has been looked up yet.
+        let stats = parse_arcstats("hits 4 0\nmisses 4 0\n");
+        assert_eq!(stats.hit_ratio(), None);
+
+        // Older modules without the counters.
+        let stats = parse_arcstats("size 4 100\nc_max 4 200\n");
+        assert_eq!(stats.hit_ratio(), None);
+        assert_eq!(parse_arcstats(""), ArcStats::default());
+    }
+
+    #[test]
+    fn test_older_metadata_stat() {
+        let stats = parse_arcstats("arc_meta_used 4 1024\ndata_size 4 2048\n");
+        assert_eq!(
+            stats.breakdown,
+            ArcBreakdown {
+                data: Some(2048),
+                metadata: Some(1024),
+                l2_size: None,
+            }
+        );
+    }
 }
@@ -10,11 +10,44 @@ pub struct NetworkHarvest {
     pub tx: u64,
     pub total_rx: u64,
     pub total_tx: u64,
+    /// How much was received since the previous harvest.
+    pub rx_delta: u64,
+    /// How much was transmitted since the previous harvest.
+    pub tx_delta: u64,
 }
 
 impl NetworkHarvest {
     pub fn first_run_cleanup(&mut self) {
         self.rx = 0;
         self.tx = 0;
+        self.rx_delta = 0;
+        self.tx_delta = 0;
+    }
+}
+
+/// Returns the names of the given interfaces for listing, sorted by name.
+pub fn interface_listing(interfaces: &[(String, NetworkHarvest)]) -> Vec<String> {
+    let mut names = interfaces
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    names.sort();
+
+    names
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn interface_listing_reflects_harvest() {
+        let harvest = ["wlan0", "eth0", "lo"]
+            .into_iter()
+            .map(|name| (name.to_string(), NetworkHarvest::default()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(interface_listing(&harvest), vec!["eth0", "lo", "wlan0"]);
+        assert!(interface_listing(&[]).is_empty());
     }
 }
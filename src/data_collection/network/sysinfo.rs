@@ -2,50 +2,168 @@
 
 use std::time::Instant;
 
+use hashbrown::HashMap;
 use sysinfo::Networks;
 
 use super::NetworkHarvest;
 use crate::app::filter::Filter;
 
-// TODO: Eventually make it so that this thing also takes individual usage into
-// account, so we can show per-interface!
+/// Returns the network usage summed across all interfaces that pass the filter,
+/// as well as the usage of each of those interfaces.
 pub fn get_network_data(
-    networks: &Networks, prev_net_access_time: Instant, prev_net_rx: &mut u64,
-    prev_net_tx: &mut u64, curr_time: Instant, filter: &Option<Filter>,
-) -> NetworkHarvest {
+    networks: &Networks, prev_net_access_time: Instant,
+    prev_totals: &mut HashMap<String, (u64, u64)>, curr_time: Instant, filter: &Option<Filter>,
+) -> (NetworkHarvest, Vec<(String, NetworkHarvest)>) {
+    let interfaces = networks
+        .iter()
+        .filter(|(name, _)| Filter::optional_should_keep(filter, name))
+        .map(|(name, network)| {
+            (
+                name.as_str(),
+                network.total_received() * 8,
+                network.total_transmitted() * 8,
+            )
+        });
+
+    let elapsed_time = curr_time.duration_since(prev_net_access_time).as_secs_f64();
+
+    aggregate_interfaces(interfaces, elapsed_time, prev_totals)
+}
+
+/// Aggregates the per-interface totals (in bits) into a single [`NetworkHarvest`],
+/// alongside a [`NetworkHarvest`] for each interface.
+///
+/// Only interfaces that were also seen in the previous harvest contribute to the
+/// rates, so an interface that just appeared (e.g. a VPN tunnel) won't cause a
+/// spike. Interfaces that are no longer present are dropped from `prev_totals`.
+/// A total lower than the previous one means the counter was reset (e.g. the
+/// interface went down and back up), so it is all counted as new traffic.
+fn aggregate_interfaces<'a>(
+    interfaces: impl Iterator<Item = (&'a str, u64, u64)>, elapsed_time: f64,
+    prev_totals: &mut HashMap<String, (u64, u64)>,
+) -> (NetworkHarvest, Vec<(String, NetworkHarvest)>) {
+    let rate = |delta: u64| {
+        if elapsed_time == 0.0 {
+            0
+        } else {
+            (delta as f64 / elapsed_time) as u64
+        }
+    };
+
     let mut total_rx: u64 = 0;
     let mut total_tx: u64 = 0;
+    let mut rx_delta: u64 = 0;
+    let mut tx_delta: u64 = 0;
 
-    for (name, network) in networks {
-        let to_keep = if let Some(filter) = filter {
-            filter.should_keep(name)
-        } else {
-            true
+    let delta = |curr: u64, prev: u64| if curr < prev { curr } else { curr - prev };
+
+    let mut curr_totals = HashMap::with_capacity(prev_totals.len());
+    let mut per_interface = Vec::with_capacity(prev_totals.len());
+
+    for (name, rx, tx) in interfaces {
+        total_rx += rx;
+        total_tx += tx;
+
+        let (interface_rx_delta, interface_tx_delta) = match prev_totals.get(name) {
+            Some((prev_rx, prev_tx)) => (delta(rx, *prev_rx), delta(tx, *prev_tx)),
+            None => (0, 0),
         };
+        rx_delta += interface_rx_delta;
+        tx_delta += interface_tx_delta;
 
-        if to_keep {
-            total_rx += network.total_received() * 8;
-            total_tx += network.total_transmitted() * 8;
-        }
+        curr_totals.insert(name.to_string(), (rx, tx));
+        per_interface.push((
+            name.to_string(),
+            NetworkHarvest {
+                rx: rate(interface_rx_delta),
+                tx: rate(interface_tx_delta),
+                total_rx: rx,
+                total_tx: tx,
+                rx_delta: interface_rx_delta,
+                tx_delta: interface_tx_delta,
+            },
+        ));
     }
 
-    let elapsed_time = curr_time.duration_since(prev_net_access_time).as_secs_f64();
+    *prev_totals = curr_totals;
+    per_interface.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
 
-    let (rx, tx) = if elapsed_time == 0.0 {
-        (0, 0)
-    } else {
-        (
-            ((total_rx.saturating_sub(*prev_net_rx)) as f64 / elapsed_time) as u64,
-            ((total_tx.saturating_sub(*prev_net_tx)) as f64 / elapsed_time) as u64,
-        )
-    };
-
-    *prev_net_rx = total_rx;
-    *prev_net_tx = total_tx;
-    NetworkHarvest {
-        rx,
-        tx,
+    let harvest = NetworkHarvest {
+        rx: rate(rx_delta),
+        tx: rate(tx_delta),
         total_rx,
         total_tx,
+        rx_delta,
+        tx_delta,
+    };
+
+    (harvest, per_interface)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new_interfaces_only_set_baseline() {
+        let mut prev = HashMap::new();
+
+        let (harvest, _) = aggregate_interfaces([("eth0", 800, 1600)].into_iter(), 1.0, &mut prev);
+        assert_eq!((harvest.rx, harvest.tx), (0, 0));
+        assert_eq!((harvest.total_rx, harvest.total_tx), (800, 1600));
+
+        let (harvest, per_interface) = aggregate_interfaces(
+            [("eth0", 1000, 2000), ("tun0", 5000, 5000)].into_iter(),
+            2.0,
+            &mut prev,
+        );
+        assert_eq!((harvest.rx, harvest.tx), (100, 200));
+        assert_eq!((harvest.total_rx, harvest.total_tx), (6000, 7000));
+        assert!(prev.contains_key("tun0"));
+
+        assert_eq!(per_interface.len(), 2);
+        assert_eq!(per_interface[0].0, "eth0");
+        assert_eq!((per_interface[0].1.rx, per_interface[0].1.tx), (100, 200));
+        assert_eq!(per_interface[1].0, "tun0");
+        assert_eq!((per_interface[1].1.rx, per_interface[1].1.tx), (0, 0));
+        assert_eq!(per_interface[1].1.total_rx, 5000);
+    }
+
+    #[test]
+    fn removed_interfaces_are_dropped() {
+        let mut prev = HashMap::new();
+
+        aggregate_interfaces(
+            [("eth0", 100, 100), ("tun0", 100, 100)].into_iter(),
+            1.0,
+            &mut prev,
+        );
+        let (harvest, _) = aggregate_interfaces([("eth0", 200, 300)].into_iter(), 1.0, &mut prev);
+
+        assert_eq!((harvest.rx, harvest.tx), (100, 200));
+        assert!(!prev.contains_key("tun0"));
+    }
+
+    #[test]
+    fn counter_resets_are_new_traffic() {
+        let mut prev = HashMap::new();
+
+        aggregate_interfaces([("eth0", 1000, 1000)].into_iter(), 1.0, &mut prev);
+        let (harvest, _) = aggregate_interfaces([("eth0", 1500, 200)].into_iter(), 1.0, &mut prev);
+
+        assert_eq!((harvest.rx_delta, harvest.tx_delta), (500, 200));
+        assert_eq!((harvest.rx, harvest.tx), (500, 200));
+    }
+
+    #[test]
+    fn empty_match_gives_zeros() {
+        let mut prev = HashMap::new();
+
+        aggregate_interfaces([("eth0", 100, 100)].into_iter(), 1.0, &mut prev);
+        let (harvest, _) = aggregate_interfaces(std::iter::empty(), 1.0, &mut prev);
+
+        assert_eq!((harvest.rx, harvest.tx), (0, 0));
+        assert_eq!((harvest.total_rx, harvest.total_tx), (0, 0));
+        assert!(prev.is_empty());
     }
 }
@@ -17,6 +17,8 @@ pub static NVML_DATA: OnceLock<Result<Nvml, NvmlError>> = OnceLock::new();
 
 pub struct GpusData {
     pub memory: Option<Vec<(String, MemHarvest)>>,
+    /// How busy each GPU's cores are, as a percentage.
+    pub utilization: Option<Vec<(String, f64)>>,
     pub temperature: Option<Vec<TempHarvest>>,
     pub procs: Option<(u64, Vec<HashMap<u32, (u64, u32)>>)>,
 }
@@ -53,6 +55,7 @@ pub fn get_nvidia_vecs(
         if let Ok(num_gpu) = nvml.device_count() {
             let mut temp_vec = Vec::with_capacity(num_gpu as usize);
             let mut mem_vec = Vec::with_capacity(num_gpu as usize);
+            let mut util_vec = Vec::with_capacity(num_gpu as usize);
             let mut proc_vec = Vec::with_capacity(num_gpu as usize);
             let mut total_mem = 0;
 
@@ -71,6 +74,12 @@ pub fn get_nvidia_vecs(
                             }
                         }
 
+                        if widgets_to_harvest.use_gpu_util {
+                            if let Ok(utilization) = device.utilization_rates() {
+                                util_vec.push((name.clone(), f64::from(utilization.gpu)));
+                            }
+                        }
+
                         if widgets_to_harvest.use_temp
                             && Filter::optional_should_keep(filter, &name)
                         {
@@ -165,6 +174,11 @@ pub fn get_nvidia_vecs(
                 } else {
                     None
                 },
+                utilization: if !util_vec.is_empty() {
+                    Some(util_vec)
+                } else {
+                    None
+                },
                 temperature: if !temp_vec.is_empty() {
                     Some(temp_vec)
                 } else {
@@ -31,7 +31,10 @@ cfg_if! {
     }
 }
 
-use std::{borrow::Cow, time::Duration};
+use std::{borrow::Cow, path::PathBuf, time::Duration};
+
+#[cfg(feature = "gpu")]
+use hashbrown::HashMap;
 
 use super::{error::CollectionResult, DataCollector};
 
@@ -45,6 +48,13 @@ cfg_if! {
     }
 }
 
+/// Whether [`ProcessHarvest::last_cpu`] is reported on this platform.
+pub const HAS_LAST_CPU: bool = cfg!(target_os = "linux");
+
+/// What is shown as a process' user or group if it isn't known, such as on
+/// platforms without user info.
+pub(crate) const UNKNOWN_NAME: &str = "N/A";
+
 #[derive(Debug, Clone, Default)]
 pub struct ProcessHarvest {
     /// The pid of the process.
@@ -80,12 +90,26 @@ pub struct ProcessHarvest {
     /// The total number of bytes written by the process.
     pub total_write_bytes: u64,
 
+    /// Whether the process' I/O couldn't be read (e.g. if permission was
+    /// denied), in which case its I/O values are all zero.
+    pub io_unknown: bool,
+
     /// The current state of the process (e.g. zombie, asleep).
     pub process_state: (String, char),
 
     /// Cumulative process uptime.
     pub time: Duration,
 
+    /// When the process started, in a platform-specific unit, or 0 if unknown.
+    /// This is only used to tell apart processes that reuse the same PID.
+    pub start_time: u64,
+
+    /// The CPU core this process last ran on. This is only available on Linux.
+    pub last_cpu: Option<usize>,
+
+    /// The path of the process' executable. This is only available on Linux.
+    pub exe: Option<PathBuf>,
+
     /// This is the *effective* user ID of the process. This is only used on
     /// Unix platforms.
     #[cfg(target_family = "unix")]
@@ -95,23 +119,40 @@ pub struct ProcessHarvest {
     /// This is the process' user.
     pub user: Cow<'static, str>,
 
-    /// Gpu memory usage as bytes.
+    /// This is the *effective* group ID of the process. This is only used on
+    /// Unix platforms.
+    #[cfg(target_family = "unix")]
+    #[allow(dead_code)]
+    pub gid: Option<libc::gid_t>,
+
+    /// This is the process' group.
+    pub group: Cow<'static, str>,
+
+    /// Gpu memory usage as bytes. This is [`None`] if no GPU lists the
+    /// process.
     #[cfg(feature = "gpu")]
-    pub gpu_mem: u64,
+    pub gpu_mem: Option<u64>,
 
-    /// Gpu memory usage as percentage.
+    /// Gpu memory usage as percentage. This is [`None`] if no GPU lists the
+    /// process, or if the GPUs' total memory isn't known.
     #[cfg(feature = "gpu")]
-    pub gpu_mem_percent: f32,
+    pub gpu_mem_percent: Option<f32>,
 
-    /// Gpu utilization as a percentage.
+    /// Gpu utilization as a percentage. This is [`None`] if no GPU lists the
+    /// process.
     #[cfg(feature = "gpu")]
-    pub gpu_util: u32,
+    pub gpu_util: Option<u32>,
     // TODO: Additional fields
     // pub rss_kb: u64,
     // pub virt_kb: u64,
 }
 
 impl ProcessHarvest {
+    /// Returns the process' user, or [`None`] if it isn't known.
+    pub(crate) fn known_user(&self) -> Option<&str> {
+        Some(self.user.as_ref()).filter(|user| !user.is_empty() && *user != UNKNOWN_NAME)
+    }
+
     pub(crate) fn add(&mut self, rhs: &ProcessHarvest) {
         self.cpu_usage_percent += rhs.cpu_usage_percent;
         self.mem_usage_bytes += rhs.mem_usage_bytes;
@@ -120,16 +161,51 @@ impl ProcessHarvest {
         self.write_bytes_per_sec += rhs.write_bytes_per_sec;
         self.total_read_bytes += rhs.total_read_bytes;
         self.total_write_bytes += rhs.total_write_bytes;
+        self.io_unknown &= rhs.io_unknown;
         self.time = self.time.max(rhs.time);
         #[cfg(feature = "gpu")]
         {
-            self.gpu_mem += rhs.gpu_mem;
-            self.gpu_util += rhs.gpu_util;
-            self.gpu_mem_percent += rhs.gpu_mem_percent;
+            self.gpu_mem = add_options(self.gpu_mem, rhs.gpu_mem);
+            self.gpu_util = add_options(self.gpu_util, rhs.gpu_util);
+            self.gpu_mem_percent = add_options(self.gpu_mem_percent, rhs.gpu_mem_percent);
+        }
+    }
+
+    /// Sets the process' GPU memory and utilization from each GPU's list of
+    /// processes, summed across the GPUs. If no GPU lists the process, it is
+    /// left without any.
+    #[cfg(feature = "gpu")]
+    pub(crate) fn set_gpu_usage(
+        &mut self, gpu_pids: &[HashMap<u32, (u64, u32)>], gpus_total_mem: Option<u64>,
+    ) {
+        self.gpu_mem = None;
+        self.gpu_util = None;
+        self.gpu_mem_percent = None;
+
+        for (mem, util) in gpu_pids
+            .iter()
+            .filter_map(|gpu| gpu.get(&(self.pid as u32)))
+        {
+            *self.gpu_mem.get_or_insert(0) += mem;
+            *self.gpu_util.get_or_insert(0) += util;
+        }
+
+        if let (Some(gpu_mem), Some(gpu_total_mem)) = (self.gpu_mem, gpus_total_mem) {
+            self.gpu_mem_percent = Some((gpu_mem as f64 / gpu_total_mem as f64 * 100.0) as f32);
         }
     }
 }
 
+/// Adds two optional values, where a missing value is treated as nothing
+/// rather than zero.
+#[cfg(feature = "gpu")]
+fn add_options<T: std::ops::Add<Output = T>>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a + b),
+        (value, None) | (None, value) => value,
+    }
+}
+
 impl DataCollector {
     pub(crate) fn get_processes(&mut self) -> CollectionResult<Vec<ProcessHarvest>> {
         cfg_if! {
@@ -5,6 +5,7 @@ mod process;
 use std::{
     fs::{self, File},
     io::{BufRead, BufReader},
+    path::Path,
     time::Duration,
 };
 
@@ -12,7 +13,7 @@ use hashbrown::HashSet;
 use process::*;
 use sysinfo::ProcessStatus;
 
-use super::{Pid, ProcessHarvest, UserTable};
+use super::{Pid, ProcessHarvest, UserTable, UNKNOWN_NAME};
 use crate::data_collection::{error::CollectionResult, DataCollector};
 
 /// Maximum character length of a `/proc/<PID>/stat`` process name.
@@ -135,9 +136,11 @@ fn read_proc(
     let Process {
         pid: _,
         uid,
+        gid,
         stat,
         io,
         cmdline,
+        exe,
     } = process;
 
     let ReadProcArgs {
@@ -196,6 +199,7 @@ fn read_proc(
     let mem_usage_percent = (mem_usage_bytes as f64 / total_memory as f64 * 100.0) as f32;
 
     // This can fail if permission is denied!
+    let io_unknown = io.is_err();
     let (total_read_bytes, total_write_bytes, read_bytes_per_sec, write_bytes_per_sec) =
         if let Ok(io) = io {
             let total_read_bytes = io.read_bytes;
@@ -224,13 +228,11 @@ fn read_proc(
         };
 
     let user = uid
-        .and_then(|uid| {
-            user_table
-                .get_uid_to_username_mapping(uid)
-                .map(Into::into)
-                .ok()
-        })
-        .unwrap_or_else(|| "N/A".into());
+        .map(|uid| user_table.username(uid).into())
+        .unwrap_or_else(|| UNKNOWN_NAME.into());
+    let group = gid
+        .map(|gid| user_table.groupname(gid).into())
+        .unwrap_or_else(|| UNKNOWN_NAME.into());
 
     let time = if let Ok(ticks_per_sec) = u32::try_from(rustix::param::clock_ticks_per_second()) {
         if ticks_per_sec == 0 {
@@ -255,16 +257,22 @@ fn read_proc(
             write_bytes_per_sec,
             total_read_bytes,
             total_write_bytes,
+            io_unknown,
             process_state,
             uid,
             user,
+            gid,
+            group,
             time,
+            start_time: stat.start_time,
+            last_cpu: stat.last_cpu,
+            exe,
             #[cfg(feature = "gpu")]
-            gpu_mem: 0,
+            gpu_mem: None,
             #[cfg(feature = "gpu")]
-            gpu_mem_percent: 0.0,
+            gpu_mem_percent: None,
             #[cfg(feature = "gpu")]
-            gpu_util: 0,
+            gpu_util: None,
         },
         new_process_times,
     ))
@@ -284,6 +292,24 @@ fn is_str_numeric(s: &str) -> bool {
     s.chars().all(|c| c.is_ascii_digit())
 }
 
+/// Returns whether the `/etc/passwd` we look users up in differs from init's,
+/// given the `/proc` at `proc_root`. This is the case if we're in a container
+/// that can see the host's processes, where our users don't belong to the
+/// system that their uids are from. Just being in another mount namespace, such
+/// as under snap, `unshare -m` or `PrivateTmp`, usually still shows the same
+/// file.
+///
+/// If either file can't be read (e.g. as it needs more permissions), this
+/// assumes they are the same.
+pub(crate) fn has_foreign_passwd(proc_root: &Path) -> bool {
+    let passwd = |pid: &str| fs::read(proc_root.join(pid).join("root/etc/passwd")).ok();
+
+    match (passwd("self"), passwd("1")) {
+        (Some(ours), Some(init)) => ours != init,
+        _ => false,
+    }
+}
+
 /// General args to keep around for reading proc data.
 #[derive(Copy, Clone)]
 pub(crate) struct ReadProcArgs {
@@ -307,6 +333,7 @@ pub(crate) fn linux_process_data(
         use_current_cpu_total: collector.use_current_cpu_total,
         unnormalized_cpu: collector.unnormalized_cpu,
     };
+    let read_exe = collector.widgets_to_harvest.use_proc_exe;
     let pid_mapping = &mut collector.pid_mapping;
     let user_table = &mut collector.user_table;
 
@@ -356,7 +383,7 @@ pub(crate) fn linux_process_data(
 
     let process_vector: Vec<ProcessHarvest> = pids
         .filter_map(|pid_path| {
-            if let Ok(process) = Process::from_path(pid_path) {
+            if let Ok(process) = Process::from_path(pid_path, read_exe) {
                 let pid = process.pid;
                 let prev_proc_details = pid_mapping.entry(pid).or_default();
 
@@ -366,18 +393,7 @@ pub(crate) fn linux_process_data(
                 {
                     #[cfg(feature = "gpu")]
                     if let Some(gpus) = &collector.gpu_pids {
-                        gpus.iter().for_each(|gpu| {
-                            // add mem/util for all gpus to pid
-                            if let Some((mem, util)) = gpu.get(&(pid as u32)) {
-                                process_harvest.gpu_mem += mem;
-                                process_harvest.gpu_util += util;
-                            }
-                        });
-                        if let Some(gpu_total_mem) = &collector.gpus_total_mem {
-                            process_harvest.gpu_mem_percent =
-                                (process_harvest.gpu_mem as f64 / *gpu_total_mem as f64 * 100.0)
-                                    as f32;
-                        }
+                        process_harvest.set_gpu_usage(gpus, collector.gpus_total_mem);
                     }
 
                     prev_proc_details.cpu_time = new_process_times;
@@ -404,6 +420,52 @@ pub(crate) fn linux_process_data(
 mod tests {
     use super::*;
 
+    /// Creates a `/proc`-style snapshot with the given `/etc/passwd` contents
+    /// as seen by ourselves and by init.
+    fn proc_snapshot(ours: &str, init: Option<&str>) -> tempfile::TempDir {
+        let proc_root = tempfile::tempdir().unwrap();
+
+        for (pid, passwd) in [("self", Some(ours)), ("1", init)] {
+            let etc_dir = proc_root.path().join(pid).join("root/etc");
+            fs::create_dir_all(&etc_dir).unwrap();
+
+            if let Some(passwd) = passwd {
+                fs::write(etc_dir.join("passwd"), passwd).unwrap();
+            }
+        }
+
+        proc_root
+    }
+
+    #[test]
+    fn host_processes_in_container() {
+        // A container sharing the host's PID namespace, where uid 0 has been
+        // looked up against the container's passwd.
+        let proc_root = proc_snapshot(
+            "root:x:0:0::/root:/bin/sh\n",
+            Some("root:x:0:0::/root:/bin/bash\nalice:x:1000:1000::/home/alice:/bin/bash\n"),
+        );
+        assert!(has_foreign_passwd(proc_root.path()));
+
+        let mut user_table = UserTable::default();
+        let _ = user_table.get_uid_to_username_mapping(0);
+        user_table.set_numeric(has_foreign_passwd(proc_root.path()));
+        assert_eq!(user_table.username(0), "0");
+    }
+
+    #[test]
+    fn processes_with_the_same_passwd() {
+        // E.g. under snap or `unshare -m`, which are in another mount
+        // namespace but still see the host's passwd.
+        let passwd = "root:x:0:0::/root:/bin/bash\n";
+        let proc_root = proc_snapshot(passwd, Some(passwd));
+        assert!(!has_foreign_passwd(proc_root.path()));
+
+        // Not being able to read init's passwd isn't treated as a mismatch.
+        let proc_root = proc_snapshot(passwd, None);
+        assert!(!has_foreign_passwd(proc_root.path()));
+    }
+
     #[test]
     fn test_proc_cpu_parse() {
         assert_eq!(
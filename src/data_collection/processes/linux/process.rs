@@ -2,14 +2,16 @@
 //! Based on the [procfs](https://github.com/eminence/procfs) crate.
 
 use std::{
+    ffi::OsString,
     fs::File,
     io::{self, BufRead, BufReader, Read},
+    os::unix::ffi::OsStringExt,
     path::PathBuf,
     sync::OnceLock,
 };
 
 use anyhow::anyhow;
-use libc::uid_t;
+use libc::{gid_t, uid_t};
 use rustix::{
     fd::OwnedFd,
     fs::{Mode, OFlags},
@@ -55,6 +57,9 @@ pub(crate) struct Stat {
 
     /// The start time of the process, represented in clock ticks.
     pub start_time: u64,
+
+    /// The CPU number the process last executed on, if reported.
+    pub last_cpu: Option<usize>,
 }
 
 impl Stat {
@@ -101,6 +106,10 @@ impl Stat {
         let mut rest = rest.skip(1);
         let rss: u64 = next_part(&mut rest)?.parse()?;
 
+        // Skip 14 fields until the processor field (rsslim through exit_signal).
+        let mut rest = rest.skip(14);
+        let last_cpu = next_part(&mut rest).ok().and_then(|p| p.parse().ok());
+
         Ok(Stat {
             comm,
             state,
@@ -109,6 +118,7 @@ impl Stat {
             stime,
             rss,
             start_time,
+            last_cpu,
         })
     }
 
@@ -130,8 +140,8 @@ pub(crate) struct Io {
 
 impl Io {
     #[inline]
-    fn from_file(f: File, buffer: &mut String) -> anyhow::Result<Io> {
-        const NUM_FIELDS: u16 = 0; // Make sure to update this if you want more fields!
+    fn from_file(f: impl Read, buffer: &mut String) -> anyhow::Result<Io> {
+        const NUM_FIELDS: u16 = 2; // Make sure to update this if you want more fields!
         enum Fields {
             ReadBytes,
             WriteBytes,
@@ -203,9 +213,11 @@ impl Io {
 pub(crate) struct Process {
     pub pid: Pid,
     pub uid: Option<uid_t>,
+    pub gid: Option<gid_t>,
     pub stat: Stat,
     pub io: anyhow::Result<Io>,
     pub cmdline: anyhow::Result<Vec<String>>,
+    pub exe: Option<PathBuf>,
 }
 
 #[inline]
@@ -216,14 +228,15 @@ fn reset(root: &mut PathBuf, buffer: &mut String) {
 
 impl Process {
     /// Creates a new [`Process`] given a `/proc/<PID>` path. This may fail if
-    /// the process no longer exists or there are permissions issues.
+    /// the process no longer exists or there are permissions issues. Where its
+    /// executable is is only read if `read_exe` is set.
     ///
     /// Note that this pre-allocates fields on **creation**! As such, some data
     /// might end up "outdated" depending on when you call some of the
     /// methods. Therefore, this struct is only useful for either fields
     /// that are unlikely to change, or are short-lived and
     /// will be discarded quickly.
-    pub(crate) fn from_path(pid_path: PathBuf) -> anyhow::Result<Process> {
+    pub(crate) fn from_path(pid_path: PathBuf, read_exe: bool) -> anyhow::Result<Process> {
         // TODO: Pass in a buffer vec/string to share?
 
         let fd = rustix::fs::openat(
@@ -245,11 +258,11 @@ impl Process {
             })
             .ok_or_else(|| anyhow!("PID for {pid_path:?} was not found"))?;
 
-        let uid = {
+        let (uid, gid) = {
             let metadata = rustix::fs::fstat(&fd);
             match metadata {
-                Ok(md) => Some(md.st_uid),
-                Err(_) => None,
+                Ok(md) => (Some(md.st_uid), Some(md.st_gid)),
+                Err(_) => (None, None),
             }
         };
 
@@ -267,12 +280,20 @@ impl Process {
 
         let io = open_at(&mut root, "io", &fd).and_then(|file| Io::from_file(file, &mut buffer));
 
+        // This will fail for kernel threads, or if permission is denied.
+        let exe = read_exe
+            .then(|| rustix::fs::readlinkat(&fd, "exe", vec![]).ok())
+            .flatten()
+            .map(|exe| PathBuf::from(OsString::from_vec(exe.into_bytes())));
+
         Ok(Process {
             pid,
             uid,
+            gid,
             stat,
             io,
             cmdline,
+            exe,
         })
     }
 }
@@ -306,3 +327,27 @@ fn open_at(root: &mut PathBuf, child: &str, fd: &OwnedFd) -> anyhow::Result<File
 
     Ok(File::from(new_fd))
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_io_from_file() {
+        let contents = "\
+rchar: 323934931
+wchar: 323929600
+syscr: 632687
+syscw: 632675
+read_bytes: 4096
+write_bytes: 323932160
+cancelled_write_bytes: 0
+";
+
+        let io = Io::from_file(contents.as_bytes(), &mut String::new()).unwrap();
+        assert_eq!(io.read_bytes, 4096);
+        assert_eq!(io.write_bytes, 323932160);
+
+        assert!(Io::from_file("read_bytes: lots\n".as_bytes(), &mut String::new()).is_err());
+    }
+}
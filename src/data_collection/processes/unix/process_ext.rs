@@ -5,7 +5,7 @@ use std::{io, time::Duration};
 use hashbrown::HashMap;
 use sysinfo::{ProcessStatus, System};
 
-use super::ProcessHarvest;
+use super::{ProcessHarvest, UNKNOWN_NAME};
 use crate::data_collection::{error::CollectionResult, processes::UserTable, Pid};
 
 pub(crate) trait UnixProcessExt {
@@ -63,6 +63,7 @@ pub(crate) trait UnixProcessExt {
                 (ps.to_string(), convert_process_status_to_char(ps))
             };
             let uid = process_val.user_id().map(|u| **u);
+            let gid = process_val.group_id().map(|g| *g);
             let pid = process_val.pid().as_u32() as Pid;
             process_vector.push(ProcessHarvest {
                 pid,
@@ -80,16 +81,16 @@ pub(crate) trait UnixProcessExt {
                 write_bytes_per_sec: disk_usage.written_bytes,
                 total_read_bytes: disk_usage.total_read_bytes,
                 total_write_bytes: disk_usage.total_written_bytes,
+                io_unknown: false,
                 process_state,
                 uid,
                 user: uid
-                    .and_then(|uid| {
-                        user_table
-                            .get_uid_to_username_mapping(uid)
-                            .map(Into::into)
-                            .ok()
-                    })
-                    .unwrap_or_else(|| "N/A".into()),
+                    .map(|uid| user_table.username(uid).into())
+                    .unwrap_or_else(|| UNKNOWN_NAME.into()),
+                gid,
+                group: gid
+                    .map(|gid| user_table.groupname(gid).into())
+                    .unwrap_or_else(|| UNKNOWN_NAME.into()),
                 time: if process_val.start_time() == 0 {
                     // Workaround for sysinfo occasionally returning a start time equal to UNIX
                     // epoch, giving a run time in the range of 50+ years. We just
@@ -100,12 +101,15 @@ pub(crate) trait UnixProcessExt {
                 } else {
                     Duration::from_secs(process_val.run_time())
                 },
+                start_time: process_val.start_time(),
+                last_cpu: None,
+                exe: None,
                 #[cfg(feature = "gpu")]
-                gpu_mem: 0,
+                gpu_mem: None,
                 #[cfg(feature = "gpu")]
-                gpu_mem_percent: 0.0,
+                gpu_mem_percent: None,
                 #[cfg(feature = "gpu")]
-                gpu_util: 0,
+                gpu_util: None,
             });
         }
 
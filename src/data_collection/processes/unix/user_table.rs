@@ -1,32 +1,244 @@
+use std::{
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
 use hashbrown::HashMap;
 
 use crate::data_collection::error::{CollectionError, CollectionResult};
 
+/// How long a looked-up user or group name is trusted before it is looked up
+/// again, in case it was renamed or the id was reused.
+const USERNAME_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Names by id, along with when they were looked up.
+type NameMapping<Id> = HashMap<Id, (String, Instant)>;
+
 #[derive(Debug, Default)]
 pub struct UserTable {
-    pub uid_user_mapping: HashMap<libc::uid_t, String>,
+    /// Usernames by uid, along with when they were looked up.
+    pub uid_user_mapping: NameMapping<libc::uid_t>,
+
+    /// Group names by gid, along with when they were looked up.
+    pub gid_group_mapping: NameMapping<libc::gid_t>,
+
+    /// Whether to show uids and gids instead of user and group names.
+    numeric: bool,
 }
 
 impl UserTable {
-    pub fn get_uid_to_username_mapping(&mut self, uid: libc::uid_t) -> CollectionResult<String> {
-        if let Some(user) = self.uid_user_mapping.get(&uid) {
-            Ok(user.clone())
+    /// Sets whether to show uids and gids instead of looking up user and group
+    /// names, e.g. if the processes are from a different system than our
+    /// `/etc/passwd`.
+    pub fn set_numeric(&mut self, numeric: bool) {
+        self.numeric = numeric;
+    }
+
+    /// Returns the name to show for `uid`. This is the uid itself if usernames
+    /// aren't being shown, or if the uid has no passwd entry.
+    pub fn username(&mut self, uid: libc::uid_t) -> String {
+        if self.numeric {
+            uid.to_string()
         } else {
-            // SAFETY: getpwuid returns a null pointer if no passwd entry is found for the
-            // uid
-            let passwd = unsafe { libc::getpwuid(uid) };
-
-            if passwd.is_null() {
-                Err("passwd is inaccessible".into())
-            } else {
-                // SAFETY: We return early if passwd is null.
-                let username = unsafe { std::ffi::CStr::from_ptr((*passwd).pw_name) }
-                    .to_str()
-                    .map_err(|err| CollectionError::General(err.into()))?
-                    .to_string();
-                self.uid_user_mapping.insert(uid, username.clone());
-
-                Ok(username)
+            self.get_uid_to_username_mapping(uid)
+                .unwrap_or_else(|_| uid.to_string())
+        }
+    }
+
+    /// Returns the name to show for `gid`. This is the gid itself if group
+    /// names aren't being shown, or if the gid has no group entry.
+    pub fn groupname(&mut self, gid: libc::gid_t) -> String {
+        if self.numeric {
+            gid.to_string()
+        } else {
+            self.get_gid_to_groupname_mapping(gid)
+                .unwrap_or_else(|_| gid.to_string())
+        }
+    }
+
+    pub fn get_uid_to_username_mapping(&mut self, uid: libc::uid_t) -> CollectionResult<String> {
+        get_or_resolve(
+            &mut self.uid_user_mapping,
+            uid,
+            Instant::now(),
+            resolve_username,
+        )
+    }
+
+    pub fn get_gid_to_groupname_mapping(&mut self, gid: libc::gid_t) -> CollectionResult<String> {
+        get_or_resolve(
+            &mut self.gid_group_mapping,
+            gid,
+            Instant::now(),
+            resolve_groupname,
+        )
+    }
+
+    /// Forgets all looked-up user and group names.
+    pub fn clear(&mut self) {
+        self.uid_user_mapping.clear();
+        self.gid_group_mapping.clear();
+    }
+}
+
+/// Returns the cached name for `id` if it was looked up within
+/// [`USERNAME_TTL`] of `now`, and otherwise looks it up again with `resolve`.
+fn get_or_resolve<Id: Copy + Eq + Hash>(
+    mapping: &mut NameMapping<Id>, id: Id, now: Instant,
+    resolve: impl FnOnce(Id) -> CollectionResult<String>,
+) -> CollectionResult<String> {
+    if let Some((name, resolved_at)) = mapping.get(&id) {
+        if now.saturating_duration_since(*resolved_at) < USERNAME_TTL {
+            return Ok(name.clone());
+        }
+    }
+
+    match resolve(id) {
+        Ok(name) => {
+            mapping.insert(id, (name.clone(), now));
+            Ok(name)
+        }
+        Err(err) => {
+            // Don't keep showing a name that no longer exists.
+            mapping.remove(&id);
+            Err(err)
+        }
+    }
+}
+
+fn resolve_username(uid: libc::uid_t) -> CollectionResult<String> {
+    // SAFETY: getpwuid returns a null pointer if no passwd entry is found for the
+    // uid
+    let passwd = unsafe { libc::getpwuid(uid) };
+
+    if passwd.is_null() {
+        Err("passwd is inaccessible".into())
+    } else {
+        // SAFETY: We return early if passwd is null.
+        let username = unsafe { std::ffi::CStr::from_ptr((*passwd).pw_name) }
+            .to_str()
+            .map_err(|err| CollectionError::General(err.into()))?
+            .to_string();
+
+        Ok(username)
+    }
+}
+
+fn resolve_groupname(gid: libc::gid_t) -> CollectionResult<String> {
+    // SAFETY: getgrgid returns a null pointer if no group entry is found for the
+    // gid
+    let group = unsafe { libc::getgrgid(gid) };
+
+    if group.is_null() {
+        Err("group is inaccessible".into())
+    } else {
+        // SAFETY: We return early if group is null.
+        let groupname = unsafe { std::ffi::CStr::from_ptr((*group).gr_name) }
+            .to_str()
+            .map_err(|err| CollectionError::General(err.into()))?
+            .to_string();
+
+        Ok(groupname)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn usernames_expire() {
+        let mut table = UserTable::default();
+        let lookups = Cell::new(0);
+        let resolve = |name: &'static str| {
+            let lookups = &lookups;
+            move |_uid| {
+                lookups.set(lookups.get() + 1);
+                Ok(name.to_string())
+            }
+        };
+
+        let start = Instant::now();
+        assert_eq!(
+            get_or_resolve(&mut table.uid_user_mapping, 1000, start, resolve("alice")).unwrap(),
+            "alice"
+        );
+        assert_eq!(lookups.get(), 1);
+
+        // Within the TTL, the cached name is used.
+        let later = start + USERNAME_TTL - Duration::from_secs(1);
+        assert_eq!(
+            get_or_resolve(&mut table.uid_user_mapping, 1000, later, resolve("bob")).unwrap(),
+            "alice"
+        );
+        assert_eq!(lookups.get(), 1);
+
+        // Past it, the name is looked up again.
+        let expired = start + USERNAME_TTL + Duration::from_secs(1);
+        assert_eq!(
+            get_or_resolve(&mut table.uid_user_mapping, 1000, expired, resolve("bob")).unwrap(),
+            "bob"
+        );
+        assert_eq!(lookups.get(), 2);
+
+        // A failed lookup drops the stale name.
+        let expired = expired + USERNAME_TTL;
+        assert!(
+            get_or_resolve(&mut table.uid_user_mapping, 1000, expired, |_| Err(
+                "passwd is inaccessible".into()
+            ))
+            .is_err()
+        );
+        assert!(table.uid_user_mapping.is_empty());
+    }
+
+    #[test]
+    fn clear_forgets_usernames() {
+        let mut table = UserTable::default();
+        let now = Instant::now();
+        get_or_resolve(&mut table.uid_user_mapping, 1000, now, |_| {
+            Ok("alice".to_string())
+        })
+        .unwrap();
+
+        table.clear();
+        assert_eq!(
+            get_or_resolve(&mut table.uid_user_mapping, 1000, now, |_| {
+                Ok("bob".to_string())
+            })
+            .unwrap(),
+            "bob"
+        );
+    }
+
+    #[test]
+    fn numeric_usernames() {
+        let mut table = UserTable::default();
+        table
+            .uid_user_mapping
+            .insert(0, ("root".into(), Instant::now()));
+        assert_eq!(table.username(0), "root");
+
+        table.set_numeric(true);
+        assert_eq!(table.username(0), "0");
+        assert_eq!(table.groupname(0), "0");
+    }
+
+    #[test]
+    fn root_group() {
+        // gid 0 is "root" on Linux and "wheel" on the BSDs and macOS, but may not
+        // exist at all in some sandboxes.
+        let mut table = UserTable::default();
+        match table.get_gid_to_groupname_mapping(0) {
+            Ok(name) => {
+                assert!(name == "root" || name == "wheel", "unexpected name {name}");
+                assert!(table.gid_group_mapping.contains_key(&0));
+            }
+            Err(_) => {
+                assert!(table.gid_group_mapping.is_empty());
+                assert_eq!(table.groupname(0), "0");
             }
         }
     }
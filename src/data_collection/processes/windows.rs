@@ -2,7 +2,7 @@
 
 use std::time::Duration;
 
-use super::ProcessHarvest;
+use super::{ProcessHarvest, UNKNOWN_NAME};
 use crate::data_collection::{error::CollectionResult, DataCollector};
 
 // TODO: There's a lot of shared code with this and the unix impl.
@@ -62,26 +62,8 @@ pub fn sysinfo_process_data(
         let disk_usage = process_val.disk_usage();
         let process_state = (process_val.status().to_string(), 'R');
 
-        #[cfg(feature = "gpu")]
-        let (gpu_mem, gpu_util, gpu_mem_percent) = {
-            let mut gpu_mem = 0;
-            let mut gpu_util = 0;
-            let mut gpu_mem_percent = 0.0;
-            if let Some(gpus) = &collector.gpu_pids {
-                gpus.iter().for_each(|gpu| {
-                    // add mem/util for all gpus to pid
-                    if let Some((mem, util)) = gpu.get(&process_val.pid().as_u32()) {
-                        gpu_mem += mem;
-                        gpu_util += util;
-                    }
-                });
-            }
-            if let Some(gpu_total_mem) = &collector.gpus_total_mem {
-                gpu_mem_percent = (gpu_mem as f64 / *gpu_total_mem as f64 * 100.0) as f32;
-            }
-            (gpu_mem, gpu_util, gpu_mem_percent)
-        };
-        process_vector.push(ProcessHarvest {
+        #[cfg_attr(not(feature = "gpu"), expect(unused_mut))]
+        let mut process_harvest = ProcessHarvest {
             pid: process_val.pid().as_u32() as _,
             parent_pid: process_val.parent().map(|p| p.as_u32() as _),
             name,
@@ -97,11 +79,13 @@ pub fn sysinfo_process_data(
             write_bytes_per_sec: disk_usage.written_bytes,
             total_read_bytes: disk_usage.total_read_bytes,
             total_write_bytes: disk_usage.total_written_bytes,
+            io_unknown: false,
             process_state,
             user: process_val
                 .user_id()
                 .and_then(|uid| users.get_user_by_id(uid))
-                .map_or_else(|| "N/A".into(), |user| user.name().to_owned().into()),
+                .map_or_else(|| UNKNOWN_NAME.into(), |user| user.name().to_owned().into()),
+            group: UNKNOWN_NAME.into(),
             time: if process_val.start_time() == 0 {
                 // Workaround for sysinfo occasionally returning a start time equal to UNIX
                 // epoch, giving a run time in the range of 50+ years. We just
@@ -112,13 +96,23 @@ pub fn sysinfo_process_data(
             } else {
                 Duration::from_secs(process_val.run_time())
             },
+            start_time: process_val.start_time(),
+            last_cpu: None,
+            exe: None,
             #[cfg(feature = "gpu")]
-            gpu_mem,
+            gpu_mem: None,
             #[cfg(feature = "gpu")]
-            gpu_util,
+            gpu_util: None,
             #[cfg(feature = "gpu")]
-            gpu_mem_percent,
-        });
+            gpu_mem_percent: None,
+        };
+
+        #[cfg(feature = "gpu")]
+        if let Some(gpus) = &collector.gpu_pids {
+            process_harvest.set_gpu_usage(gpus, collector.gpus_total_mem);
+        }
+
+        process_vector.push(process_harvest);
     }
 
     Ok(process_vector)
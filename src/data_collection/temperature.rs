@@ -62,6 +62,15 @@ impl TemperatureType {
             TemperatureType::Fahrenheit => convert_celsius_to_fahrenheit(temp_celsius),
         }
     }
+
+    /// Given a temperature in this unit, convert it back to Celsius.
+    pub fn to_celsius(self, temp: f32) -> f32 {
+        match self {
+            TemperatureType::Celsius => temp,
+            TemperatureType::Kelvin => temp - 273.15,
+            TemperatureType::Fahrenheit => (temp - 32.0) * 5.0 / 9.0,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -81,5 +90,8 @@ mod test {
         assert_eq!(TemperatureType::Kelvin.convert_temp_unit(TEMP), 373.15);
 
         assert_eq!(TemperatureType::Fahrenheit.convert_temp_unit(TEMP), 212.0);
+
+        assert_eq!(TemperatureType::Kelvin.to_celsius(373.15), TEMP);
+        assert_eq!(TemperatureType::Fahrenheit.to_celsius(212.0), TEMP);
     }
 }
@@ -13,6 +13,9 @@ use crate::app::filter::Filter;
 
 const EMPTY_NAME: &str = "Unknown";
 
+/// Where sysfs is mounted.
+const SYSFS: &str = "/sys";
+
 /// Returned results from grabbing hwmon/coretemp temperature sensor
 /// values/names.
 struct HwmonResults {
@@ -26,12 +29,13 @@ fn parse_temp(path: &Path) -> Result<f32> {
     Ok(fs::read_to_string(path)?.trim_end().parse::<f32>()? / 1_000.0)
 }
 
-/// Get all candidates from hwmon and coretemp. It will also return the number
-/// of entries from hwmon.
-fn get_hwmon_candidates() -> (HashSet<PathBuf>, usize) {
+/// Get all candidates from hwmon and coretemp under the given sysfs root. It
+/// will also return the number of entries from hwmon.
+fn get_hwmon_candidates(sysfs: &Path) -> (HashSet<PathBuf>, usize) {
     let mut dirs = HashSet::default();
+    let hwmon = sysfs.join("class/hwmon");
 
-    if let Ok(read_dir) = Path::new("/sys/class/hwmon").read_dir() {
+    if let Ok(read_dir) = hwmon.read_dir() {
         for entry in read_dir.flatten() {
             let mut path = entry.path();
 
@@ -58,7 +62,7 @@ fn get_hwmon_candidates() -> (HashSet<PathBuf>, usize) {
 
     let num_hwmon = dirs.len();
 
-    if let Ok(read_dir) = Path::new("/sys/devices/platform").read_dir() {
+    if let Ok(read_dir) = sysfs.join("devices/platform").read_dir() {
         for entry in read_dir.flatten() {
             if entry.file_name().to_string_lossy().starts_with("coretemp.") {
                 if let Ok(read_dir) = entry.path().join("hwmon").read_dir() {
@@ -73,7 +77,7 @@ fn get_hwmon_candidates() -> (HashSet<PathBuf>, usize) {
                             //
                             // For more info, see https://github.com/giampaolo/psutil/pull/1822/files
                             if let Some(child) = path.file_name() {
-                                let to_check_path = Path::new("/sys/class/hwmon").join(child);
+                                let to_check_path = hwmon.join(child);
 
                                 if !dirs.contains(&to_check_path) {
                                     dirs.insert(path);
@@ -223,11 +227,13 @@ fn is_device_awake(path: &Path) -> bool {
 /// the device is already in ACPI D0. This has the notable issue that
 /// once this happens, the device will be *kept* on through the sensor
 /// reading, and not be able to re-enter ACPI D3cold.
-fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> HwmonResults {
+fn hwmon_temperatures(
+    sysfs: &Path, temp_type: &TemperatureType, filter: &Option<Filter>,
+) -> HwmonResults {
     let mut temperatures: Vec<TempHarvest> = vec![];
     let mut seen_names: HashMap<String, u32> = HashMap::new();
 
-    let (dirs, num_hwmon) = get_hwmon_candidates();
+    let (dirs, num_hwmon) = get_hwmon_candidates(sysfs);
 
     // Note that none of this is async if we ever go back to it, but sysfs is in
     // memory, so in theory none of this should block if we're slightly careful.
@@ -352,10 +358,10 @@ fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> H
 /// See [the Linux kernel documentation](https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-thermal)
 /// for more details.
 fn add_thermal_zone_temperatures(
-    temperatures: &mut Vec<TempHarvest>, temp_type: &TemperatureType, filter: &Option<Filter>,
+    sysfs: &Path, temperatures: &mut Vec<TempHarvest>, temp_type: &TemperatureType,
+    filter: &Option<Filter>,
 ) {
-    let path = Path::new("/sys/class/thermal");
-    let Ok(read_dir) = path.read_dir() else {
+    let Ok(read_dir) = sysfs.join("class/thermal").read_dir() else {
         return;
     };
 
@@ -393,24 +399,46 @@ fn add_thermal_zone_temperatures(
     }
 }
 
+/// Gets temperature sensors and data from the given sysfs root.
+fn read_temperatures(
+    sysfs: &Path, temp_type: &TemperatureType, filter: &Option<Filter>,
+) -> Vec<TempHarvest> {
+    let mut results = hwmon_temperatures(sysfs, temp_type, filter);
+
+    if results.num_hwmon == 0 {
+        add_thermal_zone_temperatures(sysfs, &mut results.temperatures, temp_type, filter);
+    }
+
+    results.temperatures
+}
+
 /// Gets temperature sensors and data.
 pub fn get_temperature_data(
     temp_type: &TemperatureType, filter: &Option<Filter>,
 ) -> Result<Option<Vec<TempHarvest>>> {
-    let mut results = hwmon_temperatures(temp_type, filter);
+    Ok(Some(read_temperatures(Path::new(SYSFS), temp_type, filter)))
+}
 
-    if results.num_hwmon == 0 {
-        add_thermal_zone_temperatures(&mut results.temperatures, temp_type, filter);
-    }
+/// Returns the names of all temperature sensors, as they would appear in the
+/// temperature widget without any filters.
+pub fn get_sensor_names() -> Vec<String> {
+    sensor_names(Path::new(SYSFS))
+}
 
-    Ok(Some(results.temperatures))
+fn sensor_names(sysfs: &Path) -> Vec<String> {
+    read_temperatures(sysfs, &TemperatureType::Celsius, &None)
+        .into_iter()
+        .map(|temp| temp.name)
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
     use hashbrown::HashMap;
 
-    use super::finalize_name;
+    use super::{finalize_name, sensor_names};
 
     #[test]
     fn test_finalize_name() {
@@ -488,4 +516,33 @@ mod tests {
             "Unknown (4)"
         );
     }
+
+    #[test]
+    fn test_sensor_names_from_sysfs() {
+        let sysfs = tempfile::tempdir().unwrap();
+        let write = |path: &str, contents: &str| {
+            let path = sysfs.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+
+        write("class/hwmon/hwmon0/name", "k10temp\n");
+        write("class/hwmon/hwmon0/temp1_input", "45000\n");
+        write("class/hwmon/hwmon0/temp1_label", "Tctl\n");
+        write("class/hwmon/hwmon0/temp2_input", "40000\n");
+        write("class/hwmon/hwmon0/temp2_label", "Tccd1\n");
+        write("class/hwmon/hwmon1/name", "nvme\n");
+        write("class/hwmon/hwmon1/temp1_input", "38000\n");
+
+        // Thermal zones are only used if there are no hwmon entries.
+        write("class/thermal/thermal_zone0/type", "acpitz\n");
+        write("class/thermal/thermal_zone0/temp", "30000\n");
+
+        let mut names = sensor_names(sysfs.path());
+        names.sort();
+        assert_eq!(names, vec!["k10temp: Tccd1", "k10temp: Tctl", "nvme"]);
+
+        fs::remove_dir_all(sysfs.path().join("class/hwmon")).unwrap();
+        assert_eq!(sensor_names(sysfs.path()), vec!["acpitz"]);
+    }
 }
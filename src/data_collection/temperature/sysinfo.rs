@@ -5,6 +5,20 @@ use anyhow::Result;
 use super::{TempHarvest, TemperatureType};
 use crate::app::filter::Filter;
 
+/// Returns the names of all temperature sensors, as they would appear in the
+/// temperature widget without any filters.
+pub fn get_sensor_names() -> Vec<String> {
+    let components = sysinfo::Components::new_with_refreshed_list();
+
+    get_temperature_data(&components, &TemperatureType::Celsius, &None)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|temp| temp.name)
+        .collect()
+}
+
 pub fn get_temperature_data(
     components: &sysinfo::Components, temp_type: &TemperatureType, filter: &Option<Filter>,
 ) -> Result<Option<Vec<TempHarvest>>> {
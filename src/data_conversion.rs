@@ -3,12 +3,18 @@
 
 // TODO: Split this up!
 
-use std::borrow::Cow;
+use std::{borrow::Cow, time::Instant};
+
+use hashbrown::HashMap;
 
 use crate::{
-    app::{data_farmer::DataCollection, AxisScaling},
-    canvas::components::time_chart::Point,
-    data_collection::{cpu::CpuDataType, memory::MemHarvest, temperature::TemperatureType},
+    app::{data_farmer::DataCollection, time_markers::TimeMarker, AxisScaling},
+    canvas::components::time_chart::{Point, VerticalMarker},
+    data_collection::{
+        cpu::{CpuDataType, CpuTimeKind},
+        memory::MemHarvest,
+        temperature::TemperatureType,
+    },
     utils::{data_prefixes::*, data_units::DataUnit},
     widgets::{DiskWidgetData, TempWidgetData},
 };
@@ -30,6 +36,15 @@ pub struct ConvertedNetworkData {
     pub total_tx_display: Option<String>,
 }
 
+#[derive(Default, Debug)]
+pub struct ConvertedInterfaceData {
+    pub name: String,
+    pub rx: Vec<Point>,
+    pub tx: Vec<Point>,
+    pub rx_display: String,
+    pub tx_display: String,
+}
+
 #[derive(Clone, Debug)]
 pub enum CpuWidgetData {
     All,
@@ -41,6 +56,14 @@ pub enum CpuWidgetData {
     },
 }
 
+/// The share of CPU time of one kind, such as user time, over time.
+#[derive(Clone, Debug)]
+pub struct CpuBreakdownData {
+    pub kind: CpuTimeKind,
+    pub data: Vec<Point>,
+    pub last_entry: f64,
+}
+
 #[derive(Default)]
 pub struct ConvertedData {
     pub rx_display: String,
@@ -49,6 +72,10 @@ pub struct ConvertedData {
     pub total_tx_display: String,
     pub network_data_rx: Vec<Point>,
     pub network_data_tx: Vec<Point>,
+    pub network_interface_data: Vec<ConvertedInterfaceData>,
+    /// The highest RX and TX rates seen this session, as where they are drawn on
+    /// the graph and as text. See [`convert_network_session_max`].
+    pub network_session_max: [Option<(f64, String)>; 2],
 
     pub mem_labels: Option<(String, String)>,
     #[cfg(not(target_os = "windows"))]
@@ -66,29 +93,93 @@ pub struct ConvertedData {
     pub swap_data: Vec<Point>,
 
     #[cfg(feature = "zfs")]
-    pub arc_labels: Option<(String, String)>,
+    pub arc_labels: Option<ArcLabels>,
     #[cfg(feature = "zfs")]
     pub arc_data: Vec<Point>,
+    /// The labels of the ARC's data/metadata split and the L2ARC's size.
+    #[cfg(feature = "zfs")]
+    pub arc_detail_labels: Vec<String>,
 
     #[cfg(feature = "gpu")]
     pub gpu_data: Option<Vec<ConvertedGpuData>>,
+    #[cfg(feature = "gpu")]
+    pub gpu_util_data: Vec<ConvertedGpuUtilData>,
+
+    /// The charge of each battery over time, split into chunks wherever the
+    /// battery was missing.
+    #[cfg(feature = "battery")]
+    pub battery_data: Vec<Vec<Vec<Point>>>,
+
+    /// Where each gap in the data ends, as x-positions. Graphs don't connect
+    /// points across these.
+    pub gaps: Vec<f64>,
+
+    /// The points in time marked on the graphs, oldest first.
+    pub time_markers: Vec<VerticalMarker<'static>>,
 
     pub load_avg_data: [f32; 3],
     pub cpu_data: Vec<CpuWidgetData>,
+    /// How CPU time was split over time, for each kind of time that is
+    /// tracked.
+    pub cpu_breakdown_data: Vec<CpuBreakdownData>,
 
     pub disk_data: Vec<DiskWidgetData>,
     pub temp_data: Vec<TempWidgetData>,
 }
 
 impl ConvertedData {
+    /// Whether no CPU usage has been collected yet, such as at startup or
+    /// after a reset.
+    pub fn is_collecting_cpu(&self) -> bool {
+        self.cpu_data.iter().all(|cpu| match cpu {
+            CpuWidgetData::All => true,
+            CpuWidgetData::Entry { data, .. } => data.is_empty(),
+        })
+    }
+
+    /// Whether no CPU time breakdown has been collected yet. Both it and the
+    /// CPU usage come in with the second harvest, so this is only while the
+    /// CPU usage is being collected too.
+    pub fn is_collecting_cpu_breakdown(&self) -> bool {
+        self.cpu_breakdown_data.is_empty() && self.is_collecting_cpu()
+    }
+
+    /// Whether the CPU time breakdown isn't collected on this system, as there
+    /// is CPU usage without it.
+    pub fn is_cpu_breakdown_unsupported(&self) -> bool {
+        self.cpu_breakdown_data.is_empty() && !self.is_collecting_cpu()
+    }
+
+    /// Whether no memory usage has been collected yet.
+    pub fn is_collecting_mem(&self) -> bool {
+        self.mem_data.is_empty()
+    }
+
+    /// Whether no GPU utilization has been collected yet.
+    #[cfg(feature = "gpu")]
+    pub fn is_collecting_gpu_util(&self) -> bool {
+        self.gpu_util_data.iter().all(|gpu| gpu.points.is_empty())
+    }
+
+    /// Whether no network usage has been collected yet.
+    pub fn is_collecting_network(&self) -> bool {
+        self.network_data_rx.is_empty() && self.network_data_tx.is_empty()
+    }
+
     // TODO: Can probably heavily reduce this step to avoid clones.
-    pub fn convert_disk_data(&mut self, data: &DataCollection) {
+    /// Converts the harvested disks for the disk widgets. `aliases` maps mount
+    /// points and disk names to what is shown instead.
+    pub fn convert_disk_data(
+        &mut self, data: &DataCollection, compact_bytes: bool, aliases: &HashMap<String, String>,
+    ) {
+        let alias = |value: &str| aliases.get(value).map(|alias| Cow::Owned(alias.clone()));
+
         self.disk_data.clear();
 
         data.disk_harvest
             .iter()
-            .zip(&data.io_labels)
-            .for_each(|(disk, (io_read, io_write))| {
+            .zip(&data.io_rates)
+            .for_each(|(disk, io_rate)| {
                 // Because this sometimes does *not* equal to disk.total.
                 let summed_total_bytes = match (disk.used_space, disk.free_space) {
                     (Some(used), Some(free)) => Some(used + free),
@@ -98,12 +189,20 @@ impl ConvertedData {
                 self.disk_data.push(DiskWidgetData {
                     name: Cow::Owned(disk.name.to_string()),
                     mount_point: Cow::Owned(disk.mount_point.to_string()),
+                    name_alias: alias(&disk.name),
+                    mount_alias: alias(&disk.mount_point),
+                    file_system: match &disk.file_system {
+                        Some(file_system) => Cow::Owned(file_system.to_string()),
+                        None => Cow::Borrowed("N/A"),
+                    },
                     free_bytes: disk.free_space,
                     used_bytes: disk.used_space,
                     total_bytes: disk.total_space,
                     summed_total_bytes,
-                    io_read: Cow::Owned(io_read.to_string()),
-                    io_write: Cow::Owned(io_write.to_string()),
+                    io_read_rate: io_rate.map(|(read, _)| read),
+                    io_write_rate: io_rate.map(|(_, write)| write),
+                    pool: disk.pool.clone(),
+                    compact_bytes,
                 });
             });
 
@@ -113,10 +212,23 @@ impl ConvertedData {
     pub fn convert_temp_data(&mut self, data: &DataCollection, temperature_type: TemperatureType) {
         self.temp_data.clear();
 
+        let mut name_counts: HashMap<&str, usize> = HashMap::new();
         data.temp_harvest.iter().for_each(|temp_harvest| {
+            let stats = data.temp_stats.get(&temp_harvest.name);
+            let name_count = name_counts.entry(&temp_harvest.name).or_default();
+            let sensor_index = *name_count;
+            *name_count += 1;
+
             self.temp_data.push(TempWidgetData {
                 sensor: Cow::Owned(temp_harvest.name.to_string()),
+                sensor_index,
                 temperature_value: temp_harvest.temperature.map(|temp| temp.ceil() as u64),
+                max_value: stats.map(|stats| stats.max.ceil() as u64),
+                min_value: stats.map(|stats| stats.min.ceil() as u64),
+                trend: temp_harvest
+                    .temperature
+                    .and(stats)
+                    .and_then(|stats| stats.trend()),
                 temperature_type,
             });
         });
@@ -127,58 +239,63 @@ impl ConvertedData {
     pub fn convert_cpu_data(&mut self, current_data: &DataCollection) {
         let current_time = current_data.current_instant;
 
-        // (Re-)initialize the vector if the lengths don't match...
-        if let Some((_time, data)) = &current_data.timed_data_vec.last() {
-            if data.cpu_data.len() + 1 != self.cpu_data.len() {
-                self.cpu_data = Vec::with_capacity(data.cpu_data.len() + 1);
-                self.cpu_data.push(CpuWidgetData::All);
-                self.cpu_data.extend(
-                    data.cpu_data
-                        .iter()
-                        .zip(&current_data.cpu_harvest)
-                        .map(|(cpu_usage, data)| CpuWidgetData::Entry {
-                            data_type: data.data_type,
-                            data: vec![],
-                            last_entry: *cpu_usage,
-                        })
-                        .collect::<Vec<CpuWidgetData>>(),
-                );
-            } else {
-                self.cpu_data
-                    .iter_mut()
-                    .skip(1)
-                    .zip(&data.cpu_data)
-                    .for_each(|(mut cpu, cpu_usage)| match &mut cpu {
-                        CpuWidgetData::All => unreachable!(),
-                        CpuWidgetData::Entry {
-                            data_type: _,
-                            data,
-                            last_entry,
-                        } => {
-                            // A bit faster to just update all the times, so we just clear the
-                            // vector.
-                            data.clear();
-                            *last_entry = *cpu_usage;
-                        }
-                    });
-            }
+        // Entries are displayed ordered by their ID, regardless of the order in which
+        // they were first seen.
+        let mut cpu_ids = current_data.cpu_ids.clone();
+        cpu_ids.sort_unstable();
+
+        // (Re-)initialize the vector if the entries don't match...
+        let is_matching = self.cpu_data.len() == cpu_ids.len() + 1
+            && self
+                .cpu_data
+                .iter()
+                .skip(1)
+                .zip(&cpu_ids)
+                .all(|(cpu, id)| match cpu {
+                    CpuWidgetData::All => false,
+                    CpuWidgetData::Entry { data_type, .. } => data_type == id,
+                });
+
+        if !is_matching {
+            self.cpu_data = Vec::with_capacity(cpu_ids.len() + 1);
+            self.cpu_data.push(CpuWidgetData::All);
+            self.cpu_data
+                .extend(cpu_ids.iter().map(|data_type| CpuWidgetData::Entry {
+                    data_type: *data_type,
+                    data: vec![],
+                    last_entry: 0.0,
+                }));
         }
 
+        let last_timed_data = current_data.timed_data_vec.last().map(|(_time, data)| data);
+
         // TODO: [Opt] Can probably avoid data deduplication - store the shift + data +
         // original once. Now push all the data.
-        for (itx, mut cpu) in &mut self.cpu_data.iter_mut().skip(1).enumerate() {
+        for mut cpu in &mut self.cpu_data.iter_mut().skip(1) {
             match &mut cpu {
                 CpuWidgetData::All => unreachable!(),
                 CpuWidgetData::Entry {
-                    data_type: _,
+                    data_type,
                     data,
-                    last_entry: _,
+                    last_entry,
                 } => {
+                    let Some(index) = current_data.cpu_ids.iter().position(|id| id == data_type)
+                    else {
+                        continue;
+                    };
+
+                    // A bit faster to just update all the times, so we just clear the
+                    // vector.
+                    data.clear();
+                    *last_entry = last_timed_data
+                        .and_then(|timed_data| timed_data.cpu_data.get(index).copied().flatten())
+                        .unwrap_or(0.0);
+
                     for (time, timed_data) in &current_data.timed_data_vec {
                         let time_start: f64 =
                             (current_time.duration_since(*time).as_millis() as f64).floor();
 
-                        if let Some(val) = timed_data.cpu_data.get(itx) {
+                        if let Some(Some(val)) = timed_data.cpu_data.get(index) {
                             data.push((-time_start, *val));
                         }
 
@@ -191,9 +308,77 @@ impl ConvertedData {
                 }
             }
         }
+
+        self.cpu_breakdown_data = convert_cpu_breakdown(current_data);
     }
 }
 
+/// Converts how CPU time was split into points for each kind of time. Kinds
+/// that aren't tracked, such as iowait time outside of Linux, are left out.
+pub fn convert_cpu_breakdown(current_data: &DataCollection) -> Vec<CpuBreakdownData> {
+    let current_time = current_data.current_instant;
+    let mut breakdown = CpuTimeKind::ALL.map(|kind| CpuBreakdownData {
+        kind,
+        data: vec![],
+        last_entry: 0.0,
+    });
+
+    for (time, timed_data) in &current_data.timed_data_vec {
+        if let Some(cpu_breakdown) = &timed_data.cpu_breakdown {
+            let time_start = -(current_time.duration_since(*time).as_millis() as f64).floor();
+
+            for kind in &mut breakdown {
+                if let Some(value) = cpu_breakdown.get(kind.kind) {
+                    kind.data.push((time_start, value));
+                    kind.last_entry = value;
+                }
+            }
+        }
+
+        if *time == current_time {
+            break;
+        }
+    }
+
+    breakdown
+        .into_iter()
+        .filter(|kind| !kind.data.is_empty())
+        .collect()
+}
+
+/// Returns the lines to draw for the marked points in time, relative to
+/// `current_time`, tagged with their number in the list of markers. Markers
+/// newer than `current_time`, such as ones dropped while frozen, are drawn with
+/// the latest data, so that a new marker shows up right away.
+pub fn convert_time_markers(
+    markers: &[TimeMarker], current_time: Instant,
+) -> Vec<VerticalMarker<'static>> {
+    markers
+        .iter()
+        .enumerate()
+        .map(|(index, marker)| VerticalMarker {
+            value: -(current_time
+                .saturating_duration_since(marker.time)
+                .as_millis() as f64)
+                .floor(),
+            tag: (index + 1).to_string().into(),
+            label: marker.label.clone().map(Cow::Owned),
+        })
+        .collect()
+}
+
+/// Returns the x-position of each point that came after a gap in the data (see
+/// [`TimedData::after_gap`](crate::app::data_farmer::TimedData::after_gap)).
+pub fn convert_gaps(data: &DataCollection) -> Vec<f64> {
+    let current_time = data.current_instant;
+
+    data.timed_data_vec
+        .iter()
+        .filter(|(_, data)| data.after_gap)
+        .map(|(time, _)| -(current_time.duration_since(*time).as_millis() as f64).floor())
+        .collect()
+}
+
 pub fn convert_mem_data_points(data: &DataCollection) -> Vec<Point> {
     let mut result: Vec<Point> = Vec::new();
     let current_time = data.current_instant;
@@ -268,23 +453,176 @@ fn get_binary_unit_and_denominator(bytes: u64) -> (&'static str, f64) {
 
 /// Returns the unit type and denominator for given total amount of memory in
 /// kibibytes.
-pub fn convert_mem_label(harvest: &MemHarvest) -> Option<(String, String)> {
+pub fn convert_mem_label(harvest: &MemHarvest, compact_bytes: bool) -> Option<(String, String)> {
     (harvest.total_bytes > 0).then(|| {
         let percentage = harvest.used_bytes as f64 / harvest.total_bytes as f64 * 100.0;
         (format!("{percentage:3.0}%"), {
-            let (unit, denominator) = get_binary_unit_and_denominator(harvest.total_bytes);
-
-            format!(
-                "   {:.1}{}/{:.1}{}",
-                harvest.used_bytes as f64 / denominator,
-                unit,
-                (harvest.total_bytes as f64 / denominator),
-                unit
-            )
+            if compact_bytes {
+                format!(
+                    "   {}/{}",
+                    compact_bytes_string(harvest.used_bytes),
+                    compact_bytes_string(harvest.total_bytes)
+                )
+            } else {
+                let (unit, denominator) = get_binary_unit_and_denominator(harvest.total_bytes);
+
+                format!(
+                    "   {:.1}{}/{:.1}{}",
+                    harvest.used_bytes as f64 / denominator,
+                    unit,
+                    (harvest.total_bytes as f64 / denominator),
+                    unit
+                )
+            }
         })
     })
 }
 
+/// The labels of the ARC's usage and hit ratio.
+#[cfg(feature = "zfs")]
+#[derive(Debug, Default, PartialEq)]
+pub struct ArcLabels {
+    pub percent: String,
+    pub frac: String,
+    /// The ARC's hit ratio, if it is known. This is only shown in the graph's
+    /// legend, as there isn't room for it in basic mode.
+    pub hit_ratio: Option<String>,
+}
+
+/// Like [`convert_mem_label`], but with the ARC's hit ratio if it is known.
+#[cfg(feature = "zfs")]
+pub fn convert_arc_label(
+    harvest: &MemHarvest, hit_ratio: Option<f64>, compact_bytes: bool,
+) -> Option<ArcLabels> {
+    convert_mem_label(harvest, compact_bytes).map(|(percent, frac)| ArcLabels {
+        percent,
+        frac,
+        hit_ratio: hit_ratio.map(|hit_ratio| format!("   {hit_ratio:.1}% hit")),
+    })
+}
+
+/// Returns the labels of the ARC's data/metadata split and the L2ARC's size,
+/// for whichever of them are known. The L2ARC is left out if it is empty,
+/// which is usually because there isn't one.
+#[cfg(feature = "zfs")]
+pub fn convert_arc_detail_labels(
+    breakdown: &crate::data_collection::memory::arc::ArcBreakdown, compact_bytes: bool,
+) -> Vec<String> {
+    let bytes = |bytes: u64| {
+        if compact_bytes {
+            compact_bytes_string(bytes)
+        } else {
+            let (value, unit) = get_binary_bytes(bytes);
+            format!("{value:.1}{unit}")
+        }
+    };
+
+    let mut labels = vec![];
+    if let (Some(data), Some(metadata)) = (breakdown.data, breakdown.metadata) {
+        labels.push(format!(
+            "ARC data/meta:   {}/{}",
+            bytes(data),
+            bytes(metadata)
+        ));
+    }
+    if let Some(l2_size) = breakdown.l2_size.filter(|size| *size > 0) {
+        labels.push(format!("L2ARC:   {}", bytes(l2_size)));
+    }
+
+    labels
+}
+
+/// Scales a network data point (in bits) to what is displayed on the graph.
+pub fn scale_network_point(
+    value: f64, scale_type: &AxisScaling, unit_type: &DataUnit, use_binary_prefix: bool,
+) -> f64 {
+    match scale_type {
+        AxisScaling::Log => {
+            if use_binary_prefix {
+                match unit_type {
+                    // As dividing by 8 is equal to subtracting 4 in base 2!
+                    DataUnit::Byte => value.log2() - 4.0,
+                    DataUnit::Bit => value.log2(),
+                }
+            } else {
+                match unit_type {
+                    DataUnit::Byte => (value / 8.0).log10(),
+                    DataUnit::Bit => value.log10(),
+                }
+            }
+        }
+        AxisScaling::Linear => match unit_type {
+            DataUnit::Byte => value / 8.0,
+            DataUnit::Bit => value,
+        },
+    }
+}
+
+/// Returns the highest RX and TX rates seen this session, in that order, as where
+/// they are drawn on the graph and as labelled text. Directions that haven't
+/// seen any traffic are [`None`].
+pub fn convert_network_session_max(
+    data: &DataCollection, scale_type: &AxisScaling, unit_type: &DataUnit, use_binary_prefix: bool,
+) -> [Option<(f64, String)>; 2] {
+    let (rx, tx) = data.network_session_max;
+    let unit = match unit_type {
+        DataUnit::Byte => "B/s",
+        DataUnit::Bit => "b/s",
+    };
+
+    [("RX", rx), ("TX", tx)].map(|(name, max)| {
+        if max == 0 {
+            return None;
+        }
+
+        let point = scale_network_point(max as f64, scale_type, unit_type, use_binary_prefix);
+        let max = match unit_type {
+            DataUnit::Byte => max / 8,
+            DataUnit::Bit => max,
+        };
+        let (value, unit) = if use_binary_prefix {
+            get_binary_prefix(max, unit)
+        } else {
+            get_decimal_prefix(max, unit)
+        };
+
+        Some((point, format!("{name} max {value:.1}{unit}")))
+    })
+}
+
+/// Returns the text added after the current RX and TX rates, in that order, to
+/// show the highest rates and the totals seen this session.
+pub fn convert_network_session_totals(
+    data: &DataCollection, unit_type: &DataUnit, use_binary_prefix: bool,
+) -> [String; 2] {
+    let (rx_max, tx_max) = data.network_session_max;
+    let (rx_total, tx_total) = data.network_session_total;
+    let unit = match unit_type {
+        DataUnit::Byte => "B/s",
+        DataUnit::Bit => "b/s",
+    };
+
+    [(rx_max, rx_total), (tx_max, tx_total)].map(|(max, total)| {
+        let max = match unit_type {
+            DataUnit::Byte => max / 8,
+            DataUnit::Bit => max,
+        };
+        // Like the totals since boot, these are always in bytes.
+        let (max_value, max_unit) = if use_binary_prefix {
+            get_binary_prefix(max, unit)
+        } else {
+            get_decimal_prefix(max, unit)
+        };
+        let (total_value, total_unit) = if use_binary_prefix {
+            get_binary_bytes(total / 8)
+        } else {
+            get_decimal_bytes(total / 8)
+        };
+
+        format!(" (peak {max_value:.1}{max_unit}, total {total_value:.1}{total_unit})")
+    })
+}
+
 pub fn get_network_points(
     data: &DataCollection, scale_type: &AxisScaling, unit_type: &DataUnit, use_binary_prefix: bool,
 ) -> (Vec<Point>, Vec<Point>) {
@@ -296,33 +634,13 @@ pub fn get_network_points(
     for (time, data) in &data.timed_data_vec {
         let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
 
-        let (rx_data, tx_data) = match scale_type {
-            AxisScaling::Log => {
-                if use_binary_prefix {
-                    match unit_type {
-                        DataUnit::Byte => {
-                            // As dividing by 8 is equal to subtracting 4 in base 2!
-                            ((data.rx_data).log2() - 4.0, (data.tx_data).log2() - 4.0)
-                        }
-                        DataUnit::Bit => ((data.rx_data).log2(), (data.tx_data).log2()),
-                    }
-                } else {
-                    match unit_type {
-                        DataUnit::Byte => {
-                            ((data.rx_data / 8.0).log10(), (data.tx_data / 8.0).log10())
-                        }
-                        DataUnit::Bit => ((data.rx_data).log10(), (data.tx_data).log10()),
-                    }
-                }
-            }
-            AxisScaling::Linear => match unit_type {
-                DataUnit::Byte => (data.rx_data / 8.0, data.tx_data / 8.0),
-                DataUnit::Bit => (data.rx_data, data.tx_data),
-            },
-        };
+        if let (Some(rx_data), Some(tx_data)) = (data.rx_data, data.tx_data) {
+            let rx_data = scale_network_point(rx_data, scale_type, unit_type, use_binary_prefix);
+            let tx_data = scale_network_point(tx_data, scale_type, unit_type, use_binary_prefix);
 
-        rx.push((-time_from_start, rx_data));
-        tx.push((-time_from_start, tx_data));
+            rx.push((-time_from_start, rx_data));
+            tx.push((-time_from_start, tx_data));
+        }
         if *time == current_time {
             break;
         }
@@ -331,6 +649,73 @@ pub fn get_network_points(
     (rx, tx)
 }
 
+/// Returns the graph points and current usage of every network interface in
+/// the latest harvest, ordered by name.
+pub fn convert_network_interface_points(
+    data: &DataCollection, scale_type: &AxisScaling, unit_type: &DataUnit, use_binary_prefix: bool,
+) -> Vec<ConvertedInterfaceData> {
+    let current_time = data.current_instant;
+
+    let unit = match unit_type {
+        DataUnit::Byte => "B/s",
+        DataUnit::Bit => "b/s",
+    };
+    let display = |value: u64| {
+        let value = match unit_type {
+            DataUnit::Byte => value / 8,
+            DataUnit::Bit => value,
+        };
+        let (value, unit) = if use_binary_prefix {
+            get_binary_prefix(value, unit)
+        } else {
+            get_decimal_prefix(value, unit)
+        };
+
+        format!("{value:.1}{unit}")
+    };
+
+    data.network_interface_harvest
+        .iter()
+        .filter_map(|(name, harvest)| {
+            let index = data
+                .network_interface_names
+                .iter()
+                .position(|n| n == name)?;
+
+            let mut rx: Vec<Point> = Vec::new();
+            let mut tx: Vec<Point> = Vec::new();
+
+            for (time, timed_data) in &data.timed_data_vec {
+                if let Some(Some((rx_data, tx_data))) = timed_data.interface_data.get(index) {
+                    let time_from_start: f64 =
+                        (current_time.duration_since(*time).as_millis() as f64).floor();
+
+                    rx.push((
+                        -time_from_start,
+                        scale_network_point(*rx_data, scale_type, unit_type, use_binary_prefix),
+                    ));
+                    tx.push((
+                        -time_from_start,
+                        scale_network_point(*tx_data, scale_type, unit_type, use_binary_prefix),
+                    ));
+                }
+
+                if *time == current_time {
+                    break;
+                }
+            }
+
+            Some(ConvertedInterfaceData {
+                name: name.clone(),
+                rx,
+                tx,
+                rx_display: display(harvest.rx),
+                tx_display: display(harvest.tx),
+            })
+        })
+        .collect()
+}
+
 pub fn convert_network_points(
     data: &DataCollection, need_four_points: bool, scale_type: &AxisScaling, unit_type: &DataUnit,
     use_binary_prefix: bool,
@@ -492,6 +877,33 @@ pub fn dec_bytes_string(value: u64) -> String {
     }
 }
 
+/// Returns a compact string given a value in bytes, using binary units with a
+/// single-letter suffix (e.g. "12K", "345M", "1.2G"). Values below ten get one
+/// decimal place, and values that would round to 1000 or more roll over to the
+/// next unit, so the number never takes up more than three digits.
+pub fn compact_bytes_string(value: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+
+    let mut scaled = value as f64;
+    let mut unit = 0;
+    while scaled >= 999.5 && unit < UNITS.len() - 1 {
+        scaled /= KIBI_LIMIT_F64;
+        unit += 1;
+    }
+
+    if unit > 0 && scaled < 9.95 {
+        format!("{scaled:.1}{}", UNITS[unit])
+    } else {
+        format!("{scaled:.0}{}", UNITS[unit])
+    }
+}
+
+/// Returns a compact string given a value in bytes per second. See
+/// [`compact_bytes_string`].
+pub fn compact_bytes_per_second_string(value: u64) -> String {
+    format!("{}/s", compact_bytes_string(value))
+}
+
 #[cfg(feature = "zfs")]
 pub fn convert_arc_data_points(current_data: &DataCollection) -> Vec<Point> {
     let mut result: Vec<Point> = Vec::new();
@@ -551,16 +963,10 @@ pub fn convert_gpu_data(current_data: &DataCollection) -> Option<Vec<ConvertedGp
         .zip(point_vec)
         .filter_map(|(gpu, points)| {
             (gpu.1.total_bytes > 0).then(|| {
-                let short_name = {
-                    let last_words = gpu.0.split_whitespace().rev().take(2).collect::<Vec<_>>();
-                    let short_name = format!("{} {}", last_words[1], last_words[0]);
-                    short_name
-                };
-
                 let percent = gpu.1.used_bytes as f64 / gpu.1.total_bytes as f64 * 100.0;
 
                 ConvertedGpuData {
-                    name: short_name,
+                    name: short_gpu_name(&gpu.0),
                     points,
                     mem_percent: format!("{percent:3.0}%"),
                     mem_total: {
@@ -585,10 +991,213 @@ pub fn convert_gpu_data(current_data: &DataCollection) -> Option<Vec<ConvertedGp
     }
 }
 
+/// Shortens a GPU's name to its last two words, e.g. "RTX 3080".
+#[cfg(feature = "gpu")]
+fn short_gpu_name(name: &str) -> String {
+    let mut last_words = name.split_whitespace().rev().take(2).collect::<Vec<_>>();
+    last_words.reverse();
+    last_words.join(" ")
+}
+
+/// How busy a GPU has been over time.
+#[cfg(feature = "gpu")]
+#[derive(Default, Debug)]
+pub struct ConvertedGpuUtilData {
+    pub name: String,
+    /// The latest utilization, e.g. " 45%".
+    pub util_label: String,
+    pub points: Vec<Point>,
+}
+
+#[cfg(feature = "gpu")]
+pub fn convert_gpu_util_data(current_data: &DataCollection) -> Vec<ConvertedGpuUtilData> {
+    let current_time = current_data.current_instant;
+    let mut point_vec: Vec<Vec<Point>> = vec![Vec::new(); current_data.gpu_util_harvest.len()];
+
+    for (time, data) in &current_data.timed_data_vec {
+        let time_from_start = -(current_time.duration_since(*time).as_millis() as f64).floor();
+        for (points, value) in point_vec.iter_mut().zip(&data.gpu_util_data) {
+            if let Some(value) = value {
+                points.push((time_from_start, *value));
+            }
+        }
+
+        if *time == current_time {
+            break;
+        }
+    }
+
+    current_data
+        .gpu_util_harvest
+        .iter()
+        .zip(point_vec)
+        .map(|((name, util), points)| ConvertedGpuUtilData {
+            name: short_gpu_name(name),
+            util_label: format!("{util:3.0}%"),
+            points,
+        })
+        .collect()
+}
+
+/// Converts the charge of each battery into points, indexed by
+/// [`BatteryData::id`](crate::data_collection::batteries::BatteryData::id).
+///
+/// A battery's points are split into separate chunks wherever it was missing
+/// from a harvest (e.g. it was unplugged), so that no line is drawn across the
+/// gap.
+#[cfg(feature = "battery")]
+pub fn convert_battery_data_points(current_data: &DataCollection) -> Vec<Vec<Vec<Point>>> {
+    let current_time = current_data.current_instant;
+    let mut result: Vec<Vec<Vec<Point>>> = Vec::new();
+    let mut is_chunk_open: Vec<bool> = Vec::new();
+
+    for (time, data) in &current_data.timed_data_vec {
+        if result.len() < data.battery_data.len() {
+            result.resize_with(data.battery_data.len(), Vec::new);
+            is_chunk_open.resize(data.battery_data.len(), false);
+        }
+
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+
+        for (index, (chunks, is_open)) in result.iter_mut().zip(&mut is_chunk_open).enumerate() {
+            match data.battery_data.get(index).copied().flatten() {
+                Some(charge) => {
+                    if !*is_open {
+                        chunks.push(Vec::new());
+                        *is_open = true;
+                    }
+
+                    if let Some(chunk) = chunks.last_mut() {
+                        chunk.push((-time_from_start, charge));
+                    }
+                }
+                None => {
+                    *is_open = false;
+                }
+            }
+        }
+
+        if *time == current_time {
+            break;
+        }
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn collecting_until_data_comes_in() {
+        let mut data = ConvertedData::default();
+        assert!(data.is_collecting_cpu());
+        assert!(data.is_collecting_mem());
+        assert!(data.is_collecting_network());
+
+        // CPU entries exist before they have any usage in them.
+        data.cpu_data = vec![
+            CpuWidgetData::All,
+            CpuWidgetData::Entry {
+                data_type: CpuDataType::Avg,
+                data: vec![],
+                last_entry: 0.0,
+            },
+        ];
+        assert!(data.is_collecting_cpu());
+
+        if let CpuWidgetData::Entry { data, .. } = &mut data.cpu_data[1] {
+            data.push((0.0, 10.0));
+        }
+        data.mem_data.push((0.0, 50.0));
+        data.network_data_tx.push((0.0, 1.0));
+        assert!(!data.is_collecting_cpu());
+        assert!(!data.is_collecting_mem());
+        assert!(!data.is_collecting_network());
+
+        // CPU usage without a breakdown means the breakdown isn't supported.
+        assert!(!data.is_collecting_cpu_breakdown());
+        assert!(data.is_cpu_breakdown_unsupported());
+    }
+
+    #[cfg(feature = "gpu")]
+    #[test]
+    fn gpu_names_are_shortened() {
+        assert_eq!(short_gpu_name("NVIDIA GeForce RTX 3080"), "RTX 3080");
+        assert_eq!(short_gpu_name("Tesla"), "Tesla");
+        assert_eq!(short_gpu_name(""), "");
+    }
+
+    #[test]
+    fn same_named_sensors_have_distinct_keys() {
+        use crate::{
+            canvas::components::data_table::{DataToCell, RowKey},
+            data_collection::temperature::TempHarvest,
+        };
+
+        let sensor = |name: &str| TempHarvest {
+            name: name.to_string(),
+            temperature: Some(40.0),
+        };
+        let data = DataCollection {
+            temp_harvest: vec![sensor("acpitz"), sensor("nvme"), sensor("acpitz")],
+            ..Default::default()
+        };
+
+        let mut converted = ConvertedData::default();
+        converted.convert_temp_data(&data, TemperatureType::Celsius);
+        let keys = converted
+            .temp_data
+            .iter()
+            .map(|row| row.row_key().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            keys,
+            [
+                RowKey::Name("acpitz".into(), 0),
+                RowKey::Name("nvme".into(), 0),
+                RowKey::Name("acpitz".into(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn network_session_max() {
+        let mut data = DataCollection {
+            network_session_max: (8_000_000, 0),
+            ..Default::default()
+        };
+
+        let [rx, tx] =
+            convert_network_session_max(&data, &AxisScaling::Linear, &DataUnit::Byte, false);
+        assert_eq!(rx, Some((1_000_000.0, "RX max 1.0MB/s".to_string())));
+        assert_eq!(tx, None);
+
+        // On a log scale, the line goes where the same rate would be plotted.
+        data.network_session_max = (1024, 2048);
+        let [rx, tx] = convert_network_session_max(&data, &AxisScaling::Log, &DataUnit::Bit, true);
+        assert_eq!(rx, Some((10.0, "RX max 1.0Kib/s".to_string())));
+        assert_eq!(tx, Some((11.0, "TX max 2.0Kib/s".to_string())));
+    }
+
+    #[test]
+    fn network_session_totals() {
+        let data = DataCollection {
+            network_session_max: (8_000_000, 0),
+            network_session_total: (8 * 2 * 1024 * 1024, 8 * 512),
+            ..Default::default()
+        };
+
+        let [rx, tx] = convert_network_session_totals(&data, &DataUnit::Byte, true);
+        assert_eq!(rx, " (peak 976.6KiB/s, total 2.0MiB)");
+        assert_eq!(tx, " (peak 0.0B/s, total 512.0B)");
+
+        let [rx, _] = convert_network_session_totals(&data, &DataUnit::Bit, false);
+        assert_eq!(rx, " (peak 8.0Mb/s, total 2.1MB)");
+    }
+
     #[test]
     fn test_binary_byte_string() {
         assert_eq!(binary_byte_string(0), "0B".to_string());
@@ -648,4 +1257,256 @@ mod test {
             "10.4TB/s".to_string()
         );
     }
+
+    #[test]
+    fn test_compact_bytes_string() {
+        assert_eq!(compact_bytes_string(0), "0B".to_string());
+        assert_eq!(compact_bytes_string(999), "999B".to_string());
+        assert_eq!(compact_bytes_string(1000), "1.0K".to_string());
+        assert_eq!(compact_bytes_string(12 * KIBI_LIMIT), "12K".to_string());
+        assert_eq!(compact_bytes_string(345 * MEBI_LIMIT), "345M".to_string());
+        assert_eq!(
+            compact_bytes_string((1.2 * GIBI_LIMIT as f64) as u64),
+            "1.2G".to_string()
+        );
+        assert_eq!(
+            compact_bytes_string((10.36 * TEBI_LIMIT as f64) as u64),
+            "10T".to_string()
+        );
+
+        // Rounding up to the next unit.
+        assert_eq!(compact_bytes_string(1023 * KIBI_LIMIT), "1.0M".to_string());
+        assert_eq!(
+            compact_bytes_string((999.4 * MEBI_LIMIT_F64) as u64),
+            "999M".to_string()
+        );
+        assert_eq!(
+            compact_bytes_string((999.6 * MEBI_LIMIT_F64) as u64),
+            "1.0G".to_string()
+        );
+        assert_eq!(
+            compact_bytes_string((9.96 * KIBI_LIMIT_F64) as u64),
+            "10K".to_string()
+        );
+
+        assert_eq!(
+            compact_bytes_per_second_string(2 * MEBI_LIMIT),
+            "2.0M/s".to_string()
+        );
+    }
+
+    #[test]
+    fn cpu_entries_stay_associated_with_hotplug() {
+        use std::time::{Duration, Instant};
+
+        use crate::data_collection::{
+            cpu::{CpuData, CpuDataType},
+            Data,
+        };
+
+        let start = Instant::now();
+        let mut data_collection = DataCollection::default();
+
+        for tick in 0..8_u64 {
+            let offline = (3..6).contains(&tick);
+            let cpu = (0..4)
+                .filter(|core| !(offline && *core == 2))
+                .map(|core| CpuData {
+                    data_type: CpuDataType::Cpu(core),
+                    cpu_usage: (core * 10) as f64 + tick as f64,
+                })
+                .collect();
+
+            data_collection.eat_data(Box::new(Data {
+                collection_time: start + Duration::from_secs(tick),
+                cpu: Some(cpu),
+                ..Default::default()
+            }));
+
+            let mut converted = ConvertedData::default();
+            converted.convert_cpu_data(&data_collection);
+
+            for (core, entry) in converted.cpu_data.iter().skip(1).enumerate() {
+                let CpuWidgetData::Entry {
+                    data_type,
+                    data,
+                    last_entry,
+                } = entry
+                else {
+                    panic!("expected a CPU entry");
+                };
+
+                assert!(matches!(data_type, CpuDataType::Cpu(id) if *id == core));
+
+                let expected_len = if core == 2 {
+                    tick + 1 - (3..6).filter(|t| *t <= tick).count() as u64
+                } else {
+                    tick + 1
+                };
+                assert_eq!(data.len() as u64, expected_len, "core {core}, tick {tick}");
+                assert!(data.iter().all(|(_, value)| (*value as usize) / 10 == core));
+
+                if core == 2 && offline {
+                    assert_eq!(*last_entry, 0.0);
+                } else {
+                    assert_eq!(*last_entry, (core * 10) as f64 + tick as f64);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cpu_breakdown_leaves_out_untracked_times() {
+        use std::time::{Duration, Instant};
+
+        use crate::data_collection::{cpu::CpuBreakdown, Data};
+
+        let start = Instant::now();
+        let mut data_collection = DataCollection::default();
+        let mut converted = ConvertedData::default();
+
+        converted.convert_cpu_data(&data_collection);
+        assert!(converted.is_collecting_cpu_breakdown());
+        assert!(!converted.is_cpu_breakdown_unsupported());
+
+        for (tick, user) in [10.0, 30.0].into_iter().enumerate() {
+            data_collection.eat_data(Box::new(Data {
+                collection_time: start + Duration::from_secs(tick as u64),
+                cpu_breakdown: Some(CpuBreakdown {
+                    user,
+                    system: 5.0,
+                    iowait: Some(2.0),
+                    steal: None,
+                }),
+                ..Default::default()
+            }));
+        }
+        converted.convert_cpu_data(&data_collection);
+
+        let kinds = converted
+            .cpu_breakdown_data
+            .iter()
+            .map(|kind| (kind.kind, kind.data.len(), kind.last_entry))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                (CpuTimeKind::User, 2, 30.0),
+                (CpuTimeKind::System, 2, 5.0),
+                (CpuTimeKind::IoWait, 2, 2.0),
+            ]
+        );
+        assert!(!converted.is_collecting_cpu_breakdown());
+        assert!(!converted.is_cpu_breakdown_unsupported());
+    }
+
+    #[cfg(feature = "zfs")]
+    #[test]
+    fn arc_label_with_hit_ratio() {
+        let harvest = MemHarvest {
+            used_bytes: GIBI_LIMIT,
+            total_bytes: 4 * GIBI_LIMIT,
+        };
+
+        assert_eq!(
+            convert_arc_label(&harvest, Some(97.25), false),
+            Some(ArcLabels {
+                percent: " 25%".to_string(),
+                frac: "   1.0GiB/4.0GiB".to_string(),
+                hit_ratio: Some("   97.2% hit".to_string()),
+            })
+        );
+        assert_eq!(
+            convert_arc_label(&harvest, None, false).map(|labels| (labels.percent, labels.frac)),
+            convert_mem_label(&harvest, false)
+        );
+        assert_eq!(
+            convert_arc_label(&MemHarvest::default(), Some(97.25), false),
+            None
+        );
+    }
+
+    #[cfg(feature = "zfs")]
+    #[test]
+    fn arc_detail_labels() {
+        use crate::data_collection::memory::arc::ArcBreakdown;
+
+        let breakdown = ArcBreakdown {
+            data: Some(3 * GIBI_LIMIT),
+            metadata: Some(GIBI_LIMIT / 2),
+            l2_size: Some(120 * GIBI_LIMIT),
+        };
+        assert_eq!(
+            convert_arc_detail_labels(&breakdown, false),
+            vec![
+                "ARC data/meta:   3.0GiB/512.0MiB".to_string(),
+                "L2ARC:   120.0GiB".to_string()
+            ]
+        );
+        assert_eq!(
+            convert_arc_detail_labels(&breakdown, true),
+            vec![
+                "ARC data/meta:   3.0G/512M".to_string(),
+                "L2ARC:   120G".to_string()
+            ]
+        );
+
+        // Without an L2ARC, or stats for the split.
+        let breakdown = ArcBreakdown {
+            data: Some(GIBI_LIMIT),
+            metadata: None,
+            l2_size: Some(0),
+        };
+        assert!(convert_arc_detail_labels(&breakdown, false).is_empty());
+    }
+
+    #[cfg(feature = "battery")]
+    #[test]
+    fn unplugged_battery_ends_its_chunk() {
+        use std::time::{Duration, Instant};
+
+        use crate::data_collection::{
+            batteries::{BatteryData, BatteryState},
+            Data,
+        };
+
+        let battery = |id, charge_percent| BatteryData {
+            id,
+            charge_percent,
+            power_consumption: 0.0,
+            health_percent: 100.0,
+            state: BatteryState::Unknown,
+        };
+
+        let start = Instant::now();
+        let mut data_collection = DataCollection::default();
+
+        // The first battery is unplugged for ticks 2 and 3.
+        for tick in 0..6_u64 {
+            let list_of_batteries = if (2..4).contains(&tick) {
+                vec![battery(1, 50.0)]
+            } else {
+                vec![battery(0, 90.0 - tick as f64), battery(1, 50.0)]
+            };
+
+            data_collection.eat_data(Box::new(Data {
+                collection_time: start + Duration::from_secs(tick),
+                list_of_batteries: Some(list_of_batteries),
+                ..Default::default()
+            }));
+        }
+
+        let battery_data = convert_battery_data_points(&data_collection);
+
+        assert_eq!(battery_data.len(), 2);
+        assert_eq!(
+            battery_data[0],
+            vec![
+                vec![(-5000.0, 90.0), (-4000.0, 89.0)],
+                vec![(-1000.0, 86.0), (0.0, 85.0)],
+            ]
+        );
+        assert_eq!(battery_data[1].len(), 1);
+        assert_eq!(battery_data[1][0].len(), 6);
+    }
 }
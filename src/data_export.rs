@@ -0,0 +1,351 @@
+//! Exporting collected data outside of the TUI.
+
+pub mod graph_snapshot;
+pub mod json_log;
+pub mod server;
+
+use std::fmt::Write;
+
+use crate::{
+    app::data_farmer::DataCollection,
+    data_collection::{cpu::CpuDataType, memory::MemHarvest, temperature::TemperatureType},
+};
+
+/// How many of the top processes by CPU and by memory usage to export.
+const TOP_PROCESSES: usize = 10;
+
+/// Writes the metrics for a single metric family in the Prometheus text
+/// exposition format.
+struct MetricWriter<'a> {
+    output: &'a mut String,
+}
+
+impl MetricWriter<'_> {
+    /// Starts a new gauge metric family.
+    fn gauge(&mut self, name: &str, help: &str) -> &mut Self {
+        let _ = writeln!(self.output, "# HELP bottom_{name} {help}");
+        let _ = writeln!(self.output, "# TYPE bottom_{name} gauge");
+        self
+    }
+
+    /// Writes a sample of the metric family `name`.
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) -> &mut Self {
+        let _ = write!(self.output, "bottom_{name}");
+
+        if !labels.is_empty() {
+            let labels = labels
+                .iter()
+                .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+                .collect::<Vec<_>>()
+                .join(",");
+            let _ = write!(self.output, "{{{labels}}}");
+        }
+
+        let _ = writeln!(self.output, " {value}");
+        self
+    }
+}
+
+/// Escapes a label value, as per the Prometheus text exposition format.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace('\n', r"\n")
+}
+
+/// Returns the latest values of `data` in the Prometheus text exposition
+/// format.
+pub fn render_metrics(data: &DataCollection, temperature_type: TemperatureType) -> String {
+    let mut output = String::new();
+    let mut writer = MetricWriter {
+        output: &mut output,
+    };
+
+    writer.gauge("cpu_usage_percent", "CPU usage, per core and on average.");
+    for cpu in &data.cpu_harvest {
+        let cpu_label = match cpu.data_type {
+            CpuDataType::Avg => "avg".to_string(),
+            CpuDataType::Cpu(index) => index.to_string(),
+            CpuDataType::Socket(index) => format!("socket{index}"),
+        };
+        writer.sample("cpu_usage_percent", &[("cpu", &cpu_label)], cpu.cpu_usage);
+    }
+
+    let mut memory = vec![("ram", &data.memory_harvest), ("swap", &data.swap_harvest)];
+    #[cfg(not(target_os = "windows"))]
+    memory.push(("cache", &data.cache_harvest));
+    #[cfg(feature = "zfs")]
+    memory.push(("arc", &data.arc_harvest));
+
+    write_memory(&mut writer, &memory);
+
+    writer.gauge("disk_used_bytes", "Used disk space.");
+    for disk in &data.disk_harvest {
+        if let Some(used) = disk.used_space {
+            writer.sample("disk_used_bytes", &disk_labels(disk), used as f64);
+        }
+    }
+    writer.gauge("disk_total_bytes", "Total disk space.");
+    for disk in &data.disk_harvest {
+        if let Some(total) = disk.total_space {
+            writer.sample("disk_total_bytes", &disk_labels(disk), total as f64);
+        }
+    }
+    writer.gauge("disk_read_bytes_per_second", "Disk read rate.");
+    for (disk, rates) in data.disk_harvest.iter().zip(&data.io_rates) {
+        if let Some((read, _)) = rates {
+            writer.sample(
+                "disk_read_bytes_per_second",
+                &disk_labels(disk),
+                *read as f64,
+            );
+        }
+    }
+    writer.gauge("disk_write_bytes_per_second", "Disk write rate.");
+    for (disk, rates) in data.disk_harvest.iter().zip(&data.io_rates) {
+        if let Some((_, write)) = rates {
+            writer.sample(
+                "disk_write_bytes_per_second",
+                &disk_labels(disk),
+                *write as f64,
+            );
+        }
+    }
+
+    // Network values are harvested in bits.
+    let interfaces = std::iter::once(("all", &data.network_harvest)).chain(
+        data.network_interface_harvest
+            .iter()
+            .map(|(name, harvest)| (name.as_str(), harvest)),
+    );
+    let interfaces = interfaces.collect::<Vec<_>>();
+    writer.gauge("network_receive_bytes_per_second", "Network receive rate.");
+    for (name, harvest) in &interfaces {
+        writer.sample(
+            "network_receive_bytes_per_second",
+            &[("interface", name)],
+            harvest.rx as f64 / 8.0,
+        );
+    }
+    writer.gauge(
+        "network_transmit_bytes_per_second",
+        "Network transmit rate.",
+    );
+    for (name, harvest) in &interfaces {
+        writer.sample(
+            "network_transmit_bytes_per_second",
+            &[("interface", name)],
+            harvest.tx as f64 / 8.0,
+        );
+    }
+
+    // Temperatures are harvested in the configured unit, but always exported in Celsius.
+    writer.gauge("temperature_celsius", "Sensor temperature.");
+    for sensor in &data.temp_harvest {
+        if let Some(temperature) = sensor.temperature {
+            writer.sample(
+                "temperature_celsius",
+                &[("sensor", &sensor.name)],
+                f64::from(temperature_type.to_celsius(temperature)),
+            );
+        }
+    }
+
+    // Export the top processes by CPU usage and by memory usage.
+    let processes = data
+        .process_data
+        .process_harvest
+        .values()
+        .collect::<Vec<_>>();
+    let mut by_cpu = processes.clone();
+    by_cpu.sort_by(|a, b| b.cpu_usage_percent.total_cmp(&a.cpu_usage_percent));
+    let mut by_mem = processes;
+    by_mem.sort_by_key(|process| std::cmp::Reverse(process.mem_usage_bytes));
+
+    let mut top = by_cpu
+        .into_iter()
+        .take(TOP_PROCESSES)
+        .chain(by_mem.into_iter().take(TOP_PROCESSES))
+        .collect::<Vec<_>>();
+    top.sort_by_key(|process| process.pid);
+    top.dedup_by_key(|process| process.pid);
+
+    writer.gauge(
+        "process_cpu_usage_percent",
+        "CPU usage of the top processes by CPU or memory usage.",
+    );
+    for process in &top {
+        let pid = process.pid.to_string();
+        writer.sample(
+            "process_cpu_usage_percent",
+            &[("pid", &pid), ("name", &process.name)],
+            f64::from(process.cpu_usage_percent),
+        );
+    }
+    writer.gauge(
+        "process_memory_bytes",
+        "Memory usage of the top processes by CPU or memory usage.",
+    );
+    for process in &top {
+        let pid = process.pid.to_string();
+        writer.sample(
+            "process_memory_bytes",
+            &[("pid", &pid), ("name", &process.name)],
+            process.mem_usage_bytes as f64,
+        );
+    }
+
+    output
+}
+
+fn write_memory(writer: &mut MetricWriter<'_>, memory: &[(&str, &MemHarvest)]) {
+    writer.gauge("memory_used_bytes", "Used memory.");
+    for (kind, harvest) in memory {
+        writer.sample(
+            "memory_used_bytes",
+            &[("kind", kind)],
+            harvest.used_bytes as f64,
+        );
+    }
+
+    writer.gauge("memory_total_bytes", "Total memory.");
+    for (kind, harvest) in memory {
+        writer.sample(
+            "memory_total_bytes",
+            &[("kind", kind)],
+            harvest.total_bytes as f64,
+        );
+    }
+}
+
+fn disk_labels(disk: &crate::data_collection::disks::DiskHarvest) -> [(&str, &str); 2] {
+    [("disk", &disk.name), ("mount", &disk.mount_point)]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        app::data_farmer::ProcessData,
+        data_collection::{
+            cpu::CpuData, disks::DiskHarvest, network::NetworkHarvest, processes::ProcessHarvest,
+            temperature::TempHarvest,
+        },
+    };
+
+    fn process(pid: crate::data_collection::processes::Pid, cpu: f32, mem: u64) -> ProcessHarvest {
+        ProcessHarvest {
+            pid,
+            name: format!("proc{pid}"),
+            cpu_usage_percent: cpu,
+            mem_usage_bytes: mem,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn renders_metrics() {
+        let data = DataCollection {
+            cpu_harvest: vec![
+                CpuData {
+                    data_type: CpuDataType::Avg,
+                    cpu_usage: 12.5,
+                },
+                CpuData {
+                    data_type: CpuDataType::Cpu(0),
+                    cpu_usage: 25.0,
+                },
+            ],
+            memory_harvest: MemHarvest {
+                used_bytes: 1024,
+                total_bytes: 4096,
+            },
+            disk_harvest: vec![DiskHarvest {
+                name: "/dev/sda1".into(),
+                mount_point: "/".into(),
+                used_space: Some(100),
+                total_space: Some(200),
+                ..Default::default()
+            }],
+            io_rates: vec![Some((10, 20))],
+            network_interface_harvest: vec![(
+                "eth0".into(),
+                NetworkHarvest {
+                    rx: 800,
+                    tx: 80,
+                    ..Default::default()
+                },
+            )],
+            temp_harvest: vec![TempHarvest {
+                name: "cpu \"package\"".into(),
+                temperature: Some(50.0),
+            }],
+            process_data: ProcessData {
+                process_harvest: (1..=30)
+                    .map(|pid| (pid, process(pid, pid as f32, 1000 - pid as u64)))
+                    .collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let metrics = render_metrics(&data, TemperatureType::Celsius);
+        let lines = metrics.lines().collect::<Vec<_>>();
+
+        for expected in [
+            "# TYPE bottom_cpu_usage_percent gauge",
+            "bottom_cpu_usage_percent{cpu=\"avg\"} 12.5",
+            "bottom_cpu_usage_percent{cpu=\"0\"} 25",
+            "bottom_memory_used_bytes{kind=\"ram\"} 1024",
+            "bottom_memory_total_bytes{kind=\"ram\"} 4096",
+            "bottom_disk_used_bytes{disk=\"/dev/sda1\",mount=\"/\"} 100",
+            "bottom_disk_total_bytes{disk=\"/dev/sda1\",mount=\"/\"} 200",
+            "bottom_disk_read_bytes_per_second{disk=\"/dev/sda1\",mount=\"/\"} 10",
+            "bottom_disk_write_bytes_per_second{disk=\"/dev/sda1\",mount=\"/\"} 20",
+            "bottom_network_receive_bytes_per_second{interface=\"eth0\"} 100",
+            "bottom_network_transmit_bytes_per_second{interface=\"eth0\"} 10",
+            "bottom_temperature_celsius{sensor=\"cpu \\\"package\\\"\"} 50",
+            "bottom_process_cpu_usage_percent{pid=\"30\",name=\"proc30\"} 30",
+            "bottom_process_memory_bytes{pid=\"1\",name=\"proc1\"} 999",
+        ] {
+            assert!(
+                lines.contains(&expected),
+                "missing '{expected}' in:\n{metrics}"
+            );
+        }
+
+        // Only the top processes by CPU (21-30) and memory (1-10) are exported.
+        let process_count = lines
+            .iter()
+            .filter(|line| line.starts_with("bottom_process_cpu_usage_percent{"))
+            .count();
+        assert_eq!(process_count, 2 * TOP_PROCESSES);
+        assert!(!metrics.contains("pid=\"15\""));
+    }
+
+    #[test]
+    fn temperatures_are_exported_in_celsius() {
+        let data = DataCollection {
+            temp_harvest: vec![TempHarvest {
+                name: "cpu".into(),
+                temperature: Some(122.0),
+            }],
+            ..Default::default()
+        };
+
+        let metrics = render_metrics(&data, TemperatureType::Fahrenheit);
+        assert!(
+            metrics
+                .lines()
+                .any(|line| line == "bottom_temperature_celsius{sensor=\"cpu\"} 50"),
+            "{metrics}"
+        );
+    }
+
+    #[test]
+    fn label_escaping() {
+        assert_eq!(escape_label("plain"), "plain");
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
@@ -0,0 +1,148 @@
+//! Saving text snapshots of graphs, e.g. for pasting into issue reports.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    time::SystemTime,
+};
+
+use crate::utils::lock_file::{FileLock, InstanceConflict};
+
+/// The default snapshot file sub-path.
+const DEFAULT_SNAPSHOT_FILE_LOCATION: &str = "bottom/graphs.txt";
+
+/// The file snapshots are appended to if there is no local data folder.
+const FALLBACK_SNAPSHOT_PATH: &str = "bottom_graphs.txt";
+
+/// The path that sends snapshots to stdout instead of a file.
+const STDOUT_PATH: &str = "-";
+
+/// Appends graph snapshots to a file, or holds them until bottom exits to be
+/// written to stdout, as the terminal is in use until then.
+#[derive(Debug)]
+pub struct GraphSnapshots {
+    path: Option<PathBuf>,
+    conflict: InstanceConflict,
+    /// Held from the first snapshot saved to a file.
+    lock: Option<FileLock>,
+    deferred: Vec<String>,
+}
+
+/// Returns where snapshots are saved if no other path is given, which is
+/// `<LOCAL_DATA_FOLDER>/bottom/graphs.txt`. If the system has no local data
+/// folder, then `bottom_graphs.txt` in the current directory is used instead.
+pub fn default_snapshot_path() -> PathBuf {
+    dirs::data_local_dir()
+        .map(|path| path.join(DEFAULT_SNAPSHOT_FILE_LOCATION))
+        .unwrap_or_else(|| PathBuf::from(FALLBACK_SNAPSHOT_PATH))
+}
+
+impl GraphSnapshots {
+    /// Snapshots go to `path`, which defaults to [`default_snapshot_path`]. A
+    /// path of `-` means stdout. Another instance writing to the same file is
+    /// handled as given.
+    pub fn new(path: Option<PathBuf>, conflict: InstanceConflict) -> Self {
+        let path = match path {
+            Some(path) if path.as_os_str() == STDOUT_PATH => None,
+            Some(path) => Some(path),
+            None => Some(default_snapshot_path()),
+        };
+
+        Self {
+            path,
+            conflict,
+            lock: None,
+            deferred: vec![],
+        }
+    }
+
+    /// Saves a snapshot of the graph called `name`, returning a message saying
+    /// where it went.
+    pub fn save(&mut self, name: &str, snapshot: &str) -> io::Result<String> {
+        let entry = format!(
+            "{name} at {}\n{snapshot}\n\n",
+            humantime::format_rfc3339_seconds(SystemTime::now())
+        );
+
+        match &self.path {
+            Some(path) => {
+                if self.lock.is_none() {
+                    if let Some(parent_path) = path.parent() {
+                        fs::create_dir_all(parent_path)?;
+                    }
+                    self.lock = FileLock::acquire_for_writing(path, self.conflict)?;
+                }
+                let Some(lock) = &self.lock else {
+                    return Err(io::Error::other(
+                        "another instance of bottom is writing to it",
+                    ));
+                };
+
+                let path = lock.path();
+                let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+                file.write_all(entry.as_bytes())?;
+
+                Ok(format!("Saved the {name} graph to '{}'", path.display()))
+            }
+            None => {
+                self.deferred.push(entry);
+
+                Ok(format!(
+                    "The {name} graph will be written to stdout on exit"
+                ))
+            }
+        }
+    }
+
+    /// Writes any snapshots meant for stdout.
+    pub fn write_deferred(&self) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        for entry in &self.deferred {
+            stdout.write_all(entry.as_bytes())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn snapshot_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bottom").join("graphs.txt");
+
+        let mut snapshots = GraphSnapshots::new(Some(path.clone()), InstanceConflict::Skip);
+        snapshots.save("CPU", "⣀⡠").unwrap();
+        let message = snapshots.save("Memory", "⠉⠒").unwrap();
+        assert!(message.contains(&path.display().to_string()), "{message}");
+
+        // Another instance writes to its own file, or not at all.
+        let mut other = GraphSnapshots::new(Some(path.clone()), InstanceConflict::Suffix);
+        let message = other.save("CPU", "⣀⡠").unwrap();
+        assert!(message.contains("graphs.2.txt"), "{message}");
+        let mut other = GraphSnapshots::new(Some(path.clone()), InstanceConflict::Skip);
+        assert!(other.save("CPU", "⣀⡠").is_err());
+
+        // Snapshots are appended, each with a header.
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines = contents.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].starts_with("CPU at "));
+        assert_eq!(lines[1], "⣀⡠");
+        assert!(lines[3].starts_with("Memory at "));
+        assert!(snapshots.deferred.is_empty());
+
+        let mut snapshots = GraphSnapshots::new(Some(PathBuf::from("-")), InstanceConflict::Skip);
+        snapshots.save("CPU", "⣀⡠").unwrap();
+        assert_eq!(snapshots.deferred.len(), 1);
+
+        assert_eq!(
+            GraphSnapshots::new(None, InstanceConflict::Skip).path,
+            Some(default_snapshot_path())
+        );
+    }
+}
@@ -0,0 +1,420 @@
+//! Logging collected data as JSON lines, one line per harvest.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+
+use crate::{
+    app::data_farmer::DataCollection,
+    data_collection::{
+        cpu::CpuDataType, memory::MemHarvest, processes::Pid, temperature::TemperatureType,
+    },
+    utils::{
+        lock_file::{FileLock, InstanceConflict},
+        persist::FormatVersion,
+    },
+};
+
+/// The format version of each record. See [`crate::utils::persist`] for what
+/// the versions mean.
+const JSON_LOG_VERSION: FormatVersion = FormatVersion::new(1, 0);
+
+#[derive(Serialize)]
+struct Record<'a> {
+    version: FormatVersion,
+    /// Milliseconds since the Unix epoch.
+    timestamp_ms: u64,
+    cpu: Vec<CpuRecord>,
+    load_average: [f32; 3],
+    memory: Vec<MemoryRecord<'a>>,
+    network: Vec<NetworkRecord<'a>>,
+    disks: Vec<DiskRecord<'a>>,
+    temperatures: Vec<TemperatureRecord<'a>>,
+    processes: Vec<ProcessRecord<'a>>,
+    #[cfg(feature = "battery")]
+    batteries: Vec<BatteryRecord>,
+}
+
+#[derive(Serialize)]
+struct CpuRecord {
+    /// Either "avg" or the core's index.
+    cpu: String,
+    usage_percent: f64,
+}
+
+#[derive(Serialize)]
+struct MemoryRecord<'a> {
+    kind: &'a str,
+    used_bytes: u64,
+    total_bytes: u64,
+}
+
+#[derive(Serialize)]
+struct NetworkRecord<'a> {
+    /// Either "all" or the interface's name.
+    interface: &'a str,
+    rx_bytes_per_second: u64,
+    tx_bytes_per_second: u64,
+    total_rx_bytes: u64,
+    total_tx_bytes: u64,
+}
+
+#[derive(Serialize)]
+struct DiskRecord<'a> {
+    name: &'a str,
+    mount_point: &'a str,
+    file_system: Option<&'a str>,
+    used_bytes: Option<u64>,
+    free_bytes: Option<u64>,
+    total_bytes: Option<u64>,
+    read_bytes_per_second: Option<u64>,
+    write_bytes_per_second: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct TemperatureRecord<'a> {
+    sensor: &'a str,
+    temperature: Option<f32>,
+    unit: &'static str,
+}
+
+#[derive(Serialize)]
+struct ProcessRecord<'a> {
+    pid: Pid,
+    parent_pid: Option<Pid>,
+    name: &'a str,
+    command: &'a str,
+    user: &'a str,
+    state: &'a str,
+    cpu_usage_percent: f32,
+    mem_usage_percent: f32,
+    mem_usage_bytes: u64,
+    read_bytes_per_second: u64,
+    write_bytes_per_second: u64,
+    total_read_bytes: u64,
+    total_write_bytes: u64,
+}
+
+#[cfg(feature = "battery")]
+#[derive(Serialize)]
+struct BatteryRecord {
+    id: usize,
+    charge_percent: f64,
+    power_consumption_watts: f64,
+    health_percent: f64,
+    state: &'static str,
+}
+
+/// Returns the latest values of `data` as a single line of JSON, without a
+/// trailing newline.
+pub fn render_json_record(
+    data: &DataCollection, temperature_type: TemperatureType, timestamp: SystemTime,
+) -> serde_json::Result<String> {
+    let cpu = data
+        .cpu_harvest
+        .iter()
+        .map(|cpu| CpuRecord {
+            cpu: match cpu.data_type {
+                CpuDataType::Avg => "avg".to_string(),
+                CpuDataType::Cpu(index) => index.to_string(),
+                CpuDataType::Socket(index) => format!("socket{index}"),
+            },
+            usage_percent: cpu.cpu_usage,
+        })
+        .collect();
+
+    let mut memory = vec![("ram", &data.memory_harvest), ("swap", &data.swap_harvest)];
+    #[cfg(not(target_os = "windows"))]
+    memory.push(("cache", &data.cache_harvest));
+    #[cfg(feature = "zfs")]
+    memory.push(("arc", &data.arc_harvest));
+    #[cfg(feature = "gpu")]
+    memory.extend(
+        data.gpu_harvest
+            .iter()
+            .map(|(name, harvest)| (name.as_str(), harvest)),
+    );
+    let memory = memory
+        .into_iter()
+        .map(|(kind, harvest): (&str, &MemHarvest)| MemoryRecord {
+            kind,
+            used_bytes: harvest.used_bytes,
+            total_bytes: harvest.total_bytes,
+        })
+        .collect();
+
+    // Network values are harvested in bits.
+    let network = std::iter::once(("all", &data.network_harvest))
+        .chain(
+            data.network_interface_harvest
+                .iter()
+                .map(|(name, harvest)| (name.as_str(), harvest)),
+        )
+        .map(|(interface, harvest)| NetworkRecord {
+            interface,
+            rx_bytes_per_second: harvest.rx / 8,
+            tx_bytes_per_second: harvest.tx / 8,
+            total_rx_bytes: harvest.total_rx / 8,
+            total_tx_bytes: harvest.total_tx / 8,
+        })
+        .collect();
+
+    let disks = data
+        .disk_harvest
+        .iter()
+        .enumerate()
+        .map(|(index, disk)| {
+            let rates = data.io_rates.get(index).copied().flatten();
+
+            DiskRecord {
+                name: &disk.name,
+                mount_point: &disk.mount_point,
+                file_system: disk.file_system.as_deref(),
+                used_bytes: disk.used_space,
+                free_bytes: disk.free_space,
+                total_bytes: disk.total_space,
+                read_bytes_per_second: rates.map(|(read, _)| read),
+                write_bytes_per_second: rates.map(|(_, write)| write),
+            }
+        })
+        .collect();
+
+    let unit = match temperature_type {
+        TemperatureType::Celsius => "celsius",
+        TemperatureType::Kelvin => "kelvin",
+        TemperatureType::Fahrenheit => "fahrenheit",
+    };
+    let temperatures = data
+        .temp_harvest
+        .iter()
+        .map(|sensor| TemperatureRecord {
+            sensor: &sensor.name,
+            temperature: sensor.temperature,
+            unit,
+        })
+        .collect();
+
+    let processes = data
+        .process_data
+        .process_harvest
+        .values()
+        .map(|process| ProcessRecord {
+            pid: process.pid,
+            parent_pid: process.parent_pid,
+            name: &process.name,
+            command: &process.command,
+            user: &process.user,
+            state: &process.process_state.0,
+            cpu_usage_percent: process.cpu_usage_percent,
+            mem_usage_percent: process.mem_usage_percent,
+            mem_usage_bytes: process.mem_usage_bytes,
+            read_bytes_per_second: process.read_bytes_per_sec,
+            write_bytes_per_second: process.write_bytes_per_sec,
+            total_read_bytes: process.total_read_bytes,
+            total_write_bytes: process.total_write_bytes,
+        })
+        .collect();
+
+    #[cfg(feature = "battery")]
+    let batteries = data
+        .battery_harvest
+        .iter()
+        .map(|battery| BatteryRecord {
+            id: battery.id,
+            charge_percent: battery.charge_percent,
+            power_consumption_watts: battery.power_consumption,
+            health_percent: battery.health_percent,
+            state: battery.state.as_str(),
+        })
+        .collect();
+
+    let timestamp_ms = timestamp
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0);
+
+    serde_json::to_string(&Record {
+        version: JSON_LOG_VERSION,
+        timestamp_ms,
+        cpu,
+        load_average: data.load_avg_harvest,
+        memory,
+        network,
+        disks,
+        temperatures,
+        processes,
+        #[cfg(feature = "battery")]
+        batteries,
+    })
+}
+
+/// Appends JSON records to a file, or to stdout if there is no file.
+pub struct JsonLog {
+    path: Option<PathBuf>,
+    output: Box<dyn Write>,
+    /// Held for as long as records are written to a file.
+    _lock: Option<FileLock>,
+}
+
+impl JsonLog {
+    /// Opens `path` for appending, creating it if needed, and handling another
+    /// instance writing to it as given. If `path` is [`None`], records are
+    /// written to stdout.
+    pub fn open(path: Option<PathBuf>, conflict: InstanceConflict) -> io::Result<Self> {
+        let lock = path
+            .map(|path| {
+                FileLock::acquire_for_writing(&path, conflict)?
+                    .ok_or_else(|| io::Error::other("another instance of bottom is writing to it"))
+            })
+            .transpose()?;
+        let path = lock.as_ref().map(|lock| lock.path().to_path_buf());
+        let output = Self::open_output(path.as_ref())?;
+
+        Ok(Self {
+            path,
+            output,
+            _lock: lock,
+        })
+    }
+
+    fn open_output(path: Option<&PathBuf>) -> io::Result<Box<dyn Write>> {
+        Ok(match path {
+            Some(path) => Box::new(BufWriter::new(Self::open_file(path)?)),
+            None => Box::new(io::stdout()),
+        })
+    }
+
+    fn open_file(path: &PathBuf) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+
+    /// Reopens the file, so that records go to a new file at the same path
+    /// once the old one has been moved away (e.g. by `logrotate`).
+    pub fn reopen(&mut self) -> io::Result<()> {
+        self.output.flush()?;
+        self.output = Self::open_output(self.path.as_ref())?;
+
+        Ok(())
+    }
+
+    /// Writes the latest values of `data` as a line.
+    pub fn write(
+        &mut self, data: &DataCollection, temperature_type: TemperatureType,
+    ) -> io::Result<()> {
+        let record = render_json_record(data, temperature_type, SystemTime::now())?;
+        writeln!(self.output, "{record}")?;
+
+        // Flush every line, so that readers never see a partial record.
+        self.output.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        app::data_farmer::ProcessData,
+        data_collection::{
+            cpu::CpuData, disks::DiskHarvest, network::NetworkHarvest, processes::ProcessHarvest,
+            temperature::TempHarvest,
+        },
+    };
+
+    #[test]
+    fn renders_json_records() {
+        let data = DataCollection {
+            cpu_harvest: vec![CpuData {
+                data_type: CpuDataType::Cpu(0),
+                cpu_usage: 25.0,
+            }],
+            memory_harvest: MemHarvest {
+                used_bytes: 1024,
+                total_bytes: 4096,
+            },
+            disk_harvest: vec![DiskHarvest {
+                name: "/dev/sda1".into(),
+                mount_point: "/".into(),
+                used_space: Some(100),
+                total_space: Some(200),
+                ..Default::default()
+            }],
+            io_rates: vec![Some((10, 20))],
+            network_harvest: NetworkHarvest {
+                rx: 800,
+                tx: 80,
+                ..Default::default()
+            },
+            temp_harvest: vec![TempHarvest {
+                name: "cpu".into(),
+                temperature: Some(50.0),
+            }],
+            process_data: ProcessData {
+                process_harvest: [(
+                    7,
+                    ProcessHarvest {
+                        pid: 7,
+                        name: "proc\"7\"".into(),
+                        cpu_usage_percent: 1.5,
+                        ..Default::default()
+                    },
+                )]
+                .into_iter()
+                .collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let record = render_json_record(
+            &data,
+            TemperatureType::Celsius,
+            UNIX_EPOCH + Duration::from_millis(1234),
+        )
+        .unwrap();
+        assert!(!record.contains('\n'));
+
+        let value: serde_json::Value = serde_json::from_str(&record).unwrap();
+        assert_eq!(value["version"], "1.0");
+        assert_eq!(value["timestamp_ms"], 1234);
+        assert_eq!(value["cpu"][0]["cpu"], "0");
+        assert_eq!(value["cpu"][0]["usage_percent"], 25.0);
+        assert_eq!(value["memory"][0]["kind"], "ram");
+        assert_eq!(value["memory"][0]["used_bytes"], 1024);
+        assert_eq!(value["network"][0]["interface"], "all");
+        assert_eq!(value["network"][0]["rx_bytes_per_second"], 100);
+        assert_eq!(value["disks"][0]["mount_point"], "/");
+        assert_eq!(value["disks"][0]["free_bytes"], serde_json::Value::Null);
+        assert_eq!(value["disks"][0]["write_bytes_per_second"], 20);
+        assert_eq!(value["temperatures"][0]["unit"], "celsius");
+        assert_eq!(value["processes"][0]["pid"], 7);
+        assert_eq!(value["processes"][0]["name"], "proc\"7\"");
+    }
+
+    #[test]
+    fn reopens_after_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.jsonl");
+        let rotated = dir.path().join("out.jsonl.1");
+        let data = DataCollection::default();
+
+        let mut log = JsonLog::open(Some(path.clone()), InstanceConflict::Skip).unwrap();
+        assert!(JsonLog::open(Some(path.clone()), InstanceConflict::Skip).is_err());
+        log.write(&data, TemperatureType::Celsius).unwrap();
+        log.write(&data, TemperatureType::Celsius).unwrap();
+
+        std::fs::rename(&path, &rotated).unwrap();
+        log.reopen().unwrap();
+        log.write(&data, TemperatureType::Celsius).unwrap();
+
+        let line_count = |path: &PathBuf| std::fs::read_to_string(path).unwrap().lines().count();
+        assert_eq!(line_count(&rotated), 2);
+        assert_eq!(line_count(&path), 1);
+    }
+}
@@ -0,0 +1,189 @@
+//! A minimal HTTP server that serves metrics at `/metrics`, for scraping by
+//! Prometheus or similar tools.
+
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::utils::cancellation_token::CancellationToken;
+
+/// How often to check for new connections or shutdown.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long to wait on a slow client before giving up on it.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The latest metrics, shared between the main thread and the server.
+pub type SharedMetrics = Arc<Mutex<String>>;
+
+/// A running metrics server. The server is shut down when this is dropped.
+pub struct MetricsServer {
+    #[cfg(test)]
+    local_addr: SocketAddr,
+    cancellation_token: Arc<CancellationToken>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MetricsServer {
+    /// Starts serving `metrics` on `addr`.
+    pub fn start(addr: SocketAddr, metrics: SharedMetrics) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        #[cfg(test)]
+        let local_addr = listener.local_addr()?;
+
+        let cancellation_token = Arc::new(CancellationToken::default());
+        let handle = {
+            let cancellation_token = cancellation_token.clone();
+
+            thread::spawn(move || loop {
+                if cancellation_token.try_check().unwrap_or(false) {
+                    break;
+                }
+
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        if let Err(_err) = handle_connection(stream, &metrics) {
+                            crate::debug!("Failed to serve metrics request: {_err}");
+                        }
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                        if cancellation_token.sleep_with_cancellation(POLL_INTERVAL) {
+                            break;
+                        }
+                    }
+                    Err(_err) => {
+                        crate::warn!("Failed to accept metrics connection: {_err}");
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            #[cfg(test)]
+            local_addr,
+            cancellation_token,
+            handle: Some(handle),
+        })
+    }
+
+    /// The address the server is listening on.
+    #[cfg(test)]
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.cancellation_token.cancel();
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, metrics: &Mutex<String>) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Skip the headers; nothing in them matters to us.
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default();
+
+    let (status, body) = match (method, path) {
+        ("GET", "/metrics") => (
+            "200 OK",
+            metrics
+                .lock()
+                .map(|metrics| metrics.clone())
+                .unwrap_or_default(),
+        ),
+        ("GET", _) => ("404 Not Found", "Not found.\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "Method not allowed.\n".to_string(),
+        ),
+    };
+
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n\
+        Content-Type: text/plain; version=0.0.4; charset=utf-8\r\n\
+        Content-Length: {}\r\n\
+        Connection: close\r\n\
+        \r\n\
+        {body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+
+    use super::*;
+
+    fn request(addr: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serves_metrics() {
+        let metrics = SharedMetrics::default();
+        *metrics.lock().unwrap() = "bottom_test 1\n".to_string();
+
+        let server = MetricsServer::start("127.0.0.1:0".parse().unwrap(), metrics.clone()).unwrap();
+        let addr = server.local_addr();
+
+        let response = request(addr, "GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Length: 14\r\n"));
+        assert!(response.ends_with("\r\n\r\nbottom_test 1\n"));
+
+        // Updates are picked up.
+        *metrics.lock().unwrap() = "bottom_test 2\n".to_string();
+        let response = request(addr, "GET /metrics?x=y HTTP/1.1\r\n\r\n");
+        assert!(response.ends_with("bottom_test 2\n"));
+
+        let response = request(addr, "GET / HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let response = request(addr, "POST /metrics HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+
+        // Shutting down stops the listener.
+        drop(server);
+        assert!(TcpStream::connect(addr).is_err());
+    }
+}
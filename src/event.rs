@@ -5,7 +5,11 @@ use std::sync::mpsc::Sender;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 
 use crate::{
-    app::{layout_manager::WidgetDirection, App},
+    app::{
+        layout_manager::{UsedWidgets, WidgetDirection},
+        time_markers::MarkerOverlayMode,
+        App,
+    },
     data_collection::Data,
 };
 
@@ -25,13 +29,30 @@ pub enum BottomEvent {
 #[derive(Debug)]
 pub enum CollectionThreadEvent {
     Reset,
+    /// Collect data now, rather than waiting for the next update.
+    Refresh,
+    /// Collect data at a new rate, in milliseconds.
+    UpdateRate(u64),
+    /// Collect the data needed by a different set of widgets.
+    UpdateUsedWidgets(UsedWidgets),
+    /// Stop collecting, as bottom is exiting.
+    Stop,
 }
 
 /// Handle a [`MouseEvent`].
 pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
+    if app.jump_palette.is_open()
+        || app.ancestry_overlay.is_open()
+        || app.settings_overlay.is_open()
+        || app.marker_overlay.is_open()
+    {
+        return;
+    }
+
     match event.kind {
         MouseEventKind::ScrollUp => app.handle_scroll_up(),
         MouseEventKind::ScrollDown => app.handle_scroll_down(),
+        MouseEventKind::Moved => app.on_mouse_move(event.column, event.row),
         MouseEventKind::Down(button) => {
             let (x, y) = (event.column, event.row);
             if !app.app_config_fields.disable_click {
@@ -45,16 +66,47 @@ pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
                 }
             }
         }
+        MouseEventKind::Drag(crossterm::event::MouseButton::Left)
+            if !app.app_config_fields.disable_click =>
+        {
+            app.on_left_mouse_drag(event.column)
+        }
+        MouseEventKind::Up(crossterm::event::MouseButton::Left) => app.on_left_mouse_release(),
         _ => {}
     };
 }
 
 /// Handle a [`KeyEvent`].
 pub fn handle_key_event_or_break(
-    event: KeyEvent, app: &mut App, reset_sender: &Sender<CollectionThreadEvent>,
+    event: KeyEvent, app: &mut App, collection_sender: &Sender<CollectionThreadEvent>,
 ) -> bool {
     // c_debug!("KeyEvent: {event:?}");
 
+    if app.jump_palette.is_open() {
+        return handle_jump_palette_key_event_or_break(event, app);
+    }
+
+    if app.ancestry_overlay.is_open() {
+        return handle_ancestry_overlay_key_event_or_break(event, app);
+    }
+
+    if app.settings_overlay.is_open() {
+        return handle_settings_overlay_key_event_or_break(event, app, collection_sender);
+    }
+
+    if app.marker_overlay.is_open() {
+        return handle_marker_overlay_key_event_or_break(event, app);
+    }
+
+    // Refreshing is up to the collection thread, so handle it here rather than in the app.
+    if matches!(event.modifiers, KeyModifiers::NONE | KeyModifiers::SHIFT)
+        && event.code == KeyCode::Char('R')
+        && !app.is_in_search_widget()
+    {
+        let _ = collection_sender.send(CollectionThreadEvent::Refresh);
+        return false;
+    }
+
     if event.modifiers.is_empty() {
         // Required catch for searching - otherwise you couldn't search with q.
         if event.code == KeyCode::Char('q') && !app.is_in_search_widget() {
@@ -101,12 +153,13 @@ pub fn handle_key_event_or_break(
 
             match event.code {
                 KeyCode::Char('f') => app.on_slash(),
+                KeyCode::Char('p') => app.open_jump_palette(),
                 KeyCode::Left => app.move_widget_selection(&WidgetDirection::Left),
                 KeyCode::Right => app.move_widget_selection(&WidgetDirection::Right),
                 KeyCode::Up => app.move_widget_selection(&WidgetDirection::Up),
                 KeyCode::Down => app.move_widget_selection(&WidgetDirection::Down),
                 KeyCode::Char('r') => {
-                    if reset_sender.send(CollectionThreadEvent::Reset).is_ok() {
+                    if collection_sender.send(CollectionThreadEvent::Reset).is_ok() {
                         app.reset();
                     }
                 }
@@ -140,3 +193,114 @@ pub fn handle_key_event_or_break(
 
     false
 }
+
+/// Handle a [`KeyEvent`] while the jump palette is open, which takes all text
+/// input.
+fn handle_jump_palette_key_event_or_break(event: KeyEvent, app: &mut App) -> bool {
+    match (event.modifiers, event.code) {
+        (KeyModifiers::CONTROL, KeyCode::Char('c')) => return true,
+        (KeyModifiers::CONTROL, KeyCode::Char('p')) | (_, KeyCode::Esc) => app.close_jump_palette(),
+        (KeyModifiers::CONTROL, KeyCode::Char('h')) | (_, KeyCode::Backspace) => {
+            app.jump_palette.pop_char()
+        }
+        (_, KeyCode::Enter) => app.jump_to_selected(),
+        (_, KeyCode::Up) => app.jump_palette.move_selection(-1),
+        (_, KeyCode::Down) => app.jump_palette.move_selection(1),
+        (_, KeyCode::PageUp) => app.jump_palette.move_selection(-10),
+        (_, KeyCode::PageDown) => app.jump_palette.move_selection(10),
+        (KeyModifiers::NONE | KeyModifiers::SHIFT, KeyCode::Char(c)) => {
+            app.jump_palette.push_char(c)
+        }
+        _ => {}
+    }
+
+    false
+}
+
+/// Handle a [`KeyEvent`] while the ancestry overlay is open.
+fn handle_ancestry_overlay_key_event_or_break(event: KeyEvent, app: &mut App) -> bool {
+    match (event.modifiers, event.code) {
+        (KeyModifiers::CONTROL, KeyCode::Char('c')) => return true,
+        (_, KeyCode::Esc) | (KeyModifiers::NONE, KeyCode::Char('a')) => {
+            app.close_ancestry_overlay()
+        }
+        (KeyModifiers::NONE, KeyCode::Char('q')) => return true,
+        (_, KeyCode::Enter) => app.jump_to_selected_ancestor(),
+        (_, KeyCode::Left | KeyCode::Up | KeyCode::Char('h') | KeyCode::Char('k')) => {
+            app.ancestry_overlay.move_selection(-1)
+        }
+        (_, KeyCode::Right | KeyCode::Down | KeyCode::Char('l') | KeyCode::Char('j')) => {
+            app.ancestry_overlay.move_selection(1)
+        }
+        _ => {}
+    }
+
+    false
+}
+
+/// Handle a [`KeyEvent`] while the settings overlay is open, which takes all
+/// text input.
+fn handle_settings_overlay_key_event_or_break(
+    event: KeyEvent, app: &mut App, collection_sender: &Sender<CollectionThreadEvent>,
+) -> bool {
+    match (event.modifiers, event.code) {
+        (KeyModifiers::CONTROL, KeyCode::Char('c')) => return true,
+        (_, KeyCode::Esc) => app.close_settings_overlay(),
+        (KeyModifiers::CONTROL, KeyCode::Char('h')) | (_, KeyCode::Backspace) => {
+            app.settings_overlay.pop_char()
+        }
+        (_, KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down) => {
+            app.settings_overlay.toggle_field()
+        }
+        (_, KeyCode::Enter) => {
+            if let Some(update_rate_ms) = app.apply_settings() {
+                let _ = collection_sender.send(CollectionThreadEvent::UpdateRate(update_rate_ms));
+            }
+        }
+        (KeyModifiers::NONE | KeyModifiers::SHIFT, KeyCode::Char(c)) => {
+            app.settings_overlay.push_char(c)
+        }
+        _ => {}
+    }
+
+    false
+}
+
+/// Handle a [`KeyEvent`] while the marker overlay is open. While labelling a
+/// marker, it takes all text input.
+fn handle_marker_overlay_key_event_or_break(event: KeyEvent, app: &mut App) -> bool {
+    if let MarkerOverlayMode::Labelling(_) = app.marker_overlay.mode() {
+        match (event.modifiers, event.code) {
+            (KeyModifiers::CONTROL, KeyCode::Char('c')) => return true,
+            (_, KeyCode::Esc) => app.close_marker_overlay(),
+            (KeyModifiers::CONTROL, KeyCode::Char('h')) | (_, KeyCode::Backspace) => {
+                app.marker_overlay.pop_char()
+            }
+            (_, KeyCode::Enter) => app.apply_marker_label(),
+            (KeyModifiers::NONE | KeyModifiers::SHIFT, KeyCode::Char(c)) => {
+                app.marker_overlay.push_char(c)
+            }
+            _ => {}
+        }
+    } else {
+        let num_markers = app.data_collection.time_markers.len();
+
+        match (event.modifiers, event.code) {
+            (KeyModifiers::CONTROL, KeyCode::Char('c')) => return true,
+            (_, KeyCode::Esc) | (KeyModifiers::NONE, KeyCode::Char('v')) => {
+                app.close_marker_overlay()
+            }
+            (_, KeyCode::Up | KeyCode::Char('k')) => {
+                app.marker_overlay.move_selection(-1, num_markers)
+            }
+            (_, KeyCode::Down | KeyCode::Char('j')) => {
+                app.marker_overlay.move_selection(1, num_markers)
+            }
+            (_, KeyCode::Enter) => app.start_labelling_selected_marker(),
+            (_, KeyCode::Delete | KeyCode::Char('d')) => app.remove_selected_marker(),
+            _ => {}
+        }
+    }
+
+    false
+}
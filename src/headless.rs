@@ -0,0 +1,153 @@
+//! Running bottom without the TUI, streaming collected data out as JSON lines
+//! instead (e.g. on machines without a terminal).
+
+use std::{
+    io,
+    path::PathBuf,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    time::Duration,
+};
+
+use crate::{
+    app::App,
+    create_collection_thread,
+    data_export::{
+        json_log::JsonLog,
+        server::{MetricsServer, SharedMetrics},
+    },
+    event::{BottomEvent, CollectionThreadEvent},
+    utils::cancellation_token::CancellationToken,
+};
+
+/// How often to check for signals if no data arrives.
+const SIGNAL_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Runs the collection loop, writing a line of JSON per harvest to `log_path`
+/// (or stdout) until interrupted. Nothing is drawn, and the terminal is left
+/// untouched.
+pub(crate) fn run_headless(mut app: App, log_path: Option<PathBuf>) -> anyhow::Result<()> {
+    let mut log = JsonLog::open(log_path.clone(), app.app_config_fields.instance_conflict)
+        .map_err(|err| match &log_path {
+            Some(path) => anyhow::anyhow!("bottom couldn't open '{}': {err}", path.display()),
+            None => err.into(),
+        })?;
+
+    let metrics = SharedMetrics::default();
+    let metrics_server =
+        match app.app_config_fields.metrics_listen {
+            Some(addr) => Some(MetricsServer::start(addr, metrics.clone()).map_err(|err| {
+                anyhow::anyhow!("bottom couldn't serve metrics on '{addr}': {err}")
+            })?),
+            None => None,
+        };
+
+    let cancellation_token = Arc::new(CancellationToken::default());
+    let (sender, receiver) = mpsc::channel();
+
+    // Collect everything, rather than just what the layout would show.
+    let used_widgets = app.used_widgets.with_exported();
+    let (collection_thread_ctrl_sender, collection_thread_ctrl_receiver) = mpsc::channel();
+    let _collection_thread = create_collection_thread(
+        sender.clone(),
+        collection_thread_ctrl_receiver,
+        cancellation_token.clone(),
+        &app.app_config_fields,
+        app.filters.clone(),
+        used_widgets,
+        app.collects_network_interfaces(),
+        app.collection_watchdog.progress(),
+    );
+
+    ctrlc::set_handler(move || {
+        let _ = sender.send(BottomEvent::Terminate);
+    })?;
+
+    // This must come after setting the Ctrl-c handler, which also handles SIGHUP.
+    #[cfg(target_family = "unix")]
+    sighup::register()?;
+
+    let temperature_type = app.app_config_fields.temperature_type;
+    let result = loop {
+        match receiver.recv_timeout(SIGNAL_CHECK_INTERVAL) {
+            Ok(BottomEvent::Update(data)) => {
+                app.data_collection.eat_data(data);
+
+                // Everything is streamed out, so there is no history to keep (and no
+                // retention to apply).
+                app.data_collection.timed_data_vec.clear();
+
+                if metrics_server.is_some() {
+                    if let Ok(mut metrics) = metrics.lock() {
+                        *metrics = crate::data_export::render_metrics(
+                            &app.data_collection,
+                            temperature_type,
+                        );
+                    }
+                }
+
+                match log.write(&app.data_collection, temperature_type) {
+                    Ok(()) => {}
+                    // Whatever we were piped to has gone away, so treat it as a request to stop.
+                    Err(err) if err.kind() == io::ErrorKind::BrokenPipe => break Ok(()),
+                    Err(err) => break Err(err.into()),
+                }
+            }
+            Ok(BottomEvent::Terminate) | Err(RecvTimeoutError::Disconnected) => break Ok(()),
+            Ok(_) | Err(RecvTimeoutError::Timeout) => {}
+        }
+
+        #[cfg(target_family = "unix")]
+        if sighup::take() {
+            if let Err(err) = log.reopen() {
+                break Err(err.into());
+            }
+        }
+    };
+
+    cancellation_token.cancel();
+    // The collection thread waits on its channel rather than the token.
+    let _ = collection_thread_ctrl_sender.send(CollectionThreadEvent::Stop);
+    drop(metrics_server);
+
+    result
+}
+
+/// Tracks SIGHUP, which is used to reopen the log file after it has been
+/// rotated.
+#[cfg(target_family = "unix")]
+mod sighup {
+    use std::{
+        io,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    static RECEIVED: AtomicBool = AtomicBool::new(false);
+
+    extern "C" fn handle(_signal: libc::c_int) {
+        RECEIVED.store(true, Ordering::Relaxed);
+    }
+
+    pub(super) fn register() -> io::Result<()> {
+        // SAFETY: The handler only stores to an atomic, which is async-signal-safe.
+        let previous = unsafe {
+            libc::signal(
+                libc::SIGHUP,
+                handle as extern "C" fn(libc::c_int) as libc::sighandler_t,
+            )
+        };
+
+        if previous == libc::SIG_ERR {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns whether SIGHUP was received since the last call.
+    pub(super) fn take() -> bool {
+        RECEIVED.swap(false, Ordering::Relaxed)
+    }
+}
@@ -13,14 +13,18 @@ mod utils {
     pub(crate) mod data_prefixes;
     pub(crate) mod data_units;
     pub(crate) mod general;
+    pub(crate) mod lock_file;
     pub(crate) mod logging;
+    pub(crate) mod persist;
     pub(crate) mod strings;
 }
 pub(crate) mod canvas;
 pub(crate) mod constants;
 pub(crate) mod data_collection;
 pub(crate) mod data_conversion;
+pub(crate) mod data_export;
 pub(crate) mod event;
+pub(crate) mod headless;
 pub mod options;
 pub mod widgets;
 
@@ -29,14 +33,19 @@ use std::{
     io::{stderr, stdout, Stdout, Write},
     panic::{self, PanicHookInfo},
     sync::{
-        mpsc::{self, Receiver, Sender},
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
         Arc,
     },
     thread::{self, JoinHandle},
     time::{Duration, Instant},
 };
 
-use app::{layout_manager::UsedWidgets, App, AppConfigFields, DataFilters};
+use app::{
+    layout_manager::UsedWidgets,
+    saved_state::{self, SavedState, StateOverrides},
+    App, AppConfigFields, DataFilters,
+};
 use crossterm::{
     cursor::{Hide, Show},
     event::{
@@ -46,13 +55,18 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use data_collection::CollectionProgress;
 use data_conversion::*;
+use data_export::{
+    graph_snapshot::GraphSnapshots,
+    server::{MetricsServer, SharedMetrics},
+};
 use event::{handle_key_event_or_break, handle_mouse_event, BottomEvent, CollectionThreadEvent};
-use options::{args, get_or_create_config, init_app};
+use options::{args, check_conflicts, get_or_create_config, init_app};
 use tui::{backend::CrosstermBackend, Terminal};
-use utils::cancellation_token::CancellationToken;
 #[allow(unused_imports, reason = "this is needed if logging is enabled")]
 use utils::logging::*;
+use utils::{cancellation_token::CancellationToken, lock_file::FileLock};
 
 // Used for heap allocation debugging purposes.
 // #[global_allocator]
@@ -71,6 +85,20 @@ fn try_drawing(
     }
 }
 
+/// Saves a text snapshot of the selected graph, and tells the user where it went.
+fn save_graph_snapshot(
+    app: &mut App, painter: &canvas::Painter, graph_snapshots: &mut GraphSnapshots,
+) {
+    app.is_graph_snapshot_requested = false;
+
+    if let Some((name, snapshot)) = painter.graph_snapshot(app) {
+        app.status_message = Some(match graph_snapshots.save(name, &snapshot) {
+            Ok(message) => message,
+            Err(err) => format!("Couldn't save the {name} graph: {err}"),
+        });
+    }
+}
+
 /// Clean up the terminal before returning it to the user.
 fn cleanup_terminal(
     terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
@@ -145,6 +173,7 @@ fn panic_hook(panic_info: &PanicHookInfo<'_>) {
 /// Create a thread to poll for user inputs and forward them to the main thread.
 fn create_input_thread(
     sender: Sender<BottomEvent>, cancellation_token: Arc<CancellationToken>,
+    forward_mouse_moves: bool,
 ) -> JoinHandle<()> {
     thread::spawn(move || {
         let mut mouse_timer = Instant::now();
@@ -185,8 +214,19 @@ fn create_input_thread(
                                 }
                             }
                             Event::Mouse(mouse) => match mouse.kind {
-                                MouseEventKind::Moved | MouseEventKind::Drag(..) => {}
-                                MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+                                MouseEventKind::Moved if forward_mouse_moves => {
+                                    if Instant::now().duration_since(mouse_timer).as_millis() >= 20
+                                    {
+                                        if sender.send(BottomEvent::MouseInput(mouse)).is_err() {
+                                            break;
+                                        }
+                                        mouse_timer = Instant::now();
+                                    }
+                                }
+                                MouseEventKind::Moved => {}
+                                MouseEventKind::ScrollDown
+                                | MouseEventKind::ScrollUp
+                                | MouseEventKind::Drag(..) => {
                                     if Instant::now().duration_since(mouse_timer).as_millis() >= 20
                                     {
                                         if sender.send(BottomEvent::MouseInput(mouse)).is_err() {
@@ -212,30 +252,46 @@ fn create_input_thread(
     })
 }
 
-/// Create a thread to handle data collection.
+/// The least time between the start of a collection and a forced collection.
+const MIN_FORCED_COLLECTION_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Create a thread to handle data collection. It stops once it is sent
+/// [`CollectionThreadEvent::Stop`], or if the app drops its end of either
+/// channel.
 fn create_collection_thread(
     sender: Sender<BottomEvent>, control_receiver: Receiver<CollectionThreadEvent>,
     cancellation_token: Arc<CancellationToken>, app_config_fields: &AppConfigFields,
-    filters: DataFilters, used_widget_set: UsedWidgets,
+    filters: DataFilters, used_widget_set: UsedWidgets, show_network_interfaces: bool,
+    progress: CollectionProgress,
 ) -> JoinHandle<()> {
     let temp_type = app_config_fields.temperature_type;
     let use_current_cpu_total = app_config_fields.use_current_cpu_total;
     let unnormalized_cpu = app_config_fields.unnormalized_cpu;
     let show_average_cpu = app_config_fields.show_average_cpu;
-    let update_time = app_config_fields.update_rate;
+    let cpu_per_socket = app_config_fields.cpu_per_socket;
+    #[cfg(target_family = "unix")]
+    let uid_numeric = app_config_fields.uid_numeric;
+    let mut update_time = app_config_fields.update_rate;
+    let temperature_update_time = app_config_fields.temperature_update_rate;
 
     thread::spawn(move || {
         let mut data_state = data_collection::DataCollector::new(filters);
 
         data_state.set_data_collection(used_widget_set);
+        data_state.set_progress(progress);
         data_state.set_temperature_type(temp_type);
+        data_state.set_temperature_update_rate(Duration::from_millis(temperature_update_time));
         data_state.set_use_current_cpu_total(use_current_cpu_total);
         data_state.set_unnormalized_cpu(unnormalized_cpu);
         data_state.set_show_average_cpu(show_average_cpu);
+        data_state.set_cpu_per_socket(cpu_per_socket);
+        data_state.set_show_network_interfaces(show_network_interfaces);
+        #[cfg(target_family = "unix")]
+        data_state.set_uid_numeric(uid_numeric);
 
         data_state.init();
 
-        loop {
+        'collect: loop {
             // Check once at the very top... don't block though.
             if let Some(is_terminated) = cancellation_token.try_check() {
                 if is_terminated {
@@ -243,15 +299,7 @@ fn create_collection_thread(
                 }
             }
 
-            if let Ok(message) = control_receiver.try_recv() {
-                // trace!("Received message in collection thread: {message:?}");
-                match message {
-                    CollectionThreadEvent::Reset => {
-                        data_state.data.cleanup();
-                    }
-                }
-            }
-
+            let collection_start = Instant::now();
             data_state.update_data();
 
             // Yet another check to bail if needed... do not block!
@@ -267,14 +315,44 @@ fn create_collection_thread(
                 break;
             }
 
-            // Sleep while allowing for interruptions...
-            if cancellation_token.sleep_with_cancellation(Duration::from_millis(update_time)) {
-                break;
+            // Wait for the next collection, which a refresh can bring forward.
+            let collection_end = Instant::now();
+            let mut next_collection = collection_end + Duration::from_millis(update_time);
+            loop {
+                let timeout = next_collection.saturating_duration_since(Instant::now());
+
+                match control_receiver.recv_timeout(timeout) {
+                    Ok(CollectionThreadEvent::Reset) => {
+                        data_state.reset();
+                    }
+                    Ok(CollectionThreadEvent::UpdateUsedWidgets(used_widgets)) => {
+                        data_state.set_data_collection(used_widgets);
+                    }
+                    Ok(CollectionThreadEvent::UpdateRate(rate)) => {
+                        update_time = rate;
+                        next_collection = collection_end + Duration::from_millis(update_time);
+                    }
+                    Ok(CollectionThreadEvent::Refresh) => {
+                        // Debounce refreshes, which also keeps rates from being measured over
+                        // too short a time.
+                        if collection_start.elapsed() >= MIN_FORCED_COLLECTION_INTERVAL {
+                            break;
+                        }
+                    }
+                    Ok(CollectionThreadEvent::Stop) => break 'collect,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => break 'collect,
+                }
             }
         }
     })
 }
 
+/// How long to wait between cleaning out data older than `retention_ms`.
+fn cleaning_wait_time_ms(retention_ms: u64) -> u64 {
+    retention_ms + 60000
+}
+
 /// Main code to call.
 #[inline]
 pub fn start_bottom() -> anyhow::Result<()> {
@@ -282,6 +360,37 @@ pub fn start_bottom() -> anyhow::Result<()> {
 
     let args = args::get_args();
 
+    if let Some(theme) = &args.style.theme_preview {
+        return canvas::theme_preview::print_theme_preview(theme);
+    }
+
+    // The listing flags can be combined, and list everything, without any filters.
+    if args.temperature.list_sensors || args.general.list_disks || args.network.list_interfaces {
+        if args.temperature.list_sensors {
+            for name in data_collection::temperature::get_sensor_names() {
+                println!("{name}");
+            }
+        }
+
+        if args.general.list_disks || args.network.list_interfaces {
+            let mut collector = data_collection::DataCollector::new(DataFilters::default());
+
+            if args.general.list_disks {
+                for line in collector.list_disks() {
+                    println!("{line}");
+                }
+            }
+
+            if args.network.list_interfaces {
+                for name in collector.list_network_interfaces() {
+                    println!("{name}");
+                }
+            }
+        }
+
+        return Ok(());
+    }
+
     #[cfg(feature = "logging")]
     {
         if let Err(err) = init_logger(
@@ -295,8 +404,61 @@ pub fn start_bottom() -> anyhow::Result<()> {
     // Read from config file.
     let config = get_or_create_config(args.general.config_location.as_deref())?;
 
+    // Let the user know about any options that were ignored in favour of others.
+    for notice in check_conflicts(&args, &config)? {
+        eprintln!("Note: {notice}");
+    }
+
     // Create the "app" and initialize a bunch of stuff.
+    let state_overrides = StateOverrides::from_args(&args);
+    let headless = args.general.headless;
+    let log_data = args.general.log_data.clone();
+    let graph_export = args.general.graph_export.clone();
     let (mut app, widget_layout, styling) = init_app(args, config)?;
+    let mut graph_snapshots =
+        GraphSnapshots::new(graph_export, app.app_config_fields.instance_conflict);
+
+    // Without the TUI, there is no UI state to restore nor a terminal to set up.
+    if headless {
+        return headless::run_headless(app, log_data);
+    }
+
+    // Where to save and restore UI state, if enabled.
+    let state_path = if app.app_config_fields.save_state {
+        saved_state::state_path()
+    } else {
+        None
+    };
+
+    // Only one instance at a time writes to a state file; reading doesn't need the lock.
+    let state_lock = state_path.as_deref().and_then(|path| {
+        FileLock::acquire_for_writing(path, app.app_config_fields.instance_conflict)
+            .ok()
+            .flatten()
+    });
+    app.is_state_unsaved = state_path.is_some() && state_lock.is_none();
+
+    // Restore from the file this instance writes to, which may be suffixed, or
+    // from the shared one if that file hasn't been written yet.
+    if let Some(state) = state_lock
+        .as_ref()
+        .map(FileLock::path)
+        .into_iter()
+        .chain(state_path.as_deref())
+        .find_map(SavedState::load)
+    {
+        state.restore(&mut app, state_overrides);
+    }
+
+    // Start serving metrics, if enabled.
+    let metrics = SharedMetrics::default();
+    let metrics_server =
+        match app.app_config_fields.metrics_listen {
+            Some(addr) => Some(MetricsServer::start(addr, metrics.clone()).map_err(|err| {
+                anyhow::anyhow!("bottom couldn't serve metrics on '{addr}': {err}")
+            })?),
+            None => None,
+        };
 
     // Create painter and set colours.
     let mut painter = canvas::Painter::init(widget_layout, styling)?;
@@ -317,17 +479,28 @@ pub fn start_bottom() -> anyhow::Result<()> {
         &app.app_config_fields,
         app.filters.clone(),
         app.used_widgets,
+        app.collects_network_interfaces(),
+        app.collection_watchdog.progress(),
     );
 
     // Set up the input handling loop thread.
-    let _input_thread = create_input_thread(sender.clone(), cancellation_token.clone());
+    let _input_thread = create_input_thread(
+        sender.clone(),
+        cancellation_token.clone(),
+        app.app_config_fields.process_command_tooltip && !app.app_config_fields.disable_click,
+    );
 
-    // Set up the cleaning loop thread.
+    // Set up the cleaning loop thread. The retention can change while running,
+    // so how long to wait between cleanings is shared with it.
+    let cleaning_wait_time = Arc::new(AtomicU64::new(cleaning_wait_time_ms(
+        app.app_config_fields.retention_ms,
+    )));
     let _cleaning_thread = {
         let cancellation_token = cancellation_token.clone();
         let cleaning_sender = sender.clone();
-        let offset_wait_time = app.app_config_fields.retention_ms + 60000;
+        let cleaning_wait_time = cleaning_wait_time.clone();
         thread::spawn(move || loop {
+            let offset_wait_time = cleaning_wait_time.load(Ordering::Relaxed);
             if cancellation_token.sleep_with_cancellation(Duration::from_millis(offset_wait_time)) {
                 break;
             }
@@ -376,12 +549,29 @@ pub fn start_bottom() -> anyhow::Result<()> {
 
     let mut first_run = true;
 
+    // How often to check whether the collection thread has stalled if no events arrive.
+    const WATCHDOG_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
     // Draw once first to initialize the canvas, so it doesn't feel like it's
     // frozen.
     try_drawing(&mut terminal, &mut app, &mut painter)?;
 
     loop {
-        if let Ok(recv) = receiver.recv() {
+        let recv = receiver.recv_timeout(WATCHDOG_CHECK_INTERVAL);
+
+        if let Err(RecvTimeoutError::Timeout) = recv {
+            // Nothing arrived in a while; redraw so any staleness indicators or the
+            // countdown to the next update are shown and kept up to date.
+            let now = Instant::now();
+            if app.collection_watchdog.check(now).is_some()
+                || app.collection_watchdog.countdown(now).is_some()
+            {
+                try_drawing(&mut terminal, &mut app, &mut painter)?;
+            }
+            continue;
+        }
+
+        if let Ok(recv) = recv {
             match recv {
                 BottomEvent::Terminate => {
                     break;
@@ -390,9 +580,25 @@ pub fn start_bottom() -> anyhow::Result<()> {
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
                 BottomEvent::KeyInput(event) => {
+                    app.status_message = None;
+                    let retention_ms = app.app_config_fields.retention_ms;
+                    let used_widgets = app.used_widgets;
                     if handle_key_event_or_break(event, &mut app, &collection_thread_ctrl_sender) {
                         break;
                     }
+                    if app.used_widgets != used_widgets {
+                        let _ = collection_thread_ctrl_sender
+                            .send(CollectionThreadEvent::UpdateUsedWidgets(app.used_widgets));
+                    }
+                    if app.app_config_fields.retention_ms != retention_ms {
+                        cleaning_wait_time.store(
+                            cleaning_wait_time_ms(app.app_config_fields.retention_ms),
+                            Ordering::Relaxed,
+                        );
+                    }
+                    if app.is_graph_snapshot_requested {
+                        save_graph_snapshot(&mut app, &painter, &mut graph_snapshots);
+                    }
                     app.update_data();
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
@@ -409,6 +615,21 @@ pub fn start_bottom() -> anyhow::Result<()> {
                 BottomEvent::Update(data) => {
                     app.data_collection.eat_data(data);
 
+                    if metrics_server.is_some() {
+                        if let Ok(mut metrics) = metrics.lock() {
+                            *metrics = data_export::render_metrics(
+                                &app.data_collection,
+                                app.app_config_fields.temperature_type,
+                            );
+                        }
+                    }
+                    app.collection_watchdog.on_update(Instant::now());
+
+                    if !app.kill_tracker.is_empty() {
+                        app.kill_tracker
+                            .update(&app.data_collection.process_data.process_harvest);
+                    }
+
                     // This thing is required as otherwise, some widgets can't draw correctly w/o
                     // some data (or they need to be re-drawn).
                     if first_run {
@@ -416,6 +637,8 @@ pub fn start_bottom() -> anyhow::Result<()> {
                         app.is_force_redraw = true;
                     }
 
+                    app.frozen_state.on_update(&app.data_collection);
+
                     if !app.frozen_state.is_frozen() {
                         // Convert all data into data for the displayed widgets.
 
@@ -438,10 +661,47 @@ pub fn start_bottom() -> anyhow::Result<()> {
                             if let Some(total_tx_display) = network_data.total_tx_display {
                                 app.converted_data.total_tx_display = total_tx_display;
                             }
+
+                            if app.app_config_fields.network_show_session_totals
+                                && !app.app_config_fields.use_basic_mode
+                                && !app.app_config_fields.use_old_network_legend
+                            {
+                                let [rx_session, tx_session] = convert_network_session_totals(
+                                    &app.data_collection,
+                                    &app.app_config_fields.network_unit_type,
+                                    app.app_config_fields.network_use_binary_prefix,
+                                );
+                                app.converted_data.rx_display.push_str(&rx_session);
+                                app.converted_data.tx_display.push_str(&tx_session);
+                            }
+
+                            if app.app_config_fields.network_show_session_max {
+                                app.converted_data.network_session_max =
+                                    convert_network_session_max(
+                                        &app.data_collection,
+                                        &app.app_config_fields.network_scale_type,
+                                        &app.app_config_fields.network_unit_type,
+                                        app.app_config_fields.network_use_binary_prefix,
+                                    );
+                            }
+
+                            if app.uses_network_interfaces() {
+                                app.converted_data.network_interface_data =
+                                    convert_network_interface_points(
+                                        &app.data_collection,
+                                        &app.app_config_fields.network_scale_type,
+                                        &app.app_config_fields.network_unit_type,
+                                        app.app_config_fields.network_use_binary_prefix,
+                                    );
+                            }
                         }
 
                         if app.used_widgets.use_disk {
-                            app.converted_data.convert_disk_data(&app.data_collection);
+                            app.converted_data.convert_disk_data(
+                                &app.data_collection,
+                                app.app_config_fields.compact_bytes,
+                                &app.app_config_fields.disk_aliases,
+                            );
 
                             for disk in app.states.disk_state.widget_states.values_mut() {
                                 disk.force_data_update();
@@ -484,25 +744,50 @@ pub fn start_bottom() -> anyhow::Result<()> {
                                     convert_gpu_data(&app.data_collection);
                             }
 
-                            app.converted_data.mem_labels =
-                                convert_mem_label(&app.data_collection.memory_harvest);
+                            app.converted_data.mem_labels = convert_mem_label(
+                                &app.data_collection.memory_harvest,
+                                app.app_config_fields.compact_bytes,
+                            );
 
-                            app.converted_data.swap_labels =
-                                convert_mem_label(&app.data_collection.swap_harvest);
+                            app.converted_data.swap_labels = convert_mem_label(
+                                &app.data_collection.swap_harvest,
+                                app.app_config_fields.compact_bytes,
+                            );
 
                             #[cfg(not(target_os = "windows"))]
                             {
-                                app.converted_data.cache_labels =
-                                    convert_mem_label(&app.data_collection.cache_harvest);
+                                app.converted_data.cache_labels = convert_mem_label(
+                                    &app.data_collection.cache_harvest,
+                                    app.app_config_fields.compact_bytes,
+                                );
                             }
 
                             #[cfg(feature = "zfs")]
                             {
-                                app.converted_data.arc_labels =
-                                    convert_mem_label(&app.data_collection.arc_harvest);
+                                app.converted_data.arc_labels = convert_arc_label(
+                                    &app.data_collection.arc_harvest,
+                                    app.data_collection.arc_hit_ratio,
+                                    app.app_config_fields.compact_bytes,
+                                );
+                                app.converted_data.arc_detail_labels = convert_arc_detail_labels(
+                                    &app.data_collection.arc_breakdown,
+                                    app.app_config_fields.compact_bytes,
+                                );
                             }
                         }
 
+                        #[cfg(feature = "gpu")]
+                        if app.used_widgets.use_gpu_util {
+                            app.converted_data.gpu_util_data =
+                                convert_gpu_util_data(&app.data_collection);
+                        }
+
+                        #[cfg(feature = "battery")]
+                        if app.used_widgets.use_battery {
+                            app.converted_data.battery_data =
+                                convert_battery_data_points(&app.data_collection);
+                        }
+
                         if app.used_widgets.use_cpu {
                             app.converted_data.convert_cpu_data(&app.data_collection);
                             app.converted_data.load_avg_data = app.data_collection.load_avg_harvest;
@@ -529,7 +814,21 @@ pub fn start_bottom() -> anyhow::Result<()> {
     // I think doing it in this order is safe...
     // TODO: maybe move the cancellation token to the ctrl-c handler?
     cancellation_token.cancel();
+    // The collection thread waits on its channel rather than the token.
+    let _ = collection_thread_ctrl_sender.send(CollectionThreadEvent::Stop);
+    drop(metrics_server);
     cleanup_terminal(&mut terminal)?;
 
+    graph_snapshots.write_deferred()?;
+
+    if let Some(lock) = &state_lock {
+        if let Err(err) = SavedState::capture(&app).save(lock.path()) {
+            eprintln!(
+                "Note: bottom couldn't save its state to '{}': {err}",
+                lock.path().display()
+            );
+        }
+    }
+
     Ok(())
 }
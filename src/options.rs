@@ -4,12 +4,14 @@
 
 pub mod args;
 pub mod config;
+mod conflicts;
 mod error;
 
 use std::{
     convert::TryInto,
     fs,
     io::Write,
+    net::SocketAddr,
     path::{Path, PathBuf},
     str::FromStr,
     time::{Duration, Instant},
@@ -18,6 +20,7 @@ use std::{
 use anyhow::{Context, Result};
 use config::style::Styles;
 pub use config::Config;
+pub(crate) use conflicts::check_conflicts;
 pub(crate) use error::{OptionError, OptionResult};
 use hashbrown::{HashMap, HashSet};
 use indexmap::IndexSet;
@@ -30,11 +33,11 @@ use self::{
     config::{layout::Row, IgnoreList, StringOrNum},
 };
 use crate::{
-    app::{filter::Filter, layout_manager::*, *},
-    canvas::components::time_chart::LegendPosition,
+    app::{data_farmer::DEFAULT_SPIKE_THRESHOLD, filter::Filter, layout_manager::*, *},
+    canvas::components::{data_table::ColumnSeparator, time_chart::LegendPosition},
     constants::*,
     data_collection::temperature::TemperatureType,
-    utils::data_units::DataUnit,
+    utils::{data_prefixes::*, data_units::DataUnit, lock_file::InstanceConflict},
     widgets::*,
 };
 
@@ -206,15 +209,23 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
 
     let styling = Styles::new(args, config)?;
 
-    let (widget_layout, default_widget_id, default_widget_type_option) =
-        get_widget_layout(args, config)
+    let use_basic_mode = is_flag_enabled!(basic, args.general, config);
+
+    let (layout_profiles, default_widget_type_option) =
+        get_widget_layout(args, config, use_basic_mode)
             .context("Found an issue while trying to build the widget layout.")?;
+    let widget_layout = layout_profiles.current().layout.clone();
+    let default_widget_id = layout_profiles.current().default_widget_id;
+
+    // The layouts for the other mode, to switch to while running. An invalid layout here
+    // just means switching isn't possible, as it wasn't asked for at startup.
+    let alternate_layout_profiles = get_widget_layout(args, config, !use_basic_mode)
+        .ok()
+        .map(|(profiles, _)| profiles);
 
     let retention_ms = get_retention(args, config)?;
     let autohide_time = is_flag_enabled!(autohide_time, args.general, config);
     let default_time_value = get_default_time_value(args, config, retention_ms)?;
-
-    let use_basic_mode = is_flag_enabled!(basic, args.general, config);
     let expanded = is_flag_enabled!(expanded, args.general, config);
 
     // For processes
@@ -238,6 +249,7 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     let mut temp_state_map: HashMap<u64, TempWidgetState> = HashMap::new();
     let mut disk_state_map: HashMap<u64, DiskTableWidget> = HashMap::new();
     let mut battery_state_map: HashMap<u64, BatteryWidgetState> = HashMap::new();
+    let mut gpu_state_map: HashMap<u64, GpuWidgetState> = HashMap::new();
 
     let autohide_timer = if autohide_time {
         Some(Instant::now())
@@ -247,14 +259,17 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
 
     let mut initial_widget_id: u64 = default_widget_id;
     let mut initial_widget_type = Proc;
-    let is_custom_layout = config.row.is_some();
-    let mut used_widget_set = HashSet::new();
+    let is_custom_layout = config.row.is_some() || config.layouts.is_some();
 
     let network_unit_type = get_network_unit_type(args, config);
     let network_scale_type = get_network_scale_type(args, config);
     let network_use_binary_prefix =
         is_flag_enabled!(network_use_binary_prefix, args.network, config);
 
+    let disk_columns = get_disk_columns(config);
+    let temp_columns = get_temp_columns(config);
+    let memory_series = get_memory_series(config);
+
     let proc_columns: Option<IndexSet<ProcWidgetColumn>> = {
         config.processes.as_ref().and_then(|cfg| {
             if cfg.columns.is_empty() {
@@ -268,19 +283,30 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         })
     };
 
+    let custom_proc_columns = get_custom_proc_columns(config)?;
+    let (default_kill_signal, kill_signals) = get_kill_signals(args, config)?;
+
     let network_legend_position = get_network_legend_position(args, config)?;
     let memory_legend_position = get_memory_legend_position(args, config)?;
+    let gpu_legend_position = get_gpu_legend_position(args, config)?;
 
     // TODO: Can probably just reuse the options struct.
     let app_config_fields = AppConfigFields {
         update_rate: get_update_rate(args, config)?,
+        temperature_update_rate: get_temperature_update_rate(config)?,
         temperature_type: get_temperature(args, config)
             .context("Update 'temperature_type' in your config file.")?,
         show_average_cpu: get_show_average_cpu(args, config),
         use_dot: is_flag_enabled!(dot_marker, args.general, config),
+        use_thick_lines: is_flag_enabled!(thick_lines, args.general, config),
+        newest_on_left: is_flag_enabled!(newest_on_left, args.general, config),
         cpu_left_legend: is_flag_enabled!(cpu_left_legend, args.cpu, config),
         use_current_cpu_total: is_flag_enabled!(current_usage, args.process, config),
         unnormalized_cpu: is_flag_enabled!(unnormalized_cpu, args.process, config),
+        cpu_legend_heatmap: get_cpu_legend_heatmap(config) && !args.style.no_color,
+        cpu_show_load_avg: get_cpu_show_load_avg(config),
+        cpu_per_socket: get_cpu_per_socket(config),
+        cpu_breakdown: get_cpu_breakdown(config),
         use_basic_mode,
         default_time_value,
         time_interval: get_time_interval(args, config, retention_ms)?,
@@ -297,13 +323,38 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
             config
         ),
         is_advanced_kill,
+        default_kill_signal,
+        kill_signals,
         memory_legend_position,
+        gpu_legend_position,
         network_legend_position,
         network_scale_type,
         network_unit_type,
         network_use_binary_prefix,
+        network_show_interfaces: get_network_show_interfaces(config),
+        network_lock_to_session_max: get_network_lock_to_session_max(config),
+        network_show_session_max: get_network_show_session_max(config),
+        network_show_session_totals: get_network_show_session_totals(config),
+        network_y_max: get_network_y_max(config)?,
+        uid_numeric: get_uid_numeric(config),
+        process_column_separator: get_process_column_separator(config),
+        process_tree_style: get_process_tree_style(config),
+        process_show_group_count: get_process_show_group_count(config),
+        process_group_threshold: get_process_group_threshold(config),
+        process_spike_threshold: get_process_spike_threshold(config),
+        process_case_sensitive_sort: get_process_case_sensitive_sort(config),
+        disk_case_sensitive_sort: get_disk_case_sensitive_sort(config),
+        disk_aliases: get_disk_aliases(config),
+        temp_case_sensitive_sort: get_temp_case_sensitive_sort(config),
         retention_ms,
         dedicated_average_row: get_dedicated_avg_row(config),
+        compact_bytes: get_compact_bytes(config),
+        process_command_tooltip: get_process_command_tooltip(config),
+        save_state: is_flag_enabled!(save_state, args.general, config),
+        instance_conflict: get_instance_conflict(config)?,
+        metrics_listen: get_metrics_listen(args, config)?,
+        merge_tolerance: get_merge_tolerance(args, config)?,
+        debug_ui: args.general.debug_ui,
     };
 
     let table_config = ProcTableConfig {
@@ -314,118 +365,131 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         is_command: is_default_command,
     };
 
-    for row in &widget_layout.rows {
-        for col in &row.children {
-            for col_row in &col.children {
-                for widget in &col_row.children {
-                    widget_map.insert(widget.widget_id, widget.clone());
-                    if let Some(default_widget_type) = &default_widget_type_option {
-                        if !is_custom_layout || use_basic_mode {
-                            match widget.widget_type {
-                                BasicCpu => {
-                                    if let Cpu = *default_widget_type {
-                                        initial_widget_id = widget.widget_id;
-                                        initial_widget_type = Cpu;
-                                    }
-                                }
-                                BasicMem => {
-                                    if let Mem = *default_widget_type {
-                                        initial_widget_id = widget.widget_id;
-                                        initial_widget_type = Cpu;
-                                    }
-                                }
-                                BasicNet => {
-                                    if let Net = *default_widget_type {
-                                        initial_widget_id = widget.widget_id;
-                                        initial_widget_type = Cpu;
-                                    }
-                                }
-                                _ => {
-                                    if *default_widget_type == widget.widget_type {
-                                        initial_widget_id = widget.widget_id;
-                                        initial_widget_type = widget.widget_type.clone();
-                                    }
-                                }
-                            }
+    for widget in widget_layout.widgets() {
+        widget_map.insert(widget.widget_id, widget.clone());
+        if let Some(default_widget_type) = &default_widget_type_option {
+            if !is_custom_layout || use_basic_mode {
+                match widget.widget_type {
+                    BasicCpu => {
+                        if let Cpu = *default_widget_type {
+                            initial_widget_id = widget.widget_id;
+                            initial_widget_type = Cpu;
                         }
                     }
-
-                    used_widget_set.insert(widget.widget_type.clone());
-
-                    match widget.widget_type {
-                        Cpu => {
-                            cpu_state_map.insert(
-                                widget.widget_id,
-                                CpuWidgetState::new(
-                                    &app_config_fields,
-                                    default_cpu_selection,
-                                    default_time_value,
-                                    autohide_timer,
-                                    &styling,
-                                ),
-                            );
-                        }
-                        Mem => {
-                            mem_state_map.insert(
-                                widget.widget_id,
-                                MemWidgetState::init(default_time_value, autohide_timer),
-                            );
-                        }
-                        Net => {
-                            net_state_map.insert(
-                                widget.widget_id,
-                                NetWidgetState::init(default_time_value, autohide_timer),
-                            );
+                    BasicMem => {
+                        if let Mem = *default_widget_type {
+                            initial_widget_id = widget.widget_id;
+                            initial_widget_type = Cpu;
                         }
-                        Proc => {
-                            let mode = if is_grouped {
-                                ProcWidgetMode::Grouped
-                            } else if is_default_tree {
-                                ProcWidgetMode::Tree {
-                                    collapsed_pids: Default::default(),
-                                }
-                            } else {
-                                ProcWidgetMode::Normal
-                            };
-
-                            proc_state_map.insert(
-                                widget.widget_id,
-                                ProcWidgetState::new(
-                                    &app_config_fields,
-                                    mode,
-                                    table_config,
-                                    &styling,
-                                    &proc_columns,
-                                ),
-                            );
-                        }
-                        Disk => {
-                            disk_state_map.insert(
-                                widget.widget_id,
-                                DiskTableWidget::new(
-                                    &app_config_fields,
-                                    &styling,
-                                    config.disk.as_ref().map(|cfg| cfg.columns.as_slice()),
-                                ),
-                            );
-                        }
-                        Temp => {
-                            temp_state_map.insert(
-                                widget.widget_id,
-                                TempWidgetState::new(&app_config_fields, &styling),
-                            );
+                    }
+                    BasicNet => {
+                        if let Net = *default_widget_type {
+                            initial_widget_id = widget.widget_id;
+                            initial_widget_type = Cpu;
                         }
-                        Battery => {
-                            battery_state_map
-                                .insert(widget.widget_id, BatteryWidgetState::default());
+                    }
+                    _ => {
+                        if *default_widget_type == widget.widget_type {
+                            initial_widget_id = widget.widget_id;
+                            initial_widget_type = widget.widget_type.clone();
                         }
-                        _ => {}
                     }
                 }
             }
         }
     }
 
+    // Widgets in every layout get states, so that they are kept when switching layouts. This
+    // includes the layouts of the other mode, though their data is only collected once switched to.
+    for widget in layout_profiles
+        .iter()
+        .chain(
+            alternate_layout_profiles
+                .iter()
+                .flat_map(LayoutProfiles::iter),
+        )
+        .flat_map(|profile| profile.layout.widgets())
+    {
+        match widget.widget_type {
+            Cpu => {
+                cpu_state_map.insert(
+                    widget.widget_id,
+                    CpuWidgetState::new(
+                        &app_config_fields,
+                        default_cpu_selection,
+                        default_time_value,
+                        autohide_timer,
+                        &styling,
+                    ),
+                );
+            }
+            Mem => {
+                mem_state_map.insert(
+                    widget.widget_id,
+                    MemWidgetState::init(default_time_value, autohide_timer, memory_series.clone()),
+                );
+            }
+            Net => {
+                net_state_map.insert(
+                    widget.widget_id,
+                    NetWidgetState::init(
+                        default_time_value,
+                        autohide_timer,
+                        widget.interface.clone(),
+                    ),
+                );
+            }
+            Proc => {
+                let mode = if is_grouped {
+                    ProcWidgetMode::Grouped
+                } else if is_default_tree {
+                    ProcWidgetMode::Tree {
+                        collapsed_pids: Default::default(),
+                    }
+                } else {
+                    ProcWidgetMode::Normal
+                };
+
+                proc_state_map.insert(
+                    widget.widget_id,
+                    ProcWidgetState::new(
+                        &app_config_fields,
+                        mode,
+                        table_config,
+                        &styling,
+                        &proc_columns,
+                        &custom_proc_columns,
+                    ),
+                );
+            }
+            Disk => {
+                disk_state_map.insert(
+                    widget.widget_id,
+                    DiskTableWidget::new(&app_config_fields, &styling, disk_columns.as_deref()),
+                );
+            }
+            Temp => {
+                temp_state_map.insert(
+                    widget.widget_id,
+                    TempWidgetState::new(&app_config_fields, &styling, temp_columns.as_deref()),
+                );
+            }
+            Battery => {
+                battery_state_map.insert(
+                    widget.widget_id,
+                    BatteryWidgetState::init(default_time_value, autohide_timer),
+                );
+            }
+            Gpu => {
+                gpu_state_map.insert(
+                    widget.widget_id,
+                    GpuWidgetState::init(default_time_value, autohide_timer),
+                );
+            }
+            _ => {}
+        }
+    }
+
     let basic_table_widget_state = if use_basic_mode {
         Some(match initial_widget_type {
             Proc | Disk | Temp => BasicTableWidgetState {
@@ -449,30 +513,31 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         None
     };
 
-    let use_mem = used_widget_set.get(&Mem).is_some() || used_widget_set.get(&BasicMem).is_some();
-    let used_widgets = UsedWidgets {
-        use_cpu: used_widget_set.get(&Cpu).is_some() || used_widget_set.get(&BasicCpu).is_some(),
-        use_mem,
-        use_cache: use_mem && get_enable_cache_memory(args, config),
-        use_gpu: get_enable_gpu(args, config),
-        use_net: used_widget_set.get(&Net).is_some() || used_widget_set.get(&BasicNet).is_some(),
-        use_proc: used_widget_set.get(&Proc).is_some(),
-        use_disk: used_widget_set.get(&Disk).is_some(),
-        use_temp: used_widget_set.get(&Temp).is_some(),
-        use_battery: used_widget_set.get(&Battery).is_some(),
-    };
+    let mut used_widgets = get_used_widgets(args, config, &layout_profiles);
+    let mut alternate_used_widgets = alternate_layout_profiles
+        .as_ref()
+        .map(|profiles| get_used_widgets(args, config, profiles))
+        .unwrap_or_default();
+
+    // Metrics are served for everything, whatever the layout shows.
+    if app_config_fields.metrics_listen.is_some() {
+        used_widgets = used_widgets.with_exported();
+        alternate_used_widgets = alternate_used_widgets.with_exported();
+    }
 
-    let (disk_name_filter, disk_mount_filter) = {
+    let (disk_name_filter, disk_mount_filter, disk_fs_filter) = {
         match &config.disk {
             Some(cfg) => {
                 let df = get_ignore_list(&cfg.name_filter)
                     .context("Update 'disk.name_filter' in your config file")?;
                 let mf = get_ignore_list(&cfg.mount_filter)
                     .context("Update 'disk.mount_filter' in your config file")?;
+                let ff = get_ignore_list(&cfg.fs_filter)
+                    .context("Update 'disk.fs_filter' in your config file")?;
 
-                (df, mf)
+                (df, mf, ff)
             }
-            None => (None, None),
+            None => (None, None, None),
         }
     };
     let temp_sensor_filter = match &config.temperature {
@@ -480,10 +545,24 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
             .context("Update 'temperature.sensor_filter' in your config file")?,
         None => None,
     };
-    let net_interface_filter = match &config.network {
-        Some(cfg) => get_ignore_list(&cfg.interface_filter)
-            .context("Update 'network.interface_filter' in your config file")?,
-        None => None,
+    let net_interface_filter = if !args.network.network_interface.is_empty() {
+        // Interfaces passed in as arguments are treated as an exact-match allow list.
+        let interface_list = IgnoreList {
+            is_list_ignored: false,
+            list: args.network.network_interface.clone(),
+            regex: false,
+            case_sensitive: true,
+            whole_word: true,
+        };
+
+        get_ignore_list(&Some(interface_list))
+            .context("Update the '--network_interface' arguments")?
+    } else {
+        match &config.network {
+            Some(cfg) => get_ignore_list(&cfg.interface_filter)
+                .context("Update 'network.interface_filter' in your config file")?,
+            None => None,
+        }
     };
 
     let states = AppWidgetStates {
@@ -494,6 +573,7 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         temp_state: TempState::init(temp_state_map),
         disk_state: DiskState::init(disk_state_map),
         battery_state: AppBatteryState::init(battery_state_map),
+        gpu_state: GpuState::init(gpu_state_map),
         basic_table_widget_state,
     };
 
@@ -501,93 +581,161 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     let filters = DataFilters {
         disk_filter: disk_name_filter,
         mount_filter: disk_mount_filter,
+        fs_filter: disk_fs_filter,
         temp_filter: temp_sensor_filter,
         net_filter: net_interface_filter,
     };
     let is_expanded = expanded && !use_basic_mode;
 
-    Ok((
-        App::new(
-            app_config_fields,
-            states,
-            widget_map,
-            current_widget,
-            used_widgets,
-            filters,
-            is_expanded,
-        ),
-        widget_layout,
-        styling,
-    ))
+    let mut app = App::new(
+        app_config_fields,
+        states,
+        widget_map,
+        current_widget,
+        used_widgets,
+        filters,
+        is_expanded,
+    );
+    app.layout_profiles = Some(layout_profiles);
+    app.alternate_layout_profiles = alternate_layout_profiles;
+    app.alternate_used_widgets = alternate_used_widgets;
+
+    Ok((app, widget_layout, styling))
 }
 
-fn get_widget_layout(
-    args: &BottomArgs, config: &Config,
-) -> OptionResult<(BottomLayout, u64, Option<BottomWidgetType>)> {
-    let cpu_left_legend = is_flag_enabled!(cpu_left_legend, args.cpu, config);
+/// Returns what data needs to be collected for the widgets in any of the given
+/// layouts.
+fn get_used_widgets(
+    args: &BottomArgs, config: &Config, layout_profiles: &LayoutProfiles,
+) -> UsedWidgets {
+    use BottomWidgetType::*;
 
-    let (default_widget_type, mut default_widget_count) =
-        get_default_widget_and_count(args, config)?;
-    let mut default_widget_id = 1;
+    let used_widget_set: HashSet<BottomWidgetType> = layout_profiles
+        .iter()
+        .flat_map(|profile| profile.layout.widgets())
+        .map(|widget| widget.widget_type.clone())
+        .collect();
 
-    let bottom_layout = if is_flag_enabled!(basic, args.general, config) {
-        default_widget_id = DEFAULT_WIDGET_ID;
+    let use_mem = used_widget_set.get(&Mem).is_some() || used_widget_set.get(&BasicMem).is_some();
+    let use_proc = used_widget_set.get(&Proc).is_some();
+    let shows_exe = config.processes.as_ref().is_some_and(|cfg| {
+        cfg.columns
+            .iter()
+            .any(|column| ProcWidgetColumn::from(column) == ProcWidgetColumn::ExeLocation)
+    });
 
-        BottomLayout::init_basic_default(get_use_battery(args, config))
+    UsedWidgets {
+        use_cpu: used_widget_set.get(&Cpu).is_some() || used_widget_set.get(&BasicCpu).is_some(),
+        use_mem,
+        use_cache: use_mem && get_enable_cache_memory(args, config),
+        use_gpu: get_enable_gpu(args, config),
+        use_gpu_util: used_widget_set.get(&Gpu).is_some(),
+        use_net: used_widget_set.get(&Net).is_some() || used_widget_set.get(&BasicNet).is_some(),
+        use_proc,
+        use_proc_exe: use_proc && shows_exe,
+        use_disk: used_widget_set.get(&Disk).is_some(),
+        use_temp: used_widget_set.get(&Temp).is_some(),
+        use_battery: used_widget_set.get(&Battery).is_some(),
+        use_load_avg: used_widget_set.get(&LoadAvg).is_some(),
+    }
+}
+
+/// Builds the layouts to pick from, either for basic mode or for the normal
+/// layout.
+fn get_widget_layout(
+    args: &BottomArgs, config: &Config, use_basic_mode: bool,
+) -> OptionResult<(LayoutProfiles, Option<BottomWidgetType>)> {
+    let (default_widget_type, _) = get_default_widget_and_count(args, config)?;
+
+    let profiles = if use_basic_mode {
+        vec![LayoutProfile::new(
+            BottomLayout::init_basic_default(get_use_battery(args, config)),
+            DEFAULT_WIDGET_ID,
+        )]
     } else {
-        let ref_row: Vec<Row>; // Required to handle reference
-        let rows = match &config.row {
-            Some(r) => r,
-            None => {
+        let mut profiles = Vec::new();
+
+        match (&config.row, &config.layouts) {
+            (Some(rows), _) => profiles.push(get_layout_profile(args, config, rows, "[[row]]")?),
+            (None, Some(_)) => {}
+            (None, None) => {
                 // This cannot (like it really shouldn't) fail!
-                ref_row = toml_edit::de::from_str::<Config>(if get_use_battery(args, config) {
+                let rows = toml_edit::de::from_str::<Config>(if get_use_battery(args, config) {
                     DEFAULT_BATTERY_LAYOUT
                 } else {
                     DEFAULT_LAYOUT
                 })?
                 .row
                 .unwrap();
-                &ref_row
+
+                profiles.push(get_layout_profile(args, config, &rows, "[[row]]")?);
             }
-        };
+        }
 
-        let mut iter_id = 0; // A lazy way of forcing unique IDs *shrugs*
-        let mut total_height_ratio = 0;
+        for layout in config.layouts.iter().flatten() {
+            let mut profile = get_layout_profile(args, config, &layout.row, "[[layouts.row]]")?;
+            profile.min_width = layout.min_width;
+            profile.min_height = layout.min_height;
+            profiles.push(profile);
+        }
 
-        let mut ret_bottom_layout = BottomLayout {
-            rows: rows
-                .iter()
-                .map(|row| {
-                    row.convert_row_to_bottom_row(
-                        &mut iter_id,
-                        &mut total_height_ratio,
-                        &mut default_widget_id,
-                        &default_widget_type,
-                        &mut default_widget_count,
-                        cpu_left_legend,
-                    )
-                    .map_err(|err| OptionError::config(err.to_string()))
-                })
-                .collect::<OptionResult<Vec<_>>>()?,
-            total_row_height_ratio: total_height_ratio,
-        };
+        profiles
+    };
 
-        // Confirm that we have at least ONE widget left - if not, error out!
-        if iter_id > 0 {
-            ret_bottom_layout.get_movement_mappings();
-            ret_bottom_layout
-        } else {
-            return Err(OptionError::config(
-                "have at least one widget under the '[[row]]' section.",
-            ));
-        }
+    let profiles = LayoutProfiles::new(profiles).ok_or_else(|| {
+        OptionError::config(
+            "have a layout without 'min_width' or 'min_height' to fall back to, either under \
+            '[[row]]' or '[[layouts]]'.",
+        )
+    })?;
+
+    Ok((profiles, default_widget_type))
+}
+
+/// Builds a layout from the rows under the `section` of the config.
+fn get_layout_profile(
+    args: &BottomArgs, config: &Config, rows: &[Row], section: &str,
+) -> OptionResult<LayoutProfile> {
+    let cpu_left_legend = is_flag_enabled!(cpu_left_legend, args.cpu, config);
+
+    let (default_widget_type, mut default_widget_count) =
+        get_default_widget_and_count(args, config)?;
+    let mut default_widget_id = 1;
+
+    let mut iter_id = 0; // A lazy way of forcing unique IDs *shrugs*
+    let mut total_height_ratio = 0;
+
+    let mut ret_bottom_layout = BottomLayout {
+        rows: rows
+            .iter()
+            .map(|row| {
+                row.convert_row_to_bottom_row(
+                    &mut iter_id,
+                    &mut total_height_ratio,
+                    &mut default_widget_id,
+                    &default_widget_type,
+                    &mut default_widget_count,
+                    cpu_left_legend,
+                )
+                .map_err(|err| OptionError::config(err.to_string()))
+            })
+            .collect::<OptionResult<Vec<_>>>()?,
+        total_row_height_ratio: total_height_ratio,
     };
 
-    Ok((bottom_layout, default_widget_id, default_widget_type))
+    // Confirm that we have at least ONE widget left - if not, error out!
+    if iter_id > 0 {
+        ret_bottom_layout.get_movement_mappings();
+        Ok(LayoutProfile::new(ret_bottom_layout, default_widget_id))
+    } else {
+        Err(OptionError::config(format!(
+            "have at least one widget under the '{section}' section."
+        )))
+    }
 }
 
 #[inline]
-fn try_parse_ms(s: &str) -> Result<u64, ()> {
+pub(crate) fn try_parse_ms(s: &str) -> Result<u64, ()> {
     Ok(if let Ok(val) = humantime::parse_duration(s) {
         val.as_millis().try_into().map_err(|_| ())?
     } else if let Ok(val) = s.parse::<u64>() {
@@ -677,6 +825,21 @@ fn get_update_rate(args: &BottomArgs, config: &Config) -> OptionResult<u64> {
         config.flags.as_ref().and_then(|flags| flags.rate.as_ref()),
         DEFAULT_REFRESH_RATE_IN_MILLISECONDS,
         "rate",
+        Some(MIN_REFRESH_RATE_IN_MILLISECONDS),
+        None,
+    )
+}
+
+#[inline]
+fn get_temperature_update_rate(config: &Config) -> OptionResult<u64> {
+    parse_ms_option!(
+        &None::<String>,
+        config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.temperature_rate.as_ref()),
+        DEFAULT_TEMPERATURE_REFRESH_RATE_IN_MILLISECONDS,
+        "temperature_rate",
         Some(250),
         None,
     )
@@ -731,6 +894,336 @@ fn get_dedicated_avg_row(config: &Config) -> bool {
     conf
 }
 
+fn get_compact_bytes(config: &Config) -> bool {
+    config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.compact_bytes)
+        .unwrap_or(false)
+}
+
+fn get_process_command_tooltip(config: &Config) -> bool {
+    config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.process_command_tooltip)
+        .unwrap_or(false)
+}
+
+fn get_instance_conflict(config: &Config) -> OptionResult<InstanceConflict> {
+    match config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.instance_conflict.as_deref())
+    {
+        Some(value) => match value.to_ascii_lowercase().trim() {
+            "skip" => Ok(InstanceConflict::Skip),
+            "suffix" => Ok(InstanceConflict::Suffix),
+            _ => Err(OptionError::config(format!(
+                "'{value}' is an invalid value for 'instance_conflict', must be one of 'skip' or 'suffix'."
+            ))),
+        },
+        None => Ok(InstanceConflict::default()),
+    }
+}
+
+fn get_metrics_listen(args: &BottomArgs, config: &Config) -> OptionResult<Option<SocketAddr>> {
+    if let Some(addr) = &args.general.metrics_listen {
+        Ok(Some(parse_arg_value!(
+            addr.trim().parse(),
+            "metrics_listen"
+        )?))
+    } else if let Some(addr) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.metrics_listen.as_ref())
+    {
+        Ok(Some(parse_config_value!(
+            addr.trim().parse(),
+            "metrics_listen"
+        )?))
+    } else {
+        Ok(None)
+    }
+}
+
+fn get_merge_tolerance(args: &BottomArgs, config: &Config) -> OptionResult<Option<f64>> {
+    let is_valid = |tolerance: &f64| tolerance.is_finite() && *tolerance >= 0.0;
+
+    if let Some(tolerance) = &args.general.merge_tolerance {
+        let tolerance = tolerance.trim().parse::<f64>().ok().filter(is_valid);
+        Ok(Some(parse_arg_value!(
+            tolerance.ok_or(()),
+            "merge_tolerance"
+        )?))
+    } else if let Some(tolerance) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.merge_tolerance.as_ref())
+    {
+        let tolerance = match tolerance {
+            StringOrNum::String(tolerance) => tolerance.trim().parse::<f64>().ok(),
+            StringOrNum::Num(tolerance) => Some(*tolerance as f64),
+        }
+        .filter(is_valid);
+        Ok(Some(parse_config_value!(
+            tolerance.ok_or(()),
+            "merge_tolerance"
+        )?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Returns the configured disk columns, with the usage column added if it is
+/// enabled, or [`None`] to use the defaults.
+fn get_disk_columns(config: &Config) -> Option<Vec<DiskColumn>> {
+    let disk = config.disk.as_ref()?;
+    let mut columns = disk.columns.clone();
+
+    if disk.show_usage_bar.unwrap_or(false) && !columns.contains(&DiskColumn::UsageBar) {
+        if columns.is_empty() {
+            columns = DEFAULT_DISK_COLUMNS.to_vec();
+        }
+        columns.push(DiskColumn::UsageBar);
+    }
+
+    if columns.is_empty() {
+        None
+    } else {
+        Some(columns)
+    }
+}
+
+fn get_temp_columns(config: &Config) -> Option<Vec<TempWidgetColumn>> {
+    config
+        .temperature
+        .as_ref()
+        .map(|temperature| temperature.columns.clone())
+        .filter(|columns| !columns.is_empty())
+}
+
+/// Returns the series to show in the memory graph, in order. Repeats are
+/// ignored, and nothing being configured means the defaults.
+fn get_memory_series(config: &Config) -> Vec<MemorySeries> {
+    match config
+        .memory
+        .as_ref()
+        .and_then(|memory| memory.series.as_ref())
+        .filter(|series| !series.is_empty())
+    {
+        Some(series) => series.iter().fold(vec![], |mut acc, series| {
+            if !acc.contains(series) {
+                acc.push(*series);
+            }
+            acc
+        }),
+        None => DEFAULT_MEMORY_SERIES.to_vec(),
+    }
+}
+
+fn get_cpu_show_load_avg(config: &Config) -> bool {
+    config
+        .cpu
+        .as_ref()
+        .and_then(|cpu| cpu.show_load_avg)
+        .unwrap_or(true)
+}
+
+fn get_cpu_per_socket(config: &Config) -> bool {
+    config
+        .cpu
+        .as_ref()
+        .and_then(|cpu| cpu.per_socket)
+        .unwrap_or(false)
+}
+
+fn get_cpu_breakdown(config: &Config) -> bool {
+    config
+        .cpu
+        .as_ref()
+        .and_then(|cpu| cpu.breakdown)
+        .unwrap_or(false)
+}
+
+fn get_cpu_legend_heatmap(config: &Config) -> bool {
+    config
+        .cpu
+        .as_ref()
+        .and_then(|cpu| cpu.legend_heatmap)
+        .unwrap_or(false)
+}
+
+fn get_network_show_interfaces(config: &Config) -> bool {
+    config
+        .network
+        .as_ref()
+        .and_then(|network| network.show_interfaces)
+        .unwrap_or(false)
+}
+
+fn get_network_lock_to_session_max(config: &Config) -> bool {
+    config
+        .network
+        .as_ref()
+        .and_then(|network| network.lock_to_session_max)
+        .unwrap_or(false)
+}
+
+fn get_network_show_session_max(config: &Config) -> bool {
+    config
+        .network
+        .as_ref()
+        .and_then(|network| network.show_session_max)
+        .unwrap_or(false)
+}
+
+fn get_network_show_session_totals(config: &Config) -> bool {
+    config
+        .network
+        .as_ref()
+        .and_then(|network| network.show_session_totals)
+        .unwrap_or(false)
+}
+
+fn get_network_y_max(config: &Config) -> OptionResult<Option<u64>> {
+    match config
+        .network
+        .as_ref()
+        .and_then(|network| network.y_max.as_ref())
+    {
+        Some(y_max) => Ok(Some(parse_config_value!(
+            parse_network_rate(y_max).ok_or(()),
+            "y_max"
+        )?)),
+        None => Ok(None),
+    }
+}
+
+/// Parses a network rate like "100Mb", "1.5 GiB" or "500KB/s" into bits per
+/// second. The unit is required, with "b" for bits and "B" for bytes.
+fn parse_network_rate(rate: &str) -> Option<u64> {
+    let rate = rate.trim();
+    let rate = rate.strip_suffix("/s").unwrap_or(rate);
+    let split = rate
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(rate.len());
+    let (value, unit) = rate.split_at(split);
+
+    let value = value
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value > 0.0)?;
+    let unit = unit.trim();
+    let (prefix, unit_bits) = if let Some(prefix) = unit.strip_suffix('b') {
+        (prefix, 1.0)
+    } else if let Some(prefix) = unit.strip_suffix('B') {
+        (prefix, 8.0)
+    } else {
+        return None;
+    };
+    let multiplier = match prefix {
+        "" => 1.0,
+        "K" | "k" => KILO_LIMIT_F64,
+        "M" => MEGA_LIMIT_F64,
+        "G" => GIGA_LIMIT_F64,
+        "T" => TERA_LIMIT_F64,
+        "Ki" => KIBI_LIMIT_F64,
+        "Mi" => MEBI_LIMIT_F64,
+        "Gi" => GIBI_LIMIT_F64,
+        "Ti" => TEBI_LIMIT_F64,
+        _ => return None,
+    };
+
+    Some((value * multiplier * unit_bits) as u64)
+}
+
+fn get_uid_numeric(config: &Config) -> Option<bool> {
+    config
+        .processes
+        .as_ref()
+        .and_then(|processes| processes.uid_numeric)
+}
+
+fn get_process_column_separator(config: &Config) -> ColumnSeparator {
+    config
+        .processes
+        .as_ref()
+        .and_then(|processes| processes.column_separator)
+        .unwrap_or_default()
+}
+
+fn get_process_tree_style(config: &Config) -> TreeStyle {
+    config
+        .processes
+        .as_ref()
+        .and_then(|processes| processes.tree_style)
+        .unwrap_or_default()
+}
+
+fn get_process_show_group_count(config: &Config) -> bool {
+    config
+        .processes
+        .as_ref()
+        .and_then(|processes| processes.show_group_count)
+        .unwrap_or(false)
+}
+
+fn get_process_group_threshold(config: &Config) -> usize {
+    config
+        .processes
+        .as_ref()
+        .and_then(|processes| processes.group_threshold)
+        .unwrap_or(0)
+}
+
+fn get_process_spike_threshold(config: &Config) -> f32 {
+    config
+        .processes
+        .as_ref()
+        .and_then(|processes| processes.spike_threshold)
+        .map(|threshold| threshold as f32)
+        .unwrap_or(DEFAULT_SPIKE_THRESHOLD)
+}
+
+fn get_process_case_sensitive_sort(config: &Config) -> bool {
+    config
+        .processes
+        .as_ref()
+        .and_then(|processes| processes.case_sensitive_sort)
+        .unwrap_or(false)
+}
+
+fn get_disk_aliases(config: &Config) -> HashMap<String, String> {
+    config
+        .disk
+        .as_ref()
+        .and_then(|disk| disk.aliases.as_ref())
+        .map(|aliases| {
+            aliases
+                .iter()
+                .map(|(key, alias)| (key.clone(), alias.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn get_disk_case_sensitive_sort(config: &Config) -> bool {
+    config
+        .disk
+        .as_ref()
+        .and_then(|disk| disk.case_sensitive_sort)
+        .unwrap_or(false)
+}
+
+fn get_temp_case_sensitive_sort(config: &Config) -> bool {
+    config
+        .temperature
+        .as_ref()
+        .and_then(|temperature| temperature.case_sensitive_sort)
+        .unwrap_or(false)
+}
+
 #[inline]
 fn get_default_time_value(
     args: &BottomArgs, config: &Config, retention_ms: u64,
@@ -743,7 +1236,7 @@ fn get_default_time_value(
             .and_then(|flags| flags.default_time_value.as_ref()),
         DEFAULT_TIME_MILLISECONDS,
         "default_time_value",
-        Some(30000),
+        Some(STALE_MIN_MILLISECONDS),
         Some(retention_ms),
     )
 }
@@ -913,6 +1406,124 @@ fn get_ignore_list(ignore_list: &Option<IgnoreList>) -> OptionResult<Option<Filt
     }
 }
 
+fn get_custom_proc_columns(config: &Config) -> OptionResult<Vec<CustomColumn>> {
+    let Some(processes) = &config.processes else {
+        return Ok(vec![]);
+    };
+
+    processes
+        .custom_columns
+        .iter()
+        .map(|column| {
+            CustomColumn::new(&column.name, &column.expr, column.unit).map_err(|err| {
+                OptionError::config(format!(
+                    "custom process column '{}' has an invalid expression: {err}",
+                    column.name
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Returns the signal sent by default when killing a process, and the signals
+/// to list in the advanced kill dialog.
+fn get_kill_signals(args: &BottomArgs, config: &Config) -> OptionResult<(usize, Vec<usize>)> {
+    const DEFAULT_SIGNAL: usize = 15;
+
+    let arg_default = args.process.default_kill_signal.as_deref();
+    let config_default = config
+        .processes
+        .as_ref()
+        .and_then(|processes| processes.kill_signal_default.as_ref());
+    let config_signals = config
+        .processes
+        .as_ref()
+        .and_then(|processes| processes.kill_signals.as_ref());
+
+    if !cfg!(target_family = "unix") {
+        if arg_default.is_some() {
+            return Err(OptionError::arg(
+                "'--default_kill_signal' is only supported on Unix-like systems.",
+            ));
+        } else if config_default.is_some() || config_signals.is_some() {
+            return Err(OptionError::config(
+                "'kill_signal_default' and 'kill_signals' are only supported on Unix-like systems.",
+            ));
+        }
+
+        // The only option is to kill the process outright.
+        return Ok((1, vec![1]));
+    }
+
+    fn parse(signal: &StringOrNum) -> Option<usize> {
+        match signal {
+            StringOrNum::String(name) => process_killer::parse_signal(name),
+            StringOrNum::Num(number) => process_killer::parse_signal(&number.to_string()),
+        }
+    }
+
+    let kill_signals = match config_signals {
+        Some(signals) => {
+            let mut kill_signals = Vec::with_capacity(signals.len());
+            for signal in signals {
+                let parsed = parse(signal).ok_or_else(|| {
+                    OptionError::config(format!(
+                        "'{signal}' in 'kill_signals' is not a valid signal for this system."
+                    ))
+                })?;
+                if !kill_signals.contains(&parsed) {
+                    kill_signals.push(parsed);
+                }
+            }
+
+            if kill_signals.is_empty() {
+                return Err(OptionError::config("'kill_signals' must not be empty."));
+            }
+
+            kill_signals
+        }
+        None => process_killer::SIGNALS
+            .iter()
+            .map(|(number, _)| *number)
+            .collect(),
+    };
+
+    let default_kill_signal = if let Some(signal) = arg_default {
+        let parsed = process_killer::parse_signal(signal).ok_or_else(|| {
+            OptionError::arg(format!(
+                "'--default_kill_signal' was set to '{signal}', which is not a valid signal for this system."
+            ))
+        })?;
+        if !kill_signals.contains(&parsed) {
+            return Err(OptionError::arg(format!(
+                "'--default_kill_signal' was set to '{signal}', which is not in 'kill_signals'."
+            )));
+        }
+
+        parsed
+    } else if let Some(signal) = config_default {
+        let parsed = parse(signal).ok_or_else(|| {
+            OptionError::config(format!(
+                "'kill_signal_default' was set to '{signal}', which is not a valid signal for this system."
+            ))
+        })?;
+        if !kill_signals.contains(&parsed) {
+            return Err(OptionError::config(format!(
+                "'kill_signal_default' was set to '{signal}', which is not in 'kill_signals'."
+            )));
+        }
+
+        parsed
+    } else if kill_signals.contains(&DEFAULT_SIGNAL) {
+        DEFAULT_SIGNAL
+    } else {
+        // There may be no known signals to list on some platforms.
+        kill_signals.first().copied().unwrap_or(DEFAULT_SIGNAL)
+    };
+
+    Ok((default_kill_signal, kill_signals))
+}
+
 fn get_network_unit_type(args: &BottomArgs, config: &Config) -> DataUnit {
     if args.network.network_use_bytes {
         return DataUnit::Byte;
@@ -1005,18 +1616,51 @@ fn get_memory_legend_position(
     Ok(result)
 }
 
+#[cfg(feature = "gpu")]
+fn get_gpu_legend_position(
+    args: &BottomArgs, config: &Config,
+) -> OptionResult<Option<LegendPosition>> {
+    let result = if let Some(s) = &args.gpu.gpu_legend {
+        match s.to_ascii_lowercase().trim() {
+            "none" => None,
+            position => Some(parse_arg_value!(position.parse(), "gpu_legend")?),
+        }
+    } else if let Some(flags) = &config.flags {
+        if let Some(s) = &flags.gpu_legend {
+            match s.to_ascii_lowercase().trim() {
+                "none" => None,
+                position => Some(parse_config_value!(position.parse(), "gpu_legend")?),
+            }
+        } else {
+            Some(LegendPosition::default())
+        }
+    } else {
+        Some(LegendPosition::default())
+    };
+
+    Ok(result)
+}
+
+#[cfg(not(feature = "gpu"))]
+fn get_gpu_legend_position(
+    _args: &BottomArgs, _config: &Config,
+) -> OptionResult<Option<LegendPosition>> {
+    Ok(None)
+}
+
 #[cfg(test)]
 mod test {
     use clap::Parser;
 
     use super::{get_time_interval, Config};
     use crate::{
-        app::App,
+        app::{layout_manager::BottomWidgetType, App},
         args::BottomArgs,
         options::{
             config::flags::FlagConfig, get_default_time_value, get_retention, get_update_rate,
-            try_parse_ms,
+            parse_network_rate, try_parse_ms,
         },
+        widgets::ProcWidgetColumn,
     };
 
     #[test]
@@ -1193,8 +1837,21 @@ mod test {
         let default_app = create_app(BottomArgs::parse_from(["btm"]));
 
         // Skip battery since it's tricky to test depending on the platform/features
-        // we're testing with.
-        let skip = ["help", "version", "celsius", "battery", "generate_schema"];
+        // we're testing with. Skip the listing flags since they exit before the app
+        // is created, headless mode since it doesn't change the app's settings, and
+        // no_color since it only changes the styles.
+        let skip = [
+            "help",
+            "version",
+            "celsius",
+            "battery",
+            "generate_schema",
+            "headless",
+            "list_disks",
+            "list_interfaces",
+            "list_sensors",
+            "no_color",
+        ];
 
         for arg in app.get_arguments().collect::<Vec<_>>() {
             let arg_name = arg
@@ -1227,6 +1884,528 @@ mod test {
         }
     }
 
+    #[test]
+    fn network_interface_args() {
+        let args = BottomArgs::parse_from([
+            "btm",
+            "--network_interface",
+            "eth0",
+            "--network_interface",
+            "wg0",
+        ]);
+        let app = create_app(args);
+
+        let filter = app.filters.net_filter.as_ref().unwrap();
+        assert!(filter.should_keep("eth0"));
+        assert!(filter.should_keep("wg0"));
+        assert!(!filter.should_keep("eth01"));
+        assert!(!filter.should_keep("ETH0"));
+        assert!(!filter.should_keep("lo"));
+
+        let app = create_app(BottomArgs::parse_from(["btm"]));
+        assert!(app.filters.net_filter.is_none());
+    }
+
+    #[test]
+    fn exe_locations_are_only_read_when_shown() {
+        let app = create_app(BottomArgs::parse_from(["btm"]));
+        assert!(app.used_widgets.use_proc);
+        assert!(!app.used_widgets.use_proc_exe);
+
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [processes]
+            columns = ["PID", "Name", "Location"]
+            "#,
+        )
+        .unwrap();
+        let (app, _, _) = super::init_app(BottomArgs::parse_from(["btm"]), config).unwrap();
+        assert!(app.used_widgets.use_proc_exe);
+    }
+
+    fn has_widget(app: &App, widget_type: BottomWidgetType) -> bool {
+        app.widget_map
+            .values()
+            .any(|widget| widget.widget_type == widget_type)
+    }
+
+    #[test]
+    fn single_interface_widgets_collect_interfaces() {
+        use crate::data_collection::DataCollector;
+
+        /// Harvests twice, as the first network reading is only a baseline,
+        /// and returns the interfaces the app then has data for.
+        fn collected_interfaces(config: Config) -> Vec<String> {
+            let (mut app, _, _) = super::init_app(BottomArgs::parse_from(["btm"]), config).unwrap();
+
+            let mut collector = DataCollector::new(app.filters.clone());
+            collector.set_data_collection(app.used_widgets);
+            collector.set_show_network_interfaces(app.collects_network_interfaces());
+            collector.init();
+            collector.update_data();
+            collector.update_data();
+
+            app.data_collection
+                .eat_data(Box::new(collector.data.clone()));
+            app.data_collection.network_interface_names.clone()
+        }
+
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [[row]]
+                [[row.child]]
+                type = "net"
+                interface = "lo"
+            "#,
+        )
+        .unwrap();
+
+        assert!(!collected_interfaces(config).is_empty());
+        assert!(collected_interfaces(Config::default()).is_empty());
+    }
+
+    #[test]
+    fn layouts_switch_on_resize() {
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [[row]]
+                [[row.child]]
+                type = "proc"
+
+            [[layouts]]
+            min_width = 120
+                [[layouts.row]]
+                    [[layouts.row.child]]
+                    type = "proc"
+                    [[layouts.row.child]]
+                    type = "cpu"
+            "#,
+        )
+        .unwrap();
+        let (mut app, _, _) = super::init_app(BottomArgs::parse_from(["btm"]), config).unwrap();
+        let proc_id = app.current_widget.widget_id;
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::Proc);
+        assert!(!has_widget(&app, BottomWidgetType::Cpu));
+
+        app.states
+            .proc_state
+            .get_mut_widget_state(proc_id)
+            .unwrap()
+            .table
+            .state
+            .current_index = 5;
+
+        // Too small for the other layout.
+        assert!(app.fit_layout(80, 40).is_none());
+
+        assert!(app.fit_layout(120, 40).is_some());
+        assert!(has_widget(&app, BottomWidgetType::Cpu));
+        assert_eq!(app.current_widget.widget_id, proc_id);
+        assert!(app.fit_layout(150, 50).is_none());
+
+        assert!(app.fit_layout(119, 40).is_some());
+        assert!(!has_widget(&app, BottomWidgetType::Cpu));
+        assert_eq!(app.current_widget.widget_id, proc_id);
+
+        // The widget's state is kept through the switches.
+        let proc_state = app.states.proc_state.get_widget_state(proc_id).unwrap();
+        assert_eq!(proc_state.table.state.current_index, 5);
+    }
+
+    #[test]
+    fn toggling_basic_mode() {
+        let (mut app, _, _) =
+            super::init_app(BottomArgs::parse_from(["btm"]), Config::default()).unwrap();
+        let proc_id = app.current_widget.widget_id;
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::Proc);
+
+        let proc_state = app.states.proc_state.get_mut_widget_state(proc_id).unwrap();
+        proc_state.select_column(ProcWidgetColumn::Mem);
+        proc_state.search_user("alice");
+        let sort_index = proc_state.table.sort_index();
+
+        let state_count = app.states.widget_state_count();
+        let normal_widgets = app.widget_map.len();
+
+        for _ in 0..10 {
+            app.toggle_basic_mode();
+            assert!(app.app_config_fields.use_basic_mode);
+            assert!(has_widget(&app, BottomWidgetType::BasicCpu));
+            assert!(!has_widget(&app, BottomWidgetType::Cpu));
+            assert!(app.states.basic_table_widget_state.is_some());
+            assert!(app.fit_layout(100, 40).is_some());
+            assert!(app.fit_layout(100, 40).is_none());
+
+            // The process widget is still selected, and still sorted and
+            // searched the same way.
+            let basic_proc_id = app.current_widget.widget_id;
+            assert_eq!(app.current_widget.widget_type, BottomWidgetType::Proc);
+            assert_ne!(basic_proc_id, proc_id);
+            let proc_state = app
+                .states
+                .proc_state
+                .get_widget_state(basic_proc_id)
+                .unwrap();
+            assert!(proc_state.table.props.is_basic);
+            assert_eq!(proc_state.table.sort_index(), sort_index);
+            assert_eq!(proc_state.current_search_query(), "user:\"alice\"");
+
+            app.toggle_basic_mode();
+            assert!(!app.app_config_fields.use_basic_mode);
+            assert!(has_widget(&app, BottomWidgetType::Cpu));
+            assert!(app.states.basic_table_widget_state.is_none());
+            assert_eq!(app.current_widget.widget_id, proc_id);
+            assert_eq!(app.widget_map.len(), normal_widgets);
+            assert!(app.fit_layout(100, 40).is_some());
+
+            let proc_state = app.states.proc_state.get_widget_state(proc_id).unwrap();
+            assert!(!proc_state.table.props.is_basic);
+            assert_eq!(proc_state.table.sort_index(), sort_index);
+            assert_eq!(proc_state.current_search_query(), "user:\"alice\"");
+
+            // No states are made or lost along the way.
+            assert_eq!(app.states.widget_state_count(), state_count);
+        }
+    }
+
+    #[test]
+    fn toggling_basic_mode_switches_collected_widgets() {
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [[row]]
+                [[row.child]]
+                type = "cpu"
+            "#,
+        )
+        .unwrap();
+        let (mut app, _, _) = super::init_app(BottomArgs::parse_from(["btm"]), config).unwrap();
+        assert!(app.used_widgets.use_cpu);
+        assert!(!app.used_widgets.use_proc);
+
+        // The basic layout's widgets are only collected once switched to.
+        app.toggle_basic_mode();
+        assert!(app.used_widgets.use_proc);
+        assert!(app.used_widgets.use_mem);
+
+        app.toggle_basic_mode();
+        assert!(app.used_widgets.use_cpu);
+        assert!(!app.used_widgets.use_proc);
+    }
+
+    #[test]
+    fn applying_a_shorter_retention_limits_times() {
+        let (mut app, _, _) =
+            super::init_app(BottomArgs::parse_from(["btm"]), Config::default()).unwrap();
+        let cpu_id = app
+            .widget_map
+            .values()
+            .find(|widget| widget.widget_type == BottomWidgetType::Cpu)
+            .unwrap()
+            .widget_id;
+        app.app_config_fields.time_interval = 60_000;
+        app.states
+            .cpu_state
+            .get_mut_widget_state(cpu_id)
+            .unwrap()
+            .current_display_time = 120_000;
+
+        app.settings_overlay.open(1000, 45_000);
+        assert_eq!(app.apply_settings(), None);
+
+        assert_eq!(app.app_config_fields.retention_ms, 45_000);
+        assert_eq!(app.app_config_fields.default_time_value, 45_000);
+        assert_eq!(app.app_config_fields.time_interval, 45_000);
+        let cpu_state = app.states.cpu_state.get_widget_state(cpu_id).unwrap();
+        assert_eq!(cpu_state.current_display_time, 45_000);
+    }
+
+    #[test]
+    fn markers_dropped_while_frozen_are_drawn() {
+        let (mut app, _, _) =
+            super::init_app(BottomArgs::parse_from(["btm"]), Config::default()).unwrap();
+        app.frozen_state.toggle(&app.data_collection);
+        app.drop_time_marker();
+
+        let markers = &app.converted_data.time_markers;
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers[0].value, 0.0);
+        assert_eq!(markers[0].tag, "1");
+    }
+
+    #[test]
+    fn serving_metrics_collects_everything_exported() {
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [[row]]
+                [[row.child]]
+                type = "cpu"
+            "#,
+        )
+        .unwrap();
+        let (app, _, _) = super::init_app(
+            BottomArgs::parse_from(["btm", "--metrics_listen", "127.0.0.1:0"]),
+            config,
+        )
+        .unwrap();
+
+        for used_widgets in [app.used_widgets, app.alternate_used_widgets] {
+            assert!(used_widgets.use_proc);
+            assert!(used_widgets.use_disk);
+            assert!(used_widgets.use_temp);
+            assert!(used_widgets.use_net);
+        }
+        assert!(app.collects_network_interfaces());
+        assert!(!app.uses_network_interfaces());
+    }
+
+    #[test]
+    fn toggling_basic_mode_selects_equivalent_widgets() {
+        let (mut app, _, _) = super::init_app(
+            BottomArgs::parse_from(["btm", "--basic"]),
+            Config::default(),
+        )
+        .unwrap();
+        app.current_widget = app
+            .widget_map
+            .values()
+            .find(|widget| widget.widget_type == BottomWidgetType::BasicMem)
+            .unwrap()
+            .clone();
+
+        app.toggle_basic_mode();
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::Mem);
+        assert!(!app.app_config_fields.use_basic_mode);
+
+        app.is_expanded = true;
+        app.toggle_basic_mode();
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::BasicMem);
+        assert!(!app.is_expanded);
+
+        // Only the table widgets can be shown in basic mode's table.
+        let basic_table = app.states.basic_table_widget_state.as_ref().unwrap();
+        assert_eq!(
+            basic_table.currently_displayed_widget_type,
+            BottomWidgetType::Proc
+        );
+    }
+
+    #[test]
+    fn layouts_need_a_fallback() {
+        let config = |text: &str| toml_edit::de::from_str::<Config>(text).unwrap();
+        let init = |config| super::init_app(BottomArgs::parse_from(["btm"]), config);
+
+        let only_conditional = config(
+            r#"
+            [[layouts]]
+            min_height = 30
+                [[layouts.row]]
+                    [[layouts.row.child]]
+                    type = "cpu"
+            "#,
+        );
+        assert!(init(only_conditional).is_err());
+
+        let with_fallback = config(
+            r#"
+            [[layouts]]
+            min_height = 30
+                [[layouts.row]]
+                    [[layouts.row.child]]
+                    type = "cpu"
+            [[layouts]]
+                [[layouts.row]]
+                    [[layouts.row.child]]
+                    type = "mem"
+            "#,
+        );
+        let (app, _, _) = init(with_fallback).unwrap();
+        assert!(has_widget(&app, BottomWidgetType::Mem));
+        assert!(!has_widget(&app, BottomWidgetType::Cpu));
+    }
+
+    #[test]
+    fn env_precedence() {
+        use crate::options::{
+            config::cpu::{CpuConfig, CpuDefault},
+            get_default_cpu_selection,
+        };
+
+        // Environment variables are process-wide, so everything that sets them is
+        // kept to this test. Other tests don't read them, as they parse arguments
+        // without them.
+        const ENV_VARS: [&str; 3] = ["BTM_BASIC", "BTM_RATE", "BTM_DEFAULT_CPU_ENTRY"];
+        let parse = |args: &[&str]| crate::args::get_args_from(args).unwrap();
+
+        let config = Config {
+            flags: Some(FlagConfig {
+                basic: Some(false),
+                rate: Some("5s".to_string().into()),
+                ..Default::default()
+            }),
+            cpu: Some(CpuConfig {
+                default: CpuDefault::All,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let use_basic_mode = |args: BottomArgs| {
+            super::init_app(args, config.clone())
+                .unwrap()
+                .0
+                .app_config_fields
+                .use_basic_mode
+        };
+
+        for var in ENV_VARS {
+            std::env::remove_var(var);
+        }
+
+        // Without environment variables, the config is used.
+        let args = parse(&["btm"]);
+        assert_eq!(get_update_rate(&args, &config), Ok(5000));
+        assert_eq!(get_default_cpu_selection(&args, &config), CpuDefault::All);
+        assert!(!use_basic_mode(args));
+
+        // Environment variables take precedence over the config...
+        std::env::set_var("BTM_BASIC", "true");
+        std::env::set_var("BTM_RATE", "2s");
+        std::env::set_var("BTM_DEFAULT_CPU_ENTRY", "average");
+
+        let args = parse(&["btm"]);
+        assert_eq!(get_update_rate(&args, &config), Ok(2000));
+        assert_eq!(
+            get_default_cpu_selection(&args, &config),
+            CpuDefault::Average
+        );
+        assert!(use_basic_mode(args));
+
+        // ...and the command line takes precedence over environment variables.
+        let args = parse(&["btm", "--rate", "3s", "--default_cpu_entry", "all"]);
+        assert_eq!(get_update_rate(&args, &config), Ok(3000));
+        assert_eq!(get_default_cpu_selection(&args, &config), CpuDefault::All);
+
+        // Falsy values leave boolean flags unset, like leaving out the flag.
+        std::env::set_var("BTM_BASIC", "false");
+        assert!(!use_basic_mode(parse(&["btm"])));
+
+        // Invalid values are rejected like they are on the command line.
+        std::env::set_var("BTM_DEFAULT_CPU_ENTRY", "some");
+        assert!(crate::args::get_args_from(["btm"]).is_err());
+
+        for var in ENV_VARS {
+            std::env::remove_var(var);
+        }
+    }
+
+    #[test]
+    fn disk_usage_bar_column() {
+        use crate::{
+            options::{config::disk::DiskConfig, get_disk_columns},
+            widgets::{DiskColumn, DEFAULT_DISK_COLUMNS},
+        };
+
+        let mut config = Config::default();
+        assert_eq!(get_disk_columns(&config), None);
+
+        config.disk = Some(DiskConfig {
+            show_usage_bar: Some(true),
+            ..Default::default()
+        });
+        let columns = get_disk_columns(&config).unwrap();
+        assert_eq!(columns[..DEFAULT_DISK_COLUMNS.len()], DEFAULT_DISK_COLUMNS);
+        assert_eq!(columns.last(), Some(&DiskColumn::UsageBar));
+
+        // It isn't added twice.
+        config.disk = Some(DiskConfig {
+            columns: vec![DiskColumn::UsageBar, DiskColumn::Disk],
+            show_usage_bar: Some(true),
+            ..Default::default()
+        });
+        assert_eq!(
+            get_disk_columns(&config),
+            Some(vec![DiskColumn::UsageBar, DiskColumn::Disk])
+        );
+    }
+
+    #[test]
+    fn memory_series() {
+        use crate::{
+            options::{config::memory::MemoryConfig, get_memory_series},
+            widgets::{MemorySeries, DEFAULT_MEMORY_SERIES},
+        };
+
+        let mut config = Config::default();
+        assert_eq!(get_memory_series(&config), DEFAULT_MEMORY_SERIES);
+
+        config.memory = Some(MemoryConfig {
+            series: Some(vec![]),
+        });
+        assert_eq!(get_memory_series(&config), DEFAULT_MEMORY_SERIES);
+
+        // Repeats are dropped.
+        config.memory = Some(MemoryConfig {
+            series: Some(vec![
+                MemorySeries::Arc,
+                MemorySeries::Ram,
+                MemorySeries::Arc,
+            ]),
+        });
+        assert_eq!(
+            get_memory_series(&config),
+            vec![MemorySeries::Arc, MemorySeries::Ram]
+        );
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn kill_signals() {
+        use crate::options::{config::process::ProcessesConfig, get_kill_signals, OptionError};
+
+        let args = BottomArgs::parse_from(["btm"]);
+        let mut config = Config::default();
+        let (default_signal, signals) = get_kill_signals(&args, &config).unwrap();
+        assert_eq!(default_signal, 15);
+        assert!(signals.contains(&9));
+
+        config.processes = Some(ProcessesConfig {
+            kill_signal_default: Some("KILL".to_string().into()),
+            kill_signals: Some(vec![
+                "TERM".to_string().into(),
+                9.into(),
+                "sighup".to_string().into(),
+            ]),
+            ..Default::default()
+        });
+        assert_eq!(get_kill_signals(&args, &config), Ok((9, vec![15, 9, 1])));
+
+        // The argument takes precedence.
+        let args = BottomArgs::parse_from(["btm", "--default_kill_signal", "1"]);
+        assert_eq!(get_kill_signals(&args, &config), Ok((1, vec![15, 9, 1])));
+
+        // The default must be listed.
+        let args = BottomArgs::parse_from(["btm", "--default_kill_signal", "INT"]);
+        assert!(get_kill_signals(&args, &config).is_err());
+
+        let args = BottomArgs::parse_from(["btm"]);
+        config.processes = Some(ProcessesConfig {
+            kill_signals: Some(vec!["TERM".to_string().into(), "NOPE".to_string().into()]),
+            ..Default::default()
+        });
+        assert_eq!(
+            get_kill_signals(&args, &config),
+            Err(OptionError::config(
+                "'NOPE' in 'kill_signals' is not a valid signal for this system."
+            ))
+        );
+
+        // Without TERM, the first listed signal is the default.
+        config.processes = Some(ProcessesConfig {
+            kill_signals: Some(vec!["KILL".to_string().into()]),
+            ..Default::default()
+        });
+        assert_eq!(get_kill_signals(&args, &config), Ok((9, vec![9])));
+    }
+
     /// This one has slightly more complex behaviour due to `dirs` not respecting XDG on macOS, so we manually
     /// handle it. However, to ensure backwards-compatibility, we also have to do some special cases.
     #[cfg(target_os = "macos")]
@@ -1269,4 +2448,17 @@ mod test {
         // let case_3 = case_1;
         // assert_eq!(get_config_path(None), Some(case_1));
     }
+
+    #[test]
+    fn network_rates() {
+        assert_eq!(parse_network_rate("100Mb"), Some(100_000_000));
+        assert_eq!(parse_network_rate("1 GiB"), Some(8 * 1024 * 1024 * 1024));
+        assert_eq!(parse_network_rate("1.5KB/s"), Some(12_000));
+        assert_eq!(parse_network_rate(" 500b "), Some(500));
+
+        assert_eq!(parse_network_rate("100"), None);
+        assert_eq!(parse_network_rate("100Xb"), None);
+        assert_eq!(parse_network_rate("0Mb"), None);
+        assert_eq!(parse_network_rate("Mb"), None);
+    }
 }
@@ -121,6 +121,16 @@ pub struct GeneralArgs {
     )]
     pub config_location: Option<PathBuf>,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        alias = "debug-ui",
+        help = "Shows the slowest widget's draw time over it.",
+        long_help = "Shows how long the slowest widget takes to draw, averaged over its last few draws, on \
+                    that widget's title. Press 'T' to list the draw times of every widget."
+    )]
+    pub debug_ui: bool,
+
     #[arg(
         short = 't',
         long,
@@ -219,12 +229,81 @@ pub struct GeneralArgs {
     )]
     pub expanded: bool,
 
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::AnyPath,
+        help = "Where to save text snapshots of graphs taken with 'E'.",
+        long_help = "Appends the text snapshots of graphs taken by pressing 'E' on a CPU, memory, or network \
+                    graph to the given file, creating it if needed. Defaults to 'bottom/graphs.txt' under the \
+                    system's local data directory. If the path is '-', snapshots are written to stdout once \
+                    bottom exits."
+    )]
+    pub graph_export: Option<PathBuf>,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Runs without the TUI, writing collected data as JSON lines.",
+        long_help = "Runs without the TUI or any terminal setup, writing one line of JSON per refresh with \
+                    the collected data. Lines are written to stdout, or appended to the file given with \
+                    '--log_data'. Stop with Ctrl-c; on Unix, sending SIGHUP reopens the file so it can be \
+                    rotated. Data is not kept around, so '--retention' has no effect."
+    )]
+    pub headless: bool,
+
     #[arg(long, action = ArgAction::SetTrue, help = "Hides spacing between table headers and entries.")]
     pub hide_table_gap: bool,
 
     #[arg(long, action = ArgAction::SetTrue, help = "Hides the time scale from being shown.")]
     pub hide_time: bool,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        alias = "list-disks",
+        help = "Prints the names and mount points of all disks and exits.",
+        long_help = "Prints the names and mount points of all detected disks, as they would appear in the \
+                disk widget, and exits. This is useful for writing a `disk.name_filter` or \
+                `disk.mount_filter` in the config file."
+    )]
+    pub list_disks: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        alias = "log-data",
+        requires = "headless",
+        help = "Appends the JSON lines written by '--headless' to a file.",
+        long_help = "Appends the JSON lines written by '--headless' to the given file instead of stdout, \
+                    creating it if needed."
+    )]
+    pub log_data: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "TOLERANCE",
+        alias = "merge-tolerance",
+        help = "Merges consecutive data points within a percentage.",
+        long_help = "Merges consecutive data points that are within the given percentage of each other to save \
+                    memory, storing only the start and end of each run of similar points. The percentage is \
+                    relative to the larger of the two values, so it works the same for every graph, e.g. \
+                    \"1\" merges both 50% and 50.4% CPU usage, and 100 MB/s and 100.9 MB/s of network usage. \
+                    Merged runs are drawn as flat lines. Disabled by default."
+    )]
+    pub merge_tolerance: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        alias = "metrics-listen",
+        help = "Serves metrics in the Prometheus format on the given address.",
+        long_help = "Serves the latest collected data in the Prometheus text format at '/metrics' on the \
+                    given address (e.g. 127.0.0.1:9185), in addition to running the TUI. This includes \
+                    CPU, memory, disk, network, temperature, and top process usage."
+    )]
+    pub metrics_listen: Option<String>,
+
     #[arg(
         short = 'r',
         long,
@@ -246,6 +325,18 @@ pub struct GeneralArgs {
     )]
     pub retention: Option<String>,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Saves and restores some UI state between runs.",
+        long_help = "Saves some UI state on exit, such as how process widgets are sorted, how far graphs are \
+                    zoomed, and which widget is selected, and restores it on the next start. Options passed \
+                    on the command line take precedence over restored state. The state is stored in \
+                    'bottom/state.toml' under the system's state directory, or its local data directory \
+                    if there is none."
+    )]
+    pub save_state: bool,
+
     #[arg(
         long,
         action = ArgAction::SetTrue,
@@ -253,6 +344,26 @@ pub struct GeneralArgs {
     )]
     pub show_table_scroll_position: bool,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        alias = "thick-lines",
+        help = "Draws graph lines thicker.",
+        long_help = "Draws graph lines thicker, by drawing an extra point next to each point. This can help \
+        if thin lines are hard to see, e.g. on high-DPI displays."
+    )]
+    pub thick_lines: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        alias = "newest-on-left",
+        help = "Draws the newest data on the left of graphs.",
+        long_help = "Draws the newest data on the left of graphs rather than the right, so time runs \
+        from left to right into the past."
+    )]
+    pub newest_on_left: bool,
+
     #[arg(
         short = 'd',
         long,
@@ -287,13 +398,22 @@ pub struct ProcessArgs {
     )]
     pub current_usage: bool,
 
+    #[arg(
+        long,
+        value_name = "SIGNAL",
+        help = "The signal sent by default when killing a process.",
+        long_help = "The signal sent by default when killing a process, either by name (e.g. KILL) or by \
+                    number (e.g. 9). Defaults to TERM. Only supported on Unix-like systems."
+    )]
+    pub default_kill_signal: Option<String>,
+
     // TODO: Disable this on Windows?
     #[arg(
         long,
         action = ArgAction::SetTrue,
         help = "Hides additional stopping options Unix-like systems.",
-        long_help = "Hides additional stopping options Unix-like systems. Signal 15 (TERM) will be sent when \
-                    stopping a process."
+        long_help = "Hides additional stopping options Unix-like systems. The default kill signal (TERM unless \
+                    changed) will be sent when stopping a process."
     )]
     pub disable_advanced_kill: bool,
 
@@ -380,6 +500,17 @@ pub struct TemperatureArgs {
         help = "Use Kelvin as the temperature unit."
     )]
     pub kelvin: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        alias = "list-sensors",
+        help = "Prints the names of all temperature sensors and exits.",
+        long_help = "Prints the names of all detected temperature sensors, as they would appear in the \
+                temperature widget, and exits. This is useful for writing a `temperature.sensor_filter` \
+                in the config file."
+    )]
+    pub list_sensors: bool,
 }
 
 /// The default selection of the CPU widget. If the given selection is invalid,
@@ -455,6 +586,27 @@ pub struct MemoryArgs {
 #[derive(Args, Clone, Debug, Default)]
 #[command(next_help_heading = "Network Options", rename_all = "snake_case")]
 pub struct NetworkArgs {
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        alias = "list-interfaces",
+        help = "Prints the names of all network interfaces and exits.",
+        long_help = "Prints the names of all detected network interfaces and exits. This is useful for \
+                writing a `network.interface_filter` in the config file."
+    )]
+    pub list_interfaces: bool,
+
+    #[arg(
+        long,
+        value_name = "INTERFACE",
+        action = ArgAction::Append,
+        help = "Only shows data from the given network interface.",
+        long_help = "Only shows data from the given network interface. Can be set multiple times to show \
+                    multiple interfaces. The interface name must match exactly. This overrides the \
+                    'network.interface_filter' setting in the config file."
+    )]
+    pub network_interface: Vec<String>,
+
     #[arg(
         long,
         value_parser = CHART_WIDGET_POSITIONS,
@@ -520,8 +672,17 @@ pub struct BatteryArgs {
 #[derive(Args, Clone, Debug, Default)]
 #[command(next_help_heading = "GPU Options", rename_all = "snake_case")]
 pub struct GpuArgs {
-    #[arg(long, action = ArgAction::SetTrue, help = "Disable collecting and displaying NVIDIA and AMD GPU information.")]
+    #[arg(long, action = ArgAction::SetTrue, help = "Disable collecting and displaying NVIDIA, AMD, and Intel GPU information.")]
     pub disable_gpu: bool,
+
+    #[arg(
+        long,
+        value_parser = CHART_WIDGET_POSITIONS,
+        value_name = "POSITION",
+        ignore_case = true,
+        help = "Where to place the legend for the GPU chart widget.",
+    )]
+    pub gpu_legend: Option<String>,
 }
 
 /// Style arguments/config options.
@@ -554,6 +715,37 @@ pub struct StyleArgs {
         }
     )]
     pub theme: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "SCHEME",
+        value_parser = [
+            "default",
+            "default-light",
+            "gruvbox",
+            "gruvbox-light",
+            "nord",
+            "nord-light",
+        ],
+        hide_possible_values = true,
+        alias = "theme-preview",
+        help = "Prints a preview of a built-in color theme and exits.",
+        long_help = "Prints a small preview of a built-in color theme's colors, such as those of the CPU \
+                graph, borders, selected rows, and battery levels, and exits. This takes the same themes as \
+                '--theme'."
+    )]
+    pub theme_preview: Option<String>,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        alias = "no-color",
+        help = "Draws everything without colors.",
+        long_help = "Draws everything in the terminal's default foreground color, using bold, underlined, \
+                and reversed text for emphasis instead of colors. Colors set with '--theme' or in the \
+                config file are ignored."
+    )]
+    pub no_color: bool,
 }
 
 /// Other arguments. This just handle options that are for help/version
@@ -568,15 +760,47 @@ pub struct OtherArgs {
     version: (),
 }
 
+/// The prefix of the environment variables that can be used in place of
+/// arguments.
+pub const ENV_PREFIX: &str = "BTM_";
+
+/// Returns the name of the environment variable for an argument with the given
+/// long flag, e.g. `BTM_RATE` for `--rate`.
+pub fn env_name(long: &str) -> String {
+    format!("{ENV_PREFIX}{}", long.to_uppercase().replace('-', "_"))
+}
+
 /// Returns a [`BottomArgs`].
 pub fn get_args() -> BottomArgs {
-    BottomArgs::parse()
+    get_args_from(std::env::args_os()).unwrap_or_else(|err| err.exit())
 }
 
-/// Returns an [`Command`] based off of [`BottomArgs`].
-#[cfg(test)]
-pub(crate) fn build_cmd() -> Command {
-    BottomArgs::command()
+/// Returns a [`BottomArgs`] from the given arguments, falling back to
+/// environment variables for any that aren't given.
+pub fn get_args_from<I, T>(args: I) -> Result<BottomArgs, Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let mut cmd = build_cmd();
+    let matches = cmd.try_get_matches_from_mut(args)?;
+    BottomArgs::from_arg_matches(&matches).map_err(|err| err.format(&mut cmd))
+}
+
+/// Returns an [`Command`] based off of [`BottomArgs`], where every argument can
+/// also be set with an environment variable named after its long flag. Arguments
+/// given on the command line take precedence over environment variables.
+pub fn build_cmd() -> Command {
+    BottomArgs::command().mut_args(|arg| match (arg.get_long(), arg.get_action()) {
+        (_, ArgAction::Help | ArgAction::HelpShort | ArgAction::HelpLong | ArgAction::Version) => {
+            arg
+        }
+        (Some(long), _) => {
+            let env = env_name(long);
+            arg.env(env)
+        }
+        (None, _) => arg,
+    })
 }
 
 #[cfg(test)]
@@ -3,6 +3,7 @@ pub mod disk;
 pub mod flags;
 mod ignore_list;
 pub mod layout;
+pub mod memory;
 pub mod network;
 pub mod process;
 pub mod style;
@@ -10,13 +11,18 @@ pub mod temperature;
 
 use disk::DiskConfig;
 use flags::FlagConfig;
+use memory::MemoryConfig;
 use network::NetworkConfig;
 use serde::{Deserialize, Serialize};
 use style::StyleConfig;
 use temperature::TempConfig;
 
 pub use self::ignore_list::IgnoreList;
-use self::{cpu::CpuConfig, layout::Row, process::ProcessesConfig};
+use self::{
+    cpu::CpuConfig,
+    layout::{ConditionalLayout, Row},
+    process::ProcessesConfig,
+};
 
 /// Overall config for `bottom`.
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -26,11 +32,13 @@ pub struct Config {
     pub(crate) flags: Option<FlagConfig>,
     pub(crate) styles: Option<StyleConfig>,
     pub(crate) row: Option<Vec<Row>>,
+    pub(crate) layouts: Option<Vec<ConditionalLayout>>,
     pub(crate) processes: Option<ProcessesConfig>,
     pub(crate) disk: Option<DiskConfig>,
     pub(crate) temperature: Option<TempConfig>,
     pub(crate) network: Option<NetworkConfig>,
     pub(crate) cpu: Option<CpuConfig>,
+    pub(crate) memory: Option<MemoryConfig>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -54,6 +62,15 @@ impl From<u64> for StringOrNum {
     }
 }
 
+impl std::fmt::Display for StringOrNum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StringOrNum::String(value) => f.write_str(value),
+            StringOrNum::Num(value) => write!(f, "{value}"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
 
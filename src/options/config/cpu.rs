@@ -20,6 +20,25 @@ pub(crate) enum CpuDefault {
 pub(crate) struct CpuConfig {
     #[serde(default)]
     pub(crate) default: CpuDefault,
+
+    /// Whether to colour the usage of each entry in the CPU legend by how high
+    /// it is, from green to red.
+    pub(crate) legend_heatmap: Option<bool>,
+
+    /// Whether to show the 1, 5, and 15 minute load averages in the CPU
+    /// widget's title. Defaults to true. Has no effect on Windows.
+    pub(crate) show_load_avg: Option<bool>,
+
+    /// Whether to show one entry per socket, with the average usage of its
+    /// cores, instead of one per core. Only has an effect on Linux machines
+    /// with more than one socket. Defaults to false.
+    pub(crate) per_socket: Option<bool>,
+
+    /// Whether the CPU widget starts by showing how CPU time is split between
+    /// user, system, iowait, and steal time across all cores, rather than the
+    /// usage of each core. Iowait and steal time are only shown on Linux.
+    /// Defaults to false.
+    pub(crate) breakdown: Option<bool>,
 }
 
 #[cfg(test)]
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 
 use super::IgnoreList;
@@ -14,14 +16,30 @@ pub(crate) struct DiskConfig {
     /// A filter over the mount names.
     pub(crate) mount_filter: Option<IgnoreList>,
 
+    /// A filter over the filesystem types.
+    pub(crate) fs_filter: Option<IgnoreList>,
+
     /// A list of disk widget columns.
     #[serde(default)]
     pub(crate) columns: Vec<DiskColumn>, // TODO: make this more composable(?) in the future, we might need to rethink how it's done for custom widgets
+
+    /// Whether to add a column with a bar showing how full each disk is, if
+    /// it isn't already in `columns`.
+    pub(crate) show_usage_bar: Option<bool>,
+
+    /// Whether to sort text columns like the mount by case. Defaults to false.
+    pub(crate) case_sensitive_sort: Option<bool>,
+
+    /// Names to show instead of mount points or disk names, e.g.
+    /// `"/srv/storage/media" = "media"`. Filters still match the original
+    /// values.
+    pub(crate) aliases: Option<BTreeMap<String, String>>,
 }
 
 #[cfg(test)]
 mod test {
     use super::DiskConfig;
+    use crate::options::DiskColumn;
 
     #[test]
     fn empty_column_setting() {
@@ -32,13 +50,40 @@ mod test {
 
     #[test]
     fn valid_disk_column_settings() {
-        let config = r#"columns = ["disk", "mount", "used", "free", "total", "used%", "free%", "r/s", "w/s"]"#;
+        let config = r#"columns = ["disk", "mount", "fs", "used", "free", "total", "used%", "free%", "r/s", "w/s"]"#;
         toml_edit::de::from_str::<DiskConfig>(config).expect("Should succeed!");
+
+        // Names are matched regardless of case, and the order is kept.
+        let config = r#"columns = ["Used%", "MOUNT", "Disk"]"#;
+        let generated = toml_edit::de::from_str::<DiskConfig>(config).expect("Should succeed!");
+        assert_eq!(
+            generated.columns,
+            [DiskColumn::UsedPercent, DiskColumn::Mount, DiskColumn::Disk]
+        );
     }
 
     #[test]
     fn bad_disk_column_settings() {
         let config = r#"columns = ["diskk"]"#;
-        toml_edit::de::from_str::<DiskConfig>(config).expect_err("Should error out!");
+        let err = toml_edit::de::from_str::<DiskConfig>(config).expect_err("Should error out!");
+
+        // The error should point out what could have been used instead.
+        let message = err.to_string();
+        assert!(message.contains("'diskk'"), "{message}");
+        assert!(message.contains("disk, mount, fs"), "{message}");
+        assert!(message.contains("used%"), "{message}");
+    }
+
+    #[test]
+    fn alias_settings() {
+        let config = r#"
+            [aliases]
+            "/srv/storage/media/library" = "media"
+            "/dev/sda1" = "boot"
+        "#;
+        let generated = toml_edit::de::from_str::<DiskConfig>(config).expect("Should succeed!");
+        let aliases = generated.aliases.unwrap();
+        assert_eq!(aliases["/srv/storage/media/library"], "media");
+        assert_eq!(aliases["/dev/sda1"], "boot");
     }
 }
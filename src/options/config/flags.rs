@@ -8,8 +8,11 @@ use super::StringOrNum;
 pub(crate) struct FlagConfig {
     pub(crate) hide_avg_cpu: Option<bool>,
     pub(crate) dot_marker: Option<bool>,
+    pub(crate) thick_lines: Option<bool>,
+    pub(crate) newest_on_left: Option<bool>,
     pub(crate) temperature_type: Option<String>,
     pub(crate) rate: Option<StringOrNum>,
+    pub(crate) temperature_rate: Option<StringOrNum>,
     pub(crate) cpu_left_legend: Option<bool>,
     pub(crate) current_usage: Option<bool>,
     pub(crate) unnormalized_cpu: Option<bool>,
@@ -32,6 +35,7 @@ pub(crate) struct FlagConfig {
     pub(crate) no_write: Option<bool>,
     pub(crate) network_legend: Option<String>,
     pub(crate) memory_legend: Option<String>,
+    pub(crate) gpu_legend: Option<String>,
     pub(crate) process_memory_as_value: Option<bool>,
     pub(crate) tree: Option<bool>,
     pub(crate) show_table_scroll_position: Option<bool>,
@@ -44,4 +48,10 @@ pub(crate) struct FlagConfig {
     pub(crate) enable_cache_memory: Option<bool>,
     pub(crate) retention: Option<StringOrNum>,
     pub(crate) average_cpu_row: Option<bool>,
+    pub(crate) compact_bytes: Option<bool>,
+    pub(crate) process_command_tooltip: Option<bool>,
+    pub(crate) save_state: Option<bool>,
+    pub(crate) instance_conflict: Option<String>,
+    pub(crate) metrics_listen: Option<String>,
+    pub(crate) merge_tolerance: Option<StringOrNum>,
 }
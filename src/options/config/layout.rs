@@ -13,6 +13,18 @@ pub struct Row {
     pub child: Option<Vec<RowChildren>>,
 }
 
+/// A layout that is only used if the terminal is at least a certain size.
+#[derive(Clone, Deserialize, Debug, Serialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub struct ConditionalLayout {
+    /// The minimum terminal width, in columns, to use this layout for.
+    pub min_width: Option<u16>,
+    /// The minimum terminal height, in rows, to use this layout for.
+    pub min_height: Option<u16>,
+    pub row: Vec<Row>,
+}
+
 fn new_cpu(cpu_left_legend: bool, iter_id: &mut u64) -> BottomColRow {
     let cpu_id = *iter_id;
     *iter_id += 1;
@@ -116,7 +128,8 @@ impl Row {
                             _ => BottomCol::new(vec![BottomColRow::new(vec![BottomWidget::new(
                                 widget_type,
                                 *iter_id,
-                            )])])
+                            )
+                            .interface(widget.interface.clone())])])
                             .ratio(width_ratio),
                         });
                     }
@@ -187,7 +200,8 @@ impl Row {
                                         BottomColRow::new(vec![BottomWidget::new(
                                             widget_type,
                                             *iter_id,
-                                        )])
+                                        )
+                                        .interface(widget.interface.clone())])
                                         .ratio(col_row_height_ratio),
                                     )
                                 }
@@ -236,6 +250,9 @@ pub struct FinalWidget {
     #[serde(rename = "type")]
     pub widget_type: String,
     pub default: Option<bool>,
+    /// For network widgets, the name of the only interface to graph, such as
+    /// `eth0`.
+    pub interface: Option<String>,
 }
 
 #[cfg(test)]
@@ -708,4 +725,39 @@ mod test {
             Some(13)
         );
     }
+
+    #[test]
+    fn load_avg_widget() {
+        let layout = r#"
+        [[row]]
+            [[row.child]]
+                type="cpu"
+            [[row.child]]
+                type="load"
+        "#;
+        let rows = from_str::<Config>(layout).unwrap().row.unwrap();
+
+        #[cfg(target_family = "unix")]
+        {
+            let layout = test_create_layout(&rows, DEFAULT_WIDGET_ID, None, 1, false);
+            let load = &layout.rows[0].children[1].children[0].children[0];
+            assert_eq!(load.widget_type, BottomWidgetType::LoadAvg);
+        }
+
+        #[cfg(not(target_family = "unix"))]
+        {
+            let (mut iter_id, mut height, mut id, mut count) = (0, 0, DEFAULT_WIDGET_ID, 1);
+            let err = rows[0]
+                .convert_row_to_bottom_row(
+                    &mut iter_id,
+                    &mut height,
+                    &mut id,
+                    &None,
+                    &mut count,
+                    false,
+                )
+                .unwrap_err();
+            assert!(err.to_string().contains("no load average"), "{err}");
+        }
+    }
 }
@@ -0,0 +1,41 @@
+use serde::Deserialize;
+
+use crate::options::MemorySeries;
+
+/// Memory configuration.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub(crate) struct MemoryConfig {
+    /// The series shown by the memory graph, in order. Series that aren't
+    /// listed are hidden.
+    pub(crate) series: Option<Vec<MemorySeries>>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn memory_series_config() {
+        let config = r#"
+            series = ["arc", "Memory", "SWAP", "gpu", "Arc_Details"]
+        "#;
+        let generated: MemoryConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            generated.series,
+            Some(vec![
+                MemorySeries::Arc,
+                MemorySeries::Ram,
+                MemorySeries::Swap,
+                MemorySeries::Gpu,
+                MemorySeries::ArcDetails
+            ])
+        );
+
+        let config = r#"
+            series = ["ram", "disk"]
+        "#;
+        toml_edit::de::from_str::<MemoryConfig>(config).expect_err("Should error out!");
+    }
+}
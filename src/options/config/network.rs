@@ -9,4 +9,24 @@ use super::IgnoreList;
 pub(crate) struct NetworkConfig {
     /// A filter over the network interface names.
     pub(crate) interface_filter: Option<IgnoreList>,
+
+    /// Whether to show the usage of each interface separately in the network graph,
+    /// rather than the sum of all of them.
+    pub(crate) show_interfaces: Option<bool>,
+
+    /// Whether the network graph's y-axis should grow to the largest value seen
+    /// this session and never shrink, rather than fitting what is shown.
+    pub(crate) lock_to_session_max: Option<bool>,
+
+    /// Whether to draw a line at the highest RX and TX rates seen this session
+    /// in the network graph.
+    pub(crate) show_session_max: Option<bool>,
+
+    /// Whether to show the highest rates and the totals seen this session next
+    /// to the current rates in the network graph's legend.
+    pub(crate) show_session_totals: Option<bool>,
+
+    /// Pins the network graph's y-axis max to a rate per second, e.g. "100Mb"
+    /// or "1GiB", rather than fitting what is shown.
+    pub(crate) y_max: Option<String>,
 }
@@ -1,6 +1,10 @@
 use serde::Deserialize;
 
-use crate::widgets::ProcColumn;
+use super::StringOrNum;
+use crate::{
+    canvas::components::data_table::ColumnSeparator,
+    widgets::{CustomColumnUnit, ProcColumn, TreeStyle},
+};
 
 /// Process configuration.
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -10,12 +14,73 @@ pub(crate) struct ProcessesConfig {
     /// A list of process widget columns.
     #[serde(default)]
     pub(crate) columns: Vec<ProcColumn>, // TODO: make this more composable(?) in the future, we might need to rethink how it's done for custom widgets
+
+    /// A list of custom columns, computed from other process values. These are
+    /// shown after all other columns.
+    #[serde(default)]
+    pub(crate) custom_columns: Vec<CustomColumnConfig>,
+
+    /// The signal sent when killing a process, either by name (e.g. "KILL") or
+    /// by number. Defaults to TERM. Only supported on Unix-like systems.
+    pub(crate) kill_signal_default: Option<StringOrNum>,
+
+    /// The signals to list in the advanced kill dialog, either by name or by
+    /// number. Defaults to all signals. Only supported on Unix-like systems.
+    pub(crate) kill_signals: Option<Vec<StringOrNum>>,
+
+    /// Whether to show numeric uids and gids instead of user and group names.
+    /// If unset, this is done automatically on Linux if bottom's `/etc/passwd`
+    /// differs from the host's, e.g. in a container that can see the host's
+    /// processes. Only supported on Unix-like systems.
+    pub(crate) uid_numeric: Option<bool>,
+
+    /// What to draw between columns: "space" (the default), "padding" for
+    /// extra space, or "line" for a vertical line.
+    pub(crate) column_separator: Option<ColumnSeparator>,
+
+    /// How the process tree is drawn: "lines" (the default) to join processes
+    /// to their parents with lines, or "plain" to only indent them.
+    pub(crate) tree_style: Option<TreeStyle>,
+
+    /// Whether to show how many processes are in each group next to its name
+    /// when grouping processes, e.g. `chrome (12)`. Defaults to false.
+    pub(crate) show_group_count: Option<bool>,
+
+    /// When grouping processes, only group those with more than this many
+    /// processes of the same name, and show the rest on their own. Defaults to
+    /// 0, which groups everything.
+    pub(crate) group_threshold: Option<usize>,
+
+    /// Whether to sort text columns like the name by case, so that e.g. `Xorg`
+    /// comes before `bash`. Defaults to false.
+    pub(crate) case_sensitive_sort: Option<bool>,
+
+    /// The CPU usage, as a percentage, that a process must go over for the
+    /// "last spike" column and `spiked_within` searches to count it as a
+    /// spike. Defaults to 50.
+    pub(crate) spike_threshold: Option<u32>,
+}
+
+/// A custom process column.
+#[derive(Clone, Debug, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub(crate) struct CustomColumnConfig {
+    /// The name of the column, shown in its header.
+    pub(crate) name: String,
+
+    /// The expression used to compute the column's value, e.g. `mem_bytes / cpu_percent`.
+    pub(crate) expr: String,
+
+    /// How to format the column's values.
+    #[serde(default)]
+    pub(crate) unit: CustomColumnUnit,
 }
 
 #[cfg(test)]
 mod test {
-    use super::{ProcColumn, ProcessesConfig};
-    use crate::widgets::ProcWidgetColumn;
+    use super::{ColumnSeparator, CustomColumnConfig, ProcColumn, ProcessesConfig};
+    use crate::widgets::{CustomColumnUnit, ProcWidgetColumn};
 
     #[test]
     fn empty_column_setting() {
@@ -34,7 +99,7 @@ mod test {
     #[test]
     fn valid_process_column_config() {
         let config = r#"
-            columns = ["CPU%", "PiD", "user", "MEM", "Tread", "T.Write", "Rps", "W/s", "tiMe", "USER", "state"]
+            columns = ["CPU%", "PiD", "user", "MEM", "Tread", "T.Write", "Rps", "W/s", "tiMe", "USER", "state", "Group"]
         "#;
 
         let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
@@ -52,6 +117,7 @@ mod test {
                 ProcWidgetColumn::Time,
                 ProcWidgetColumn::User,
                 ProcWidgetColumn::State,
+                ProcWidgetColumn::Group,
             ],
         );
     }
@@ -92,4 +158,53 @@ mod test {
             vec![ProcWidgetColumn::WritePerSecond; 3]
         );
     }
+
+    #[test]
+    fn custom_column_config() {
+        let config = r#"
+            [[custom_columns]]
+            name = "RSS/core"
+            expr = "mem_bytes / cpu_percent"
+            unit = "bytes"
+
+            [[custom_columns]]
+            name = "IO"
+            expr = "read_per_sec + write_per_sec"
+        "#;
+
+        let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            generated.custom_columns,
+            vec![
+                CustomColumnConfig {
+                    name: "RSS/core".to_string(),
+                    expr: "mem_bytes / cpu_percent".to_string(),
+                    unit: CustomColumnUnit::Bytes,
+                },
+                CustomColumnConfig {
+                    name: "IO".to_string(),
+                    expr: "read_per_sec + write_per_sec".to_string(),
+                    unit: CustomColumnUnit::None,
+                },
+            ]
+        );
+
+        let config = r#"
+            [[custom_columns]]
+            name = "Bad"
+            expr = "1"
+            unit = "furlongs"
+        "#;
+        toml_edit::de::from_str::<ProcessesConfig>(config).expect_err("Should error out!");
+    }
+
+    #[test]
+    fn column_separator_config() {
+        let generated: ProcessesConfig =
+            toml_edit::de::from_str(r#"column_separator = "line""#).unwrap();
+        assert_eq!(generated.column_separator, Some(ColumnSeparator::Line));
+
+        toml_edit::de::from_str::<ProcessesConfig>(r#"column_separator = "dots""#)
+            .expect_err("Should error out!");
+    }
 }
@@ -15,7 +15,7 @@ use std::borrow::Cow;
 
 use battery::BatteryStyle;
 use cpu::CpuStyle;
-use graphs::GraphStyle;
+use graphs::{GraphStyle, DEFAULT_GRIDLINES};
 use memory::MemoryStyle;
 use network::NetworkStyle;
 use serde::{Deserialize, Serialize};
@@ -108,9 +108,16 @@ pub struct Styles {
     pub(crate) tx_style: Style,
     pub(crate) total_rx_style: Style,
     pub(crate) total_tx_style: Style,
+    pub(crate) network_interface_colours: Vec<Style>,
     pub(crate) all_cpu_colour: Style,
     pub(crate) avg_cpu_colour: Style,
     pub(crate) cpu_colour_styles: Vec<Style>,
+    /// The styles of the user, system, iowait, and steal time lines in the CPU
+    /// widget's breakdown.
+    pub(crate) cpu_user_style: Style,
+    pub(crate) cpu_system_style: Style,
+    pub(crate) cpu_iowait_style: Style,
+    pub(crate) cpu_steal_style: Style,
     pub(crate) border_style: Style,
     pub(crate) highlighted_border_style: Style,
     pub(crate) text_style: Style,
@@ -124,7 +131,11 @@ pub struct Styles {
     pub(crate) low_battery: Style,
     pub(crate) invalid_query_style: Style,
     pub(crate) disabled_text_style: Style,
+    pub(crate) added_text_style: Style,
     pub(crate) border_type: BorderType,
+    /// Where to draw gridlines on graphs of percentages. This is empty if they
+    /// aren't shown.
+    pub(crate) gridlines: Vec<f64>,
 }
 
 impl Default for Styles {
@@ -135,6 +146,15 @@ impl Default for Styles {
 
 impl Styles {
     pub fn new(args: &BottomArgs, config: &Config) -> anyhow::Result<Self> {
+        if args.style.no_color {
+            let mut palette = Self::monochrome_palette();
+            if let Some(config_style) = &config.styles {
+                palette.set_non_colour_styles_from_config(config_style);
+            }
+
+            return Ok(palette);
+        }
+
         let mut palette = match &args.style.theme {
             Some(theme) => Self::from_theme(theme)?,
             None => match config.styles.as_ref().and_then(|s| s.theme.as_ref()) {
@@ -151,7 +171,7 @@ impl Styles {
         Ok(palette)
     }
 
-    fn from_theme(theme: &str) -> anyhow::Result<Self> {
+    pub(crate) fn from_theme(theme: &str) -> anyhow::Result<Self> {
         let lower_case = theme.to_lowercase();
         match lower_case.as_str() {
             "default" => Ok(Self::default_style()),
@@ -172,6 +192,10 @@ impl Styles {
         set_colour!(self.avg_cpu_colour, config.cpu, avg_entry_color);
         set_colour!(self.all_cpu_colour, config.cpu, all_entry_color);
         set_colour_list!(self.cpu_colour_styles, config.cpu, cpu_core_colors);
+        set_colour!(self.cpu_user_style, config.cpu, user_color);
+        set_colour!(self.cpu_system_style, config.cpu, system_color);
+        set_colour!(self.cpu_iowait_style, config.cpu, iowait_color);
+        set_colour!(self.cpu_steal_style, config.cpu, steal_color);
 
         // Memory
         set_colour!(self.ram_style, config.memory, ram_color);
@@ -191,6 +215,11 @@ impl Styles {
         set_colour!(self.tx_style, config.network, tx_color);
         set_colour!(self.total_rx_style, config.network, rx_total_color);
         set_colour!(self.total_tx_style, config.network, tx_total_color);
+        set_colour_list!(
+            self.network_interface_colours,
+            config.network,
+            interface_colors
+        );
 
         // Battery
         set_colour!(self.high_battery, config.battery, high_battery_color);
@@ -209,6 +238,7 @@ impl Styles {
         set_style!(self.text_style, config.widgets, text);
         set_style!(self.selected_text_style, config.widgets, selected_text);
         set_style!(self.disabled_text_style, config.widgets, disabled_text);
+        set_style!(self.added_text_style, config.widgets, added_text);
 
         // Widget borders
         set_colour!(self.border_style, config.widgets, border_color);
@@ -218,13 +248,30 @@ impl Styles {
             selected_border_color
         );
 
+        self.set_non_colour_styles_from_config(config);
+
+        Ok(())
+    }
+
+    /// Sets the styles that have nothing to do with colours, which are kept
+    /// even when drawing without colours.
+    fn set_non_colour_styles_from_config(&mut self, config: &StyleConfig) {
+        if let Some(graphs) = &config.graphs {
+            match graphs.show_gridlines {
+                Some(true) => {
+                    let gridlines = graphs.gridlines.as_deref().unwrap_or(&DEFAULT_GRIDLINES);
+                    self.gridlines = gridlines.iter().map(|&y| f64::from(y)).collect();
+                }
+                Some(false) => self.gridlines.clear(),
+                None => {}
+            }
+        }
+
         if let Some(widgets) = &config.widgets {
             if let Some(widget_borders) = widgets.widget_border_type {
                 self.border_type = widget_borders.into();
             }
         }
-
-        Ok(())
     }
 }
 
@@ -269,4 +316,133 @@ mod test {
         Styles::from_theme("nord").unwrap();
         Styles::from_theme("nord-light").unwrap();
     }
+
+    #[test]
+    fn gridlines_from_config() {
+        use super::{graphs::GraphStyle, StyleConfig};
+
+        let gridlines = |graphs: GraphStyle| {
+            let mut styles = Styles::default();
+            styles.set_non_colour_styles_from_config(&StyleConfig {
+                graphs: Some(graphs),
+                ..Default::default()
+            });
+
+            styles.gridlines
+        };
+
+        assert!(Styles::default().gridlines.is_empty());
+        assert_eq!(
+            gridlines(GraphStyle {
+                show_gridlines: Some(true),
+                ..Default::default()
+            }),
+            [25.0, 50.0, 75.0]
+        );
+        assert_eq!(
+            gridlines(GraphStyle {
+                show_gridlines: Some(true),
+                gridlines: Some(vec![10, 90]),
+                ..Default::default()
+            }),
+            [10.0, 90.0]
+        );
+
+        // The positions alone don't show them.
+        assert!(gridlines(GraphStyle {
+            gridlines: Some(vec![10, 90]),
+            ..Default::default()
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn monochrome_has_no_colours() {
+        let styles = Styles::monochrome_palette();
+
+        // Every style is listed, so that new ones have to be checked too.
+        let Styles {
+            ram_style,
+            #[cfg(not(target_os = "windows"))]
+            cache_style,
+            swap_style,
+            #[cfg(feature = "zfs")]
+            arc_style,
+            #[cfg(feature = "gpu")]
+            gpu_colours,
+            rx_style,
+            tx_style,
+            total_rx_style,
+            total_tx_style,
+            network_interface_colours,
+            all_cpu_colour,
+            avg_cpu_colour,
+            cpu_colour_styles,
+            cpu_user_style,
+            cpu_system_style,
+            cpu_iowait_style,
+            cpu_steal_style,
+            border_style,
+            highlighted_border_style,
+            text_style,
+            selected_text_style,
+            table_header_style,
+            widget_title_style,
+            graph_style,
+            graph_legend_style,
+            high_battery,
+            medium_battery,
+            low_battery,
+            invalid_query_style,
+            disabled_text_style,
+            added_text_style,
+            border_type: _,
+            gridlines: _,
+        } = &styles;
+
+        let mut all = vec![
+            ram_style,
+            #[cfg(not(target_os = "windows"))]
+            cache_style,
+            swap_style,
+            #[cfg(feature = "zfs")]
+            arc_style,
+            rx_style,
+            tx_style,
+            total_rx_style,
+            total_tx_style,
+            all_cpu_colour,
+            avg_cpu_colour,
+            cpu_user_style,
+            cpu_system_style,
+            cpu_iowait_style,
+            cpu_steal_style,
+            border_style,
+            highlighted_border_style,
+            text_style,
+            selected_text_style,
+            table_header_style,
+            widget_title_style,
+            graph_style,
+            graph_legend_style,
+            high_battery,
+            medium_battery,
+            low_battery,
+            invalid_query_style,
+            disabled_text_style,
+            added_text_style,
+        ];
+        #[cfg(feature = "gpu")]
+        all.extend(gpu_colours);
+        all.extend(network_interface_colours);
+        all.extend(cpu_colour_styles);
+
+        for style in all {
+            assert!(!matches!(style.fg, Some(Color::Rgb(..))));
+            assert_eq!((style.fg, style.bg), (None, None));
+        }
+
+        // Selected entries still stand out.
+        assert_ne!(styles.selected_text_style, styles.text_style);
+    }
 }
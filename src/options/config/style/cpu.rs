@@ -18,4 +18,20 @@ pub(crate) struct CpuStyle {
     /// Colour of each CPU threads' label and graph line. Read in order.
     #[serde(alias = "cpu_core_colours")]
     pub(crate) cpu_core_colors: Option<Vec<ColorStr>>,
+
+    /// The colour of the user time label and graph line in the breakdown.
+    #[serde(alias = "user_colour")]
+    pub(crate) user_color: Option<ColorStr>,
+
+    /// The colour of the system time label and graph line in the breakdown.
+    #[serde(alias = "system_colour")]
+    pub(crate) system_color: Option<ColorStr>,
+
+    /// The colour of the iowait time label and graph line in the breakdown.
+    #[serde(alias = "iowait_colour")]
+    pub(crate) iowait_color: Option<ColorStr>,
+
+    /// The colour of the steal time label and graph line in the breakdown.
+    #[serde(alias = "steal_colour")]
+    pub(crate) steal_color: Option<ColorStr>,
 }
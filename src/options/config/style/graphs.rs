@@ -2,6 +2,9 @@ use serde::{Deserialize, Serialize};
 
 use super::{ColorStr, TextStyleConfig};
 
+/// Where gridlines go by default, as percentages.
+pub(crate) const DEFAULT_GRIDLINES: [u8; 3] = [25, 50, 75];
+
 /// General styling for graph widgets.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
@@ -13,4 +16,10 @@ pub(crate) struct GraphStyle {
 
     /// Text styling for graph's legend text.
     pub(crate) legend_text: Option<TextStyleConfig>,
+
+    /// Whether to draw faint lines across the CPU and memory graphs.
+    pub(crate) show_gridlines: Option<bool>,
+
+    /// The percentages to draw gridlines at. Defaults to 25, 50, and 75.
+    pub(crate) gridlines: Option<Vec<u8>>,
 }
@@ -22,4 +22,9 @@ pub(crate) struct NetworkStyle {
     /// The colour of the total TX (upload) label in basic mode.
     #[serde(alias = "tx_total_colour")]
     pub(crate) tx_total_color: Option<ColorStr>,
+
+    /// Colour of each network interface's graph lines when showing interfaces
+    /// separately. Read in order, with each interface using two colours (RX and TX).
+    #[serde(alias = "interface_colours")]
+    pub(crate) interface_colors: Option<Vec<ColorStr>>,
 }
@@ -2,6 +2,7 @@
 
 pub(super) mod default;
 pub(super) mod gruvbox;
+pub(super) mod monochrome;
 pub(super) mod nord;
 
 macro_rules! color {
@@ -41,6 +41,16 @@ impl Styles {
             tx_style: color!(SECOND_COLOUR),
             total_rx_style: color!(THIRD_COLOUR),
             total_tx_style: color!(FOURTH_COLOUR),
+            network_interface_colours: vec![
+                color!(Color::LightMagenta),
+                color!(Color::LightYellow),
+                color!(Color::LightCyan),
+                color!(Color::LightGreen),
+                color!(Color::LightBlue),
+                color!(Color::Cyan),
+                color!(Color::Green),
+                color!(Color::Blue),
+            ],
             all_cpu_colour: color!(ALL_COLOUR),
             avg_cpu_colour: color!(AVG_COLOUR),
             cpu_colour_styles: vec![
@@ -53,6 +63,10 @@ impl Styles {
                 color!(Color::Green),
                 color!(Color::Blue),
             ],
+            cpu_user_style: color!(Color::LightGreen),
+            cpu_system_style: color!(Color::LightRed),
+            cpu_iowait_style: color!(Color::LightBlue),
+            cpu_steal_style: color!(Color::LightYellow),
             border_style: color!(TEXT_COLOUR),
             highlighted_border_style: color!(HIGHLIGHT_COLOUR),
             text_style: color!(TEXT_COLOUR),
@@ -66,7 +80,9 @@ impl Styles {
             low_battery: color!(Color::Red),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: color!(Color::DarkGray),
+            added_text_style: color!(Color::Green),
             border_type: BorderType::Plain,
+            gridlines: vec![],
         }
     }
 
@@ -92,6 +108,15 @@ impl Styles {
             tx_style: color!(Color::Red),
             total_rx_style: color!(Color::LightBlue),
             total_tx_style: color!(Color::LightRed),
+            network_interface_colours: vec![
+                color!(Color::LightMagenta),
+                color!(Color::LightBlue),
+                color!(Color::LightRed),
+                color!(Color::Cyan),
+                color!(Color::Green),
+                color!(Color::Blue),
+                color!(Color::Red),
+            ],
             cpu_colour_styles: vec![
                 color!(Color::LightMagenta),
                 color!(Color::LightBlue),
@@ -101,6 +126,10 @@ impl Styles {
                 color!(Color::Blue),
                 color!(Color::Red),
             ],
+            cpu_user_style: color!(Color::Green),
+            cpu_system_style: color!(Color::Red),
+            cpu_iowait_style: color!(Color::Blue),
+            cpu_steal_style: color!(Color::Magenta),
             border_style: color!(Color::Black),
             text_style: color!(Color::Black),
             selected_text_style: color!(Color::White).bg(Color::LightBlue),
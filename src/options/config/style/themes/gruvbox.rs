@@ -29,6 +29,28 @@ impl Styles {
             tx_style: hex!("#fabd2f"),
             total_rx_style: hex!("#689d6a"),
             total_tx_style: hex!("#d79921"),
+            network_interface_colours: vec![
+                hex!("#cc241d"),
+                hex!("#98971a"),
+                hex!("#d79921"),
+                hex!("#458588"),
+                hex!("#b16286"),
+                hex!("#689d6a"),
+                hex!("#fe8019"),
+                hex!("#b8bb26"),
+                hex!("#fabd2f"),
+                hex!("#83a598"),
+                hex!("#d3869b"),
+                hex!("#d65d0e"),
+                hex!("#9d0006"),
+                hex!("#79740e"),
+                hex!("#b57614"),
+                hex!("#076678"),
+                hex!("#8f3f71"),
+                hex!("#427b58"),
+                hex!("#d65d03"),
+                hex!("#af3a03"),
+            ],
             all_cpu_colour: hex!("#8ec07c"),
             avg_cpu_colour: hex!("#fb4934"),
             cpu_colour_styles: vec![
@@ -53,6 +75,10 @@ impl Styles {
                 hex!("#d65d03"),
                 hex!("#af3a03"),
             ],
+            cpu_user_style: hex!("#b8bb26"),
+            cpu_system_style: hex!("#fb4934"),
+            cpu_iowait_style: hex!("#83a598"),
+            cpu_steal_style: hex!("#fabd2f"),
             border_style: hex!("#ebdbb2"),
             highlighted_border_style: hex!("#fe8019"),
             text_style: hex!("#ebdbb2"),
@@ -66,7 +92,9 @@ impl Styles {
             low_battery: hex!("#fb4934"),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#665c54"),
+            added_text_style: hex!("#98971a"),
             border_type: BorderType::Plain,
+            gridlines: vec![],
         }
     }
 
@@ -92,6 +120,28 @@ impl Styles {
             tx_style: hex!("#cc241d"),
             total_rx_style: hex!("#689d6a"),
             total_tx_style: hex!("#d79921"),
+            network_interface_colours: vec![
+                hex!("#cc241d"),
+                hex!("#98971a"),
+                hex!("#d79921"),
+                hex!("#458588"),
+                hex!("#b16286"),
+                hex!("#689d6a"),
+                hex!("#fe8019"),
+                hex!("#b8bb26"),
+                hex!("#fabd2f"),
+                hex!("#83a598"),
+                hex!("#d3869b"),
+                hex!("#d65d0e"),
+                hex!("#9d0006"),
+                hex!("#79740e"),
+                hex!("#b57614"),
+                hex!("#076678"),
+                hex!("#8f3f71"),
+                hex!("#427b58"),
+                hex!("#d65d03"),
+                hex!("#af3a03"),
+            ],
             all_cpu_colour: hex!("#8ec07c"),
             avg_cpu_colour: hex!("#fb4934"),
             cpu_colour_styles: vec![
@@ -116,6 +166,10 @@ impl Styles {
                 hex!("#d65d03"),
                 hex!("#af3a03"),
             ],
+            cpu_user_style: hex!("#79740e"),
+            cpu_system_style: hex!("#9d0006"),
+            cpu_iowait_style: hex!("#076678"),
+            cpu_steal_style: hex!("#b57614"),
             border_style: hex!("#3c3836"),
             highlighted_border_style: hex!("#af3a03"),
             text_style: hex!("#3c3836"),
@@ -129,7 +183,9 @@ impl Styles {
             low_battery: hex!("#cc241d"),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#d5c4a1"),
+            added_text_style: hex!("#98971a"),
             border_type: BorderType::Plain,
+            gridlines: vec![],
         }
     }
 }
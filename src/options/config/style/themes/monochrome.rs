@@ -0,0 +1,55 @@
+use tui::{
+    style::{Modifier, Style},
+    widgets::BorderType,
+};
+
+use crate::options::config::style::Styles;
+
+impl Styles {
+    /// A palette without any colours, for terminals that can't show them.
+    /// Everything is drawn in the terminal's own foreground colour, and
+    /// emphasis is shown with bold, underlined, or reversed text instead.
+    pub(crate) fn monochrome_palette() -> Self {
+        const PLAIN: Style = Style::new();
+        const BOLD: Style = Style::new().add_modifier(Modifier::BOLD);
+
+        Self {
+            ram_style: PLAIN,
+            #[cfg(not(target_os = "windows"))]
+            cache_style: PLAIN,
+            swap_style: PLAIN,
+            #[cfg(feature = "zfs")]
+            arc_style: PLAIN,
+            #[cfg(feature = "gpu")]
+            gpu_colours: vec![PLAIN],
+            rx_style: PLAIN,
+            tx_style: PLAIN,
+            total_rx_style: PLAIN,
+            total_tx_style: PLAIN,
+            network_interface_colours: vec![PLAIN],
+            all_cpu_colour: PLAIN,
+            avg_cpu_colour: PLAIN,
+            cpu_colour_styles: vec![PLAIN],
+            cpu_user_style: PLAIN,
+            cpu_system_style: BOLD,
+            cpu_iowait_style: Style::new().add_modifier(Modifier::DIM),
+            cpu_steal_style: Style::new().add_modifier(Modifier::UNDERLINED),
+            border_style: PLAIN,
+            highlighted_border_style: BOLD,
+            text_style: PLAIN,
+            selected_text_style: Style::new().add_modifier(Modifier::REVERSED),
+            table_header_style: BOLD.add_modifier(Modifier::UNDERLINED),
+            widget_title_style: BOLD,
+            graph_style: PLAIN,
+            graph_legend_style: PLAIN,
+            high_battery: PLAIN,
+            medium_battery: PLAIN,
+            low_battery: BOLD,
+            invalid_query_style: Style::new().add_modifier(Modifier::UNDERLINED),
+            disabled_text_style: Style::new().add_modifier(Modifier::DIM),
+            added_text_style: BOLD,
+            border_type: BorderType::Plain,
+            gridlines: vec![],
+        }
+    }
+}
@@ -29,6 +29,16 @@ impl Styles {
             tx_style: hex!("#d08770"),
             total_rx_style: hex!("#5e81ac"),
             total_tx_style: hex!("#8fbcbb"),
+            network_interface_colours: vec![
+                hex!("#5e81ac"),
+                hex!("#81a1c1"),
+                hex!("#d8dee9"),
+                hex!("#b48ead"),
+                hex!("#a3be8c"),
+                hex!("#ebcb8b"),
+                hex!("#d08770"),
+                hex!("#bf616a"),
+            ],
             all_cpu_colour: hex!("#88c0d0"),
             avg_cpu_colour: hex!("#8fbcbb"),
             cpu_colour_styles: vec![
@@ -41,6 +51,10 @@ impl Styles {
                 hex!("#d08770"),
                 hex!("#bf616a"),
             ],
+            cpu_user_style: hex!("#a3be8c"),
+            cpu_system_style: hex!("#bf616a"),
+            cpu_iowait_style: hex!("#81a1c1"),
+            cpu_steal_style: hex!("#ebcb8b"),
             border_style: hex!("#88c0d0"),
             highlighted_border_style: hex!("#5e81ac"),
             text_style: hex!("#e5e9f0"),
@@ -54,7 +68,9 @@ impl Styles {
             low_battery: hex!("#bf616a"),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#4c566a"),
+            added_text_style: hex!("#a3be8c"),
             border_type: BorderType::Plain,
+            gridlines: vec![],
         }
     }
 
@@ -80,6 +96,16 @@ impl Styles {
             tx_style: hex!("#d08770"),
             total_rx_style: hex!("#5e81ac"),
             total_tx_style: hex!("#8fbcbb"),
+            network_interface_colours: vec![
+                hex!("#5e81ac"),
+                hex!("#88c0d0"),
+                hex!("#4c566a"),
+                hex!("#b48ead"),
+                hex!("#a3be8c"),
+                hex!("#ebcb8b"),
+                hex!("#d08770"),
+                hex!("#bf616a"),
+            ],
             all_cpu_colour: hex!("#81a1c1"),
             avg_cpu_colour: hex!("#8fbcbb"),
             cpu_colour_styles: vec![
@@ -92,6 +118,10 @@ impl Styles {
                 hex!("#d08770"),
                 hex!("#bf616a"),
             ],
+            cpu_user_style: hex!("#a3be8c"),
+            cpu_system_style: hex!("#bf616a"),
+            cpu_iowait_style: hex!("#5e81ac"),
+            cpu_steal_style: hex!("#d08770"),
             border_style: hex!("#2e3440"),
             highlighted_border_style: hex!("#5e81ac"),
             text_style: hex!("#2e3440"),
@@ -105,7 +135,9 @@ impl Styles {
             low_battery: hex!("#bf616a"),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#d8dee9"),
+            added_text_style: hex!("#a3be8c"),
             border_type: BorderType::Plain,
+            gridlines: vec![],
         }
     }
 }
@@ -27,6 +27,10 @@ pub(crate) struct WidgetStyle {
     /// Text styling for text when representing something that is disabled.
     pub(crate) disabled_text: Option<TextStyleConfig>,
 
+    /// Text styling for text when representing something that is new, like a
+    /// process that started after the data was frozen.
+    pub(crate) added_text: Option<TextStyleConfig>,
+
     /// Widget borders type.
     pub(crate) widget_border_type: Option<WidgetBorderType>,
 }
@@ -1,6 +1,7 @@
 use serde::Deserialize;
 
 use super::IgnoreList;
+use crate::widgets::TempWidgetColumn;
 
 /// Temperature configuration.
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -9,4 +10,43 @@ use super::IgnoreList;
 pub(crate) struct TempConfig {
     /// A filter over the sensor names.
     pub(crate) sensor_filter: Option<IgnoreList>,
+
+    /// A list of temperature widget columns.
+    #[serde(default)]
+    pub(crate) columns: Vec<TempWidgetColumn>,
+
+    /// Whether to sort the sensor column by case. Defaults to false.
+    pub(crate) case_sensitive_sort: Option<bool>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::TempConfig;
+    use crate::widgets::TempWidgetColumn;
+
+    #[test]
+    fn empty_column_setting() {
+        let generated: TempConfig = toml_edit::de::from_str("").unwrap();
+        assert!(generated.columns.is_empty());
+    }
+
+    #[test]
+    fn valid_temp_column_settings() {
+        let config = r#"columns = ["Sensor", "temperature", "MAX", "min", "trend"]"#;
+        let generated = toml_edit::de::from_str::<TempConfig>(config).expect("Should succeed!");
+        assert_eq!(
+            generated.columns,
+            [
+                TempWidgetColumn::Sensor,
+                TempWidgetColumn::Temp,
+                TempWidgetColumn::Max,
+                TempWidgetColumn::Min,
+                TempWidgetColumn::Trend
+            ]
+        );
+
+        let config = r#"columns = ["avg"]"#;
+        let err = toml_edit::de::from_str::<TempConfig>(config).expect_err("Should error out!");
+        assert!(err.to_string().contains("'avg'"), "{err}");
+    }
 }
@@ -0,0 +1,385 @@
+//! Checks for options that can't sensibly be used together, once the arguments
+//! and the config file have been merged.
+//!
+//! Each [`Rule`] either has a winner, in which case the others are ignored and
+//! the user is told so at startup, or rejects the combination outright.
+
+use std::fmt;
+
+use super::{
+    args::{self, BottomArgs},
+    config::{cpu::CpuDefault, Config},
+    OptionError, OptionResult,
+};
+use crate::data_collection::temperature::TemperatureType;
+
+/// Where an option was set. Environment variables count as the command line,
+/// as they are read as arguments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Source {
+    Args,
+    Config,
+}
+
+/// An option that was set, and where.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Setting {
+    name: String,
+    source: Source,
+}
+
+impl Setting {
+    fn new(name: impl Into<String>, source: Source) -> Self {
+        Self {
+            name: name.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for Setting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.source {
+            Source::Args => write!(f, "'--{}' (from the command line)", self.name),
+            Source::Config => write!(f, "'{}' (from the config file)", self.name),
+        }
+    }
+}
+
+/// Returns where a boolean flag was enabled, if it was. Like when merging, the
+/// command line is checked first.
+fn flag_setting(name: &'static str, arg: bool, config: Option<bool>) -> Option<Setting> {
+    if arg {
+        Some(Setting::new(name, Source::Args))
+    } else if config == Some(true) {
+        Some(Setting::new(name, Source::Config))
+    } else {
+        None
+    }
+}
+
+macro_rules! flag_setting {
+    ($flag_name:ident, $arg:expr, $config:expr) => {
+        flag_setting(
+            stringify!($flag_name),
+            $arg.$flag_name,
+            $config.flags.as_ref().and_then(|flags| flags.$flag_name),
+        )
+    };
+}
+
+/// What happens when all of a rule's options are set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Resolution {
+    /// The first option is used, and the rest are ignored.
+    FirstWins,
+
+    /// The options contradict each other, so bottom won't start.
+    Reject,
+}
+
+/// A combination of options that conflict.
+struct Rule {
+    /// Returns the conflicting options, if they are all set.
+    find: fn(&BottomArgs, &Config) -> Option<Vec<Setting>>,
+    resolution: Resolution,
+    /// Why they conflict, or what happens as a result.
+    reason: &'static str,
+}
+
+/// The known conflicts, checked in order.
+const RULES: &[Rule] = &[
+    Rule {
+        find: basic_and_expanded,
+        resolution: Resolution::FirstWins,
+        reason: "basic mode has no expanded widgets",
+    },
+    Rule {
+        find: hide_time_and_autohide_time,
+        resolution: Resolution::FirstWins,
+        reason: "the time scale is always hidden",
+    },
+    Rule {
+        find: group_processes_and_tree,
+        resolution: Resolution::FirstWins,
+        reason: "grouped processes can't be shown as a tree",
+    },
+    Rule {
+        find: temperature_units,
+        resolution: Resolution::FirstWins,
+        reason: "only one temperature unit can be used",
+    },
+    Rule {
+        find: hidden_average_cpu_by_default,
+        resolution: Resolution::Reject,
+        reason: "the average CPU entry can't be selected by default if it is hidden",
+    },
+];
+
+fn basic_and_expanded(args: &BottomArgs, config: &Config) -> Option<Vec<Setting>> {
+    Some(vec![
+        flag_setting!(basic, args.general, config)?,
+        flag_setting!(expanded, args.general, config)?,
+    ])
+}
+
+fn hide_time_and_autohide_time(args: &BottomArgs, config: &Config) -> Option<Vec<Setting>> {
+    Some(vec![
+        flag_setting!(hide_time, args.general, config)?,
+        flag_setting!(autohide_time, args.general, config)?,
+    ])
+}
+
+fn group_processes_and_tree(args: &BottomArgs, config: &Config) -> Option<Vec<Setting>> {
+    Some(vec![
+        flag_setting!(group_processes, args.process, config)?,
+        flag_setting!(tree, args.process, config)?,
+    ])
+}
+
+/// A unit on the command line and a different one in the config file. Invalid
+/// units in the config file are reported when they are parsed instead.
+fn temperature_units(args: &BottomArgs, config: &Config) -> Option<Vec<Setting>> {
+    let (name, unit) = if args.temperature.fahrenheit {
+        ("fahrenheit", TemperatureType::Fahrenheit)
+    } else if args.temperature.kelvin {
+        ("kelvin", TemperatureType::Kelvin)
+    } else if args.temperature.celsius {
+        ("celsius", TemperatureType::Celsius)
+    } else {
+        return None;
+    };
+
+    let config_unit = config.flags.as_ref()?.temperature_type.as_deref()?;
+    if config_unit.parse::<TemperatureType>().ok()? == unit {
+        return None;
+    }
+
+    Some(vec![
+        Setting::new(name, Source::Args),
+        Setting::new(
+            format!("temperature_type = \"{config_unit}\""),
+            Source::Config,
+        ),
+    ])
+}
+
+fn hidden_average_cpu_by_default(args: &BottomArgs, config: &Config) -> Option<Vec<Setting>> {
+    let hidden = flag_setting!(hide_avg_cpu, args.cpu, config)?;
+    let default = match &args.cpu.default_cpu_entry {
+        Some(args::CpuDefault::Average) => Setting::new("default_cpu_entry average", Source::Args),
+        Some(args::CpuDefault::All) => return None,
+        None => match config.cpu.as_ref()?.default {
+            CpuDefault::Average => Setting::new("cpu.default = \"average\"", Source::Config),
+            CpuDefault::All => return None,
+        },
+    };
+
+    Some(vec![hidden, default])
+}
+
+/// Joins settings into a list like "a, b and c".
+fn join(settings: &[Setting]) -> String {
+    match settings {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [rest @ .., last] => {
+            let rest = rest
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("{rest} and {last}")
+        }
+    }
+}
+
+/// Checks the merged options against every rule, returning a notice for each
+/// conflict that was resolved, or an error for the first one that can't be.
+pub(crate) fn check_conflicts(args: &BottomArgs, config: &Config) -> OptionResult<Vec<String>> {
+    let mut notices = vec![];
+
+    for rule in RULES {
+        let Some(settings) = (rule.find)(args, config) else {
+            continue;
+        };
+
+        match rule.resolution {
+            Resolution::FirstWins => notices.push(format!(
+                "{} takes precedence over {}, as {}.",
+                settings[0],
+                join(&settings[1..]),
+                rule.reason
+            )),
+            Resolution::Reject => {
+                return Err(OptionError::other(format!(
+                    "{} can't be used together, as {}. Please remove one of them.",
+                    join(&settings),
+                    rule.reason
+                )));
+            }
+        }
+    }
+
+    Ok(notices)
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::*;
+    use crate::options::config::{cpu::CpuConfig, flags::FlagConfig};
+
+    fn check(args: &[&str], flags: FlagConfig) -> OptionResult<Vec<String>> {
+        let config = Config {
+            flags: Some(flags),
+            ..Default::default()
+        };
+
+        check_conflicts(&BottomArgs::parse_from(args), &config)
+    }
+
+    #[test]
+    fn no_conflicts() {
+        assert_eq!(
+            check(&["btm", "--basic"], FlagConfig::default()),
+            Ok(vec![])
+        );
+        assert_eq!(
+            check(
+                &["btm"],
+                FlagConfig {
+                    expanded: Some(true),
+                    basic: Some(false),
+                    ..Default::default()
+                }
+            ),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn basic_wins_over_expanded() {
+        assert_eq!(
+            check(
+                &["btm", "--expanded"],
+                FlagConfig {
+                    basic: Some(true),
+                    ..Default::default()
+                }
+            ),
+            Ok(vec![
+                "'basic' (from the config file) takes precedence over '--expanded' (from the \
+                command line), as basic mode has no expanded widgets."
+                    .to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn hide_time_wins_over_autohide_time() {
+        assert_eq!(
+            check(
+                &["btm", "--hide_time", "--autohide_time"],
+                FlagConfig::default()
+            ),
+            Ok(vec![
+                "'--hide_time' (from the command line) takes precedence over '--autohide_time' \
+                (from the command line), as the time scale is always hidden."
+                    .to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn grouping_wins_over_tree() {
+        let notices = check(
+            &["btm", "--group_processes"],
+            FlagConfig {
+                tree: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(notices.len(), 1);
+        assert!(notices[0].starts_with("'--group_processes' (from the command line)"));
+        assert!(notices[0].contains("'tree' (from the config file)"));
+    }
+
+    #[test]
+    fn command_line_temperature_unit_wins() {
+        let config_unit = |unit: &str| FlagConfig {
+            temperature_type: Some(unit.to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            check(&["btm", "--kelvin"], config_unit("f")),
+            Ok(vec![
+                "'--kelvin' (from the command line) takes precedence over 'temperature_type = \"f\"' \
+                (from the config file), as only one temperature unit can be used."
+                    .to_string()
+            ])
+        );
+
+        // Units that agree, or no unit on the command line, are fine.
+        assert_eq!(check(&["btm", "-k"], config_unit("kelvin")), Ok(vec![]));
+        assert_eq!(check(&["btm"], config_unit("f")), Ok(vec![]));
+
+        // Invalid units are left to be reported when parsed.
+        assert_eq!(check(&["btm", "-k"], config_unit("rankine")), Ok(vec![]));
+    }
+
+    #[test]
+    fn hidden_average_cpu_is_rejected() {
+        let err = check(
+            &["btm", "--hide_avg_cpu", "--default_cpu_entry", "average"],
+            FlagConfig::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, OptionError::Other(_)));
+        assert!(
+            err.to_string().contains(
+                "'--hide_avg_cpu' (from the command line) and '--default_cpu_entry average' \
+                (from the command line) can't be used together"
+            ),
+            "{err}"
+        );
+
+        // From the config file.
+        let config = Config {
+            flags: Some(FlagConfig {
+                hide_avg_cpu: Some(true),
+                ..Default::default()
+            }),
+            cpu: Some(CpuConfig {
+                default: CpuDefault::Average,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let err = check_conflicts(&BottomArgs::parse_from(["btm"]), &config).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("'cpu.default = \"average\"' (from the config file)"));
+
+        // The command line's default takes precedence over the config file's.
+        let args = BottomArgs::parse_from(["btm", "--default_cpu_entry", "all"]);
+        assert_eq!(check_conflicts(&args, &config), Ok(vec![]));
+    }
+
+    #[test]
+    fn joined_settings() {
+        let settings = ["a", "b", "c"].map(|name| Setting::new(name, Source::Args));
+
+        assert_eq!(join(&settings[..1]), "'--a' (from the command line)");
+        assert_eq!(
+            join(&settings),
+            "'--a' (from the command line), '--b' (from the command line) and '--c' (from the \
+            command line)"
+        );
+    }
+}
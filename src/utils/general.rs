@@ -24,6 +24,29 @@ pub fn partial_ordering_desc<T: PartialOrd>(a: T, b: T) -> Ordering {
     partial_ordering(a, b).reverse()
 }
 
+/// Returns an [`Ordering`] between two strings for sorting a table by them.
+///
+/// Unless `case_sensitive` is set, case is ignored (e.g. `bash` comes before
+/// `Xorg`), and strings that only differ by case are ordered by their bytes so
+/// that the order is still deterministic. Nothing is allocated, as this is
+/// called for every comparison.
+pub fn sort_str_fn(a: &str, b: &str, is_descending: bool, case_sensitive: bool) -> Ordering {
+    let ordering = if case_sensitive {
+        a.cmp(b)
+    } else {
+        a.chars()
+            .flat_map(char::to_lowercase)
+            .cmp(b.chars().flat_map(char::to_lowercase))
+            .then_with(|| a.cmp(b))
+    };
+
+    if is_descending {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
+
 /// A trait for additional clamping functions on numeric types.
 pub trait ClampExt {
     /// Restrict a value by a lower bound. If the current value is _lower_ than
@@ -107,4 +130,27 @@ mod test {
         y.sort_by(|a, b| sort_partial_fn(true)(a, b));
         assert_eq!(y, vec![16.15, 15.0, 1.0, -1.0, -100.0, -100.0, -100.1]);
     }
+
+    #[test]
+    fn test_sort_str_fn() {
+        let mut x = vec!["bash", "Xorg", "BASH", "xorg", "Bash", "ÄBC", "abd"];
+
+        x.sort_by(|a, b| sort_str_fn(a, b, false, false));
+        assert_eq!(
+            x,
+            vec!["abd", "BASH", "Bash", "bash", "Xorg", "xorg", "ÄBC"]
+        );
+
+        x.sort_by(|a, b| sort_str_fn(a, b, true, false));
+        assert_eq!(
+            x,
+            vec!["ÄBC", "xorg", "Xorg", "bash", "Bash", "BASH", "abd"]
+        );
+
+        x.sort_by(|a, b| sort_str_fn(a, b, false, true));
+        assert_eq!(
+            x,
+            vec!["BASH", "Bash", "Xorg", "abd", "bash", "xorg", "ÄBC"]
+        );
+    }
 }
@@ -0,0 +1,245 @@
+//! Advisory lock files, so that multiple running instances of bottom don't
+//! overwrite each other's files.
+//!
+//! A lock on `<path>` is an OS file lock on a `<path>.lock` file, which is
+//! released by the OS when its holder exits, even after a crash. The lock file
+//! itself is left in place, as removing it could let two instances lock
+//! different files at the same path. Only writers take locks; reads remain
+//! lock-free.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+};
+
+/// How many instance-suffixed paths to try before giving up.
+const MAX_INSTANCES: usize = 16;
+
+/// What to do if another instance already holds the lock on a file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InstanceConflict {
+    /// Don't write the file.
+    #[default]
+    Skip,
+
+    /// Write to an instance-suffixed path instead, e.g. `state.2.toml`.
+    Suffix,
+}
+
+/// A held lock on a file, which is released when dropped.
+#[derive(Debug)]
+pub struct FileLock {
+    /// The path of the locked file, which is where the holder should write to.
+    path: PathBuf,
+    _lock_file: File,
+}
+
+impl FileLock {
+    /// Tries to lock `path`, returning [`None`] if another running instance
+    /// holds the lock.
+    pub fn try_acquire(path: &Path) -> io::Result<Option<Self>> {
+        let lock_path = lock_path(path);
+        if let Some(parent_path) = lock_path.parent() {
+            fs::create_dir_all(parent_path)?;
+        }
+
+        let lock_file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)?;
+
+        Ok(try_lock(&lock_file)?.then(|| Self {
+            path: path.to_path_buf(),
+            _lock_file: lock_file,
+        }))
+    }
+
+    /// Locks `path` for writing, handling any conflict with another instance
+    /// as given. Returns [`None`] if the file shouldn't be written.
+    pub fn acquire_for_writing(
+        path: &Path, conflict: InstanceConflict,
+    ) -> io::Result<Option<Self>> {
+        match conflict {
+            InstanceConflict::Skip => Self::try_acquire(path),
+            InstanceConflict::Suffix => {
+                for instance in 1..=MAX_INSTANCES {
+                    if let Some(lock) = Self::try_acquire(&instance_path(path, instance))? {
+                        return Ok(Some(lock));
+                    }
+                }
+
+                Ok(None)
+            }
+        }
+    }
+
+    /// The path of the locked file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Takes an exclusive lock on `file` without waiting, returning whether it was
+/// taken. The lock is held until the file is closed.
+#[cfg(target_family = "unix")]
+fn try_lock(file: &File) -> io::Result<bool> {
+    use std::os::fd::AsRawFd;
+
+    // SAFETY: The file descriptor is valid for as long as `file` is.
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+        return Ok(true);
+    }
+
+    match io::Error::last_os_error() {
+        err if err.kind() == io::ErrorKind::WouldBlock => Ok(false),
+        err => Err(err),
+    }
+}
+
+/// Takes an exclusive lock on `file` without waiting, returning whether it was
+/// taken. The lock is held until the file is closed.
+#[cfg(target_os = "windows")]
+fn try_lock(file: &File) -> io::Result<bool> {
+    use std::os::windows::io::AsRawHandle;
+
+    use windows::{
+        core::HRESULT,
+        Win32::{
+            Foundation::{ERROR_LOCK_VIOLATION, HANDLE},
+            Storage::FileSystem::{LockFileEx, LOCKFILE_EXCLUSIVE_LOCK, LOCKFILE_FAIL_IMMEDIATELY},
+            System::IO::OVERLAPPED,
+        },
+    };
+
+    let mut overlapped = OVERLAPPED::default();
+
+    // SAFETY: The handle is valid for as long as `file` is, and `overlapped`
+    // outlives the call, which doesn't wait.
+    let result = unsafe {
+        LockFileEx(
+            HANDLE(file.as_raw_handle()),
+            LOCKFILE_EXCLUSIVE_LOCK | LOCKFILE_FAIL_IMMEDIATELY,
+            None,
+            u32::MAX,
+            u32::MAX,
+            &mut overlapped,
+        )
+    };
+
+    match result {
+        Ok(()) => Ok(true),
+        Err(err) if err.code() == HRESULT::from_win32(ERROR_LOCK_VIOLATION.0) => Ok(false),
+        Err(err) => Err(io::Error::other(err)),
+    }
+}
+
+fn lock_path(path: &Path) -> PathBuf {
+    let mut lock_path = path.as_os_str().to_owned();
+    lock_path.push(".lock");
+    lock_path.into()
+}
+
+/// Returns the path to use for the nth instance, e.g. `state.2.toml`. The
+/// first instance uses the path as is.
+fn instance_path(path: &Path, instance: usize) -> PathBuf {
+    if instance <= 1 {
+        return path.to_path_buf();
+    }
+
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let file_name = match path.extension() {
+        Some(extension) => format!("{stem}.{instance}.{}", extension.to_string_lossy()),
+        None => format!("{stem}.{instance}"),
+    };
+
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::{Arc, Barrier},
+        thread,
+    };
+
+    use super::*;
+
+    /// Has two threads race for the lock on the same path.
+    fn contend(path: &Path, conflict: InstanceConflict) -> Vec<Option<PathBuf>> {
+        let barrier = Arc::new(Barrier::new(2));
+
+        let handles = (0..2)
+            .map(|_| {
+                let barrier = barrier.clone();
+                let path = path.to_path_buf();
+
+                thread::spawn(move || {
+                    barrier.wait();
+                    let lock = FileLock::acquire_for_writing(&path, conflict).unwrap();
+
+                    // Hold the lock until both threads have tried.
+                    barrier.wait();
+                    lock.map(|lock| lock.path().to_path_buf())
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn contention_skips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("skip.toml");
+        let results = contend(&path, InstanceConflict::Skip);
+
+        assert_eq!(results.iter().filter(|result| result.is_some()).count(), 1);
+        assert!(results.contains(&Some(path.clone())));
+
+        // Both locks were released.
+        assert!(FileLock::try_acquire(&path).unwrap().is_some());
+    }
+
+    #[test]
+    fn contention_suffixes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("suffix.toml");
+        let results = contend(&path, InstanceConflict::Suffix);
+
+        assert!(results.contains(&Some(path.clone())));
+        assert!(results.contains(&Some(dir.path().join("suffix.2.toml"))));
+    }
+
+    #[test]
+    fn locks_are_released_when_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.toml");
+
+        let lock = FileLock::try_acquire(&path).unwrap().unwrap();
+        assert!(FileLock::try_acquire(&path).unwrap().is_none());
+        drop(lock);
+
+        // A lock file left behind, e.g. after a crash, isn't held by anyone.
+        assert!(lock_path(&path).exists());
+        assert!(FileLock::try_acquire(&path).unwrap().is_some());
+    }
+
+    #[test]
+    fn instance_paths() {
+        let path = Path::new("/tmp/bottom/state.toml");
+        assert_eq!(instance_path(path, 1), path);
+        assert_eq!(
+            instance_path(path, 3),
+            Path::new("/tmp/bottom/state.3.toml")
+        );
+        assert_eq!(
+            instance_path(Path::new("/tmp/log"), 2),
+            Path::new("/tmp/log.2")
+        );
+    }
+}
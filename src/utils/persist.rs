@@ -0,0 +1,316 @@
+//! Versioning for the files that bottom writes and later reads back, such as
+//! the state file.
+//!
+//! Each file has a `version` field with a `MAJOR.MINOR` format version. Minor
+//! versions only add fields, so files from a newer minor version still load
+//! (the new fields are just ignored). Files from a newer major version are
+//! rejected, and files from an older one are upgraded by running their
+//! [`Persisted::MIGRATIONS`] in order before being deserialized. Files without
+//! a version predate versioning, and are treated as version `1.0`.
+
+use std::{fmt, str::FromStr};
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use toml_edit::DocumentMut;
+
+/// The key of the format version in each file.
+pub const VERSION_KEY: &str = "version";
+
+/// The version of files written before versioning was added.
+const UNVERSIONED: FormatVersion = FormatVersion::new(1, 0);
+
+/// The version of a file's format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FormatVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl FormatVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl fmt::Display for FormatVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for FormatVersion {
+    type Err = ();
+
+    /// Parses a `MAJOR.MINOR` version, or just a `MAJOR` one.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (major, minor) = s.split_once('.').unwrap_or((s, "0"));
+        let major = major.parse().map_err(|_| ())?;
+        let minor = minor.parse().map_err(|_| ())?;
+
+        if major == 0 {
+            Err(())
+        } else {
+            Ok(Self { major, minor })
+        }
+    }
+}
+
+impl Serialize for FormatVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for FormatVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("'{value}' isn't a valid version")))
+    }
+}
+
+/// Upgrades a file by one major version, in place.
+pub type Migration = fn(&mut DocumentMut);
+
+/// A type that is saved to a versioned file.
+pub trait Persisted: Serialize + DeserializeOwned {
+    /// What the file is called in messages, e.g. "state file".
+    const KIND: &'static str;
+
+    /// The current format version.
+    const VERSION: FormatVersion;
+
+    /// The migrations from older major versions, where the first upgrades a
+    /// file from major version 1 to 2, the second from 2 to 3, and so on. There
+    /// must be one fewer than the current major version.
+    const MIGRATIONS: &'static [Migration];
+}
+
+/// An error from reading or writing a versioned file.
+#[derive(Debug)]
+pub enum PersistError {
+    /// The file isn't valid TOML.
+    Parse(toml_edit::TomlError),
+
+    /// The file's version isn't a valid version.
+    InvalidVersion(String),
+
+    /// The file is from a newer major version than is supported.
+    TooNew {
+        kind: &'static str,
+        found: FormatVersion,
+        supported: FormatVersion,
+    },
+
+    /// The file's contents don't match its version's format.
+    Deserialize(toml_edit::de::Error),
+
+    /// The value couldn't be written as TOML.
+    Serialize(toml_edit::ser::Error),
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistError::Parse(err) => err.fmt(f),
+            PersistError::InvalidVersion(version) => {
+                write!(f, "'{version}' isn't a valid {VERSION_KEY}")
+            }
+            PersistError::TooNew {
+                kind,
+                found,
+                supported,
+            } => write!(
+                f,
+                "the {kind} is from a newer version of bottom (format {found}, but only up to {}.x is supported)",
+                supported.major
+            ),
+            PersistError::Deserialize(err) => err.fmt(f),
+            PersistError::Serialize(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for PersistError {}
+
+/// Returns the format version of `doc`, without removing it.
+fn doc_version(doc: &DocumentMut) -> Result<FormatVersion, PersistError> {
+    match doc.get(VERSION_KEY) {
+        None => Ok(UNVERSIONED),
+        Some(item) => {
+            let version = match item.as_str() {
+                Some(version) => version.to_string(),
+                None => item.to_string().trim().to_string(),
+            };
+
+            version
+                .parse()
+                .map_err(|_| PersistError::InvalidVersion(version))
+        }
+    }
+}
+
+/// Checks that a file with the given version can be loaded as a `T`.
+fn check_version<T: Persisted>(found: FormatVersion) -> Result<(), PersistError> {
+    if found.major > T::VERSION.major {
+        Err(PersistError::TooNew {
+            kind: T::KIND,
+            found,
+            supported: T::VERSION,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns whether `contents` is a file that can't be loaded as a `T`
+/// because it is from a newer major version, and so shouldn't be overwritten.
+pub fn is_too_new<T: Persisted>(contents: &str) -> bool {
+    contents
+        .parse::<DocumentMut>()
+        .ok()
+        .and_then(|doc| doc_version(&doc).ok())
+        .is_some_and(|found| check_version::<T>(found).is_err())
+}
+
+/// Loads a `T` from the TOML in `contents`, migrating it from an older major
+/// version if needed.
+pub fn from_toml<T: Persisted>(contents: &str) -> Result<T, PersistError> {
+    debug_assert_eq!(T::MIGRATIONS.len() as u32 + 1, T::VERSION.major);
+
+    let mut doc = contents
+        .parse::<DocumentMut>()
+        .map_err(PersistError::Parse)?;
+    let found = doc_version(&doc)?;
+    check_version::<T>(found)?;
+
+    doc.remove(VERSION_KEY);
+    for migrate in &T::MIGRATIONS[(found.major - 1) as usize..] {
+        migrate(&mut doc);
+    }
+
+    toml_edit::de::from_document(doc).map_err(PersistError::Deserialize)
+}
+
+/// Writes `value` as TOML, starting with its format version.
+pub fn to_toml<T: Persisted>(value: &T) -> Result<String, PersistError> {
+    let contents = toml_edit::ser::to_string_pretty(value).map_err(PersistError::Serialize)?;
+
+    Ok(format!("{VERSION_KEY} = \"{}\"\n{contents}", T::VERSION))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A file that renamed `name` to `names` and made it a list in version 2,
+    /// and then added `count` in version 2.1.
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Example {
+        names: Vec<String>,
+        #[serde(default)]
+        count: u32,
+    }
+
+    fn names_to_list(doc: &mut DocumentMut) {
+        if let Some(name) = doc.remove("name") {
+            let mut names = toml_edit::Array::new();
+            if let Some(name) = name.as_str() {
+                names.push(name);
+            }
+            doc.insert("names", toml_edit::value(names));
+        }
+    }
+
+    impl Persisted for Example {
+        const KIND: &'static str = "example file";
+        const VERSION: FormatVersion = FormatVersion::new(2, 1);
+        const MIGRATIONS: &'static [Migration] = &[names_to_list];
+    }
+
+    #[test]
+    fn round_trip() {
+        let example = Example {
+            names: vec!["a".into(), "b".into()],
+            count: 3,
+        };
+        let contents = to_toml(&example).unwrap();
+
+        assert!(contents.starts_with("version = \"2.1\"\n"));
+        assert_eq!(from_toml::<Example>(&contents).unwrap(), example);
+    }
+
+    #[test]
+    fn older_versions_are_migrated() {
+        let expected = Example {
+            names: vec!["a".into()],
+            count: 0,
+        };
+
+        assert_eq!(
+            from_toml::<Example>("version = \"1.0\"\nname = \"a\"\n").unwrap(),
+            expected
+        );
+
+        // Files without a version are from before versioning.
+        assert_eq!(from_toml::<Example>("name = \"a\"\n").unwrap(), expected);
+
+        // Older minor versions don't need migrating.
+        assert_eq!(
+            from_toml::<Example>("version = \"2.0\"\nnames = [\"a\"]\n").unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn newer_versions() {
+        // Newer minor versions only add fields, which are ignored.
+        assert_eq!(
+            from_toml::<Example>("version = \"2.7\"\nnames = []\nnew_field = 1\n").unwrap(),
+            Example {
+                names: vec![],
+                count: 0,
+            }
+        );
+
+        let contents = "version = \"3.0\"\nsomething_else = true\n";
+        let err = from_toml::<Example>(contents).unwrap_err();
+        assert!(matches!(
+            err,
+            PersistError::TooNew {
+                found: FormatVersion { major: 3, minor: 0 },
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "the example file is from a newer version of bottom (format 3.0, but only up to 2.x is supported)"
+        );
+        assert!(is_too_new::<Example>(contents));
+        assert!(!is_too_new::<Example>("version = \"2.7\"\n"));
+        assert!(!is_too_new::<Example>("[[["));
+    }
+
+    #[test]
+    fn invalid_versions() {
+        for contents in ["version = \"one\"", "version = \"0.1\"", "version = [1]"] {
+            assert!(
+                matches!(
+                    from_toml::<Example>(contents),
+                    Err(PersistError::InvalidVersion(_))
+                ),
+                "{contents}"
+            );
+        }
+
+        // A bare major version is fine too.
+        assert_eq!(
+            from_toml::<Example>("version = 2\nnames = []\n").unwrap(),
+            Example {
+                names: vec![],
+                count: 0,
+            }
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use tui::text::Text;
 use unicode_ellipsis::truncate_str;
 
@@ -9,6 +11,19 @@ pub fn truncate_to_text<'a, U: Into<usize>>(content: &str, width: U) -> Text<'a>
     Text::raw(truncate_str(content, width.into()).to_string())
 }
 
+/// Truncates a table cell's text to `width` columns, cutting it between
+/// graphemes and adding an ellipsis at the end if needed. Text that already
+/// fits is returned as-is, so most cells don't need a new allocation.
+#[inline]
+pub fn truncate_cell(content: Cow<'static, str>, width: usize) -> Cow<'static, str> {
+    let truncated = match truncate_str(&content, width) {
+        Cow::Borrowed(text) if text.len() == content.len() => None,
+        truncated => Some(truncated.into_owned()),
+    };
+
+    truncated.map_or(content, Cow::Owned)
+}
+
 /// Checks that the first string is equal to any of the other ones in a ASCII
 /// case-insensitive match.
 ///
@@ -35,6 +50,35 @@ macro_rules! multi_eq_ignore_ascii_case {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_cell_at_graphemes() {
+        assert_eq!(truncate_cell("firefox".into(), 7), "firefox");
+        assert_eq!(truncate_cell("firefox".into(), 4), "fir…");
+
+        // Wide characters aren't cut in half, even if that leaves a gap.
+        assert_eq!(truncate_cell("日本語のプロセス".into(), 6), "日本…");
+        assert_eq!(truncate_cell("日本語のプロセス".into(), 5), "日本…");
+        assert_eq!(truncate_cell("日本語".into(), 6), "日本語");
+
+        // Nor are combined emoji.
+        assert_eq!(truncate_cell("a👩‍👩‍👧b".into(), 3), "a…");
+        assert_eq!(truncate_cell("a👩‍👩‍👧bc".into(), 4), "a👩‍👩‍👧…");
+
+        assert_eq!(truncate_cell("firefox".into(), 0), "");
+    }
+
+    #[test]
+    fn truncate_cell_reuses_fitting_text() {
+        let content = String::from("firefox");
+        let ptr = content.as_ptr();
+
+        match truncate_cell(content.into(), 10) {
+            Cow::Owned(text) => assert_eq!(text.as_ptr(), ptr),
+            Cow::Borrowed(_) => panic!("the text should have been kept"),
+        }
+    }
 
     #[test]
     fn test_multi_eq_ignore_ascii_case() {
@@ -1,6 +1,7 @@
 pub mod battery_info;
 pub mod cpu_graph;
 pub mod disk_table;
+pub mod gpu_graph;
 pub mod mem_graph;
 pub mod net_graph;
 pub mod process_table;
@@ -9,6 +10,7 @@ pub mod temperature_table;
 pub use battery_info::*;
 pub use cpu_graph::*;
 pub use disk_table::*;
+pub use gpu_graph::*;
 pub use mem_graph::*;
 pub use net_graph::*;
 pub use process_table::*;
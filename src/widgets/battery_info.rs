@@ -1,5 +1,19 @@
-#[derive(Default)]
+use std::time::Instant;
+
 pub struct BatteryWidgetState {
     pub currently_selected_battery_index: usize,
     pub tab_click_locs: Option<Vec<((u16, u16), (u16, u16))>>,
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl BatteryWidgetState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        BatteryWidgetState {
+            currently_selected_battery_index: 0,
+            tab_click_locs: None,
+            current_display_time,
+            autohide_timer,
+        }
+    }
 }
@@ -1,15 +1,16 @@
 use std::{borrow::Cow, num::NonZeroU16, time::Instant};
 
 use concat_string::concat_string;
-use tui::widgets::Row;
+use tui::{style::Style, widgets::Row};
 
 use crate::{
     app::AppConfigFields,
     canvas::{
         components::data_table::{
-            Column, ColumnHeader, DataTable, DataTableColumn, DataTableProps, DataTableStyling,
-            DataToCell,
+            Column, ColumnHeader, ColumnSeparator, DataTable, DataTableColumn, DataTableProps,
+            DataTableStyling, DataToCell,
         },
+        drawing_utils::{load_colour, ColourDepth},
         Painter,
     },
     data_collection::cpu::CpuDataType,
@@ -36,11 +37,13 @@ pub enum CpuWidgetTableData {
     Entry {
         data_type: CpuDataType,
         last_entry: f64,
+        /// Whether to colour the usage by how high it is.
+        heatmap: bool,
     },
 }
 
 impl CpuWidgetTableData {
-    pub fn from_cpu_widget_data(data: &CpuWidgetData) -> CpuWidgetTableData {
+    pub fn from_cpu_widget_data(data: &CpuWidgetData, heatmap: bool) -> CpuWidgetTableData {
         match data {
             CpuWidgetData::All => CpuWidgetTableData::All,
             CpuWidgetData::Entry {
@@ -50,9 +53,23 @@ impl CpuWidgetTableData {
             } => CpuWidgetTableData::Entry {
                 data_type: *data_type,
                 last_entry: *last_entry,
+                heatmap,
             },
         }
     }
+
+    /// The style of the usage cell, if it is coloured by load. Only the text is
+    /// coloured, so that the entry still matches its line in the graph.
+    fn use_style(&self, depth: ColourDepth) -> Option<Style> {
+        match self {
+            CpuWidgetTableData::Entry {
+                last_entry,
+                heatmap: true,
+                ..
+            } => Some(Style::default().fg(load_colour(*last_entry, depth))),
+            _ => None,
+        }
+    }
 }
 
 impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
@@ -78,6 +95,7 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
             CpuWidgetTableData::Entry {
                 data_type,
                 last_entry,
+                ..
             } => {
                 if calculated_width == 0 {
                     None
@@ -93,6 +111,16 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
                                     concat_string!("CPU", index_str).into()
                                 };
 
+                                Some(text)
+                            }
+                            CpuDataType::Socket(index) => {
+                                let index_str = index.to_string();
+                                let text = if calculated_width < CPU_TRUNCATE_BREAKPOINT {
+                                    concat_string!("S", index_str).into()
+                                } else {
+                                    concat_string!("SKT", index_str).into()
+                                };
+
                                 Some(text)
                             }
                         },
@@ -107,12 +135,9 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
     fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
         let style = match self {
             CpuWidgetTableData::All => painter.styles.all_cpu_colour,
-            CpuWidgetTableData::Entry {
-                data_type,
-                last_entry: _,
-            } => match data_type {
+            CpuWidgetTableData::Entry { data_type, .. } => match data_type {
                 CpuDataType::Avg => painter.styles.avg_cpu_colour,
-                CpuDataType::Cpu(index) => {
+                CpuDataType::Cpu(index) | CpuDataType::Socket(index) => {
                     painter.styles.cpu_colour_styles[index % painter.styles.cpu_colour_styles.len()]
                 }
             },
@@ -121,6 +146,13 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
         row.style(style)
     }
 
+    fn style_cell(&self, column: &CpuWidgetColumn, painter: &Painter) -> Option<Style> {
+        match column {
+            CpuWidgetColumn::Cpu => None,
+            CpuWidgetColumn::Use => self.use_style(painter.colour_depth),
+        }
+    }
+
     fn column_widths<C: DataTableColumn<CpuWidgetColumn>>(
         _data: &[Self], _columns: &[C],
     ) -> Vec<u16>
@@ -136,6 +168,12 @@ pub struct CpuWidgetState {
     pub is_legend_hidden: bool,
     pub autohide_timer: Option<Instant>,
     pub table: DataTable<CpuWidgetTableData, CpuWidgetColumn>,
+    /// Whether to colour the legend's usages by how high they are.
+    pub legend_heatmap: bool,
+    /// Whether the graph shows how CPU time is split between user, system,
+    /// iowait, and steal time instead of the entries in the legend, which is
+    /// hidden.
+    pub show_breakdown: bool,
 }
 
 impl CpuWidgetState {
@@ -155,6 +193,8 @@ impl CpuWidgetState {
             is_basic: false,
             show_table_scroll_position: false, // TODO: Should this be possible?
             show_current_entry_when_unfocused: true,
+            column_separator: ColumnSeparator::default(),
+            show_scrollbar: false,
         };
 
         let styling = DataTableStyling::from_palette(colours);
@@ -172,14 +212,97 @@ impl CpuWidgetState {
             is_legend_hidden: false,
             autohide_timer,
             table,
+            legend_heatmap: config.cpu_legend_heatmap,
+            show_breakdown: config.cpu_breakdown,
         }
     }
 
     pub fn update_table(&mut self, data: &[CpuWidgetData]) {
         self.table.set_data(
             data.iter()
-                .map(CpuWidgetTableData::from_cpu_widget_data)
+                .map(|data| CpuWidgetTableData::from_cpu_widget_data(data, self.legend_heatmap))
                 .collect(),
         );
     }
 }
+
+#[cfg(test)]
+mod test {
+    use tui::style::Color;
+
+    use super::*;
+
+    /// The usage text and its colour for each entry, as the legend would show
+    /// them.
+    fn legend(loads: &[f64], depth: ColourDepth) -> Vec<(String, Option<Color>)> {
+        let width = NonZeroU16::new(5).unwrap();
+
+        loads
+            .iter()
+            .enumerate()
+            .map(|(index, &load)| {
+                let entry = CpuWidgetTableData::Entry {
+                    data_type: CpuDataType::Cpu(index),
+                    last_entry: load,
+                    heatmap: true,
+                };
+
+                (
+                    entry
+                        .to_cell(&CpuWidgetColumn::Use, width)
+                        .unwrap()
+                        .into_owned(),
+                    entry.use_style(depth).and_then(|style| style.fg),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn legend_heatmap() {
+        let idle = [2.0, 0.0, 5.0, 1.0];
+        assert_eq!(
+            legend(&idle, ColourDepth::TrueColour),
+            vec![
+                ("2%".to_string(), Some(Color::Rgb(10, 255, 0))),
+                ("0%".to_string(), Some(Color::Rgb(0, 255, 0))),
+                ("5%".to_string(), Some(Color::Rgb(26, 255, 0))),
+                ("1%".to_string(), Some(Color::Rgb(5, 255, 0))),
+            ]
+        );
+
+        let skewed = [100.0, 75.0, 40.0, 3.0];
+        assert_eq!(
+            legend(&skewed, ColourDepth::TrueColour),
+            vec![
+                ("100%".to_string(), Some(Color::Rgb(255, 0, 0))),
+                ("75%".to_string(), Some(Color::Rgb(255, 128, 0))),
+                ("40%".to_string(), Some(Color::Rgb(204, 255, 0))),
+                ("3%".to_string(), Some(Color::Rgb(15, 255, 0))),
+            ]
+        );
+        assert_eq!(
+            legend(&skewed, ColourDepth::Ansi16),
+            vec![
+                ("100%".to_string(), Some(Color::Red)),
+                ("75%".to_string(), Some(Color::Red)),
+                ("40%".to_string(), Some(Color::Yellow)),
+                ("3%".to_string(), Some(Color::Green)),
+            ]
+        );
+    }
+
+    #[test]
+    fn legend_heatmap_disabled() {
+        let entry = CpuWidgetTableData::Entry {
+            data_type: CpuDataType::Avg,
+            last_entry: 100.0,
+            heatmap: false,
+        };
+        assert_eq!(entry.use_style(ColourDepth::TrueColour), None);
+        assert_eq!(
+            CpuWidgetTableData::All.use_style(ColourDepth::TrueColour),
+            None
+        );
+    }
+}
@@ -1,55 +1,92 @@
 use std::{borrow::Cow, cmp::max, num::NonZeroU16};
 
+use itertools::Itertools;
 use serde::Deserialize;
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
     app::AppConfigFields,
     canvas::components::data_table::{
-        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
-        SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+        ColumnHeader, ColumnSeparator, ColumnWidthBounds, DataTableColumn, DataTableProps,
+        DataTableStyling, DataToCell, RowKey, SortColumn, SortDataTable, SortDataTableProps,
+        SortOrder, SortsRow,
+    },
+    data_collection::disks::PoolDetails,
+    data_conversion::{
+        compact_bytes_per_second_string, compact_bytes_string, dec_bytes_per_second_string,
     },
     options::config::style::Styles,
-    utils::{data_prefixes::get_decimal_bytes, general::sort_partial_fn},
+    utils::{
+        data_prefixes::get_decimal_bytes,
+        general::{sort_partial_fn, sort_str_fn},
+    },
 };
 
 #[derive(Clone, Debug)]
 pub struct DiskWidgetData {
     pub name: Cow<'static, str>,
     pub mount_point: Cow<'static, str>,
+    /// What is shown instead of the name, if the disk has an alias.
+    pub name_alias: Option<Cow<'static, str>>,
+    /// What is shown instead of the mount point, if the mount has an alias.
+    pub mount_alias: Option<Cow<'static, str>>,
+    pub file_system: Cow<'static, str>,
     pub free_bytes: Option<u64>,
     pub used_bytes: Option<u64>,
     pub total_bytes: Option<u64>,
     pub summed_total_bytes: Option<u64>,
-    pub io_read: Cow<'static, str>,
-    pub io_write: Cow<'static, str>,
+    pub io_read_rate: Option<u64>,
+    pub io_write_rate: Option<u64>,
+    pub pool: Option<PoolDetails>,
+    pub compact_bytes: bool,
 }
 
 impl DiskWidgetData {
-    fn total_space(&self) -> Cow<'static, str> {
-        if let Some(total_bytes) = self.total_bytes {
-            let converted_total_space = get_decimal_bytes(total_bytes);
-            format!("{:.0}{}", converted_total_space.0, converted_total_space.1).into()
-        } else {
-            "N/A".into()
+    /// The name shown in the table, which is the disk's alias if it has one.
+    pub fn display_name(&self) -> &Cow<'static, str> {
+        self.name_alias.as_ref().unwrap_or(&self.name)
+    }
+
+    /// The mount point shown in the table, which is the mount's alias if it
+    /// has one.
+    pub fn display_mount(&self) -> &Cow<'static, str> {
+        self.mount_alias.as_ref().unwrap_or(&self.mount_point)
+    }
+
+    /// Whether the name or mount point is shown as an alias.
+    pub fn has_alias(&self) -> bool {
+        self.name_alias.is_some() || self.mount_alias.is_some()
+    }
+
+    fn bytes_string(&self, bytes: Option<u64>) -> Cow<'static, str> {
+        match bytes {
+            Some(bytes) if self.compact_bytes => compact_bytes_string(bytes).into(),
+            Some(bytes) => {
+                let converted_bytes = get_decimal_bytes(bytes);
+                format!("{:.0}{}", converted_bytes.0, converted_bytes.1).into()
+            }
+            None => "N/A".into(),
         }
     }
 
-    fn free_space(&self) -> Cow<'static, str> {
-        if let Some(free_bytes) = self.free_bytes {
-            let converted_free_space = get_decimal_bytes(free_bytes);
-            format!("{:.0}{}", converted_free_space.0, converted_free_space.1).into()
-        } else {
-            "N/A".into()
+    fn rate_string(&self, rate: Option<u64>) -> Cow<'static, str> {
+        match rate {
+            Some(rate) if self.compact_bytes => compact_bytes_per_second_string(rate).into(),
+            Some(rate) => dec_bytes_per_second_string(rate).into(),
+            None => "N/A".into(),
         }
     }
 
+    fn total_space(&self) -> Cow<'static, str> {
+        self.bytes_string(self.total_bytes)
+    }
+
+    fn free_space(&self) -> Cow<'static, str> {
+        self.bytes_string(self.free_bytes)
+    }
+
     fn used_space(&self) -> Cow<'static, str> {
-        if let Some(used_bytes) = self.used_bytes {
-            let converted_free_space = get_decimal_bytes(used_bytes);
-            format!("{:.0}{}", converted_free_space.0, converted_free_space.1).into()
-        } else {
-            "N/A".into()
-        }
+        self.bytes_string(self.used_bytes)
     }
 
     fn free_percent(&self) -> Option<f64> {
@@ -66,6 +103,25 @@ impl DiskWidgetData {
         }
     }
 
+    /// How much of the disk's total space is used, as a percentage. This is
+    /// [`None`] if the total isn't known (e.g. for some LUKS setups) or is zero.
+    fn usage_percent(&self) -> Option<f64> {
+        match (self.used_bytes, self.total_bytes) {
+            (Some(used_bytes), Some(total_bytes)) if total_bytes > 0 => {
+                Some(used_bytes as f64 / total_bytes as f64 * 100_f64)
+            }
+            _ => None,
+        }
+    }
+
+    fn health(&self) -> Option<&str> {
+        self.pool.as_ref()?.health.as_deref()
+    }
+
+    fn fragmentation(&self) -> Option<u8> {
+        self.pool.as_ref()?.fragmentation
+    }
+
     fn used_percent(&self) -> Option<f64> {
         if let (Some(used_bytes), Some(summed_total_bytes)) =
             (self.used_bytes, self.summed_total_bytes)
@@ -86,10 +142,11 @@ impl DiskWidgetData {
     feature = "generate_schema",
     derive(schemars::JsonSchema, strum::VariantArray)
 )]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+#[derive(PartialEq, Eq)]
 pub enum DiskColumn {
     Disk,
     Mount,
+    FileSystem,
     Used,
     Free,
     Total,
@@ -97,6 +154,9 @@ pub enum DiskColumn {
     FreePercent,
     IoRead,
     IoWrite,
+    UsageBar,
+    Health,
+    Fragmentation,
 }
 
 impl<'de> Deserialize<'de> for DiskColumn {
@@ -105,30 +165,49 @@ impl<'de> Deserialize<'de> for DiskColumn {
         D: serde::Deserializer<'de>,
     {
         let value = String::deserialize(deserializer)?.to_lowercase();
-        match value.as_str() {
-            "disk" => Ok(DiskColumn::Disk),
-            "mount" => Ok(DiskColumn::Mount),
-            "used" => Ok(DiskColumn::Used),
-            "free" => Ok(DiskColumn::Free),
-            "total" => Ok(DiskColumn::Total),
-            "usedpercent" | "used%" => Ok(DiskColumn::UsedPercent),
-            "freepercent" | "free%" => Ok(DiskColumn::FreePercent),
-            "r/s" => Ok(DiskColumn::IoRead),
-            "w/s" => Ok(DiskColumn::IoWrite),
-            _ => Err(serde::de::Error::custom(
-                "doesn't match any disk column name",
-            )),
-        }
+        DiskColumn::NAMES
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, column)| column.clone())
+            .ok_or_else(|| {
+                serde::de::Error::custom(format!(
+                    "'{value}' doesn't match any disk column name; valid names are {}",
+                    DiskColumn::NAMES.iter().map(|(name, _)| name).join(", ")
+                ))
+            })
     }
 }
 
 impl DiskColumn {
+    /// The names that can be used for columns in the config file, ignoring
+    /// case, and the columns they refer to.
+    const NAMES: [(&'static str, DiskColumn); 17] = [
+        ("disk", DiskColumn::Disk),
+        ("mount", DiskColumn::Mount),
+        ("fs", DiskColumn::FileSystem),
+        ("filesystem", DiskColumn::FileSystem),
+        ("used", DiskColumn::Used),
+        ("free", DiskColumn::Free),
+        ("total", DiskColumn::Total),
+        ("used%", DiskColumn::UsedPercent),
+        ("usedpercent", DiskColumn::UsedPercent),
+        ("free%", DiskColumn::FreePercent),
+        ("freepercent", DiskColumn::FreePercent),
+        ("r/s", DiskColumn::IoRead),
+        ("w/s", DiskColumn::IoWrite),
+        ("usage", DiskColumn::UsageBar),
+        ("health", DiskColumn::Health),
+        ("frag%", DiskColumn::Fragmentation),
+        ("fragmentation", DiskColumn::Fragmentation),
+    ];
+
     /// An ugly hack to generate the JSON schema.
     #[cfg(feature = "generate_schema")]
     pub fn get_schema_names(&self) -> &[&'static str] {
         match self {
             DiskColumn::Disk => &["Disk"],
             DiskColumn::Mount => &["Mount"],
+            DiskColumn::FileSystem => &["FS", "FileSystem"],
             DiskColumn::Used => &["Used"],
             DiskColumn::Free => &["Free"],
             DiskColumn::Total => &["Total"],
@@ -136,6 +215,26 @@ impl DiskColumn {
             DiskColumn::FreePercent => &["Free%"],
             DiskColumn::IoRead => &["R/s", "Read", "Rps"],
             DiskColumn::IoWrite => &["W/s", "Write", "Wps"],
+            DiskColumn::UsageBar => &["Usage"],
+            DiskColumn::Health => &["Health"],
+            DiskColumn::Fragmentation => &["Frag%", "Fragmentation"],
+        }
+    }
+
+    /// The order that columns are hidden in when the table is too narrow,
+    /// lowest first. Columns without one are never hidden to make room.
+    fn hide_priority(&self) -> Option<u8> {
+        match self {
+            DiskColumn::Fragmentation => Some(0),
+            DiskColumn::Health => Some(1),
+            DiskColumn::FileSystem => Some(2),
+            DiskColumn::FreePercent => Some(3),
+            DiskColumn::Free => Some(4),
+            DiskColumn::Total => Some(5),
+            DiskColumn::UsageBar => Some(6),
+            DiskColumn::Mount => Some(7),
+            DiskColumn::UsedPercent => Some(8),
+            DiskColumn::Disk | DiskColumn::Used | DiskColumn::IoRead | DiskColumn::IoWrite => None,
         }
     }
 }
@@ -145,6 +244,7 @@ impl ColumnHeader for DiskColumn {
         match self {
             DiskColumn::Disk => "Disk(d)",
             DiskColumn::Mount => "Mount(m)",
+            DiskColumn::FileSystem => "FS",
             DiskColumn::Used => "Used(u)",
             DiskColumn::Free => "Free(n)",
             DiskColumn::Total => "Total(t)",
@@ -152,6 +252,9 @@ impl ColumnHeader for DiskColumn {
             DiskColumn::FreePercent => "Free%",
             DiskColumn::IoRead => "R/s(r)",
             DiskColumn::IoWrite => "W/s(w)",
+            DiskColumn::UsageBar => "Usage",
+            DiskColumn::Health => "Health",
+            DiskColumn::Fragmentation => "Frag%",
         }
         .into()
     }
@@ -159,7 +262,7 @@ impl ColumnHeader for DiskColumn {
 
 impl DataToCell<DiskColumn> for DiskWidgetData {
     fn to_cell(
-        &self, column: &DiskColumn, _calculated_width: NonZeroU16,
+        &self, column: &DiskColumn, calculated_width: NonZeroU16,
     ) -> Option<Cow<'static, str>> {
         fn percent_string(value: Option<f64>) -> Cow<'static, str> {
             match value {
@@ -169,35 +272,95 @@ impl DataToCell<DiskColumn> for DiskWidgetData {
         }
 
         let text = match column {
-            DiskColumn::Disk => self.name.clone(),
-            DiskColumn::Mount => self.mount_point.clone(),
+            DiskColumn::Disk => self.display_name().clone(),
+            DiskColumn::Mount => self.display_mount().clone(),
+            DiskColumn::FileSystem => self.file_system.clone(),
             DiskColumn::Used => self.used_space(),
             DiskColumn::Free => self.free_space(),
             DiskColumn::UsedPercent => percent_string(self.used_percent()),
             DiskColumn::FreePercent => percent_string(self.free_percent()),
             DiskColumn::Total => self.total_space(),
-            DiskColumn::IoRead => self.io_read.clone(),
-            DiskColumn::IoWrite => self.io_write.clone(),
+            DiskColumn::IoRead => self.rate_string(self.io_read_rate),
+            DiskColumn::IoWrite => self.rate_string(self.io_write_rate),
+            DiskColumn::UsageBar => usage_bar(self.usage_percent(), calculated_width.get()),
+            DiskColumn::Health => match self.health() {
+                Some(health) => health.to_string().into(),
+                None => "N/A".into(),
+            },
+            DiskColumn::Fragmentation => match self.fragmentation() {
+                Some(fragmentation) => format!("{fragmentation}%").into(),
+                None => "N/A".into(),
+            },
         };
 
         Some(text)
     }
 
-    fn column_widths<C: DataTableColumn<DiskColumn>>(data: &[Self], _columns: &[C]) -> Vec<u16>
+    /// A disk can be mounted in more than one place, so the mount point is
+    /// part of the key.
+    fn row_key(&self) -> Option<RowKey<'_>> {
+        Some(RowKey::Mount(
+            Cow::Borrowed(&self.name),
+            Cow::Borrowed(&self.mount_point),
+        ))
+    }
+
+    fn column_widths<C: DataTableColumn<DiskColumn>>(data: &[Self], columns: &[C]) -> Vec<u16>
     where
         Self: Sized,
     {
-        let mut widths = vec![0; 7];
+        let mut widths = vec![0; columns.len()];
 
-        data.iter().for_each(|row| {
-            widths[0] = max(widths[0], row.name.len() as u16);
-            widths[1] = max(widths[1], row.mount_point.len() as u16);
-        });
+        // Only the text columns are soft, so those are all that need a desired width.
+        for row in data {
+            for (width, column) in widths.iter_mut().zip(columns) {
+                let len = match column.inner() {
+                    DiskColumn::Disk => row.display_name().width(),
+                    DiskColumn::Mount => row.display_mount().width(),
+                    DiskColumn::UsageBar => usize::from(USAGE_BAR_DESIRED_WIDTH),
+                    _ => continue,
+                };
+                *width = max(*width, len as u16);
+            }
+        }
 
         widths
     }
 }
 
+/// The width the usage column asks for, which fits a bar of 10 characters.
+const USAGE_BAR_DESIRED_WIDTH: u16 = 17;
+
+/// The narrowest bar that is drawn in the usage column. Any narrower, and just
+/// the percentage is shown.
+const MIN_USAGE_BAR_WIDTH: usize = 4;
+
+/// Returns a bar filled up to `percent` along with the percentage itself, like
+/// `[█████     ]  52%`, that fits within `width`.
+fn usage_bar(percent: Option<f64>, width: u16) -> Cow<'static, str> {
+    let Some(percent) = percent else {
+        return "N/A".into();
+    };
+
+    // Leave room for the brackets and the widest percentage.
+    let bar_width = usize::from(width).saturating_sub("[] 100%".len());
+    let text = format!("{percent:.0}%");
+
+    if bar_width < MIN_USAGE_BAR_WIDTH {
+        text.into()
+    } else {
+        let filled = ((percent.clamp(0.0, 100.0) / 100.0 * bar_width as f64).round() as usize)
+            .min(bar_width);
+
+        format!(
+            "[{}{}] {text:>4}",
+            "█".repeat(filled),
+            " ".repeat(bar_width - filled)
+        )
+        .into()
+    }
+}
+
 pub struct DiskTableWidget {
     pub table: SortDataTable<DiskWidgetData, DiskColumn>,
     pub force_update_data: bool,
@@ -206,13 +369,32 @@ pub struct DiskTableWidget {
 impl SortsRow for DiskColumn {
     type DataType = DiskWidgetData;
 
-    fn sort_data(&self, data: &mut [Self::DataType], descending: bool) {
+    fn sort_data(&self, data: &mut [Self::DataType], descending: bool, case_sensitive: bool) {
         match self {
             DiskColumn::Disk => {
-                data.sort_by(|a, b| sort_partial_fn(descending)(&a.name, &b.name));
+                data.sort_by(|a, b| {
+                    sort_str_fn(
+                        a.display_name(),
+                        b.display_name(),
+                        descending,
+                        case_sensitive,
+                    )
+                });
             }
             DiskColumn::Mount => {
-                data.sort_by(|a, b| sort_partial_fn(descending)(&a.mount_point, &b.mount_point));
+                data.sort_by(|a, b| {
+                    sort_str_fn(
+                        a.display_mount(),
+                        b.display_mount(),
+                        descending,
+                        case_sensitive,
+                    )
+                });
+            }
+            DiskColumn::FileSystem => {
+                data.sort_by(|a, b| {
+                    sort_str_fn(&a.file_system, &b.file_system, descending, case_sensitive)
+                });
             }
             DiskColumn::Used => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(&a.used_bytes, &b.used_bytes));
@@ -234,19 +416,64 @@ impl SortsRow for DiskColumn {
                 data.sort_by(|a, b| sort_partial_fn(descending)(&a.total_bytes, &b.total_bytes));
             }
             DiskColumn::IoRead => {
-                data.sort_by(|a, b| sort_partial_fn(descending)(&a.io_read, &b.io_read));
+                data.sort_by(|a, b| sort_partial_fn(descending)(&a.io_read_rate, &b.io_read_rate));
             }
             DiskColumn::IoWrite => {
-                data.sort_by(|a, b| sort_partial_fn(descending)(&a.io_write, &b.io_write));
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(&a.io_write_rate, &b.io_write_rate)
+                });
+            }
+            DiskColumn::UsageBar => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(&a.usage_percent(), &b.usage_percent())
+                });
+            }
+            DiskColumn::Health => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(&a.health(), &b.health()));
+            }
+            DiskColumn::Fragmentation => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(&a.fragmentation(), &b.fragmentation())
+                });
             }
         }
     }
 }
 
-const fn create_column(column_type: &DiskColumn) -> SortColumn<DiskColumn> {
+/// The most of the table's width that the name and mount columns can each
+/// take up by default.
+const DEFAULT_SOFT_COLUMN_SHARE: f32 = 0.2;
+
+/// The narrowest the name, mount, and usage bar columns can get before other
+/// columns are hidden to make room for them.
+const MIN_SOFT_COLUMN_WIDTH: u16 = 10;
+
+/// Roughly how much of the table's width is taken up by each of the other,
+/// fixed-width, columns.
+const HARD_COLUMN_SHARE: f32 = 0.1;
+
+/// Returns how much of the table's width the name and mount columns can each
+/// take up, so that they get more room when there are fewer other columns.
+fn soft_column_share(columns: &[DiskColumn]) -> f32 {
+    let soft_columns = columns
+        .iter()
+        .filter(|column| matches!(column, DiskColumn::Disk | DiskColumn::Mount))
+        .count();
+    let hard_columns = columns.len() - soft_columns;
+
+    if soft_columns == 0 {
+        return DEFAULT_SOFT_COLUMN_SHARE;
+    }
+
+    ((1.0 - HARD_COLUMN_SHARE * hard_columns as f32) / soft_columns as f32)
+        .max(DEFAULT_SOFT_COLUMN_SHARE)
+}
+
+const fn create_column(column_type: &DiskColumn, soft_share: f32) -> SortColumn<DiskColumn> {
     match column_type {
-        DiskColumn::Disk => SortColumn::soft(DiskColumn::Disk, Some(0.2)),
-        DiskColumn::Mount => SortColumn::soft(DiskColumn::Mount, Some(0.2)),
+        DiskColumn::Disk => SortColumn::soft(DiskColumn::Disk, Some(soft_share)),
+        DiskColumn::Mount => SortColumn::soft(DiskColumn::Mount, Some(soft_share)),
+        DiskColumn::FileSystem => SortColumn::hard(DiskColumn::FileSystem, 9),
         DiskColumn::Used => SortColumn::hard(DiskColumn::Used, 8).default_descending(),
         DiskColumn::Free => SortColumn::hard(DiskColumn::Free, 8).default_descending(),
         DiskColumn::Total => SortColumn::hard(DiskColumn::Total, 9).default_descending(),
@@ -258,21 +485,25 @@ const fn create_column(column_type: &DiskColumn) -> SortColumn<DiskColumn> {
         }
         DiskColumn::IoRead => SortColumn::hard(DiskColumn::IoRead, 10).default_descending(),
         DiskColumn::IoWrite => SortColumn::hard(DiskColumn::IoWrite, 11).default_descending(),
+        DiskColumn::UsageBar => SortColumn::soft(DiskColumn::UsageBar, None).default_descending(),
+        DiskColumn::Health => SortColumn::hard(DiskColumn::Health, 9),
+        DiskColumn::Fragmentation => {
+            SortColumn::hard(DiskColumn::Fragmentation, 7).default_descending()
+        }
     }
 }
 
-const fn default_disk_columns() -> [SortColumn<DiskColumn>; 8] {
-    [
-        create_column(&DiskColumn::Disk),
-        create_column(&DiskColumn::Mount),
-        create_column(&DiskColumn::Used),
-        create_column(&DiskColumn::Free),
-        create_column(&DiskColumn::Total),
-        create_column(&DiskColumn::UsedPercent),
-        create_column(&DiskColumn::IoRead),
-        create_column(&DiskColumn::IoWrite),
-    ]
-}
+/// The columns shown if none are configured.
+pub const DEFAULT_DISK_COLUMNS: [DiskColumn; 8] = [
+    DiskColumn::Disk,
+    DiskColumn::Mount,
+    DiskColumn::Used,
+    DiskColumn::Free,
+    DiskColumn::Total,
+    DiskColumn::UsedPercent,
+    DiskColumn::IoRead,
+    DiskColumn::IoWrite,
+];
 
 impl DiskTableWidget {
     pub fn new(config: &AppConfigFields, palette: &Styles, columns: Option<&[DiskColumn]>) -> Self {
@@ -284,25 +515,26 @@ impl DiskTableWidget {
                 is_basic: config.use_basic_mode,
                 show_table_scroll_position: config.show_table_scroll_position,
                 show_current_entry_when_unfocused: false,
+                column_separator: ColumnSeparator::default(),
+                show_scrollbar: false,
             },
             sort_index: 0,
             order: SortOrder::Ascending,
+            case_sensitive: config.disk_case_sensitive_sort,
         };
 
         let styling = DataTableStyling::from_palette(palette);
 
-        match columns {
-            Some(columns) => {
-                let columns = columns.iter().map(create_column).collect::<Vec<_>>();
-                Self {
-                    table: SortDataTable::new_sortable(columns, props, styling),
-                    force_update_data: false,
-                }
-            }
-            None => Self {
-                table: SortDataTable::new_sortable(default_disk_columns(), props, styling),
-                force_update_data: false,
-            },
+        let columns = columns.unwrap_or(&DEFAULT_DISK_COLUMNS);
+        let soft_share = soft_column_share(columns);
+        let columns = columns
+            .iter()
+            .map(|column| create_column(column, soft_share))
+            .collect::<Vec<_>>();
+
+        Self {
+            table: SortDataTable::new_sortable(columns, props, styling),
+            force_update_data: false,
         }
     }
 
@@ -312,17 +544,420 @@ impl DiskTableWidget {
         self.force_update_data = true;
     }
 
-    /// Update the current table data.
+    /// Update the current table data. This is re-sorted each time, so that
+    /// changing values like the I/O rates stay in order.
     pub fn set_table_data(&mut self, data: &[DiskWidgetData]) {
         let mut data = data.to_vec();
+
+        // Disks are harvested in no particular order, so order them by name first
+        // to keep ties from jumping around between updates.
+        data.sort_by(|a, b| {
+            a.name
+                .cmp(&b.name)
+                .then_with(|| a.mount_point.cmp(&b.mount_point))
+        });
+
         if let Some(column) = self.table.columns.get(self.table.sort_index()) {
-            column.sort_by(&mut data, self.table.order());
+            column.sort_by(
+                &mut data,
+                self.table.order(),
+                self.table.is_case_sensitive(),
+            );
         }
         self.table.set_data(data);
     }
 
-    pub fn set_index(&mut self, index: usize) {
-        self.table.set_sort_index(index);
-        self.force_data_update();
+    /// Hides the least important columns until the rest fit in `width` without
+    /// getting too narrow to read, and shows the rest again. Columns are hidden
+    /// in the order given by [`DiskColumn::hide_priority`].
+    pub fn fit_columns(&mut self, width: u16) {
+        let spacing = self.table.props.column_separator.width();
+        let columns = &mut self.table.columns;
+
+        let mut hide_order = columns
+            .iter()
+            .enumerate()
+            .filter_map(|(index, column)| {
+                column
+                    .inner()
+                    .hide_priority()
+                    .map(|priority| (priority, index))
+            })
+            .collect::<Vec<_>>();
+        hide_order.sort_unstable();
+        let mut hide_order = hide_order.into_iter().map(|(_, index)| index);
+
+        for column in columns.iter_mut() {
+            column.is_hidden = false;
+        }
+
+        loop {
+            let needed_width = columns
+                .iter()
+                .filter(|column| !column.is_hidden())
+                .map(|column| {
+                    let min_width = match column.bounds() {
+                        ColumnWidthBounds::Hard(width) => width,
+                        ColumnWidthBounds::Soft { .. } => MIN_SOFT_COLUMN_WIDTH,
+                        ColumnWidthBounds::FollowHeader => 0,
+                    };
+
+                    max(min_width, column.header_len() as u16) + spacing
+                })
+                .sum::<u16>()
+                .saturating_sub(spacing);
+
+            if needed_width <= width {
+                break;
+            }
+
+            match hide_order.next() {
+                Some(index) => columns[index].is_hidden = true,
+                None => break,
+            }
+        }
+    }
+
+    /// Sorts by the given column if it is shown, or reverses the sort if it
+    /// is already sorted by it.
+    pub fn select_column(&mut self, column: DiskColumn) {
+        if let Some(index) = self
+            .table
+            .columns
+            .iter()
+            .position(|col| *col.inner() == column)
+        {
+            self.table.set_sort_index(index);
+            self.force_data_update();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hashbrown::HashMap;
+
+    use super::*;
+    use crate::{
+        app::data_farmer::DataCollection, data_collection::disks::DiskHarvest,
+        data_conversion::ConvertedData,
+    };
+
+    fn disk(used_bytes: Option<u64>, free_bytes: Option<u64>) -> DiskWidgetData {
+        DiskWidgetData {
+            name: "/dev/sda1".into(),
+            mount_point: "/".into(),
+            name_alias: None,
+            mount_alias: None,
+            file_system: "ext4".into(),
+            free_bytes,
+            used_bytes,
+            total_bytes: None,
+            summed_total_bytes: used_bytes.zip(free_bytes).map(|(used, free)| used + free),
+            io_read_rate: None,
+            io_write_rate: None,
+            pool: None,
+            compact_bytes: false,
+        }
+    }
+
+    fn cell(data: &DiskWidgetData, column: DiskColumn) -> Cow<'static, str> {
+        data.to_cell(&column, NonZeroU16::new(10).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn percent_columns() {
+        let data = disk(Some(25), Some(75));
+        assert_eq!(cell(&data, DiskColumn::UsedPercent), "25.0%");
+        assert_eq!(cell(&data, DiskColumn::FreePercent), "75.0%");
+    }
+
+    #[test]
+    fn percent_columns_without_total() {
+        // A total of zero shouldn't divide by zero.
+        let data = disk(Some(0), Some(0));
+        assert_eq!(cell(&data, DiskColumn::UsedPercent), "N/A");
+        assert_eq!(cell(&data, DiskColumn::FreePercent), "N/A");
+
+        let data = disk(None, Some(10));
+        assert_eq!(cell(&data, DiskColumn::UsedPercent), "N/A");
+        assert_eq!(cell(&data, DiskColumn::FreePercent), "N/A");
+
+        let data = disk(Some(10), None);
+        assert_eq!(cell(&data, DiskColumn::UsedPercent), "N/A");
+        assert_eq!(cell(&data, DiskColumn::FreePercent), "N/A");
+    }
+
+    #[test]
+    fn pool_columns() {
+        let mut data = disk(Some(25), Some(75));
+        assert_eq!(cell(&data, DiskColumn::Health), "N/A");
+        assert_eq!(cell(&data, DiskColumn::Fragmentation), "N/A");
+
+        data.pool = Some(PoolDetails {
+            health: Some("DEGRADED".into()),
+            fragmentation: Some(12),
+        });
+        assert_eq!(cell(&data, DiskColumn::Health), "DEGRADED");
+        assert_eq!(cell(&data, DiskColumn::Fragmentation), "12%");
+    }
+
+    #[test]
+    fn usage_bars() {
+        let mut data = disk(Some(52), Some(48));
+        data.total_bytes = Some(100);
+
+        assert_eq!(
+            data.to_cell(&DiskColumn::UsageBar, NonZeroU16::new(17).unwrap())
+                .unwrap(),
+            "[█████     ]  52%"
+        );
+        assert_eq!(
+            data.to_cell(&DiskColumn::UsageBar, NonZeroU16::new(11).unwrap())
+                .unwrap(),
+            "[██  ]  52%"
+        );
+
+        // Too narrow for a bar.
+        assert_eq!(
+            data.to_cell(&DiskColumn::UsageBar, NonZeroU16::new(10).unwrap())
+                .unwrap(),
+            "52%"
+        );
+
+        // Without a total, there is nothing to divide by.
+        data.total_bytes = None;
+        assert_eq!(cell(&data, DiskColumn::UsageBar), "N/A");
+        data.total_bytes = Some(0);
+        assert_eq!(cell(&data, DiskColumn::UsageBar), "N/A");
+    }
+
+    #[test]
+    fn custom_column_widths() {
+        let mut long_mount = disk(None, None);
+        long_mount.mount_point = "/mnt/a/much/longer/mount".into();
+        let data = [disk(None, None), long_mount];
+
+        let columns = [DiskColumn::Mount, DiskColumn::Used, DiskColumn::Disk]
+            .iter()
+            .map(|column| create_column(column, DEFAULT_SOFT_COLUMN_SHARE))
+            .collect::<Vec<_>>();
+        assert_eq!(DiskWidgetData::column_widths(&data, &columns), [24, 0, 9]);
+    }
+
+    #[test]
+    fn desired_widths_use_display_width() {
+        let mut data = disk(None, None);
+        data.mount_point = "/mnt/ディスク".into();
+        let columns = [SortColumn::soft(DiskColumn::Mount, None)];
+
+        assert_eq!(DiskWidgetData::column_widths(&[data], &columns), [13]);
+    }
+
+    #[test]
+    fn narrow_tables_hide_columns() {
+        use DiskColumn::*;
+
+        let mut widget =
+            DiskTableWidget::new(&AppConfigFields::default(), &Styles::default(), None);
+        let mut shown = |width| {
+            widget.fit_columns(width);
+            widget
+                .table
+                .columns
+                .iter()
+                .filter(|column| !column.is_hidden())
+                .map(|column| column.inner().clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(shown(100), DEFAULT_DISK_COLUMNS);
+        assert_eq!(
+            shown(80),
+            [Disk, Mount, Used, Total, UsedPercent, IoRead, IoWrite]
+        );
+        assert_eq!(shown(70), [Disk, Mount, Used, UsedPercent, IoRead, IoWrite]);
+        assert_eq!(shown(60), [Disk, Used, UsedPercent, IoRead, IoWrite]);
+        assert_eq!(shown(45), [Disk, Used, IoRead, IoWrite]);
+        assert_eq!(shown(20), [Disk, Used, IoRead, IoWrite]);
+
+        // Widening the table shows them again.
+        assert_eq!(shown(100), DEFAULT_DISK_COLUMNS);
+    }
+
+    #[test]
+    fn soft_column_shares() {
+        assert_eq!(
+            soft_column_share(&DEFAULT_DISK_COLUMNS),
+            DEFAULT_SOFT_COLUMN_SHARE
+        );
+
+        // Fewer columns give the name and mount more room...
+        assert_eq!(
+            soft_column_share(&[DiskColumn::Disk, DiskColumn::UsedPercent]),
+            0.9
+        );
+        assert_eq!(
+            soft_column_share(&[DiskColumn::Disk, DiskColumn::Mount, DiskColumn::Used]),
+            0.45
+        );
+
+        // ...but more never gives them less than the default.
+        let mut many_columns = DEFAULT_DISK_COLUMNS.to_vec();
+        many_columns.extend([DiskColumn::FileSystem, DiskColumn::FreePercent]);
+        assert_eq!(soft_column_share(&many_columns), DEFAULT_SOFT_COLUMN_SHARE);
+    }
+
+    fn harvest(
+        name: &str, used: Option<u64>, free: Option<u64>, total: Option<u64>,
+    ) -> DiskHarvest {
+        DiskHarvest {
+            name: name.into(),
+            mount_point: format!("/mnt/{name}"),
+            used_space: used,
+            free_space: free,
+            total_space: total,
+            ..Default::default()
+        }
+    }
+
+    /// Converts harvested disks to what the table shows, in the same way as
+    /// the app does.
+    fn convert(disks: Vec<DiskHarvest>, io_rates: Vec<Option<(u64, u64)>>) -> Vec<DiskWidgetData> {
+        let data = DataCollection {
+            disk_harvest: disks,
+            io_rates,
+            ..Default::default()
+        };
+        let mut converted = ConvertedData::default();
+        converted.convert_disk_data(&data, false, &HashMap::default());
+        converted.disk_data
+    }
+
+    fn names(widget: &DiskTableWidget) -> Vec<&str> {
+        widget
+            .table
+            .data()
+            .iter()
+            .map(|disk| disk.name.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn sorting() {
+        // Harvested out of order, with ties in used space, total space, and
+        // write rate.
+        let data = convert(
+            vec![
+                harvest("sdb", Some(50), Some(20), Some(70)),
+                harvest("sdc", None, None, None),
+                harvest("nvme0n1", Some(10), Some(90), Some(100)),
+                harvest("sda", Some(50), Some(50), Some(100)),
+            ],
+            vec![Some((2000, 500)), None, Some((300, 0)), Some((10, 500))],
+        );
+
+        let mut widget =
+            DiskTableWidget::new(&AppConfigFields::default(), &Styles::default(), None);
+        widget.set_table_data(&data);
+        assert_eq!(names(&widget), ["nvme0n1", "sda", "sdb", "sdc"]);
+
+        let mut sort_by = |column: DiskColumn| {
+            widget.select_column(column);
+            widget.set_table_data(&data);
+            names(&widget)
+                .into_iter()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        // Already sorted by disk, so this reverses it.
+        assert_eq!(sort_by(DiskColumn::Disk), ["sdc", "sdb", "sda", "nvme0n1"]);
+        assert_eq!(sort_by(DiskColumn::Mount), ["nvme0n1", "sda", "sdb", "sdc"]);
+
+        // Ties are kept in order of their names, either way.
+        assert_eq!(sort_by(DiskColumn::Used), ["sda", "sdb", "nvme0n1", "sdc"]);
+        assert_eq!(sort_by(DiskColumn::Used), ["sdc", "nvme0n1", "sda", "sdb"]);
+        assert_eq!(sort_by(DiskColumn::Free), ["nvme0n1", "sda", "sdb", "sdc"]);
+        assert_eq!(sort_by(DiskColumn::Total), ["nvme0n1", "sda", "sdb", "sdc"]);
+        assert_eq!(sort_by(DiskColumn::Total), ["sdc", "sdb", "nvme0n1", "sda"]);
+        assert_eq!(
+            sort_by(DiskColumn::UsedPercent),
+            ["sdb", "sda", "nvme0n1", "sdc"]
+        );
+
+        // Rates are sorted by value, not by how they are shown.
+        assert_eq!(
+            sort_by(DiskColumn::IoRead),
+            ["sdb", "nvme0n1", "sda", "sdc"]
+        );
+        assert_eq!(
+            sort_by(DiskColumn::IoWrite),
+            ["sda", "sdb", "nvme0n1", "sdc"]
+        );
+
+        // Columns that aren't shown don't change the sort.
+        assert_eq!(
+            sort_by(DiskColumn::FileSystem),
+            ["sda", "sdb", "nvme0n1", "sdc"]
+        );
+    }
+
+    #[test]
+    fn sorting_follows_new_data() {
+        let disks = vec![
+            harvest("sda", Some(1), Some(1), Some(2)),
+            harvest("sdb", Some(1), Some(1), Some(2)),
+        ];
+
+        let mut widget =
+            DiskTableWidget::new(&AppConfigFields::default(), &Styles::default(), None);
+        widget.select_column(DiskColumn::IoRead);
+
+        widget.set_table_data(&convert(disks.clone(), vec![Some((10, 0)), Some((20, 0))]));
+        assert_eq!(names(&widget), ["sdb", "sda"]);
+
+        widget.set_table_data(&convert(disks, vec![Some((30, 0)), Some((20, 0))]));
+        assert_eq!(names(&widget), ["sda", "sdb"]);
+    }
+
+    #[test]
+    fn aliases() {
+        let data = DataCollection {
+            disk_harvest: vec![
+                harvest("sda", Some(1), Some(1), Some(2)),
+                harvest("sdb", Some(1), Some(1), Some(2)),
+                harvest("sdc", Some(1), Some(1), Some(2)),
+            ],
+            io_rates: vec![None; 3],
+            ..Default::default()
+        };
+        let aliases = HashMap::from([
+            ("/mnt/sda".to_string(), "zeta".to_string()),
+            ("sdb".to_string(), "backup".to_string()),
+            ("/mnt/unknown".to_string(), "unused".to_string()),
+        ]);
+        let mut converted = ConvertedData::default();
+        converted.convert_disk_data(&data, false, &aliases);
+        let disks = converted.disk_data;
+
+        let cells = |disk: &DiskWidgetData| {
+            [DiskColumn::Disk, DiskColumn::Mount].map(|column| cell(disk, column))
+        };
+        assert_eq!(cells(&disks[0]), ["sda", "zeta"]);
+        assert_eq!(cells(&disks[1]), ["backup", "/mnt/sdb"]);
+        assert_eq!(cells(&disks[2]), ["sdc", "/mnt/sdc"]);
+        assert!(disks[0].has_alias() && disks[1].has_alias() && !disks[2].has_alias());
+
+        // The real values are kept.
+        assert_eq!(disks[0].mount_point, "/mnt/sda");
+        assert_eq!(disks[1].name, "sdb");
+
+        // Sorting goes by what is shown.
+        let mut widget =
+            DiskTableWidget::new(&AppConfigFields::default(), &Styles::default(), None);
+        widget.select_column(DiskColumn::Mount);
+        widget.set_table_data(&disks);
+        assert_eq!(names(&widget), ["sdb", "sdc", "sda"]);
     }
 }
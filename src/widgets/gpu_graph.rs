@@ -0,0 +1,15 @@
+use std::time::Instant;
+
+pub struct GpuWidgetState {
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl GpuWidgetState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        GpuWidgetState {
+            current_display_time,
+            autohide_timer,
+        }
+    }
+}
@@ -1,15 +1,67 @@
 use std::time::Instant;
 
+use serde::Deserialize;
+
+/// A series that can be shown in the memory graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "generate_schema",
+    derive(schemars::JsonSchema),
+    schemars(rename_all = "lowercase")
+)]
+pub enum MemorySeries {
+    Ram,
+    Cache,
+    Swap,
+    Arc,
+    /// The ARC's data/metadata split and the L2ARC's size, without graph lines.
+    #[cfg_attr(feature = "generate_schema", schemars(rename = "arc_details"))]
+    ArcDetails,
+    Gpu,
+}
+
+impl<'de> Deserialize<'de> for MemorySeries {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?.to_lowercase();
+        match value.as_str() {
+            "ram" | "memory" => Ok(MemorySeries::Ram),
+            "cache" => Ok(MemorySeries::Cache),
+            "swap" => Ok(MemorySeries::Swap),
+            "arc" => Ok(MemorySeries::Arc),
+            "arc_details" => Ok(MemorySeries::ArcDetails),
+            "gpu" => Ok(MemorySeries::Gpu),
+            _ => Err(serde::de::Error::custom("doesn't match any memory series")),
+        }
+    }
+}
+
+/// The series shown by default, in order.
+pub const DEFAULT_MEMORY_SERIES: [MemorySeries; 5] = [
+    MemorySeries::Ram,
+    MemorySeries::Cache,
+    MemorySeries::Swap,
+    MemorySeries::Arc,
+    MemorySeries::Gpu,
+];
+
 pub struct MemWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
+    /// The series to show, in order. Series without data are skipped.
+    pub series: Vec<MemorySeries>,
 }
 
 impl MemWidgetState {
-    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+    pub fn init(
+        current_display_time: u64, autohide_timer: Option<Instant>, series: Vec<MemorySeries>,
+    ) -> Self {
         MemWidgetState {
             current_display_time,
             autohide_timer,
+            series,
         }
     }
 }
@@ -3,13 +3,18 @@ use std::time::Instant;
 pub struct NetWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
+    /// The only interface to graph, if one was picked in the layout.
+    pub interface: Option<String>,
 }
 
 impl NetWidgetState {
-    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+    pub fn init(
+        current_display_time: u64, autohide_timer: Option<Instant>, interface: Option<String>,
+    ) -> Self {
         NetWidgetState {
             current_display_time,
             autohide_timer,
+            interface,
         }
     }
 }
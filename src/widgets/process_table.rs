@@ -1,28 +1,39 @@
+pub mod custom_column;
 pub mod process_columns;
 pub mod process_data;
 pub mod query;
 mod sort_table;
 
-use std::{borrow::Cow, collections::BTreeMap};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+};
 
+use concat_string::concat_string;
+pub use custom_column::*;
 use hashbrown::{HashMap, HashSet};
 use indexmap::IndexSet;
 use itertools::Itertools;
 pub use process_columns::*;
 pub use process_data::*;
 use query::{parse_query, ProcessQuery};
+use serde::Deserialize;
 use sort_table::SortTableColumn;
+use unicode_segmentation::GraphemeCursor;
 
 use crate::{
     app::{
         data_farmer::{DataCollection, ProcessData},
+        frozen_state::DiffBaseline,
         AppConfigFields, AppSearchState,
     },
     canvas::components::data_table::{
-        Column, ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataTableProps,
-        DataTableStyling, SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+        Column, ColumnHeader, ColumnSeparator, ColumnWidthBounds, DataTable, DataTableColumn,
+        DataTableProps, DataTableStyling, SortColumn, SortDataTable, SortDataTableProps, SortOrder,
+        SortsRow,
     },
-    data_collection::processes::{Pid, ProcessHarvest},
+    data_collection::processes::{Pid, ProcessHarvest, UNKNOWN_NAME},
     options::config::style::Styles,
 };
 
@@ -62,9 +73,68 @@ impl ProcessSearchState {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ProcWidgetMode {
-    Tree { collapsed_pids: HashSet<Pid> },
+    Tree {
+        collapsed_pids: HashSet<Pid>,
+    },
     Grouped,
     Normal,
+    /// One row per user, totalling the usage of their processes.
+    Users,
+}
+
+/// What processes are grouped by in [`ProcWidgetMode::Users`]. Processes with
+/// a known user ID are grouped by it, and the rest by their user's name.
+/// Processes without a known user are all grouped together.
+#[derive(PartialEq, Eq, Hash)]
+enum UserKey<'a> {
+    #[cfg(target_family = "unix")]
+    Uid(libc::uid_t),
+    Name(&'a str),
+    Unknown,
+}
+
+impl<'a> UserKey<'a> {
+    fn of(process: &'a ProcessHarvest) -> Self {
+        let Some(user) = process.known_user() else {
+            return UserKey::Unknown;
+        };
+
+        #[cfg(target_family = "unix")]
+        if let Some(uid) = process.uid {
+            return UserKey::Uid(uid);
+        }
+
+        UserKey::Name(user)
+    }
+}
+
+/// Totals the usage of `processes` per user in a single pass, returning each
+/// user's totals and PIDs. The totals are named after the user, and processes
+/// without a known user are totalled under [`UNKNOWN_NAME`].
+fn aggregate_users<'a>(
+    processes: impl Iterator<Item = &'a ProcessHarvest>,
+) -> Vec<(ProcessHarvest, Vec<Pid>)> {
+    let mut users: HashMap<UserKey<'a>, (ProcessHarvest, Vec<Pid>)> = HashMap::default();
+
+    for process in processes {
+        if let Some((total, pids)) = users.get_mut(&UserKey::of(process)) {
+            total.add(process);
+            pids.push(process.pid);
+        } else {
+            let user = process.known_user().unwrap_or(UNKNOWN_NAME).to_string();
+            let total = ProcessHarvest {
+                pid: process.pid,
+                name: user.clone(),
+                command: user.clone(),
+                user: user.into(),
+                ..process.clone()
+            };
+
+            users.insert(UserKey::of(process), (total, vec![process.pid]));
+        }
+    }
+
+    users.into_values().collect()
 }
 
 type ProcessTable = SortDataTable<ProcWidgetData, ProcColumn>;
@@ -76,6 +146,8 @@ fn make_column(column: ProcColumn) -> SortColumn<ProcColumn> {
 
     match column {
         CpuPercent => SortColumn::new(CpuPercent).default_descending(),
+        CpuHistory => SortColumn::new(CpuHistory).default_descending(),
+        LastSpike => SortColumn::new(LastSpike).default_descending(),
         MemValue => SortColumn::new(MemValue).default_descending(),
         MemPercent => SortColumn::new(MemPercent).default_descending(),
         Pid => SortColumn::new(Pid),
@@ -87,14 +159,17 @@ fn make_column(column: ProcColumn) -> SortColumn<ProcColumn> {
         TotalRead => SortColumn::hard(TotalRead, 8).default_descending(),
         TotalWrite => SortColumn::hard(TotalWrite, 8).default_descending(),
         User => SortColumn::soft(User, Some(0.05)),
+        Group => SortColumn::soft(Group, Some(0.05)),
         State => SortColumn::hard(State, 9),
         Time => SortColumn::new(Time),
+        ExeLocation => SortColumn::soft(ExeLocation, Some(0.1)),
         #[cfg(feature = "gpu")]
         GpuMemValue => SortColumn::new(GpuMemValue).default_descending(),
         #[cfg(feature = "gpu")]
         GpuMemPercent => SortColumn::new(GpuMemPercent).default_descending(),
         #[cfg(feature = "gpu")]
         GpuUtilPercent => SortColumn::new(GpuUtilPercent).default_descending(),
+        Custom(id) => SortColumn::soft(Custom(id), Some(0.15)).default_descending(),
     }
 }
 
@@ -107,24 +182,62 @@ pub struct ProcTableConfig {
     pub is_command: bool,
 }
 
+/// How the process tree is drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum TreeStyle {
+    /// Lines joining each process to its parent, like `├─` and `└─`.
+    #[default]
+    Lines,
+    /// Just indentation, for fonts that draw box characters poorly.
+    Plain,
+}
+
+impl TreeStyle {
+    /// What is drawn right before a process in the tree.
+    fn branch(self, is_last: bool) -> &'static str {
+        match self {
+            TreeStyle::Lines if is_last => "└─ ",
+            TreeStyle::Lines => "├─ ",
+            TreeStyle::Plain => "  ",
+        }
+    }
+
+    /// What is drawn under a process for its children, which lines up with
+    /// [`TreeStyle::branch`].
+    fn continuation(self, is_last: bool) -> &'static str {
+        match self {
+            TreeStyle::Lines if is_last => "   ",
+            TreeStyle::Lines => "│  ",
+            TreeStyle::Plain => "  ",
+        }
+    }
+}
+
 /// A hacky workaround for now.
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 pub enum ProcWidgetColumn {
     PidOrCount,
     ProcNameOrCommand,
     Cpu,
+    CpuHistory,
+    LastSpike,
     Mem,
     ReadPerSecond,
     WritePerSecond,
     TotalRead,
     TotalWrite,
     User,
+    Group,
     State,
     Time,
+    ExeLocation,
     #[cfg(feature = "gpu")]
     GpuMem,
     #[cfg(feature = "gpu")]
     GpuUtil,
+    Custom(usize),
 }
 
 // This is temporary. Switch back to `ProcColumn` later!
@@ -154,6 +267,29 @@ pub struct ProcWidgetState {
     /// The default sort order.
     default_sort_order: SortOrder,
 
+    /// If set, only show processes that last ran on this CPU core.
+    pub core_filter: Option<usize>,
+
+    /// Whether to show byte values in the compact format.
+    compact_bytes: bool,
+
+    /// When grouping, processes are only grouped if there are more than this
+    /// many with the same name.
+    group_threshold: usize,
+
+    /// How the process tree is drawn.
+    tree_style: TreeStyle,
+
+    /// Whether to show how many processes are in each group next to its name.
+    show_group_count: bool,
+
+    /// Whether to show CPU usage out of 100% per core, rather than out of 100%
+    /// across all cores.
+    pub unnormalized_cpu: bool,
+
+    /// Any user-defined columns, which are always shown after the other columns.
+    custom_columns: Arc<[CustomColumn]>,
+
     pub is_sort_open: bool,
     pub force_rerender: bool,
     pub force_update_data: bool,
@@ -170,6 +306,8 @@ impl ProcWidgetState {
             is_basic: false,
             show_table_scroll_position: false,
             show_current_entry_when_unfocused: false,
+            column_separator: ColumnSeparator::default(),
+            show_scrollbar: false,
         };
         let styling = DataTableStyling::from_palette(palette);
 
@@ -187,11 +325,14 @@ impl ProcWidgetState {
             is_basic: config.use_basic_mode,
             show_table_scroll_position: config.show_table_scroll_position,
             show_current_entry_when_unfocused: false,
+            column_separator: config.process_column_separator,
+            show_scrollbar: true,
         };
         let props = SortDataTableProps {
             inner: inner_props,
             sort_index: default_index,
             order: default_order,
+            case_sensitive: config.process_case_sensitive_sort,
         };
         let styling = DataTableStyling::from_palette(colours);
 
@@ -201,6 +342,7 @@ impl ProcWidgetState {
     pub fn new(
         config: &AppConfigFields, mode: ProcWidgetMode, table_config: ProcTableConfig,
         colours: &Styles, config_columns: &Option<IndexSet<ProcWidgetColumn>>,
+        custom_columns: &[CustomColumn],
     ) -> Self {
         let process_search_state = {
             let mut pss = ProcessSearchState::default();
@@ -246,6 +388,8 @@ impl ProcWidgetState {
                                 }
                             }
                             ProcWidgetColumn::Cpu => CpuPercent,
+                            ProcWidgetColumn::CpuHistory => CpuHistory,
+                            ProcWidgetColumn::LastSpike => LastSpike,
                             ProcWidgetColumn::Mem => {
                                 if mem_as_values {
                                     MemValue
@@ -258,8 +402,10 @@ impl ProcWidgetState {
                             ProcWidgetColumn::TotalRead => TotalRead,
                             ProcWidgetColumn::TotalWrite => TotalWrite,
                             ProcWidgetColumn::User => User,
+                            ProcWidgetColumn::Group => Group,
                             ProcWidgetColumn::State => State,
                             ProcWidgetColumn::Time => Time,
+                            ProcWidgetColumn::ExeLocation => ExeLocation,
                             #[cfg(feature = "gpu")]
                             ProcWidgetColumn::GpuMem => {
                                 if mem_as_values {
@@ -270,6 +416,9 @@ impl ProcWidgetState {
                             }
                             #[cfg(feature = "gpu")]
                             ProcWidgetColumn::GpuUtil => GpuUtilPercent,
+                            ProcWidgetColumn::Custom(index) => {
+                                Custom(custom_columns[*index].id(*index))
+                            }
                         };
 
                         make_column(col)
@@ -294,6 +443,15 @@ impl ProcWidgetState {
                 }
             }
         };
+        let columns = columns
+            .into_iter()
+            .chain(
+                custom_columns
+                    .iter()
+                    .enumerate()
+                    .map(|(index, column)| make_column(ProcColumn::Custom(column.id(index)))),
+            )
+            .collect_vec();
 
         let column_mapping = columns
             .iter()
@@ -302,6 +460,8 @@ impl ProcWidgetState {
 
                 match col.inner() {
                     CpuPercent => ProcWidgetColumn::Cpu,
+                    CpuHistory => ProcWidgetColumn::CpuHistory,
+                    LastSpike => ProcWidgetColumn::LastSpike,
                     MemValue | MemPercent => ProcWidgetColumn::Mem,
                     Pid | Count => ProcWidgetColumn::PidOrCount,
                     Name | Command => ProcWidgetColumn::ProcNameOrCommand,
@@ -311,11 +471,14 @@ impl ProcWidgetState {
                     TotalWrite => ProcWidgetColumn::TotalWrite,
                     State => ProcWidgetColumn::State,
                     User => ProcWidgetColumn::User,
+                    Group => ProcWidgetColumn::Group,
                     Time => ProcWidgetColumn::Time,
+                    ExeLocation => ProcWidgetColumn::ExeLocation,
                     #[cfg(feature = "gpu")]
                     GpuMemValue | GpuMemPercent => ProcWidgetColumn::GpuMem,
                     #[cfg(feature = "gpu")]
                     GpuUtilPercent => ProcWidgetColumn::GpuUtil,
+                    Custom(id) => ProcWidgetColumn::Custom(id.index),
                 }
             })
             .collect::<IndexSet<_>>();
@@ -350,6 +513,13 @@ impl ProcWidgetState {
             sort_table,
             id_pid_map,
             column_mapping,
+            core_filter: None,
+            compact_bytes: config.compact_bytes,
+            tree_style: config.process_tree_style,
+            show_group_count: config.process_show_group_count,
+            group_threshold: config.process_group_threshold,
+            unnormalized_cpu: config.unnormalized_cpu,
+            custom_columns: custom_columns.into(),
             is_sort_open: false,
             mode,
             force_rerender: true,
@@ -382,6 +552,109 @@ impl ProcWidgetState {
             .unwrap_or(false)
     }
 
+    /// Returns a function to look up the CPU history of a process, if the CPU
+    /// history column is being shown.
+    fn cpu_history_getter<'a>(
+        &self, process_data: &'a ProcessData,
+    ) -> impl Fn(Pid) -> Option<&'a VecDeque<f32>> {
+        let is_showing_history = self.column_mapping.contains(&ProcWidgetColumn::CpuHistory);
+
+        move |pid| {
+            if is_showing_history {
+                process_data.cpu_history.get(&pid)
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns a function to compute the custom column values of a process. Only
+    /// columns that are visible or being sorted by are computed.
+    fn custom_values_getter(&self) -> impl Fn(&ProcessHarvest) -> Vec<Option<f64>> {
+        let sort_index = self.table.sort_index();
+        let is_needed = self
+            .table
+            .columns
+            .iter()
+            .enumerate()
+            .filter_map(|(index, column)| match column.inner() {
+                ProcColumn::Custom(id) => {
+                    Some((id.index, !column.is_hidden || index == sort_index))
+                }
+                _ => None,
+            })
+            .filter(|(_, is_needed)| *is_needed)
+            .map(|(index, _)| index)
+            .collect::<HashSet<_>>();
+        let custom_columns = self.custom_columns.clone();
+
+        move |process| {
+            if is_needed.is_empty() {
+                Vec::default()
+            } else {
+                custom_columns
+                    .iter()
+                    .enumerate()
+                    .map(|(index, column)| {
+                        if is_needed.contains(&index) {
+                            column.eval(process)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns a function to turn a process into a row, with the CPU history
+    /// and latest spike of each of the given pids, such as those of a group.
+    fn widget_data_getter<'a>(
+        &self, process_data: &'a ProcessData,
+    ) -> impl Fn(&ProcessHarvest, &[Pid]) -> ProcWidgetData + 'a {
+        let is_using_command = self.is_using_command();
+        let is_mem_percent = self.is_mem_percent();
+        let compact_bytes = self.compact_bytes;
+        let unnormalized_cpu = self.unnormalized_cpu;
+        let cpu_history = self.cpu_history_getter(process_data);
+        let custom_values = self.custom_values_getter();
+
+        move |process, pids| {
+            pids.iter().fold(
+                ProcWidgetData::from_data(process, is_using_command, is_mem_percent)
+                    .compact_bytes(compact_bytes)
+                    .custom_values(custom_values(process)),
+                |data, pid| {
+                    data.cpu_history(cpu_history(*pid))
+                        .spiked_ago(process_data.spiked_ago(*pid, unnormalized_cpu))
+                },
+            )
+        }
+    }
+
+    /// Sets the core used to filter processes, or clears it if `None`.
+    pub fn set_core_filter(&mut self, core: Option<usize>) {
+        if self.core_filter != core {
+            self.core_filter = core;
+            self.table.props.title = Some(match core {
+                Some(core) => format!(" Processes (CPU{core}) ").into(),
+                None => " Processes ".into(),
+            });
+            self.force_data_update();
+        }
+    }
+
+    /// Returns a function that checks whether a process passes the core filter.
+    /// Processes that don't report the core they last ran on are always kept.
+    fn core_filter_check(&self) -> impl Fn(&ProcessHarvest) -> bool {
+        let core_filter = self.core_filter;
+
+        move |process| match (core_filter, process.last_cpu) {
+            (Some(core), Some(last_cpu)) => core == last_cpu,
+            _ => true,
+        }
+    }
+
     fn get_query(&self) -> &Option<ProcessQuery> {
         if self.proc_search.search_state.is_invalid_or_blank_search() {
             &None
@@ -395,29 +668,42 @@ impl ProcWidgetState {
     /// This function *only* updates the displayed process data. If there is a
     /// need to update the actual *stored* data, call it before this
     /// function.
-    pub fn set_table_data(&mut self, data_collection: &DataCollection) {
-        let data = match &self.mode {
-            ProcWidgetMode::Grouped | ProcWidgetMode::Normal => {
-                self.get_normal_data(&data_collection.process_data.process_harvest)
+    ///
+    /// If `diff_baseline` is set, processes are compared against it. This is
+    /// only done when showing each process on its own row, not when grouped or
+    /// in a tree.
+    pub fn set_table_data(
+        &mut self, data_collection: &DataCollection, diff_baseline: Option<&DiffBaseline>,
+    ) {
+        let mut data = match &self.mode {
+            ProcWidgetMode::Grouped | ProcWidgetMode::Normal | ProcWidgetMode::Users => {
+                self.get_normal_data(&data_collection.process_data, diff_baseline)
             }
             ProcWidgetMode::Tree { collapsed_pids } => {
                 self.get_tree_data(collapsed_pids, data_collection)
             }
         };
+
+        // Normalizing is just a division by the number of cores, so it can be
+        // switched without waiting for new data.
+        let cpu_scale = data_collection
+            .process_data
+            .cpu_usage_scale(self.unnormalized_cpu);
+        if cpu_scale != 1.0 {
+            data.iter_mut()
+                .for_each(|process| process.scale_cpu_usage(cpu_scale));
+        }
+
         self.table.set_data(data);
     }
 
     fn get_tree_data(
         &self, collapsed_pids: &HashSet<Pid>, data_collection: &DataCollection,
     ) -> Vec<ProcWidgetData> {
-        const BRANCH_END: char = '└';
-        const BRANCH_SPLIT: char = '├';
-        const BRANCH_HORIZONTAL: char = '─';
-        const SPACED_BRANCH_VERTICAL: &str = "│  ";
-
         let search_query = self.get_query();
         let is_using_command = self.is_using_command();
-        let is_mem_percent = self.is_mem_percent();
+        let is_on_core = self.core_filter_check();
+        let widget_data = self.widget_data_getter(&data_collection.process_data);
 
         let ProcessData {
             process_harvest,
@@ -432,10 +718,19 @@ impl ProcWidgetState {
             .process_harvest
             .iter()
             .filter_map(|(pid, process)| {
-                if search_query
-                    .as_ref()
-                    .map(|q| q.check(process, is_using_command))
-                    .unwrap_or(true)
+                if is_on_core(process)
+                    && search_query
+                        .as_ref()
+                        .map(|q| {
+                            q.check(
+                                process,
+                                is_using_command,
+                                data_collection
+                                    .process_data
+                                    .spiked_ago(process.pid, self.unnormalized_cpu),
+                            )
+                        })
+                        .unwrap_or(true)
                 {
                     Some(*pid)
                 } else {
@@ -449,17 +744,25 @@ impl ProcWidgetState {
             current_process: &ProcessHarvest, kept_pids: &HashSet<Pid>,
             process_harvest: &BTreeMap<Pid, ProcessHarvest>,
         ) -> bool {
-            if let Some(ppid) = current_process.parent_pid {
+            let mut current_process = current_process;
+
+            // This is bounded by the number of processes in case the parent mappings
+            // somehow form a cycle.
+            for _ in 0..process_harvest.len() {
+                let Some(ppid) = current_process.parent_pid else {
+                    return false;
+                };
+
                 if kept_pids.contains(&ppid) {
-                    true
+                    return true;
                 } else if let Some(parent) = process_harvest.get(&ppid) {
-                    is_ancestor_shown(parent, kept_pids, process_harvest)
+                    current_process = parent;
                 } else {
-                    false
+                    return false;
                 }
-            } else {
-                false
             }
+
+            false
         }
 
         // A process is shown under the filtered tree if at least one of these
@@ -471,20 +774,25 @@ impl ProcWidgetState {
             let mut filtered_tree: HashMap<Pid, Vec<Pid>> = HashMap::default();
 
             // We do a simple DFS traversal to build our filtered parent-to-tree mappings.
+            // Children are only expanded once and never revisited, so a corrupted parent
+            // mapping with a cycle can't loop forever.
             let mut visited_pids: HashMap<Pid, bool> = HashMap::default();
+            let mut expanded_pids: HashSet<Pid> = HashSet::default();
             let mut stack = orphan_pids
                 .iter()
                 .filter_map(|process| process_harvest.get(process))
                 .collect_vec();
 
             while let Some(process) = stack.last() {
+                if visited_pids.contains_key(&process.pid) {
+                    stack.pop();
+                    continue;
+                }
+
                 let is_process_matching = kept_pids.contains(&process.pid);
 
                 if let Some(children_pids) = process_parent_mapping.get(&process.pid) {
-                    if children_pids
-                        .iter()
-                        .all(|pid| visited_pids.contains_key(pid))
-                    {
+                    if !expanded_pids.insert(process.pid) {
                         let shown_children = children_pids
                             .iter()
                             .filter(|pid| visited_pids.get(*pid).copied().unwrap_or(false))
@@ -516,6 +824,9 @@ impl ProcWidgetState {
                     } else {
                         children_pids
                             .iter()
+                            .filter(|pid| {
+                                !visited_pids.contains_key(*pid) && !expanded_pids.contains(*pid)
+                            })
                             .filter_map(|process| process_harvest.get(process))
                             .rev()
                             .for_each(|process| {
@@ -544,9 +855,9 @@ impl ProcWidgetState {
             .iter()
             .filter_map(|pid| {
                 if filtered_tree.contains_key(pid) {
-                    process_harvest.get(pid).map(|process| {
-                        ProcWidgetData::from_data(process, is_using_command, is_mem_percent)
-                    })
+                    process_harvest
+                        .get(pid)
+                        .map(|process| widget_data(process, &[process.pid]))
                 } else {
                     None
                 }
@@ -556,7 +867,12 @@ impl ProcWidgetState {
         stack.sort_unstable_by_key(|p| p.pid);
 
         let column = self.table.columns.get(self.table.sort_index()).unwrap();
-        sort_skip_pid_asc(column.inner(), &mut stack, self.table.order());
+        sort_skip_pid_asc(
+            column.inner(),
+            &mut stack,
+            self.table.order(),
+            self.table.is_case_sensitive(),
+        );
 
         let mut length_stack = vec![stack.len()];
         stack.reverse();
@@ -570,37 +886,33 @@ impl ProcWidgetState {
             if collapsed_pids.contains(&process.pid) {
                 let mut summed_process = process.clone();
 
-                if let Some(children_pids) = filtered_tree.get(&process.pid) {
-                    let mut sum_queue = children_pids
-                        .iter()
-                        .filter_map(|child| {
-                            process_harvest.get(child).map(|p| {
-                                ProcWidgetData::from_data(p, is_using_command, is_mem_percent)
-                            })
-                        })
-                        .collect_vec();
+                // Sum up all descendants. We track what has been summed to avoid double
+                // counting (or looping forever) if the tree is somehow malformed.
+                let mut summed_pids: HashSet<Pid> = HashSet::from_iter([process.pid]);
+                let mut sum_queue = filtered_tree.get(&process.pid).cloned().unwrap_or_default();
+
+                while let Some(pid) = sum_queue.pop() {
+                    if !summed_pids.insert(pid) {
+                        continue;
+                    }
 
-                    while let Some(process) = sum_queue.pop() {
-                        summed_process.add(&process);
+                    if let Some(p) = process_harvest.get(&pid) {
+                        summed_process.add(&widget_data(p, &[p.pid]));
+                    }
 
-                        if let Some(pids) = filtered_tree.get(&process.pid) {
-                            sum_queue.extend(pids.iter().filter_map(|child| {
-                                process_harvest.get(child).map(|p| {
-                                    ProcWidgetData::from_data(p, is_using_command, is_mem_percent)
-                                })
-                            }));
-                        }
+                    if let Some(children_pids) = filtered_tree.get(&pid) {
+                        sum_queue.extend(children_pids);
                     }
                 }
 
+                let hidden_count = summed_pids.len() - 1;
                 let prefix = if prefixes.is_empty() {
-                    "+ ".to_string()
+                    format!("(+{hidden_count}) ")
                 } else {
                     format!(
-                        "{}{}{} + ",
+                        "{}{}(+{hidden_count}) ",
                         prefixes.join(""),
-                        if is_last { BRANCH_END } else { BRANCH_SPLIT },
-                        BRANCH_HORIZONTAL
+                        self.tree_style.branch(is_last)
                     )
                 };
 
@@ -609,12 +921,7 @@ impl ProcWidgetState {
                 let prefix = if prefixes.is_empty() {
                     String::default()
                 } else {
-                    format!(
-                        "{}{}{} ",
-                        prefixes.join(""),
-                        if is_last { BRANCH_END } else { BRANCH_SPLIT },
-                        BRANCH_HORIZONTAL
-                    )
+                    concat_string!(prefixes.join(""), self.tree_style.branch(is_last))
                 };
                 let pid = process.pid;
                 data.push(process.prefix(Some(prefix)).disabled(disabled));
@@ -623,23 +930,23 @@ impl ProcWidgetState {
                     if prefixes.is_empty() {
                         prefixes.push("");
                     } else {
-                        prefixes.push(if is_last {
-                            "   "
-                        } else {
-                            SPACED_BRANCH_VERTICAL
-                        });
+                        prefixes.push(self.tree_style.continuation(is_last));
                     }
 
                     let mut children = children_pids
                         .iter()
                         .filter_map(|child_pid| {
-                            process_harvest.get(child_pid).map(|p| {
-                                ProcWidgetData::from_data(p, is_using_command, is_mem_percent)
-                            })
+                            process_harvest
+                                .get(child_pid)
+                                .map(|p| widget_data(p, &[p.pid]))
                         })
                         .collect_vec();
 
-                    column.sort_by(&mut children, self.table.order().rev());
+                    column.sort_by(
+                        &mut children,
+                        self.table.order().rev(),
+                        self.table.is_case_sensitive(),
+                    );
 
                     length_stack.push(children.len());
                     stack.extend(children);
@@ -660,18 +967,28 @@ impl ProcWidgetState {
     }
 
     fn get_normal_data(
-        &mut self, process_harvest: &BTreeMap<Pid, ProcessHarvest>,
+        &mut self, process_data: &ProcessData, diff_baseline: Option<&DiffBaseline>,
     ) -> Vec<ProcWidgetData> {
         let search_query = self.get_query();
         let is_using_command = self.is_using_command();
-        let is_mem_percent = self.is_mem_percent();
+        let is_on_core = self.core_filter_check();
+        let widget_data = self.widget_data_getter(process_data);
+        let process_harvest = &process_data.process_harvest;
 
-        let filtered_iter = process_harvest.values().filter(|process| {
-            search_query
-                .as_ref()
-                .map(|query| query.check(process, is_using_command))
-                .unwrap_or(true)
-        });
+        let is_shown = |process: &&ProcessHarvest| {
+            is_on_core(process)
+                && search_query
+                    .as_ref()
+                    .map(|query| {
+                        query.check(
+                            process,
+                            is_using_command,
+                            process_data.spiked_ago(process.pid, self.unnormalized_cpu),
+                        )
+                    })
+                    .unwrap_or(true)
+        };
+        let filtered_iter = process_harvest.values().filter(is_shown);
 
         let mut id_pid_map: HashMap<String, Vec<Pid>> = HashMap::default();
         let mut filtered_data: Vec<ProcWidgetData> = if let ProcWidgetMode::Grouped = self.mode {
@@ -699,31 +1016,74 @@ impl ProcWidgetState {
                 }
             }
 
-            id_process_mapping
-                .values()
-                .map(|process| {
-                    let id = if is_using_command {
-                        &process.command
-                    } else {
-                        &process.name
-                    };
+            let mut data = Vec::with_capacity(id_process_mapping.len());
+            for process in id_process_mapping.values() {
+                let id = if is_using_command {
+                    &process.command
+                } else {
+                    &process.name
+                };
+
+                let pids = id_pid_map.get(id).map(Vec::as_slice).unwrap_or_default();
+                if pids.len() > self.group_threshold {
+                    data.push(
+                        widget_data(process, pids)
+                            .num_similar(pids.len() as u64)
+                            .show_num_similar(self.show_group_count),
+                    );
+                } else {
+                    // Too few to be worth grouping, so show each one on its own.
+                    data.extend(pids.iter().filter_map(|pid| {
+                        process_harvest
+                            .get(pid)
+                            .map(|process| widget_data(process, &[*pid]))
+                    }));
+                }
+            }
 
-                    let num_similar = id_pid_map.get(id).map(|val| val.len()).unwrap_or(1) as u64;
+            // Processes that aren't grouped are killed on their own.
+            let group_threshold = self.group_threshold;
+            id_pid_map.retain(|_, pids| pids.len() > group_threshold);
 
-                    ProcWidgetData::from_data(process, is_using_command, is_mem_percent)
-                        .num_similar(num_similar)
-                })
+            data
+        } else if let ProcWidgetMode::Users = self.mode {
+            aggregate_users(filtered_iter)
+                .into_iter()
+                .map(|(process, pids)| widget_data(&process, &pids).num_similar(pids.len() as u64))
                 .collect()
         } else {
-            filtered_iter
-                .map(|process| ProcWidgetData::from_data(process, is_using_command, is_mem_percent))
-                .collect()
+            let mut data = filtered_iter
+                .map(|process| {
+                    let data = widget_data(process, &[process.pid]);
+
+                    match diff_baseline {
+                        Some(baseline) => data.diff_from(baseline.matching(process)),
+                        None => data,
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            if let Some(baseline) = diff_baseline {
+                data.extend(
+                    baseline
+                        .recently_exited()
+                        .filter(is_shown)
+                        .map(|process| widget_data(process, &[]).exited()),
+                );
+            }
+
+            data
         };
 
         self.id_pid_map = id_pid_map;
 
         if let Some(column) = self.table.columns.get(self.table.sort_index()) {
-            sort_skip_pid_asc(column.inner(), &mut filtered_data, self.table.order());
+            sort_skip_pid_asc(
+                column.inner(),
+                &mut filtered_data,
+                self.table.order(),
+                self.table.is_case_sensitive(),
+            );
         }
 
         filtered_data
@@ -848,7 +1208,9 @@ impl ProcWidgetState {
                         if let ColumnWidthBounds::Soft { max_percentage, .. } = col.bounds_mut() {
                             *max_percentage = match self.mode {
                                 ProcWidgetMode::Tree { .. } => Some(0.5),
-                                ProcWidgetMode::Grouped | ProcWidgetMode::Normal => Some(0.3),
+                                ProcWidgetMode::Grouped
+                                | ProcWidgetMode::Normal
+                                | ProcWidgetMode::Users => Some(0.3),
                             };
                         }
                     }
@@ -863,13 +1225,13 @@ impl ProcWidgetState {
     /// Toggles the appropriate columns/settings when tab is pressed.
     ///
     /// If count is enabled, we should set the mode to
-    /// [`ProcWidgetMode::Grouped`], and switch off the User and State
+    /// [`ProcWidgetMode::Grouped`], and switch off the User, Group, and State
     /// columns. We should also move the user off of the columns if they were
     /// selected, as those columns are now hidden (handled by internal
     /// method calls), and go back to the "defaults".
     ///
-    /// Otherwise, if count is disabled, then if the columns exist, the User and
-    /// State columns should be re-enabled, and the mode switched to
+    /// Otherwise, if count is disabled, then if the columns exist, the User,
+    /// Group, and State columns should be re-enabled, and the mode switched to
     /// [`ProcWidgetMode::Normal`].
     pub fn toggle_tab(&mut self) {
         if !matches!(self.mode, ProcWidgetMode::Tree { .. }) {
@@ -885,6 +1247,7 @@ impl ProcWidgetState {
                             sort_col.default_order = SortOrder::Descending;
 
                             self.hide_column(ProcWidgetColumn::User);
+                            self.hide_column(ProcWidgetColumn::Group);
                             self.hide_column(ProcWidgetColumn::State);
                             self.mode = ProcWidgetMode::Grouped;
                         }
@@ -893,6 +1256,7 @@ impl ProcWidgetState {
                             sort_col.default_order = SortOrder::Ascending;
 
                             self.show_column(ProcWidgetColumn::User);
+                            self.show_column(ProcWidgetColumn::Group);
                             self.show_column(ProcWidgetColumn::State);
                             self.mode = ProcWidgetMode::Normal;
                         }
@@ -906,6 +1270,63 @@ impl ProcWidgetState {
         }
     }
 
+    /// Toggles between showing CPU usage out of 100% per core and out of 100%
+    /// across all cores.
+    pub fn toggle_cpu_normalization(&mut self) {
+        self.unnormalized_cpu = !self.unnormalized_cpu;
+        self.force_data_update();
+    }
+
+    /// Toggles between [`ProcWidgetMode::Users`] and [`ProcWidgetMode::Normal`],
+    /// hiding and showing the same columns as grouping does. This does nothing
+    /// in tree mode.
+    pub fn toggle_users(&mut self) {
+        match self.mode {
+            ProcWidgetMode::Tree { .. } => {}
+            ProcWidgetMode::Users => self.toggle_tab(),
+            ProcWidgetMode::Normal => {
+                self.toggle_tab();
+                if let ProcWidgetMode::Grouped = self.mode {
+                    self.mode = ProcWidgetMode::Users;
+                }
+            }
+            ProcWidgetMode::Grouped => {
+                self.mode = ProcWidgetMode::Users;
+                self.force_rerender_and_update();
+            }
+        }
+    }
+
+    /// Returns the user of the selected row in [`ProcWidgetMode::Users`], if
+    /// it is a known user.
+    pub fn selected_user(&self) -> Option<&str> {
+        match self.mode {
+            ProcWidgetMode::Users => self
+                .table
+                .current_item()
+                .map(|data| data.user.as_str())
+                .filter(|user| *user != UNKNOWN_NAME),
+            _ => None,
+        }
+    }
+
+    /// Leaves [`ProcWidgetMode::Users`] and searches for the processes of
+    /// `user`, replacing the current search.
+    pub fn search_user(&mut self, user: &str) {
+        if let ProcWidgetMode::Users = self.mode {
+            self.toggle_users();
+        }
+
+        let search_state = &mut self.proc_search.search_state;
+        search_state.reset();
+        search_state.current_search_query = format!("user:\"{user}\"");
+        let len = search_state.current_search_query.len();
+        search_state.grapheme_cursor = GraphemeCursor::new(len, len, true);
+        search_state.is_enabled = true;
+        self.update_query();
+        self.force_data_update();
+    }
+
     pub fn column_text(&self) -> Vec<Cow<'static, str>> {
         self.table
             .columns
@@ -1010,22 +1431,24 @@ impl ProcWidgetState {
 }
 
 #[inline]
-fn sort_skip_pid_asc(column: &ProcColumn, data: &mut [ProcWidgetData], order: SortOrder) {
+fn sort_skip_pid_asc(
+    column: &ProcColumn, data: &mut [ProcWidgetData], order: SortOrder, case_sensitive: bool,
+) {
     let descending = matches!(order, SortOrder::Descending);
     match column {
         ProcColumn::Pid if !descending => {}
         _ => {
-            column.sort_data(data, descending);
+            column.sort_data(data, descending, case_sensitive);
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use std::time::Duration;
+    use std::{num::NonZeroU16, time::Duration};
 
     use super::*;
-    use crate::widgets::MemUsage;
+    use crate::{canvas::components::data_table::DataToCell, widgets::MemUsage};
 
     #[test]
     fn test_proc_sort() {
@@ -1034,24 +1457,33 @@ mod test {
             ppid: None,
             id: "A".into(),
             cpu_usage_percent: 0.0,
+            cpu_history: vec![],
+            spiked_ago: None,
             mem_usage: MemUsage::Percent(1.1),
+            compact_bytes: false,
             rps: 0,
             wps: 0,
             total_read: 0,
             total_write: 0,
+            io_unknown: false,
             process_state: "N/A".to_string(),
             process_char: '?',
             #[cfg(target_family = "unix")]
             user: "root".to_string(),
             #[cfg(not(target_family = "unix"))]
             user: "N/A".to_string(),
+            group: "N/A".to_string(),
             num_similar: 0,
+            show_num_similar: false,
             disabled: false,
             time: Duration::from_secs(0),
+            exe_location: "N/A".into(),
             #[cfg(feature = "gpu")]
-            gpu_mem_usage: MemUsage::Percent(1.1),
+            gpu_mem_usage: Some(MemUsage::Percent(1.1)),
             #[cfg(feature = "gpu")]
-            gpu_usage: 0,
+            gpu_usage: Some(0),
+            custom_values: vec![],
+            diff: None,
         };
 
         let b = ProcWidgetData {
@@ -1084,7 +1516,12 @@ mod test {
 
         // Assume we had sorted over by pid.
         data.sort_by_key(|p| p.pid);
-        sort_skip_pid_asc(&ProcColumn::CpuPercent, &mut data, SortOrder::Descending);
+        sort_skip_pid_asc(
+            &ProcColumn::CpuPercent,
+            &mut data,
+            SortOrder::Descending,
+            false,
+        );
         assert_eq!(
             [&c, &b, &a, &d].iter().map(|d| (d.pid)).collect::<Vec<_>>(),
             data.iter().map(|d| (d.pid)).collect::<Vec<_>>(),
@@ -1092,14 +1529,24 @@ mod test {
 
         // Note that the PID ordering for ties is still ascending.
         data.sort_by_key(|p| p.pid);
-        sort_skip_pid_asc(&ProcColumn::CpuPercent, &mut data, SortOrder::Ascending);
+        sort_skip_pid_asc(
+            &ProcColumn::CpuPercent,
+            &mut data,
+            SortOrder::Ascending,
+            false,
+        );
         assert_eq!(
             [&a, &d, &b, &c].iter().map(|d| (d.pid)).collect::<Vec<_>>(),
             data.iter().map(|d| (d.pid)).collect::<Vec<_>>(),
         );
 
         data.sort_by_key(|p| p.pid);
-        sort_skip_pid_asc(&ProcColumn::MemPercent, &mut data, SortOrder::Descending);
+        sort_skip_pid_asc(
+            &ProcColumn::MemPercent,
+            &mut data,
+            SortOrder::Descending,
+            false,
+        );
         assert_eq!(
             [&b, &a, &c, &d].iter().map(|d| (d.pid)).collect::<Vec<_>>(),
             data.iter().map(|d| (d.pid)).collect::<Vec<_>>(),
@@ -1107,7 +1554,12 @@ mod test {
 
         // Note that the PID ordering for ties is still ascending.
         data.sort_by_key(|p| p.pid);
-        sort_skip_pid_asc(&ProcColumn::MemPercent, &mut data, SortOrder::Ascending);
+        sort_skip_pid_asc(
+            &ProcColumn::MemPercent,
+            &mut data,
+            SortOrder::Ascending,
+            false,
+        );
         assert_eq!(
             [&c, &d, &a, &b].iter().map(|d| (d.pid)).collect::<Vec<_>>(),
             data.iter().map(|d| (d.pid)).collect::<Vec<_>>(),
@@ -1139,9 +1591,37 @@ mod test {
             table_config,
             &styling,
             &columns,
+            &[],
         )
     }
 
+    /// Returns data with the given processes, where `children` lists the
+    /// children of each process in the tree. Processes that aren't anyone's
+    /// child are the roots.
+    fn process_data(
+        processes: impl IntoIterator<Item = ProcessHarvest>, children: &[(Pid, &[Pid])],
+    ) -> ProcessData {
+        let process_harvest: BTreeMap<Pid, ProcessHarvest> = processes
+            .into_iter()
+            .map(|process| (process.pid, process))
+            .collect();
+        let orphan_pids = process_harvest
+            .keys()
+            .copied()
+            .filter(|pid| !children.iter().any(|(_, children)| children.contains(pid)))
+            .collect();
+
+        ProcessData {
+            process_harvest,
+            process_parent_mapping: children
+                .iter()
+                .map(|(pid, children)| (*pid, children.to_vec()))
+                .collect(),
+            orphan_pids,
+            ..Default::default()
+        }
+    }
+
     fn init_default_state(columns: &[ProcWidgetColumn]) -> ProcWidgetState {
         init_state(ProcTableConfig::default(), columns)
     }
@@ -1494,4 +1974,639 @@ mod test {
         state.toggle_command();
         assert_eq!(get_columns(&state.table), original_columns);
     }
+
+    #[test]
+    fn core_filter() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+        ];
+        let mut state = init_default_state(&init_columns);
+
+        let process_data = process_data(
+            [(1, Some(0)), (2, Some(1)), (3, None)].map(|(pid, last_cpu)| ProcessHarvest {
+                pid,
+                last_cpu,
+                ..Default::default()
+            }),
+            &[],
+        );
+        let shown_pids = |state: &mut ProcWidgetState| {
+            state
+                .get_normal_data(&process_data, None)
+                .iter()
+                .map(|data| data.pid)
+                .sorted()
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(shown_pids(&mut state), vec![1, 2, 3]);
+
+        state.set_core_filter(Some(1));
+        assert!(state.force_update_data);
+        assert_eq!(
+            state.table.props.title.as_deref(),
+            Some(" Processes (CPU1) ")
+        );
+        assert_eq!(shown_pids(&mut state), vec![2, 3]);
+
+        state.set_core_filter(None);
+        assert_eq!(state.table.props.title.as_deref(), Some(" Processes "));
+        assert_eq!(shown_pids(&mut state), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn cpu_normalization_toggle() {
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+        ]);
+
+        let process_data = ProcessData {
+            unnormalized_cpu: false,
+            num_cpus: 8,
+            ..process_data(
+                [ProcessHarvest {
+                    pid: 1,
+                    name: "p1".into(),
+                    cpu_usage_percent: 12.5,
+                    ..Default::default()
+                }],
+                &[],
+            )
+        };
+        let data_collection = DataCollection {
+            process_data,
+            ..Default::default()
+        };
+        let cpu_usage = |state: &ProcWidgetState| state.table.data()[0].cpu_usage_percent;
+
+        assert!(!state.unnormalized_cpu);
+        state.set_table_data(&data_collection, None);
+        assert_eq!(cpu_usage(&state), 12.5);
+
+        // The same harvest, but out of 100% per core.
+        state.toggle_cpu_normalization();
+        assert!(state.force_update_data);
+        state.set_table_data(&data_collection, None);
+        assert_eq!(cpu_usage(&state), 100.0);
+
+        state.toggle_cpu_normalization();
+        state.set_table_data(&data_collection, None);
+        assert_eq!(cpu_usage(&state), 12.5);
+    }
+
+    #[test]
+    fn users_mode() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::User,
+        ];
+        let mut state = init_default_state(&init_columns);
+
+        let processes = [
+            (1, 0, "root", 1.0),
+            (2, 1000, "alice", 2.0),
+            (3, 1000, "alice", 3.0),
+            (4, 1001, "bob", 0.0),
+            (5, 1002, "", 4.0),
+            (6, 1003, "N/A", 1.0),
+            (7, 2000, "alice2", 1.0),
+        ];
+        let process_data = process_data(
+            processes.map(|(pid, _uid, user, cpu_usage_percent)| ProcessHarvest {
+                pid,
+                name: format!("p{pid}"),
+                #[cfg(target_family = "unix")]
+                uid: Some(_uid),
+                user: user.into(),
+                cpu_usage_percent,
+                ..Default::default()
+            }),
+            &[],
+        );
+        let data_collection = DataCollection {
+            process_data,
+            ..Default::default()
+        };
+
+        state.toggle_users();
+        assert_eq!(state.mode, ProcWidgetMode::Users);
+        assert_eq!(
+            get_columns(&state.table),
+            vec![ProcColumn::Count, ProcColumn::Name, ProcColumn::CpuPercent]
+        );
+
+        state.set_table_data(&data_collection, None);
+        let users = state
+            .table
+            .data()
+            .iter()
+            .map(|data| {
+                (
+                    data.id.to_string(),
+                    data.num_similar,
+                    data.cpu_usage_percent,
+                )
+            })
+            .sorted_by(|a, b| a.0.cmp(&b.0))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            users,
+            vec![
+                ("N/A".to_string(), 2, 5.0),
+                ("alice".to_string(), 2, 5.0),
+                ("alice2".to_string(), 1, 1.0),
+                ("bob".to_string(), 1, 0.0),
+                ("root".to_string(), 1, 1.0),
+            ]
+        );
+
+        // Searching for a user goes back to the normal table.
+        state.search_user("alice");
+        assert_eq!(state.mode, ProcWidgetMode::Normal);
+        assert_eq!(state.current_search_query(), "user:\"alice\"");
+        assert!(state.is_search_enabled());
+        assert_eq!(
+            get_columns(&state.table),
+            vec![
+                ProcColumn::Pid,
+                ProcColumn::Name,
+                ProcColumn::CpuPercent,
+                ProcColumn::User
+            ]
+        );
+
+        state.set_table_data(&data_collection, None);
+        assert_eq!(
+            state
+                .table
+                .data()
+                .iter()
+                .map(|data| data.pid)
+                .sorted()
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+
+        // Grouping can switch straight to the users view and back.
+        state.toggle_tab();
+        state.toggle_users();
+        assert_eq!(state.mode, ProcWidgetMode::Users);
+        state.toggle_tab();
+        assert_eq!(state.mode, ProcWidgetMode::Normal);
+        assert_eq!(state.selected_user(), None);
+    }
+
+    #[test]
+    fn group_count_in_name() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+        ];
+        let mut state = init_default_state(&init_columns);
+
+        let process_data = process_data(
+            [(1, "chrome"), (2, "bash"), (3, "chrome"), (4, "chrome")].map(|(pid, name)| {
+                ProcessHarvest {
+                    pid,
+                    name: name.into(),
+                    ..Default::default()
+                }
+            }),
+            &[],
+        );
+        let data_collection = DataCollection {
+            process_data,
+            ..Default::default()
+        };
+
+        let names = |state: &ProcWidgetState| {
+            state
+                .table
+                .data()
+                .iter()
+                .map(|data| {
+                    data.to_cell(&ProcColumn::Name, NonZeroU16::new(20).unwrap())
+                        .unwrap()
+                        .into_owned()
+                })
+                .sorted()
+                .collect::<Vec<_>>()
+        };
+
+        state.toggle_tab();
+        state.show_group_count = true;
+        state.set_table_data(&data_collection, None);
+        assert_eq!(names(&state), vec!["bash (1)", "chrome (3)"]);
+
+        state.show_group_count = false;
+        state.set_table_data(&data_collection, None);
+        assert_eq!(names(&state), vec!["bash", "chrome"]);
+
+        // Ungrouped processes never show a count.
+        state.show_group_count = true;
+        state.toggle_tab();
+        state.set_table_data(&data_collection, None);
+        assert_eq!(names(&state), vec!["bash", "chrome", "chrome", "chrome"]);
+    }
+
+    #[test]
+    fn group_threshold() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+        ];
+        let mut state = init_default_state(&init_columns);
+
+        let process_data = process_data(
+            [
+                (1, "chrome"),
+                (2, "bash"),
+                (3, "chrome"),
+                (4, "chrome"),
+                (5, "ssh"),
+                (6, "ssh"),
+            ]
+            .map(|(pid, name)| ProcessHarvest {
+                pid,
+                name: name.into(),
+                ..Default::default()
+            }),
+            &[],
+        );
+        let data_collection = DataCollection {
+            process_data,
+            ..Default::default()
+        };
+
+        let rows = |state: &ProcWidgetState| {
+            state
+                .table
+                .data()
+                .iter()
+                .map(|data| (data.id.to_prefixed_string(), data.num_similar))
+                .sorted()
+                .collect::<Vec<_>>()
+        };
+
+        state.toggle_tab();
+        state.group_threshold = 2;
+        state.set_table_data(&data_collection, None);
+        assert_eq!(
+            rows(&state),
+            vec![
+                ("bash".to_string(), 1),
+                ("chrome".to_string(), 3),
+                ("ssh".to_string(), 1),
+                ("ssh".to_string(), 1),
+            ]
+        );
+        assert_eq!(
+            state.id_pid_map.keys().collect::<Vec<_>>(),
+            vec![&"chrome".to_string()]
+        );
+
+        // Without a threshold, everything is grouped.
+        state.group_threshold = 0;
+        state.set_table_data(&data_collection, None);
+        assert_eq!(
+            rows(&state),
+            vec![
+                ("bash".to_string(), 1),
+                ("chrome".to_string(), 3),
+                ("ssh".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_against_frozen_data() {
+        use crate::app::frozen_state::{FrozenState, EXITED_PROCESS_UPDATES};
+
+        fn data(processes: &[(Pid, u64, f32, u64)]) -> DataCollection {
+            let processes =
+                processes
+                    .iter()
+                    .map(
+                        |&(pid, start_time, cpu_usage_percent, mem_usage_bytes)| ProcessHarvest {
+                            pid,
+                            start_time,
+                            name: format!("p{pid}"),
+                            cpu_usage_percent,
+                            mem_usage_bytes,
+                            ..Default::default()
+                        },
+                    );
+
+            DataCollection {
+                process_data: process_data(processes, &[]),
+                ..Default::default()
+            }
+        }
+
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::Mem,
+        ];
+        let mut state = init_default_state(&init_columns);
+        state.toggle_mem_percentage();
+
+        let mut frozen_state = FrozenState::default();
+        frozen_state.toggle_diff(&data(&[
+            (1, 10, 5.0, 2048),
+            (2, 20, 1.0, 1024),
+            (3, 30, 2.0, 0),
+        ]));
+
+        // 2 exits, 3's PID is reused, and 4 starts.
+        let live = data(&[(1, 10, 2.5, 3072), (3, 31, 1.0, 0), (4, 40, 3.0, 1024)]);
+        frozen_state.on_update(&live);
+        state.set_table_data(&live, frozen_state.diff_baseline());
+
+        let rows = state
+            .table
+            .data()
+            .iter()
+            .map(|row| {
+                (
+                    row.pid,
+                    row.diff.unwrap().change,
+                    row.to_cell(&ProcColumn::CpuPercent, NonZeroU16::new(10).unwrap())
+                        .unwrap()
+                        .to_string(),
+                    row.to_cell(&ProcColumn::MemValue, NonZeroU16::new(10).unwrap())
+                        .unwrap()
+                        .to_string(),
+                )
+            })
+            .sorted_by(|a, b| (a.0, a.2.clone()).cmp(&(b.0, b.2.clone())))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            rows,
+            vec![
+                (1, ProcChange::Kept, "-2.5%".into(), "+1KiB".into()),
+                (2, ProcChange::Exited, "-1.0%".into(), "-1KiB".into()),
+                (3, ProcChange::New, "+1.0%".into(), "+0B".into()),
+                (3, ProcChange::Exited, "-2.0%".into(), "+0B".into()),
+                (4, ProcChange::New, "+3.0%".into(), "+1KiB".into()),
+            ]
+        );
+        assert!(state
+            .table
+            .data()
+            .iter()
+            .filter(|row| row.is_exited())
+            .all(|row| row.disabled));
+
+        // Exited processes are eventually dropped.
+        for _ in 0..EXITED_PROCESS_UPDATES {
+            frozen_state.on_update(&live);
+        }
+        state.set_table_data(&live, frozen_state.diff_baseline());
+        assert_eq!(state.table.data().len(), 3);
+
+        // Without anything to compare to, the usual values are shown.
+        state.set_table_data(&live, None);
+        assert!(state.table.data().iter().all(|row| row.diff.is_none()));
+    }
+
+    #[test]
+    fn collapsed_tree_aggregates_children() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+        ];
+        let state = init_default_state(&init_columns);
+
+        // 1 -> 2 -> 3, and 1 -> 4 <-> 5, where 4 and 5 are (incorrectly) each
+        // other's parent.
+        let parents = [
+            (1, None),
+            (2, Some(1)),
+            (3, Some(2)),
+            (4, Some(5)),
+            (5, Some(4)),
+        ];
+        let process_data = process_data(
+            parents.map(|(pid, parent_pid)| ProcessHarvest {
+                pid,
+                parent_pid,
+                name: format!("p{pid}"),
+                cpu_usage_percent: 1.0,
+                ..Default::default()
+            }),
+            &[(1, &[2, 4]), (2, &[3]), (4, &[5]), (5, &[4])],
+        );
+        let data_collection = DataCollection {
+            process_data,
+            ..Default::default()
+        };
+
+        let expanded = state.get_tree_data(&HashSet::default(), &data_collection);
+        assert_eq!(
+            expanded
+                .iter()
+                .map(|data| data.pid)
+                .sorted()
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+
+        let collapsed = state.get_tree_data(&HashSet::from_iter([1]), &data_collection);
+        assert_eq!(collapsed.len(), 1);
+        assert_eq!(collapsed[0].cpu_usage_percent, 5.0);
+        assert_eq!(collapsed[0].id.to_prefixed_string(), "(+4) p1");
+
+        let collapsed = state.get_tree_data(&HashSet::from_iter([2]), &data_collection);
+        assert_eq!(
+            collapsed
+                .iter()
+                .map(|data| data.pid)
+                .sorted()
+                .collect::<Vec<_>>(),
+            vec![1, 2, 4, 5]
+        );
+        let p2 = collapsed.iter().find(|data| data.pid == 2).unwrap();
+        assert_eq!(p2.cpu_usage_percent, 2.0);
+        assert!(p2.id.to_prefixed_string().ends_with(" (+1) p2"));
+    }
+
+    #[test]
+    fn plain_tree_style_only_indents() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+        ];
+        let mut state = init_default_state(&init_columns);
+
+        // 1 -> 2 -> 3, and 1 -> 4.
+        let parents = [(1, None), (2, Some(1)), (3, Some(2)), (4, Some(1))];
+        let process_data = process_data(
+            parents.map(|(pid, parent_pid)| ProcessHarvest {
+                pid,
+                parent_pid,
+                name: format!("p{pid}"),
+                ..Default::default()
+            }),
+            &[(1, &[2, 4]), (2, &[3])],
+        );
+        let data_collection = DataCollection {
+            process_data,
+            ..Default::default()
+        };
+
+        let names = |state: &ProcWidgetState| {
+            state
+                .get_tree_data(&HashSet::default(), &data_collection)
+                .iter()
+                .map(|data| (data.pid, data.id.to_prefixed_string()))
+                .sorted()
+                .collect::<Vec<_>>()
+        };
+
+        assert!(names(&state)[2].1.ends_with("└─ p3"));
+
+        state.tree_style = TreeStyle::Plain;
+        assert_eq!(
+            names(&state),
+            vec![
+                (1, "p1".to_string()),
+                (2, "  p2".to_string()),
+                (3, "    p3".to_string()),
+                (4, "  p4".to_string()),
+            ]
+        );
+
+        let collapsed = state.get_tree_data(&HashSet::from_iter([2]), &data_collection);
+        let p2 = collapsed.iter().find(|data| data.pid == 2).unwrap();
+        assert_eq!(p2.id.to_prefixed_string(), "  (+1) p2");
+    }
+
+    #[test]
+    fn collapsed_tree_counts_shared_children_once() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::Mem,
+        ];
+        let state = init_default_state(&init_columns);
+
+        // 3 is (incorrectly) listed as a child of both 1 and 2.
+        let process_data = process_data(
+            [(1, None), (2, Some(1)), (3, Some(2))].map(|(pid, parent_pid)| ProcessHarvest {
+                pid,
+                parent_pid,
+                name: format!("p{pid}"),
+                cpu_usage_percent: 1.5,
+                mem_usage_percent: 2.0,
+                ..Default::default()
+            }),
+            &[(1, &[2, 3]), (2, &[3])],
+        );
+        let data_collection = DataCollection {
+            process_data,
+            ..Default::default()
+        };
+
+        let collapsed = state.get_tree_data(&HashSet::from_iter([1]), &data_collection);
+        assert_eq!(collapsed.len(), 1);
+        assert_eq!(collapsed[0].cpu_usage_percent, 4.5);
+        assert!(matches!(collapsed[0].mem_usage, MemUsage::Percent(mem) if mem == 6.0));
+        assert_eq!(collapsed[0].id.to_prefixed_string(), "(+2) p1");
+    }
+
+    #[test]
+    fn user_defined_columns() {
+        let config = AppConfigFields::default();
+        let styling = Styles::default();
+        let columns = Some(
+            [
+                ProcWidgetColumn::PidOrCount,
+                ProcWidgetColumn::ProcNameOrCommand,
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let custom_columns = [
+            CustomColumn::new(
+                "Mem/CPU",
+                "mem_bytes / cpu_percent",
+                CustomColumnUnit::Bytes,
+            )
+            .unwrap(),
+            CustomColumn::new("Half", "cpu_percent / 2", CustomColumnUnit::Percent).unwrap(),
+        ];
+
+        let mut state = ProcWidgetState::new(
+            &config,
+            ProcWidgetMode::Normal,
+            ProcTableConfig::default(),
+            &styling,
+            &columns,
+            &custom_columns,
+        );
+
+        assert_eq!(
+            state
+                .table
+                .columns
+                .iter()
+                .map(|column| column.inner().text())
+                .collect::<Vec<_>>(),
+            vec!["PID", "Name", "Mem/CPU", "Half"]
+        );
+        assert_eq!(
+            state
+                .column_mapping
+                .get_index_of(&ProcWidgetColumn::Custom(1)),
+            Some(3)
+        );
+
+        let process_data = process_data(
+            [(1, 2000, 10.0), (2, 2000, 0.0), (3, 1000, 1.0)].map(
+                |(pid, mem_usage_bytes, cpu_usage_percent)| ProcessHarvest {
+                    pid,
+                    mem_usage_bytes,
+                    cpu_usage_percent,
+                    ..Default::default()
+                },
+            ),
+            &[],
+        );
+
+        state.table.set_sort_index(2);
+        state.table.set_order(SortOrder::Descending);
+        let data = state.get_normal_data(&process_data, None);
+        assert_eq!(
+            data.iter()
+                .map(|data| (data.pid, data.custom_value(0)))
+                .collect::<Vec<_>>(),
+            vec![(3, Some(1000.0)), (1, Some(200.0)), (2, None)]
+        );
+        let cell = |data: &ProcWidgetData, index: usize| {
+            data.to_cell(
+                &ProcColumn::Custom(custom_columns[index].id(index)),
+                NonZeroU16::new(10).unwrap(),
+            )
+            .unwrap()
+        };
+        assert_eq!(cell(&data[0], 0), "1KB");
+        assert_eq!(cell(&data[0], 1), "0.5%");
+        assert_eq!(cell(&data[2], 0), "");
+
+        // Hidden columns that aren't being sorted by aren't computed.
+        state.table.columns[3].is_hidden = true;
+        let data = state.get_normal_data(&process_data, None);
+        assert!(data.iter().all(|data| data.custom_value(1).is_none()));
+    }
 }
@@ -0,0 +1,357 @@
+//! Custom process columns, computed from a small arithmetic expression over the
+//! numeric values of a process.
+
+use std::{iter::Peekable, str::CharIndices};
+
+use serde::Deserialize;
+
+use crate::data_collection::processes::ProcessHarvest;
+
+/// How to format the values of a custom column.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Hash, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+pub enum CustomColumnUnit {
+    /// A plain number.
+    #[default]
+    #[serde(rename = "none")]
+    None,
+
+    /// A number of bytes.
+    #[serde(rename = "bytes")]
+    Bytes,
+
+    /// A number of bytes per second.
+    #[serde(rename = "bytes/s")]
+    BytesPerSecond,
+
+    /// A percentage.
+    #[serde(rename = "percent")]
+    Percent,
+}
+
+/// Identifies a custom column in a process widget's table.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct CustomColumnId {
+    /// The index of the column's value in [`super::ProcWidgetData::custom_values`].
+    pub index: usize,
+    pub name: &'static str,
+    pub unit: CustomColumnUnit,
+}
+
+/// A numeric value of a process that can be used in an expression.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Field {
+    CpuPercent,
+    MemBytes,
+    MemPercent,
+    ReadPerSecond,
+    WritePerSecond,
+    TotalRead,
+    TotalWrite,
+    Time,
+    #[cfg(feature = "gpu")]
+    GpuMemBytes,
+    #[cfg(feature = "gpu")]
+    GpuMemPercent,
+    #[cfg(feature = "gpu")]
+    GpuUtil,
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "cpu_percent" => Field::CpuPercent,
+            "mem_bytes" => Field::MemBytes,
+            "mem_percent" => Field::MemPercent,
+            "read_per_sec" => Field::ReadPerSecond,
+            "write_per_sec" => Field::WritePerSecond,
+            "total_read" => Field::TotalRead,
+            "total_write" => Field::TotalWrite,
+            "time" => Field::Time,
+            #[cfg(feature = "gpu")]
+            "gpu_mem_bytes" => Field::GpuMemBytes,
+            #[cfg(feature = "gpu")]
+            "gpu_mem_percent" => Field::GpuMemPercent,
+            #[cfg(feature = "gpu")]
+            "gpu_util" => Field::GpuUtil,
+            _ => return None,
+        })
+    }
+
+    fn value(&self, process: &ProcessHarvest) -> f64 {
+        match self {
+            Field::CpuPercent => process.cpu_usage_percent as f64,
+            Field::MemBytes => process.mem_usage_bytes as f64,
+            Field::MemPercent => process.mem_usage_percent as f64,
+            Field::ReadPerSecond => process.read_bytes_per_sec as f64,
+            Field::WritePerSecond => process.write_bytes_per_sec as f64,
+            Field::TotalRead => process.total_read_bytes as f64,
+            Field::TotalWrite => process.total_write_bytes as f64,
+            Field::Time => process.time.as_secs_f64(),
+            #[cfg(feature = "gpu")]
+            Field::GpuMemBytes => process.gpu_mem.unwrap_or(0) as f64,
+            #[cfg(feature = "gpu")]
+            Field::GpuMemPercent => process.gpu_mem_percent.unwrap_or(0.0) as f64,
+            #[cfg(feature = "gpu")]
+            Field::GpuUtil => process.gpu_util.unwrap_or(0) as f64,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Expr {
+    Number(f64),
+    Field(Field),
+    Neg(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Evaluates the expression. Returns [`None`] if the result is undefined,
+    /// e.g. due to a division by zero.
+    fn eval(&self, process: &ProcessHarvest) -> Option<f64> {
+        match self {
+            Expr::Number(value) => Some(*value),
+            Expr::Field(field) => Some(field.value(process)),
+            Expr::Neg(expr) => expr.eval(process).map(|value| -value),
+            Expr::Binary(op, lhs, rhs) => {
+                let lhs = lhs.eval(process)?;
+                let rhs = rhs.eval(process)?;
+
+                match op {
+                    Op::Add => Some(lhs + rhs),
+                    Op::Sub => Some(lhs - rhs),
+                    Op::Mul => Some(lhs * rhs),
+                    Op::Div => {
+                        if rhs == 0.0 {
+                            None
+                        } else {
+                            Some(lhs / rhs)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A simple recursive descent parser for expressions, which supports `+`, `-`,
+/// `*`, `/`, parentheses, numbers, and field names.
+struct Parser<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            chars: input.char_indices().peekable(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn parse(mut self) -> Result<Expr, String> {
+        let expr = self.expr()?;
+
+        match self.peek() {
+            None => Ok(expr),
+            Some(c) => Err(format!("unexpected '{c}'")),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.term()?;
+
+        loop {
+            let op = match self.peek() {
+                Some('+') => Op::Add,
+                Some('-') => Op::Sub,
+                _ => return Ok(lhs),
+            };
+            self.chars.next();
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.factor()?;
+
+        loop {
+            let op = match self.peek() {
+                Some('*') => Op::Mul,
+                Some('/') => Op::Div,
+                _ => return Ok(lhs),
+            };
+            self.chars.next();
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.factor()?));
+        }
+    }
+
+    fn factor(&mut self) -> Result<Expr, String> {
+        match self.peek() {
+            Some('-') => {
+                self.chars.next();
+                Ok(Expr::Neg(Box::new(self.factor()?)))
+            }
+            Some('(') => {
+                self.chars.next();
+                let expr = self.expr()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.chars.next();
+                        Ok(expr)
+                    }
+                    _ => Err("missing closing ')'".to_string()),
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let token = self.token(|c| c.is_ascii_digit() || c == '.');
+                token
+                    .parse()
+                    .map(Expr::Number)
+                    .map_err(|_| format!("invalid number '{token}'"))
+            }
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                let token = self.token(|c| c.is_ascii_alphanumeric() || c == '_');
+                Field::from_name(token)
+                    .map(Expr::Field)
+                    .ok_or_else(|| format!("unknown field '{token}'"))
+            }
+            Some(c) => Err(format!("unexpected '{c}'")),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+
+    /// Consumes characters while they match the predicate, returning them.
+    fn token(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let start = self
+            .chars
+            .peek()
+            .map(|(i, _)| *i)
+            .unwrap_or(self.input.len());
+        while self.chars.next_if(|(_, c)| predicate(*c)).is_some() {}
+        let end = self
+            .chars
+            .peek()
+            .map(|(i, _)| *i)
+            .unwrap_or(self.input.len());
+
+        &self.input[start..end]
+    }
+}
+
+/// A user-defined process column.
+#[derive(Debug, Clone)]
+pub struct CustomColumn {
+    name: &'static str,
+    unit: CustomColumnUnit,
+    expr: Expr,
+}
+
+impl CustomColumn {
+    /// Creates a new [`CustomColumn`], returning an error describing the problem
+    /// if the expression is invalid.
+    pub fn new(name: &str, expr: &str, unit: CustomColumnUnit) -> Result<Self, String> {
+        let expr = Parser::new(expr).parse()?;
+
+        Ok(Self {
+            // Column headers must be 'static. Custom columns are only created once
+            // on startup, so leaking the name is fine.
+            name: Box::leak(name.to_string().into_boxed_str()),
+            unit,
+            expr,
+        })
+    }
+
+    /// Returns the [`CustomColumnId`] for this column, if it is the `index`-th
+    /// custom column.
+    pub fn id(&self, index: usize) -> CustomColumnId {
+        CustomColumnId {
+            index,
+            name: self.name,
+            unit: self.unit,
+        }
+    }
+
+    /// Computes the column's value for a process. Returns [`None`] if the value
+    /// is undefined.
+    pub fn eval(&self, process: &ProcessHarvest) -> Option<f64> {
+        self.expr.eval(process).filter(|value| value.is_finite())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn process() -> ProcessHarvest {
+        ProcessHarvest {
+            cpu_usage_percent: 50.0,
+            mem_usage_bytes: 1000,
+            read_bytes_per_sec: 300,
+            write_bytes_per_sec: 100,
+            time: Duration::from_secs(4),
+            ..Default::default()
+        }
+    }
+
+    fn eval(expr: &str) -> Option<f64> {
+        CustomColumn::new("test", expr, CustomColumnUnit::None)
+            .unwrap()
+            .eval(&process())
+    }
+
+    #[test]
+    fn evaluates_expressions() {
+        assert_eq!(eval("mem_bytes / cpu_percent"), Some(20.0));
+        assert_eq!(eval("1 + 2 * 3"), Some(7.0));
+        assert_eq!(eval("(1 + 2) * 3"), Some(9.0));
+        assert_eq!(eval("10 - 4 - 3"), Some(3.0));
+        assert_eq!(eval("-time + 0.5"), Some(-3.5));
+        assert_eq!(eval(" (read_per_sec+write_per_sec)/time "), Some(100.0));
+    }
+
+    #[test]
+    fn division_by_zero_is_empty() {
+        assert_eq!(eval("mem_bytes / total_read"), None);
+        assert_eq!(eval("1 / (time - 4) + 1"), None);
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        for (expr, err) in [
+            ("mem_bytes / rss", "unknown field 'rss'"),
+            ("(1 + 2", "missing closing ')'"),
+            ("1 +", "unexpected end of expression"),
+            ("1 2", "unexpected '2'"),
+            ("1..2", "invalid number '1..2'"),
+            ("cpu_percent % 2", "unexpected '%'"),
+            ("", "unexpected end of expression"),
+        ] {
+            assert_eq!(
+                CustomColumn::new("test", expr, CustomColumnUnit::None).unwrap_err(),
+                err,
+                "expression: {expr}"
+            );
+        }
+    }
+}
@@ -2,20 +2,19 @@ use std::{borrow::Cow, cmp::Reverse};
 
 use serde::Deserialize;
 
-use super::{ProcWidgetColumn, ProcWidgetData};
+use super::{CustomColumnId, ProcWidgetColumn, ProcWidgetData};
 use crate::{
     canvas::components::data_table::{ColumnHeader, SortsRow},
-    utils::general::sort_partial_fn,
+    utils::general::{sort_partial_fn, sort_str_fn},
 };
 
 /// A column in the process widget.
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
-#[cfg_attr(
-    feature = "generate_schema",
-    derive(schemars::JsonSchema, strum::VariantArray)
-)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
 pub enum ProcColumn {
     CpuPercent,
+    CpuHistory,
+    LastSpike,
     MemValue,
     MemPercent,
     Pid,
@@ -28,13 +27,49 @@ pub enum ProcColumn {
     TotalWrite,
     State,
     User,
+    Group,
     Time,
+    ExeLocation,
     #[cfg(feature = "gpu")]
     GpuMemValue,
     #[cfg(feature = "gpu")]
     GpuMemPercent,
     #[cfg(feature = "gpu")]
     GpuUtilPercent,
+    /// A user-defined column. These are set up separately from the other columns.
+    #[cfg_attr(feature = "generate_schema", schemars(skip))]
+    Custom(CustomColumnId),
+}
+
+// Custom columns can't be named in the column list, so they are left out here.
+#[cfg(feature = "generate_schema")]
+impl strum::VariantArray for ProcColumn {
+    const VARIANTS: &'static [Self] = &[
+        ProcColumn::CpuPercent,
+        ProcColumn::CpuHistory,
+        ProcColumn::LastSpike,
+        ProcColumn::MemValue,
+        ProcColumn::MemPercent,
+        ProcColumn::Pid,
+        ProcColumn::Count,
+        ProcColumn::Name,
+        ProcColumn::Command,
+        ProcColumn::ReadPerSecond,
+        ProcColumn::WritePerSecond,
+        ProcColumn::TotalRead,
+        ProcColumn::TotalWrite,
+        ProcColumn::State,
+        ProcColumn::User,
+        ProcColumn::Group,
+        ProcColumn::Time,
+        ProcColumn::ExeLocation,
+        #[cfg(feature = "gpu")]
+        ProcColumn::GpuMemValue,
+        #[cfg(feature = "gpu")]
+        ProcColumn::GpuMemPercent,
+        #[cfg(feature = "gpu")]
+        ProcColumn::GpuUtilPercent,
+    ];
 }
 
 impl ProcColumn {
@@ -47,6 +82,8 @@ impl ProcColumn {
             ProcColumn::Name => &["Name"],
             ProcColumn::Command => &["Command"],
             ProcColumn::CpuPercent => &["CPU%"],
+            ProcColumn::CpuHistory => &["CPU History", "CPU Hist"],
+            ProcColumn::LastSpike => &["Last Spike", "Spike"],
             // TODO: Change this
             ProcColumn::MemValue | ProcColumn::MemPercent => &["Mem", "Mem%"],
             ProcColumn::ReadPerSecond => &["R/s", "Read", "Rps"],
@@ -55,12 +92,15 @@ impl ProcColumn {
             ProcColumn::TotalWrite => &["T.Write", "TRead"],
             ProcColumn::State => &["State"],
             ProcColumn::User => &["User"],
+            ProcColumn::Group => &["Group"],
             ProcColumn::Time => &["Time"],
+            ProcColumn::ExeLocation => &["Location", "Exe"],
             #[cfg(feature = "gpu")]
             // TODO: Change this
             ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => &["GMem", "GMem%"],
             #[cfg(feature = "gpu")]
             ProcColumn::GpuUtilPercent => &["GPU%"],
+            ProcColumn::Custom(_) => &[],
         }
     }
 }
@@ -69,6 +109,8 @@ impl ColumnHeader for ProcColumn {
     fn text(&self) -> Cow<'static, str> {
         match self {
             ProcColumn::CpuPercent => "CPU%",
+            ProcColumn::CpuHistory => "CPU Hist",
+            ProcColumn::LastSpike => "Last Spike",
             ProcColumn::MemValue => "Mem",
             ProcColumn::MemPercent => "Mem%",
             ProcColumn::Pid => "PID",
@@ -81,13 +123,16 @@ impl ColumnHeader for ProcColumn {
             ProcColumn::TotalWrite => "T.Write",
             ProcColumn::State => "State",
             ProcColumn::User => "User",
+            ProcColumn::Group => "Group",
             ProcColumn::Time => "Time",
+            ProcColumn::ExeLocation => "Location",
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemValue => "GMem",
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemPercent => "GMem%",
             #[cfg(feature = "gpu")]
             ProcColumn::GpuUtilPercent => "GPU%",
+            ProcColumn::Custom(id) => id.name,
         }
         .into()
     }
@@ -108,15 +153,33 @@ impl ColumnHeader for ProcColumn {
 impl SortsRow for ProcColumn {
     type DataType = ProcWidgetData;
 
-    fn sort_data(&self, data: &mut [ProcWidgetData], descending: bool) {
+    fn sort_data(&self, data: &mut [ProcWidgetData], descending: bool, case_sensitive: bool) {
         match self {
             ProcColumn::CpuPercent => {
                 data.sort_by(|a, b| {
-                    sort_partial_fn(descending)(a.cpu_usage_percent, b.cpu_usage_percent)
+                    sort_partial_fn(descending)(a.shown_cpu_usage(), b.shown_cpu_usage())
+                });
+            }
+            ProcColumn::CpuHistory => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.average_cpu_history(), b.average_cpu_history())
+                });
+            }
+            ProcColumn::LastSpike => {
+                // The most recent spikes come first when descending, and
+                // processes that never spiked come last.
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(
+                        a.spiked_ago.map(Reverse),
+                        b.spiked_ago.map(Reverse),
+                    )
                 });
             }
             ProcColumn::MemValue | ProcColumn::MemPercent => {
-                data.sort_by(|a, b| sort_partial_fn(descending)(&a.mem_usage, &b.mem_usage));
+                data.sort_by(|a, b| match (&a.diff, &b.diff) {
+                    (Some(a), Some(b)) => sort_partial_fn(descending)(a.mem, b.mem),
+                    _ => sort_partial_fn(descending)(&a.mem_usage, &b.mem_usage),
+                });
             }
             ProcColumn::Pid => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.pid, b.pid));
@@ -125,11 +188,9 @@ impl SortsRow for ProcColumn {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.num_similar, b.num_similar));
             }
             ProcColumn::Name | ProcColumn::Command => {
-                if descending {
-                    data.sort_by_cached_key(|pd| Reverse(pd.id.to_lowercase()));
-                } else {
-                    data.sort_by_cached_key(|pd| pd.id.to_lowercase());
-                }
+                data.sort_by(|a, b| {
+                    sort_str_fn(a.id.as_str(), b.id.as_str(), descending, case_sensitive)
+                });
             }
             ProcColumn::ReadPerSecond => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.rps, b.rps));
@@ -144,22 +205,29 @@ impl SortsRow for ProcColumn {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.total_write, b.total_write));
             }
             ProcColumn::State => {
-                if descending {
-                    data.sort_by_cached_key(|pd| Reverse(pd.process_state.to_lowercase()));
-                } else {
-                    data.sort_by_cached_key(|pd| pd.process_state.to_lowercase());
-                }
+                data.sort_by(|a, b| {
+                    sort_str_fn(
+                        &a.process_state,
+                        &b.process_state,
+                        descending,
+                        case_sensitive,
+                    )
+                });
             }
             ProcColumn::User => {
-                if descending {
-                    data.sort_by_cached_key(|pd| Reverse(pd.user.to_lowercase()));
-                } else {
-                    data.sort_by_cached_key(|pd| pd.user.to_lowercase());
-                }
+                data.sort_by(|a, b| sort_str_fn(&a.user, &b.user, descending, case_sensitive));
+            }
+            ProcColumn::Group => {
+                data.sort_by(|a, b| sort_str_fn(&a.group, &b.group, descending, case_sensitive));
             }
             ProcColumn::Time => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.time, b.time));
             }
+            ProcColumn::ExeLocation => {
+                data.sort_by(|a, b| {
+                    sort_str_fn(&a.exe_location, &b.exe_location, descending, case_sensitive)
+                });
+            }
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => {
                 data.sort_by(|a, b| {
@@ -170,6 +238,11 @@ impl SortsRow for ProcColumn {
             ProcColumn::GpuUtilPercent => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.gpu_usage, b.gpu_usage));
             }
+            ProcColumn::Custom(id) => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.custom_value(id.index), b.custom_value(id.index))
+                });
+            }
         }
     }
 }
@@ -182,6 +255,8 @@ impl<'de> Deserialize<'de> for ProcColumn {
         let value = String::deserialize(deserializer)?.to_lowercase();
         match value.as_str() {
             "cpu%" => Ok(ProcColumn::CpuPercent),
+            "cpu history" | "cpu hist" => Ok(ProcColumn::CpuHistory),
+            "last spike" | "spike" => Ok(ProcColumn::LastSpike),
             // TODO: Maybe change this in the future.
             "mem" | "mem%" => Ok(ProcColumn::MemPercent),
             "pid" => Ok(ProcColumn::Pid),
@@ -194,7 +269,9 @@ impl<'de> Deserialize<'de> for ProcColumn {
             "twrite" | "t.write" => Ok(ProcColumn::TotalWrite),
             "state" => Ok(ProcColumn::State),
             "user" => Ok(ProcColumn::User),
+            "group" => Ok(ProcColumn::Group),
             "time" => Ok(ProcColumn::Time),
+            "location" | "exe" => Ok(ProcColumn::ExeLocation),
             #[cfg(feature = "gpu")]
             // TODO: Maybe change this in the future.
             "gmem" | "gmem%" => Ok(ProcColumn::GpuMemPercent),
@@ -213,6 +290,8 @@ impl From<&ProcColumn> for ProcWidgetColumn {
             ProcColumn::Pid | ProcColumn::Count => ProcWidgetColumn::PidOrCount,
             ProcColumn::Name | ProcColumn::Command => ProcWidgetColumn::ProcNameOrCommand,
             ProcColumn::CpuPercent => ProcWidgetColumn::Cpu,
+            ProcColumn::CpuHistory => ProcWidgetColumn::CpuHistory,
+            ProcColumn::LastSpike => ProcWidgetColumn::LastSpike,
             ProcColumn::MemPercent | ProcColumn::MemValue => ProcWidgetColumn::Mem,
             ProcColumn::ReadPerSecond => ProcWidgetColumn::ReadPerSecond,
             ProcColumn::WritePerSecond => ProcWidgetColumn::WritePerSecond,
@@ -220,11 +299,14 @@ impl From<&ProcColumn> for ProcWidgetColumn {
             ProcColumn::TotalWrite => ProcWidgetColumn::TotalWrite,
             ProcColumn::State => ProcWidgetColumn::State,
             ProcColumn::User => ProcWidgetColumn::User,
+            ProcColumn::Group => ProcWidgetColumn::Group,
             ProcColumn::Time => ProcWidgetColumn::Time,
+            ProcColumn::ExeLocation => ProcWidgetColumn::ExeLocation,
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemPercent | ProcColumn::GpuMemValue => ProcWidgetColumn::GpuMem,
             #[cfg(feature = "gpu")]
             ProcColumn::GpuUtilPercent => ProcWidgetColumn::GpuUtil,
+            ProcColumn::Custom(id) => ProcWidgetColumn::Custom(id.index),
         }
     }
 }
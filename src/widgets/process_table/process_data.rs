@@ -1,22 +1,31 @@
 use std::{
     borrow::Cow,
     cmp::{max, Ordering},
+    collections::VecDeque,
     fmt::Display,
     num::NonZeroU16,
+    path::{Component, Path},
     time::Duration,
 };
 
 use concat_string::concat_string;
 use tui::widgets::Row;
+use unicode_width::UnicodeWidthStr;
 
-use super::process_columns::ProcColumn;
+use super::{
+    custom_column::{CustomColumnId, CustomColumnUnit},
+    process_columns::ProcColumn,
+};
 use crate::{
     canvas::{
-        components::data_table::{DataTableColumn, DataToCell},
+        components::data_table::{DataTableColumn, DataToCell, RowKey},
         Painter,
     },
-    data_collection::processes::{Pid, ProcessHarvest},
-    data_conversion::{binary_byte_string, dec_bytes_per_second_string, dec_bytes_string},
+    data_collection::processes::{Pid, ProcessHarvest, UNKNOWN_NAME},
+    data_conversion::{
+        binary_byte_string, compact_bytes_per_second_string, compact_bytes_string,
+        dec_bytes_per_second_string, dec_bytes_string,
+    },
 };
 
 #[derive(Clone, Debug)]
@@ -166,6 +175,91 @@ fn format_time(dur: Duration) -> String {
     }
 }
 
+/// Formats how long ago a process last spiked, like `2m ago`, or `—` if it
+/// never has.
+fn format_spiked_ago(spiked_ago: Option<Duration>) -> String {
+    let Some(ago) = spiked_ago else {
+        return "—".to_string();
+    };
+
+    if ago.num_days() > 0 {
+        format!("{}d ago", ago.num_days())
+    } else if ago.num_hours() > 0 {
+        format!("{}h ago", ago.num_hours())
+    } else if ago.num_minutes() > 0 {
+        format!("{}m ago", ago.num_minutes())
+    } else {
+        format!("{}s ago", ago.as_secs())
+    }
+}
+
+/// Returns a sparkline for the given CPU usage values, oldest first. The top of
+/// the sparkline is 100%, or the highest value if that is more, as usage can
+/// go past 100% when it isn't normalized across cores.
+fn cpu_sparkline(history: &[f32]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let top = history.iter().copied().fold(100.0, f32::max);
+
+    history
+        .iter()
+        .map(|usage| {
+            let index = (usage.max(0.0) / top * (BARS.len() - 1) as f32).round();
+            BARS[index as usize]
+        })
+        .collect()
+}
+
+/// Returns a rough idea of where an executable came from, based on the
+/// top-level directory it lives in (e.g. `/usr/bin`, `/snap`, `/opt`). This is
+/// a cheap stand-in for looking up the package that owns it.
+fn exe_location(exe: Option<&Path>) -> Cow<'static, str> {
+    let Some(exe) = exe else {
+        return "N/A".into();
+    };
+
+    let mut components = exe.components();
+    if components.next() != Some(Component::RootDir) {
+        return "N/A".into();
+    }
+
+    let components = components
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>();
+
+    // Only count directories, not the executable itself.
+    match &components[..] {
+        // Anonymous executables, e.g. `/memfd:name (deleted)`.
+        [name] if name.starts_with("memfd:") => "memfd".into(),
+        [] | [_] => "/".into(),
+        // Split up `/usr`, as where things are in it is telling.
+        [usr, second, _, ..] if usr == "usr" => concat_string!("/usr/", second).into(),
+        [first, ..] => concat_string!("/", first).into(),
+    }
+}
+
+/// How a process has changed since the data was frozen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcChange {
+    /// The process was already running.
+    Kept,
+    /// The process started since.
+    New,
+    /// The process has exited since.
+    Exited,
+}
+
+/// The difference between a process and the same process when the data was
+/// frozen. Processes that weren't running count as using nothing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProcDiff {
+    pub change: ProcChange,
+    /// The change in CPU usage, as a percentage.
+    pub cpu: f32,
+    /// The change in memory usage, in the same unit as the memory column.
+    pub mem: f64,
+}
+
 #[derive(Clone)]
 pub struct ProcWidgetData {
     pub pid: Pid,
@@ -173,21 +267,44 @@ pub struct ProcWidgetData {
     pub ppid: Option<Pid>,
     pub id: Id,
     pub cpu_usage_percent: f32,
+    pub cpu_history: Vec<f32>,
+    /// How long ago the CPU usage last went over the spike threshold, or
+    /// [`None`] if it never has.
+    pub spiked_ago: Option<Duration>,
     pub mem_usage: MemUsage,
+    pub compact_bytes: bool,
     pub rps: u64,
     pub wps: u64,
     pub total_read: u64,
     pub total_write: u64,
+    /// Whether the I/O values are unknown, in which case they are shown as
+    /// "N/A".
+    pub io_unknown: bool,
     pub process_state: String,
     pub process_char: char,
     pub user: String,
+    pub group: String,
     pub num_similar: u64,
+    /// Whether to show how many processes are grouped together next to the
+    /// name, e.g. `chrome (12)`.
+    pub show_num_similar: bool,
     pub disabled: bool,
     pub time: Duration,
+    pub exe_location: Cow<'static, str>,
+    /// The GPU memory used, which is [`None`] (and shown as blank) if no GPU
+    /// lists the process.
     #[cfg(feature = "gpu")]
-    pub gpu_mem_usage: MemUsage,
+    pub gpu_mem_usage: Option<MemUsage>,
+    /// The GPU utilization, which is [`None`] (and shown as blank) if no GPU
+    /// lists the process.
     #[cfg(feature = "gpu")]
-    pub gpu_usage: u32,
+    pub gpu_usage: Option<u32>,
+    /// The values of any custom columns, by index. A value is [`None`] if it is
+    /// undefined, or if the column wasn't computed.
+    pub custom_values: Vec<Option<f64>>,
+    /// How the process changed since the data was frozen, if that is being
+    /// shown. The CPU and memory columns then show the change instead.
+    pub diff: Option<ProcDiff>,
 }
 
 impl ProcWidgetData {
@@ -212,25 +329,36 @@ impl ProcWidgetData {
             ppid: process.parent_pid,
             id,
             cpu_usage_percent: process.cpu_usage_percent,
+            cpu_history: Vec::default(),
+            spiked_ago: None,
             mem_usage,
+            compact_bytes: false,
             rps: process.read_bytes_per_sec,
             wps: process.write_bytes_per_sec,
             total_read: process.total_read_bytes,
             total_write: process.total_write_bytes,
+            io_unknown: process.io_unknown,
             process_state: process.process_state.0.clone(),
             process_char: process.process_state.1,
             user: process.user.to_string(),
+            group: process.group.to_string(),
             num_similar: 1,
+            show_num_similar: false,
             disabled: false,
             time: process.time,
+            exe_location: exe_location(process.exe.as_deref()),
             #[cfg(feature = "gpu")]
             gpu_mem_usage: if is_mem_percent {
-                MemUsage::Percent(process.gpu_mem_percent)
+                process
+                    .gpu_mem
+                    .map(|_| MemUsage::Percent(process.gpu_mem_percent.unwrap_or(0.0)))
             } else {
-                MemUsage::Bytes(process.gpu_mem)
+                process.gpu_mem.map(MemUsage::Bytes)
             },
             #[cfg(feature = "gpu")]
             gpu_usage: process.gpu_util,
+            custom_values: Vec::default(),
+            diff: None,
         }
     }
 
@@ -239,18 +367,157 @@ impl ProcWidgetData {
         self
     }
 
+    pub fn show_num_similar(mut self, show_num_similar: bool) -> Self {
+        self.show_num_similar = show_num_similar;
+        self
+    }
+
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
         self
     }
 
+    pub fn compact_bytes(mut self, compact_bytes: bool) -> Self {
+        self.compact_bytes = compact_bytes;
+        self
+    }
+
+    pub fn custom_values(mut self, custom_values: Vec<Option<f64>>) -> Self {
+        self.custom_values = custom_values;
+        self
+    }
+
+    /// Returns the value of the custom column at `index`, if there is one.
+    pub fn custom_value(&self, index: usize) -> Option<f64> {
+        self.custom_values.get(index).copied().flatten()
+    }
+
     pub fn prefix(mut self, prefix: Option<String>) -> Self {
         self.id.prefix = prefix;
         self
     }
 
+    pub fn cpu_history(mut self, history: Option<&VecDeque<f32>>) -> Self {
+        if let Some(history) = history {
+            self.add_cpu_history(history.iter().copied());
+        }
+        self
+    }
+
+    /// Sets how long ago the CPU usage last spiked, keeping the more recent
+    /// spike if this entry already has one.
+    pub fn spiked_ago(mut self, spiked_ago: Option<Duration>) -> Self {
+        self.add_spiked_ago(spiked_ago);
+        self
+    }
+
+    fn add_spiked_ago(&mut self, other: Option<Duration>) {
+        self.spiked_ago = match (self.spiked_ago, other) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (spiked_ago, None) | (None, spiked_ago) => spiked_ago,
+        };
+    }
+
+    /// Adds the given CPU history to this entry's history, aligned by the most
+    /// recent value.
+    fn add_cpu_history(&mut self, other: impl DoubleEndedIterator<Item = f32> + ExactSizeIterator) {
+        if other.len() > self.cpu_history.len() {
+            let missing = other.len() - self.cpu_history.len();
+            self.cpu_history
+                .splice(0..0, std::iter::repeat(0.0).take(missing));
+        }
+
+        for (current, other) in self.cpu_history.iter_mut().rev().zip(other.rev()) {
+            *current += other;
+        }
+    }
+
+    /// The memory usage as a number, in whichever unit is being shown.
+    fn mem_value(&self) -> f64 {
+        match self.mem_usage {
+            MemUsage::Percent(percent) => f64::from(percent),
+            MemUsage::Bytes(bytes) => bytes as f64,
+        }
+    }
+
+    /// Compares the process to `old`, the same process when the data was
+    /// frozen, or [`None`] if it has started since.
+    pub fn diff_from(mut self, old: Option<&ProcessHarvest>) -> Self {
+        let (change, old_cpu, old_mem) = match old {
+            Some(old) => {
+                let old_mem = match self.mem_usage {
+                    MemUsage::Percent(_) => f64::from(old.mem_usage_percent),
+                    MemUsage::Bytes(_) => old.mem_usage_bytes as f64,
+                };
+                (ProcChange::Kept, old.cpu_usage_percent, old_mem)
+            }
+            None => (ProcChange::New, 0.0, 0.0),
+        };
+
+        self.diff = Some(ProcDiff {
+            change,
+            cpu: self.cpu_usage_percent - old_cpu,
+            mem: self.mem_value() - old_mem,
+        });
+        self
+    }
+
+    /// Marks the process as having exited since the data was frozen, where
+    /// this entry was made from the frozen data.
+    pub fn exited(mut self) -> Self {
+        self.disabled = true;
+        self.diff = Some(ProcDiff {
+            change: ProcChange::Exited,
+            cpu: -self.cpu_usage_percent,
+            mem: -self.mem_value(),
+        });
+        self
+    }
+
+    /// Whether the process has exited since the data was frozen.
+    pub fn is_exited(&self) -> bool {
+        matches!(
+            self.diff,
+            Some(ProcDiff {
+                change: ProcChange::Exited,
+                ..
+            })
+        )
+    }
+
+    /// The CPU usage shown, which is the change in usage if comparing to
+    /// frozen data.
+    pub fn shown_cpu_usage(&self) -> f32 {
+        match &self.diff {
+            Some(diff) => diff.cpu,
+            None => self.cpu_usage_percent,
+        }
+    }
+
+    /// Multiplies the CPU usage and its history by `scale`.
+    pub fn scale_cpu_usage(&mut self, scale: f32) {
+        self.cpu_usage_percent *= scale;
+        if let Some(diff) = &mut self.diff {
+            diff.cpu *= scale;
+        }
+        self.cpu_history
+            .iter_mut()
+            .for_each(|usage| *usage *= scale);
+    }
+
+    /// The average of the stored CPU history, or zero if there is none.
+    pub fn average_cpu_history(&self) -> f32 {
+        if self.cpu_history.is_empty() {
+            0.0
+        } else {
+            self.cpu_history.iter().sum::<f32>() / self.cpu_history.len() as f32
+        }
+    }
+
     pub fn add(&mut self, other: &Self) {
         self.cpu_usage_percent += other.cpu_usage_percent;
+        self.add_cpu_history(other.cpu_history.iter().copied());
+        self.add_spiked_ago(other.spiked_ago);
         self.mem_usage = match (&self.mem_usage, &other.mem_usage) {
             (MemUsage::Percent(a), MemUsage::Percent(b)) => MemUsage::Percent(a + b),
             (MemUsage::Bytes(a), MemUsage::Bytes(b)) => MemUsage::Bytes(a + b),
@@ -263,39 +530,156 @@ impl ProcWidgetData {
         self.wps += other.wps;
         self.total_read += other.total_read;
         self.total_write += other.total_write;
+        self.io_unknown &= other.io_unknown;
         self.time = self.time.max(other.time);
         #[cfg(feature = "gpu")]
         {
             self.gpu_mem_usage = match (&self.gpu_mem_usage, &other.gpu_mem_usage) {
-                (MemUsage::Percent(a), MemUsage::Percent(b)) => MemUsage::Percent(a + b),
-                (MemUsage::Bytes(a), MemUsage::Bytes(b)) => MemUsage::Bytes(a + b),
-                (MemUsage::Percent(_), MemUsage::Bytes(_))
-                | (MemUsage::Bytes(_), MemUsage::Percent(_)) => {
+                (Some(MemUsage::Percent(a)), Some(MemUsage::Percent(b))) => {
+                    Some(MemUsage::Percent(a + b))
+                }
+                (Some(MemUsage::Bytes(a)), Some(MemUsage::Bytes(b))) => {
+                    Some(MemUsage::Bytes(a + b))
+                }
+                (Some(MemUsage::Percent(_)), Some(MemUsage::Bytes(_)))
+                | (Some(MemUsage::Bytes(_)), Some(MemUsage::Percent(_))) => {
                     unreachable!("trying to add together two different memory usage types!")
                 }
+                (usage, None) | (None, usage) => usage.clone(),
+            };
+            self.gpu_usage = match (self.gpu_usage, other.gpu_usage) {
+                (Some(a), Some(b)) => Some(a + b),
+                (usage, None) | (None, usage) => usage,
+            };
+        }
+
+        if other.custom_values.len() > self.custom_values.len() {
+            self.custom_values.resize(other.custom_values.len(), None);
+        }
+        for (current, other) in self.custom_values.iter_mut().zip(&other.custom_values) {
+            *current = match (*current, *other) {
+                (Some(a), Some(b)) => Some(a + b),
+                (value, None) | (None, value) => value,
             };
-            self.gpu_usage += other.gpu_usage;
+        }
+    }
+
+    fn mem_usage_string(&self, mem_usage: &MemUsage) -> String {
+        match mem_usage {
+            MemUsage::Bytes(bytes) if self.compact_bytes => compact_bytes_string(*bytes),
+            _ => mem_usage.to_string(),
+        }
+    }
+
+    fn cpu_string(&self) -> String {
+        match &self.diff {
+            Some(diff) => format!("{:+.1}%", diff.cpu),
+            None => format!("{:.1}%", self.cpu_usage_percent),
+        }
+    }
+
+    fn mem_string(&self) -> String {
+        let Some(diff) = &self.diff else {
+            return self.mem_usage_string(&self.mem_usage);
+        };
+
+        match self.mem_usage {
+            MemUsage::Percent(_) => format!("{:+.1}%", diff.mem),
+            MemUsage::Bytes(_) => {
+                let sign = if diff.mem < 0.0 { "-" } else { "+" };
+                let bytes = self.mem_usage_string(&MemUsage::Bytes(diff.mem.abs() as u64));
+                concat_string!(sign, bytes)
+            }
+        }
+    }
+
+    /// Processes that aren't using a GPU are left blank, to tell them apart
+    /// from those that are using 0%.
+    #[cfg(feature = "gpu")]
+    fn gpu_mem_string(&self) -> String {
+        self.gpu_mem_usage
+            .as_ref()
+            .map(|usage| self.mem_usage_string(usage))
+            .unwrap_or_default()
+    }
+
+    #[cfg(feature = "gpu")]
+    fn gpu_usage_string(&self) -> String {
+        self.gpu_usage
+            .map(|usage| format!("{usage:.1}%"))
+            .unwrap_or_default()
+    }
+
+    fn bytes_per_second_string(&self, value: u64) -> String {
+        if self.compact_bytes {
+            compact_bytes_per_second_string(value)
+        } else {
+            dec_bytes_per_second_string(value)
+        }
+    }
+
+    fn bytes_string(&self, value: u64) -> String {
+        if self.compact_bytes {
+            compact_bytes_string(value)
+        } else {
+            dec_bytes_string(value)
+        }
+    }
+
+    /// Formats an I/O value with `format`, or returns "N/A" if the I/O values
+    /// are unknown.
+    fn io_string(&self, value: u64, format: fn(&Self, u64) -> String) -> String {
+        if self.io_unknown {
+            UNKNOWN_NAME.to_string()
+        } else {
+            format(self, value)
+        }
+    }
+
+    fn name_string(&self) -> String {
+        if self.show_num_similar {
+            format!("{} ({})", self.id.to_prefixed_string(), self.num_similar)
+        } else {
+            self.id.to_prefixed_string()
+        }
+    }
+
+    fn custom_string(&self, id: &CustomColumnId) -> String {
+        let Some(value) = self.custom_value(id.index) else {
+            return String::default();
+        };
+
+        match id.unit {
+            CustomColumnUnit::None => format!("{value:.2}"),
+            CustomColumnUnit::Bytes => self.bytes_string(value.max(0.0) as u64),
+            CustomColumnUnit::BytesPerSecond => self.bytes_per_second_string(value.max(0.0) as u64),
+            CustomColumnUnit::Percent => format!("{value:.1}%"),
         }
     }
 
     fn to_string(&self, column: &ProcColumn) -> String {
         match column {
-            ProcColumn::CpuPercent => format!("{:.1}%", self.cpu_usage_percent),
-            ProcColumn::MemValue | ProcColumn::MemPercent => self.mem_usage.to_string(),
+            ProcColumn::CpuPercent => self.cpu_string(),
+            ProcColumn::CpuHistory => cpu_sparkline(&self.cpu_history),
+            ProcColumn::LastSpike => format_spiked_ago(self.spiked_ago),
+            ProcColumn::MemValue | ProcColumn::MemPercent => self.mem_string(),
             ProcColumn::Pid => self.pid.to_string(),
             ProcColumn::Count => self.num_similar.to_string(),
-            ProcColumn::Name | ProcColumn::Command => self.id.to_prefixed_string(),
-            ProcColumn::ReadPerSecond => dec_bytes_per_second_string(self.rps),
-            ProcColumn::WritePerSecond => dec_bytes_per_second_string(self.wps),
-            ProcColumn::TotalRead => dec_bytes_string(self.total_read),
-            ProcColumn::TotalWrite => dec_bytes_string(self.total_write),
+            ProcColumn::Name | ProcColumn::Command => self.name_string(),
+            ProcColumn::ReadPerSecond => self.io_string(self.rps, Self::bytes_per_second_string),
+            ProcColumn::WritePerSecond => self.io_string(self.wps, Self::bytes_per_second_string),
+            ProcColumn::TotalRead => self.io_string(self.total_read, Self::bytes_string),
+            ProcColumn::TotalWrite => self.io_string(self.total_write, Self::bytes_string),
             ProcColumn::State => self.process_char.to_string(),
             ProcColumn::User => self.user.clone(),
+            ProcColumn::Group => self.group.clone(),
             ProcColumn::Time => format_time(self.time),
+            ProcColumn::ExeLocation => self.exe_location.to_string(),
             #[cfg(feature = "gpu")]
-            ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => self.gpu_mem_usage.to_string(),
+            ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => self.gpu_mem_string(),
             #[cfg(feature = "gpu")]
-            ProcColumn::GpuUtilPercent => format!("{:.1}%", self.gpu_usage),
+            ProcColumn::GpuUtilPercent => self.gpu_usage_string(),
+            ProcColumn::Custom(id) => self.custom_string(id),
         }
     }
 }
@@ -310,15 +694,21 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
         // TODO: Also maybe just pull in the to_string call but add a variable for the
         // differences.
         Some(match column {
-            ProcColumn::CpuPercent => format!("{:.1}%", self.cpu_usage_percent).into(),
-            ProcColumn::MemValue | ProcColumn::MemPercent => self.mem_usage.to_string().into(),
+            ProcColumn::CpuPercent => self.cpu_string().into(),
+            ProcColumn::CpuHistory => cpu_sparkline(&self.cpu_history).into(),
+            ProcColumn::LastSpike => format_spiked_ago(self.spiked_ago).into(),
+            ProcColumn::MemValue | ProcColumn::MemPercent => self.mem_string().into(),
             ProcColumn::Pid => self.pid.to_string().into(),
             ProcColumn::Count => self.num_similar.to_string().into(),
-            ProcColumn::Name | ProcColumn::Command => self.id.to_prefixed_string().into(),
-            ProcColumn::ReadPerSecond => dec_bytes_per_second_string(self.rps).into(),
-            ProcColumn::WritePerSecond => dec_bytes_per_second_string(self.wps).into(),
-            ProcColumn::TotalRead => dec_bytes_string(self.total_read).into(),
-            ProcColumn::TotalWrite => dec_bytes_string(self.total_write).into(),
+            ProcColumn::Name | ProcColumn::Command => self.name_string().into(),
+            ProcColumn::ReadPerSecond => self
+                .io_string(self.rps, Self::bytes_per_second_string)
+                .into(),
+            ProcColumn::WritePerSecond => self
+                .io_string(self.wps, Self::bytes_per_second_string)
+                .into(),
+            ProcColumn::TotalRead => self.io_string(self.total_read, Self::bytes_string).into(),
+            ProcColumn::TotalWrite => self.io_string(self.total_write, Self::bytes_string).into(),
             ProcColumn::State => {
                 if calculated_width < 8 {
                     self.process_char.to_string().into()
@@ -327,13 +717,14 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
                 }
             }
             ProcColumn::User => self.user.clone().into(),
+            ProcColumn::Group => self.group.clone().into(),
             ProcColumn::Time => format_time(self.time).into(),
+            ProcColumn::ExeLocation => self.exe_location.clone(),
             #[cfg(feature = "gpu")]
-            ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => {
-                self.gpu_mem_usage.to_string().into()
-            }
+            ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => self.gpu_mem_string().into(),
             #[cfg(feature = "gpu")]
-            ProcColumn::GpuUtilPercent => format!("{:.1}%", self.gpu_usage).into(),
+            ProcColumn::GpuUtilPercent => self.gpu_usage_string().into(),
+            ProcColumn::Custom(id) => self.custom_string(id).into(),
         })
     }
 
@@ -341,11 +732,29 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
     fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
         if self.disabled {
             row.style(painter.styles.disabled_text_style)
+        } else if matches!(
+            self.diff,
+            Some(ProcDiff {
+                change: ProcChange::New,
+                ..
+            })
+        ) {
+            row.style(painter.styles.added_text_style)
         } else {
             row
         }
     }
 
+    /// Grouped rows are kept selected by what they're grouped by, as the PID
+    /// they show is just one of theirs.
+    fn row_key(&self) -> Option<RowKey<'_>> {
+        Some(if self.num_similar > 1 {
+            RowKey::Group(Cow::Borrowed(self.id.as_str()))
+        } else {
+            RowKey::Pid(self.pid)
+        })
+    }
+
     fn column_widths<C: DataTableColumn<ProcColumn>>(data: &[Self], columns: &[C]) -> Vec<u16>
     where
         Self: Sized,
@@ -354,7 +763,7 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
 
         for d in data {
             for (w, c) in widths.iter_mut().zip(columns) {
-                *w = max(*w, d.to_string(c.inner()).len() as u16);
+                *w = max(*w, d.to_string(c.inner()).width() as u16);
             }
         }
 
@@ -364,9 +773,11 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
 
 #[cfg(test)]
 mod test {
-    use std::time::Duration;
+    use std::{path::Path, time::Duration};
 
-    use crate::widgets::process_data::format_time;
+    use crate::widgets::process_data::{
+        cpu_sparkline, exe_location, format_spiked_ago, format_time,
+    };
 
     #[test]
     fn test_format_time() {
@@ -398,4 +809,167 @@ mod test {
             "364d 23h 59m"
         );
     }
+
+    #[test]
+    fn spiked_ago_text() {
+        assert_eq!(format_spiked_ago(None), "—");
+        assert_eq!(format_spiked_ago(Some(Duration::from_secs(5))), "5s ago");
+        assert_eq!(format_spiked_ago(Some(Duration::from_secs(90))), "1m ago");
+        assert_eq!(format_spiked_ago(Some(Duration::from_secs(7200))), "2h ago");
+        assert_eq!(
+            format_spiked_ago(Some(Duration::from_secs(3 * 24 * 60 * 60))),
+            "3d ago"
+        );
+    }
+
+    #[test]
+    fn test_cpu_sparkline() {
+        assert_eq!(cpu_sparkline(&[]), "");
+        assert_eq!(cpu_sparkline(&[0.0, 50.0, 100.0]), "▁▅█");
+        assert_eq!(
+            cpu_sparkline(&[0.0, 14.3, 28.6, 42.9, 57.1, 71.4, 85.7, 100.0]),
+            "▁▂▃▄▅▆▇█"
+        );
+        assert_eq!(cpu_sparkline(&[-5.0, 100.0]), "▁█");
+
+        // Usage past 100% isn't cut off.
+        assert_eq!(cpu_sparkline(&[100.0, 200.0, 400.0]), "▃▅█");
+    }
+
+    #[test]
+    fn test_exe_location() {
+        let location = |path: &str| exe_location(Some(Path::new(path)));
+
+        assert_eq!(location("/usr/bin/bash"), "/usr/bin");
+        assert_eq!(location("/usr/sbin/sshd"), "/usr/sbin");
+        assert_eq!(location("/usr/lib/firefox/firefox"), "/usr/lib");
+        assert_eq!(location("/usr/local/bin/btm"), "/usr/local");
+        assert_eq!(location("/usr/foo"), "/usr");
+        assert_eq!(location("/bin/sh"), "/bin");
+        assert_eq!(
+            location("/snap/firefox/123/usr/lib/firefox/firefox"),
+            "/snap"
+        );
+        assert_eq!(location("/opt/google/chrome/chrome"), "/opt");
+        assert_eq!(location("/home/user/.cargo/bin/btm"), "/home");
+        assert_eq!(location("/nix/store/abc-bottom/bin/btm"), "/nix");
+        assert_eq!(location("/init"), "/");
+        assert_eq!(location("/memfd:runner (deleted)"), "memfd");
+        assert_eq!(location("relative/path"), "N/A");
+        assert_eq!(exe_location(None), "N/A");
+    }
+
+    #[cfg(feature = "gpu")]
+    #[test]
+    fn gpu_columns_without_gpu_usage() {
+        use std::num::NonZeroU16;
+
+        use hashbrown::HashMap;
+
+        use crate::{
+            canvas::components::data_table::{DataToCell, SortsRow},
+            data_collection::processes::ProcessHarvest,
+            widgets::{process_data::ProcWidgetData, ProcColumn},
+        };
+
+        // One process using a GPU at 0%, one using it more on two GPUs, and
+        // one that no GPU lists.
+        let gpu_pids = [
+            HashMap::from([(1, (0, 0)), (2, (1024, 30))]),
+            HashMap::from([(2, (1024, 20))]),
+        ];
+        let harvest = |pid| {
+            let mut process = ProcessHarvest {
+                pid,
+                ..Default::default()
+            };
+            process.set_gpu_usage(&gpu_pids, Some(4096));
+            process
+        };
+        let data = |pid| ProcWidgetData::from_data(&harvest(pid), false, true);
+
+        // Usage is summed across the GPUs listing the process.
+        let usage = |pid| {
+            let process = harvest(pid);
+            (process.gpu_mem, process.gpu_util, process.gpu_mem_percent)
+        };
+        assert_eq!(usage(1), (Some(0), Some(0), Some(0.0)));
+        assert_eq!(usage(2), (Some(2048), Some(50), Some(50.0)));
+        assert_eq!(usage(3), (None, None, None));
+        let cell = |data: &ProcWidgetData, column| {
+            data.to_cell(&column, NonZeroU16::new(10).unwrap())
+                .unwrap()
+                .to_string()
+        };
+
+        let mut rows = vec![data(1), data(2), data(3)];
+        assert_eq!(cell(&rows[0], ProcColumn::GpuUtilPercent), "0%");
+        assert_eq!(cell(&rows[0], ProcColumn::GpuMemPercent), "0.0%");
+        assert_eq!(cell(&rows[1], ProcColumn::GpuUtilPercent), "50%");
+        assert_eq!(cell(&rows[1], ProcColumn::GpuMemPercent), "50.0%");
+        assert_eq!(cell(&rows[2], ProcColumn::GpuUtilPercent), "");
+        assert_eq!(cell(&rows[2], ProcColumn::GpuMemPercent), "");
+
+        let pids = |rows: &[ProcWidgetData]| rows.iter().map(|row| row.pid).collect::<Vec<_>>();
+        ProcColumn::GpuUtilPercent.sort_data(&mut rows, true, false);
+        assert_eq!(pids(&rows), [2, 1, 3]);
+        ProcColumn::GpuMemPercent.sort_data(&mut rows, false, false);
+        assert_eq!(pids(&rows), [3, 1, 2]);
+
+        // Grouping only adds up what is there.
+        let mut grouped = data(3);
+        grouped.add(&data(3));
+        assert_eq!(grouped.gpu_usage, None);
+        grouped.add(&data(1));
+        assert_eq!(grouped.gpu_usage, Some(0));
+    }
+
+    #[test]
+    fn io_columns_when_io_is_unknown() {
+        use std::num::NonZeroU16;
+
+        use crate::{
+            canvas::components::data_table::DataToCell,
+            data_collection::processes::ProcessHarvest,
+            widgets::{process_data::ProcWidgetData, ProcColumn},
+        };
+
+        let data = |io_unknown| {
+            ProcWidgetData::from_data(
+                &ProcessHarvest {
+                    total_read_bytes: 2000,
+                    io_unknown,
+                    ..Default::default()
+                },
+                false,
+                true,
+            )
+        };
+        let cell = |data: &ProcWidgetData, column| {
+            data.to_cell(&column, NonZeroU16::new(10).unwrap())
+                .unwrap()
+                .to_string()
+        };
+
+        let known = data(false);
+        assert_eq!(cell(&known, ProcColumn::TotalRead), "2KB");
+        assert_eq!(cell(&known, ProcColumn::ReadPerSecond), "0B/s");
+
+        let unknown = data(true);
+        for column in [
+            ProcColumn::ReadPerSecond,
+            ProcColumn::WritePerSecond,
+            ProcColumn::TotalRead,
+            ProcColumn::TotalWrite,
+        ] {
+            assert_eq!(cell(&unknown, column), "N/A");
+        }
+
+        // Grouped processes show what is known.
+        let mut grouped = data(true);
+        grouped.add(&data(true));
+        assert!(grouped.io_unknown);
+        grouped.add(&known);
+        assert!(!grouped.io_unknown);
+    }
 }
@@ -60,20 +60,28 @@ const AND_LIST: [&str; 2] = ["and", "&&"];
 ///   Enclosing anything, including prefixes, in quotes, means we treat it as an
 ///   entire process rather than a prefix.
 /// - PIDs: Use prefix `pid`, can use regex or match word (case is irrelevant).
+///   Can compare with `>` and `<`, e.g. `pid > 1000`.
 /// - CPU: Use prefix `cpu`, cannot use r/m/c (regex, match word, case).  Can
 ///   compare.
 /// - MEM: Use prefix `mem`, cannot use r/m/c.  Can compare.
-/// - STATE: Use prefix `state`, can use regex, match word, or case.
-/// - USER: Use prefix `user`, can use regex, match word, or case.
+/// - STATE: Use prefix `state`, can use regex, match word, or case. Matches
+///   either the state's name or its single-letter code (e.g. `Z` for zombie).
+/// - USER: Use prefix `user`, can use regex, match word, or case. Processes
+///   without a known user never match.
 /// - Read/s: Use prefix `r`.  Can compare.
 /// - Write/s: Use prefix `w`.  Can compare.
 /// - Total read: Use prefix `read`.  Can compare.
 /// - Total write: Use prefix `write`.  Can compare.
 ///
-/// For queries, whitespaces are our delimiters.  We will merge together any
-/// adjacent non-prefixed or quoted elements after splitting to treat as process
-/// names. Furthermore, we want to support boolean joiners like AND and OR, and
-/// brackets.
+/// Prefixes can also be joined to their value with a colon, e.g.
+/// `state:zombie` or `mem:>500mb`. Values in bytes can have a unit, either
+/// attached or separate, and a unit on `mem` compares in bytes. A quoted value
+/// for `pid`, `state`, or `user`, as in `user:"alice"`, must match in full and
+/// isn't a regex.
+///
+/// For queries, whitespaces are our delimiters, except within quotes, which
+/// are treated as one process name. Furthermore, we want to support boolean
+/// joiners like AND and OR, where AND binds tighter, and brackets.
 pub(crate) fn parse_query(
     search_query: &str, is_searching_whole_word: bool, is_ignoring_case: bool,
     is_searching_with_regex: bool,
@@ -164,42 +172,111 @@ pub(crate) fn parse_query(
         Ok(And { lhs, rhs })
     }
 
-    #[inline]
-    fn process_prefix_units(query: &mut VecDeque<String>, value: &mut f64) {
-        // If no unit, assume base.
-        //
-        // Furthermore, base must be PEEKED at initially, and will
-        // require (likely) prefix_type specific checks
-        // Lastly, if it *is* a unit, remember to POP!
-        if let Some(potential_unit) = query.front() {
-            if potential_unit.eq_ignore_ascii_case("tb") {
-                *value *= TERA_LIMIT_F64;
-                query.pop_front();
-            } else if potential_unit.eq_ignore_ascii_case("tib") {
-                *value *= TEBI_LIMIT_F64;
-                query.pop_front();
-            } else if potential_unit.eq_ignore_ascii_case("gb") {
-                *value *= GIGA_LIMIT_F64;
-                query.pop_front();
-            } else if potential_unit.eq_ignore_ascii_case("gib") {
-                *value *= GIBI_LIMIT_F64;
-                query.pop_front();
-            } else if potential_unit.eq_ignore_ascii_case("mb") {
-                *value *= MEGA_LIMIT_F64;
-                query.pop_front();
-            } else if potential_unit.eq_ignore_ascii_case("mib") {
-                *value *= MEBI_LIMIT_F64;
-                query.pop_front();
-            } else if potential_unit.eq_ignore_ascii_case("kb") {
-                *value *= KILO_LIMIT_F64;
-                query.pop_front();
-            } else if potential_unit.eq_ignore_ascii_case("kib") {
-                *value *= KIBI_LIMIT_F64;
-                query.pop_front();
-            } else if potential_unit.eq_ignore_ascii_case("b") {
-                query.pop_front();
+    /// Returns how many bytes are in a unit, e.g. 1000 for `kb`.
+    fn unit_multiplier(unit: &str) -> Option<f64> {
+        let multiplier = match unit.to_ascii_lowercase().as_str() {
+            "tb" => TERA_LIMIT_F64,
+            "tib" => TEBI_LIMIT_F64,
+            "gb" => GIGA_LIMIT_F64,
+            "gib" => GIBI_LIMIT_F64,
+            "mb" => MEGA_LIMIT_F64,
+            "mib" => MEBI_LIMIT_F64,
+            "kb" => KILO_LIMIT_F64,
+            "kib" => KIBI_LIMIT_F64,
+            "b" => 1.0,
+            _ => return None,
+        };
+
+        Some(multiplier)
+    }
+
+    /// Reads the comparison starting with `content`, returning it and the
+    /// value being compared against. A value on its own means equality, e.g.
+    /// `cpu:5`.
+    fn process_comparison(
+        content: String, query: &mut VecDeque<String>,
+    ) -> QueryResult<(QueryComparison, String)> {
+        if content == "=" {
+            let value = query.pop_front().ok_or(QueryError::missing_value())?;
+            Ok((QueryComparison::Equal, value))
+        } else if content == ">" || content == "<" {
+            // We also have to check if the next string is an "="...
+            let queue_next = query.pop_front().ok_or(QueryError::missing_value())?;
+            if queue_next == "=" {
+                let condition = if content == ">" {
+                    QueryComparison::GreaterOrEqual
+                } else {
+                    QueryComparison::LessOrEqual
+                };
+                let value = query.pop_front().ok_or(QueryError::missing_value())?;
+                Ok((condition, value))
+            } else {
+                let condition = if content == ">" {
+                    QueryComparison::Greater
+                } else {
+                    QueryComparison::Less
+                };
+                Ok((condition, queue_next))
+            }
+        } else {
+            Ok((QueryComparison::Equal, content))
+        }
+    }
+
+    /// Parses a number for a numerical prefix, returning it along with the
+    /// prefix to compare it with. Prefixes measured in bytes take an optional
+    /// unit, either attached (`500mb`) or as the next string (`500 mb`), and a
+    /// unit turns `mem` into a comparison in bytes.
+    fn process_numerical_value(
+        prefix_type: PrefixType, value: &str, query: &mut VecDeque<String>,
+    ) -> QueryResult<(PrefixType, f64)> {
+        let prefix_type = match prefix_type {
+            PrefixType::PMem
+                if value.ends_with(|c: char| c.is_ascii_alphabetic())
+                    || query
+                        .front()
+                        .and_then(|unit| unit_multiplier(unit))
+                        .is_some() =>
+            {
+                PrefixType::MemBytes
+            }
+            prefix_type => prefix_type,
+        };
+        let takes_units = match prefix_type {
+            PrefixType::MemBytes
+            | PrefixType::Rps
+            | PrefixType::Wps
+            | PrefixType::TRead
+            | PrefixType::TWrite => true,
+            #[cfg(feature = "gpu")]
+            PrefixType::GMem => true,
+            _ => false,
+        };
+
+        if let Ok(mut number) = value.parse::<f64>() {
+            // If no unit, assume base.
+            if takes_units {
+                if let Some(multiplier) = query.front().and_then(|unit| unit_multiplier(unit)) {
+                    number *= multiplier;
+                    query.pop_front();
+                }
             }
+
+            return Ok((prefix_type, number));
         }
+
+        let unit_start = value
+            .find(|c: char| c.is_ascii_alphabetic())
+            .ok_or_else(|| QueryError::new(format!("Invalid number \"{value}\"")))?;
+        let (number, unit) = value.split_at(unit_start);
+        let number = number
+            .parse::<f64>()
+            .map_err(|_| QueryError::new(format!("Invalid number \"{value}\"")))?;
+        let multiplier = unit_multiplier(unit)
+            .filter(|_| takes_units)
+            .ok_or_else(|| QueryError::new(format!("Invalid unit \"{unit}\"")))?;
+
+        Ok((prefix_type, number * multiplier))
     }
 
     fn process_prefix(query: &mut VecDeque<String>, inside_quotation: bool) -> QueryResult<Prefix> {
@@ -323,7 +400,7 @@ pub(crate) fn parse_query(
                 };
 
                 if let Some(content) = content {
-                    match &prefix_type {
+                    match prefix_type {
                         PrefixType::Name => {
                             return Ok(Prefix {
                                 or: None,
@@ -331,7 +408,48 @@ pub(crate) fn parse_query(
                                 compare_prefix: None,
                             })
                         }
+                        PrefixType::Pid if content == ">" || content == "<" => {
+                            let (condition, value) = process_comparison(content, query)?;
+                            let (prefix_type, value) =
+                                process_numerical_value(prefix_type, &value, query)?;
+
+                            return Ok(Prefix {
+                                or: None,
+                                regex_prefix: None,
+                                compare_prefix: Some((
+                                    prefix_type,
+                                    ComparableQuery::Numerical(NumericalQuery { condition, value }),
+                                )),
+                            });
+                        }
                         PrefixType::Pid | PrefixType::State | PrefixType::User => {
+                            // A quoted value, with or without an "=", must match exactly.
+                            let content = if content == "="
+                                && query.front().is_some_and(|next| next == "\"")
+                            {
+                                query.pop_front().unwrap_or(content)
+                            } else {
+                                content
+                            };
+                            if content == "\"" {
+                                let mut quoted_string = String::new();
+                                while let Some(next_str) = query.pop_front() {
+                                    if next_str == "\"" {
+                                        return Ok(Prefix {
+                                            or: None,
+                                            regex_prefix: Some((
+                                                prefix_type,
+                                                StringQuery::Exact(quoted_string),
+                                            )),
+                                            compare_prefix: None,
+                                        });
+                                    }
+                                    quoted_string.push_str(&next_str);
+                                }
+
+                                return Err(QueryError::new("Missing closing quotation"));
+                            }
+
                             // We have to check if someone put an "="...
                             if content == "=" {
                                 // Check next string if possible
@@ -364,6 +482,22 @@ pub(crate) fn parse_query(
                                 });
                             }
                         }
+                        PrefixType::SpikedWithin => {
+                            let duration = parse_duration(&content)
+                                .map_err(|err| QueryError::new(err.to_string()))?;
+
+                            return Ok(Prefix {
+                                or: None,
+                                regex_prefix: None,
+                                compare_prefix: Some((
+                                    prefix_type,
+                                    ComparableQuery::Time(TimeQuery {
+                                        condition: QueryComparison::LessOrEqual,
+                                        duration,
+                                    }),
+                                )),
+                            });
+                        }
                         PrefixType::Time => {
                             let mut condition: Option<QueryComparison> = None;
                             let mut duration_string: Option<String> = None;
@@ -414,83 +548,18 @@ pub(crate) fn parse_query(
                         }
                         _ => {
                             // Assume it's some numerical value.
-                            // Now we gotta parse the content... yay.
-
-                            let mut condition: Option<QueryComparison> = None;
-                            let mut value: Option<f64> = None;
-
-                            // TODO: Jeez, what the heck did I write here... add some tests and
-                            // clean this up in the future.
-                            if content == "=" {
-                                condition = Some(QueryComparison::Equal);
-                                if let Some(queue_next) = query.pop_front() {
-                                    value = queue_next.parse::<f64>().ok();
-                                } else {
-                                    return Err(QueryError::missing_value());
-                                }
-                            } else if content == ">" || content == "<" {
-                                // We also have to check if the next string is an "="...
-                                if let Some(queue_next) = query.pop_front() {
-                                    if queue_next == "=" {
-                                        condition = Some(if content == ">" {
-                                            QueryComparison::GreaterOrEqual
-                                        } else {
-                                            QueryComparison::LessOrEqual
-                                        });
-                                        if let Some(queue_next_next) = query.pop_front() {
-                                            value = queue_next_next.parse::<f64>().ok();
-                                        } else {
-                                            return Err(QueryError::missing_value());
-                                        }
-                                    } else {
-                                        condition = Some(if content == ">" {
-                                            QueryComparison::Greater
-                                        } else {
-                                            QueryComparison::Less
-                                        });
-                                        value = queue_next.parse::<f64>().ok();
-                                    }
-                                } else {
-                                    return Err(QueryError::missing_value());
-                                }
-                            }
-
-                            if let Some(condition) = condition {
-                                if let Some(read_value) = value {
-                                    // Note that the values *might* have a unit or need to be parsed
-                                    // differently based on the
-                                    // prefix type!
-
-                                    let mut value = read_value;
-
-                                    match prefix_type {
-                                        PrefixType::MemBytes
-                                        | PrefixType::Rps
-                                        | PrefixType::Wps
-                                        | PrefixType::TRead
-                                        | PrefixType::TWrite => {
-                                            process_prefix_units(query, &mut value);
-                                        }
-                                        #[cfg(feature = "gpu")]
-                                        PrefixType::GMem => {
-                                            process_prefix_units(query, &mut value);
-                                        }
-                                        _ => {}
-                                    }
+                            let (condition, value) = process_comparison(content, query)?;
+                            let (prefix_type, value) =
+                                process_numerical_value(prefix_type, &value, query)?;
 
-                                    return Ok(Prefix {
-                                        or: None,
-                                        regex_prefix: None,
-                                        compare_prefix: Some((
-                                            prefix_type,
-                                            ComparableQuery::Numerical(NumericalQuery {
-                                                condition,
-                                                value,
-                                            }),
-                                        )),
-                                    });
-                                }
-                            }
+                            return Ok(Prefix {
+                                or: None,
+                                regex_prefix: None,
+                                compare_prefix: Some((
+                                    prefix_type,
+                                    ComparableQuery::Numerical(NumericalQuery { condition, value }),
+                                )),
+                            });
                         }
                     }
                 } else {
@@ -505,22 +574,63 @@ pub(crate) fn parse_query(
         Err(QueryError::new("Invalid query"))
     }
 
-    let mut split_query = VecDeque::new();
+    // Pushes a string that isn't quoted, splitting a `prefix:value` string
+    // into the prefix and the value. A prefix with a colon on its own, like
+    // `pid:`, is only split if a comparison or a quoted value follows it, as in
+    // `pid:>1000` or `user:"alice"`.
+    fn push_unquoted(split_query: &mut VecDeque<String>, token: &str, next: Option<char>) {
+        if let Some((prefix, value)) = token.split_once(':') {
+            let is_prefix = !matches!(prefix.parse::<PrefixType>(), Ok(PrefixType::Name));
+            if is_prefix && !value.is_empty() {
+                split_query.push_back(prefix.to_owned());
+                split_query.push_back(value.to_owned());
+                return;
+            } else if is_prefix && matches!(next, Some('=' | '>' | '<' | '"')) {
+                split_query.push_back(prefix.to_owned());
+                return;
+            }
+        }
 
-    search_query.split_whitespace().for_each(|s| {
-        // From https://stackoverflow.com/a/56923739 in order to get a split, but include the parentheses
-        let mut last = 0;
-        for (index, matched) in s.match_indices(|x| DELIMITER_LIST.contains(&x)) {
-            if last != index {
-                split_query.push_back(s[last..index].to_owned());
+        split_query.push_back(token.to_owned());
+    }
+
+    // Whitespace separates strings, and delimiters are strings of their own.
+    // Anything in quotes, including whitespace, is kept as one string.
+    let mut split_query = VecDeque::new();
+    let mut token = String::new();
+    let mut in_quotes = false;
+
+    for c in search_query.chars() {
+        if c == '"' {
+            if !token.is_empty() {
+                if in_quotes {
+                    split_query.push_back(std::mem::take(&mut token));
+                } else {
+                    push_unquoted(&mut split_query, &std::mem::take(&mut token), Some(c));
+                }
             }
-            split_query.push_back(matched.to_owned());
-            last = index + matched.len();
+            split_query.push_back(c.to_string());
+            in_quotes = !in_quotes;
+        } else if in_quotes {
+            token.push(c);
+        } else if c.is_whitespace() || DELIMITER_LIST.contains(&c) {
+            if !token.is_empty() {
+                push_unquoted(&mut split_query, &std::mem::take(&mut token), Some(c));
+            }
+            if !c.is_whitespace() {
+                split_query.push_back(c.to_string());
+            }
+        } else {
+            token.push(c);
         }
-        if last < s.len() {
-            split_query.push_back(s[last..].to_owned());
+    }
+    if !token.is_empty() {
+        if in_quotes {
+            split_query.push_back(token);
+        } else {
+            push_unquoted(&mut split_query, &token, None);
         }
-    });
+    }
 
     let mut process_filter = process_string_to_filter(&mut split_query)?;
     process_filter.process_regexes(
@@ -553,10 +663,12 @@ impl ProcessQuery {
         Ok(())
     }
 
-    pub(crate) fn check(&self, process: &ProcessHarvest, is_using_command: bool) -> bool {
+    pub(crate) fn check(
+        &self, process: &ProcessHarvest, is_using_command: bool, spiked_ago: Option<Duration>,
+    ) -> bool {
         self.query
             .iter()
-            .all(|ok| ok.check(process, is_using_command))
+            .all(|ok| ok.check(process, is_using_command, spiked_ago))
     }
 }
 
@@ -593,11 +705,14 @@ impl Or {
         Ok(())
     }
 
-    fn check(&self, process: &ProcessHarvest, is_using_command: bool) -> bool {
+    fn check(
+        &self, process: &ProcessHarvest, is_using_command: bool, spiked_ago: Option<Duration>,
+    ) -> bool {
         if let Some(rhs) = &self.rhs {
-            self.lhs.check(process, is_using_command) || rhs.check(process, is_using_command)
+            self.lhs.check(process, is_using_command, spiked_ago)
+                || rhs.check(process, is_using_command, spiked_ago)
         } else {
-            self.lhs.check(process, is_using_command)
+            self.lhs.check(process, is_using_command, spiked_ago)
         }
     }
 }
@@ -638,11 +753,14 @@ impl And {
         Ok(())
     }
 
-    fn check(&self, process: &ProcessHarvest, is_using_command: bool) -> bool {
+    fn check(
+        &self, process: &ProcessHarvest, is_using_command: bool, spiked_ago: Option<Duration>,
+    ) -> bool {
         if let Some(rhs) = &self.rhs {
-            self.lhs.check(process, is_using_command) && rhs.check(process, is_using_command)
+            self.lhs.check(process, is_using_command, spiked_ago)
+                && rhs.check(process, is_using_command, spiked_ago)
         } else {
-            self.lhs.check(process, is_using_command)
+            self.lhs.check(process, is_using_command, spiked_ago)
         }
     }
 }
@@ -670,6 +788,7 @@ enum PrefixType {
     State,
     User,
     Time,
+    SpikedWithin,
     #[cfg(feature = "gpu")]
     PGpu,
     #[cfg(feature = "gpu")]
@@ -711,6 +830,8 @@ impl std::str::FromStr for PrefixType {
             result = User;
         } else if multi_eq_ignore_ascii_case!(s, "time") {
             result = Time;
+        } else if multi_eq_ignore_ascii_case!(s, "spiked_within") {
+            result = SpikedWithin;
         }
         #[cfg(feature = "gpu")]
         {
@@ -746,6 +867,23 @@ impl Prefix {
                 is_ignoring_case,
                 is_searching_with_regex,
             );
+        } else if let Some((
+            PrefixType::Pid | PrefixType::State | PrefixType::User,
+            StringQuery::Exact(value),
+        )) = &mut self.regex_prefix
+        {
+            let final_regex_string = format!(
+                "^{}{}$",
+                if is_ignoring_case { "(?i)" } else { "" },
+                regex::escape(value)
+            );
+
+            if let Some((taken_pt, _)) = self.regex_prefix.take() {
+                self.regex_prefix = Some((
+                    taken_pt,
+                    StringQuery::Regex(Regex::new(&final_regex_string)?),
+                ));
+            }
         } else if let Some((
             PrefixType::Pid | PrefixType::Name | PrefixType::State | PrefixType::User,
             StringQuery::Value(regex_string),
@@ -777,7 +915,9 @@ impl Prefix {
         Ok(())
     }
 
-    fn check(&self, process: &ProcessHarvest, is_using_command: bool) -> bool {
+    fn check(
+        &self, process: &ProcessHarvest, is_using_command: bool, spiked_ago: Option<Duration>,
+    ) -> bool {
         fn matches_condition<I: Into<f64>, J: Into<f64>>(
             condition: &QueryComparison, lhs: I, rhs: J,
         ) -> bool {
@@ -804,7 +944,7 @@ impl Prefix {
         }
 
         if let Some(and) = &self.or {
-            and.check(process, is_using_command)
+            and.check(process, is_using_command, spiked_ago)
         } else if let Some((prefix_type, query_content)) = &self.regex_prefix {
             if let StringQuery::Regex(r) = query_content {
                 match prefix_type {
@@ -814,8 +954,12 @@ impl Prefix {
                         process.name.as_str()
                     }),
                     PrefixType::Pid => r.is_match(process.pid.to_string().as_str()),
-                    PrefixType::State => r.is_match(process.process_state.0.as_str()),
-                    PrefixType::User => r.is_match(process.user.as_ref()),
+                    PrefixType::State => {
+                        let (name, code) = &process.process_state;
+                        r.is_match(name) || r.is_match(code.encode_utf8(&mut [0; 4]))
+                    }
+                    // Without user info, there is no user to match.
+                    PrefixType::User => process.known_user().is_some_and(|user| r.is_match(user)),
                     _ => true,
                 }
             } else {
@@ -824,6 +968,11 @@ impl Prefix {
         } else if let Some((prefix_type, comparable_query)) = &self.compare_prefix {
             match comparable_query {
                 ComparableQuery::Numerical(numerical_query) => match prefix_type {
+                    PrefixType::Pid => matches_condition(
+                        &numerical_query.condition,
+                        process.pid as f64,
+                        numerical_query.value,
+                    ),
                     PrefixType::PCpu => matches_condition(
                         &numerical_query.condition,
                         process.cpu_usage_percent,
@@ -862,19 +1011,19 @@ impl Prefix {
                     #[cfg(feature = "gpu")]
                     PrefixType::PGpu => matches_condition(
                         &numerical_query.condition,
-                        process.gpu_util,
+                        process.gpu_util.unwrap_or(0),
                         numerical_query.value,
                     ),
                     #[cfg(feature = "gpu")]
                     PrefixType::GMem => matches_condition(
                         &numerical_query.condition,
-                        process.gpu_mem as f64,
+                        process.gpu_mem.unwrap_or(0) as f64,
                         numerical_query.value,
                     ),
                     #[cfg(feature = "gpu")]
                     PrefixType::PGMem => matches_condition(
                         &numerical_query.condition,
-                        process.gpu_mem_percent,
+                        process.gpu_mem_percent.unwrap_or(0.0),
                         numerical_query.value,
                     ),
                     _ => true,
@@ -883,6 +1032,9 @@ impl Prefix {
                     PrefixType::Time => {
                         matches_duration(&time_query.condition, process.time, time_query.duration)
                     }
+                    PrefixType::SpikedWithin => spiked_ago.is_some_and(|spiked_ago| {
+                        matches_duration(&time_query.condition, spiked_ago, time_query.duration)
+                    }),
                     _ => true,
                 },
             }
@@ -919,6 +1071,8 @@ enum QueryComparison {
 #[derive(Debug)]
 enum StringQuery {
     Value(String),
+    /// A quoted value, which must match in full and isn't a regex.
+    Exact(String),
     Regex(Regex),
 }
 
@@ -939,3 +1093,297 @@ struct TimeQuery {
     condition: QueryComparison,
     duration: Duration,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::data_collection::processes::{Pid, UNKNOWN_NAME};
+
+    fn process(pid: Pid, name: &str, state: (&str, char)) -> ProcessHarvest {
+        ProcessHarvest {
+            pid,
+            name: name.to_string(),
+            process_state: (state.0.to_string(), state.1),
+            ..Default::default()
+        }
+    }
+
+    fn matching(query: &str, is_searching_whole_word: bool) -> Vec<Pid> {
+        let processes = [
+            process(1, "systemd", ("Sleeping", 'S')),
+            process(2, "defunct", ("Zombie", 'Z')),
+            process(3, "cargo", ("Running", 'R')),
+            process(4, "state:zombie", ("Running", 'R')),
+        ];
+        let query = parse_query(query, is_searching_whole_word, true, false).unwrap();
+
+        processes
+            .iter()
+            .filter(|process| query.check(process, false, None))
+            .map(|process| process.pid)
+            .collect()
+    }
+
+    #[test]
+    fn state_prefix() {
+        assert_eq!(matching("state:zombie", false), vec![2]);
+        assert_eq!(matching("state zombie", false), vec![2]);
+        assert_eq!(matching("state=zombie", false), vec![2]);
+        assert_eq!(matching("STATE:Zombie", false), vec![2]);
+
+        // The single-letter codes match too.
+        assert_eq!(matching("state:r", true), vec![3, 4]);
+        assert_eq!(matching("state:z or state:s", true), vec![1, 2]);
+        assert_eq!(matching("(state:running) cargo", false), vec![3]);
+
+        // Quoting treats it as a name instead.
+        assert_eq!(matching("\"state:zombie\"", false), vec![4]);
+    }
+
+    #[test]
+    fn quoted_values_match_exactly() {
+        assert_eq!(matching("state:run", false), vec![3, 4]);
+        assert!(matching("state:\"run\"", false).is_empty());
+        assert_eq!(matching("state:\"running\"", false), vec![3, 4]);
+        assert_eq!(matching("state = \"Zombie\"", false), vec![2]);
+        assert_eq!(matching("pid:\"1\"", false), vec![1]);
+
+        // Regex characters are matched literally.
+        assert!(matching("state:\"r.*\"", false).is_empty());
+
+        assert!(parse_query("user:\"alice", false, true, false).is_err());
+    }
+
+    fn matching_users(query: &str, is_ignoring_case: bool, is_using_regex: bool) -> Vec<Pid> {
+        let processes = [
+            ("systemd", "root"),
+            ("bash", "alice"),
+            ("sshd", "root"),
+            ("kthreadd", UNKNOWN_NAME),
+            ("root", ""),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(index, (name, user))| ProcessHarvest {
+            pid: index as Pid + 1,
+            name: name.to_string(),
+            user: user.to_string().into(),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+        let query = parse_query(query, false, is_ignoring_case, is_using_regex).unwrap();
+
+        processes
+            .iter()
+            .filter(|process| query.check(process, false, None))
+            .map(|process| process.pid)
+            .collect()
+    }
+
+    #[test]
+    fn user_prefix() {
+        assert_eq!(matching_users("user:root", true, false), vec![1, 3]);
+        assert_eq!(matching_users("user root", true, false), vec![1, 3]);
+
+        // Combined with a name.
+        assert_eq!(matching_users("user:root and sshd", true, false), vec![3]);
+        assert_eq!(
+            matching_users("sshd and user:alice", true, false),
+            Vec::<Pid>::new()
+        );
+        assert_eq!(
+            matching_users("user:alice or sshd", true, false),
+            vec![2, 3]
+        );
+        assert_eq!(
+            matching_users("(user:root or user:alice) and s", true, false),
+            vec![1, 2, 3]
+        );
+
+        // Case sensitivity and regexes apply as for names.
+        assert_eq!(matching_users("user:ROOT", true, false), vec![1, 3]);
+        assert_eq!(matching_users("user:ROOT", false, false), Vec::<Pid>::new());
+        assert_eq!(matching_users("user:^ro+t$", true, true), vec![1, 3]);
+
+        // Processes without a known user never match, even by what is shown
+        // in their place.
+        assert_eq!(matching_users("user:.*", true, true), vec![1, 2, 3]);
+        assert_eq!(matching_users("user:N/A", true, false), Vec::<Pid>::new());
+    }
+
+    #[test]
+    fn colon_splits_prefixes() {
+        assert_eq!(matching("cargo:", false), Vec::<Pid>::new());
+        assert_eq!(matching("pid:2", true), vec![2]);
+        assert_eq!(matching("pid:>2", true), vec![3, 4]);
+        assert_eq!(matching("pid:>=2 pid:<=3", true), vec![2, 3]);
+
+        // Not a prefix, so this is just a name.
+        assert_eq!(matching("e:z", false), vec![4]);
+        assert!(parse_query("state:", false, true, false).is_ok());
+        assert!(parse_query("cpu:5", false, true, false).is_ok());
+    }
+
+    #[test]
+    fn spiked_within_prefix() {
+        let minutes = |minutes: u64| Some(Duration::from_secs(minutes * 60));
+        let processes = [
+            ("idle", None),
+            ("recent", minutes(1)),
+            ("earlier", minutes(5)),
+            ("old", minutes(60)),
+        ];
+        let matching = |query: &str| {
+            let query = parse_query(query, false, true, false).unwrap();
+            processes
+                .iter()
+                .filter(|(name, spiked_ago)| {
+                    let process = ProcessHarvest {
+                        name: name.to_string(),
+                        ..Default::default()
+                    };
+                    query.check(&process, false, *spiked_ago)
+                })
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matching("spiked_within:5m"), ["recent", "earlier"]);
+        assert_eq!(matching("spiked_within 2m"), ["recent"]);
+        assert_eq!(matching("SPIKED_WITHIN:1h"), ["recent", "earlier", "old"]);
+        assert_eq!(matching("spiked_within:1h and e"), ["recent", "earlier"]);
+        assert!(parse_query("spiked_within:soon", false, true, false).is_err());
+    }
+
+    fn matching_usage(query: &str) -> QueryResult<Vec<Pid>> {
+        let processes = [
+            ("postgres", 500 * 1000 * 1000, 10.0),
+            ("nginx", 200 * 1024, 1.0),
+            ("redis server", 2 * 1024 * 1024 * 1024, 50.0),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(
+            |(index, (name, mem_usage_bytes, cpu_usage_percent))| ProcessHarvest {
+                pid: index as Pid + 1000,
+                name: name.to_string(),
+                mem_usage_bytes,
+                cpu_usage_percent,
+                ..Default::default()
+            },
+        )
+        .collect::<Vec<_>>();
+        let query = parse_query(query, false, true, false)?;
+
+        Ok(processes
+            .iter()
+            .filter(|process| query.check(process, false, None))
+            .map(|process| process.pid)
+            .collect())
+    }
+
+    #[test]
+    fn precedence() {
+        // AND binds tighter than OR.
+        assert_eq!(
+            matching_usage("postgres or nginx and cpu > 5").unwrap(),
+            vec![1000]
+        );
+        assert_eq!(
+            matching_usage("nginx and cpu > 5 or postgres").unwrap(),
+            vec![1000]
+        );
+        assert_eq!(
+            matching_usage("(postgres or nginx) and cpu < 5").unwrap(),
+            vec![1001]
+        );
+    }
+
+    #[test]
+    fn quoted_strings_keep_spaces() {
+        assert_eq!(matching_usage("\"redis server\"").unwrap(), vec![1002]);
+        assert_eq!(
+            matching_usage("\"redis   server\"").unwrap(),
+            Vec::<Pid>::new()
+        );
+        assert_eq!(matching_usage("\"s ser\"").unwrap(), vec![1002]);
+    }
+
+    #[test]
+    fn units() {
+        assert_eq!(matching_usage("mem:>500mb").unwrap(), vec![1002]);
+        assert_eq!(matching_usage("mem:>=500mb").unwrap(), vec![1000, 1002]);
+        assert_eq!(matching_usage("memb < 200kib").unwrap(), Vec::<Pid>::new());
+        assert_eq!(matching_usage("memb <= 200 KiB").unwrap(), vec![1001]);
+        assert_eq!(matching_usage("memb > 1.5gib").unwrap(), vec![1002]);
+        assert_eq!(matching_usage("memb > 205kb").unwrap(), vec![1000, 1002]);
+
+        // Without a unit, `mem` is still a percentage.
+        assert_eq!(matching_usage("mem > 500").unwrap(), Vec::<Pid>::new());
+    }
+
+    #[test]
+    fn pid_ranges() {
+        assert_eq!(matching_usage("pid:>1000").unwrap(), vec![1001, 1002]);
+        assert_eq!(
+            matching_usage("pid > 1000 and pid < 1002").unwrap(),
+            vec![1001]
+        );
+        assert_eq!(
+            matching_usage("pid:<1001 or cpu:>20").unwrap(),
+            vec![1000, 1002]
+        );
+        assert_eq!(matching_usage("pid:>999 ngin").unwrap(), vec![1001]);
+    }
+
+    #[test]
+    fn invalid_queries() {
+        for query in [
+            "pid:>",
+            "cpu > lots",
+            "cpu > 5mb",
+            "memb > 5zb",
+            "mem:>",
+            "(postgres",
+            "\"postgres",
+        ] {
+            assert!(matching_usage(query).is_err(), "{query} should be invalid");
+        }
+    }
+
+    #[cfg(feature = "gpu")]
+    #[test]
+    fn gpu_prefixes() {
+        use hashbrown::HashMap;
+
+        // One process using a GPU at 0%, one using it more, and one that no
+        // GPU lists.
+        let gpu_pids = [HashMap::from([(1000, (0, 0)), (1001, (1024, 30))])];
+        let processes = (1000..=1002)
+            .map(|pid| {
+                let mut process = ProcessHarvest {
+                    pid,
+                    ..Default::default()
+                };
+                process.set_gpu_usage(&gpu_pids, Some(4096));
+                process
+            })
+            .collect::<Vec<_>>();
+        let matching = |query: &str| {
+            let query = parse_query(query, false, true, false).unwrap();
+            processes
+                .iter()
+                .filter(|process| query.check(process, false, None))
+                .map(|process| process.pid)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matching("gpu% > 0"), vec![1001]);
+        assert_eq!(matching("gmem% >= 25"), vec![1001]);
+        assert_eq!(matching("gmem > 512b"), vec![1001]);
+
+        // Processes without any GPU usage are kept, and match as 0.
+        assert_eq!(matching("gpu% = 0"), vec![1000, 1002]);
+        assert_eq!(matching("gmem <= 0"), vec![1000, 1002]);
+    }
+}
@@ -1,28 +1,95 @@
-use std::{borrow::Cow, cmp::max, num::NonZeroU16};
+use std::{borrow::Cow, cmp::Ordering, num::NonZeroU16};
 
 use concat_string::concat_string;
+use itertools::Itertools;
+use serde::Deserialize;
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
     app::AppConfigFields,
     canvas::components::data_table::{
-        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
-        SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+        ColumnHeader, ColumnSeparator, DataTableColumn, DataTableProps, DataTableStyling,
+        DataToCell, RowKey, SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
     },
     data_collection::temperature::TemperatureType,
     options::config::style::Styles,
-    utils::general::sort_partial_fn,
+    utils::general::{sort_partial_fn, sort_str_fn},
 };
 
 #[derive(Clone, Debug)]
 pub struct TempWidgetData {
     pub sensor: Cow<'static, str>,
+    /// How many sensors before this one have the same name.
+    pub sensor_index: usize,
     pub temperature_value: Option<u64>,
+    /// The highest reading this session.
+    pub max_value: Option<u64>,
+    /// The lowest reading this session.
+    pub min_value: Option<u64>,
+    /// How the current reading compares to the previous one, if both are known.
+    pub trend: Option<Ordering>,
     pub temperature_type: TemperatureType,
 }
 
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(
+    feature = "generate_schema",
+    derive(schemars::JsonSchema, strum::VariantArray)
+)]
+#[derive(PartialEq, Eq)]
 pub enum TempWidgetColumn {
     Sensor,
     Temp,
+    Max,
+    Min,
+    Trend,
+}
+
+impl<'de> Deserialize<'de> for TempWidgetColumn {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?.to_lowercase();
+        TempWidgetColumn::NAMES
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, column)| *column)
+            .ok_or_else(|| {
+                serde::de::Error::custom(format!(
+                    "'{value}' doesn't match any temperature column name; valid names are {}",
+                    TempWidgetColumn::NAMES
+                        .iter()
+                        .map(|(name, _)| name)
+                        .join(", ")
+                ))
+            })
+    }
+}
+
+impl TempWidgetColumn {
+    /// The names that can be used for columns in the config file, ignoring
+    /// case, and the columns they refer to.
+    const NAMES: [(&'static str, TempWidgetColumn); 6] = [
+        ("sensor", TempWidgetColumn::Sensor),
+        ("temp", TempWidgetColumn::Temp),
+        ("temperature", TempWidgetColumn::Temp),
+        ("max", TempWidgetColumn::Max),
+        ("min", TempWidgetColumn::Min),
+        ("trend", TempWidgetColumn::Trend),
+    ];
+
+    /// An ugly hack to generate the JSON schema.
+    #[cfg(feature = "generate_schema")]
+    pub fn get_schema_names(&self) -> &[&'static str] {
+        match self {
+            TempWidgetColumn::Sensor => &["Sensor"],
+            TempWidgetColumn::Temp => &["Temp", "Temperature"],
+            TempWidgetColumn::Max => &["Max"],
+            TempWidgetColumn::Min => &["Min"],
+            TempWidgetColumn::Trend => &["Trend"],
+        }
+    }
 }
 
 impl ColumnHeader for TempWidgetColumn {
@@ -30,13 +97,20 @@ impl ColumnHeader for TempWidgetColumn {
         match self {
             TempWidgetColumn::Sensor => "Sensor(s)".into(),
             TempWidgetColumn::Temp => "Temp(t)".into(),
+            TempWidgetColumn::Max => "Max".into(),
+            TempWidgetColumn::Min => "Min".into(),
+            TempWidgetColumn::Trend => "Trend".into(),
         }
     }
 }
 
+/// The columns shown if none are configured.
+pub const DEFAULT_TEMP_COLUMNS: [TempWidgetColumn; 2] =
+    [TempWidgetColumn::Sensor, TempWidgetColumn::Temp];
+
 impl TempWidgetData {
-    pub fn temperature(&self) -> Cow<'static, str> {
-        match self.temperature_value {
+    fn format_temperature(&self, value: Option<u64>) -> Cow<'static, str> {
+        match value {
             Some(temp_val) => {
                 let temp_type = match self.temperature_type {
                     TemperatureType::Celsius => "°C",
@@ -48,6 +122,22 @@ impl TempWidgetData {
             None => "N/A".to_string().into(),
         }
     }
+
+    pub fn temperature(&self) -> Cow<'static, str> {
+        self.format_temperature(self.temperature_value)
+    }
+
+    /// An arrow for whether the temperature went up or down since the previous
+    /// reading, or a line if it stayed the same. This is blank if there is no
+    /// current or previous reading.
+    fn trend_symbol(&self) -> &'static str {
+        match self.trend {
+            Some(Ordering::Greater) => "▲",
+            Some(Ordering::Less) => "▼",
+            Some(Ordering::Equal) => "─",
+            None => "",
+        }
+    }
 }
 
 impl DataToCell<TempWidgetColumn> for TempWidgetData {
@@ -57,21 +147,36 @@ impl DataToCell<TempWidgetColumn> for TempWidgetData {
         Some(match column {
             TempWidgetColumn::Sensor => self.sensor.clone(),
             TempWidgetColumn::Temp => self.temperature(),
+            TempWidgetColumn::Max => self.format_temperature(self.max_value),
+            TempWidgetColumn::Min => self.format_temperature(self.min_value),
+            TempWidgetColumn::Trend => self.trend_symbol().into(),
         })
     }
 
+    fn row_key(&self) -> Option<RowKey<'_>> {
+        Some(RowKey::Name(Cow::Borrowed(&self.sensor), self.sensor_index))
+    }
+
     fn column_widths<C: DataTableColumn<TempWidgetColumn>>(
-        data: &[TempWidgetData], _columns: &[C],
+        data: &[TempWidgetData], columns: &[C],
     ) -> Vec<u16>
     where
         Self: Sized,
     {
-        let mut widths = vec![0; 2];
+        let mut widths = vec![0; columns.len()];
 
-        data.iter().for_each(|row| {
-            widths[0] = max(widths[0], row.sensor.len() as u16);
-            widths[1] = max(widths[1], row.temperature().len() as u16);
-        });
+        for row in data {
+            for (width, column) in widths.iter_mut().zip(columns) {
+                let len = match column.inner() {
+                    TempWidgetColumn::Sensor => row.sensor.width(),
+                    TempWidgetColumn::Temp => row.temperature().len(),
+                    TempWidgetColumn::Max => row.format_temperature(row.max_value).len(),
+                    TempWidgetColumn::Min => row.format_temperature(row.min_value).len(),
+                    TempWidgetColumn::Trend => continue,
+                };
+                *width = (*width).max(len as u16);
+            }
+        }
 
         widths
     }
@@ -80,16 +185,25 @@ impl DataToCell<TempWidgetColumn> for TempWidgetData {
 impl SortsRow for TempWidgetColumn {
     type DataType = TempWidgetData;
 
-    fn sort_data(&self, data: &mut [Self::DataType], descending: bool) {
+    fn sort_data(&self, data: &mut [Self::DataType], descending: bool, case_sensitive: bool) {
         match self {
             TempWidgetColumn::Sensor => {
-                data.sort_by(move |a, b| sort_partial_fn(descending)(&a.sensor, &b.sensor));
+                data.sort_by(|a, b| sort_str_fn(&a.sensor, &b.sensor, descending, case_sensitive));
             }
             TempWidgetColumn::Temp => {
                 data.sort_by(|a, b| {
                     sort_partial_fn(descending)(a.temperature_value, b.temperature_value)
                 });
             }
+            TempWidgetColumn::Max => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.max_value, b.max_value));
+            }
+            TempWidgetColumn::Min => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.min_value, b.min_value));
+            }
+            TempWidgetColumn::Trend => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.trend, b.trend));
+            }
         }
     }
 }
@@ -100,11 +214,20 @@ pub struct TempWidgetState {
 }
 
 impl TempWidgetState {
-    pub(crate) fn new(config: &AppConfigFields, palette: &Styles) -> Self {
-        let columns = [
-            SortColumn::soft(TempWidgetColumn::Sensor, Some(0.8)),
-            SortColumn::soft(TempWidgetColumn::Temp, None).default_descending(),
-        ];
+    pub(crate) fn new(
+        config: &AppConfigFields, palette: &Styles, columns: Option<&[TempWidgetColumn]>,
+    ) -> Self {
+        let columns = columns
+            .unwrap_or(&DEFAULT_TEMP_COLUMNS)
+            .iter()
+            .map(|&column| match column {
+                TempWidgetColumn::Sensor => SortColumn::soft(column, Some(0.8)),
+                TempWidgetColumn::Temp | TempWidgetColumn::Max | TempWidgetColumn::Min => {
+                    SortColumn::soft(column, None).default_descending()
+                }
+                TempWidgetColumn::Trend => SortColumn::hard(column, 5).default_descending(),
+            })
+            .collect::<Vec<_>>();
 
         let props = SortDataTableProps {
             inner: DataTableProps {
@@ -114,9 +237,12 @@ impl TempWidgetState {
                 is_basic: config.use_basic_mode,
                 show_table_scroll_position: config.show_table_scroll_position,
                 show_current_entry_when_unfocused: false,
+                column_separator: ColumnSeparator::default(),
+                show_scrollbar: false,
             },
             sort_index: 0,
             order: SortOrder::Ascending,
+            case_sensitive: config.temp_case_sensitive_sort,
         };
 
         let styling = DataTableStyling::from_palette(palette);
@@ -133,11 +259,28 @@ impl TempWidgetState {
         self.force_update_data = true;
     }
 
+    /// Sorts by the given column, if it is shown.
+    pub fn select_column(&mut self, column: TempWidgetColumn) {
+        if let Some(index) = self
+            .table
+            .columns
+            .iter()
+            .position(|col| *col.inner() == column)
+        {
+            self.table.set_sort_index(index);
+            self.force_data_update();
+        }
+    }
+
     /// Update the current table data.
     pub fn set_table_data(&mut self, data: &[TempWidgetData]) {
         let mut data = data.to_vec();
         if let Some(column) = self.table.columns.get(self.table.sort_index()) {
-            column.sort_by(&mut data, self.table.order());
+            column.sort_by(
+                &mut data,
+                self.table.order(),
+                self.table.is_case_sensitive(),
+            );
         }
         self.table.set_data(data);
     }
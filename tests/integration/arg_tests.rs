@@ -178,3 +178,14 @@ fn test_help() {
     btm_command(&["--help"]).assert().success();
     btm_command(&["-h"]).assert().success();
 }
+
+/// The listing flags can be used together, and each lists what it finds.
+#[test]
+#[cfg(target_os = "linux")]
+fn test_combined_listing_flags() {
+    no_cfg_btm_command()
+        .args(["--list_sensors", "--list_interfaces"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match("(?m)^lo$").unwrap());
+}
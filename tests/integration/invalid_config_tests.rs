@@ -132,6 +132,17 @@ fn test_invalid_process_column() {
         .stderr(predicate::str::contains("doesn't match"));
 }
 
+#[test]
+fn test_invalid_custom_process_column() {
+    btm_command(&[
+        "-C",
+        "./tests/invalid_configs/invalid_custom_process_column.toml",
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("unknown field 'rss'"));
+}
+
 #[test]
 fn test_invalid_disk_column() {
     btm_command(&["-C", "./tests/invalid_configs/invalid_disk_column.toml"])